@@ -5,9 +5,6 @@ use arith::{Field, FieldParameters, MontyField31, MontyParameters, PackedMontyPa
 /// The prime field `2^31 - 2^27 + 1`, a.k.a. the Baby Bear field.
 pub type BabyBear = MontyField31<BabyBearParameters>;
 
-/// The modulus
-pub const BABY_BEAR_MOD: u32 = 0x78000001;
-
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq)]
 pub struct BabyBearParameters;
 
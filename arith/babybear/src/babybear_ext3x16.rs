@@ -166,12 +166,18 @@ impl From<BabyBearExt3> for BabyBearExt3x16 {
 }
 
 impl Field for BabyBearExt3x16 {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
     const NAME: &'static str = "AVX Vectorized Baby Bear Extension 3";
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
     const NAME: &'static str = "Neon Vectorized Baby Bear Extension 3";
 
+    #[cfg(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    ))]
+    const NAME: &'static str = "Portable Vectorized Baby Bear Extension 3";
+
     const SIZE: usize = 512 / 8 * 3;
 
     const FIELD_SIZE: usize = 32 * 3;
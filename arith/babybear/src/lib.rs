@@ -1,13 +1,7 @@
 // Most of the code is from https://github.com/Plonky3/Plonky3
 //
-// Ideally we want to simply wrap plonky3/baby-bear::AVXBabyBear
-// But plonky3 has a feature flag on avx256/avx512 where expander
-// decides whether to use avx256 or avx512 at compile time.
-// This is not compatible with our compile time flag for avx256/avx512
-// in the core crate.
-// So we re-implement the BabyBearx16 field in our own crate.
-
-#![cfg_attr(target_arch = "x86_64", feature(stdarch_x86_avx512))]
+// The packed BabyBearx16 field uses the Monty31 SIMD backends of the arith crate,
+// which pick avx256/avx512 with the same compile time flag as the core crate.
 
 mod babybear;
 pub use babybear::{BabyBear, BabyBearParameters};
@@ -1,6 +1,7 @@
 use arith::{
-    random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_inversion_tests, random_simd_field_tests, Field, FieldParameters,
+    random_binomial_extension_tests, random_extension_field_tests, random_fft_field_tests,
+    random_field_tests, random_inversion_tests, random_simd_field_tests, BinomialExtension,
+    BinomialExtensionParameters, Field, FieldParameters,
 };
 use ark_std::test_rng;
 use serdes::ExpSerde;
@@ -62,6 +63,26 @@ fn test_ext_field() {
     // random_simd_field_tests::<BabyBearExt4x16>("Simd Baby Bear Ext4".to_string());
 }
 
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+struct BabyBearExt3Parameters;
+
+impl BinomialExtensionParameters<BabyBear, 3> for BabyBearExt3Parameters {
+    const NAME: &'static str = "Baby Bear Extension 3";
+
+    const PACKED_NAME: &'static str = "Vectorized Baby Bear Extension 3";
+
+    const W: u32 = 2;
+}
+
+#[test]
+fn test_binomial_extension() {
+    // the generic extension modulo x^3 - 2 agrees with the hand-written one
+    random_binomial_extension_tests::<
+        BabyBearExt3,
+        BinomialExtension<BabyBear, BabyBearExt3Parameters, 3>,
+    >("Binomial Baby Bear Ext3".to_string());
+}
+
 #[test]
 fn baby_bear_two_inverse() {
     let two = BabyBear::new(2);
@@ -4,16 +4,28 @@ use arith::{Field, SimdField};
 
 use crate::{GF2x64, GF2};
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
 mod avx;
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
 pub type GF2x128 = avx::AVXGF2x128;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub type GF2x128 = neon::NeonGF2x128;
 
+// Without SIMD instructions, with the `portable` feature or on other architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type GF2x128 = portable::PortableGF2x128;
+
 impl SimdField for GF2x128 {
     type Scalar = GF2;
 
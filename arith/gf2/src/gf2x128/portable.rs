@@ -0,0 +1,247 @@
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::Field;
+use ethnum::U256;
+use serdes::ExpSerde;
+
+use crate::GF2;
+
+/// PortableGF2x128 packs 128 GF2 elements into two u64, without any SIMD instructions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Hash, ExpSerde)]
+pub struct PortableGF2x128 {
+    pub(crate) v: [u64; 2],
+}
+
+impl Field for PortableGF2x128 {
+    const NAME: &'static str = "Portable Galois Field 2 SIMD 128";
+
+    const SIZE: usize = 128 / 8;
+
+    const FIELD_SIZE: usize = 1; // in bits
+
+    const ZERO: Self = PortableGF2x128 { v: [0; 2] };
+
+    const ONE: Self = PortableGF2x128 { v: [!0u64; 2] };
+
+    const INV_2: Self = PortableGF2x128 { v: [0; 2] };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == [0; 2]
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2x128 {
+            v: [rng.next_u64(), rng.next_u64()],
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2x128 {
+            v: [rng.next_u64(), rng.next_u64()],
+        }
+    }
+
+    #[inline(always)]
+    fn exp(&self, exponent: u128) -> Self {
+        if exponent == 0 {
+            return Self::one();
+        }
+        *self
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GFx128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        PortableGF2x128 {
+            v: [
+                u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+                u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            ],
+        }
+    }
+}
+
+impl Mul<&PortableGF2x128> for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: &PortableGF2x128) -> PortableGF2x128 {
+        PortableGF2x128 {
+            v: [self.v[0] & rhs.v[0], self.v[1] & rhs.v[1]],
+        }
+    }
+}
+
+impl Mul<PortableGF2x128> for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    fn mul(self, rhs: PortableGF2x128) -> PortableGF2x128 {
+        self * &rhs
+    }
+}
+
+impl MulAssign<&PortableGF2x128> for PortableGF2x128 {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: &PortableGF2x128) {
+        *self = *self * rhs;
+    }
+}
+
+impl MulAssign<PortableGF2x128> for PortableGF2x128 {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: PortableGF2x128) {
+        *self = *self * rhs;
+    }
+}
+
+impl Add<&PortableGF2x128> for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: &PortableGF2x128) -> PortableGF2x128 {
+        PortableGF2x128 {
+            v: [self.v[0] ^ rhs.v[0], self.v[1] ^ rhs.v[1]],
+        }
+    }
+}
+
+impl Add for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    fn add(self, rhs: PortableGF2x128) -> PortableGF2x128 {
+        self + &rhs
+    }
+}
+
+impl AddAssign<&PortableGF2x128> for PortableGF2x128 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: &PortableGF2x128) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign for PortableGF2x128 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: PortableGF2x128) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<&PortableGF2x128> for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: &PortableGF2x128) -> PortableGF2x128 {
+        self + rhs
+    }
+}
+
+impl Sub for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: PortableGF2x128) -> PortableGF2x128 {
+        self + rhs
+    }
+}
+
+impl SubAssign<&PortableGF2x128> for PortableGF2x128 {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: &PortableGF2x128) {
+        *self = *self - rhs;
+    }
+}
+
+impl SubAssign for PortableGF2x128 {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: PortableGF2x128) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: std::borrow::Borrow<PortableGF2x128>> std::iter::Sum<T> for PortableGF2x128 {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, item| acc + item.borrow())
+    }
+}
+
+impl<T: std::borrow::Borrow<PortableGF2x128>> std::iter::Product<T> for PortableGF2x128 {
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, item| acc * item.borrow())
+    }
+}
+
+impl Neg for PortableGF2x128 {
+    type Output = PortableGF2x128;
+
+    #[inline(always)]
+    fn neg(self) -> PortableGF2x128 {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2x128 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        assert!(v < 2);
+        if v == 0 {
+            PortableGF2x128::ZERO
+        } else {
+            PortableGF2x128::ONE
+        }
+    }
+}
+
+impl From<u64> for PortableGF2x128 {
+    #[inline(always)]
+    fn from(v: u64) -> Self {
+        assert!(v < 2);
+        if v == 0 {
+            PortableGF2x128::ZERO
+        } else {
+            PortableGF2x128::ONE
+        }
+    }
+}
+
+impl From<GF2> for PortableGF2x128 {
+    #[inline(always)]
+    fn from(v: GF2) -> Self {
+        assert!(v.v < 2);
+        if v.v == 0 {
+            PortableGF2x128::ZERO
+        } else {
+            PortableGF2x128::ONE
+        }
+    }
+}
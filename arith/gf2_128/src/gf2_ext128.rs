@@ -5,16 +5,29 @@ use gf2::GF2x8;
 
 use crate::GF2_128x8;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub type GF2_128 = neon::NeonGF2_128;
 
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
 mod avx;
-#[cfg(target_arch = "x86_64")]
+#[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
 pub type GF2_128 = avx::AVXGF2_128;
 
+// Without carry-less multiplication instructions, with the `portable` feature or on other
+// architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub(crate) mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type GF2_128 = portable::PortableGF2_128;
+
 impl Ord for GF2_128 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
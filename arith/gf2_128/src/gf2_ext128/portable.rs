@@ -0,0 +1,235 @@
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field};
+use ethnum::U256;
+use gf2::GF2;
+use serdes::ExpSerde;
+
+/// PortableGF2_128 stores the coefficient of x^i in the i-th bit of a u128,
+/// and multiplies without carry-less multiplication instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ExpSerde)]
+pub struct PortableGF2_128 {
+    pub(crate) v: u128,
+}
+
+field_common!(PortableGF2_128);
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    PortableGF2_128 { v: a.v ^ b.v }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    PortableGF2_128 { v: gfmul(a.v, b.v) }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2_128, b: &PortableGF2_128) -> PortableGF2_128 {
+    add_internal(a, b)
+}
+
+impl Field for PortableGF2_128 {
+    const NAME: &'static str = "Portable Galois Field 2^128";
+
+    const SIZE: usize = 128 / 8;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = PortableGF2_128 { v: 0 };
+
+    const ONE: Self = PortableGF2_128 { v: 1 };
+
+    const INV_2: Self = PortableGF2_128 { v: 0 };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        let mut u = [0u8; 16];
+        rng.fill_bytes(&mut u);
+        PortableGF2_128 {
+            v: u128::from_le_bytes(u),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128 {
+            v: (rng.next_u32() & 1) as u128,
+        }
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v == 0
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let p_m2 = u128::MAX - 1;
+        Some(Self::exp(self, p_m2))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        PortableGF2_128 {
+            v: u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+        }
+    }
+}
+
+impl ExtensionField for PortableGF2_128 {
+    const DEGREE: usize = 128;
+
+    const W: u32 = 0x87;
+
+    const X: Self = PortableGF2_128 { v: 2 };
+
+    type BaseField = GF2;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        if base.is_zero() {
+            Self::zero()
+        } else {
+            *self
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128 {
+            v: self.v ^ base.v as u128,
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        PortableGF2_128 {
+            v: mul_by_x_internal(self.v),
+        }
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        PortableGF2_128 {
+            v: limbs
+                .iter()
+                .take(Self::DEGREE)
+                .enumerate()
+                .fold(0, |acc, (i, limb)| acc | ((limb.v as u128) << i)),
+        }
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        (0..Self::DEGREE)
+            .map(|i| GF2 {
+                v: ((self.v >> i) & 1) as u8,
+            })
+            .collect()
+    }
+}
+
+impl Mul<GF2> for PortableGF2_128 {
+    type Output = PortableGF2_128;
+
+    #[inline]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl From<GF2> for PortableGF2_128 {
+    #[inline(always)]
+    fn from(v: GF2) -> Self {
+        match v.v {
+            0 => Self::zero(),
+            1 => Self::one(),
+            _ => panic!("Invalid value for GF2"),
+        }
+    }
+}
+
+impl Neg for PortableGF2_128 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2_128 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        PortableGF2_128 { v: v as u128 }
+    }
+}
+
+impl From<u64> for PortableGF2_128 {
+    #[inline(always)]
+    fn from(v: u64) -> Self {
+        PortableGF2_128 { v: v as u128 }
+    }
+}
+
+/// Carry-less product of two u64, bit by bit
+#[inline(always)]
+fn clmul64(a: u64, b: u64) -> u128 {
+    (0..64).fold(0u128, |acc, i| {
+        let mask = 0u128.wrapping_sub(((b >> i) & 1) as u128);
+        acc ^ (((a as u128) << i) & mask)
+    })
+}
+
+/// Multiplication modulo x^128 + x^7 + x^2 + x + 1
+#[inline]
+pub(crate) fn gfmul(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64, (a >> 64) as u64);
+    let (b0, b1) = (b as u64, (b >> 64) as u64);
+
+    // Karatsuba over the 64-bit halves
+    let lo = clmul64(a0, b0);
+    let hi = clmul64(a1, b1);
+    let mid = clmul64(a0 ^ a1, b0 ^ b1) ^ lo ^ hi;
+
+    // the product is hi * x^128 + low
+    let low = lo ^ (mid << 64);
+    let hi = hi ^ (mid >> 64);
+
+    // x^128 = x^7 + x^2 + x + 1, the bits shifted out of hi are reduced once more
+    let overflow = (hi >> 127) ^ (hi >> 126) ^ (hi >> 121);
+    let hi = hi ^ overflow;
+    low ^ hi ^ (hi << 1) ^ (hi << 2) ^ (hi << 7)
+}
+
+#[inline(always)]
+pub(crate) fn mul_by_x_internal(a: u128) -> u128 {
+    (a << 1) ^ ((a >> 127) * 0x87)
+}
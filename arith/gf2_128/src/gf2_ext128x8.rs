@@ -5,22 +5,50 @@ use gf2::GF2x8;
 
 use crate::GF2_128;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub(crate) mod neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub type GF2_128x8 = neon::NeonGF2_128x8;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 mod avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 pub type GF2_128x8 = avx512::AVX512GF2_128x8;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 mod avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 pub type GF2_128x8 = avx256::AVX256GF2_128x8;
 
+// Without SIMD instructions, with the `portable` feature or on other architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type GF2_128x8 = portable::PortableGF2_128x8;
+
 impl Ord for GF2_128x8 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
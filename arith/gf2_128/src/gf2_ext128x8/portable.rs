@@ -0,0 +1,286 @@
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use arith::{field_common, ExtensionField, Field, SimdField};
+use ethnum::U256;
+use gf2::{GF2x8, GF2};
+use serdes::ExpSerde;
+
+use crate::gf2_ext128::portable::{gfmul, mul_by_x_internal, PortableGF2_128};
+use crate::GF2_128;
+
+/// PortableGF2_128x8 packs 8 GF2_128 elements, lane by lane without SIMD instructions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ExpSerde)]
+pub struct PortableGF2_128x8 {
+    v: [u128; 8],
+}
+
+field_common!(PortableGF2_128x8);
+
+impl Field for PortableGF2_128x8 {
+    const NAME: &'static str = "Portable Galois Field 2^128 SIMD 8";
+
+    const SIZE: usize = 16 * 8;
+
+    const FIELD_SIZE: usize = 128; // in bits
+
+    const ZERO: Self = PortableGF2_128x8 { v: [0; 8] };
+
+    const ONE: Self = PortableGF2_128x8 { v: [1; 8] };
+
+    const INV_2: Self = PortableGF2_128x8 { v: [0; 8] };
+
+    const MODULUS: U256 = unimplemented!(); // should not be used
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| *x == 0)
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128x8 {
+            v: [(); 8].map(|_| PortableGF2_128::random_unsafe(&mut rng).v),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        PortableGF2_128x8 {
+            v: [(); 8].map(|_| (rng.next_u32() % 2) as u128),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+        let p_m2 = !(0u128) - 1;
+        Some(Self::exp(self, p_m2))
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("u32 for GF128 doesn't make sense")
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(_bytes: &[u8]) -> Self {
+        unimplemented!("from_uniform_bytes for GF128 doesn't make sense")
+    }
+}
+
+impl SimdField for PortableGF2_128x8 {
+    type Scalar = PortableGF2_128;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        PortableGF2_128x8 {
+            v: self.v.map(|x| gfmul(x, challenge.v)),
+        }
+    }
+
+    const PACK_SIZE: usize = 8;
+
+    #[inline]
+    fn pack_full(base: &Self::Scalar) -> Self {
+        Self { v: [base.v; 8] }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == 8);
+        Self {
+            v: std::array::from_fn(|i| base_vec[i].v),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.iter().map(|&v| PortableGF2_128 { v }).collect()
+    }
+}
+
+impl From<PortableGF2_128> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: PortableGF2_128) -> Self {
+        PortableGF2_128x8 { v: [v.v; 8] }
+    }
+}
+
+impl Neg for PortableGF2_128x8 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        self
+    }
+}
+
+impl From<u32> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: u32) -> Self {
+        PortableGF2_128x8 { v: [v as u128; 8] }
+    }
+}
+
+impl From<u64> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: u64) -> Self {
+        PortableGF2_128x8 { v: [v as u128; 8] }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    PortableGF2_128x8 {
+        v: std::array::from_fn(|i| a.v[i] ^ b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    add_internal(a, b)
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableGF2_128x8, b: &PortableGF2_128x8) -> PortableGF2_128x8 {
+    PortableGF2_128x8 {
+        v: std::array::from_fn(|i| gfmul(a.v[i], b.v[i])),
+    }
+}
+
+/// The i-th bit of a GF2x8, i.e., the GF2 of the i-th lane
+#[inline(always)]
+fn gf2x8_lane(v: &GF2x8, i: usize) -> u128 {
+    ((v.v >> i) & 1) as u128
+}
+
+impl ExtensionField for PortableGF2_128x8 {
+    const DEGREE: usize = PortableGF2_128::DEGREE;
+
+    const W: u32 = PortableGF2_128::W;
+
+    const X: Self = PortableGF2_128x8 { v: [2; 8] };
+
+    type BaseField = GF2x8;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i] * gf2x8_lane(base, i)),
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| self.v[i] ^ gf2x8_lane(base, i)),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        PortableGF2_128x8 {
+            v: self.v.map(mul_by_x_internal),
+        }
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        let mut local_limbs = limbs.to_vec();
+        local_limbs.resize(Self::DEGREE, Self::BaseField::ZERO);
+
+        let mut buffer = vec![GF2::ZERO; Self::DEGREE * Self::PACK_SIZE];
+
+        local_limbs.iter().enumerate().for_each(|(ith_limb, limb)| {
+            let unpacked = limb.unpack();
+            unpacked.iter().enumerate().for_each(|(ith_gf2, gf2_val)| {
+                buffer[ith_gf2 * Self::DEGREE + ith_limb] = *gf2_val;
+            });
+        });
+
+        let gf2_128s: Vec<_> = buffer
+            .chunks(Self::DEGREE)
+            .map(GF2_128::from_limbs)
+            .collect();
+
+        Self::pack(&gf2_128s)
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        let gf2_128s = self.unpack();
+
+        let mut buffer = vec![GF2::ZERO; Self::DEGREE * Self::PACK_SIZE];
+        gf2_128s
+            .iter()
+            .enumerate()
+            .for_each(|(ith_gf2_128, gf2_128_val)| {
+                let limbs = gf2_128_val.to_limbs();
+                limbs.iter().enumerate().for_each(|(ith_limb, limb)| {
+                    buffer[ith_limb * Self::PACK_SIZE + ith_gf2_128] = *limb;
+                })
+            });
+
+        buffer.chunks(Self::PACK_SIZE).map(GF2x8::pack).collect()
+    }
+}
+
+impl From<GF2x8> for PortableGF2_128x8 {
+    #[inline(always)]
+    fn from(v: GF2x8) -> Self {
+        PortableGF2_128x8 {
+            v: std::array::from_fn(|i| gf2x8_lane(&v, i)),
+        }
+    }
+}
+
+impl Mul<GF2x8> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline]
+    fn mul(self, rhs: GF2x8) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl Mul<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn mul(self, rhs: GF2) -> Self::Output {
+        if rhs.is_zero() {
+            Self::zero()
+        } else {
+            self
+        }
+    }
+}
+
+impl Add<GF2> for PortableGF2_128x8 {
+    type Output = PortableGF2_128x8;
+
+    #[inline(always)]
+    fn add(self, rhs: GF2) -> Self::Output {
+        PortableGF2_128x8 {
+            v: self.v.map(|x| x ^ rhs.v as u128),
+        }
+    }
+}
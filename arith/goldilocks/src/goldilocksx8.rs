@@ -1,24 +1,52 @@
 // A Goldilocksx8 stores 512 bits of data.
 // With AVX it stores a single __m512i element.
 // With NEON it stores 8 u64 elements.
-// NEON doesn't use and arm instructions and can be used as a fallback for other architectures.
+// Without SIMD instructions it stores 8 Goldilocks elements.
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 mod goldilocks_neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub type Goldilocksx8 = goldilocks_neon::NeonGoldilocks;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 mod goldilocks_avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 pub type Goldilocksx8 = goldilocks_avx512::AVXGoldilocks;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 mod goldilocks_avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 pub type Goldilocksx8 = goldilocks_avx256::AVXGoldilocks;
 
+// Without SIMD instructions, with the `portable` feature or on other architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+mod goldilocks_portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type Goldilocksx8 = goldilocks_portable::PortableGoldilocks;
+
 impl Ord for Goldilocksx8 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
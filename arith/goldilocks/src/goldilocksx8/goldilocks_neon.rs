@@ -209,7 +209,7 @@ impl Add<Goldilocks> for NeonGoldilocks {
     type Output = NeonGoldilocks;
     #[inline(always)]
     fn add(self, rhs: Goldilocks) -> Self::Output {
-        let res = self.v.map(|x| x * rhs);
+        let res = self.v.map(|x| x + rhs);
         Self { v: res }
     }
 }
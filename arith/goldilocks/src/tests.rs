@@ -1,7 +1,7 @@
 use arith::{
-    random_extension_field_tests, random_fft_field_tests, random_field_tests,
-    random_from_limbs_to_limbs_tests, random_inversion_tests, random_simd_field_tests,
-    ExtensionField, Field,
+    random_binomial_extension_tests, random_extension_field_tests, random_fft_field_tests,
+    random_field_tests, random_from_limbs_to_limbs_tests, random_inversion_tests,
+    random_simd_field_tests, BinomialExtension, BinomialExtensionParameters, ExtensionField, Field,
 };
 use ark_std::test_rng;
 use ethnum::U256;
@@ -14,7 +14,12 @@ use crate::{
 };
 
 fn get_avx_version() -> &'static str {
-    if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+    if cfg!(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    )) {
+        return "Portable";
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
         return "AVX512";
     } else if cfg!(all(
         target_arch = "x86_64",
@@ -33,6 +38,7 @@ fn test_avx_version() {
     let avx_version = get_avx_version();
     println!("Current AVX version: {}", avx_version);
     assert!([
+        "Portable",
         "arm64",
         "AVX512",
         "AVX2 (256-bit)",
@@ -89,6 +95,26 @@ fn test_ext_field() {
     );
 }
 
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+struct GoldilocksExt2Parameters;
+
+impl BinomialExtensionParameters<Goldilocks, 2> for GoldilocksExt2Parameters {
+    const NAME: &'static str = "Goldilocks Extension 2";
+
+    const PACKED_NAME: &'static str = "Vectorized Goldilocks Extension 2";
+
+    const W: u32 = 7;
+}
+
+#[test]
+fn test_binomial_extension() {
+    // the generic extension modulo x^2 - 7 agrees with the hand-written one
+    random_binomial_extension_tests::<
+        GoldilocksExt2,
+        BinomialExtension<Goldilocks, GoldilocksExt2Parameters, 2>,
+    >("Binomial Goldilocks Ext2".to_string());
+}
+
 /// Compare to test vectors for extension field arithmetic
 #[test]
 fn test_ext_field_vectors() {
@@ -0,0 +1,18 @@
+use arith::{bench_ext_field, bench_field};
+use criterion::{criterion_group, criterion_main, Criterion};
+use koalabear::{KoalaBear, KoalaBearExt4, KoalaBearExt4x16, KoalaBearx16};
+
+fn ext_by_base_benchmark(c: &mut Criterion) {
+    bench_ext_field::<KoalaBearExt4>(c);
+    bench_ext_field::<KoalaBearExt4x16>(c);
+}
+
+fn field_benchmark(c: &mut Criterion) {
+    bench_field::<KoalaBear>(c);
+    bench_field::<KoalaBearx16>(c);
+    bench_field::<KoalaBearExt4>(c);
+    bench_field::<KoalaBearExt4x16>(c);
+}
+
+criterion_group!(bench, ext_by_base_benchmark, field_benchmark);
+criterion_main!(bench);
@@ -0,0 +1,44 @@
+use arith::{BinomialExtension, BinomialExtensionParameters, Field};
+
+use crate::koalabear::KoalaBear;
+
+/// The degree 4 extension of KoalaBear, modulo x^4 - 3.
+/// As p = 2 mod 3, every element of KoalaBear is a cube and there is no binomial
+/// extension of degree 3.
+pub type KoalaBearExt4 = BinomialExtension<KoalaBear, KoalaBearExt4Parameters, 4>;
+
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct KoalaBearExt4Parameters;
+
+impl BinomialExtensionParameters<KoalaBear, 4> for KoalaBearExt4Parameters {
+    const NAME: &'static str = "Koala Bear Extension 4";
+
+    #[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
+    const PACKED_NAME: &'static str = "AVX Vectorized Koala Bear Extension 4";
+
+    #[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
+    const PACKED_NAME: &'static str = "Neon Vectorized Koala Bear Extension 4";
+
+    #[cfg(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    ))]
+    const PACKED_NAME: &'static str = "Portable Vectorized Koala Bear Extension 4";
+
+    const W: u32 = 3;
+
+    #[inline(always)]
+    fn mul_by_w<G: Field>(x: &G) -> G {
+        x.mul_by_3()
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> [KoalaBear; 4] {
+        assert!(bytes.len() >= 16);
+        std::array::from_fn(|i| {
+            KoalaBear::new(u32::from_be_bytes(
+                bytes[i * 4..(i + 1) * 4].try_into().unwrap(),
+            ))
+        })
+    }
+}
@@ -0,0 +1,6 @@
+use arith::PackedBinomialExtension;
+
+use crate::{KoalaBearExt4Parameters, KoalaBearx16};
+
+/// 16 elements of `KoalaBearExt4`, over the packed base field `KoalaBearx16`
+pub type KoalaBearExt4x16 = PackedBinomialExtension<KoalaBearx16, KoalaBearExt4Parameters, 4>;
@@ -0,0 +1,10 @@
+// A KoalaBearx16 stores 512 bits of data.
+// With AVX it stores a single __m512i element.
+// With NEON it stores four uint32x4_t elements.
+// Without SIMD instructions it stores sixteen KoalaBear elements.
+//
+// The backends are shared with the other Monty31 fields, see `arith::PackedMontyField31`.
+
+use crate::KoalaBearParameters;
+
+pub type KoalaBearx16 = arith::PackedMontyField31<KoalaBearParameters>;
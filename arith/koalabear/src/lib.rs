@@ -0,0 +1,19 @@
+// Most of the code is from https://github.com/Plonky3/Plonky3
+//
+// The packed KoalaBearx16 field uses the Monty31 SIMD backends of the arith crate,
+// which pick avx256/avx512 with the same compile time flag as the core crate.
+
+mod koalabear;
+pub use koalabear::{KoalaBear, KoalaBearParameters};
+
+mod koalabearx16;
+pub use koalabearx16::KoalaBearx16;
+
+mod koalabear_ext;
+pub use koalabear_ext::{KoalaBearExt4, KoalaBearExt4Parameters};
+
+mod koalabear_ext4x16;
+pub use koalabear_ext4x16::KoalaBearExt4x16;
+
+#[cfg(test)]
+mod tests;
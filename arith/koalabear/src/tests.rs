@@ -0,0 +1,200 @@
+use arith::{
+    random_extension_field_tests, random_fft_field_tests, random_field_tests,
+    random_inversion_tests, random_simd_field_tests, BinomialExtension,
+    BinomialExtensionParameters, ExtensionField, Field, FieldParameters, PackedBinomialExtension,
+    SimdField,
+};
+use ark_std::test_rng;
+use serdes::ExpSerde;
+
+use crate::{
+    koalabear::KoalaBearParameters, KoalaBear, KoalaBearExt4, KoalaBearExt4x16, KoalaBearx16,
+};
+
+// CMD: RUSTFLAGS="-C target-feature=+avx512f" cargo test --package arith --lib --
+// tests::koala_bear::test_field --exact --show-output
+#[test]
+fn test_base_field() {
+    random_field_tests::<KoalaBear>("KoalaBear".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<KoalaBear, _>(&mut rng, "KoalaBear".to_string());
+
+    random_fft_field_tests::<KoalaBear>("Koala Bear".to_string());
+}
+
+#[test]
+fn test_simd_field() {
+    random_field_tests::<KoalaBearx16>("KoalaBearx16".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<KoalaBearx16, _>(&mut rng, "KoalaBearx16".to_string());
+
+    random_simd_field_tests::<KoalaBearx16>("KoalaBearx16".to_string());
+
+    random_fft_field_tests::<KoalaBearx16>("KoalaBearx16".to_string());
+
+    let a = KoalaBearx16::from(256u32 + 2);
+    let mut buffer = vec![];
+    assert!(a.serialize_into(&mut buffer).is_ok());
+    let b = KoalaBearx16::deserialize_from(buffer.as_slice());
+    assert!(b.is_ok());
+    let b = b.unwrap();
+    assert_eq!(a, b);
+}
+
+// CMD: RUSTFLAGS="-C target-feature=+avx512f" cargo test --package arith --lib --
+// tests::koala_bear_ext::test_field --exact --show-output
+#[test]
+fn test_ext_field() {
+    // Deg 4
+    random_field_tests::<KoalaBearExt4>("Koala Bear Ext4".to_string());
+    random_extension_field_tests::<KoalaBearExt4>("Koala Bear Ext4".to_string());
+
+    random_field_tests::<KoalaBearExt4x16>("Simd Koala Bear Ext4".to_string());
+    random_extension_field_tests::<KoalaBearExt4x16>("Simd Koala Bear Ext4".to_string());
+    random_simd_field_tests::<KoalaBearExt4x16>("Simd Koala Bear Ext4".to_string());
+
+    random_fft_field_tests::<KoalaBearExt4>("Koala Bear Ext4".to_string());
+    random_fft_field_tests::<KoalaBearExt4x16>("SIMD Koala Bear Ext4".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<KoalaBearExt4, _>(&mut rng, "Koala Bear Ext4".to_string());
+    random_inversion_tests::<KoalaBearExt4x16, _>(&mut rng, "Simd Koala Bear Ext4".to_string());
+}
+
+// polynomial mod (x^4 - 3), without Karatsuba
+fn schoolbook_mul(a: &[KoalaBear; 4], b: &[KoalaBear; 4]) -> [KoalaBear; 4] {
+    let w = KoalaBear::new(3);
+    [
+        a[0] * b[0] + w * (a[1] * b[3] + a[2] * b[2] + a[3] * b[1]),
+        a[0] * b[1] + a[1] * b[0] + w * (a[2] * b[3] + a[3] * b[2]),
+        a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + w * a[3] * b[3],
+        a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0],
+    ]
+}
+
+#[test]
+fn test_ext_field_binomial() {
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let a = KoalaBearExt4::random_unsafe(&mut rng);
+        let b = KoalaBearExt4::random_unsafe(&mut rng);
+        assert_eq!((a * b).v, schoolbook_mul(&a.v, &b.v));
+        assert_eq!(a * a.inv().unwrap(), KoalaBearExt4::ONE);
+
+        // serialized as the limbs, from the lowest degree
+        let mut buffer = vec![];
+        a.serialize_into(&mut buffer).unwrap();
+        let mut limbs = vec![];
+        a.v.iter()
+            .for_each(|x| x.serialize_into(&mut limbs).unwrap());
+        assert_eq!(buffer, limbs);
+        assert_eq!(buffer.len(), KoalaBearExt4::SIZE);
+        assert_eq!(
+            KoalaBearExt4::deserialize_from(buffer.as_slice()).unwrap(),
+            a
+        );
+
+        let packed: Vec<KoalaBearExt4> = (0..KoalaBearExt4x16::PACK_SIZE)
+            .map(|_| KoalaBearExt4::random_unsafe(&mut rng))
+            .collect();
+        let base = KoalaBearx16::random_unsafe(&mut rng);
+        let product = KoalaBearExt4x16::pack(&packed) * b + base;
+        product
+            .unpack()
+            .iter()
+            .zip(packed.iter().zip(base.unpack()))
+            .for_each(|(p, (a, s))| assert_eq!(*p, *a * b + s));
+        assert_eq!(
+            b * base,
+            KoalaBearExt4x16::pack_full(&b).mul_by_base_field(&base)
+        );
+    }
+
+    let bytes: Vec<u8> = (0..16).collect();
+    assert_eq!(
+        KoalaBearExt4::from_uniform_bytes(&bytes).v,
+        [
+            KoalaBear::new(0x00010203),
+            KoalaBear::new(0x04050607),
+            KoalaBear::new(0x08090a0b),
+            KoalaBear::new(0x0c0d0e0f),
+        ]
+    );
+}
+
+/// x^2 - 3 is irreducible as x^4 - 3 is, declared with the default multiplication by W
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+struct KoalaBearExt2Parameters;
+
+impl BinomialExtensionParameters<KoalaBear, 2> for KoalaBearExt2Parameters {
+    const NAME: &'static str = "Koala Bear Extension 2";
+
+    const PACKED_NAME: &'static str = "Vectorized Koala Bear Extension 2";
+
+    const W: u32 = 3;
+}
+
+type KoalaBearExt2 = BinomialExtension<KoalaBear, KoalaBearExt2Parameters, 2>;
+type KoalaBearExt2x16 = PackedBinomialExtension<KoalaBearx16, KoalaBearExt2Parameters, 2>;
+
+#[test]
+fn test_binomial_extension_declaration() {
+    random_field_tests::<KoalaBearExt2>("Koala Bear Ext2".to_string());
+    random_extension_field_tests::<KoalaBearExt2>("Koala Bear Ext2".to_string());
+    random_field_tests::<KoalaBearExt2x16>("Simd Koala Bear Ext2".to_string());
+    random_extension_field_tests::<KoalaBearExt2x16>("Simd Koala Bear Ext2".to_string());
+    random_simd_field_tests::<KoalaBearExt2x16>("Simd Koala Bear Ext2".to_string());
+
+    let mut rng = test_rng();
+    random_inversion_tests::<KoalaBearExt2, _>(&mut rng, "Koala Bear Ext2".to_string());
+
+    // the square of the generator of x^2 - 3 embeds into the quartic extension
+    let x = KoalaBearExt2::X;
+    assert_eq!(x * x, KoalaBearExt2::from(KoalaBear::new(3)));
+    let y = KoalaBearExt4::X * KoalaBearExt4::X;
+    assert_eq!(y * y, KoalaBearExt4::from(KoalaBear::new(3)));
+}
+
+#[test]
+fn koala_bear_two_inverse() {
+    let two = KoalaBear::new(2);
+    let two_inverse = KoalaBearParameters::try_inverse(&two).unwrap();
+    // Display impl converts to canonical form
+    println!("2^-1 (canonical form): {two_inverse} Montgomery {two_inverse:?}");
+
+    // Check correctness
+    let two = KoalaBear::new(2);
+    let two_inverse_canonical: u32 = 1065353217;
+    assert_eq!(two_inverse, KoalaBear::new(two_inverse_canonical));
+    let one = KoalaBear::ONE;
+    assert_eq!(one, two * two_inverse)
+}
+
+#[test]
+fn test_cube_map_permutation() {
+    // 3 does not divide p - 1, the cube map is inverted by x -> x^(3^-1 mod p - 1)
+    let cube_root_exponent = 1420470955u128;
+    let mut rng = test_rng();
+    for _ in 0..100 {
+        let x = KoalaBear::random_unsafe(&mut rng);
+        assert_eq!(x.exp(3).exp(cube_root_exponent), x);
+    }
+}
+
+#[test]
+fn test_exponentiation() {
+    use rand::{rngs::OsRng, Rng};
+    let mut rng = OsRng;
+
+    for _ in 0..1000 {
+        // Use a small base to avoid overflow
+        let base_u32: u32 = rng.gen_range(0..=10);
+        let base = KoalaBear::new(base_u32);
+        // Use a small exponent to avoid overflow
+        let exponent: u32 = rng.gen_range(0..=5);
+        let expected_result = KoalaBear::new(base_u32.pow(exponent));
+        assert_eq!(base.exp(exponent as u128), expected_result);
+    }
+}
@@ -0,0 +1,45 @@
+use arith::CircleFFTField;
+use ark_std::test_rng;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use mersenne31::{M31Ext3, M31};
+
+fn bench_circle_fft<F: CircleFFTField>(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Circle FFT");
+
+    const MAX_FFT_SIZE: usize = 1 << 20;
+    let mut rng = test_rng();
+    let mut buf: Vec<F> = (0..MAX_FFT_SIZE)
+        .map(|_| F::random_unsafe(&mut rng))
+        .collect();
+
+    for group_size_bits in (10..=MAX_FFT_SIZE.ilog2()).step_by(2) {
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("{} {group_size_bits}-bits circle FFT in place", F::NAME),
+                group_size_bits,
+            ),
+            &group_size_bits,
+            |b, group_size_bits| {
+                b.iter(|| F::circle_fft_in_place(&mut buf[..1 << group_size_bits]))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new(
+                format!("{} {group_size_bits}-bits circle iFFT in place", F::NAME),
+                group_size_bits,
+            ),
+            &group_size_bits,
+            |b, group_size_bits| {
+                b.iter(|| F::circle_ifft_in_place(&mut buf[..1 << group_size_bits]))
+            },
+        );
+    }
+}
+
+fn circle_fft_benchmark(c: &mut Criterion) {
+    bench_circle_fft::<M31>(c);
+    bench_circle_fft::<M31Ext3>(c);
+}
+
+criterion_group!(bench, circle_fft_benchmark);
+criterion_main!(bench);
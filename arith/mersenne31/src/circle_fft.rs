@@ -0,0 +1,174 @@
+use std::ops::Mul;
+
+use arith::{bit_reverse, CircleFFTField, CirclePoint, Field};
+
+use crate::{M31Ext3, M31};
+
+/// A generator of the circle group over Mersenne 31, of order p + 1 = 2^31
+const M31_CIRCLE_GENERATOR: CirclePoint<M31> = CirclePoint {
+    x: M31 { v: 2 },
+    y: M31 { v: 1268011823 },
+};
+
+const M31_CIRCLE_TWO_ADICITY: usize = 31;
+
+/// Twiddle factors of the circle FFT over the canonic domain of size 2^log_size.
+/// The first layer holds the y coordinates of the half coset (4i + 1) q, with q of order
+/// 2^(log_size + 1), the following layers the x coordinates of the half cosets (4i + 1) 2^k q,
+/// whose x coordinates are the images of the previous layer by π(x) = 2x^2 - 1.
+fn circle_twiddles(log_size: usize) -> Vec<Vec<M31>> {
+    let mut q = M31::circle_subgroup_generator(log_size + 1);
+    let mut layers = vec![];
+    for layer in 0..log_size {
+        let step = q.double().double();
+        let points = (0..1usize << (log_size - layer - 1)).scan(q, |p, _| {
+            let point = *p;
+            *p = *p + step;
+            Some(point)
+        });
+        layers.push(if layer == 0 {
+            points.map(|p| p.y).collect()
+        } else {
+            points.map(|p| p.x).collect()
+        });
+        q = q.double();
+    }
+    layers
+}
+
+/// Batch inversion of non-zero elements, with a single field inversion
+fn batch_inverse(elems: &[M31]) -> Vec<M31> {
+    let mut acc = M31::ONE;
+    let prefix_products: Vec<_> = elems
+        .iter()
+        .map(|x| {
+            let prefix = acc;
+            acc *= x;
+            prefix
+        })
+        .collect();
+
+    let mut acc_inv = acc.inv().unwrap();
+    let mut inverses = vec![M31::ZERO; elems.len()];
+    for i in (0..elems.len()).rev() {
+        inverses[i] = acc_inv * prefix_products[i];
+        acc_inv *= elems[i];
+    }
+    inverses
+}
+
+fn bit_reverse_permute<T: Copy>(elems: &mut [T]) {
+    let log_size = elems.len().ilog2() as usize;
+    for i in 0..elems.len() {
+        let j = bit_reverse(i, log_size);
+        if i < j {
+            elems.swap(i, j);
+        }
+    }
+}
+
+/// Butterflies of one layer, on each chunk of `2 * twiddles.len()` elements:
+/// (a, b) -> (a + t b, a - t b)
+#[inline]
+fn butterfly_layer<T: Field + Mul<M31, Output = T>>(elems: &mut [T], twiddles: &[M31]) {
+    elems.chunks_mut(twiddles.len() * 2).for_each(|chunk| {
+        let (left, right) = chunk.split_at_mut(twiddles.len());
+        left.iter_mut()
+            .zip(right.iter_mut())
+            .zip(twiddles)
+            .for_each(|((a, b), t)| {
+                let tb = *b * *t;
+                *b = *a - tb;
+                *a += tb;
+            });
+    });
+}
+
+/// Inverse butterflies of one layer, on each chunk of `2 * twiddles_inv.len()` elements:
+/// (a, b) -> (a + b, (a - b) / t)
+#[inline]
+fn inverse_butterfly_layer<T: Field + Mul<M31, Output = T>>(elems: &mut [T], twiddles_inv: &[M31]) {
+    elems.chunks_mut(twiddles_inv.len() * 2).for_each(|chunk| {
+        let (left, right) = chunk.split_at_mut(twiddles_inv.len());
+        left.iter_mut()
+            .zip(right.iter_mut())
+            .zip(twiddles_inv)
+            .for_each(|((a, b), t_inv)| {
+                let diff = *a - *b;
+                *a += *b;
+                *b = diff * *t_inv;
+            });
+    });
+}
+
+/// Circle FFT, from the coefficients to the evaluations over the canonic domain
+pub(crate) fn circle_fft_in_place<T: Field + Mul<M31, Output = T>>(coeffs: &mut [T]) {
+    assert!(coeffs.len().is_power_of_two());
+    let log_size = coeffs.len().ilog2() as usize;
+    assert!(log_size < M31_CIRCLE_TWO_ADICITY);
+
+    bit_reverse_permute(coeffs);
+    // from the innermost line layer out to the circle layer
+    circle_twiddles(log_size)
+        .iter()
+        .rev()
+        .for_each(|twiddles| butterfly_layer(coeffs, twiddles));
+}
+
+/// Inverse circle FFT, from the evaluations over the canonic domain to the coefficients
+pub(crate) fn circle_ifft_in_place<T: Field + Mul<M31, Output = T>>(evals: &mut [T]) {
+    assert!(evals.len().is_power_of_two());
+    let log_size = evals.len().ilog2() as usize;
+    assert!(log_size < M31_CIRCLE_TWO_ADICITY);
+
+    // from the circle layer in, each layer doubles the coefficients
+    circle_twiddles(log_size)
+        .iter()
+        .for_each(|twiddles| inverse_butterfly_layer(evals, &batch_inverse(twiddles)));
+
+    let n_inv = M31::from(evals.len() as u32).inv().unwrap();
+    evals.iter_mut().for_each(|x| *x = *x * n_inv);
+    bit_reverse_permute(evals);
+}
+
+impl CircleFFTField for M31 {
+    type CircleField = M31;
+
+    const CIRCLE_TWO_ADICITY: usize = M31_CIRCLE_TWO_ADICITY;
+
+    #[inline(always)]
+    fn circle_generator() -> CirclePoint<M31> {
+        M31_CIRCLE_GENERATOR
+    }
+
+    #[inline(always)]
+    fn circle_fft_in_place(coeffs: &mut [Self]) {
+        circle_fft_in_place(coeffs)
+    }
+
+    #[inline(always)]
+    fn circle_ifft_in_place(evals: &mut [Self]) {
+        circle_ifft_in_place(evals)
+    }
+}
+
+impl CircleFFTField for M31Ext3 {
+    type CircleField = M31;
+
+    const CIRCLE_TWO_ADICITY: usize = M31_CIRCLE_TWO_ADICITY;
+
+    #[inline(always)]
+    fn circle_generator() -> CirclePoint<M31> {
+        M31_CIRCLE_GENERATOR
+    }
+
+    #[inline(always)]
+    fn circle_fft_in_place(coeffs: &mut [Self]) {
+        circle_fft_in_place(coeffs)
+    }
+
+    #[inline(always)]
+    fn circle_ifft_in_place(evals: &mut [Self]) {
+        circle_ifft_in_place(evals)
+    }
+}
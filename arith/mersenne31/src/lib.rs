@@ -24,5 +24,12 @@ pub use m31_ext6::M31Ext6;
 mod m31_ext3x16;
 pub use m31_ext3x16::M31Ext3x16;
 
+/// SIMDx16 for Degree 6 extension field for Mersenne-31
+mod m31_ext6x16;
+pub use m31_ext6x16::M31Ext6x16;
+
+/// Circle FFT over Mersenne-31 and its degree 3 extension
+mod circle_fft;
+
 #[cfg(test)]
 mod tests;
@@ -163,12 +163,18 @@ impl From<M31Ext3> for M31Ext3x16 {
 }
 
 impl Field for M31Ext3x16 {
-    #[cfg(target_arch = "x86_64")]
+    #[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
     const NAME: &'static str = "AVX Vectorized Mersenne 31 Extension 3";
 
-    #[cfg(target_arch = "aarch64")]
+    #[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
     const NAME: &'static str = "Neon Vectorized Mersenne 31 Extension 3";
 
+    #[cfg(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    ))]
+    const NAME: &'static str = "Portable Vectorized Mersenne 31 Extension 3";
+
     const SIZE: usize = 512 / 8 * 3;
 
     const FIELD_SIZE: usize = 32 * 3;
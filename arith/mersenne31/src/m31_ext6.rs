@@ -1,4 +1,4 @@
-use arith::{field_common, ExtensionField, FFTField, Field};
+use arith::{field_common, ExtensionField, FFTField, Field, SimdField};
 use ethnum::U256;
 use rand::RngCore;
 use serdes::ExpSerde;
@@ -7,8 +7,15 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use crate::{m31::mod_reduce_u32_safe, M31Ext3, M31};
+use crate::{m31::mod_reduce_u32_safe, M31Ext3, M31Ext6x16, M31x16, M31};
 
+/// M31Ext6 is the degree 2 extension of M31Ext3 modulo y^2 + 2, i.e., a0 + a1 * y.
+/// As an `ExtensionField` it is viewed as a degree 6 extension of M31, whose limbs are
+/// the coefficients of a0 followed by those of a1.
+///
+/// The limbs are not the coefficients of the powers of a single element: `W` and `X` are the
+/// parameters of the tower, y^2 = W and X = y, whose limbs are those of y, the fourth one.
+/// `mul_by_x` multiplies by y, it is not a shift of the limbs.
 #[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, ExpSerde)]
 pub struct M31Ext6 {
     pub v: [M31Ext3; 2],
@@ -118,37 +125,37 @@ impl Field for M31Ext6 {
 }
 
 impl ExtensionField for M31Ext6 {
-    const DEGREE: usize = 2;
+    const DEGREE: usize = 6;
 
     /// Extension Field
-    /// (Y^2 + 2), then the (Y^2 - W) has W = mod - 2
+    /// (Y^2 + 2) over M31Ext3, then the (Y^2 - W) has W = mod - 2, note that X^6 != W
     const W: u32 = (1 << 31) - 3;
 
+    /// y, the generator of the degree 2 extension over M31Ext3, not of the limb basis
     const X: Self = M31Ext6 {
         v: [M31Ext3::ZERO, M31Ext3::ONE],
     };
 
     /// Base field for the extension
-    type BaseField = M31Ext3;
+    type BaseField = M31;
 
     #[inline(always)]
     /// Multiply the extension field with the base field
     fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
-        let mut res = self.v;
-        res[0] *= base;
-        res[1] *= base;
-        Self { v: res }
+        Self {
+            v: [self.v[0] * *base, self.v[1] * *base],
+        }
     }
 
     #[inline(always)]
     /// Add the extension field with the base field
     fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
-        let mut res = self.v;
-        res[0] += base;
-        Self { v: res }
+        Self {
+            v: [self.v[0] + *base, self.v[1]],
+        }
     }
 
-    /// Multiply the extension field by x, i.e, 0 + x + 0 x^2 + 0 x^3 + ...
+    /// Multiply the extension field by y, i.e, 0 + y
     #[inline(always)]
     fn mul_by_x(&self) -> Self {
         Self {
@@ -156,13 +163,13 @@ impl ExtensionField for M31Ext6 {
         }
     }
 
-    /// Extract polynomial field coefficients from the extension field instance
+    /// Extract the M31 coefficients of a0 and then a1
     #[inline(always)]
     fn to_limbs(&self) -> Vec<Self::BaseField> {
-        vec![self.v[0], self.v[1]]
+        [self.v[0].v, self.v[1].v].concat()
     }
 
-    /// Construct a new instance of extension field from coefficients
+    /// Construct a new instance of extension field from the M31 coefficients of a0 and then a1
     #[inline(always)]
     fn from_limbs(limbs: &[Self::BaseField]) -> Self {
         let mut v = [Self::BaseField::default(); Self::DEGREE];
@@ -171,7 +178,16 @@ impl ExtensionField for M31Ext6 {
         } else {
             v.copy_from_slice(&limbs[..Self::DEGREE])
         }
-        Self { v }
+        Self {
+            v: [
+                M31Ext3 {
+                    v: [v[0], v[1], v[2]],
+                },
+                M31Ext3 {
+                    v: [v[3], v[4], v[5]],
+                },
+            ],
+        }
     }
 }
 
@@ -191,10 +207,30 @@ impl Mul<M31Ext3> for M31Ext6 {
 
     #[inline(always)]
     fn mul(self, rhs: M31Ext3) -> Self::Output {
+        Self {
+            v: [self.v[0] * rhs, self.v[1] * rhs],
+        }
+    }
+}
+
+impl Mul<M31> for M31Ext6 {
+    type Output = M31Ext6;
+
+    #[inline(always)]
+    fn mul(self, rhs: M31) -> Self::Output {
         self.mul_by_base_field(&rhs)
     }
 }
 
+impl Add<M31> for M31Ext6 {
+    type Output = M31Ext6;
+
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        self.add_by_base_field(&rhs)
+    }
+}
+
 impl Neg for M31Ext6 {
     type Output = M31Ext6;
     #[inline(always)]
@@ -232,6 +268,13 @@ impl From<M31Ext3> for M31Ext6 {
     }
 }
 
+impl From<M31> for M31Ext6 {
+    #[inline(always)]
+    fn from(x: M31) -> Self {
+        Self::from(M31Ext3::from(x))
+    }
+}
+
 #[inline(always)]
 fn add_internal(a: &M31Ext6, b: &M31Ext6) -> M31Ext6 {
     let mut vv = a.v;
@@ -289,3 +332,41 @@ impl PartialOrd for M31Ext6 {
         unimplemented!("PartialOrd for M31Ext6 is not supported")
     }
 }
+
+impl SimdField for M31Ext6 {
+    type Scalar = Self;
+
+    const PACK_SIZE: usize = 1;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * challenge
+    }
+
+    #[inline(always)]
+    fn pack_full(x: &Self::Scalar) -> Self {
+        *x
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert_eq!(base_vec.len(), 1);
+        base_vec[0]
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        vec![*self]
+    }
+}
+
+impl Mul<M31x16> for M31Ext6 {
+    type Output = M31Ext6x16;
+
+    #[inline(always)]
+    fn mul(self, rhs: M31x16) -> Self::Output {
+        M31Ext6x16 {
+            v: [self.v[0] * rhs, self.v[1] * rhs],
+        }
+    }
+}
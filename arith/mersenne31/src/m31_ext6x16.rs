@@ -0,0 +1,387 @@
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, ExtensionField, Field, SimdField};
+use ethnum::U256;
+use serdes::ExpSerde;
+
+use crate::{m31::M31, M31Ext3, M31Ext3x16, M31Ext6, M31x16};
+
+/// SIMDx16 of M31Ext6, i.e., a0 + a1 * y mod y^2 + 2 with a0, a1 in M31Ext3x16
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, ExpSerde)]
+pub struct M31Ext6x16 {
+    pub v: [M31Ext3x16; 2],
+}
+
+field_common!(M31Ext6x16);
+
+impl SimdField for M31Ext6x16 {
+    type Scalar = M31Ext6;
+
+    const PACK_SIZE: usize = M31x16::PACK_SIZE;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline]
+    fn pack_full(base: &Self::Scalar) -> Self {
+        Self {
+            v: [
+                M31Ext3x16::pack_full(&base.v[0]),
+                M31Ext3x16::pack_full(&base.v[1]),
+            ],
+        }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == Self::PACK_SIZE);
+        let v0s: Vec<_> = base_vec.iter().map(|scalar| scalar.v[0]).collect();
+        let v1s: Vec<_> = base_vec.iter().map(|scalar| scalar.v[1]).collect();
+
+        Self {
+            v: [M31Ext3x16::pack(&v0s), M31Ext3x16::pack(&v1s)],
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let v0s = self.v[0].unpack();
+        let v1s = self.v[1].unpack();
+
+        v0s.into_iter()
+            .zip(v1s)
+            .map(|(v0, v1)| M31Ext6 { v: [v0, v1] })
+            .collect()
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(&self) -> Self::Scalar {
+        Self::Scalar {
+            v: [self.v[0].horizontal_sum(), self.v[1].horizontal_sum()],
+        }
+    }
+}
+
+impl From<M31x16> for M31Ext6x16 {
+    #[inline(always)]
+    fn from(x: M31x16) -> Self {
+        Self {
+            v: [M31Ext3x16::from(x), M31Ext3x16::ZERO],
+        }
+    }
+}
+
+impl ExtensionField for M31Ext6x16 {
+    const DEGREE: usize = 6;
+
+    /// y^2 = W over M31Ext3x16, as for `M31Ext6`
+    const W: u32 = (1 << 31) - 3;
+
+    /// y, the generator of the degree 2 extension over M31Ext3x16, not of the limb basis
+    const X: Self = M31Ext6x16 {
+        v: [M31Ext3x16::ZERO, M31Ext3x16::ONE],
+    };
+
+    type BaseField = M31x16;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &Self::BaseField) -> Self {
+        M31Ext6x16 {
+            v: [self.v[0] * *base, self.v[1] * *base],
+        }
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &Self::BaseField) -> Self {
+        M31Ext6x16 {
+            v: [self.v[0] + *base, self.v[1]],
+        }
+    }
+
+    /// Multiply the extension field by y, i.e, 0 + y
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self {
+            v: [-self.v[1].double(), self.v[0]],
+        }
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[Self::BaseField]) -> Self {
+        let mut v = [Self::BaseField::default(); Self::DEGREE];
+        if limbs.len() < Self::DEGREE {
+            v[..limbs.len()].copy_from_slice(limbs)
+        } else {
+            v.copy_from_slice(&limbs[..Self::DEGREE])
+        }
+        Self {
+            v: [
+                M31Ext3x16 {
+                    v: [v[0], v[1], v[2]],
+                },
+                M31Ext3x16 {
+                    v: [v[3], v[4], v[5]],
+                },
+            ],
+        }
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<Self::BaseField> {
+        [self.v[0].v, self.v[1].v].concat()
+    }
+}
+
+impl Mul<M31x16> for M31Ext6x16 {
+    type Output = M31Ext6x16;
+
+    #[inline]
+    fn mul(self, rhs: M31x16) -> Self::Output {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl From<M31Ext6> for M31Ext6x16 {
+    #[inline(always)]
+    fn from(x: M31Ext6) -> Self {
+        Self::pack_full(&x)
+    }
+}
+
+impl Field for M31Ext6x16 {
+    #[cfg(all(not(feature = "portable"), target_arch = "x86_64"))]
+    const NAME: &'static str = "AVX Vectorized Mersenne 31 Extension 6";
+
+    #[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
+    const NAME: &'static str = "Neon Vectorized Mersenne 31 Extension 6";
+
+    #[cfg(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    ))]
+    const NAME: &'static str = "Portable Vectorized Mersenne 31 Extension 6";
+
+    const SIZE: usize = 512 / 8 * 6;
+
+    const FIELD_SIZE: usize = 32 * 6;
+
+    const ZERO: Self = Self {
+        v: [M31Ext3x16::ZERO; 2],
+    };
+
+    const ONE: Self = Self {
+        v: [M31Ext3x16::ONE, M31Ext3x16::ZERO],
+    };
+
+    const INV_2: Self = Self {
+        v: [M31Ext3x16::INV_2, M31Ext3x16::ZERO],
+    };
+
+    const MODULUS: U256 = M31::MODULUS;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v[0].is_zero() && self.v[1].is_zero()
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl rand::RngCore) -> Self {
+        M31Ext6x16 {
+            v: [
+                M31Ext3x16::random_unsafe(&mut rng),
+                M31Ext3x16::random_unsafe(&mut rng),
+            ],
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl rand::RngCore) -> Self {
+        M31Ext6x16 {
+            v: [M31Ext3x16::random_bool(&mut rng), M31Ext3x16::ZERO],
+        }
+    }
+
+    #[inline(always)]
+    fn square(&self) -> Self {
+        Self {
+            v: square_internal(&self.v),
+        }
+    }
+
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        let mut m31_ext6_vec = self.unpack();
+        let is_non_zero = m31_ext6_vec.iter().all(|x| !x.is_zero());
+        if !is_non_zero {
+            return None;
+        }
+
+        m31_ext6_vec.iter_mut().for_each(|x| *x = x.inv().unwrap()); // safe unwrap
+
+        Some(Self::pack(&m31_ext6_vec))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    fn from_uniform_bytes(_bytes: &[u8]) -> Self {
+        unimplemented!("vec m31: cannot convert from 32 bytes")
+    }
+}
+
+impl Mul<M31Ext6> for M31Ext6x16 {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: M31Ext6) -> Self::Output {
+        // polynomial mod (y^2 + 2), see `M31Ext6`
+        let a = &self.v;
+        let b = &rhs.v;
+        Self {
+            v: [
+                a[0] * b[0] - (a[1] * b[1]).double(),
+                a[0] * b[1] + a[1] * b[0],
+            ],
+        }
+    }
+}
+
+impl Mul<M31Ext3> for M31Ext6x16 {
+    type Output = Self;
+    #[inline(always)]
+    fn mul(self, rhs: M31Ext3) -> Self::Output {
+        Self {
+            v: [self.v[0] * rhs, self.v[1] * rhs],
+        }
+    }
+}
+
+impl Mul<M31> for M31Ext6x16 {
+    type Output = M31Ext6x16;
+    #[inline(always)]
+    fn mul(self, rhs: M31) -> Self::Output {
+        M31Ext6x16 {
+            v: [self.v[0] * rhs, self.v[1] * rhs],
+        }
+    }
+}
+
+impl Add<M31> for M31Ext6x16 {
+    type Output = M31Ext6x16;
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        M31Ext6x16 {
+            v: [self.v[0] + rhs, self.v[1]],
+        }
+    }
+}
+
+impl Neg for M31Ext6x16 {
+    type Output = M31Ext6x16;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        M31Ext6x16 {
+            v: [-self.v[0], -self.v[1]],
+        }
+    }
+}
+
+impl From<u32> for M31Ext6x16 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        M31Ext6x16 {
+            v: [M31Ext3x16::from(x), M31Ext3x16::ZERO],
+        }
+    }
+}
+
+impl From<u64> for M31Ext6x16 {
+    #[inline(always)]
+    fn from(x: u64) -> Self {
+        M31Ext6x16 {
+            v: [M31Ext3x16::from(x), M31Ext3x16::ZERO],
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &M31Ext6x16, b: &M31Ext6x16) -> M31Ext6x16 {
+    let mut vv = a.v;
+    vv[0] += b.v[0];
+    vv[1] += b.v[1];
+
+    M31Ext6x16 { v: vv }
+}
+
+#[inline(always)]
+fn sub_internal(a: &M31Ext6x16, b: &M31Ext6x16) -> M31Ext6x16 {
+    let mut vv = a.v;
+    vv[0] -= b.v[0];
+    vv[1] -= b.v[1];
+
+    M31Ext6x16 { v: vv }
+}
+
+// polynomial mod (y^2 + 2)
+//
+//   (a0 + a1*y) * (b0 + b1*y)              mod (y^2 + 2)
+// = a0*b0 + (a0*b1 + a1*b0)*y + a1*b1*y^2  mod (y^2 + 2)
+// = (a0*b0 - 2*a1*b1) + (a0*b1 + a1*b0)*y
+#[inline(always)]
+fn mul_internal(a: &M31Ext6x16, b: &M31Ext6x16) -> M31Ext6x16 {
+    let a = &a.v;
+    let b = &b.v;
+    let mut res = [M31Ext3x16::default(); 2];
+
+    res[0] = a[0] * b[0] - (a[1] * b[1]).double();
+    res[1] = a[0] * b[1] + a[1] * b[0];
+
+    M31Ext6x16 { v: res }
+}
+
+#[inline(always)]
+fn square_internal(a: &[M31Ext3x16; 2]) -> [M31Ext3x16; 2] {
+    let mut res = [M31Ext3x16::ZERO; 2];
+    res[0] = a[0].square() - a[1].square().double();
+    res[1] = a[0] * a[1].double();
+    res
+}
+
+impl Ord for M31Ext6x16 {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for M31Ext6x16 is not supported")
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for M31Ext6x16 {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for M31Ext6x16 is not supported")
+    }
+}
+
+impl Add<M31x16> for M31Ext6x16 {
+    type Output = M31Ext6x16;
+
+    #[inline(always)]
+    fn add(self, rhs: M31x16) -> Self::Output {
+        self.add_by_base_field(&rhs)
+    }
+}
@@ -1,23 +1,52 @@
 // A M31x16 stores 512 bits of data.
 // With AVX it stores a single __m512i element.
 // With NEON it stores four uint32x4_t elements.
+// Without SIMD instructions it stores sixteen M31 elements.
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 mod m31_neon;
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 pub type M31x16 = m31_neon::NeonM31;
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 mod m31_avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 pub type M31x16 = m31_avx512::AVXM31;
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 mod m31_avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 pub type M31x16 = m31_avx256::AVXM31;
 
+// Without SIMD instructions, with the `portable` feature or on other architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+mod m31_portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub type M31x16 = m31_portable::PortableM31;
+
 impl Ord for M31x16 {
     #[inline(always)]
     fn cmp(&self, _: &Self) -> std::cmp::Ordering {
@@ -0,0 +1,235 @@
+use std::{
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use arith::{field_common, Field, SimdField};
+use ethnum::U256;
+use rand::RngCore;
+use serdes::ExpSerde;
+
+use crate::{m31::M31_MOD, M31};
+
+const M31_PACK_SIZE: usize = 16;
+
+/// PortableM31 packs 16 M31 elements and operates on them lane by lane,
+/// without any SIMD instructions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ExpSerde)]
+pub struct PortableM31 {
+    pub v: [M31; M31_PACK_SIZE],
+}
+
+field_common!(PortableM31);
+
+impl Field for PortableM31 {
+    const NAME: &'static str = "Portable Packed Mersenne 31";
+
+    // size in bytes
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self {
+        v: [M31::ZERO; M31_PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [M31::ONE; M31_PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [M31::INV_2; M31_PACK_SIZE],
+    };
+
+    const MODULUS: U256 = M31::MODULUS;
+
+    #[inline(always)]
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        // Caution: this may not produce uniformly random elements
+        Self {
+            v: [(); M31_PACK_SIZE].map(|_| M31 {
+                v: rng.next_u32() % M31_MOD,
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        Self {
+            v: [(); M31_PACK_SIZE].map(|_| M31::random_bool(&mut rng)),
+        }
+    }
+
+    #[inline(always)]
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(Self {
+            v: self.v.map(|x| x.inv().unwrap()), // safe unwrap
+        })
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    #[inline]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::pack_full(&M31::from_uniform_bytes(bytes))
+    }
+
+    #[inline(always)]
+    fn mul_by_2(&self) -> Self {
+        Self {
+            v: self.v.map(|x| x.double()),
+        }
+    }
+
+    #[inline(always)]
+    fn mul_by_5(&self) -> Self {
+        Self {
+            v: self.v.map(|x| x.mul_by_5()),
+        }
+    }
+}
+
+impl SimdField for PortableM31 {
+    type Scalar = M31;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        Self {
+            v: self.v.map(|x| x * challenge),
+        }
+    }
+
+    const PACK_SIZE: usize = M31_PACK_SIZE;
+
+    #[inline(always)]
+    fn pack_full(x: &M31) -> Self {
+        Self {
+            v: [*x; M31_PACK_SIZE],
+        }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert!(base_vec.len() == M31_PACK_SIZE);
+        Self {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(&self) -> Self::Scalar {
+        // every lane is below 2^31, the sum of 16 lanes fits in a u64
+        let mut buffer: u64 = self.v.iter().map(|x| x.v as u64).sum();
+
+        buffer = (buffer & M31_MOD as u64) + (buffer >> 31);
+        if buffer == M31_MOD as u64 {
+            Self::Scalar::ZERO
+        } else {
+            Self::Scalar { v: buffer as u32 }
+        }
+    }
+}
+
+impl From<M31> for PortableM31 {
+    #[inline(always)]
+    fn from(x: M31) -> Self {
+        Self::pack_full(&x)
+    }
+}
+
+impl Mul<&M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn mul(self, rhs: &M31) -> Self::Output {
+        self.scale(rhs)
+    }
+}
+
+impl Mul<M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn mul(self, rhs: M31) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Add<M31> for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn add(self, rhs: M31) -> Self::Output {
+        Self {
+            v: self.v.map(|x| x + rhs),
+        }
+    }
+}
+
+impl From<u32> for PortableM31 {
+    #[inline(always)]
+    fn from(x: u32) -> Self {
+        Self::pack_full(&M31::from(x))
+    }
+}
+
+impl From<u64> for PortableM31 {
+    #[inline(always)]
+    fn from(x: u64) -> Self {
+        Self::pack_full(&M31::from(x))
+    }
+}
+
+impl Neg for PortableM31 {
+    type Output = PortableM31;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal(a: &PortableM31, b: &PortableM31) -> PortableM31 {
+    PortableM31 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
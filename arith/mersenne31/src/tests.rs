@@ -2,7 +2,7 @@ use arith::{
     random_extension_field_tests, random_fft_field_tests, random_field_tests,
     random_inversion_tests, random_simd_field_tests, SimdField,
 };
-use arith::{random_from_limbs_to_limbs_tests, Field};
+use arith::{random_from_limbs_to_limbs_tests, CircleFFTField, CirclePoint, ExtensionField, Field};
 use ark_std::test_rng;
 use ethnum::U256;
 use gkr_hashers::{FiatShamirHasher, PoseidonFiatShamirHasher, PoseidonStateTrait};
@@ -10,11 +10,16 @@ use serdes::ExpSerde;
 
 use crate::{
     m31::{mod_reduce_u32_safe, M31_MOD},
-    M31Ext3, M31Ext3x16, M31Ext6, M31x16, M31,
+    M31Ext3, M31Ext3x16, M31Ext6, M31Ext6x16, M31x16, M31,
 };
 
 fn get_avx_version() -> &'static str {
-    if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
+    if cfg!(any(
+        feature = "portable",
+        not(any(target_arch = "x86_64", target_arch = "aarch64"))
+    )) {
+        return "Portable";
+    } else if cfg!(all(target_arch = "x86_64", target_feature = "avx512f")) {
         return "AVX512";
     } else if cfg!(all(
         target_arch = "x86_64",
@@ -33,6 +38,7 @@ fn test_avx_version() {
     let avx_version = get_avx_version();
     println!("Current AVX version: {}", avx_version);
     assert!([
+        "Portable",
         "arm64",
         "AVX512",
         "AVX2 (256-bit)",
@@ -79,12 +85,69 @@ fn test_ext_field() {
     random_field_tests::<M31Ext6>("M31 Ext6".to_string());
     random_extension_field_tests::<M31Ext6>("M31 Ext6".to_string());
     random_fft_field_tests::<M31Ext6>("M31 Ext6".to_string());
+    random_simd_field_tests::<M31Ext6>("Simd M31 Ext6".to_string());
 
     random_field_tests::<M31Ext3x16>("Simd M31 Ext3".to_string());
     random_extension_field_tests::<M31Ext3x16>("Simd M31 Ext3".to_string());
     random_simd_field_tests::<M31Ext3x16>("Simd M31 Ext3".to_string());
     random_from_limbs_to_limbs_tests::<M31, M31Ext3>("M31 Ext3".to_string());
     random_from_limbs_to_limbs_tests::<M31x16, M31Ext3x16>("Simd M31 Ext3".to_string());
+
+    random_field_tests::<M31Ext6x16>("Simd M31 Ext6".to_string());
+    random_extension_field_tests::<M31Ext6x16>("Simd M31 Ext6".to_string());
+    random_simd_field_tests::<M31Ext6x16>("Simd M31 Ext6".to_string());
+    random_from_limbs_to_limbs_tests::<M31, M31Ext6>("M31 Ext6".to_string());
+    random_from_limbs_to_limbs_tests::<M31x16, M31Ext6x16>("Simd M31 Ext6".to_string());
+}
+
+#[test]
+fn test_m31_ext6_limbs() {
+    let mut rng = test_rng();
+    let a = M31Ext6::random_unsafe(&mut rng);
+    let b = M31Ext6::random_unsafe(&mut rng);
+    let s = M31::random_unsafe(&mut rng);
+
+    // limbs are M31-linear
+    let limbs: Vec<_> = a
+        .to_limbs()
+        .iter()
+        .zip(b.to_limbs())
+        .map(|(x, y)| *x * s + y)
+        .collect();
+    assert_eq!(M31Ext6::from_limbs(&limbs), a * s + b);
+
+    // X is y, the fourth limb, and mul_by_x multiplies by y with y^2 = W
+    let mut x_limbs = vec![M31::ZERO; 6];
+    x_limbs[3] = M31::ONE;
+    assert_eq!(M31Ext6::X.to_limbs(), x_limbs);
+    assert_eq!(
+        M31Ext6::X * M31Ext6::X,
+        M31Ext6::from(M31::from(M31Ext6::W))
+    );
+    assert_eq!(a.mul_by_x(), a * M31Ext6::X);
+    assert_eq!(
+        a.mul_by_x().to_limbs(),
+        (a.v[1] * M31Ext3::from(M31::from(M31Ext6::W)))
+            .v
+            .iter()
+            .chain(&a.v[0].v)
+            .copied()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(M31Ext6x16::X.unpack(), vec![M31Ext6::X; 16]);
+    assert_eq!(
+        M31Ext6x16::pack(&[a; 16]).mul_by_x().unpack(),
+        vec![a.mul_by_x(); 16]
+    );
+
+    // packing commutes with the arithmetic
+    let a_simd = M31Ext6x16::pack(&(0..16).map(|i| a * M31::from(i as u32)).collect::<Vec<_>>());
+    let ab_simd = a_simd * b;
+    ab_simd
+        .unpack()
+        .iter()
+        .enumerate()
+        .for_each(|(i, x)| assert_eq!(*x, a * b * M31::from(i as u32)));
 }
 
 /// Compare to test vectors generated in SageMath
@@ -235,3 +298,62 @@ fn test_mod_u256_large_values() {
     let expected = mod_reduce_u32_safe(255); // Theoretical result based on powers of 2
     assert_eq!(M31::from_u256(max_value), M31 { v: expected });
 }
+
+#[test]
+fn test_circle_generator() {
+    let g = M31::circle_generator();
+    assert!(g.is_on_circle());
+    assert_eq!(g.scalar_mul(1 << 31), CirclePoint::identity());
+    // the point of order 2
+    assert_eq!(
+        g.scalar_mul(1 << 30),
+        CirclePoint {
+            x: -M31::ONE,
+            y: M31::ZERO
+        }
+    );
+
+    for log_size in 0..8 {
+        let domain = M31::circle_domain(log_size);
+        assert_eq!(domain.len(), 1 << log_size);
+        assert!(domain.iter().all(|p| p.is_on_circle()));
+        // the canonic domain is the coset of odd multiples of a point of order 2^(log_size + 1)
+        let q = M31::circle_subgroup_generator(log_size + 1);
+        let mut coset: Vec<_> = (0..1u128 << log_size)
+            .map(|i| q.scalar_mul(2 * i + 1))
+            .map(|p| (p.x.v, p.y.v))
+            .collect();
+        let mut points: Vec<_> = domain.iter().map(|p| (p.x.v, p.y.v)).collect();
+        coset.sort();
+        points.sort();
+        assert_eq!(coset, points);
+    }
+}
+
+fn test_circle_fft_helper<F: CircleFFTField<CircleField = M31>>() {
+    let mut rng = test_rng();
+
+    for log_size in 0..10 {
+        let evals: Vec<F> = (0..1 << log_size)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect();
+
+        let coeffs = F::circle_ifft(&evals);
+        assert_eq!(F::circle_fft(&coeffs), evals);
+
+        let domain = M31::circle_domain(log_size);
+        domain.iter().zip(&evals).for_each(|(p, e)| {
+            let point = CirclePoint {
+                x: F::from(p.x),
+                y: F::from(p.y),
+            };
+            assert_eq!(F::circle_evaluate(&coeffs, &point), *e);
+        });
+    }
+}
+
+#[test]
+fn test_circle_fft() {
+    test_circle_fft_helper::<M31>();
+    test_circle_fft_helper::<M31Ext3>();
+}
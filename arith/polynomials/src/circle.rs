@@ -0,0 +1,79 @@
+use arith::{CircleFFTField, CirclePoint};
+
+/// A polynomial on the circle, in the basis of the circle FFT, see `CircleFFTField`
+#[derive(Debug, Clone, Default)]
+pub struct CirclePoly<F: CircleFFTField> {
+    pub coeffs: Vec<F>,
+}
+
+/// Evaluations of a polynomial on the canonic circle domain of the same size
+#[derive(Debug, Clone, Default)]
+pub struct CircleEvaluation<F: CircleFFTField> {
+    pub evals: Vec<F>,
+}
+
+impl<F: CircleFFTField> CirclePoly<F> {
+    #[inline]
+    pub fn new(coeffs: Vec<F>) -> Self {
+        assert!(coeffs.len().is_power_of_two());
+
+        Self { coeffs }
+    }
+
+    #[inline]
+    pub fn random(log_size: usize, mut rng: impl rand::RngCore) -> Self {
+        let coeffs = (0..1 << log_size)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect();
+        Self { coeffs }
+    }
+
+    #[inline]
+    pub fn log_size(&self) -> usize {
+        self.coeffs.len().ilog2() as usize
+    }
+
+    #[inline]
+    pub fn evaluate(&self, point: &CirclePoint<F>) -> F {
+        F::circle_evaluate(&self.coeffs, point)
+    }
+
+    #[inline]
+    pub fn fft(mut self) -> CircleEvaluation<F> {
+        F::circle_fft_in_place(&mut self.coeffs);
+
+        CircleEvaluation::new(self.coeffs)
+    }
+
+    /// Evaluations on the canonic domain of size 2^log_size, no smaller than the polynomial
+    #[inline]
+    pub fn extend(&self, log_size: usize) -> CircleEvaluation<F> {
+        assert!(log_size >= self.log_size());
+
+        let mut coeffs = self.coeffs.clone();
+        coeffs.resize(1 << log_size, F::ZERO);
+        Self::new(coeffs).fft()
+    }
+}
+
+impl<F: CircleFFTField> CircleEvaluation<F> {
+    #[inline]
+    pub fn new(evals: Vec<F>) -> Self {
+        assert!(evals.len().is_power_of_two());
+
+        Self { evals }
+    }
+
+    /// The points of the domain, in the order of the evaluations
+    #[inline]
+    pub fn domain(&self) -> Vec<CirclePoint<F::CircleField>> {
+        F::circle_domain(self.evals.len().ilog2() as usize)
+    }
+
+    #[inline]
+    pub fn ifft(mut self) -> CirclePoly<F> {
+        F::circle_ifft_in_place(&mut self.evals);
+
+        CirclePoly::new(self.evals)
+    }
+}
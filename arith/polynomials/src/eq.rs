@@ -1,5 +1,9 @@
+use std::ops::Mul;
+
 use arith::Field;
 
+use crate::MultilinearExtension;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct EqPolynomial<F> {
     _phantom: std::marker::PhantomData<F>,
@@ -137,6 +141,32 @@ impl<F: Field> EqPolynomial<F> {
             })
             .product()
     }
+
+    /// The inner product of f and g weighted by eq(r, x), i.e., \sum_x eq(r, x) f(x) g(x),
+    /// the claim of a sumcheck of f * g * eq(r, .). With g = 1 this is f(r).
+    #[inline]
+    pub fn eq_weighted_inner_product<BaseF, EvalF>(
+        r: &[F],
+        f: &impl MultilinearExtension<BaseF>,
+        g: &impl MultilinearExtension<BaseF>,
+    ) -> EvalF
+    where
+        BaseF: Field,
+        F: Mul<BaseF, Output = EvalF>,
+        EvalF: Field,
+    {
+        assert_eq!(f.num_vars(), r.len());
+        assert_eq!(g.num_vars(), r.len());
+
+        let mut eq_evals = vec![F::ZERO; 1 << r.len()];
+        Self::build_eq_x_r_with_buf(r, &F::ONE, &mut eq_evals);
+        eq_evals
+            .iter()
+            .zip(f.hypercube_basis_ref())
+            .zip(g.hypercube_basis_ref())
+            .map(|((eq, f), g)| *eq * (*f * g))
+            .sum()
+    }
 }
 
 // Private functions
@@ -7,11 +7,23 @@ pub use ref_mle::*;
 mod univariate;
 pub use univariate::*;
 
+mod circle;
+pub use circle::*;
+
 mod eq;
 pub use eq::*;
 
 mod sum_of_products;
 pub use sum_of_products::*;
 
+mod virtual_poly;
+pub use virtual_poly::*;
+
+mod sparse_mle;
+pub use sparse_mle::*;
+
+mod streaming;
+pub use streaming::*;
+
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,6 @@
-use std::ops::{Add, Index, IndexMut, Mul};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
-use arith::Field;
+use arith::{Field, SimdField};
 use ark_std::log2;
 
 use crate::{EqPolynomial, MultilinearExtension, MutableMultilinearExtension};
@@ -183,6 +183,75 @@ impl<F: Field> MultiLinearPoly<F> {
             scratch[0]
         }
     }
+
+    /// The polynomial f(x) * g(y) of the variables x of f = `self` followed by the variables y
+    /// of g = `other`
+    #[inline]
+    pub fn tensor_product(&self, other: &Self) -> Self {
+        let coeffs = other
+            .coeffs
+            .iter()
+            .flat_map(|b| self.coeffs.iter().map(move |a| *a * b))
+            .collect();
+        Self { coeffs }
+    }
+
+    /// The linear combination \sum_i coefs_i * polys_i. The polynomials of fewer variables are
+    /// lifted to the largest number of variables with zeros, as `lift_to_n_vars` does.
+    #[inline]
+    pub fn linear_combination(polys: &[impl MultilinearExtension<F>], coefs: &[F]) -> Self {
+        Self::linear_combination_with(polys, coefs, |eval, coef| *eval * coef)
+    }
+
+    #[inline]
+    fn linear_combination_with<S>(
+        polys: &[impl MultilinearExtension<F>],
+        coefs: &[S],
+        mul: impl Fn(&F, &S) -> F,
+    ) -> Self {
+        assert_eq!(polys.len(), coefs.len());
+
+        let num_vars = polys.iter().map(|p| p.num_vars()).max().unwrap_or(0);
+        let mut coeffs = vec![F::ZERO; 1 << num_vars];
+        polys.iter().zip(coefs).for_each(|(poly, coef)| {
+            coeffs
+                .iter_mut()
+                .zip(poly.hypercube_basis_ref())
+                .for_each(|(acc, eval)| *acc += mul(eval, coef));
+        });
+        Self { coeffs }
+    }
+}
+
+impl<F: SimdField> MultiLinearPoly<F> {
+    /// Scale all the evaluations by an element of the scalar field
+    #[inline]
+    pub fn scale(&self, scalar: &F::Scalar) -> Self {
+        let coeffs = self.coeffs.iter().map(|c| c.scale(scalar)).collect();
+        Self { coeffs }
+    }
+
+    /// The linear combination of the polynomials with coefficients in the scalar field, see
+    /// `linear_combination`
+    #[inline]
+    pub fn scalar_linear_combination(
+        polys: &[impl MultilinearExtension<F>],
+        coefs: &[F::Scalar],
+    ) -> Self {
+        Self::linear_combination_with(polys, coefs, |eval, coef| eval.scale(coef))
+    }
+
+    /// Evaluate the polynomial at the top variable, with an element of the scalar field
+    #[inline]
+    pub fn fix_top_variable_scalar(&mut self, r: &F::Scalar) {
+        let n = self.coeffs.len() / 2;
+        let (left, right) = self.coeffs.split_at_mut(n);
+
+        left.iter_mut().zip(right.iter()).for_each(|(a, b)| {
+            *a += (*b - *a).scale(r);
+        });
+        self.coeffs.truncate(n);
+    }
 }
 
 impl<F: Field> Add<&MultiLinearPoly<F>> for MultiLinearPoly<F> {
@@ -200,6 +269,21 @@ impl<F: Field> Add<&MultiLinearPoly<F>> for MultiLinearPoly<F> {
     }
 }
 
+impl<F: Field> Sub<&MultiLinearPoly<F>> for MultiLinearPoly<F> {
+    type Output = Self;
+
+    fn sub(self, other: &MultiLinearPoly<F>) -> Self::Output {
+        assert_eq!(self.coeffs.len(), other.coeffs.len());
+        let coeffs = self
+            .coeffs
+            .iter()
+            .zip(other.coeffs.iter())
+            .map(|(a, b)| *a - *b)
+            .collect();
+        MultiLinearPoly { coeffs }
+    }
+}
+
 impl<F: Field> Mul<&F> for &MultiLinearPoly<F> {
     type Output = MultiLinearPoly<F>;
 
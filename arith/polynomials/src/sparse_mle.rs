@@ -0,0 +1,113 @@
+use std::ops::{Add, Mul};
+
+use arith::Field;
+
+use crate::{EqPolynomial, MultiLinearPoly, MultilinearExtension};
+
+/// A multilinear polynomial given by its nonzero evaluations over the hypercube, e.g., a
+/// selector or the multiplicities of a lookup.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseMultiLinearPoly<F: Field> {
+    pub num_vars: usize,
+    /// The nonzero evaluations with their indices on the hypercube, sorted by index
+    pub entries: Vec<(usize, F)>,
+}
+
+impl<F: Field> SparseMultiLinearPoly<F> {
+    /// The evaluations at the same index are summed, the zero evaluations are dropped.
+    #[inline]
+    pub fn new(num_vars: usize, mut entries: Vec<(usize, F)>) -> Self {
+        assert!(entries.iter().all(|(index, _)| *index < 1 << num_vars));
+        entries.sort_unstable_by_key(|(index, _)| *index);
+
+        let mut merged: Vec<(usize, F)> = Vec::with_capacity(entries.len());
+        for (index, value) in entries {
+            match merged.last_mut() {
+                Some((last, sum)) if *last == index => *sum += value,
+                _ => merged.push((index, value)),
+            }
+        }
+        merged.retain(|(_, value)| *value != F::ZERO);
+
+        Self {
+            num_vars,
+            entries: merged,
+        }
+    }
+
+    #[inline]
+    pub fn from_dense(poly: &impl MultilinearExtension<F>) -> Self {
+        let entries = poly
+            .hypercube_basis_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value != F::ZERO)
+            .map(|(index, value)| (index, *value))
+            .collect();
+        Self {
+            num_vars: poly.num_vars(),
+            entries,
+        }
+    }
+
+    #[inline]
+    pub fn to_dense(&self) -> MultiLinearPoly<F> {
+        let mut coeffs = vec![F::ZERO; 1 << self.num_vars];
+        self.entries
+            .iter()
+            .for_each(|(index, value)| coeffs[*index] = *value);
+        MultiLinearPoly { coeffs }
+    }
+
+    #[inline]
+    pub fn num_nonzeros(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The evaluation at the `index`-th vertex of the hypercube
+    #[inline]
+    pub fn get(&self, index: usize) -> F {
+        assert!(index < 1 << self.num_vars);
+        self.entries
+            .binary_search_by_key(&index, |(i, _)| *i)
+            .map_or(F::ZERO, |position| self.entries[position].1)
+    }
+
+    /// Evaluate the polynomial at `point` in O(num_nonzeros * num_vars) time, without
+    /// allocating the hypercube
+    #[inline]
+    pub fn evaluate<ChallengeF, EvalF>(&self, point: &[ChallengeF]) -> EvalF
+    where
+        ChallengeF: Field + Mul<F, Output = EvalF>,
+        EvalF: Field,
+    {
+        assert_eq!(point.len(), self.num_vars);
+        self.entries
+            .iter()
+            .map(|(index, value)| EqPolynomial::ith_eq_vec_elem(point, *index) * *value)
+            .sum()
+    }
+}
+
+impl<F: Field> Add<&SparseMultiLinearPoly<F>> for SparseMultiLinearPoly<F> {
+    type Output = Self;
+
+    fn add(mut self, other: &SparseMultiLinearPoly<F>) -> Self::Output {
+        assert_eq!(self.num_vars, other.num_vars);
+        self.entries.extend_from_slice(&other.entries);
+        SparseMultiLinearPoly::new(self.num_vars, self.entries)
+    }
+}
+
+impl<F: Field> Mul<&F> for &SparseMultiLinearPoly<F> {
+    type Output = SparseMultiLinearPoly<F>;
+
+    fn mul(self, scalar: &F) -> Self::Output {
+        let entries = self
+            .entries
+            .iter()
+            .map(|(index, value)| (*index, *value * scalar))
+            .collect();
+        SparseMultiLinearPoly::new(self.num_vars, entries)
+    }
+}
@@ -0,0 +1,121 @@
+//! Evaluation of multilinear polynomials larger than the memory, from their evaluations over the
+//! hypercube arriving in chunks, e.g., read from a file.
+
+use std::{
+    io::Read,
+    marker::PhantomData,
+    ops::{Add, Mul},
+};
+
+use arith::Field;
+use serdes::SerdeResult;
+
+use crate::MultiLinearPoly;
+
+/// Evaluates a multilinear polynomial from chunks of `2^chunk_vars` consecutive evaluations,
+/// i.e., the restrictions of the polynomial to the subcubes of its top variables, in order.
+///
+/// Only one chunk and O(num_vars) partial evaluations are held in memory: each chunk is
+/// evaluated at the bottom variables of the point, and the evaluations of two sibling subcubes
+/// are folded with the next variable as soon as both are known.
+pub struct StreamingMleEvaluator<F, ChallengeF, EvalF> {
+    point: Vec<ChallengeF>,
+    chunk_vars: usize,
+    scratch: Vec<EvalF>,
+    /// The evaluations of the complete subcubes waiting for their sibling, with the number of
+    /// top variables they span
+    pending: Vec<(usize, EvalF)>,
+    num_absorbed: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F, ChallengeF, EvalF> StreamingMleEvaluator<F, ChallengeF, EvalF>
+where
+    F: Field,
+    ChallengeF: Field + Mul<F, Output = EvalF>,
+    EvalF: Field
+        + From<F>
+        + Mul<F, Output = EvalF>
+        + Add<F, Output = EvalF>
+        + Mul<ChallengeF, Output = EvalF>,
+{
+    /// An evaluator at `point`, a chunk larger than the hypercube is the whole hypercube
+    #[inline]
+    pub fn new(point: &[ChallengeF], chunk_vars: usize) -> Self {
+        let chunk_vars = chunk_vars.min(point.len());
+        Self {
+            point: point.to_vec(),
+            chunk_vars,
+            scratch: vec![EvalF::ZERO; 1 << chunk_vars],
+            pending: Vec::with_capacity(point.len() - chunk_vars + 1),
+            num_absorbed: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn chunk_size(&self) -> usize {
+        1 << self.chunk_vars
+    }
+
+    #[inline]
+    pub fn num_chunks(&self) -> usize {
+        1 << (self.point.len() - self.chunk_vars)
+    }
+
+    /// Absorb the next chunk of evaluations
+    #[inline]
+    pub fn absorb(&mut self, chunk: &[F]) {
+        assert_eq!(chunk.len(), self.chunk_size());
+        assert!(self.num_absorbed < self.num_chunks(), "too many chunks");
+
+        let mut eval = MultiLinearPoly::evaluate_with_buffer(
+            chunk,
+            &self.point[..self.chunk_vars],
+            &mut self.scratch,
+        );
+        let mut level = 0;
+        while matches!(self.pending.last(), Some((top, _)) if *top == level) {
+            let (_, left) = self.pending.pop().unwrap();
+            eval = left + (eval - left) * self.point[self.chunk_vars + level];
+            level += 1;
+        }
+        self.pending.push((level, eval));
+        self.num_absorbed += 1;
+    }
+
+    /// The evaluation of the polynomial, once all the chunks are absorbed
+    #[inline]
+    pub fn finalize(self) -> EvalF {
+        assert_eq!(self.num_absorbed, self.num_chunks(), "missing chunks");
+        self.pending[0].1
+    }
+}
+
+/// Evaluate the polynomial of `point.len()` variables whose serialized evaluations are read from
+/// `reader`, `2^chunk_vars` evaluations at a time.
+#[inline]
+pub fn evaluate_from_reader<F, ChallengeF, EvalF>(
+    mut reader: impl Read,
+    point: &[ChallengeF],
+    chunk_vars: usize,
+) -> SerdeResult<EvalF>
+where
+    F: Field,
+    ChallengeF: Field + Mul<F, Output = EvalF>,
+    EvalF: Field
+        + From<F>
+        + Mul<F, Output = EvalF>
+        + Add<F, Output = EvalF>
+        + Mul<ChallengeF, Output = EvalF>,
+{
+    let mut evaluator = StreamingMleEvaluator::new(point, chunk_vars);
+    let mut chunk = vec![F::ZERO; evaluator.chunk_size()];
+    for _ in 0..evaluator.num_chunks() {
+        for eval in chunk.iter_mut() {
+            *eval = F::deserialize_from(&mut reader)?;
+        }
+        evaluator.absorb(&chunk);
+    }
+    Ok(evaluator.finalize())
+}
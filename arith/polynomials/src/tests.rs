@@ -1,7 +1,10 @@
 use super::*;
-use arith::{FFTField, Field};
+use arith::{CircleFFTField, CirclePoint, FFTField, Field, SimdField};
 use ark_std::test_rng;
 use halo2curves::bn256::Fr;
+use mersenne31::{M31Ext3, M31Ext3x16, M31x16, M31};
+use rand::RngCore;
+use serdes::ExpSerde;
 
 #[test]
 fn test_scaled_eq_xr() {
@@ -259,3 +262,288 @@ fn test_univariate_degree_one_quotient() {
         assert_eq!(poly.coeffs, vec![Fr::ONE, Fr::ZERO, Fr::ZERO, Fr::ZERO]);
     }
 }
+
+#[test]
+fn test_virtual_polynomial() {
+    let mut rng = test_rng();
+    let nv = 4;
+    let f = std::sync::Arc::new(MultiLinearPoly::<Fr>::random(nv, &mut rng));
+    let g = std::sync::Arc::new(MultiLinearPoly::<Fr>::random(nv, &mut rng));
+    let h = std::sync::Arc::new(MultiLinearPoly::<Fr>::random(nv, &mut rng));
+    let c0 = Fr::random_unsafe(&mut rng);
+    let c1 = Fr::random_unsafe(&mut rng);
+
+    // c0 * f * g * h + c1 * f * f
+    let mut poly = VirtualPolynomial::new(nv);
+    poly.add_mle_list([f.clone(), g.clone(), h.clone()], c0);
+    poly.add_mle_list([f.clone(), f.clone()], c1);
+    assert_eq!(poly.flattened_ml_extensions.len(), 3);
+    assert_eq!(poly.degree(), 3);
+
+    let expected_sum = (0..1 << nv)
+        .map(|i| c0 * f[i] * g[i] * h[i] + c1 * f[i] * f[i])
+        .sum::<Fr>();
+    assert_eq!(poly.sum(), expected_sum);
+
+    let point = (0..nv)
+        .map(|_| Fr::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let (f_r, g_r, h_r) = (
+        f.eval_reverse_order(&point),
+        g.eval_reverse_order(&point),
+        h.eval_reverse_order(&point),
+    );
+    assert_eq!(poly.evaluate(&point), c0 * f_r * g_r * h_r + c1 * f_r * f_r);
+
+    // (c0 * f * g * h + c1 * f * f) * 2 * g
+    poly.mul_by_mle(g.clone(), Fr::from(2u32));
+    assert_eq!(poly.flattened_ml_extensions.len(), 3);
+    assert_eq!(poly.degree(), 4);
+    assert_eq!(
+        poly.evaluate(&point),
+        (c0 * f_r * g_r * h_r + c1 * f_r * f_r) * Fr::from(2u32) * g_r
+    );
+}
+
+#[test]
+fn test_virtual_polynomial_unpack() {
+    let mut rng = test_rng();
+    let nv = 3;
+    let f = std::sync::Arc::new(MultiLinearPoly::<M31x16>::random(nv, &mut rng));
+    let g = std::sync::Arc::new(MultiLinearPoly::<M31x16>::random(nv, &mut rng));
+    let c = M31::random_unsafe(&mut rng);
+
+    // c * f * g, with the lanes as the lowest variables
+    let mut poly = VirtualPolynomial::new(nv);
+    poly.add_mle_list([f.clone(), g.clone()], M31x16::pack_full(&c));
+    let unpacked = poly.unpack();
+    assert_eq!(unpacked.num_vars, poly.unpacked_num_vars());
+    assert_eq!(unpacked.num_vars, nv + 4);
+    assert_eq!(
+        unpacked.flattened_ml_extensions[0][3 * 16 + 5],
+        f[3].unpack()[5]
+    );
+    assert_eq!(unpacked.sum(), poly.sum().horizontal_sum());
+
+    // fixing the top variable with a scalar commutes with the unpacking
+    let r = M31::random_unsafe(&mut rng);
+    let mut f_r = (*f).clone();
+    f_r.fix_top_variable_scalar(&r);
+    let mut unpacked_f_r = (*unpacked.flattened_ml_extensions[0]).clone();
+    unpacked_f_r.fix_top_variable(r);
+    assert_eq!(
+        f_r.coeffs
+            .iter()
+            .flat_map(|c| c.unpack())
+            .collect::<Vec<_>>(),
+        unpacked_f_r.coeffs
+    );
+}
+
+#[test]
+fn test_circle_poly_extension() {
+    let mut rng = test_rng();
+
+    for log_size in 0..8 {
+        let poly = CirclePoly::<M31Ext3>::random(log_size, &mut rng);
+
+        let evals = poly.clone().fft();
+        assert_eq!(evals.clone().ifft().coeffs, poly.coeffs);
+
+        // the low degree extension agrees with the polynomial on the larger domain
+        let extended = poly.extend(log_size + 2);
+        extended
+            .domain()
+            .iter()
+            .zip(&extended.evals)
+            .for_each(|(p, e)| {
+                let point = CirclePoint {
+                    x: M31Ext3::from(p.x),
+                    y: M31Ext3::from(p.y),
+                };
+                assert_eq!(poly.evaluate(&point), *e);
+            });
+
+        let mut padded = poly.coeffs.clone();
+        padded.resize(1 << (log_size + 2), M31Ext3::ZERO);
+        assert_eq!(extended.ifft().coeffs, padded);
+        assert_eq!(M31Ext3::circle_domain(log_size).len(), 1 << log_size);
+    }
+}
+
+fn random_point<F: Field>(nv: usize, mut rng: impl RngCore) -> Vec<F> {
+    (0..nv).map(|_| F::random_unsafe(&mut rng)).collect()
+}
+
+#[test]
+fn test_linear_combination() {
+    let mut rng = test_rng();
+    for nv in 4..10 {
+        // polynomials of fewer variables are lifted with zeros
+        let polys: Vec<MultiLinearPoly<Fr>> = (0..4)
+            .map(|i| MultiLinearPoly::random(nv - i, &mut rng))
+            .collect();
+        let coefs: Vec<Fr> = random_point(polys.len(), &mut rng);
+        let combination = MultiLinearPoly::linear_combination(&polys, &coefs);
+        assert_eq!(combination.get_num_vars(), nv);
+
+        let point: Vec<Fr> = random_point(nv, &mut rng);
+        let expected: Fr = polys
+            .iter()
+            .zip(&coefs)
+            .map(|(poly, coef)| {
+                let mut lifted = poly.clone();
+                lifted.lift_to_n_vars(nv);
+                lifted.evaluate::<Fr, Fr>(&point) * coef
+            })
+            .sum();
+        assert_eq!(combination.evaluate::<Fr, Fr>(&point), expected);
+
+        let difference = combination.clone() - &combination;
+        assert!(difference.coeffs.iter().all(|c| *c == Fr::zero()));
+
+        // coefficients in the scalar field of a SIMD field
+        let polys: Vec<MultiLinearPoly<M31x16>> = (0..4)
+            .map(|_| MultiLinearPoly::random(nv, &mut rng))
+            .collect();
+        let coefs: Vec<M31> = random_point(polys.len(), &mut rng);
+        let packed_coefs: Vec<M31x16> = coefs.iter().map(M31x16::pack_full).collect();
+        assert_eq!(
+            MultiLinearPoly::scalar_linear_combination(&polys, &coefs),
+            MultiLinearPoly::linear_combination(&polys, &packed_coefs)
+        );
+        assert_eq!(polys[0].scale(&coefs[0]), &polys[0] * &packed_coefs[0]);
+    }
+}
+
+#[test]
+fn test_tensor_product() {
+    let mut rng = test_rng();
+    for nv in 1..6 {
+        let f = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(nv + 1, &mut rng);
+        let product = f.tensor_product(&g);
+        assert_eq!(product.get_num_vars(), 2 * nv + 1);
+
+        let point: Vec<Fr> = random_point(2 * nv + 1, &mut rng);
+        assert_eq!(
+            product.evaluate::<Fr, Fr>(&point),
+            f.evaluate::<Fr, Fr>(&point[..nv]) * g.evaluate::<Fr, Fr>(&point[nv..])
+        );
+    }
+}
+
+#[test]
+fn test_sparse_multilinear_poly() {
+    let mut rng = test_rng();
+    for nv in 4..10 {
+        let entries: Vec<(usize, Fr)> = (0..nv)
+            .map(|_| {
+                let index = rng.next_u32() as usize % (1 << nv);
+                (index, Fr::random_unsafe(&mut rng))
+            })
+            .collect();
+        let sparse = SparseMultiLinearPoly::new(nv, entries.clone());
+        let dense = sparse.to_dense();
+        assert_eq!(SparseMultiLinearPoly::from_dense(&dense), sparse);
+        assert!(sparse.num_nonzeros() <= entries.len());
+        (0..1 << nv).for_each(|i| assert_eq!(sparse.get(i), dense[i]));
+
+        let point: Vec<Fr> = random_point(nv, &mut rng);
+        assert_eq!(
+            sparse.evaluate::<Fr, Fr>(&point),
+            dense.evaluate::<Fr, Fr>(&point)
+        );
+
+        let coef = Fr::random_unsafe(&mut rng);
+        assert_eq!((&sparse * &coef).to_dense(), &dense * &coef);
+        let negated = &sparse * &-Fr::from(1u64);
+        assert_eq!((sparse.clone() + &negated).num_nonzeros(), 0);
+        assert_eq!(
+            (sparse.clone() + &sparse).to_dense(),
+            dense.clone() + &dense
+        );
+
+        // packed evaluations at a point of the extension field
+        let entries: Vec<(usize, M31x16)> = (0..nv)
+            .map(|i| (i * i, M31x16::random_unsafe(&mut rng)))
+            .collect();
+        let sparse = SparseMultiLinearPoly::new(nv, entries);
+        let point: Vec<M31Ext3> = random_point(nv, &mut rng);
+        assert_eq!(
+            sparse.evaluate::<M31Ext3, M31Ext3x16>(&point),
+            sparse.to_dense().evaluate::<M31Ext3, M31Ext3x16>(&point)
+        );
+    }
+}
+
+#[test]
+fn test_eq_weighted_inner_product() {
+    let mut rng = test_rng();
+    for nv in 4..10 {
+        let f = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let r: Vec<Fr> = random_point(nv, &mut rng);
+
+        let expected: Fr = (0..1 << nv)
+            .map(|i| EqPolynomial::ith_eq_vec_elem(&r, i) * f[i] * g[i])
+            .sum();
+        assert_eq!(
+            EqPolynomial::<Fr>::eq_weighted_inner_product(&r, &f, &g),
+            expected
+        );
+
+        let one = MultiLinearPoly::new(vec![Fr::from(1u64); 1 << nv]);
+        assert_eq!(
+            EqPolynomial::<Fr>::eq_weighted_inner_product(&r, &f, &one),
+            f.evaluate::<Fr, Fr>(&r)
+        );
+    }
+}
+
+#[test]
+fn test_streaming_evaluation() {
+    let mut rng = test_rng();
+    for nv in 0..10 {
+        let poly = MultiLinearPoly::<Fr>::random(nv, &mut rng);
+        let point: Vec<Fr> = random_point(nv, &mut rng);
+        let expected = poly.evaluate::<Fr, Fr>(&point);
+
+        let mut bytes = vec![];
+        poly.coeffs
+            .iter()
+            .for_each(|c| c.serialize_into(&mut bytes).unwrap());
+
+        // chunks of more variables than the polynomial are the whole polynomial
+        for chunk_vars in 0..nv + 2 {
+            let mut evaluator = StreamingMleEvaluator::<Fr, Fr, Fr>::new(&point, chunk_vars);
+            poly.coeffs
+                .chunks(evaluator.chunk_size())
+                .for_each(|chunk| evaluator.absorb(chunk));
+            assert_eq!(evaluator.finalize(), expected);
+
+            assert_eq!(
+                evaluate_from_reader::<Fr, Fr, Fr>(bytes.as_slice(), &point, chunk_vars).unwrap(),
+                expected
+            );
+        }
+        // a truncated stream
+        if nv > 0 {
+            assert!(
+                evaluate_from_reader::<Fr, Fr, Fr>(&bytes[..bytes.len() - 1], &point, 1).is_err()
+            );
+        }
+
+        let poly = MultiLinearPoly::<M31x16>::random(nv, &mut rng);
+        let point: Vec<M31Ext3> = random_point(nv, &mut rng);
+        let mut evaluator =
+            StreamingMleEvaluator::<M31x16, M31Ext3, M31Ext3x16>::new(&point, nv / 2);
+        poly.coeffs
+            .chunks(evaluator.chunk_size())
+            .for_each(|chunk| evaluator.absorb(chunk));
+        assert_eq!(
+            evaluator.finalize(),
+            poly.evaluate::<M31Ext3, M31Ext3x16>(&point)
+        );
+    }
+}
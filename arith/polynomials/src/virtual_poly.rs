@@ -0,0 +1,157 @@
+use std::{collections::HashMap, sync::Arc};
+
+use arith::{Field, SimdField};
+
+use crate::MultiLinearPoly;
+
+#[derive(Clone, Debug, Default)]
+/// A sum of products of multi-linear polynomials with coefficients:
+/// f = c0 * g00 * g01 * ... + c1 * g10 * g11 * ... + ...
+/// where the multi-linear polynomials are shared between the products, and referred to by their
+/// index in `flattened_ml_extensions`.
+/// The sumcheck over this polynomial has the degree of the largest product.
+///
+/// Over a `SimdField`, the lanes are the lowest variables of the polynomial, see `unpack`.
+pub struct VirtualPolynomial<F: Field> {
+    /// The number of variables of every multi-linear polynomial
+    pub num_vars: usize,
+    /// The products, as a coefficient and the indices of the multiplied polynomials
+    pub products: Vec<(F, Vec<usize>)>,
+    /// The multi-linear polynomials referred to by the products
+    pub flattened_ml_extensions: Vec<Arc<MultiLinearPoly<F>>>,
+    /// The index of each polynomial, by the address of its shared reference
+    raw_pointers_lookup_table: HashMap<usize, usize>,
+}
+
+impl<F: Field> VirtualPolynomial<F> {
+    /// Create an empty polynomial over `num_vars` variables
+    #[inline]
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            ..Default::default()
+        }
+    }
+
+    /// Create the polynomial `coefficient * mle`
+    #[inline]
+    pub fn new_from_mle(mle: &Arc<MultiLinearPoly<F>>, coefficient: F) -> Self {
+        let mut poly = Self::new(mle.get_num_vars());
+        poly.add_mle_list([mle.clone()], coefficient);
+        poly
+    }
+
+    /// The degree of the sumcheck over this polynomial
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.products
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Add the product `coefficient * mle_0 * mle_1 * ...` to the polynomial.
+    /// A polynomial that is already referred to by another product is not copied.
+    pub fn add_mle_list(
+        &mut self,
+        mle_list: impl IntoIterator<Item = Arc<MultiLinearPoly<F>>>,
+        coefficient: F,
+    ) {
+        let indices = mle_list
+            .into_iter()
+            .map(|mle| self.mle_index(mle))
+            .collect::<Vec<_>>();
+        assert!(
+            !indices.is_empty(),
+            "a product needs at least one polynomial"
+        );
+        self.products.push((coefficient, indices));
+    }
+
+    /// Multiply every product of the polynomial by `coefficient * mle`
+    pub fn mul_by_mle(&mut self, mle: Arc<MultiLinearPoly<F>>, coefficient: F) {
+        let index = self.mle_index(mle);
+        self.products.iter_mut().for_each(|(coef, indices)| {
+            *coef *= coefficient;
+            indices.push(index);
+        });
+    }
+
+    /// Evaluate the polynomial at a point, which is big endian as for `SumOfProductsPoly`
+    pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_vars);
+        let evals = self
+            .flattened_ml_extensions
+            .iter()
+            .map(|mle| mle.eval_reverse_order(point))
+            .collect::<Vec<_>>();
+        self.products
+            .iter()
+            .map(|(coef, indices)| indices.iter().fold(*coef, |acc, &i| acc * evals[i]))
+            .sum()
+    }
+
+    /// The sum of the polynomial over the boolean hypercube
+    pub fn sum(&self) -> F {
+        self.products
+            .iter()
+            .map(|(coef, indices)| {
+                let prod_sum = (0..1 << self.num_vars)
+                    .map(|b| {
+                        indices
+                            .iter()
+                            .fold(F::one(), |acc, &i| acc * self.flattened_ml_extensions[i][b])
+                    })
+                    .sum::<F>();
+                *coef * prod_sum
+            })
+            .sum()
+    }
+
+    #[inline]
+    fn mle_index(&mut self, mle: Arc<MultiLinearPoly<F>>) -> usize {
+        assert_eq!(mle.get_num_vars(), self.num_vars);
+        let key = Arc::as_ptr(&mle) as usize;
+        *self
+            .raw_pointers_lookup_table
+            .entry(key)
+            .or_insert_with(|| {
+                self.flattened_ml_extensions.push(mle);
+                self.flattened_ml_extensions.len() - 1
+            })
+    }
+}
+
+impl<F: SimdField> VirtualPolynomial<F> {
+    /// The number of variables of the polynomial, including the variables of the SIMD lanes
+    #[inline]
+    pub fn unpacked_num_vars(&self) -> usize {
+        self.num_vars + F::PACK_SIZE.ilog2() as usize
+    }
+
+    /// Unpack the polynomial over the scalar field: the lane `i` of the evaluation at `b` is the
+    /// evaluation at `b * F::PACK_SIZE + i`.
+    /// The coefficients are to be the same in every lane.
+    pub fn unpack(&self) -> VirtualPolynomial<F::Scalar> {
+        assert!(F::PACK_SIZE.is_power_of_two());
+        let mut poly = VirtualPolynomial::new(self.unpacked_num_vars());
+        self.flattened_ml_extensions.iter().for_each(|mle| {
+            let evals = mle.coeffs.iter().flat_map(|eval| eval.unpack()).collect();
+            poly.mle_index(Arc::new(MultiLinearPoly::new(evals)));
+        });
+        poly.products = self
+            .products
+            .iter()
+            .map(|(coef, indices)| {
+                let lanes = coef.unpack();
+                assert!(
+                    lanes.iter().all(|lane| *lane == lanes[0]),
+                    "the coefficient differs between the lanes"
+                );
+                (lanes[0], indices.clone())
+            })
+            .collect();
+        poly
+    }
+}
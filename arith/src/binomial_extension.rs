@@ -0,0 +1,844 @@
+//! Binomial extensions F[x] / (x^D - W) of a field F, and their packed counterparts over a
+//! SIMD field, declared from the non-residue W, e.g.,
+//!
+//! ```ignore
+//! #[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+//! pub struct KoalaBearExt4Parameters;
+//!
+//! impl BinomialExtensionParameters<KoalaBear, 4> for KoalaBearExt4Parameters {
+//!     const NAME: &'static str = "Koala Bear Extension 4";
+//!     const PACKED_NAME: &'static str = "Vectorized Koala Bear Extension 4";
+//!     const W: u32 = 3;
+//! }
+//!
+//! pub type KoalaBearExt4 = BinomialExtension<KoalaBear, KoalaBearExt4Parameters, 4>;
+//! pub type KoalaBearExt4x16 =
+//!     PackedBinomialExtension<KoalaBearx16, KoalaBearExt4Parameters, 4>;
+//! ```
+//!
+//! The operations with the packed base field come with arith for the Monty31 fields, and are
+//! declared with `packed_binomial_extension_base_ops` for the other fields.
+//!
+//! Both serialize as their D limbs, from the lowest degree to the highest.
+
+#![allow(clippy::needless_range_loop)]
+
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    io::{Read, Write},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use ethnum::U256;
+use rand::RngCore;
+use serdes::{ExpSerde, SerdeResult};
+
+use crate::{ExtensionField, FFTField, Field, SimdField};
+
+/// The parameters of the extension F[x] / (x^D - W), where x^D - W is irreducible over F.
+/// In particular D divides p - 1, which the inversion relies on.
+pub trait BinomialExtensionParameters<F: Field, const D: usize>:
+    'static + Debug + Clone + Copy + Default + Hash + PartialEq + Eq + Send + Sync
+{
+    /// Name of the extension field
+    const NAME: &'static str;
+
+    /// Name of the packed extension field
+    const PACKED_NAME: &'static str;
+
+    /// The non-residue W
+    const W: u32;
+
+    /// Multiply an element of F, or of a packed F, by W.
+    /// Override with an addition chain for small W, e.g., `Field::mul_by_3`.
+    #[inline(always)]
+    fn mul_by_w<G: Field>(x: &G) -> G {
+        *x * G::from(Self::W)
+    }
+
+    /// Sample the limbs of an element from uniform bytes, see `Field::from_uniform_bytes`
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> [F; D] {
+        assert!(bytes.len() >= F::SIZE * D);
+        std::array::from_fn(|i| F::from_uniform_bytes(&bytes[i * F::SIZE..]))
+    }
+}
+
+/// An element of F[x] / (x^D - W)
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BinomialExtension<F: Field, P, const D: usize> {
+    pub v: [F; D],
+    _phantom: PhantomData<P>,
+}
+
+/// An element of PF[x] / (x^D - W) for a packed field PF, i.e., PF::PACK_SIZE elements of
+/// the extension of F = PF::Scalar.
+/// F is a parameter of its own for the coherence of `Mul<F>` and `Add<F>` with the other
+/// operations, which do not hold for the projection PF::Scalar.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct PackedBinomialExtension<PF, P, const D: usize, F = <PF as SimdField>::Scalar>
+where
+    PF: SimdField<Scalar = F>,
+    F: Field,
+{
+    pub v: [PF; D],
+    _phantom: PhantomData<(P, F)>,
+}
+
+// ========================
+// arithmetic over the limbs
+// ========================
+
+/// The product modulo x^D - W, with Karatsuba multiplication for D = 2, 3, 4
+#[inline(always)]
+fn mul_internal<F, G, P, const D: usize>(a: &[G; D], b: &[G; D]) -> [G; D]
+where
+    F: Field,
+    G: Field,
+    P: BinomialExtensionParameters<F, D>,
+{
+    let mut res = [G::ZERO; D];
+    match D {
+        2 => {
+            let (c0, c1, c2) = karatsuba_2(a[0], a[1], b[0], b[1]);
+            res[0] = c0 + P::mul_by_w(&c2);
+            res[1] = c1;
+        }
+        3 => {
+            let v0 = a[0] * b[0];
+            let v1 = a[1] * b[1];
+            let v2 = a[2] * b[2];
+            res[0] = v0 + P::mul_by_w(&((a[1] + a[2]) * (b[1] + b[2]) - v1 - v2));
+            res[1] = (a[0] + a[1]) * (b[0] + b[1]) - v0 - v1 + P::mul_by_w(&v2);
+            res[2] = (a[0] + a[2]) * (b[0] + b[2]) - v0 - v2 + v1;
+        }
+        4 => {
+            // (a0 + a1 x) + (a2 + a3 x) x^2, one level of Karatsuba over x^2 and one over x
+            let p0 = karatsuba_2(a[0], a[1], b[0], b[1]);
+            let p2 = karatsuba_2(a[2], a[3], b[2], b[3]);
+            let p1 = karatsuba_2(a[0] + a[2], a[1] + a[3], b[0] + b[2], b[1] + b[3]);
+            let p1 = (p1.0 - p0.0 - p2.0, p1.1 - p0.1 - p2.1, p1.2 - p0.2 - p2.2);
+            res[0] = p0.0 + P::mul_by_w(&(p2.0 + p1.2));
+            res[1] = p0.1 + P::mul_by_w(&p2.1);
+            res[2] = p0.2 + p1.0 + P::mul_by_w(&p2.2);
+            res[3] = p1.1;
+        }
+        _ => {
+            for i in 0..D {
+                for j in 0..D {
+                    if i + j < D {
+                        res[i + j] += a[i] * b[j];
+                    } else {
+                        res[i + j - D] += P::mul_by_w(&(a[i] * b[j]));
+                    }
+                }
+            }
+        }
+    }
+    res
+}
+
+/// (a0 + a1 x) * (b0 + b1 x) with three multiplications
+#[inline(always)]
+fn karatsuba_2<G: Field>(a0: G, a1: G, b0: G, b1: G) -> (G, G, G) {
+    let c0 = a0 * b0;
+    let c2 = a1 * b1;
+    let c1 = (a0 + a1) * (b0 + b1) - c0 - c2;
+    (c0, c1, c2)
+}
+
+/// Multiply by x, i.e., rotate the limbs and reduce x^D to W
+#[inline(always)]
+fn mul_by_x_internal<F, G, P, const D: usize>(a: &[G; D]) -> [G; D]
+where
+    F: Field,
+    G: Field,
+    P: BinomialExtensionParameters<F, D>,
+{
+    std::array::from_fn(|i| {
+        if i == 0 {
+            P::mul_by_w(&a[D - 1])
+        } else {
+            a[i - 1]
+        }
+    })
+}
+
+// ========================
+// common operations
+// ========================
+
+macro_rules! binomial_field_common {
+    ([$($generics:tt)*], $field:ty, $base:ident) => {
+        impl<$($generics)*>
+            $field
+        {
+            #[inline(always)]
+            pub const fn new(v: [$base; D]) -> Self {
+                Self {
+                    v,
+                    _phantom: PhantomData,
+                }
+            }
+
+            /// The element of the base field, panics if the element is not in the base field
+            #[inline(always)]
+            pub fn to_base_field(&self) -> $base {
+                assert!(
+                    self.v[1..].iter().all(|x| x.is_zero()),
+                    "the element is not in the base field"
+                );
+                self.to_base_field_unsafe()
+            }
+
+            #[inline(always)]
+            pub fn to_base_field_unsafe(&self) -> $base {
+                self.v[0]
+            }
+
+            #[inline(always)]
+            fn constant(x: $base) -> Self {
+                let mut v = [$base::ZERO; D];
+                v[0] = x;
+                Self::new(v)
+            }
+        }
+
+        impl<$($generics)*>
+            Default for $field
+        {
+            #[inline(always)]
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl<$($generics)*>
+            ExpSerde for $field
+        {
+            #[inline(always)]
+            fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+                for limb in &self.v {
+                    limb.serialize_into(&mut writer)?;
+                }
+                Ok(())
+            }
+
+            #[inline(always)]
+            fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+                let mut v = [$base::ZERO; D];
+                for i in 0..D {
+                    v[i] = $base::deserialize_from(&mut reader)?;
+                }
+                Ok(Self::new(v))
+            }
+        }
+
+        impl<$($generics)*> Ord
+            for $field
+        {
+            #[inline(always)]
+            fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+                unimplemented!("Ord for {} is not supported", Self::NAME)
+            }
+        }
+
+        #[allow(clippy::non_canonical_partial_ord_impl)]
+        impl<$($generics)*>
+            PartialOrd for $field
+        {
+            #[inline(always)]
+            fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+                unimplemented!("PartialOrd for {} is not supported", Self::NAME)
+            }
+        }
+
+        impl<$($generics)*>
+            From<u32> for $field
+        {
+            #[inline(always)]
+            fn from(x: u32) -> Self {
+                Self::constant($base::from(x))
+            }
+        }
+
+        impl<$($generics)*>
+            From<u64> for $field
+        {
+            #[inline(always)]
+            fn from(x: u64) -> Self {
+                Self::constant($base::from(x))
+            }
+        }
+
+        impl<$($generics)*>
+            From<$base> for $field
+        {
+            #[inline(always)]
+            fn from(x: $base) -> Self {
+                Self::constant(x)
+            }
+        }
+
+        impl<$($generics)*>
+            Neg for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn neg(self) -> Self {
+                Self::new(self.v.map(|x| -x))
+            }
+        }
+
+        impl<$($generics)*>
+            Add for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self {
+                Self::new(std::array::from_fn(|i| self.v[i] + rhs.v[i]))
+            }
+        }
+
+        impl<$($generics)*>
+            Add<&Self> for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn add(self, rhs: &Self) -> Self {
+                self + *rhs
+            }
+        }
+
+        impl<$($generics)*>
+            AddAssign for $field
+        {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl<$($generics)*>
+            AddAssign<&Self> for $field
+        {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: &Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl<$($generics)*>
+            Sub for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self {
+                Self::new(std::array::from_fn(|i| self.v[i] - rhs.v[i]))
+            }
+        }
+
+        impl<$($generics)*>
+            Sub<&Self> for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn sub(self, rhs: &Self) -> Self {
+                self - *rhs
+            }
+        }
+
+        impl<$($generics)*>
+            SubAssign for $field
+        {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl<$($generics)*>
+            SubAssign<&Self> for $field
+        {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: &Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl<$($generics)*>
+            Mul for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                Self::new(mul_internal::<F, $base, P, D>(&self.v, &rhs.v))
+            }
+        }
+
+        impl<$($generics)*>
+            Mul<&Self> for $field
+        {
+            type Output = Self;
+
+            #[inline(always)]
+            fn mul(self, rhs: &Self) -> Self {
+                self * *rhs
+            }
+        }
+
+        impl<$($generics)*>
+            MulAssign for $field
+        {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs
+            }
+        }
+
+        impl<$($generics)*>
+            MulAssign<&Self> for $field
+        {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: &Self) {
+                *self = *self * rhs
+            }
+        }
+
+        impl<T, $($generics)*>
+            Sum<T> for $field
+        where
+            T: core::borrow::Borrow<Self>,
+        {
+            fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(Self::ZERO, |acc, item| acc + item.borrow())
+            }
+        }
+
+        impl<T, $($generics)*>
+            Product<T> for $field
+        where
+            T: core::borrow::Borrow<Self>,
+        {
+            fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+                iter.fold(Self::ONE, |acc, item| acc * item.borrow())
+            }
+        }
+    };
+}
+
+binomial_field_common!(
+    [F: Field, P: BinomialExtensionParameters<F, D>, const D: usize],
+    BinomialExtension<F, P, D>,
+    F
+);
+binomial_field_common!(
+    [F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize],
+    PackedBinomialExtension<PF, P, D, F>,
+    PF
+);
+
+// ========================
+// the extension field
+// ========================
+
+impl<F: Field, P: BinomialExtensionParameters<F, D>, const D: usize> BinomialExtension<F, P, D> {
+    /// The k-th power of the Frobenius map a -> a^p, where x^p = W^((p - 1) / D) x
+    #[inline(always)]
+    fn frobenius(&self, k: usize, gamma_powers: &[F; D]) -> Self {
+        Self::new(std::array::from_fn(|i| self.v[i] * gamma_powers[i * k % D]))
+    }
+}
+
+impl<F: Field, P: BinomialExtensionParameters<F, D>, const D: usize> Field
+    for BinomialExtension<F, P, D>
+{
+    const NAME: &'static str = P::NAME;
+
+    const SIZE: usize = F::SIZE * D;
+
+    const FIELD_SIZE: usize = F::FIELD_SIZE * D;
+
+    const ZERO: Self = Self::new([F::ZERO; D]);
+
+    const ONE: Self = Self::new(unit_limbs(F::ZERO, F::ONE, 0));
+
+    const INV_2: Self = Self::new(unit_limbs(F::ZERO, F::INV_2, 0));
+
+    const MODULUS: U256 = F::MODULUS;
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        Self::new(std::array::from_fn(|_| F::random_unsafe(&mut rng)))
+    }
+
+    #[inline(always)]
+    fn random_bool(rng: impl RngCore) -> Self {
+        Self::constant(F::random_bool(rng))
+    }
+
+    /// a^-1 = N(a)^-1 * a^p * a^(p^2) * ... * a^(p^(D-1)), where the norm
+    /// N(a) = a * a^p * ... * a^(p^(D-1)) lies in the base field
+    fn inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let exponent = (F::MODULUS - U256::ONE) / U256::from(D as u64);
+        let gamma = F::from(P::W).exp(exponent.as_u128());
+        let mut gamma_powers = [F::ONE; D];
+        for i in 1..D {
+            gamma_powers[i] = gamma_powers[i - 1] * gamma;
+        }
+
+        let conjugates: Self = (1..D).map(|k| self.frobenius(k, &gamma_powers)).product();
+        let norm = (*self * conjugates).v[0];
+        Some(conjugates.mul_by_base_field(&norm.inv()?))
+    }
+
+    #[inline(always)]
+    fn as_u32_unchecked(&self) -> u32 {
+        self.v[0].as_u32_unchecked()
+    }
+
+    #[inline(always)]
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::new(P::from_uniform_bytes(bytes))
+    }
+}
+
+/// The limbs of the constant `one * x^index`, in a const context
+const fn unit_limbs<F: Copy, const D: usize>(zero: F, one: F, index: usize) -> [F; D] {
+    let mut v = [zero; D];
+    v[index] = one;
+    v
+}
+
+impl<F: Field, P: BinomialExtensionParameters<F, D>, const D: usize> ExtensionField
+    for BinomialExtension<F, P, D>
+{
+    const DEGREE: usize = D;
+
+    const W: u32 = P::W;
+
+    const X: Self = Self::new(unit_limbs(F::ZERO, F::ONE, 1));
+
+    type BaseField = F;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &F) -> Self {
+        Self::new(self.v.map(|x| x * base))
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &F) -> Self {
+        let mut v = self.v;
+        v[0] += base;
+        Self::new(v)
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self::new(mul_by_x_internal::<F, F, P, D>(&self.v))
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<F> {
+        self.v.to_vec()
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[F]) -> Self {
+        let mut v = [F::ZERO; D];
+        let len = limbs.len().min(D);
+        v[..len].copy_from_slice(&limbs[..len]);
+        Self::new(v)
+    }
+}
+
+impl<F: FFTField, P: BinomialExtensionParameters<F, D>, const D: usize> FFTField
+    for BinomialExtension<F, P, D>
+{
+    const TWO_ADICITY: usize = F::TWO_ADICITY;
+
+    #[inline(always)]
+    fn root_of_unity() -> Self {
+        Self::from(F::root_of_unity())
+    }
+}
+
+impl<F: Field, P: BinomialExtensionParameters<F, D>, const D: usize> Mul<F>
+    for BinomialExtension<F, P, D>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: F) -> Self {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl<F: Field, P: BinomialExtensionParameters<F, D>, const D: usize> Add<F>
+    for BinomialExtension<F, P, D>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: F) -> Self {
+        self.add_by_base_field(&rhs)
+    }
+}
+
+// ========================
+// the packed extension field
+// ========================
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    Field for PackedBinomialExtension<PF, P, D, F>
+{
+    const NAME: &'static str = P::PACKED_NAME;
+
+    const SIZE: usize = PF::SIZE * D;
+
+    const FIELD_SIZE: usize = PF::FIELD_SIZE * D;
+
+    const ZERO: Self = Self::new([PF::ZERO; D]);
+
+    const ONE: Self = Self::new(unit_limbs(PF::ZERO, PF::ONE, 0));
+
+    const INV_2: Self = Self::new(unit_limbs(PF::ZERO, PF::INV_2, 0));
+
+    const MODULUS: U256 = PF::MODULUS;
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.v.iter().all(|x| x.is_zero())
+    }
+
+    #[inline(always)]
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        Self::new(std::array::from_fn(|_| PF::random_unsafe(&mut rng)))
+    }
+
+    #[inline(always)]
+    fn random_bool(rng: impl RngCore) -> Self {
+        Self::constant(PF::random_bool(rng))
+    }
+
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        let mut scalars = self.unpack();
+        if scalars.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        scalars.iter_mut().for_each(|x| *x = x.inv().unwrap()); // safe unwrap
+        Some(Self::pack(&scalars))
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    fn from_uniform_bytes(_bytes: &[u8]) -> Self {
+        unimplemented!("vec extension field: cannot convert from uniform bytes")
+    }
+}
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    SimdField for PackedBinomialExtension<PF, P, D, F>
+{
+    type Scalar = BinomialExtension<F, P, D>;
+
+    const PACK_SIZE: usize = PF::PACK_SIZE;
+
+    #[inline(always)]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_full(base: &Self::Scalar) -> Self {
+        Self::new(base.v.map(|x| PF::pack_full(&x)))
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        assert_eq!(base_vec.len(), Self::PACK_SIZE);
+        Self::new(std::array::from_fn(|i| {
+            let limbs: Vec<F> = base_vec.iter().map(|x| x.v[i]).collect();
+            PF::pack(&limbs)
+        }))
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        let limbs = self.v.map(|x| x.unpack());
+        (0..Self::PACK_SIZE)
+            .map(|i| Self::Scalar::new(std::array::from_fn(|j| limbs[j][i])))
+            .collect()
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(&self) -> Self::Scalar {
+        Self::Scalar::new(self.v.map(|x| x.horizontal_sum()))
+    }
+}
+
+/// The base field of the packed extension is the packed base field, whose multiplication and
+/// addition, `Mul<PF>` and `Add<PF>`, are implemented generically for the Monty31 fields and
+/// for each other field by `packed_binomial_extension_base_ops`
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    ExtensionField for PackedBinomialExtension<PF, P, D, F>
+where
+    Self: Mul<PF, Output = Self>,
+{
+    const DEGREE: usize = D;
+
+    const W: u32 = P::W;
+
+    const X: Self = Self::new(unit_limbs(PF::ZERO, PF::ONE, 1));
+
+    type BaseField = PF;
+
+    #[inline(always)]
+    fn mul_by_base_field(&self, base: &PF) -> Self {
+        Self::new(self.v.map(|x| x * base))
+    }
+
+    #[inline(always)]
+    fn add_by_base_field(&self, base: &PF) -> Self {
+        let mut v = self.v;
+        v[0] += base;
+        Self::new(v)
+    }
+
+    #[inline(always)]
+    fn mul_by_x(&self) -> Self {
+        Self::new(mul_by_x_internal::<F, PF, P, D>(&self.v))
+    }
+
+    #[inline(always)]
+    fn to_limbs(&self) -> Vec<PF> {
+        self.v.to_vec()
+    }
+
+    #[inline(always)]
+    fn from_limbs(limbs: &[PF]) -> Self {
+        let mut v = [PF::ZERO; D];
+        let len = limbs.len().min(D);
+        v[..len].copy_from_slice(&limbs[..len]);
+        Self::new(v)
+    }
+}
+
+impl<
+        F: Field,
+        PF: SimdField<Scalar = F> + FFTField,
+        P: BinomialExtensionParameters<F, D>,
+        const D: usize,
+    > FFTField for PackedBinomialExtension<PF, P, D, F>
+where
+    Self: Mul<PF, Output = Self>,
+{
+    const TWO_ADICITY: usize = PF::TWO_ADICITY;
+
+    #[inline(always)]
+    fn root_of_unity() -> Self {
+        Self::from(PF::root_of_unity())
+    }
+}
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    From<BinomialExtension<F, P, D>> for PackedBinomialExtension<PF, P, D, F>
+{
+    #[inline(always)]
+    fn from(x: BinomialExtension<F, P, D>) -> Self {
+        Self::pack_full(&x)
+    }
+}
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    Mul<BinomialExtension<F, P, D>> for PackedBinomialExtension<PF, P, D, F>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: BinomialExtension<F, P, D>) -> Self {
+        self * Self::pack_full(&rhs)
+    }
+}
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    Mul<F> for PackedBinomialExtension<PF, P, D, F>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: F) -> Self {
+        Self::new(self.v.map(|x| x.scale(&rhs)))
+    }
+}
+
+impl<F: Field, PF: SimdField<Scalar = F>, P: BinomialExtensionParameters<F, D>, const D: usize>
+    Add<F> for PackedBinomialExtension<PF, P, D, F>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: F) -> Self {
+        let mut v = self.v;
+        v[0] += PF::from(rhs);
+        Self::new(v)
+    }
+}
+
+/// The operations between a binomial extension, its packed counterpart, and the packed base
+/// field `$packed_base`, which the coherence rules keep from being generic over the packed
+/// base field: `Mul<$packed_base>` and `Add<$packed_base>` for the packed extension, and
+/// `Mul<$packed_base>` for the extension, into the packed extension.
+/// To be invoked in the crate of `$packed_base`.
+#[macro_export]
+macro_rules! packed_binomial_extension_base_ops {
+    ($ext:ty, $packed_ext:ty, $packed_base:ty) => {
+        impl core::ops::Mul<$packed_base> for $packed_ext {
+            type Output = $packed_ext;
+
+            #[inline(always)]
+            fn mul(self, rhs: $packed_base) -> $packed_ext {
+                <$packed_ext as $crate::ExtensionField>::mul_by_base_field(&self, &rhs)
+            }
+        }
+
+        impl core::ops::Add<$packed_base> for $packed_ext {
+            type Output = $packed_ext;
+
+            #[inline(always)]
+            fn add(self, rhs: $packed_base) -> $packed_ext {
+                <$packed_ext as $crate::ExtensionField>::add_by_base_field(&self, &rhs)
+            }
+        }
+
+        impl core::ops::Mul<$packed_base> for $ext {
+            type Output = $packed_ext;
+
+            #[inline(always)]
+            fn mul(self, rhs: $packed_base) -> $packed_ext {
+                <$packed_ext>::from(self) * rhs
+            }
+        }
+    };
+}
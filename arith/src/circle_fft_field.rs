@@ -0,0 +1,178 @@
+use std::ops::{Add, Mul, Neg};
+
+use crate::Field;
+
+/// A point on the circle x^2 + y^2 = 1 over F.
+/// The points form a group, with (x0, y0) + (x1, y1) = (x0 x1 - y0 y1, x0 y1 + y0 x1)
+/// and the identity (1, 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CirclePoint<F: Field> {
+    pub x: F,
+    pub y: F,
+}
+
+impl<F: Field> CirclePoint<F> {
+    #[inline(always)]
+    pub fn identity() -> Self {
+        Self {
+            x: F::ONE,
+            y: F::ZERO,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_on_circle(&self) -> bool {
+        self.x.square() + self.y.square() == F::ONE
+    }
+
+    #[inline(always)]
+    pub fn double(&self) -> Self {
+        // (x, y) + (x, y) = (2x^2 - 1, 2xy)
+        Self {
+            x: self.x.square().double() - F::ONE,
+            y: (self.x * self.y).double(),
+        }
+    }
+
+    /// Add the point to itself `scalar` times
+    #[inline]
+    pub fn scalar_mul(&self, mut scalar: u128) -> Self {
+        let mut res = Self::identity();
+        let mut base = *self;
+        while scalar > 0 {
+            if scalar & 1 == 1 {
+                res = res + base;
+            }
+            base = base.double();
+            scalar >>= 1;
+        }
+        res
+    }
+}
+
+impl<F: Field> Add for CirclePoint<F> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x * rhs.x - self.y * rhs.y,
+            y: self.x * rhs.y + self.y * rhs.x,
+        }
+    }
+}
+
+impl<F: Field> Neg for CirclePoint<F> {
+    type Output = Self;
+
+    /// The inverse of a point is its conjugate (x, -y)
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+        }
+    }
+}
+
+/// Fields over which the circle group has a large subgroup of order a power of two, e.g.,
+/// Mersenne 31 with p + 1 = 2^31, while its multiplicative group has no large two-adic subgroup.
+/// The circle FFT of Circle STARKs (https://eprint.iacr.org/2024/278) interpolates over the
+/// canonic coset of size 2^n of the circle group, i.e., the odd multiples of a point of order
+/// 2^(n + 1).
+///
+/// Evaluations are ordered as the points of `circle_domain`.
+/// Coefficients are in the basis y^j0 x^j1 π(x)^j2 ... π^(n - 2)(x)^j(n-1) with π(x) = 2x^2 - 1,
+/// indexed by j = j0 + 2 j1 + ... + 2^(n - 1) j(n-1),
+/// so that padding the coefficients with zeros extends the evaluations to a larger domain.
+pub trait CircleFFTField:
+    Field + From<Self::CircleField> + Mul<Self::CircleField, Output = Self>
+{
+    /// Field of the coordinates of the circle group, i.e., of the twiddle factors
+    type CircleField: Field;
+
+    /// log2 of the order of `circle_generator`
+    const CIRCLE_TWO_ADICITY: usize;
+
+    /// A generator of the circle subgroup of order 2^CIRCLE_TWO_ADICITY
+    fn circle_generator() -> CirclePoint<Self::CircleField>;
+
+    /// Forward transform, coefficients into evaluations over the domain of the same size
+    fn circle_fft_in_place(coeffs: &mut [Self]);
+
+    /// Inverse transform, evaluations over the domain into coefficients
+    fn circle_ifft_in_place(evals: &mut [Self]);
+
+    /// A generator of the circle subgroup of order 2^bits
+    #[inline]
+    fn circle_subgroup_generator(bits: usize) -> CirclePoint<Self::CircleField> {
+        assert!(bits <= Self::CIRCLE_TWO_ADICITY);
+        let mut g = Self::circle_generator();
+        for _ in bits..Self::CIRCLE_TWO_ADICITY {
+            g = g.double();
+        }
+        g
+    }
+
+    /// The canonic domain of size 2^log_size, in the order of the evaluations:
+    /// the half coset (4i + 1) q for i < 2^(log_size - 1), with q of order 2^(log_size + 1),
+    /// followed by the conjugates of these points in the same order.
+    fn circle_domain(log_size: usize) -> Vec<CirclePoint<Self::CircleField>> {
+        let q = Self::circle_subgroup_generator(log_size + 1);
+        if log_size == 0 {
+            return vec![q];
+        }
+
+        let step = q.double().double();
+        let half_coset: Vec<_> = (0..1usize << (log_size - 1))
+            .scan(q, |p, _| {
+                let point = *p;
+                *p = *p + step;
+                Some(point)
+            })
+            .collect();
+        let conjugates: Vec<_> = half_coset.iter().map(|p| -*p).collect();
+        [half_coset, conjugates].concat()
+    }
+
+    #[inline(always)]
+    fn circle_fft(coeffs: &[Self]) -> Vec<Self> {
+        let mut evals = coeffs.to_vec();
+        Self::circle_fft_in_place(&mut evals);
+        evals
+    }
+
+    #[inline(always)]
+    fn circle_ifft(evals: &[Self]) -> Vec<Self> {
+        let mut coeffs = evals.to_vec();
+        Self::circle_ifft_in_place(&mut coeffs);
+        coeffs
+    }
+
+    /// Evaluate the polynomial with `coeffs` at any point of the circle over Self,
+    /// e.g., a point out of the domain when Self is an extension field
+    fn circle_evaluate(coeffs: &[Self], point: &CirclePoint<Self>) -> Self {
+        assert!(coeffs.len().is_power_of_two());
+        let log_size = coeffs.len().ilog2() as usize;
+
+        // fold the coefficients by the lowest bit of the index, the factors being y, x, π(x), ...
+        let mut factors = Vec::with_capacity(log_size);
+        if log_size > 0 {
+            factors.push(point.y);
+        }
+        let mut x = point.x;
+        for _ in 1..log_size {
+            factors.push(x);
+            x = x.square().double() - Self::ONE;
+        }
+
+        let mut folded = coeffs.to_vec();
+        for factor in factors {
+            folded = folded
+                .chunks(2)
+                .map(|pair| pair[0] + pair[1] * factor)
+                .collect();
+        }
+        folded[0]
+    }
+}
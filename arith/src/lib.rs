@@ -7,9 +7,15 @@ pub use field::*;
 mod fft_field;
 pub use fft_field::*;
 
+mod circle_fft_field;
+pub use circle_fft_field::*;
+
 mod extension_field;
 pub use extension_field::*;
 
+mod binomial_extension;
+pub use binomial_extension::*;
+
 mod bn254;
 pub use bn254::*;
 
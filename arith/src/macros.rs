@@ -2,7 +2,11 @@
 #[macro_export]
 macro_rules! field_common {
     ($field:ident) => {
-        impl Sub<&$field> for $field {
+        $crate::field_common!([], $field);
+    };
+    // for a generic field type, e.g. `field_common!([MP: FieldParameters], Packed<MP>)`
+    ([$($generics:tt)*], $field:ty) => {
+        impl<$($generics)*> Sub<&$field> for $field {
             type Output = $field;
 
             #[inline]
@@ -11,7 +15,7 @@ macro_rules! field_common {
             }
         }
 
-        impl Sub<$field> for $field {
+        impl<$($generics)*> Sub<$field> for $field {
             type Output = $field;
 
             #[inline]
@@ -20,14 +24,14 @@ macro_rules! field_common {
             }
         }
 
-        impl SubAssign for $field {
+        impl<$($generics)*> SubAssign for $field {
             #[inline]
             fn sub_assign(&mut self, rhs: $field) {
                 *self = (*self).sub(rhs)
             }
         }
 
-        impl SubAssign<&$field> for $field {
+        impl<$($generics)*> SubAssign<&$field> for $field {
             #[inline]
             fn sub_assign(&mut self, rhs: &$field) {
                 *self = (*self).sub(rhs)
@@ -38,7 +42,7 @@ macro_rules! field_common {
         // additions
         // ========================
 
-        impl Add<&$field> for $field {
+        impl<$($generics)*> Add<&$field> for $field {
             type Output = $field;
 
             #[inline]
@@ -47,7 +51,7 @@ macro_rules! field_common {
             }
         }
 
-        impl Add<$field> for $field {
+        impl<$($generics)*> Add<$field> for $field {
             type Output = $field;
 
             #[inline]
@@ -56,21 +60,21 @@ macro_rules! field_common {
             }
         }
 
-        impl AddAssign for $field {
+        impl<$($generics)*> AddAssign for $field {
             #[inline]
             fn add_assign(&mut self, rhs: $field) {
                 *self = (*self).add(rhs)
             }
         }
 
-        impl<'b> AddAssign<&'b $field> for $field {
+        impl<'b, $($generics)*> AddAssign<&'b $field> for $field {
             #[inline]
             fn add_assign(&mut self, rhs: &'b $field) {
                 *self = (*self).add(rhs)
             }
         }
 
-        impl<T> Sum<T> for $field
+        impl<T, $($generics)*> Sum<T> for $field
         where
             T: core::borrow::Borrow<Self>,
         {
@@ -85,7 +89,7 @@ macro_rules! field_common {
         // ========================
         // multiplications
         // ========================
-        impl Mul<$field> for $field {
+        impl<$($generics)*> Mul<$field> for $field {
             type Output = $field;
 
             #[inline]
@@ -94,7 +98,7 @@ macro_rules! field_common {
             }
         }
 
-        impl<'b> Mul<&'b $field> for $field {
+        impl<'b, $($generics)*> Mul<&'b $field> for $field {
             type Output = $field;
 
             #[inline]
@@ -103,7 +107,7 @@ macro_rules! field_common {
             }
         }
 
-        impl Mul<$field> for &$field {
+        impl<$($generics)*> Mul<$field> for &$field {
             type Output = $field;
 
             #[inline(always)]
@@ -112,7 +116,7 @@ macro_rules! field_common {
             }
         }
 
-        impl Mul<&$field> for &$field {
+        impl<$($generics)*> Mul<&$field> for &$field {
             type Output = $field;
 
             #[inline(always)]
@@ -121,21 +125,21 @@ macro_rules! field_common {
             }
         }
 
-        impl MulAssign for $field {
+        impl<$($generics)*> MulAssign for $field {
             #[inline]
             fn mul_assign(&mut self, rhs: $field) {
                 *self = self.clone().mul(rhs)
             }
         }
 
-        impl<'b> MulAssign<&'b $field> for $field {
+        impl<'b, $($generics)*> MulAssign<&'b $field> for $field {
             #[inline]
             fn mul_assign(&mut self, rhs: &'b $field) {
                 *self = self.clone().mul(rhs)
             }
         }
 
-        impl<T> Product<T> for $field
+        impl<T, $($generics)*> Product<T> for $field
         where
             T: core::borrow::Borrow<Self>,
         {
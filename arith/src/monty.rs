@@ -21,26 +21,56 @@ use utils::{from_monty, monty_reduce, to_monty};
 
 use crate::{FFTField, Field};
 
+mod packed;
+
 mod param;
 pub use param::*;
 
 mod utils;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 mod neon;
-#[cfg(target_arch = "aarch64")]
-pub use neon::PackedMontyParameters;
-
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
+pub use neon::{PackedMontyField31, PackedMontyParameters};
+
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 mod avx512;
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
-pub use avx512::PackedMontyParameters;
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
+pub use avx512::{PackedMontyField31, PackedMontyParameters};
 
 // Fallback, use avx2
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 mod avx256;
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
-pub use avx256::PackedMontyParameters;
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
+pub use avx256::{PackedMontyField31, PackedMontyParameters};
+
+// Without SIMD instructions, with the `portable` feature or on other architectures
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+mod portable;
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+pub use portable::{PackedMontyField31, PackedMontyParameters};
 
 #[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 #[repr(transparent)] // Packed field implementations rely on this!
@@ -347,11 +377,8 @@ impl<MP: FieldParameters> Field for MontyField31<MP> {
 
     const MODULUS: U256 = U256([MP::PRIME as u128, 0]);
 
-    // See test below
-    const INV_2: Self = Self {
-        value: 134217727,
-        _phantom: PhantomData,
-    };
+    // (p + 1) / 2 is the inverse of 2
+    const INV_2: Self = Self::new(MP::HALF_P_PLUS_1);
 
     #[inline(always)]
     fn zero() -> Self {
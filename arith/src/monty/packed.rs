@@ -0,0 +1,124 @@
+//! The operations of `PackedMontyField31` that do not depend on the SIMD backend.
+
+use std::ops::{Add, Mul};
+
+use super::{FieldParameters, MontyField31, PackedMontyField31};
+use crate::{
+    BinomialExtension, BinomialExtensionParameters, ExtensionField, FFTField, Field,
+    PackedBinomialExtension, SimdField,
+};
+
+impl<MP: FieldParameters> Mul<&MontyField31<MP>> for PackedMontyField31<MP> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: &MontyField31<MP>) -> Self::Output {
+        self * Self::pack_full(rhs)
+    }
+}
+
+impl<MP: FieldParameters> Mul<MontyField31<MP>> for PackedMontyField31<MP> {
+    type Output = Self;
+
+    #[inline(always)]
+    #[allow(clippy::op_ref)]
+    fn mul(self, rhs: MontyField31<MP>) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl<MP: FieldParameters> Add<MontyField31<MP>> for PackedMontyField31<MP> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: MontyField31<MP>) -> Self::Output {
+        self + Self::pack_full(&rhs)
+    }
+}
+
+impl<MP: FieldParameters> From<MontyField31<MP>> for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn from(x: MontyField31<MP>) -> Self {
+        Self::pack_full(&x)
+    }
+}
+
+impl<MP: FieldParameters> From<u32> for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn from(value: u32) -> Self {
+        Self::pack_full(&MontyField31::from(value))
+    }
+}
+
+impl<MP: FieldParameters> From<u64> for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn from(value: u64) -> Self {
+        Self::pack_full(&MontyField31::from(value))
+    }
+}
+
+impl<MP: FieldParameters> Default for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<MP: FieldParameters> Ord for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        unimplemented!("Ord for {} is not supported", Self::NAME)
+    }
+}
+
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl<MP: FieldParameters> PartialOrd for PackedMontyField31<MP> {
+    #[inline(always)]
+    fn partial_cmp(&self, _: &Self) -> Option<std::cmp::Ordering> {
+        unimplemented!("PartialOrd for {} is not supported", Self::NAME)
+    }
+}
+
+impl<MP: FieldParameters> FFTField for PackedMontyField31<MP> {
+    const TWO_ADICITY: usize = MP::TWO_ADICITY;
+
+    fn root_of_unity() -> Self {
+        Self::from(MP::TWO_ADICITY_GENERATOR_IN_U32)
+    }
+}
+
+// The base field operations of the binomial extensions of Monty31 fields, which other packed
+// base fields declare with `packed_binomial_extension_base_ops`
+
+impl<MP: FieldParameters, P: BinomialExtensionParameters<MontyField31<MP>, D>, const D: usize>
+    Mul<PackedMontyField31<MP>> for PackedBinomialExtension<PackedMontyField31<MP>, P, D>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: PackedMontyField31<MP>) -> Self {
+        self.mul_by_base_field(&rhs)
+    }
+}
+
+impl<MP: FieldParameters, P: BinomialExtensionParameters<MontyField31<MP>, D>, const D: usize>
+    Add<PackedMontyField31<MP>> for PackedBinomialExtension<PackedMontyField31<MP>, P, D>
+{
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: PackedMontyField31<MP>) -> Self {
+        self.add_by_base_field(&rhs)
+    }
+}
+
+impl<MP: FieldParameters, P: BinomialExtensionParameters<MontyField31<MP>, D>, const D: usize>
+    Mul<PackedMontyField31<MP>> for BinomialExtension<MontyField31<MP>, P, D>
+{
+    type Output = PackedBinomialExtension<PackedMontyField31<MP>, P, D>;
+
+    #[inline(always)]
+    fn mul(self, rhs: PackedMontyField31<MP>) -> Self::Output {
+        Self::Output::from(self) * rhs
+    }
+}
@@ -1,13 +1,27 @@
 use std::{fmt::Debug, hash::Hash};
 
-#[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    target_feature = "avx512f"
+))]
 use super::avx512::PackedMontyParameters;
 
-#[cfg(all(target_arch = "x86_64", not(target_feature = "avx512f")))]
+#[cfg(all(
+    not(feature = "portable"),
+    target_arch = "x86_64",
+    not(target_feature = "avx512f")
+))]
 use super::avx256::PackedMontyParameters;
 
-#[cfg(target_arch = "aarch64")]
+#[cfg(all(not(feature = "portable"), target_arch = "aarch64"))]
 use super::neon::PackedMontyParameters;
+
+#[cfg(any(
+    feature = "portable",
+    not(any(target_arch = "x86_64", target_arch = "aarch64"))
+))]
+use super::portable::PackedMontyParameters;
 use super::MontyField31;
 
 /// MontyParameters contains the prime P along with constants needed to convert elements into and
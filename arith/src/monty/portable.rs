@@ -0,0 +1,183 @@
+use std::{
+    io::{Read, Write},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use ethnum::U256;
+use rand::RngCore;
+use serdes::{ExpSerde, SerdeResult};
+
+use super::{FieldParameters, MontyField31, MontyParameters};
+use crate::{field_common, Field, SimdField};
+
+const PACK_SIZE: usize = 16;
+
+/// PackedMontyParameters contains constants needed for MONTY operations for packings of Monty31
+/// fields.
+pub trait PackedMontyParameters: MontyParameters {}
+
+/// PackedMontyField31 packs 16 elements of a Monty31 field and operates on them lane by lane,
+/// without any SIMD instructions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedMontyField31<MP: FieldParameters> {
+    pub v: [MontyField31<MP>; PACK_SIZE],
+}
+
+field_common!([MP: FieldParameters], PackedMontyField31<MP>);
+
+impl<MP: FieldParameters> ExpSerde for PackedMontyField31<MP> {
+    /// Same layout as the SIMD backends: the 16 elements in Montgomery form
+    #[inline(always)]
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        for x in self.v.iter() {
+            writer.write_all(&x.value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        let mut v = [MontyField31::ZERO; PACK_SIZE];
+        for x in v.iter_mut() {
+            let mut u = [0u8; 4];
+            reader.read_exact(&mut u)?;
+            *x = MontyField31::new_monty(u32::from_le_bytes(u) % MP::PRIME);
+        }
+        Ok(Self { v })
+    }
+}
+
+impl<MP: FieldParameters> Field for PackedMontyField31<MP> {
+    const NAME: &'static str = "Portable Packed Monty Field";
+
+    const SIZE: usize = 512 / 8;
+
+    const FIELD_SIZE: usize = 32;
+
+    const ZERO: Self = Self {
+        v: [MontyField31::ZERO; PACK_SIZE],
+    };
+
+    const ONE: Self = Self {
+        v: [MontyField31::ONE; PACK_SIZE],
+    };
+
+    const INV_2: Self = Self {
+        v: [MontyField31::INV_2; PACK_SIZE],
+    };
+
+    const MODULUS: U256 = U256([MP::PRIME as u128, 0]);
+
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    fn one() -> Self {
+        Self::ONE
+    }
+
+    fn random_unsafe(mut rng: impl RngCore) -> Self {
+        Self {
+            v: [(); PACK_SIZE].map(|_| MontyField31::random_unsafe(&mut rng)),
+        }
+    }
+
+    fn random_bool(mut rng: impl RngCore) -> Self {
+        Self {
+            v: [(); PACK_SIZE].map(|_| MontyField31::random_bool(&mut rng)),
+        }
+    }
+
+    fn inv(&self) -> Option<Self> {
+        // slow, should not be used in production
+        if self.v.iter().any(|x| x.is_zero()) {
+            return None;
+        }
+        Some(Self {
+            v: self.v.map(|x| x.inv().unwrap()),
+        })
+    }
+
+    fn as_u32_unchecked(&self) -> u32 {
+        unimplemented!("self is a vector, cannot convert to u32")
+    }
+
+    fn from_uniform_bytes(bytes: &[u8]) -> Self {
+        Self::pack_full(&MontyField31::from_uniform_bytes(bytes))
+    }
+}
+
+impl<MP: FieldParameters> SimdField for PackedMontyField31<MP> {
+    type Scalar = MontyField31<MP>;
+
+    const PACK_SIZE: usize = PACK_SIZE;
+
+    #[inline]
+    fn scale(&self, challenge: &Self::Scalar) -> Self {
+        *self * *challenge
+    }
+
+    #[inline(always)]
+    fn pack_full(x: &Self::Scalar) -> Self {
+        Self { v: [*x; PACK_SIZE] }
+    }
+
+    #[inline(always)]
+    fn pack(base_vec: &[Self::Scalar]) -> Self {
+        debug_assert!(base_vec.len() == PACK_SIZE);
+        Self {
+            v: base_vec.try_into().unwrap(),
+        }
+    }
+
+    #[inline(always)]
+    fn unpack(&self) -> Vec<Self::Scalar> {
+        self.v.to_vec()
+    }
+}
+
+impl<MP: FieldParameters> Neg for PackedMontyField31<MP> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        Self {
+            v: self.v.map(|x| -x),
+        }
+    }
+}
+
+#[inline(always)]
+fn add_internal<MP: FieldParameters>(
+    a: &PackedMontyField31<MP>,
+    b: &PackedMontyField31<MP>,
+) -> PackedMontyField31<MP> {
+    PackedMontyField31 {
+        v: std::array::from_fn(|i| a.v[i] + b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn sub_internal<MP: FieldParameters>(
+    a: &PackedMontyField31<MP>,
+    b: &PackedMontyField31<MP>,
+) -> PackedMontyField31<MP> {
+    PackedMontyField31 {
+        v: std::array::from_fn(|i| a.v[i] - b.v[i]),
+    }
+}
+
+#[inline(always)]
+fn mul_internal<MP: FieldParameters>(
+    a: &PackedMontyField31<MP>,
+    b: &PackedMontyField31<MP>,
+) -> PackedMontyField31<MP> {
+    PackedMontyField31 {
+        v: std::array::from_fn(|i| a.v[i] * b.v[i]),
+    }
+}
@@ -133,6 +133,38 @@ pub fn random_field_tests<F: Field>(type_name: String) {
     }
 }
 
+/// The generic extension `Generic` agrees with the hand-written `Handwritten` of the same
+/// modulus, on the same limbs
+pub fn random_binomial_extension_tests<Handwritten, Generic>(type_name: String)
+where
+    Handwritten: ExtensionField,
+    Generic: ExtensionField<BaseField = Handwritten::BaseField>,
+{
+    random_field_tests::<Generic>(type_name);
+
+    let mut rng = test_rng();
+    for _ in 0..1000 {
+        let a = Handwritten::random_unsafe(&mut rng);
+        let b = Handwritten::random_unsafe(&mut rng);
+        let a_generic = Generic::from_limbs(&a.to_limbs());
+        let b_generic = Generic::from_limbs(&b.to_limbs());
+
+        assert_eq!((a * b).to_limbs(), (a_generic * b_generic).to_limbs());
+        assert_eq!(a.square().to_limbs(), a_generic.square().to_limbs());
+        assert_eq!(a.mul_by_x().to_limbs(), a_generic.mul_by_x().to_limbs());
+        assert_eq!(
+            a.inv().unwrap().to_limbs(),
+            a_generic.inv().unwrap().to_limbs()
+        );
+
+        let mut buffer = vec![];
+        a.serialize_into(&mut buffer).unwrap();
+        let mut generic_buffer = vec![];
+        a_generic.serialize_into(&mut generic_buffer).unwrap();
+        assert_eq!(buffer, generic_buffer);
+    }
+}
+
 pub fn random_from_limbs_to_limbs_tests<F: Field, ExtF: ExtensionField<BaseField = F>>(
     type_name: String,
 ) {
@@ -191,6 +223,27 @@ pub fn random_simd_field_tests<F: SimdField>(_name: String) {
         let x_repacked = F::pack(&scalars);
         assert_eq!(x, x_repacked);
     }
+
+    {
+        // lane by lane against the scalar field, e.g., SIMD against portable backends
+        let a = F::random_unsafe(&mut rng);
+        let b = F::random_unsafe(&mut rng);
+        let (a_lanes, b_lanes) = (a.unpack(), b.unpack());
+        assert_eq!(a_lanes.len(), F::PACK_SIZE);
+
+        let lanes = |f: fn(F::Scalar, F::Scalar) -> F::Scalar| -> Vec<F::Scalar> {
+            a_lanes
+                .iter()
+                .zip(b_lanes.iter())
+                .map(|(x, y)| f(*x, *y))
+                .collect()
+        };
+        assert_eq!((a + b).unpack(), lanes(|x, y| x + y));
+        assert_eq!((a - b).unpack(), lanes(|x, y| x - y));
+        assert_eq!((a * b).unpack(), lanes(|x, y| x * y));
+        assert_eq!((-a).unpack(), lanes(|x, _| -x));
+        assert_eq!(a.horizontal_sum(), a_lanes.iter().sum::<F::Scalar>());
+    }
 }
 
 pub fn random_fft_field_tests<F: Field + FFTField>(_name: String) {
@@ -0,0 +1,155 @@
+//! Convert circuits and witnesses between the binary format and the text format.
+//!
+//! Text files are recognized by their header line, anything else is read as binary and
+//! written as text. The field is detected from the modulus stored in the input file.
+
+use std::fs;
+
+use arith::Field;
+use circuit::{
+    Circuit, LAYERED_CIRCUIT_HEADER, RECURSIVE_CIRCUIT_HEADER, RecursiveCircuit, TextFormat,
+    Witness, text_header_kind,
+};
+use clap::{Parser, ValueEnum};
+use ethnum::U256;
+use gkr_engine::{
+    BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, Goldilocksx1Config,
+    KoalaBearx16Config, M31x1Config,
+};
+use serdes::ExpSerde;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Kind {
+    /// A compiled circuit, as produced by the compiler
+    Circuit,
+    /// A flattened layered circuit
+    Layered,
+    /// A witness file
+    Witness,
+}
+
+/// ...
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Kind of the input file, required for binary inputs
+    #[arg(short, long, value_enum)]
+    kind: Option<Kind>,
+
+    /// Input file path
+    #[arg(short, long)]
+    input: String,
+
+    /// Output file path
+    #[arg(short, long)]
+    output: String,
+
+    /// Flatten a compiled circuit into a layered circuit before writing it
+    #[arg(long, default_value_t = false)]
+    flatten: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+    let bytes = fs::read(&args.input).expect("Unable to read input file.");
+
+    let text = std::str::from_utf8(&bytes).ok();
+    let (kind, modulus) = match text.and_then(text_header_kind) {
+        Some(header) => {
+            let kind = match header {
+                RECURSIVE_CIRCUIT_HEADER => Kind::Circuit,
+                LAYERED_CIRCUIT_HEADER => Kind::Layered,
+                _ => Kind::Witness,
+            };
+            (kind, modulus_from_text(text.unwrap()))
+        }
+        None => {
+            let kind = args
+                .kind
+                .expect("--kind is required for binary input files");
+            // the offset of the modulus in each binary format
+            let offset = match kind {
+                Kind::Circuit => 8,
+                Kind::Layered => 8,
+                Kind::Witness => 24,
+            };
+            let modulus_bytes: [u8; 32] = bytes
+                .get(offset..offset + 32)
+                .and_then(|b| b.try_into().ok())
+                .expect("Input file is too short.");
+            (kind, U256::from_le_bytes(modulus_bytes))
+        }
+    };
+    if let Some(expected) = args.kind {
+        assert_eq!(expected, kind, "Input file does not contain a {expected:?}");
+    }
+
+    if modulus == <M31x1Config as FieldEngine>::CircuitField::MODULUS {
+        convert::<M31x1Config>(&args, kind, &bytes, text.is_some())
+    } else if modulus == <BN254Config as FieldEngine>::CircuitField::MODULUS {
+        convert::<BN254Config>(&args, kind, &bytes, text.is_some())
+    } else if modulus == <GF2ExtConfig as FieldEngine>::CircuitField::MODULUS {
+        convert::<GF2ExtConfig>(&args, kind, &bytes, text.is_some())
+    } else if modulus == <Goldilocksx1Config as FieldEngine>::CircuitField::MODULUS {
+        convert::<Goldilocksx1Config>(&args, kind, &bytes, text.is_some())
+    } else if modulus == <BabyBearx16Config as FieldEngine>::CircuitField::MODULUS {
+        convert::<BabyBearx16Config>(&args, kind, &bytes, text.is_some())
+    } else if modulus == <KoalaBearx16Config as FieldEngine>::CircuitField::MODULUS {
+        convert::<KoalaBearx16Config>(&args, kind, &bytes, text.is_some())
+    } else {
+        panic!("Unsupported field modulus {modulus}");
+    }
+}
+
+fn modulus_from_text(text: &str) -> U256 {
+    text.lines()
+        .find_map(|line| line.trim().strip_prefix("modulus "))
+        .and_then(|modulus| U256::from_str_radix(modulus.trim(), 10).ok())
+        .expect("Text file has no valid modulus line.")
+}
+
+fn convert<C: FieldEngine>(args: &Args, kind: Kind, bytes: &[u8], from_text: bool) {
+    let input = std::str::from_utf8(bytes).unwrap_or_default();
+    let output = match kind {
+        Kind::Circuit => {
+            let rc = if from_text {
+                RecursiveCircuit::<C>::from_text(input).unwrap_or_else(|e| panic!("{e}"))
+            } else {
+                RecursiveCircuit::<C>::deserialize_from(bytes).expect("Unable to load circuit.")
+            };
+            if args.flatten {
+                write_output(&rc.flatten(), from_text)
+            } else {
+                write_output(&rc, from_text)
+            }
+        }
+        Kind::Layered => {
+            let circuit = if from_text {
+                Circuit::<C>::from_text(input).unwrap_or_else(|e| panic!("{e}"))
+            } else {
+                Circuit::<C>::deserialize_from(bytes).expect("Unable to load layered circuit.")
+            };
+            write_output(&circuit, from_text)
+        }
+        Kind::Witness => {
+            let witness = if from_text {
+                Witness::<C>::from_text(input).unwrap_or_else(|e| panic!("{e}"))
+            } else {
+                Witness::<C>::deserialize_from(bytes).expect("Unable to load witness.")
+            };
+            write_output(&witness, from_text)
+        }
+    };
+    fs::write(&args.output, output).expect("Unable to write output file.");
+}
+
+/// Text inputs are written as binary and binary inputs as text
+fn write_output<T: TextFormat + ExpSerde>(value: &T, to_binary: bool) -> Vec<u8> {
+    if to_binary {
+        let mut buffer = vec![];
+        value.serialize_into(&mut buffer).unwrap();
+        buffer
+    } else {
+        value.to_text().into_bytes()
+    }
+}
@@ -0,0 +1,144 @@
+//! Print per-layer statistics, lints and cost estimates of a compiled circuit.
+
+use std::str::FromStr;
+
+use bin::executor::detect_field_type_from_circuit_file;
+use circuit::RecursiveCircuit;
+use clap::Parser;
+use gkr::{
+    BN254ConfigMIMC5KZG, BN254ConfigMIMC5Raw, BN254ConfigSha2Hyrax, BN254ConfigSha2Raw,
+    GF2ExtConfigSha2Orion, GF2ExtConfigSha2Raw, Goldilocksx8ConfigSha2Orion,
+    Goldilocksx8ConfigSha2Raw, M31x16ConfigPoseidonRawVanilla, M31x16ConfigSha2OrionVanilla,
+    M31x16ConfigSha2RawVanilla, estimate_gkr_cost, measure_pcs_proof_bytes,
+};
+use gkr_engine::{FiatShamirHashType, FieldType, GKREngine, PolynomialCommitmentType};
+
+/// ...
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Circuit File Path
+    #[arg(short, long)]
+    circuit_file: String,
+
+    /// Fiat-Shamir Hash: SHA256, or Poseidon, or MiMC5
+    #[arg(short, long, default_value = "SHA256")]
+    fiat_shamir_hash: String,
+
+    /// Polynomial Commitment Scheme: Raw, Orion, Hyrax, or KZG
+    #[arg(short, long, default_value = "Raw")]
+    poly_commitment_scheme: String,
+
+    /// MPI size of the prover
+    #[arg(short, long, default_value_t = 1)]
+    mpi_size: usize,
+
+    /// Run the PCS once on a zero polynomial to measure commitment and opening sizes
+    #[arg(long, default_value_t = false)]
+    measure_pcs: bool,
+
+    /// Run the circuit optimizer before computing the estimates
+    #[arg(long, default_value_t = false)]
+    optimize: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+    assert!(args.mpi_size.is_power_of_two(), "MPI size must be a power of 2");
+
+    let fs_hash_type = FiatShamirHashType::from_str(&args.fiat_shamir_hash).unwrap();
+    let pcs_type = PolynomialCommitmentType::from_str(&args.poly_commitment_scheme).unwrap();
+    let field_type = detect_field_type_from_circuit_file(&args.circuit_file);
+
+    match (fs_hash_type.clone(), pcs_type.clone(), field_type.clone()) {
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::M31x16) => {
+            print_stats::<M31x16ConfigSha2RawVanilla>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::M31x16) => {
+            print_stats::<M31x16ConfigSha2OrionVanilla>(&args)
+        }
+        (FiatShamirHashType::Poseidon, PolynomialCommitmentType::Raw, FieldType::M31x16) => {
+            print_stats::<M31x16ConfigPoseidonRawVanilla>(&args)
+        }
+        (FiatShamirHashType::MIMC5, PolynomialCommitmentType::Raw, FieldType::BN254) => {
+            print_stats::<BN254ConfigMIMC5Raw>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::BN254) => {
+            print_stats::<BN254ConfigSha2Raw>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Hyrax, FieldType::BN254) => {
+            print_stats::<BN254ConfigSha2Hyrax>(&args)
+        }
+        (FiatShamirHashType::MIMC5, PolynomialCommitmentType::KZG, FieldType::BN254) => {
+            print_stats::<BN254ConfigMIMC5KZG>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::GF2Ext128) => {
+            print_stats::<GF2ExtConfigSha2Raw>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::GF2Ext128) => {
+            print_stats::<GF2ExtConfigSha2Orion>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::Goldilocksx8) => {
+            print_stats::<Goldilocksx8ConfigSha2Raw>(&args)
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::Goldilocksx8) => {
+            print_stats::<Goldilocksx8ConfigSha2Orion>(&args)
+        }
+        _ => panic!(
+            "FS: {fs_hash_type:?}, PCS: {pcs_type:?}, Field: {field_type:?} setting is not yet integrated in circuit-stats"
+        ),
+    }
+}
+
+fn print_stats<Cfg: GKREngine>(args: &Args) {
+    let rc = RecursiveCircuit::<Cfg::FieldConfig>::load(&args.circuit_file)
+        .expect("Unable to load circuit file.");
+    println!("Number of segments: {}", rc.segments.len());
+
+    let mut circuit = rc.flatten();
+    println!("===== flattened circuit =====");
+    println!("{}", circuit.stats());
+
+    let lints = circuit.lint();
+    println!("===== lints ({}) =====", lints.len());
+    lints.iter().for_each(|lint| println!("warning: {lint}"));
+
+    if args.optimize {
+        let report = circuit.optimize();
+        println!("===== optimized circuit =====");
+        println!(
+            "Layers: {} -> {}, gates: {} -> {}, total input size: {} -> {}",
+            report.layers_before,
+            report.layers_after,
+            report.gates_before,
+            report.gates_after,
+            report.input_size_before,
+            report.input_size_after
+        );
+        println!("{}", circuit.stats());
+    }
+
+    // the proven circuit may carry an extra input relay layer
+    circuit.pre_process_gkr();
+    let estimate = estimate_gkr_cost::<Cfg>(&circuit, args.mpi_size);
+    println!("===== estimates for mpi size {} =====", args.mpi_size);
+    println!("Sumcheck rounds:       {}", estimate.sumcheck_rounds());
+    println!("Public input bytes:    {}", estimate.public_input_bytes);
+    println!("Sumcheck bytes:        {}", estimate.sumcheck_bytes());
+    println!("Lookup bytes:          {}", estimate.lookup_bytes);
+    println!("IOP proof bytes:       {}", estimate.iop_proof_bytes());
+    println!("PCS openings:          {}", estimate.num_pcs_openings);
+    println!("PCS polynomial vars:   {}", estimate.pcs_num_vars);
+    println!("Prover field ops/rank: {}", estimate.prover_field_ops());
+
+    if args.measure_pcs {
+        let (commitment_bytes, opening_bytes) =
+            measure_pcs_proof_bytes::<Cfg>(circuit.log_input_size());
+        let total = estimate.iop_proof_bytes()
+            + commitment_bytes
+            + opening_bytes * estimate.num_pcs_openings;
+        println!("PCS commitment bytes:  {commitment_bytes}");
+        println!("PCS opening bytes:     {opening_bytes}");
+        println!("Total proof bytes:     {total} (single process PCS)");
+    }
+}
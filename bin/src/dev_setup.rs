@@ -12,13 +12,13 @@ use circuit::Circuit;
 use clap::Parser;
 use gkr::Verifier;
 use gkr::{
-    BN254ConfigSha2Raw, GF2ExtConfigSha2Raw, M31x16ConfigSha2RawVanilla, Prover,
     utils::{
-        KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS,
-        KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS, dev_env_data_setup,
+        dev_env_data_setup, KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT,
+        KECCAK_GF2_WITNESS, KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS,
     },
+    BN254ConfigSha2Raw, GF2ExtConfigSha2Raw, M31x16ConfigSha2RawVanilla, Prover,
 };
-use gkr_engine::{FieldEngine, FieldType, GKREngine, MPIConfig};
+use gkr_engine::{FieldEngine, FieldType, GKREngine, MPIConfig, MPIEngine};
 use poly_commit::expander_pcs_init_testing_only;
 use serdes::ExpSerde;
 
@@ -134,7 +134,7 @@ fn proof_gen<'a, C: GKREngine>(mpi_config: MPIConfig<'a>, generate_only: bool) {
 
     if !generate_only {
         // verify the proof (skip for generate_only mode as verification is done separately)
-        let verifier = Verifier::<C>::new(mpi_config.clone());
+        let verifier = Verifier::<C>::new(circuit.proof_shape(mpi_config.world_size()));
 
         let public_input = circuit.public_input.clone();
 
@@ -3,21 +3,37 @@ use std::str::FromStr;
 use bin::executor::*;
 use clap::Parser;
 use gkr::gkr_configs::*;
+#[cfg(feature = "mpi")]
+use gkr_engine::MPIConfig;
 use gkr_engine::{
-    FiatShamirHashType, FieldType, MPIConfig, MPIEngine, PolynomialCommitmentType, root_println,
+    FiatShamirHashType, FieldType, MPIEngine, PolynomialCommitmentType, TcpMPIConfig, root_println,
 };
 
 #[tokio::main]
 async fn main() {
     let expander_exec_args = ExpanderExecArgs::parse();
 
+    if expander_exec_args.tcp {
+        let mpi_config = TcpMPIConfig::from_env().expect("Unable to connect the TCP world.");
+        run(&expander_exec_args, &mpi_config).await;
+    } else {
+        #[cfg(feature = "mpi")]
+        {
+            let universe = MPIConfig::init().unwrap();
+            let world = universe.world();
+            let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
+            run(&expander_exec_args, &mpi_config).await;
+        }
+        #[cfg(not(feature = "mpi"))]
+        panic!("expander-exec is built without the `mpi` feature, run it with --tcp");
+    }
+}
+
+async fn run<M: MPIEngine + Clone>(expander_exec_args: &ExpanderExecArgs, mpi_config: &M) {
     let fs_hash_type = FiatShamirHashType::from_str(&expander_exec_args.fiat_shamir_hash).unwrap();
     let pcs_type =
         PolynomialCommitmentType::from_str(&expander_exec_args.poly_commitment_scheme).unwrap();
 
-    let universe = MPIConfig::init().unwrap();
-    let world = universe.world();
-    let mpi_config = MPIConfig::prover_new(Some(&universe), Some(&world));
     root_println!(mpi_config, "Fiat-Shamir Hash Type: {:?}", &fs_hash_type);
     root_println!(
         mpi_config,
@@ -33,38 +49,44 @@ async fn main() {
     };
 
     let field_type = detect_field_type_from_circuit_file(circuit_file);
-    root_println!(&mpi_config, "field type: {:?}", field_type);
+    root_println!(mpi_config, "field type: {:?}", field_type);
 
     match (fs_hash_type.clone(), pcs_type.clone(), field_type.clone()) {
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::M31x16) => {
-            run_command::<M31x16ConfigSha2OrionVanilla>(&expander_exec_args, &mpi_config).await;
+            run_command::<M31x16ConfigSha2OrionVanilla, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::Poseidon, PolynomialCommitmentType::Raw, FieldType::M31x16) => {
-            run_command::<M31x16ConfigPoseidonRawVanilla>(&expander_exec_args, &mpi_config).await;
+            run_command::<M31x16ConfigPoseidonRawVanilla, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::MIMC5, PolynomialCommitmentType::Raw, FieldType::BN254) => {
-            run_command::<BN254ConfigMIMC5Raw>(&expander_exec_args, &mpi_config).await;
+            run_command::<BN254ConfigMIMC5Raw, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::BN254) => {
-            run_command::<BN254ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
+            run_command::<BN254ConfigSha2Raw, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Hyrax, FieldType::BN254) => {
-            run_command::<BN254ConfigSha2Hyrax>(&expander_exec_args, &mpi_config).await;
+            run_command::<BN254ConfigSha2Hyrax, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::MIMC5, PolynomialCommitmentType::KZG, FieldType::BN254) => {
-            run_command::<BN254ConfigMIMC5KZG>(&expander_exec_args, &mpi_config).await;
+            run_command::<BN254ConfigMIMC5KZG, _>(expander_exec_args, mpi_config).await;
+        }
+        (FiatShamirHashType::Keccak256, PolynomialCommitmentType::KZG, FieldType::BN254) => {
+            run_command::<BN254ConfigKeccakKZG, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::GF2Ext128) => {
-            run_command::<GF2ExtConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
+            run_command::<GF2ExtConfigSha2Orion, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::GF2Ext128) => {
-            run_command::<GF2ExtConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
+            run_command::<GF2ExtConfigSha2Raw, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::Goldilocksx8) => {
-            run_command::<Goldilocksx8ConfigSha2Orion>(&expander_exec_args, &mpi_config).await;
+            run_command::<Goldilocksx8ConfigSha2Orion, _>(expander_exec_args, mpi_config).await;
         }
         (FiatShamirHashType::SHA256, PolynomialCommitmentType::Orion, FieldType::BabyBearx16) => {
-            run_command::<BabyBearx16ConfigSha2Raw>(&expander_exec_args, &mpi_config).await;
+            run_command::<BabyBearx16ConfigSha2Raw, _>(expander_exec_args, mpi_config).await;
+        }
+        (FiatShamirHashType::SHA256, PolynomialCommitmentType::Raw, FieldType::KoalaBearx16) => {
+            run_command::<KoalaBearx16ConfigSha2Raw, _>(expander_exec_args, mpi_config).await;
         }
         _ => panic!(
             "FS: {fs_hash_type:?}, PCS: {pcs_type:?}, Field: {field_type:?} setting is not yet integrated in expander-exec"
@@ -8,14 +8,16 @@ use std::{
 use arith::Field;
 use circuit::Circuit;
 use clap::{Parser, Subcommand};
-use gkr::{Prover, Verifier};
+use gkr::{OutputStatement, Prover, Verifier, gather_profiles};
 use gkr_engine::{
-    BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config, M31x16Config,
-    MPIConfig, MPIEngine, MPISharedMemory, Proof,
+    BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, Goldilocksx8Config,
+    KoalaBearx16Config, M31x16Config, MPIEngine, MPISharedMemory, Proof, ProofShape,
+    ThreadMPIConfig,
 };
 use log::info;
 use poly_commit::expander_pcs_init_testing_only;
 use serdes::{ExpSerde, SerdeError};
+use utils::profiler;
 use warp::{Filter, http::StatusCode, reply};
 
 #[derive(Parser, Debug)]
@@ -29,6 +31,11 @@ pub struct ExpanderExecArgs {
     #[arg(short, long, default_value = "Raw")]
     pub poly_commitment_scheme: String,
 
+    /// Run the collectives over TCP instead of MPI, the process is set up by the
+    /// EXPANDER_TCP_ROOT_ADDR, EXPANDER_TCP_WORLD_SIZE and EXPANDER_TCP_WORLD_RANK variables
+    #[arg(long, default_value_t = false)]
+    pub tcp: bool,
+
     /// Prove, Verify, or Serve subcommands
     #[clap(subcommand)]
     pub subcommands: ExpanderExecSubCommand,
@@ -48,6 +55,14 @@ pub enum ExpanderExecSubCommand {
         /// Output Proof Path
         #[arg(short, long)]
         output_proof_file: String,
+
+        /// Profile Output Path, the spans of the prover on every rank are written there
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Profile Format: json, or chrome for the Chrome trace event format
+        #[arg(long, default_value = "json")]
+        profile_format: String,
     },
     Verify {
         /// Circuit File Path
@@ -65,6 +80,11 @@ pub enum ExpanderExecSubCommand {
         /// MPI size
         #[arg(short, long, default_value_t = 1)]
         mpi_size: u32,
+
+        /// Outputs File Path, for circuits writing outputs past those they constrain to zero:
+        /// a serialized `Vec` of these other outputs, concatenated over the processes
+        #[arg(long)]
+        outputs_file: Option<String>,
     },
     Serve {
         /// Circuit File Path
@@ -102,6 +122,27 @@ pub fn load_proof_and_claimed_v<F: Field>(bytes: &[u8]) -> Result<(Proof, F), Se
     Ok((proof, claimed_v))
 }
 
+/// The outputs the proof is verified against. Without `other_outputs`, these are the outputs the
+/// circuit constrains to zero, see `OutputStatement::from_circuit`. Otherwise the first
+/// `expected_num_output_zeros` outputs of every process are zero and the others are read from
+/// `other_outputs`, a serialized `Vec` concatenated over the processes.
+pub fn load_output_statement<C: FieldEngine>(
+    circuit: &Circuit<C>,
+    other_outputs: Option<&[u8]>,
+) -> Result<OutputStatement<C>, String> {
+    match other_outputs {
+        Some(bytes) => {
+            let other_outputs = Vec::<C::SimdCircuitField>::deserialize_from(bytes)
+                .map_err(|e| format!("Unable to deserialize the outputs: {e:?}"))?;
+            Ok(OutputStatement::expected_zeros(circuit, other_outputs))
+        }
+        None => OutputStatement::from_circuit(circuit).ok_or_else(|| {
+            "The circuit writes outputs it does not constrain to zero, their values must be given."
+                .to_string()
+        }),
+    }
+}
+
 pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
     // read last 32 byte of sentinel field element to determine field type
     let bytes = fs::read(circuit_file).expect("Unable to read circuit file.");
@@ -111,6 +152,7 @@ pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
         BN254Config::SENTINEL => FieldType::BN254,
         GF2ExtConfig::SENTINEL => FieldType::GF2Ext128,
         Goldilocksx8Config::SENTINEL => FieldType::Goldilocksx8,
+        KoalaBearx16Config::SENTINEL => FieldType::KoalaBearx16,
         _ => {
             println!("Unknown field type. Field byte value: {field_bytes:?}");
             exit(1);
@@ -118,9 +160,9 @@ pub fn detect_field_type_from_circuit_file(circuit_file: &str) -> FieldType {
     }
 }
 
-pub fn prove<Cfg: GKREngine>(
+pub fn prove<Cfg: GKREngine, M: MPIEngine + Clone>(
     circuit: &mut Circuit<Cfg::FieldConfig>,
-    mpi_config: MPIConfig,
+    mpi_config: M,
 ) -> (
     <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField,
     Proof,
@@ -128,7 +170,7 @@ pub fn prove<Cfg: GKREngine>(
 where
     Cfg::FieldConfig: FieldEngine,
 {
-    let mut prover = Prover::<Cfg>::new(mpi_config.clone());
+    let mut prover = Prover::<Cfg, M>::new(mpi_config.clone());
     prover.prepare_mem(circuit);
 
     // TODO: Read PCS setup from files
@@ -144,21 +186,24 @@ where
 
 pub fn verify<Cfg: GKREngine>(
     circuit: &mut Circuit<Cfg::FieldConfig>,
-    mpi_config: MPIConfig,
+    proof_shape: ProofShape,
     proof: &Proof,
-    claimed_v: &<<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField,
+    output_statement: &OutputStatement<Cfg::FieldConfig>,
 ) -> bool {
     // TODO: Read PCS setup from files
-    let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
-        Cfg::FieldConfig,
-        Cfg::PCSConfig,
-    >(circuit.log_input_size(), &mpi_config);
-    let verifier = Verifier::<Cfg>::new(mpi_config);
+    // the PCS setup depends on the number of processes of the prover
+    let setup_mpi_config = ThreadMPIConfig::new_world(proof_shape.world_size).swap_remove(0);
+    let (pcs_params, _, pcs_verification_key, _) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &setup_mpi_config,
+        );
+    let verifier = Verifier::<Cfg>::new(proof_shape);
     let public_input = circuit.public_input.clone();
-    verifier.verify(
+    verifier.verify_output_statement(
         circuit,
         &public_input,
-        claimed_v,
+        output_statement,
         &pcs_params,
         &pcs_verification_key,
         proof,
@@ -168,9 +213,9 @@ pub fn verify<Cfg: GKREngine>(
 // The 'Prove' command can be run with mpi more than one process
 // The 'Verify' command must be run with mpi size = 1
 // The 'Serve' command must be run with mpi size = 1
-pub async fn run_command<'a, Cfg: GKREngine + 'static>(
+pub async fn run_command<Cfg: GKREngine + 'static, M: MPIEngine + Clone>(
     command: &ExpanderExecArgs,
-    mpi_config: &MPIConfig<'a>,
+    mpi_config: &M,
 ) where
     Cfg::FieldConfig: FieldEngine,
 {
@@ -181,13 +226,31 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             circuit_file,
             witness_file,
             output_proof_file,
+            profile,
+            profile_format,
         } => {
             let (mut circuit, mut window) =
                 Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(&circuit_file, mpi_config);
-            let prover = Prover::<Cfg>::new(mpi_config.clone());
+            let prover = Prover::<Cfg, M>::new(mpi_config.clone());
 
             circuit.prover_load_witness_file(&witness_file, mpi_config);
-            let (claimed_v, proof) = prove::<Cfg>(&mut circuit, mpi_config.clone());
+            let export_profile: fn(&[profiler::Profile]) -> String = match profile_format.as_str() {
+                "json" => profiler::to_json,
+                "chrome" => profiler::to_chrome_trace,
+                _ => panic!("Unknown profile format: {profile_format}"),
+            };
+            if profile.is_some() {
+                profiler::start();
+            }
+            let (claimed_v, proof) = prove::<Cfg, M>(&mut circuit, mpi_config.clone());
+
+            if let Some(profile_file) = profile {
+                let profiles = gather_profiles(&profiler::finish().unwrap(), mpi_config);
+                if prover.mpi_config.is_root() {
+                    fs::write(profile_file, export_profile(&profiles))
+                        .expect("Unable to write profile to file.");
+                }
+            }
 
             if prover.mpi_config.is_root() {
                 let bytes = dump_proof_and_claimed_v(&proof, &claimed_v)
@@ -202,42 +265,45 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             witness_file,
             input_proof_file,
             mpi_size,
+            outputs_file,
         } => {
-            let mpi_config = MPIConfig::verifier_new(mpi_size as i32);
-            let verifier = Verifier::<Cfg>::new(mpi_config);
-
-            // this assertion is not right: the MPI size = 2 so that the verifier knows the prover
-            // is running in 2 threads. The verifier itself is running in 1 thread.
-            //
-            // assert!(
-            //     verifier.mpi_config.world_size() == 1,
-            //     "Do not run verifier with mpiexec."
-            // );
-
             println!("loading circuit file");
 
             let mut circuit =
                 Circuit::<Cfg::FieldConfig>::verifier_load_circuit::<Cfg>(&circuit_file);
 
+            // the verifier runs in a single process, `mpi_size` is the number of processes
+            // of the prover
+            let proof_shape = circuit.proof_shape(mpi_size as usize);
+
             println!("loading witness file");
 
-            circuit.verifier_load_witness_file(&witness_file, &verifier.mpi_config);
+            circuit.verifier_load_witness_file(&witness_file, &proof_shape);
 
             println!("loading proof file");
 
             let bytes = fs::read(&input_proof_file).expect("Unable to read proof from file.");
-            let (proof, claimed_v) = load_proof_and_claimed_v::<
+            // the claimed output value is not trusted, the outputs are given by the statement
+            let (proof, _) = load_proof_and_claimed_v::<
                 <Cfg::FieldConfig as FieldEngine>::ChallengeField,
             >(&bytes)
             .expect("Unable to deserialize proof.");
 
+            let other_outputs = outputs_file
+                .map(|file| fs::read(file).expect("Unable to read outputs from file."));
+            let output_statement = load_output_statement(&circuit, other_outputs.as_deref())
+                .unwrap_or_else(|e| {
+                    println!("{e} Use --outputs-file.");
+                    exit(1);
+                });
+
             println!("verifying proof");
 
             assert!(verify::<Cfg>(
                 &mut circuit,
-                verifier.mpi_config,
+                proof_shape,
                 &proof,
-                &claimed_v
+                &output_statement
             ));
 
             println!("success");
@@ -247,8 +313,8 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
             host_ip,
             port,
         } => {
-            let mpi_config = MPIConfig::prover_new(None, None);
-            let prover = Prover::<Cfg>::new(mpi_config.clone());
+            let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+            let prover = Prover::<Cfg, _>::new(mpi_config.clone());
 
             assert!(
                 prover.mpi_config.world_size() == 1,
@@ -292,13 +358,13 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
                         let witness_bytes: Vec<u8> = bytes.to_vec();
 
                         let mut circuit = circuit.lock().unwrap();
-                        let mut prover = Prover::<Cfg>::new(MPIConfig::prover_new(None, None));
+                        let mut prover = Prover::<Cfg, _>::new(mpi_config.clone());
                         prover.prepare_mem(&circuit);
                         let pcs_params = pcs_params.lock().unwrap();
                         let pcs_proving_key = pcs_proving_key.lock().unwrap();
                         let mut pcs_scratch = pcs_scratch.lock().unwrap();
 
-                        circuit.load_witness_bytes(&witness_bytes, &prover.mpi_config, true, true);
+                        circuit.load_witness_bytes(&witness_bytes, &prover.mpi_config, true);
                         let (claimed_v, proof) = prover.prove(
                             &mut circuit,
                             &pcs_params,
@@ -326,22 +392,31 @@ pub async fn run_command<'a, Cfg: GKREngine + 'static>(
                             &witness_and_proof_bytes[16..16 + length_of_witness_bytes];
                         let proof_bytes = &witness_and_proof_bytes[16 + length_of_witness_bytes
                             ..16 + length_of_witness_bytes + length_of_proof_bytes];
+                        // optionally followed by the outputs past those constrained to zero
+                        let other_outputs = &witness_and_proof_bytes
+                            [16 + length_of_witness_bytes + length_of_proof_bytes..];
 
                         let mut circuit = circuit_clone_for_verifier.lock().unwrap();
-                        let verifier = Verifier::<Cfg>::new(MPIConfig::verifier_new(1));
+                        let proof_shape = circuit.proof_shape(1);
                         let pcs_verification_key = pcs_verification_key.lock().unwrap();
-                        circuit.load_witness_bytes(
-                            witness_bytes,
-                            &verifier.mpi_config,
-                            false,
-                            true,
-                        );
+                        circuit.verifier_load_witness_bytes(witness_bytes, &proof_shape, true);
+                        let verifier = Verifier::<Cfg>::new(proof_shape);
                         let public_input = circuit.public_input.clone();
-                        let (proof, claimed_v) = load_proof_and_claimed_v(proof_bytes).unwrap();
-                        if verifier.verify(
+                        let (proof, _) = load_proof_and_claimed_v::<
+                            <Cfg::FieldConfig as FieldEngine>::ChallengeField,
+                        >(proof_bytes)
+                        .unwrap();
+                        let output_statement = match load_output_statement(
+                            &circuit,
+                            (!other_outputs.is_empty()).then_some(other_outputs),
+                        ) {
+                            Ok(output_statement) => output_statement,
+                            Err(e) => return format!("failure: {e}"),
+                        };
+                        if verifier.verify_output_statement(
                             &mut circuit,
                             &public_input,
-                            &claimed_v,
+                            &output_statement,
                             &pcs_params_clone_for_verifier.lock().unwrap(),
                             &pcs_verification_key,
                             &proof,
@@ -0,0 +1,214 @@
+//! Wrap the GKR proofs of BN254 circuits with the MiMC5 transcript into Groth16 proofs, from
+//! the proof files of `expander-exec`.
+//!
+//! The setup is local and only fit for testing. The keys only depend on the circuit and the
+//! shape of its proofs, so that they are set up from any proof of the circuit.
+
+use std::{fs, str::FromStr};
+
+use arith::Fr;
+use bin::executor::load_proof_and_claimed_v;
+use circuit::Circuit;
+use clap::{Parser, Subcommand};
+use gkr::{BN254ConfigMIMC5KZG, BN254ConfigMIMC5Raw};
+use gkr_engine::{BN254Config, GKREngine, PolynomialCommitmentType, Proof, ThreadMPIConfig};
+use groth16::{GKRWrapper, ProvingKey, VerifyingKey, WrappedProof};
+use poly_commit::expander_pcs_init_testing_only;
+use recursion_circuit::MiMC5Transcript;
+use serdes::ExpSerde;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Groth16Args {
+    /// Polynomial Commitment Scheme of the GKR proofs: Raw, or KZG
+    #[arg(short, long, default_value = "Raw")]
+    poly_commitment_scheme: String,
+
+    /// Setup, Prove, or Verify subcommands
+    #[clap(subcommand)]
+    subcommands: Groth16SubCommand,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+enum Groth16SubCommand {
+    /// Generate the keys wrapping the proofs of a circuit, from one of its proofs
+    Setup {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Witness File Path, for the public inputs
+        #[arg(short, long)]
+        witness_file: String,
+
+        /// GKR Proof Path
+        #[arg(short, long)]
+        input_proof_file: String,
+
+        /// Output Proving Key Path
+        #[arg(long)]
+        proving_key_file: String,
+
+        /// Output Verifying Key Path
+        #[arg(long)]
+        verifying_key_file: String,
+    },
+    Prove {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Witness File Path, for the public inputs
+        #[arg(short, long)]
+        witness_file: String,
+
+        /// GKR Proof Path
+        #[arg(short, long)]
+        input_proof_file: String,
+
+        /// Proving Key Path
+        #[arg(long)]
+        proving_key_file: String,
+
+        /// Output Wrapped Proof Path
+        #[arg(short, long)]
+        output_proof_file: String,
+    },
+    Verify {
+        /// Circuit File Path
+        #[arg(short, long)]
+        circuit_file: String,
+
+        /// Witness File Path, for the public inputs
+        #[arg(short, long)]
+        witness_file: String,
+
+        /// Wrapped Proof Path
+        #[arg(short, long)]
+        input_proof_file: String,
+
+        /// Verifying Key Path
+        #[arg(long)]
+        verifying_key_file: String,
+    },
+}
+
+fn main() {
+    let args = Groth16Args::parse();
+    let pcs_type = PolynomialCommitmentType::from_str(&args.poly_commitment_scheme).unwrap();
+
+    match pcs_type {
+        PolynomialCommitmentType::Raw => run_command::<BN254ConfigMIMC5Raw>(&args.subcommands),
+        PolynomialCommitmentType::KZG => run_command::<BN254ConfigMIMC5KZG>(&args.subcommands),
+        _ => panic!("PCS: {pcs_type:?} setting is not yet integrated in expander-groth16"),
+    }
+}
+
+/// The circuit and the public inputs of its witness, the statement of the proofs
+fn load_statement<Cfg: GKREngine<FieldConfig = BN254Config>>(
+    circuit_file: &str,
+    witness_file: &str,
+) -> (Circuit<BN254Config>, Vec<Fr>) {
+    let mut circuit = Circuit::<BN254Config>::verifier_load_circuit::<Cfg>(circuit_file);
+    let proof_shape = circuit.proof_shape(1);
+    circuit.verifier_load_witness_file(witness_file, &proof_shape);
+    let public_input = circuit.public_input.clone();
+    (circuit, public_input)
+}
+
+fn load_proof(input_proof_file: &str) -> (Proof, Fr) {
+    let bytes = fs::read(input_proof_file).expect("Unable to read proof from file.");
+    load_proof_and_claimed_v::<Fr>(&bytes).expect("Unable to deserialize proof.")
+}
+
+fn write<T: ExpSerde>(file: &str, value: &T) {
+    let mut bytes = vec![];
+    value
+        .serialize_into(&mut bytes)
+        .expect("Unable to serialize.");
+    fs::write(file, bytes).expect("Unable to write to file.");
+}
+
+fn read<T: ExpSerde>(file: &str) -> T {
+    let bytes = fs::read(file).expect("Unable to read from file.");
+    T::deserialize_from(bytes.as_slice()).expect("Unable to deserialize.")
+}
+
+fn run_command<Cfg>(command: &Groth16SubCommand)
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, TranscriptConfig = MiMC5Transcript>,
+{
+    let mut rng = rand::thread_rng();
+
+    match command {
+        Groth16SubCommand::Setup {
+            circuit_file,
+            witness_file,
+            input_proof_file,
+            proving_key_file,
+            verifying_key_file,
+        } => {
+            let (circuit, public_input) = load_statement::<Cfg>(circuit_file, witness_file);
+            let (proof, claimed_v) = load_proof(input_proof_file);
+
+            println!("setting up keys");
+
+            let wrapper = GKRWrapper::<Cfg>::new(circuit.proof_shape(1));
+            let (pk, vk) = wrapper
+                .setup_for_testing(&circuit, &public_input, &claimed_v, &proof, &mut rng)
+                .expect("Unable to set up the keys.");
+            write(proving_key_file, &pk);
+            write(verifying_key_file, &vk);
+        }
+        Groth16SubCommand::Prove {
+            circuit_file,
+            witness_file,
+            input_proof_file,
+            proving_key_file,
+            output_proof_file,
+        } => {
+            let (circuit, public_input) = load_statement::<Cfg>(circuit_file, witness_file);
+            let (proof, claimed_v) = load_proof(input_proof_file);
+            let pk: ProvingKey = read(proving_key_file);
+
+            println!("wrapping proof");
+
+            let wrapper = GKRWrapper::<Cfg>::new(circuit.proof_shape(1));
+            let wrapped_proof = wrapper
+                .prove(&pk, &circuit, &public_input, &claimed_v, &proof, &mut rng)
+                .expect("Unable to wrap the proof.");
+            write(output_proof_file, &wrapped_proof);
+        }
+        Groth16SubCommand::Verify {
+            circuit_file,
+            witness_file,
+            input_proof_file,
+            verifying_key_file,
+        } => {
+            let (circuit, public_input) = load_statement::<Cfg>(circuit_file, witness_file);
+            let wrapped_proof: WrappedProof = read(input_proof_file);
+            let vk: VerifyingKey = read(verifying_key_file);
+
+            // TODO: Read PCS setup from files
+            let (pcs_params, _, pcs_verification_key, _) =
+                expander_pcs_init_testing_only::<BN254Config, Cfg::PCSConfig>(
+                    circuit.log_input_size(),
+                    &ThreadMPIConfig::new_world(1).swap_remove(0),
+                );
+
+            println!("verifying proof");
+
+            let wrapper = GKRWrapper::<Cfg>::new(circuit.proof_shape(1));
+            assert!(wrapper.verify(
+                &vk,
+                &circuit,
+                &public_input,
+                &wrapped_proof,
+                &pcs_params,
+                &pcs_verification_key,
+            ));
+
+            println!("success");
+        }
+    }
+}
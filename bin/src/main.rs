@@ -9,7 +9,8 @@ use clap::Parser;
 use gkr::{
     BN254ConfigMIMC5KZG, BN254ConfigSha2Hyrax, BN254ConfigSha2Raw, GF2ExtConfigSha2Orion,
     GF2ExtConfigSha2Raw, Goldilocksx8ConfigSha2Orion, Goldilocksx8ConfigSha2Raw,
-    M31x1ConfigSha2RawVanilla, M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla, Prover,
+    M31x1ConfigSha2RawVanilla, M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla,
+    M31x16Ext6ConfigSha2OrionVanilla, M31x16Ext6ConfigSha2RawVanilla, Prover,
     utils::{
         KECCAK_BABYBEAR_CIRCUIT, KECCAK_BABYBEAR_WITNESS, KECCAK_BN254_CIRCUIT,
         KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS, KECCAK_GOLDILOCKS_CIRCUIT,
@@ -26,7 +27,7 @@ use serdes::ExpSerde;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Field Identifier: fr, m31ext3, gf2ext128, goldilocks
+    /// Field Identifier: fr, m31ext3, m31ext6, gf2ext128, goldilocks
     #[arg(short, long,default_value_t = String::from("m31ext3"))]
     field: String,
 
@@ -78,6 +79,21 @@ fn main() {
             },
             _ => unreachable!("Unsupported PCS type for M31"),
         },
+        "m31ext6" => match pcs_type {
+            PolynomialCommitmentType::Raw => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<M31x16Ext6ConfigSha2RawVanilla>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(),
+            },
+            PolynomialCommitmentType::Orion => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<M31x16Ext6ConfigSha2OrionVanilla>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(""),
+            },
+            _ => unreachable!("Unsupported PCS type for M31"),
+        },
         "fr" => match pcs_type {
             PolynomialCommitmentType::Raw => match args.circuit.as_str() {
                 "keccak" => run_benchmark::<BN254ConfigSha2Raw>(&args, mpi_config.clone()),
@@ -138,14 +154,16 @@ where
                     KECCAK_GF2_CIRCUIT,
                 )
             }
-            FieldType::M31x1 => {
+            FieldType::M31x1 | FieldType::M31x1Ext6 => {
+                Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
+                    KECCAK_M31_CIRCUIT,
+                )
+            }
+            FieldType::M31x16 | FieldType::M31x16Ext6 => {
                 Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
                     KECCAK_M31_CIRCUIT,
                 )
             }
-            FieldType::M31x16 => Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<
-                Cfg,
-            >(KECCAK_M31_CIRCUIT),
             FieldType::BN254 => {
                 Circuit::<Cfg::FieldConfig>::single_thread_prover_load_circuit::<Cfg>(
                     KECCAK_BN254_CIRCUIT,
@@ -166,6 +184,7 @@ where
                     KECCAK_GOLDILOCKS_CIRCUIT,
                 )
             }
+            FieldType::KoalaBearx16 => unimplemented!("no keccak circuit over KoalaBear yet"),
         },
         _ => unreachable!(),
     };
@@ -173,12 +192,13 @@ where
     let witness_path = match args.circuit.as_str() {
         "keccak" => match Cfg::FieldConfig::FIELD_TYPE {
             FieldType::GF2Ext128 => KECCAK_GF2_WITNESS,
-            FieldType::M31x1 => KECCAK_M31_WITNESS,
-            FieldType::M31x16 => KECCAK_M31_WITNESS,
+            FieldType::M31x1 | FieldType::M31x1Ext6 => KECCAK_M31_WITNESS,
+            FieldType::M31x16 | FieldType::M31x16Ext6 => KECCAK_M31_WITNESS,
             FieldType::BN254 => KECCAK_BN254_WITNESS,
             FieldType::Goldilocksx1 => KECCAK_GOLDILOCKS_WITNESS,
             FieldType::Goldilocksx8 => KECCAK_GOLDILOCKS_WITNESS,
             FieldType::BabyBearx16 => KECCAK_BABYBEAR_WITNESS,
+            FieldType::KoalaBearx16 => unimplemented!("no keccak circuit over KoalaBear yet"),
         },
         _ => unreachable!(),
     };
@@ -189,6 +209,7 @@ where
         (FieldType::GF2Ext128, "keccak") => 1,
         (FieldType::M31x1, "keccak") => 2,
         (FieldType::M31x16, "keccak") => 2,
+        (FieldType::M31x16Ext6, "keccak") => 2,
         (FieldType::Goldilocksx8, "keccak") => 2,
         (FieldType::BabyBearx16, "keccak") => 2,
         (FieldType::BN254, "keccak") => 2,
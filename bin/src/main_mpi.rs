@@ -5,7 +5,8 @@ use clap::Parser;
 use gkr::{
     BN254ConfigMIMC5KZG, BN254ConfigSha2Hyrax, BN254ConfigSha2Raw, GF2ExtConfigSha2Orion,
     GF2ExtConfigSha2Raw, Goldilocksx8ConfigSha2Orion, Goldilocksx8ConfigSha2Raw,
-    M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla, Prover,
+    M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla, M31x16Ext6ConfigSha2OrionVanilla,
+    M31x16Ext6ConfigSha2RawVanilla, Prover,
     utils::{
         KECCAK_BABYBEAR_CIRCUIT, KECCAK_BABYBEAR_WITNESS, KECCAK_BN254_CIRCUIT,
         KECCAK_BN254_WITNESS, KECCAK_GF2_CIRCUIT, KECCAK_GF2_WITNESS, KECCAK_GOLDILOCKS_CIRCUIT,
@@ -23,7 +24,7 @@ use serdes::ExpSerde;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Field Identifier: fr, m31, m31ext3
+    /// Field Identifier: fr, m31, m31ext3, m31ext6
     #[arg(short, long,default_value_t = String::from("m31ext3"))]
     field: String,
 
@@ -72,6 +73,21 @@ fn main() {
             },
             _ => unreachable!("Unsupported PCS type for M31"),
         },
+        "m31ext6" => match pcs_type {
+            PolynomialCommitmentType::Raw => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<M31x16Ext6ConfigSha2RawVanilla>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(),
+            },
+            PolynomialCommitmentType::Orion => match args.circuit.as_str() {
+                "keccak" => {
+                    run_benchmark::<M31x16Ext6ConfigSha2OrionVanilla>(&args, mpi_config.clone())
+                }
+                _ => unreachable!(""),
+            },
+            _ => unreachable!("Unsupported PCS type for M31"),
+        },
         "fr" => match pcs_type {
             PolynomialCommitmentType::Raw => match args.circuit.as_str() {
                 "keccak" => run_benchmark::<BN254ConfigSha2Raw>(&args, mpi_config.clone()),
@@ -126,13 +142,15 @@ where
                 KECCAK_GF2_CIRCUIT,
                 &mpi_config,
             ),
-            FieldType::M31x1 => {
+            FieldType::M31x1 | FieldType::M31x1Ext6 => {
                 unimplemented!("x1 mod only used for testing. use main instead of main_mpi")
             }
-            FieldType::M31x16 => Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(
-                KECCAK_M31_CIRCUIT,
-                &mpi_config,
-            ),
+            FieldType::M31x16 | FieldType::M31x16Ext6 => {
+                Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(
+                    KECCAK_M31_CIRCUIT,
+                    &mpi_config,
+                )
+            }
             FieldType::BN254 => Circuit::<Cfg::FieldConfig>::prover_load_circuit::<Cfg>(
                 KECCAK_BN254_CIRCUIT,
                 &mpi_config,
@@ -148,6 +166,7 @@ where
                 KECCAK_BABYBEAR_CIRCUIT,
                 &mpi_config,
             ),
+            FieldType::KoalaBearx16 => unimplemented!("no keccak circuit over KoalaBear yet"),
         },
         _ => unreachable!(),
     };
@@ -155,16 +174,17 @@ where
     let witness_path = match args.circuit.as_str() {
         "keccak" => match Cfg::FieldConfig::FIELD_TYPE {
             FieldType::GF2Ext128 => KECCAK_GF2_WITNESS,
-            FieldType::M31x1 => {
+            FieldType::M31x1 | FieldType::M31x1Ext6 => {
                 unimplemented!("x1 mod only used for testing. use main instead of main_mpi")
             }
-            FieldType::M31x16 => KECCAK_M31_WITNESS,
+            FieldType::M31x16 | FieldType::M31x16Ext6 => KECCAK_M31_WITNESS,
             FieldType::BN254 => KECCAK_BN254_WITNESS,
             FieldType::Goldilocksx1 => {
                 unimplemented!("x1 mod only used for testing. use main instead of main_mpi")
             }
             FieldType::Goldilocksx8 => KECCAK_GOLDILOCKS_WITNESS,
             FieldType::BabyBearx16 => KECCAK_BABYBEAR_WITNESS,
+            FieldType::KoalaBearx16 => unimplemented!("no keccak circuit over KoalaBear yet"),
         },
         _ => unreachable!(),
     };
@@ -174,6 +194,7 @@ where
     let circuit_copy_size: usize = match (Cfg::FieldConfig::FIELD_TYPE, args.circuit.as_str()) {
         (FieldType::GF2Ext128, "keccak") => 1,
         (FieldType::M31x16, "keccak") => 2,
+        (FieldType::M31x16Ext6, "keccak") => 2,
         (FieldType::BN254, "keccak") => 2,
         (FieldType::Goldilocksx8, "keccak") => 2,
         _ => unreachable!(),
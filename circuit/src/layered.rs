@@ -1,7 +1,14 @@
 mod circuit;
 mod gates;
+mod lookup;
+mod optimizer;
 mod serde;
+#[cfg(feature = "prover")]
 mod shared_mem;
+mod stats;
 
 pub use circuit::*;
 pub use gates::*;
+pub use lookup::*;
+pub use optimizer::*;
+pub use stats::*;
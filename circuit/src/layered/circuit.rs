@@ -2,9 +2,12 @@ use std::fs;
 use std::io::Cursor;
 
 use arith::{Field, SimdField};
+#[cfg(feature = "prover")]
 use ark_std::test_rng;
-use gkr_engine::{root_println, FieldEngine, GKREngine, MPIConfig, MPIEngine, Transcript};
-use mpi::ffi::ompi_win_t;
+#[cfg(feature = "prover")]
+use gkr_engine::{root_println, MPIEngine, SharedMemHandle};
+use gkr_engine::{FieldEngine, GKREngine, ProofShape, Transcript};
+use gkr_hashers::{FiatShamirHasher, SHA256hasher};
 use serdes::ExpSerde;
 
 use crate::*;
@@ -13,6 +16,7 @@ use crate::*;
 pub struct StructureInfo {
     // If a layer contains only linear combination of fan-in-one gates, we can skip the second
     // phase of sumcheck e.g. y = a + b + c, and y = a^5 + b^5 + c^5
+    // i.e. the sumcheck of the layer leaves a single claim on its input
     pub skip_sumcheck_phase_two: bool,
 }
 
@@ -28,11 +32,13 @@ pub struct CircuitLayer<C: FieldEngine> {
     pub add: Vec<GateAdd<C>>,
     pub const_: Vec<GateConst<C>>,
     pub uni: Vec<GateUni<C>>,
+    pub monomial: Vec<GateMonomial<C>>,
 
     pub structure_info: StructureInfo,
 }
 
 impl<C: FieldEngine> CircuitLayer<C> {
+    #[cfg(feature = "prover")]
     #[inline]
     pub fn evaluate(
         &self,
@@ -83,6 +89,15 @@ impl<C: FieldEngine> CircuitLayer<C> {
                 _ => panic!("Unknown gate type: {}", gate.gate_type),
             }
         }
+
+        for gate in &self.monomial {
+            let o = &mut res[gate.o_id];
+            let mut v = C::SimdCircuitField::one();
+            for (i, e) in gate.i_ids.iter().zip(&gate.exponents) {
+                v *= self.input_vals[*i].exp(*e as u128);
+            }
+            *o += v * gate.coef;
+        }
     }
 
     #[inline]
@@ -107,11 +122,17 @@ impl<C: FieldEngine> CircuitLayer<C> {
                 rnd_coefs.push(&mut gate.coef);
             }
         }
+        for gate in &mut self.monomial {
+            if gate.coef_type == CoefType::Random {
+                rnd_coefs.push(&mut gate.coef);
+            }
+        }
     }
 
     #[inline]
     pub fn identify_structure_info(&mut self) {
-        self.structure_info.skip_sumcheck_phase_two = self.mul.is_empty();
+        self.structure_info.skip_sumcheck_phase_two =
+            self.mul.is_empty() && self.monomial.iter().all(|g| g.i_ids.len() <= 1);
     }
 }
 
@@ -120,6 +141,7 @@ pub struct Circuit<C: FieldEngine> {
     pub layers: Vec<CircuitLayer<C>>,
     pub public_input: Vec<C::SimdCircuitField>,
     pub expected_num_output_zeros: usize,
+    pub lookups: Vec<Lookup<C>>,
 
     pub rnd_coefs_identified: bool,
     pub rnd_coefs: Vec<*mut C::CircuitField>, // unsafe
@@ -131,6 +153,7 @@ impl<C: FieldEngine> Clone for Circuit<C> {
             layers: self.layers.clone(),
             public_input: self.public_input.clone(),
             expected_num_output_zeros: self.expected_num_output_zeros,
+            lookups: self.lookups.clone(),
 
             rnd_coefs_identified: false,
             rnd_coefs: vec![],
@@ -157,7 +180,8 @@ impl<C: FieldEngine> Circuit<C> {
 
     // Used for prover with mpi_size = 1.
     // This avoids the overhead of shared memory
-    // No need to call discard_control_of_shared_mem() and free_shared_mem(window) after this
+    // No need to call discard_control_of_shared_mem() and free_shared_mem(handle) after this
+    #[cfg(feature = "prover")]
     #[inline(always)]
     pub fn single_thread_prover_load_circuit<Cfg: GKREngine<FieldConfig = C>>(
         filename: &str,
@@ -168,12 +192,13 @@ impl<C: FieldEngine> Circuit<C> {
     // The root process loads a circuit from a file and shares it with other processes
     // with shared memory
     // Used in the mpi case, ok if mpi_size = 1, but
-    // circuit.discard_control_of_shared_mem() and mpi_config.free_shared_mem(window) should be
+    // circuit.discard_control_of_shared_mem() and mpi_config.free_shared_mem(handle) should be
     // called before the end of the program
+    #[cfg(feature = "prover")]
     pub fn prover_load_circuit<Cfg: GKREngine<FieldConfig = C>>(
         filename: &str,
-        mpi_config: &MPIConfig,
-    ) -> (Self, *mut ompi_win_t) {
+        mpi_config: &impl MPIEngine,
+    ) -> (Self, SharedMemHandle) {
         let circuit = if mpi_config.is_root() {
             let rc = RecursiveCircuit::<C>::load(filename).unwrap();
             let circuit = rc.flatten();
@@ -187,34 +212,78 @@ impl<C: FieldEngine> Circuit<C> {
         (circuit, window)
     }
 
+    #[cfg(feature = "prover")]
     pub fn load_witness_allow_padding_testing_only(
         &mut self,
         filename: &str,
-        mpi_config: &MPIConfig,
+        mpi_config: &impl MPIEngine,
     ) {
         let file_bytes = fs::read(filename).unwrap();
-        self.load_witness_bytes(&file_bytes, mpi_config, true, true);
+        self.load_witness_bytes(&file_bytes, mpi_config, true);
     }
 
-    pub fn prover_load_witness_file(&mut self, filename: &str, mpi_config: &MPIConfig) {
+    #[cfg(feature = "prover")]
+    pub fn prover_load_witness_file(&mut self, filename: &str, mpi_config: &impl MPIEngine) {
         let file_bytes = fs::read(filename)
             .unwrap_or_else(|_| panic!("Failed to read witness file: {filename}"));
-        self.load_witness_bytes(&file_bytes, mpi_config, true, false);
+        self.load_witness_bytes(&file_bytes, mpi_config, false);
     }
 
-    pub fn verifier_load_witness_file(&mut self, filename: &str, mpi_config: &MPIConfig) {
+    pub fn verifier_load_witness_file(&mut self, filename: &str, proof_shape: &ProofShape) {
         let file_bytes = fs::read(filename)
             .unwrap_or_else(|_| panic!("Failed to read witness file: {filename}"));
-        self.load_witness_bytes(&file_bytes, mpi_config, false, false);
+        self.verifier_load_witness_bytes(&file_bytes, proof_shape, false);
     }
 
+    #[cfg(feature = "prover")]
     pub fn load_witness_bytes(
         &mut self,
         file_bytes: &[u8],
-        mpi_config: &MPIConfig,
-        is_prover: bool,
+        mpi_config: &impl MPIEngine,
         allow_padding_for_testing: bool, // TODO: Consider remove this
     ) {
+        let witness = self.parse_witness_bytes(
+            file_bytes,
+            mpi_config.world_size(),
+            allow_padding_for_testing,
+        );
+        root_println!(
+            mpi_config,
+            "Witness loaded: {} private inputs, {} public inputs, x{} witnesses",
+            witness.num_private_inputs_per_witness,
+            witness.num_public_inputs_per_witness,
+            witness.num_witnesses
+        );
+        self.prover_process_witness(witness, mpi_config);
+    }
+
+    pub fn verifier_load_witness_bytes(
+        &mut self,
+        file_bytes: &[u8],
+        proof_shape: &ProofShape,
+        allow_padding_for_testing: bool,
+    ) {
+        let witness = self.parse_witness_bytes(
+            file_bytes,
+            proof_shape.world_size,
+            allow_padding_for_testing,
+        );
+        log::info!(
+            "Witness loaded: {} private inputs, {} public inputs, x{} witnesses",
+            witness.num_private_inputs_per_witness,
+            witness.num_public_inputs_per_witness,
+            witness.num_witnesses
+        );
+        self.verifier_process_witness(witness, proof_shape);
+    }
+
+    /// Deserialize a witness and fit it to `world_size` processes
+    fn parse_witness_bytes(
+        &self,
+        file_bytes: &[u8],
+        world_size: usize,
+        allow_padding_for_testing: bool,
+    ) -> Witness<C> {
         let cursor = Cursor::new(file_bytes);
         let mut witness = Witness::<C>::deserialize_from(cursor).unwrap();
 
@@ -223,16 +292,9 @@ impl<C: FieldEngine> Circuit<C> {
         let public_input_size = witness.num_public_inputs_per_witness;
         let total_size = private_input_size + public_input_size;
         assert_eq!(witness.num_private_inputs_per_witness, private_input_size);
-        root_println!(
-            mpi_config,
-            "Witness loaded: {} private inputs, {} public inputs, x{} witnesses",
-            private_input_size,
-            public_input_size,
-            witness.num_witnesses
-        );
 
         // the number of witnesses should be equal to the number of MPI processes * simd width
-        let desired_number_of_witnesses = C::get_field_pack_size() * mpi_config.world_size();
+        let desired_number_of_witnesses = C::get_field_pack_size() * world_size;
 
         #[allow(clippy::comparison_chain)]
         if witness.num_witnesses < desired_number_of_witnesses {
@@ -263,14 +325,11 @@ impl<C: FieldEngine> Circuit<C> {
             witness.num_witnesses = desired_number_of_witnesses;
         }
 
-        if is_prover {
-            self.prover_process_witness(witness, mpi_config);
-        } else {
-            self.verifier_process_witness(witness, mpi_config);
-        }
+        witness
     }
 
-    pub fn prover_process_witness(&mut self, witness: Witness<C>, mpi_config: &MPIConfig) {
+    #[cfg(feature = "prover")]
+    pub fn prover_process_witness(&mut self, witness: Witness<C>, mpi_config: &impl MPIEngine) {
         let rank = mpi_config.world_rank();
         let private_input_size = 1 << self.log_input_size();
         let public_input_size = witness.num_public_inputs_per_witness;
@@ -301,7 +360,7 @@ impl<C: FieldEngine> Circuit<C> {
         }
     }
 
-    pub fn verifier_process_witness(&mut self, witness: Witness<C>, mpi_config: &MPIConfig) {
+    pub fn verifier_process_witness(&mut self, witness: Witness<C>, proof_shape: &ProofShape) {
         let private_input_size = 1 << self.log_input_size();
         let public_input_size = witness.num_public_inputs_per_witness;
         let total_size =
@@ -310,7 +369,7 @@ impl<C: FieldEngine> Circuit<C> {
         let public_input = &mut self.public_input;
         public_input.clear();
 
-        for i_rank in 0..mpi_config.world_size() {
+        for i_rank in 0..proof_shape.world_size {
             let input = &witness.values[i_rank * total_size * C::get_field_pack_size()
                 ..(i_rank + 1) * total_size * C::get_field_pack_size()];
 
@@ -326,6 +385,7 @@ impl<C: FieldEngine> Circuit<C> {
 }
 
 impl<C: FieldEngine> Circuit<C> {
+    #[cfg(feature = "prover")]
     /// Clone for batch GKR: copies values but SHARES gate arrays via unsafe pointer aliasing.
     /// The returned circuits borrow gate data from `self` — `self` MUST outlive them.
     /// The caller MUST NOT drop the returned circuits normally — use `drop_batch_clone` instead.
@@ -341,16 +401,19 @@ impl<C: FieldEngine> Circuit<C> {
                     add: std::ptr::read(&layer.add),
                     const_: std::ptr::read(&layer.const_),
                     uni: std::ptr::read(&layer.uni),
+                    monomial: std::ptr::read(&layer.monomial),
                     structure_info: layer.structure_info.clone(),
                 }
             }).collect(),
             public_input: self.public_input.clone(),
             expected_num_output_zeros: self.expected_num_output_zeros,
+            lookups: self.lookups.clone(),
             rnd_coefs_identified: self.rnd_coefs_identified,
             rnd_coefs: self.rnd_coefs.clone(),
         }
     }
 
+    #[cfg(feature = "prover")]
     /// Drop a circuit created by `clone_for_batch` without freeing the shared gate arrays.
     pub unsafe fn drop_batch_clone(mut self) {
         for layer in &mut self.layers {
@@ -359,10 +422,12 @@ impl<C: FieldEngine> Circuit<C> {
             std::mem::forget(std::mem::take(&mut layer.add));
             std::mem::forget(std::mem::take(&mut layer.const_));
             std::mem::forget(std::mem::take(&mut layer.uni));
+            std::mem::forget(std::mem::take(&mut layer.monomial));
         }
         // Now safe to drop the rest (values, public_input)
     }
 
+    #[cfg(feature = "prover")]
     /// Create N batch circuits from pre-allocated flat buffers.
     /// Each circuit shares gates with `self` and gets a slice of the flat buffers for values.
     /// Returns (circuits, flat_buffers) — flat_buffers must outlive circuits.
@@ -404,11 +469,13 @@ impl<C: FieldEngine> Circuit<C> {
                         add: std::ptr::read(&layer.add),
                         const_: std::ptr::read(&layer.const_),
                         uni: std::ptr::read(&layer.uni),
+                        monomial: std::ptr::read(&layer.monomial),
                         structure_info: layer.structure_info.clone(),
                     }
                 }).collect(),
                 public_input: self.public_input.clone(),
                 expected_num_output_zeros: self.expected_num_output_zeros,
+                lookups: self.lookups.clone(),
                 rnd_coefs_identified: self.rnd_coefs_identified,
                 rnd_coefs: self.rnd_coefs.clone(),
             }
@@ -417,6 +484,7 @@ impl<C: FieldEngine> Circuit<C> {
         (circuits, flat_buffers)
     }
 
+    #[cfg(feature = "prover")]
     /// Drop batch circuits created by `create_batch`.
     /// Prevents freeing shared gates AND aliased value buffers.
     pub unsafe fn drop_batch(circuits: Vec<Self>) {
@@ -426,6 +494,7 @@ impl<C: FieldEngine> Circuit<C> {
                 std::mem::forget(std::mem::take(&mut layer.add));
                 std::mem::forget(std::mem::take(&mut layer.const_));
                 std::mem::forget(std::mem::take(&mut layer.uni));
+                std::mem::forget(std::mem::take(&mut layer.monomial));
                 std::mem::forget(std::mem::take(&mut layer.input_vals));
                 std::mem::forget(std::mem::take(&mut layer.output_vals));
             }
@@ -436,6 +505,28 @@ impl<C: FieldEngine> Circuit<C> {
         self.layers[0].input_var_num
     }
 
+    /// The shape of a proof of this circuit generated by `world_size` processes
+    pub fn proof_shape(&self, world_size: usize) -> ProofShape {
+        ProofShape::new::<C>(
+            world_size,
+            self.log_input_size(),
+            self.layers.last().unwrap().output_var_num,
+        )
+    }
+
+    /// SHA-256 digest of the circuit description, i.e. its serialization, lookups included,
+    /// which binds a proof to the circuit. The values of random coefficients are not included,
+    /// they are sampled from the transcript.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        self.serialize_into(&mut bytes).unwrap();
+
+        let mut digest = [0u8; 32];
+        SHA256hasher::new().hash(&mut digest, &bytes);
+        digest
+    }
+
+    #[cfg(feature = "prover")]
     // Build a random mock circuit with binary inputs
     pub fn set_random_input_for_test(&mut self) {
         let mut rng = test_rng();
@@ -444,6 +535,7 @@ impl<C: FieldEngine> Circuit<C> {
             .collect();
     }
 
+    #[cfg(feature = "prover")]
     pub fn evaluate(&mut self) {
         for i in 0..self.layers.len() - 1 {
             let (layer_p_1, layer_p_2) = self.layers.split_at_mut(i + 1);
@@ -17,6 +17,14 @@ pub struct Gate<C: FieldEngine, const INPUT_NUM: usize> {
     pub gate_type: usize,
 }
 
+/// Gate type id of the pow5 uni gate
+pub const UNI_GATE_TYPE_POW5: usize = 12345;
+/// Gate type id of the pow1 (identity) uni gate
+pub const UNI_GATE_TYPE_POW1: usize = 12346;
+/// Gate type id of the monomial gate in the custom gate encoding, which lists each input as many
+/// times as its exponent
+pub const CUSTOM_GATE_TYPE_MONOMIAL: usize = 12347;
+
 pub type GateMul<C> = Gate<C, 2>;
 pub type GateAdd<C> = Gate<C, 1>;
 pub type GateUni<C> = Gate<C, 1>;
@@ -25,3 +33,22 @@ pub type GateConst<C> = Gate<C, 0>;
 // If I Simply add derive(Copy) to the Gate struct, the compiler does not seem to recognize it
 // for the type aliases. Explicitly state it here.
 impl<C: FieldEngine, const INPUT_NUM: usize> Copy for Gate<C, INPUT_NUM> {}
+
+/// A gate of arbitrary fan-in and degree, `o = coef * in_0^e_0 * ... * in_k^e_k`,
+/// e.g. a 3-input mul gate or an x^7 S-box.
+#[derive(Debug, Clone)]
+pub struct GateMonomial<C: FieldEngine> {
+    pub i_ids: Vec<usize>,
+    pub exponents: Vec<usize>,
+    pub o_id: usize,
+    pub coef_type: CoefType,
+    pub coef: C::CircuitField,
+}
+
+impl<C: FieldEngine> GateMonomial<C> {
+    /// Total degree of the monomial
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.exponents.iter().sum()
+    }
+}
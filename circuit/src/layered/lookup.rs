@@ -0,0 +1,122 @@
+use arith::Field;
+use ethnum::U256;
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine};
+
+use crate::Circuit;
+
+/// A lookup of a block of input wires into a table: the wires
+/// `[input_offset, input_offset + 2^log_input_num)` of the input layer, in every SIMD lane and
+/// every process, must take values in `table`.
+///
+/// The block is aligned, i.e. `input_offset` is a multiple of `2^log_input_num`, so that the
+/// looked-up values are the input MLE restricted to a subcube. Only input wires can be looked
+/// up: a value computed by the circuit must be supplied as an input and constrained to equal
+/// the computed one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lookup<C: FieldEngine> {
+    pub table: Vec<C::CircuitField>,
+    pub input_offset: usize,
+    pub log_input_num: usize,
+}
+
+impl<C: FieldEngine> Lookup<C> {
+    pub fn new(table: Vec<C::CircuitField>, input_offset: usize, log_input_num: usize) -> Self {
+        assert!(!table.is_empty(), "a lookup table cannot be empty");
+        assert!(log_input_num > 0, "a lookup needs at least two wires");
+        assert_eq!(
+            input_offset % (1 << log_input_num),
+            0,
+            "the looked-up wires must be an aligned block"
+        );
+        Self {
+            table,
+            input_offset,
+            log_input_num,
+        }
+    }
+
+    /// Why the lookup is invalid in a circuit of `2^log_input_size` inputs, if it is
+    pub fn validate(&self, log_input_size: usize) -> Result<(), &'static str> {
+        if self.table.is_empty() {
+            return Err("a lookup table cannot be empty");
+        }
+        if self.log_input_num == 0 {
+            return Err("a lookup needs at least two wires");
+        }
+        if (self.input_offset.trailing_zeros() as usize) < self.log_input_num {
+            return Err("the looked-up wires must be an aligned block");
+        }
+        if self.log_input_num > log_input_size
+            || self
+                .input_offset
+                .checked_shr(log_input_size as u32)
+                .is_some_and(|high_bits| high_bits != 0)
+        {
+            return Err("the looked-up wires must be input wires");
+        }
+        Ok(())
+    }
+
+    /// The number of variables of the looked-up values of `world_size` processes,
+    /// ordered as the SIMD variables, the variables of the block, then the MPI variables
+    #[inline]
+    pub fn value_var_num(&self, world_size: usize) -> usize {
+        C::get_field_pack_size().trailing_zeros() as usize
+            + self.log_input_num
+            + world_size.trailing_zeros() as usize
+    }
+
+    /// LogUp counts the multiplicities in the circuit field, which must not wrap around
+    #[inline]
+    pub fn fits_field(&self, world_size: usize) -> bool {
+        U256::ONE << (self.value_var_num(world_size) as u32) < C::CircuitField::MODULUS
+    }
+
+    /// The input variables fixed by the position of the block, from the lowest
+    pub fn offset_bits(&self, log_input_size: usize) -> Vec<bool> {
+        (self.log_input_num..log_input_size)
+            .map(|i| (self.input_offset >> i) & 1 == 1)
+            .collect()
+    }
+
+    /// The input challenge of a point of the looked-up values, see `value_var_num`
+    pub fn input_challenge(
+        &self,
+        log_input_size: usize,
+        point: &[C::ChallengeField],
+    ) -> ExpanderSingleVarChallenge<C> {
+        let simd_var_num = C::get_field_pack_size().trailing_zeros() as usize;
+        let (r_simd, rest) = point.split_at(simd_var_num);
+        let (r_block, r_mpi) = rest.split_at(self.log_input_num);
+        let rz = r_block
+            .iter()
+            .cloned()
+            .chain(self.offset_bits(log_input_size).into_iter().map(|b| {
+                if b {
+                    C::ChallengeField::ONE
+                } else {
+                    C::ChallengeField::ZERO
+                }
+            }))
+            .collect();
+        ExpanderSingleVarChallenge::new(rz, r_simd.to_vec(), r_mpi.to_vec())
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Declare that the input wires `[input_offset, input_offset + 2^log_input_num)` take values
+    /// in `table`, see `Lookup` for the restrictions on the wires. Lookups are serialized with
+    /// the circuit.
+    pub fn declare_lookup(
+        &mut self,
+        table: Vec<C::CircuitField>,
+        input_offset: usize,
+        log_input_num: usize,
+    ) {
+        let lookup = Lookup::new(table, input_offset, log_input_num);
+        if let Err(message) = lookup.validate(self.log_input_size()) {
+            panic!("{message}");
+        }
+        self.lookups.push(lookup);
+    }
+}
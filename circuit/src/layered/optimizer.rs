@@ -0,0 +1,628 @@
+//! Gate-level optimizer for flattened layered circuits.
+//!
+//! Every pass keeps the input layout of the first layer and the output layout of the last layer,
+//! so the witness format and `expected_num_output_zeros` stay valid.
+//! The passes move gates around and invalidate the random coefficient pointers, they must run
+//! before `pre_process_gkr`. All passes are deterministic, so that the prover and the verifier
+//! optimizing the same circuit end up with the same gates in the same order.
+
+use std::cmp::max;
+use std::collections::{BTreeMap, BTreeSet};
+
+use arith::Field;
+use gkr_engine::FieldEngine;
+
+use super::circuit::{Circuit, CircuitLayer};
+use super::gates::{
+    CoefType, Gate, GateAdd, GateConst, GateMonomial, GateUni, UNI_GATE_TYPE_POW1,
+    UNI_GATE_TYPE_POW5,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OptimizationReport {
+    pub layers_before: usize,
+    pub layers_after: usize,
+    pub gates_before: usize,
+    pub gates_after: usize,
+    /// Sum of the layer input sizes, i.e., the size of the sumcheck bookkeeping tables
+    pub input_size_before: usize,
+    pub input_size_after: usize,
+}
+
+#[inline]
+fn var_num_for(n_wires: usize) -> usize {
+    // var_num >= 1, as in `RecursiveCircuit::flatten`
+    max(n_wires.next_power_of_two().trailing_zeros() as usize, 1)
+}
+
+/// Product of two gate coefficients, `None` if the product cannot be expressed by a single gate.
+/// Only a constant coefficient can be scaled freely, a random or public input coefficient
+/// is only kept if the other factor is one.
+#[inline]
+fn combine_coefs<F: Field>(
+    (coef_type, coef): (CoefType, F),
+    factor: (CoefType, F),
+) -> Option<(CoefType, F)> {
+    match (coef_type, factor.0) {
+        (CoefType::Constant, CoefType::Constant) => Some((CoefType::Constant, coef * factor.1)),
+        (CoefType::Constant, other) if coef == F::ONE => Some((other, factor.1)),
+        (other, CoefType::Constant) if factor.1 == F::ONE => Some((other, coef)),
+        _ => None,
+    }
+}
+
+#[inline]
+fn with_coef<C: FieldEngine, const INPUT_NUM: usize>(
+    gate: &Gate<C, INPUT_NUM>,
+    (coef_type, coef): (CoefType, C::CircuitField),
+) -> Gate<C, INPUT_NUM> {
+    Gate {
+        coef_type,
+        coef,
+        ..*gate
+    }
+}
+
+#[inline]
+fn is_zero_constant<C: FieldEngine, const INPUT_NUM: usize>(gate: &Gate<C, INPUT_NUM>) -> bool {
+    gate.coef_type == CoefType::Constant && gate.coef.is_zero()
+}
+
+/// Sum up the coefficients of gates with identical wiring, keeping the first occurrence's position.
+/// Gates with random or public input coefficients are never merged.
+fn merge_duplicates<C: FieldEngine, const INPUT_NUM: usize>(
+    gates: &mut Vec<Gate<C, INPUT_NUM>>,
+    commutative: bool,
+) -> bool {
+    let len_before = gates.len();
+    let mut positions: BTreeMap<(Vec<usize>, usize, usize), usize> = BTreeMap::new();
+    let mut merged: Vec<Gate<C, INPUT_NUM>> = Vec::with_capacity(gates.len());
+    for gate in gates.iter() {
+        if gate.coef_type != CoefType::Constant {
+            merged.push(*gate);
+            continue;
+        }
+        let mut i_ids = gate.i_ids.to_vec();
+        if commutative {
+            i_ids.sort_unstable();
+        }
+        match positions.get(&(i_ids.clone(), gate.o_id, gate.gate_type)) {
+            Some(&pos) => merged[pos].coef += gate.coef,
+            None => {
+                positions.insert((i_ids, gate.o_id, gate.gate_type), merged.len());
+                merged.push(*gate);
+            }
+        }
+    }
+    merged.retain(|g| !is_zero_constant(g));
+    let changed = merged.len() != len_before;
+    *gates = merged;
+    changed
+}
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    #[inline]
+    pub fn num_gates(&self) -> usize {
+        self.mul.len() + self.add.len() + self.const_.len() + self.uni.len() + self.monomial.len()
+    }
+
+    /// A layer that only forwards scaled inputs, i.e., fan-in-one linear gates
+    /// with constant coefficients
+    pub fn is_relay_layer(&self) -> bool {
+        self.mul.is_empty()
+            && self.const_.is_empty()
+            && self.monomial.is_empty()
+            && self.add.iter().all(|g| g.coef_type == CoefType::Constant)
+            && self
+                .uni
+                .iter()
+                .all(|g| g.gate_type == UNI_GATE_TYPE_POW1 && g.coef_type == CoefType::Constant)
+    }
+
+    /// Fan-in-one relays of a relay layer, keyed by output: `o_id -> (i_id, coef)`.
+    /// `None` if some output is written by more than one gate.
+    fn relay_map(&self) -> Option<BTreeMap<usize, (usize, C::CircuitField)>> {
+        if !self.is_relay_layer() {
+            return None;
+        }
+        let mut map = BTreeMap::new();
+        for (i_id, o_id, coef) in self
+            .add
+            .iter()
+            .chain(self.uni.iter())
+            .map(|g| (g.i_ids[0], g.o_id, g.coef))
+        {
+            if map.insert(o_id, (i_id, coef)).is_some() {
+                return None;
+            }
+        }
+        Some(map)
+    }
+
+    fn retain_gates(
+        &mut self,
+        mut keep_inputs: impl FnMut(&[usize]) -> bool,
+        mut keep_output: impl FnMut(usize) -> bool,
+    ) -> bool {
+        let len_before = self.num_gates();
+        self.mul
+            .retain(|g| keep_inputs(&g.i_ids) && keep_output(g.o_id));
+        self.add
+            .retain(|g| keep_inputs(&g.i_ids) && keep_output(g.o_id));
+        self.const_.retain(|g| keep_output(g.o_id));
+        self.uni
+            .retain(|g| keep_inputs(&g.i_ids) && keep_output(g.o_id));
+        self.monomial
+            .retain(|g| keep_inputs(&g.i_ids) && keep_output(g.o_id));
+        self.num_gates() != len_before
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    /// Run all optimization passes until a fixpoint is reached, then compact the wires.
+    pub fn optimize(&mut self) -> OptimizationReport {
+        assert!(
+            !self.rnd_coefs_identified,
+            "the circuit must be optimized before pre_process_gkr"
+        );
+
+        let input_size = |c: &Self| c.layers.iter().map(|l| 1usize << l.input_var_num).sum();
+        let num_gates = |c: &Self| c.layers.iter().map(|l| l.num_gates()).sum();
+        let mut report = OptimizationReport {
+            layers_before: self.layers.len(),
+            gates_before: num_gates(self),
+            input_size_before: input_size(self),
+            ..Default::default()
+        };
+
+        loop {
+            let mut changed = self.fold_constants();
+            changed |= self.merge_duplicate_gates();
+            changed |= self.remove_dead_gates();
+            changed |= self.merge_relay_layers();
+            if !changed {
+                break;
+            }
+        }
+        self.compact_wires();
+
+        report.layers_after = self.layers.len();
+        report.gates_after = num_gates(self);
+        report.input_size_after = input_size(self);
+        log::trace!("circuit optimized: {report:?}");
+        report
+    }
+
+    /// Merge constant gates writing to the same wire, and substitute wires that only carry a
+    /// constant into the gates of the next layer that read them.
+    pub fn fold_constants(&mut self) -> bool {
+        let mut changed = false;
+
+        for layer in &mut self.layers {
+            changed |= merge_duplicates(&mut layer.const_, false);
+        }
+
+        for i in 0..self.layers.len().saturating_sub(1) {
+            let (lower, upper) = self.layers.split_at_mut(i + 1);
+            let lower = lower.last().unwrap();
+            let upper = &mut upper[0];
+
+            let mut non_constant_wires: BTreeSet<usize> = lower
+                .mul
+                .iter()
+                .map(|g| g.o_id)
+                .chain(lower.add.iter().map(|g| g.o_id))
+                .chain(lower.uni.iter().map(|g| g.o_id))
+                .chain(lower.monomial.iter().map(|g| g.o_id))
+                .collect();
+            non_constant_wires.extend(
+                lower
+                    .const_
+                    .iter()
+                    .filter(|g| g.coef_type != CoefType::Constant)
+                    .map(|g| g.o_id),
+            );
+            let constants: BTreeMap<usize, C::CircuitField> = lower
+                .const_
+                .iter()
+                .filter(|g| !non_constant_wires.contains(&g.o_id))
+                .map(|g| (g.o_id, g.coef))
+                .collect();
+            if constants.is_empty() {
+                continue;
+            }
+
+            let mut new_consts = vec![];
+            let mut new_adds = vec![];
+
+            upper.mul.retain(|g| {
+                if g.coef_type != CoefType::Constant {
+                    return true;
+                }
+                match (constants.get(&g.i_ids[0]), constants.get(&g.i_ids[1])) {
+                    (Some(k0), Some(k1)) => new_consts.push(GateConst::<C> {
+                        i_ids: [],
+                        o_id: g.o_id,
+                        coef_type: CoefType::Constant,
+                        coef: g.coef * *k0 * *k1,
+                        gate_type: 0,
+                    }),
+                    (Some(k), None) | (None, Some(k)) => {
+                        let i_id = if constants.contains_key(&g.i_ids[0]) {
+                            g.i_ids[1]
+                        } else {
+                            g.i_ids[0]
+                        };
+                        new_adds.push(GateAdd::<C> {
+                            i_ids: [i_id],
+                            o_id: g.o_id,
+                            coef_type: CoefType::Constant,
+                            coef: g.coef * *k,
+                            gate_type: 0,
+                        })
+                    }
+                    (None, None) => return true,
+                }
+                false
+            });
+
+            let mut fold_fan_in_one = |g: &GateUni<C>, k: C::CircuitField| {
+                new_consts.push(GateConst::<C> {
+                    i_ids: [],
+                    o_id: g.o_id,
+                    coef_type: CoefType::Constant,
+                    coef: g.coef * k,
+                    gate_type: 0,
+                });
+            };
+            upper.add.retain(|g| match constants.get(&g.i_ids[0]) {
+                Some(k) if g.coef_type == CoefType::Constant => {
+                    fold_fan_in_one(g, *k);
+                    false
+                }
+                _ => true,
+            });
+            upper.uni.retain(|g| match constants.get(&g.i_ids[0]) {
+                Some(k) if g.coef_type == CoefType::Constant => match g.gate_type {
+                    UNI_GATE_TYPE_POW5 => {
+                        fold_fan_in_one(g, k.exp(5));
+                        false
+                    }
+                    UNI_GATE_TYPE_POW1 => {
+                        fold_fan_in_one(g, *k);
+                        false
+                    }
+                    _ => true,
+                },
+                _ => true,
+            });
+
+            if !new_consts.is_empty() || !new_adds.is_empty() {
+                changed = true;
+                upper.const_.extend(new_consts);
+                upper.add.extend(new_adds);
+                merge_duplicates(&mut upper.const_, false);
+            }
+        }
+
+        changed
+    }
+
+    /// Merge gates with identical wiring into one gate, and drop gates with a zero coefficient.
+    pub fn merge_duplicate_gates(&mut self) -> bool {
+        let mut changed = false;
+        for layer in &mut self.layers {
+            changed |= merge_duplicates(&mut layer.mul, true);
+            changed |= merge_duplicates(&mut layer.add, false);
+            changed |= merge_duplicates(&mut layer.const_, false);
+            changed |= merge_duplicates(&mut layer.uni, false);
+        }
+        changed
+    }
+
+    /// Remove gates whose outputs are never read by the next layer, and gates reading wires
+    /// that are never written, as those always carry zero.
+    /// The outputs of the last layer and the inputs of the first layer are always kept.
+    pub fn remove_dead_gates(&mut self) -> bool {
+        let mut changed = false;
+
+        for i in 1..self.layers.len() {
+            let written = self.layers[i - 1].used_output_ids();
+            changed |= self.layers[i].retain_gates(
+                |i_ids| i_ids.iter().all(|id| written.contains(id)),
+                |_| true,
+            );
+        }
+
+        for i in (0..self.layers.len().saturating_sub(1)).rev() {
+            let read = self.layers[i + 1].used_input_ids();
+            changed |= self.layers[i].retain_gates(|_| true, |o_id| read.contains(&o_id));
+        }
+
+        changed
+    }
+
+    /// Merge each relay layer into one of its neighbours.
+    pub fn merge_relay_layers(&mut self) -> bool {
+        let mut changed = false;
+        let mut i = 0;
+        while i + 1 < self.layers.len() {
+            match Self::compose_layers(&self.layers[i], &self.layers[i + 1]) {
+                Some(merged) => {
+                    log::trace!("merged layers {} and {}", i, i + 1);
+                    self.layers[i] = merged;
+                    self.layers.remove(i + 1);
+                    changed = true;
+                }
+                None => i += 1,
+            }
+        }
+        changed
+    }
+
+    /// Compose two consecutive layers into one if either of them is a relay layer and the
+    /// result does not need more gates than the two layers together.
+    fn compose_layers(lower: &CircuitLayer<C>, upper: &CircuitLayer<C>) -> Option<CircuitLayer<C>> {
+        Self::compose_into_relay(lower, upper).or_else(|| Self::compose_from_relay(lower, upper))
+    }
+
+    /// `upper` is a relay layer: substitute every relayed wire by the gates of `lower`
+    /// writing it, scaled by the relay coefficient.
+    fn compose_into_relay(
+        lower: &CircuitLayer<C>,
+        upper: &CircuitLayer<C>,
+    ) -> Option<CircuitLayer<C>> {
+        // monomial gates of `lower` are not substituted into the relays
+        if !upper.is_relay_layer() || !lower.monomial.is_empty() {
+            return None;
+        }
+
+        let mut n_reads: BTreeMap<usize, usize> = BTreeMap::new();
+        let relays: Vec<(usize, usize, C::CircuitField)> = upper
+            .add
+            .iter()
+            .chain(upper.uni.iter())
+            .map(|g| {
+                *n_reads.entry(g.i_ids[0]).or_default() += 1;
+                (g.i_ids[0], g.o_id, g.coef)
+            })
+            .collect();
+
+        // a random coefficient is sampled once per gate, so a gate carrying one
+        // must not be copied into several places
+        let has_random = |coef_type: CoefType| coef_type == CoefType::Random;
+        let random_wires: BTreeSet<usize> = lower
+            .mul
+            .iter()
+            .filter(|g| has_random(g.coef_type))
+            .map(|g| g.o_id)
+            .chain(
+                lower
+                    .add
+                    .iter()
+                    .filter(|g| has_random(g.coef_type))
+                    .map(|g| g.o_id),
+            )
+            .chain(
+                lower
+                    .const_
+                    .iter()
+                    .filter(|g| has_random(g.coef_type))
+                    .map(|g| g.o_id),
+            )
+            .chain(
+                lower
+                    .uni
+                    .iter()
+                    .filter(|g| has_random(g.coef_type))
+                    .map(|g| g.o_id),
+            )
+            .collect();
+        if random_wires
+            .iter()
+            .any(|w| n_reads.get(w).copied().unwrap_or(0) > 1)
+        {
+            return None;
+        }
+
+        let mut writers: BTreeMap<usize, usize> = BTreeMap::new();
+        lower
+            .mul
+            .iter()
+            .map(|g| g.o_id)
+            .chain(lower.add.iter().map(|g| g.o_id))
+            .chain(lower.const_.iter().map(|g| g.o_id))
+            .chain(lower.uni.iter().map(|g| g.o_id))
+            .for_each(|o_id| *writers.entry(o_id).or_default() += 1);
+        let n_merged_gates: usize = relays
+            .iter()
+            .map(|(w, _, _)| writers.get(w).copied().unwrap_or(0))
+            .sum();
+        if n_merged_gates > lower.num_gates() + upper.num_gates() {
+            return None;
+        }
+
+        let mut merged = CircuitLayer::<C> {
+            input_var_num: lower.input_var_num,
+            output_var_num: upper.output_var_num,
+            ..Default::default()
+        };
+
+        for (w, o_id, coef) in relays {
+            let factor = (CoefType::Constant, coef);
+            for g in lower.mul.iter().filter(|g| g.o_id == w) {
+                let coef = combine_coefs((g.coef_type, g.coef), factor)?;
+                merged.mul.push(Gate {
+                    o_id,
+                    ..with_coef(g, coef)
+                });
+            }
+            for g in lower.add.iter().filter(|g| g.o_id == w) {
+                let coef = combine_coefs((g.coef_type, g.coef), factor)?;
+                merged.add.push(Gate {
+                    o_id,
+                    ..with_coef(g, coef)
+                });
+            }
+            for g in lower.const_.iter().filter(|g| g.o_id == w) {
+                let coef = combine_coefs((g.coef_type, g.coef), factor)?;
+                merged.const_.push(Gate {
+                    o_id,
+                    ..with_coef(g, coef)
+                });
+            }
+            for g in lower.uni.iter().filter(|g| g.o_id == w) {
+                let coef = combine_coefs((g.coef_type, g.coef), factor)?;
+                merged.uni.push(Gate {
+                    o_id,
+                    ..with_coef(g, coef)
+                });
+            }
+        }
+
+        Some(merged)
+    }
+
+    /// `lower` is a relay layer where every output is written by one gate: rename the inputs of
+    /// `upper` to the relayed wires and fold the relay coefficients into its gates.
+    fn compose_from_relay(
+        lower: &CircuitLayer<C>,
+        upper: &CircuitLayer<C>,
+    ) -> Option<CircuitLayer<C>> {
+        let relays = lower.relay_map()?;
+
+        let mut merged = CircuitLayer::<C> {
+            input_var_num: lower.input_var_num,
+            output_var_num: upper.output_var_num,
+            const_: upper.const_.clone(),
+            ..Default::default()
+        };
+
+        // wires without a relay carry zero, gates reading them are dropped
+        for g in &upper.mul {
+            if let (Some(&(i0, c0)), Some(&(i1, c1))) =
+                (relays.get(&g.i_ids[0]), relays.get(&g.i_ids[1]))
+            {
+                let coef = combine_coefs((g.coef_type, g.coef), (CoefType::Constant, c0 * c1))?;
+                merged.mul.push(Gate {
+                    i_ids: [i0, i1],
+                    ..with_coef(g, coef)
+                });
+            }
+        }
+        for g in &upper.add {
+            if let Some(&(i0, c0)) = relays.get(&g.i_ids[0]) {
+                let coef = combine_coefs((g.coef_type, g.coef), (CoefType::Constant, c0))?;
+                merged.add.push(Gate {
+                    i_ids: [i0],
+                    ..with_coef(g, coef)
+                });
+            }
+        }
+        for g in &upper.uni {
+            if let Some(&(i0, c0)) = relays.get(&g.i_ids[0]) {
+                let factor = match g.gate_type {
+                    UNI_GATE_TYPE_POW5 => c0.exp(5),
+                    UNI_GATE_TYPE_POW1 => c0,
+                    _ => return None,
+                };
+                let coef = combine_coefs((g.coef_type, g.coef), (CoefType::Constant, factor))?;
+                merged.uni.push(Gate {
+                    i_ids: [i0],
+                    ..with_coef(g, coef)
+                });
+            }
+        }
+        for g in &upper.monomial {
+            let relayed: Option<Vec<(usize, C::CircuitField)>> =
+                g.i_ids.iter().map(|i| relays.get(i).copied()).collect();
+            if let Some(relayed) = relayed {
+                let factor: C::CircuitField = relayed
+                    .iter()
+                    .zip(&g.exponents)
+                    .map(|((_, c), e)| c.exp(*e as u128))
+                    .product();
+                let (coef_type, coef) =
+                    combine_coefs((g.coef_type, g.coef), (CoefType::Constant, factor))?;
+                merged.monomial.push(GateMonomial {
+                    i_ids: relayed.iter().map(|(i, _)| *i).collect(),
+                    exponents: g.exponents.clone(),
+                    o_id: g.o_id,
+                    coef_type,
+                    coef,
+                });
+            }
+        }
+
+        Some(merged)
+    }
+
+    /// Renumber the wires between consecutive layers densely, shrinking `output_var_num` of the
+    /// lower layer and `input_var_num` of the upper layer where possible.
+    pub fn compact_wires(&mut self) -> bool {
+        let mut changed = false;
+
+        for i in 0..self.layers.len().saturating_sub(1) {
+            let live: Vec<usize> = self.layers[i]
+                .used_output_ids()
+                .intersection(&self.layers[i + 1].used_input_ids())
+                .copied()
+                .collect();
+            let new_var_num = var_num_for(live.len());
+            if new_var_num >= self.layers[i].output_var_num {
+                continue;
+            }
+
+            let remap: BTreeMap<usize, usize> = live
+                .iter()
+                .enumerate()
+                .map(|(new, &old)| (old, new))
+                .collect();
+            let (lower, upper) = self.layers.split_at_mut(i + 1);
+            let lower = lower.last_mut().unwrap();
+            let upper = &mut upper[0];
+
+            // gates outside of `live` are dead and would have been removed by `remove_dead_gates`
+            lower.retain_gates(|_| true, |o_id| remap.contains_key(&o_id));
+            lower.mul.iter_mut().for_each(|g| g.o_id = remap[&g.o_id]);
+            lower.add.iter_mut().for_each(|g| g.o_id = remap[&g.o_id]);
+            lower
+                .const_
+                .iter_mut()
+                .for_each(|g| g.o_id = remap[&g.o_id]);
+            lower.uni.iter_mut().for_each(|g| g.o_id = remap[&g.o_id]);
+            lower
+                .monomial
+                .iter_mut()
+                .for_each(|g| g.o_id = remap[&g.o_id]);
+            lower.output_var_num = new_var_num;
+
+            upper.retain_gates(
+                |i_ids| i_ids.iter().all(|id| remap.contains_key(id)),
+                |_| true,
+            );
+            upper
+                .mul
+                .iter_mut()
+                .for_each(|g| g.i_ids = g.i_ids.map(|id| remap[&id]));
+            upper
+                .add
+                .iter_mut()
+                .for_each(|g| g.i_ids = [remap[&g.i_ids[0]]]);
+            upper
+                .uni
+                .iter_mut()
+                .for_each(|g| g.i_ids = [remap[&g.i_ids[0]]]);
+            upper
+                .monomial
+                .iter_mut()
+                .flat_map(|g| g.i_ids.iter_mut())
+                .for_each(|id| *id = remap[&*id]);
+            upper.input_var_num = new_var_num;
+
+            changed = true;
+        }
+
+        changed
+    }
+}
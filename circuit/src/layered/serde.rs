@@ -1,11 +1,16 @@
 use std::io::{Read, Write};
 
 use arith::Field;
+use ethnum::U256;
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 
 use super::circuit::{Circuit, CircuitLayer, StructureInfo};
-use super::gates::{CoefType, Gate, GateAdd, GateConst, GateMul, GateUni};
+use super::gates::{
+    CoefType, Gate, GateAdd, GateConst, GateMonomial, GateMul, GateUni, CUSTOM_GATE_TYPE_MONOMIAL,
+};
+use super::lookup::Lookup;
+use crate::CustomGateWrapper;
 
 impl ExpSerde for CoefType {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
@@ -97,6 +102,96 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for Gate<C, INPUT_NUM> {
     }
 }
 
+type RawCustomGate<C> = (
+    usize,
+    Vec<usize>,
+    usize,
+    CoefType,
+    <C as FieldEngine>::CircuitField,
+);
+
+/// Read a custom gate of any fan-in: gate type, inputs, output and coefficient
+fn deserialize_custom_gate<C: FieldEngine, R: Read>(
+    mut reader: R,
+) -> SerdeResult<RawCustomGate<C>> {
+    let gate_type = usize::deserialize_from(&mut reader)?;
+    let i_ids = Vec::<usize>::deserialize_from(&mut reader)?;
+    let o_id = usize::deserialize_from(&mut reader)?;
+    let (coef_type, coef) = match u8::deserialize_from(&mut reader)? {
+        1 => (
+            CoefType::Constant,
+            C::CircuitField::deserialize_from(&mut reader)?,
+        ),
+        2 => (CoefType::Random, C::CircuitField::ZERO),
+        // public inputs can only be used with constant gates
+        _ => return Err(SerdeError::DeserializeError),
+    };
+    Ok((gate_type, i_ids, o_id, coef_type, coef))
+}
+
+impl<C: FieldEngine> GateMonomial<C> {
+    /// Group the repeated inputs of the custom gate encoding into exponents
+    fn from_custom_gate(
+        i_ids: &[usize],
+        o_id: usize,
+        coef_type: CoefType,
+        coef: C::CircuitField,
+    ) -> Self {
+        let mut gate = GateMonomial {
+            i_ids: vec![],
+            exponents: vec![],
+            o_id,
+            coef_type,
+            coef,
+        };
+        for &i_id in i_ids {
+            if gate.i_ids.last() == Some(&i_id) {
+                *gate.exponents.last_mut().unwrap() += 1;
+            } else {
+                gate.i_ids.push(i_id);
+                gate.exponents.push(1);
+            }
+        }
+        gate
+    }
+}
+
+/// Monomial gates use the custom gate encoding, with each input repeated as many times as its
+/// exponent, so that they can share the list of custom gates with uni gates
+impl<C: FieldEngine> ExpSerde for GateMonomial<C> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        CUSTOM_GATE_TYPE_MONOMIAL.serialize_into(&mut writer)?;
+        self.i_ids
+            .iter()
+            .zip(&self.exponents)
+            .flat_map(|(i_id, e)| std::iter::repeat_n(*i_id, *e))
+            .collect::<Vec<usize>>()
+            .serialize_into(&mut writer)?;
+        self.o_id.serialize_into(&mut writer)?;
+        match self.coef_type {
+            CoefType::Constant => {
+                1u8.serialize_into(&mut writer)?;
+                self.coef.serialize_into(&mut writer)?;
+            }
+            CoefType::Random => {
+                2u8.serialize_into(&mut writer)?;
+            }
+            CoefType::PublicInput(_) => {
+                panic!("Public Input can only be used with constant gates")
+            }
+        }
+        Ok(())
+    }
+
+    fn deserialize_from<R: Read>(reader: R) -> SerdeResult<Self> {
+        let (gate_type, i_ids, o_id, coef_type, coef) = deserialize_custom_gate::<C, R>(reader)?;
+        if gate_type != CUSTOM_GATE_TYPE_MONOMIAL {
+            return Err(SerdeError::DeserializeError);
+        }
+        Ok(Self::from_custom_gate(&i_ids, o_id, coef_type, coef))
+    }
+}
+
 impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
         self.input_var_num.serialize_into(&mut writer)?;
@@ -104,7 +199,14 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
         self.mul.serialize_into(&mut writer)?;
         self.add.serialize_into(&mut writer)?;
         self.const_.serialize_into(&mut writer)?;
-        self.uni.serialize_into(&mut writer)?;
+        // uni gates are distinguished by their gate type, which `Gate` does not store
+        (self.uni.len() + self.monomial.len()).serialize_into(&mut writer)?;
+        for uni in &self.uni {
+            CustomGateWrapper::<C, 1> { custom_gate: *uni }.serialize_into(&mut writer)?;
+        }
+        for monomial in &self.monomial {
+            monomial.serialize_into(&mut writer)?;
+        }
         Ok(())
     }
 
@@ -114,7 +216,26 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
         let mul = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
         let add = Vec::<GateAdd<C>>::deserialize_from(&mut reader)?;
         let const_ = Vec::<GateConst<C>>::deserialize_from(&mut reader)?;
-        let uni = Vec::<GateUni<C>>::deserialize_from(&mut reader)?;
+        let custom_len = usize::deserialize_from(&mut reader)?;
+        let mut uni = vec![];
+        let mut monomial = vec![];
+        for _ in 0..custom_len {
+            let (gate_type, i_ids, o_id, coef_type, coef) =
+                deserialize_custom_gate::<C, _>(&mut reader)?;
+            if gate_type == CUSTOM_GATE_TYPE_MONOMIAL {
+                monomial.push(GateMonomial::from_custom_gate(
+                    &i_ids, o_id, coef_type, coef,
+                ));
+            } else {
+                uni.push(GateUni::<C> {
+                    i_ids: i_ids.try_into().map_err(|_| SerdeError::DeserializeError)?,
+                    o_id,
+                    coef_type,
+                    coef,
+                    gate_type,
+                });
+            }
+        }
         Ok(CircuitLayer {
             input_var_num,
             output_var_num,
@@ -126,22 +247,68 @@ impl<C: FieldEngine> ExpSerde for CircuitLayer<C> {
             add,
             const_,
             uni,
+            monomial,
 
             structure_info: StructureInfo::default(),
         })
     }
 }
 
+impl<C: FieldEngine> ExpSerde for Lookup<C> {
+    fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.table.serialize_into(&mut writer)?;
+        self.input_offset.serialize_into(&mut writer)?;
+        self.log_input_num.serialize_into(&mut writer)?;
+        Ok(())
+    }
+
+    fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        Ok(Self {
+            table: Vec::<C::CircuitField>::deserialize_from(&mut reader)?,
+            input_offset: usize::deserialize_from(&mut reader)?,
+            log_input_num: usize::deserialize_from(&mut reader)?,
+        })
+    }
+}
+
+/// The layout of layered circuits, whose uni gates store their gate type, which start with the
+/// modulus and the number of expected zero outputs and end with the lookups. The layers of older
+/// circuits directly followed their count, which is rejected as an unsupported version.
+const LAYERED_VERSION_NUM: usize = 3622096219965833548; // b'LAYERED2'
+
 impl<C: FieldEngine> ExpSerde for Circuit<C> {
     fn serialize_into<W: Write>(&self, mut writer: W) -> SerdeResult<()> {
+        LAYERED_VERSION_NUM.serialize_into(&mut writer)?;
+        C::CircuitField::MODULUS.serialize_into(&mut writer)?;
+        self.expected_num_output_zeros.serialize_into(&mut writer)?;
         self.layers.serialize_into(&mut writer)?;
+        self.lookups.serialize_into(&mut writer)?;
         Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
+        let version_num = usize::deserialize_from(&mut reader)?;
+        if version_num != LAYERED_VERSION_NUM {
+            return Err(SerdeError::UnsupportedVersion(version_num));
+        }
+        let modulus = U256::deserialize_from(&mut reader)?;
+        if modulus != C::CircuitField::MODULUS {
+            return Err(SerdeError::DeserializeError);
+        }
+        let expected_num_output_zeros = usize::deserialize_from(&mut reader)?;
         let layers = Vec::<CircuitLayer<C>>::deserialize_from(&mut reader)?;
+        let lookups = Vec::<Lookup<C>>::deserialize_from(&mut reader)?;
+        let log_input_size = layers.first().map_or(0, |layer| layer.input_var_num);
+        if lookups
+            .iter()
+            .any(|lookup| lookup.validate(log_input_size).is_err())
+        {
+            return Err(SerdeError::DeserializeError);
+        }
         Ok(Circuit {
             layers,
+            expected_num_output_zeros,
+            lookups,
 
             ..Default::default()
         })
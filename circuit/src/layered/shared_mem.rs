@@ -1,7 +1,18 @@
 use super::circuit::{Circuit, CircuitLayer, StructureInfo};
-use super::gates::{GateAdd, GateConst, GateMul, GateUni};
+use super::gates::{GateAdd, GateConst, GateMonomial, GateMul, GateUni};
 
 use gkr_engine::{FieldEngine, MPISharedMemory};
+use serdes::ExpSerde;
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    /// Monomial gates own their inputs and cannot be laid out in shared memory as is,
+    /// they are shared serialized and every process deserializes its own copy
+    fn monomial_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.monomial.serialize_into(&mut bytes).unwrap();
+        bytes
+    }
+}
 
 impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
     fn bytes_size(&self) -> usize {
@@ -10,6 +21,7 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
             + self.add.bytes_size()
             + self.const_.bytes_size()
             + self.uni.bytes_size()
+            + self.monomial_bytes().bytes_size()
     }
 
     fn to_memory(&self, ptr: &mut *mut u8) {
@@ -19,6 +31,7 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
         self.add.to_memory(ptr);
         self.const_.to_memory(ptr);
         self.uni.to_memory(ptr);
+        self.monomial_bytes().to_memory(ptr);
     }
 
     fn new_from_memory(ptr: &mut *mut u8) -> Self {
@@ -28,6 +41,9 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
         let add = Vec::<GateAdd<C>>::new_from_memory(ptr);
         let const_ = Vec::<GateConst<C>>::new_from_memory(ptr);
         let uni = Vec::<GateUni<C>>::new_from_memory(ptr);
+        let monomial_bytes = Vec::<u8>::new_from_memory(ptr);
+        let monomial = Vec::<GateMonomial<C>>::deserialize_from(&monomial_bytes[..]).unwrap();
+        monomial_bytes.discard_control_of_shared_mem();
 
         CircuitLayer {
             input_var_num,
@@ -40,6 +56,7 @@ impl<C: FieldEngine> MPISharedMemory for CircuitLayer<C> {
             add,
             const_,
             uni,
+            monomial,
 
             structure_info: StructureInfo::default(),
         }
@@ -83,6 +100,7 @@ impl<C: FieldEngine> MPISharedMemory for Circuit<C> {
 
             public_input: vec![],
             expected_num_output_zeros,
+            lookups: vec![],
 
             rnd_coefs_identified: false,
             rnd_coefs: vec![],
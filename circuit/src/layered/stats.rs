@@ -0,0 +1,514 @@
+//! Structural statistics and lints for layered circuits.
+//!
+//! These are purely syntactic: nothing here evaluates the circuit, so the
+//! numbers can be collected right after loading, before any witness is attached.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use gkr_engine::FieldEngine;
+
+use super::circuit::{Circuit, CircuitLayer};
+use super::gates::{CoefType, Gate, UNI_GATE_TYPE_POW1, UNI_GATE_TYPE_POW5};
+use crate::RecursiveCircuit;
+
+/// A layer is flagged as heavily padded if less than `1 / PADDING_WARNING_RATIO`
+/// of its slots are used.
+const PADDING_WARNING_RATIO: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerStats {
+    pub input_var_num: usize,
+    pub output_var_num: usize,
+
+    pub n_mul: usize,
+    pub n_add: usize,
+    pub n_const: usize,
+    pub n_uni: usize,
+    pub n_monomial: usize,
+
+    /// Gates whose coefficient is sampled from the transcript
+    pub n_random_coefs: usize,
+    /// Constant gates whose value is a public input
+    pub n_public_input_coefs: usize,
+
+    /// Number of distinct input slots read by at least one gate
+    pub used_inputs: usize,
+    /// Number of distinct output slots written by at least one gate
+    pub used_outputs: usize,
+    /// Largest input slot referenced by a gate, if any
+    pub max_input_id: Option<usize>,
+    /// Largest output slot written by a gate, if any
+    pub max_output_id: Option<usize>,
+
+    /// Gates that share kind, inputs, output and coefficient with an earlier gate
+    pub duplicate_gates: usize,
+
+    /// Whether the layer only contains fan-in-one gates, i.e. leaves a single claim on its input
+    pub skip_sumcheck_phase_two: bool,
+}
+
+impl LayerStats {
+    #[inline]
+    pub fn num_gates(&self) -> usize {
+        self.n_mul + self.n_add + self.n_const + self.n_uni + self.n_monomial
+    }
+
+    #[inline]
+    pub fn input_size(&self) -> usize {
+        1 << self.input_var_num
+    }
+
+    #[inline]
+    pub fn output_size(&self) -> usize {
+        1 << self.output_var_num
+    }
+
+    /// Input slots that are allocated by the power-of-two padding but never read
+    #[inline]
+    pub fn wasted_inputs(&self) -> usize {
+        self.input_size().saturating_sub(self.used_inputs)
+    }
+
+    /// Output slots that are allocated by the power-of-two padding but never written
+    #[inline]
+    pub fn wasted_outputs(&self) -> usize {
+        self.output_size().saturating_sub(self.used_outputs)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitStats {
+    pub layers: Vec<LayerStats>,
+    /// Number of public inputs referenced by constant gates
+    pub num_public_inputs: usize,
+    pub expected_num_output_zeros: usize,
+}
+
+impl CircuitStats {
+    #[inline]
+    pub fn num_layers(&self) -> usize {
+        self.layers.len()
+    }
+
+    #[inline]
+    pub fn log_input_size(&self) -> usize {
+        self.layers.first().map_or(0, |l| l.input_var_num)
+    }
+
+    pub fn total_mul(&self) -> usize {
+        self.layers.iter().map(|l| l.n_mul).sum()
+    }
+
+    pub fn total_add(&self) -> usize {
+        self.layers.iter().map(|l| l.n_add).sum()
+    }
+
+    pub fn total_const(&self) -> usize {
+        self.layers.iter().map(|l| l.n_const).sum()
+    }
+
+    pub fn total_uni(&self) -> usize {
+        self.layers.iter().map(|l| l.n_uni).sum()
+    }
+
+    pub fn total_monomial(&self) -> usize {
+        self.layers.iter().map(|l| l.n_monomial).sum()
+    }
+
+    pub fn total_gates(&self) -> usize {
+        self.layers.iter().map(|l| l.num_gates()).sum()
+    }
+
+    pub fn total_duplicate_gates(&self) -> usize {
+        self.layers.iter().map(|l| l.duplicate_gates).sum()
+    }
+
+    pub fn total_wasted_inputs(&self) -> usize {
+        self.layers.iter().map(|l| l.wasted_inputs()).sum()
+    }
+
+    /// Number of layers that only run the first phase of the sumcheck
+    pub fn num_skip_sumcheck_phase_two(&self) -> usize {
+        self.layers
+            .iter()
+            .filter(|l| l.skip_sumcheck_phase_two)
+            .count()
+    }
+}
+
+impl fmt::Display for CircuitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Number of layers: {}", self.num_layers())?;
+        writeln!(f, "Number of public inputs: {}", self.num_public_inputs)?;
+        writeln!(
+            f,
+            "Expected number of output zeros: {}",
+            self.expected_num_output_zeros
+        )?;
+        writeln!(
+            f,
+            "{:>5} {:>5} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>6} {:>7}",
+            "layer",
+            "i_var",
+            "o_var",
+            "mul",
+            "add",
+            "const",
+            "uni",
+            "unused_in",
+            "unused_out",
+            "dup",
+            "phase2"
+        )?;
+        for (i, l) in self.layers.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5} {:>5} {:>5} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>6} {:>7}",
+                i,
+                l.input_var_num,
+                l.output_var_num,
+                l.n_mul,
+                l.n_add,
+                l.n_const,
+                l.n_uni,
+                l.wasted_inputs(),
+                l.wasted_outputs(),
+                l.duplicate_gates,
+                if l.skip_sumcheck_phase_two {
+                    "no"
+                } else {
+                    "yes"
+                },
+            )?;
+        }
+        writeln!(
+            f,
+            "Total gates: {} (mul: {}, add: {}, const: {}, uni: {}, monomial: {})",
+            self.total_gates(),
+            self.total_mul(),
+            self.total_add(),
+            self.total_const(),
+            self.total_uni(),
+            self.total_monomial()
+        )?;
+        writeln!(f, "Duplicate gates: {}", self.total_duplicate_gates())?;
+        write!(
+            f,
+            "Layers skipping sumcheck phase two: {}/{}",
+            self.num_skip_sumcheck_phase_two(),
+            self.num_layers()
+        )
+    }
+}
+
+/// Suspicious structures found by `Circuit::lint`.
+/// None of them makes a circuit unsound, but most of them waste prover time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitLint {
+    /// A gate references a slot outside of the layer's declared size
+    OutOfRange {
+        layer: usize,
+        kind: &'static str,
+        id: usize,
+        size: usize,
+    },
+    /// A uni gate carries a gate type unknown to the evaluator
+    UnknownUniGateType { layer: usize, gate_type: usize },
+    /// The layer has no gates at all
+    EmptyLayer { layer: usize },
+    /// Inputs allocated to the layer but never read
+    UnusedInputs { layer: usize, count: usize },
+    /// Outputs written by the layer but never read by the next layer
+    DeadOutputs { layer: usize, count: usize },
+    /// Several gates that could be merged into one
+    DuplicateGates { layer: usize, count: usize },
+    /// Most of the layer's input hypercube is padding
+    HeavyPadding {
+        layer: usize,
+        used: usize,
+        size: usize,
+    },
+    /// The expected number of zero outputs exceeds the output size
+    TooManyExpectedZeros { expected: usize, size: usize },
+}
+
+impl fmt::Display for CircuitLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitLint::OutOfRange {
+                layer,
+                kind,
+                id,
+                size,
+            } => write!(
+                f,
+                "layer {layer}: {kind} gate references slot {id} out of range {size}"
+            ),
+            CircuitLint::UnknownUniGateType { layer, gate_type } => {
+                write!(f, "layer {layer}: unknown uni gate type {gate_type}")
+            }
+            CircuitLint::EmptyLayer { layer } => write!(f, "layer {layer}: no gates"),
+            CircuitLint::UnusedInputs { layer, count } => {
+                write!(f, "layer {layer}: {count} input(s) are never read")
+            }
+            CircuitLint::DeadOutputs { layer, count } => write!(
+                f,
+                "layer {layer}: {count} output(s) are never read by the next layer"
+            ),
+            CircuitLint::DuplicateGates { layer, count } => {
+                write!(f, "layer {layer}: {count} duplicate gate(s) can be merged")
+            }
+            CircuitLint::HeavyPadding { layer, used, size } => write!(
+                f,
+                "layer {layer}: only {used} of {size} input slots are used"
+            ),
+            CircuitLint::TooManyExpectedZeros { expected, size } => write!(
+                f,
+                "expected {expected} zero outputs but the output layer only has {size} slots"
+            ),
+        }
+    }
+}
+
+/// Hashable identity of a gate, used for duplicate detection.
+/// Mul gates are commutative so their inputs are sorted.
+type GateKey = (u8, Vec<usize>, usize, Option<Vec<u8>>, usize);
+
+fn gate_key<C: FieldEngine, const INPUT_NUM: usize>(
+    kind: u8,
+    gate: &Gate<C, INPUT_NUM>,
+) -> GateKey {
+    let mut i_ids = gate.i_ids.to_vec();
+    i_ids.sort_unstable();
+    // random coefficients are independent per gate, so they never duplicate
+    let coef = match gate.coef_type {
+        CoefType::Constant => {
+            let mut buf = vec![];
+            serdes::ExpSerde::serialize_into(&gate.coef, &mut buf).unwrap();
+            Some(buf)
+        }
+        CoefType::Random => None,
+        CoefType::PublicInput(idx) => Some(idx.to_le_bytes().to_vec()),
+    };
+    (kind, i_ids, gate.o_id, coef, gate.gate_type)
+}
+
+impl<C: FieldEngine> CircuitLayer<C> {
+    /// Distinct input slots read by the gates of this layer
+    pub fn used_input_ids(&self) -> BTreeSet<usize> {
+        self.mul
+            .iter()
+            .flat_map(|g| g.i_ids)
+            .chain(self.add.iter().flat_map(|g| g.i_ids))
+            .chain(self.uni.iter().flat_map(|g| g.i_ids))
+            .chain(self.monomial.iter().flat_map(|g| g.i_ids.iter().copied()))
+            .collect()
+    }
+
+    /// Distinct output slots written by the gates of this layer
+    pub fn used_output_ids(&self) -> BTreeSet<usize> {
+        self.mul
+            .iter()
+            .map(|g| g.o_id)
+            .chain(self.add.iter().map(|g| g.o_id))
+            .chain(self.const_.iter().map(|g| g.o_id))
+            .chain(self.uni.iter().map(|g| g.o_id))
+            .chain(self.monomial.iter().map(|g| g.o_id))
+            .collect()
+    }
+
+    pub fn stats(&self) -> LayerStats {
+        let used_inputs = self.used_input_ids();
+        let used_outputs = self.used_output_ids();
+
+        let mut n_random_coefs = 0;
+        let mut n_public_input_coefs = 0;
+        let mut count_coef = |coef_type: &CoefType| match coef_type {
+            CoefType::Random => n_random_coefs += 1,
+            CoefType::PublicInput(_) => n_public_input_coefs += 1,
+            CoefType::Constant => {}
+        };
+        self.mul.iter().for_each(|g| count_coef(&g.coef_type));
+        self.add.iter().for_each(|g| count_coef(&g.coef_type));
+        self.const_.iter().for_each(|g| count_coef(&g.coef_type));
+        self.uni.iter().for_each(|g| count_coef(&g.coef_type));
+        self.monomial.iter().for_each(|g| count_coef(&g.coef_type));
+
+        let mut seen: HashMap<GateKey, usize> = HashMap::new();
+        self.mul
+            .iter()
+            .map(|g| gate_key(0, g))
+            .chain(self.add.iter().map(|g| gate_key(1, g)))
+            .chain(self.const_.iter().map(|g| gate_key(2, g)))
+            .chain(self.uni.iter().map(|g| gate_key(3, g)))
+            .filter(|key| key.3.is_some())
+            .for_each(|key| *seen.entry(key).or_default() += 1);
+        let duplicate_gates = seen.values().map(|cnt| cnt - 1).sum();
+
+        LayerStats {
+            input_var_num: self.input_var_num,
+            output_var_num: self.output_var_num,
+            n_mul: self.mul.len(),
+            n_add: self.add.len(),
+            n_const: self.const_.len(),
+            n_uni: self.uni.len(),
+            n_monomial: self.monomial.len(),
+            n_random_coefs,
+            n_public_input_coefs,
+            used_inputs: used_inputs.len(),
+            used_outputs: used_outputs.len(),
+            max_input_id: used_inputs.last().copied(),
+            max_output_id: used_outputs.last().copied(),
+            duplicate_gates,
+            skip_sumcheck_phase_two: self.mul.is_empty()
+                && self.monomial.iter().all(|g| g.i_ids.len() <= 1),
+        }
+    }
+}
+
+impl<C: FieldEngine> Circuit<C> {
+    pub fn stats(&self) -> CircuitStats {
+        let num_public_inputs = self
+            .layers
+            .iter()
+            .flat_map(|l| l.const_.iter())
+            .filter_map(|g| match g.coef_type {
+                CoefType::PublicInput(idx) => Some(idx + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        CircuitStats {
+            layers: self.layers.iter().map(|l| l.stats()).collect(),
+            num_public_inputs,
+            expected_num_output_zeros: self.expected_num_output_zeros,
+        }
+    }
+
+    /// Scan the circuit for structures that are legal but likely unintended.
+    pub fn lint(&self) -> Vec<CircuitLint> {
+        let mut lints = vec![];
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            let stats = layer.stats();
+            let input_size = stats.input_size();
+            let output_size = stats.output_size();
+
+            let out_of_range_inputs = layer
+                .mul
+                .iter()
+                .flat_map(|g| g.i_ids.map(|id| ("mul", id)))
+                .chain(layer.add.iter().map(|g| ("add", g.i_ids[0])))
+                .chain(layer.uni.iter().map(|g| ("uni", g.i_ids[0])))
+                .filter(|(_, id)| *id >= input_size)
+                .map(|(kind, id)| CircuitLint::OutOfRange {
+                    layer: i,
+                    kind,
+                    id,
+                    size: input_size,
+                });
+            let out_of_range_outputs = layer
+                .mul
+                .iter()
+                .map(|g| ("mul", g.o_id))
+                .chain(layer.add.iter().map(|g| ("add", g.o_id)))
+                .chain(layer.const_.iter().map(|g| ("const", g.o_id)))
+                .chain(layer.uni.iter().map(|g| ("uni", g.o_id)))
+                .filter(|(_, id)| *id >= output_size)
+                .map(|(kind, id)| CircuitLint::OutOfRange {
+                    layer: i,
+                    kind,
+                    id,
+                    size: output_size,
+                });
+            lints.extend(out_of_range_inputs.chain(out_of_range_outputs));
+
+            let unknown_uni_types: BTreeSet<usize> = layer
+                .uni
+                .iter()
+                .map(|g| g.gate_type)
+                .filter(|t| *t != UNI_GATE_TYPE_POW5 && *t != UNI_GATE_TYPE_POW1)
+                .collect();
+            lints.extend(unknown_uni_types.into_iter().map(|gate_type| {
+                CircuitLint::UnknownUniGateType {
+                    layer: i,
+                    gate_type,
+                }
+            }));
+
+            if stats.num_gates() == 0 {
+                lints.push(CircuitLint::EmptyLayer { layer: i });
+                continue;
+            }
+
+            // Unused slots beyond the largest referenced id are pure power-of-two padding,
+            // only holes below it indicate inputs that were allocated and then ignored.
+            let holes = stats
+                .max_input_id
+                .map_or(0, |max_id| max_id + 1 - stats.used_inputs);
+            if holes > 0 {
+                lints.push(CircuitLint::UnusedInputs {
+                    layer: i,
+                    count: holes,
+                });
+            }
+
+            if stats.used_inputs * PADDING_WARNING_RATIO < input_size {
+                lints.push(CircuitLint::HeavyPadding {
+                    layer: i,
+                    used: stats.used_inputs,
+                    size: input_size,
+                });
+            }
+
+            if stats.duplicate_gates > 0 {
+                lints.push(CircuitLint::DuplicateGates {
+                    layer: i,
+                    count: stats.duplicate_gates,
+                });
+            }
+
+            if let Some(next) = self.layers.get(i + 1) {
+                let read_by_next = next.used_input_ids();
+                let dead = layer
+                    .used_output_ids()
+                    .iter()
+                    .filter(|id| !read_by_next.contains(id))
+                    .count();
+                if dead > 0 {
+                    lints.push(CircuitLint::DeadOutputs {
+                        layer: i,
+                        count: dead,
+                    });
+                }
+            }
+        }
+
+        if let Some(output_layer) = self.layers.last() {
+            let size = 1 << output_layer.output_var_num;
+            if self.expected_num_output_zeros > size {
+                lints.push(CircuitLint::TooManyExpectedZeros {
+                    expected: self.expected_num_output_zeros,
+                    size,
+                });
+            }
+        }
+
+        lints
+    }
+}
+
+impl<C: FieldEngine> RecursiveCircuit<C> {
+    /// Statistics of the flattened circuit, i.e., the circuit that is actually proven.
+    pub fn stats(&self) -> CircuitStats {
+        let mut stats = self.flatten().stats();
+        stats.num_public_inputs = stats.num_public_inputs.max(self.num_public_inputs);
+        stats
+    }
+
+    pub fn lint(&self) -> Vec<CircuitLint> {
+        self.flatten().lint()
+    }
+}
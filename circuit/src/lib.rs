@@ -7,5 +7,13 @@ pub use layered::*;
 mod witness;
 pub use witness::*;
 
+#[cfg(feature = "prover")]
+mod witness_solver;
+#[cfg(feature = "prover")]
+pub use witness_solver::*;
+
 mod serde;
 pub use serde::*;
+
+mod text_format;
+pub use text_format::*;
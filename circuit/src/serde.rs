@@ -1,6 +1,6 @@
 use arith::Field;
 use gkr_engine::FieldEngine;
-use serdes::{ExpSerde, SerdeResult};
+use serdes::{ExpSerde, SerdeError, SerdeResult};
 use std::{
     io::{Read, Write},
     vec,
@@ -13,15 +13,34 @@ pub struct CustomGateWrapper<C: FieldEngine, const INPUT_NUM: usize> {
 }
 
 impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, INPUT_NUM> {
-    fn serialize_into<W: std::io::Write>(&self, mut _writer: W) -> SerdeResult<()> {
-        todo!()
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        let gate = &self.custom_gate;
+        gate.gate_type.serialize_into(&mut writer)?;
+        gate.i_ids.to_vec().serialize_into(&mut writer)?;
+        gate.o_id.serialize_into(&mut writer)?;
+
+        match gate.coef_type {
+            CoefType::Constant => {
+                1u8.serialize_into(&mut writer)?;
+                gate.coef.serialize_into(&mut writer)?;
+            }
+            CoefType::Random => {
+                2u8.serialize_into(&mut writer)?;
+            }
+            CoefType::PublicInput(id) => {
+                3u8.serialize_into(&mut writer)?;
+                id.serialize_into(&mut writer)?;
+            }
+        }
+
+        Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
-        let gate_type = <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
+        let gate_type = <usize as ExpSerde>::deserialize_from(&mut reader)?;
         let i_ids: [usize; INPUT_NUM] = <Vec<usize> as ExpSerde>::deserialize_from(&mut reader)?
             .try_into()
-            .unwrap();
+            .map_err(|_| SerdeError::DeserializeError)?;
 
         let o_id = <usize as ExpSerde>::deserialize_from(&mut reader)?;
 
@@ -33,8 +52,9 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, I
             ),
             2 => (CoefType::Random, C::CircuitField::ZERO),
             3 => {
+                // public inputs can only be used with constant gates
                 if INPUT_NUM > 0 {
-                    panic!("Public Input can only be used with constant gates")
+                    return Err(SerdeError::DeserializeError);
                 };
 
                 (
@@ -42,7 +62,7 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, I
                     C::CircuitField::ZERO,
                 )
             }
-            _ => unreachable!(),
+            _ => return Err(SerdeError::InvalidVariantIndex(coef_type_u8 as usize)),
         };
 
         Ok(Self {
@@ -59,8 +79,9 @@ impl<C: FieldEngine, const INPUT_NUM: usize> ExpSerde for CustomGateWrapper<C, I
 
 impl<C: FieldEngine> ExpSerde for Segment<C> {
     fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
-        <usize as ExpSerde>::serialize_into(&self.i_var_num, &mut writer)?;
-        <usize as ExpSerde>::serialize_into(&self.o_var_num, &mut writer)?;
+        // the lengths are stored, not the number of variables
+        <usize as ExpSerde>::serialize_into(&(1 << self.i_var_num), &mut writer)?;
+        <usize as ExpSerde>::serialize_into(&(1 << self.o_var_num), &mut writer)?;
 
         self.child_segs.serialize_into(&mut writer)?;
         self.gate_muls.serialize_into(&mut writer)?;
@@ -78,8 +99,9 @@ impl<C: FieldEngine> ExpSerde for Segment<C> {
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
         let i_len = <usize as ExpSerde>::deserialize_from(&mut reader)?;
         let o_len = <usize as ExpSerde>::deserialize_from(&mut reader)?;
-        assert!(i_len.is_power_of_two());
-        assert!(o_len.is_power_of_two());
+        if !i_len.is_power_of_two() || !o_len.is_power_of_two() {
+            return Err(SerdeError::DeserializeError);
+        }
 
         let child_segs = Vec::<(SegmentId, Vec<Allocation>)>::deserialize_from(&mut reader)?;
         let gate_muls = Vec::<GateMul<C>>::deserialize_from(&mut reader)?;
@@ -122,17 +144,19 @@ impl<C: FieldEngine> ExpSerde for RecursiveCircuit<C> {
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
-        let version_num = <usize as ExpSerde>::deserialize_from(&mut reader).unwrap();
-        assert_eq!(version_num, VERSION_NUM);
+        let version_num = <usize as ExpSerde>::deserialize_from(&mut reader)?;
         let expected_mod = <C::CircuitField as Field>::MODULUS;
         let mut read_mod = [0u8; 32];
-        reader.read_exact(&mut read_mod).unwrap();
-        assert_eq!(read_mod, expected_mod.to_le_bytes());
+        reader.read_exact(&mut read_mod)?;
+        // a circuit of another version or of another field
+        if version_num != VERSION_NUM || read_mod != expected_mod.to_le_bytes() {
+            return Err(SerdeError::DeserializeError);
+        }
 
         Ok(RecursiveCircuit {
-            num_public_inputs: <usize as ExpSerde>::deserialize_from(&mut reader).unwrap(),
-            num_outputs: <usize as ExpSerde>::deserialize_from(&mut reader).unwrap(),
-            expected_num_output_zeros: <usize as ExpSerde>::deserialize_from(&mut reader).unwrap(),
+            num_public_inputs: <usize as ExpSerde>::deserialize_from(&mut reader)?,
+            num_outputs: <usize as ExpSerde>::deserialize_from(&mut reader)?,
+            expected_num_output_zeros: <usize as ExpSerde>::deserialize_from(&mut reader)?,
 
             segments: Vec::<Segment<C>>::deserialize_from(&mut reader)?,
             layers: <Vec<usize> as ExpSerde>::deserialize_from(&mut reader)?,
@@ -141,8 +165,19 @@ impl<C: FieldEngine> ExpSerde for RecursiveCircuit<C> {
 }
 
 impl<C: FieldEngine> ExpSerde for Witness<C> {
-    fn serialize_into<W: std::io::Write>(&self, mut _writer: W) -> SerdeResult<()> {
-        todo!()
+    fn serialize_into<W: std::io::Write>(&self, mut writer: W) -> SerdeResult<()> {
+        self.num_witnesses.serialize_into(&mut writer)?;
+        self.num_private_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        self.num_public_inputs_per_witness
+            .serialize_into(&mut writer)?;
+        C::CircuitField::MODULUS.serialize_into(&mut writer)?;
+
+        for value in &self.values {
+            value.serialize_into(&mut writer)?;
+        }
+
+        Ok(())
     }
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
@@ -0,0 +1,681 @@
+//! A human readable text format for circuits and witnesses, for hand-writing small
+//! test circuits and inspecting the ones produced by the compiler.
+//! Conversion to and from the binary format is lossless.
+//!
+//! The format is line based: tokens are separated by whitespace and `#` starts a comment.
+//! Field elements are written as canonical integers, in decimal or in hex with a `0x` prefix.
+//! Gates are written as
+//!
+//! ```text
+//! mul <in_0> <in_1> <out> <coef>
+//! add <in> <out> <coef>
+//! const <out> <coef>
+//! uni <gate_type> <in> <out> <coef>
+//! monomial <in_0>^<e_0> ... <in_k>^<e_k> <out> <coef>
+//! ```
+//!
+//! where `coef` is a field element, `random`, or `public:<index>` for a public input.
+//! Monomial gates only appear in layered circuits, which end with their lookups, written as
+//!
+//! ```text
+//! lookup <input_offset> <log_input_num> <table_0> ... <table_k>
+//! ```
+
+use std::fmt::Write;
+
+use arith::Field;
+use ethnum::U256;
+use gkr_engine::FieldEngine;
+use thiserror::Error;
+
+use crate::{
+    Allocation, Circuit, CircuitLayer, CoefType, Gate, GateMonomial, Lookup, RecursiveCircuit,
+    Segment, Witness,
+};
+
+pub const RECURSIVE_CIRCUIT_HEADER: &str = "expander_circuit";
+pub const LAYERED_CIRCUIT_HEADER: &str = "expander_layered_circuit";
+pub const WITNESS_HEADER: &str = "expander_witness";
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("line {line}: {message}")]
+pub struct TextFormatError {
+    pub line: usize,
+    pub message: String,
+}
+
+pub type TextFormatResult<T> = Result<T, TextFormatError>;
+
+/// Conversion to and from the text format
+pub trait TextFormat: Sized {
+    fn to_text(&self) -> String;
+
+    fn from_text(text: &str) -> TextFormatResult<Self>;
+}
+
+/// The canonical decimal representation of a field element
+pub fn field_to_text<F: Field>(value: &F) -> String {
+    value.to_u256().to_string()
+}
+
+/// Parse a canonical field element, in decimal or in hex with a `0x` prefix
+pub fn field_from_text<F: Field>(token: &str) -> Result<F, String> {
+    let value = match token.strip_prefix("0x") {
+        Some(hex) => U256::from_str_radix(hex, 16),
+        None => U256::from_str_radix(token, 10),
+    }
+    .map_err(|_| format!("invalid field element `{token}`"))?;
+
+    if value >= F::MODULUS {
+        return Err(format!(
+            "field element `{token}` is not less than the modulus"
+        ));
+    }
+    Ok(F::from_u256(value))
+}
+
+/// The kind of object stored in a text file, read from its first line
+pub fn text_header_kind(text: &str) -> Option<&'static str> {
+    let first = text.lines().map(strip_comment).find(|l| !l.is_empty())?;
+    [
+        RECURSIVE_CIRCUIT_HEADER,
+        LAYERED_CIRCUIT_HEADER,
+        WITNESS_HEADER,
+    ]
+    .into_iter()
+    .find(|header| *header == first)
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split('#').next().unwrap().trim()
+}
+
+fn error<T>(line: usize, message: impl Into<String>) -> TextFormatResult<T> {
+    Err(TextFormatError {
+        line,
+        message: message.into(),
+    })
+}
+
+/// Non-empty lines of the input, split into tokens, along with their 1-based line numbers
+struct Lines<'a> {
+    lines: Vec<(usize, Vec<&'a str>)>,
+    pos: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, strip_comment(line).split_whitespace().collect()))
+            .filter(|(_, tokens): &(usize, Vec<&str>)| !tokens.is_empty())
+            .collect();
+        Self { lines, pos: 0 }
+    }
+
+    fn peek_keyword(&self) -> Option<&'a str> {
+        self.lines.get(self.pos).map(|(_, tokens)| tokens[0])
+    }
+
+    fn last_line(&self) -> usize {
+        self.lines.last().map_or(0, |(line, _)| *line)
+    }
+
+    /// The next line, which must start with `keyword`; returns the line number and the
+    /// remaining tokens
+    fn expect(&mut self, keyword: &str) -> TextFormatResult<(usize, Vec<&'a str>)> {
+        match self.lines.get(self.pos) {
+            Some((line, tokens)) if tokens[0] == keyword => {
+                self.pos += 1;
+                Ok((*line, tokens[1..].to_vec()))
+            }
+            Some((line, tokens)) => error(
+                *line,
+                format!("expected `{keyword}`, found `{}`", tokens[0]),
+            ),
+            None => error(
+                self.last_line(),
+                format!("expected `{keyword}`, found end of file"),
+            ),
+        }
+    }
+
+    fn expect_args(&mut self, keyword: &str, n: usize) -> TextFormatResult<(usize, Vec<&'a str>)> {
+        let (line, args) = self.expect(keyword)?;
+        if args.len() != n {
+            return error(
+                line,
+                format!("`{keyword}` takes {n} arguments, found {}", args.len()),
+            );
+        }
+        Ok((line, args))
+    }
+
+    fn expect_usize(&mut self, keyword: &str) -> TextFormatResult<usize> {
+        let (line, args) = self.expect_args(keyword, 1)?;
+        parse_usize(line, args[0])
+    }
+
+    fn expect_modulus<F: Field>(&mut self) -> TextFormatResult<()> {
+        let (line, args) = self.expect_args("modulus", 1)?;
+        if U256::from_str_radix(args[0], 10).ok() != Some(F::MODULUS) {
+            return error(
+                line,
+                format!("modulus {} does not match {}", args[0], F::MODULUS),
+            );
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> TextFormatResult<()> {
+        match self.lines.get(self.pos) {
+            Some((line, tokens)) => error(*line, format!("unexpected `{}`", tokens[0])),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_usize(line: usize, token: &str) -> TextFormatResult<usize> {
+    token
+        .parse()
+        .or_else(|_| error(line, format!("invalid integer `{token}`")))
+}
+
+fn parse_field<F: Field>(line: usize, token: &str) -> TextFormatResult<F> {
+    field_from_text(token).or_else(|message| error(line, message))
+}
+
+fn parse_coef<F: Field>(line: usize, token: &str) -> TextFormatResult<(CoefType, F)> {
+    if token == "random" {
+        Ok((CoefType::Random, F::ZERO))
+    } else if let Some(idx) = token.strip_prefix("public:") {
+        Ok((CoefType::PublicInput(parse_usize(line, idx)?), F::ZERO))
+    } else {
+        Ok((CoefType::Constant, parse_field(line, token)?))
+    }
+}
+
+fn coef_to_text<F: Field>(coef_type: &CoefType, coef: &F) -> String {
+    match coef_type {
+        CoefType::Constant => field_to_text(coef),
+        CoefType::Random => "random".to_string(),
+        CoefType::PublicInput(idx) => format!("public:{idx}"),
+    }
+}
+
+fn write_gate<C: FieldEngine, const INPUT_NUM: usize>(
+    out: &mut String,
+    keyword: &str,
+    gate: &Gate<C, INPUT_NUM>,
+) {
+    out.push_str(keyword);
+    if keyword == "uni" {
+        write!(out, " {}", gate.gate_type).unwrap();
+    }
+    for id in &gate.i_ids {
+        write!(out, " {id}").unwrap();
+    }
+    writeln!(
+        out,
+        " {} {}",
+        gate.o_id,
+        coef_to_text(&gate.coef_type, &gate.coef)
+    )
+    .unwrap();
+}
+
+/// Parse the arguments of a gate line: `[gate_type] <inputs> <out> <coef>`
+fn parse_gate<C: FieldEngine, const INPUT_NUM: usize>(
+    line: usize,
+    args: &[&str],
+    with_gate_type: bool,
+) -> TextFormatResult<Gate<C, INPUT_NUM>> {
+    let n_args = INPUT_NUM + 2 + with_gate_type as usize;
+    if args.len() != n_args {
+        return error(
+            line,
+            format!("gate takes {n_args} arguments, found {}", args.len()),
+        );
+    }
+
+    let (gate_type, args) = if with_gate_type {
+        (parse_usize(line, args[0])?, &args[1..])
+    } else {
+        (0, args)
+    };
+    let mut i_ids = [0usize; INPUT_NUM];
+    for (id, token) in i_ids.iter_mut().zip(args) {
+        *id = parse_usize(line, token)?;
+    }
+    let o_id = parse_usize(line, args[INPUT_NUM])?;
+    let (coef_type, coef) = parse_coef(line, args[INPUT_NUM + 1])?;
+    if INPUT_NUM > 0 && matches!(coef_type, CoefType::PublicInput(_)) {
+        return error(
+            line,
+            "public input coefficients are only allowed in const gates",
+        );
+    }
+
+    Ok(Gate {
+        i_ids,
+        o_id,
+        coef_type,
+        coef,
+        gate_type,
+    })
+}
+
+fn write_monomial<C: FieldEngine>(out: &mut String, gate: &GateMonomial<C>) {
+    out.push_str("monomial");
+    for (id, exponent) in gate.i_ids.iter().zip(&gate.exponents) {
+        write!(out, " {id}^{exponent}").unwrap();
+    }
+    writeln!(
+        out,
+        " {} {}",
+        gate.o_id,
+        coef_to_text(&gate.coef_type, &gate.coef)
+    )
+    .unwrap();
+}
+
+/// Parse the arguments of a monomial gate line: `<in>^<exponent>... <out> <coef>`
+fn parse_monomial<C: FieldEngine>(line: usize, args: &[&str]) -> TextFormatResult<GateMonomial<C>> {
+    if args.len() < 3 {
+        return error(
+            line,
+            format!(
+                "monomial gate takes at least 3 arguments, found {}",
+                args.len()
+            ),
+        );
+    }
+
+    let (inputs, args) = args.split_at(args.len() - 2);
+    let mut i_ids = Vec::with_capacity(inputs.len());
+    let mut exponents = Vec::with_capacity(inputs.len());
+    for token in inputs {
+        let Some((id, exponent)) = token.split_once('^') else {
+            return error(
+                line,
+                format!("invalid monomial input `{token}`, expected `<in>^<exponent>`"),
+            );
+        };
+        let exponent = parse_usize(line, exponent)?;
+        if exponent == 0 {
+            return error(line, format!("zero exponent in `{token}`"));
+        }
+        i_ids.push(parse_usize(line, id)?);
+        exponents.push(exponent);
+    }
+    let o_id = parse_usize(line, args[0])?;
+    let (coef_type, coef) = parse_coef(line, args[1])?;
+    if matches!(coef_type, CoefType::PublicInput(_)) {
+        return error(
+            line,
+            "public input coefficients are only allowed in const gates",
+        );
+    }
+
+    Ok(GateMonomial {
+        i_ids,
+        exponents,
+        o_id,
+        coef_type,
+        coef,
+    })
+}
+
+/// Gates of a segment or a layer, stored in the same four vectors, and the monomial gates of a
+/// layer
+struct Gates<'a, C: FieldEngine> {
+    mul: &'a mut Vec<Gate<C, 2>>,
+    add: &'a mut Vec<Gate<C, 1>>,
+    const_: &'a mut Vec<Gate<C, 0>>,
+    uni: &'a mut Vec<Gate<C, 1>>,
+    monomial: Option<&'a mut Vec<GateMonomial<C>>>,
+}
+
+impl<C: FieldEngine> Gates<'_, C> {
+    /// Parse the next line if it is a gate, returns whether it was
+    fn parse_next(&mut self, lines: &mut Lines) -> TextFormatResult<bool> {
+        match lines.peek_keyword() {
+            Some("mul") => {
+                let (line, args) = lines.expect("mul")?;
+                self.mul.push(parse_gate(line, &args, false)?);
+            }
+            Some("add") => {
+                let (line, args) = lines.expect("add")?;
+                self.add.push(parse_gate(line, &args, false)?);
+            }
+            Some("const") => {
+                let (line, args) = lines.expect("const")?;
+                self.const_.push(parse_gate(line, &args, false)?);
+            }
+            Some("uni") => {
+                let (line, args) = lines.expect("uni")?;
+                self.uni.push(parse_gate(line, &args, true)?);
+            }
+            Some("monomial") if self.monomial.is_some() => {
+                let (line, args) = lines.expect("monomial")?;
+                let gate = parse_monomial(line, &args)?;
+                self.monomial.as_mut().unwrap().push(gate);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn write_gates<C: FieldEngine>(
+    out: &mut String,
+    mul: &[Gate<C, 2>],
+    add: &[Gate<C, 1>],
+    const_: &[Gate<C, 0>],
+    uni: &[Gate<C, 1>],
+) {
+    mul.iter().for_each(|g| write_gate(out, "mul", g));
+    add.iter().for_each(|g| write_gate(out, "add", g));
+    const_.iter().for_each(|g| write_gate(out, "const", g));
+    uni.iter().for_each(|g| write_gate(out, "uni", g));
+}
+
+fn write_values<F: Field>(out: &mut String, keyword: &str, values: &[F]) {
+    out.push_str(keyword);
+    for v in values {
+        write!(out, " {}", field_to_text(v)).unwrap();
+    }
+    out.push('\n');
+}
+
+fn parse_values<F: Field>(
+    lines: &mut Lines,
+    keyword: &str,
+    expected_len: usize,
+) -> TextFormatResult<Vec<F>> {
+    let (line, args) = lines.expect_args(keyword, expected_len)?;
+    args.iter().map(|token| parse_field(line, token)).collect()
+}
+
+impl<C: FieldEngine> TextFormat for RecursiveCircuit<C> {
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{RECURSIVE_CIRCUIT_HEADER}").unwrap();
+        writeln!(out, "modulus {}", C::CircuitField::MODULUS).unwrap();
+        writeln!(out, "num_public_inputs {}", self.num_public_inputs).unwrap();
+        writeln!(out, "num_outputs {}", self.num_outputs).unwrap();
+        writeln!(
+            out,
+            "expected_num_output_zeros {}",
+            self.expected_num_output_zeros
+        )
+        .unwrap();
+
+        for (id, segment) in self.segments.iter().enumerate() {
+            writeln!(
+                out,
+                "\nsegment {id} {} {}",
+                segment.i_var_num, segment.o_var_num
+            )
+            .unwrap();
+            for (child_id, allocations) in &segment.child_segs {
+                write!(out, "child {child_id}").unwrap();
+                for a in allocations {
+                    write!(out, " {}:{}", a.i_offset, a.o_offset).unwrap();
+                }
+                out.push('\n');
+            }
+            write_gates(
+                &mut out,
+                &segment.gate_muls,
+                &segment.gate_adds,
+                &segment.gate_consts,
+                &segment.gate_uni,
+            );
+        }
+
+        out.push_str("\nlayers");
+        for id in &self.layers {
+            write!(out, " {id}").unwrap();
+        }
+        out.push('\n');
+        out
+    }
+
+    fn from_text(text: &str) -> TextFormatResult<Self> {
+        let mut lines = Lines::new(text);
+        lines.expect_args(RECURSIVE_CIRCUIT_HEADER, 0)?;
+        lines.expect_modulus::<C::CircuitField>()?;
+        let num_public_inputs = lines.expect_usize("num_public_inputs")?;
+        let num_outputs = lines.expect_usize("num_outputs")?;
+        let expected_num_output_zeros = lines.expect_usize("expected_num_output_zeros")?;
+
+        let mut segments = vec![];
+        while lines.peek_keyword() == Some("segment") {
+            let (line, args) = lines.expect_args("segment", 3)?;
+            if parse_usize(line, args[0])? != segments.len() {
+                return error(line, format!("expected segment {}", segments.len()));
+            }
+            let mut segment = Segment::<C> {
+                i_var_num: parse_usize(line, args[1])?,
+                o_var_num: parse_usize(line, args[2])?,
+                ..Default::default()
+            };
+
+            loop {
+                if lines.peek_keyword() == Some("child") {
+                    let (line, args) = lines.expect("child")?;
+                    let Some((child_id, allocations)) = args.split_first() else {
+                        return error(line, "`child` takes a segment id");
+                    };
+                    let allocations = allocations
+                        .iter()
+                        .map(|token| match token.split_once(':') {
+                            Some((i, o)) => Ok(Allocation {
+                                i_offset: parse_usize(line, i)?,
+                                o_offset: parse_usize(line, o)?,
+                            }),
+                            None => error(line, format!("invalid allocation `{token}`")),
+                        })
+                        .collect::<TextFormatResult<Vec<_>>>()?;
+                    segment
+                        .child_segs
+                        .push((parse_usize(line, child_id)?, allocations));
+                    continue;
+                }
+
+                let mut gates = Gates {
+                    mul: &mut segment.gate_muls,
+                    add: &mut segment.gate_adds,
+                    const_: &mut segment.gate_consts,
+                    uni: &mut segment.gate_uni,
+                    monomial: None,
+                };
+                if !gates.parse_next(&mut lines)? {
+                    break;
+                }
+            }
+            segments.push(segment);
+        }
+
+        let (line, args) = lines.expect("layers")?;
+        let layers = args
+            .iter()
+            .map(|token| parse_usize(line, token))
+            .collect::<TextFormatResult<Vec<_>>>()?;
+        if let Some(id) = layers.iter().find(|id| **id >= segments.len()) {
+            return error(line, format!("unknown segment {id}"));
+        }
+        lines.finish()?;
+
+        Ok(Self {
+            num_public_inputs,
+            num_outputs,
+            expected_num_output_zeros,
+            segments,
+            layers,
+        })
+    }
+}
+
+impl<C: FieldEngine> TextFormat for Circuit<C> {
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{LAYERED_CIRCUIT_HEADER}").unwrap();
+        writeln!(out, "modulus {}", C::CircuitField::MODULUS).unwrap();
+        writeln!(
+            out,
+            "expected_num_output_zeros {}",
+            self.expected_num_output_zeros
+        )
+        .unwrap();
+
+        for (i, layer) in self.layers.iter().enumerate() {
+            writeln!(
+                out,
+                "\n# layer {i}\nlayer {} {}",
+                layer.input_var_num, layer.output_var_num
+            )
+            .unwrap();
+            write_gates(&mut out, &layer.mul, &layer.add, &layer.const_, &layer.uni);
+            layer
+                .monomial
+                .iter()
+                .for_each(|g| write_monomial(&mut out, g));
+        }
+
+        if !self.lookups.is_empty() {
+            out.push('\n');
+        }
+        for lookup in &self.lookups {
+            let keyword = format!("lookup {} {}", lookup.input_offset, lookup.log_input_num);
+            write_values(&mut out, &keyword, &lookup.table);
+        }
+        out
+    }
+
+    fn from_text(text: &str) -> TextFormatResult<Self> {
+        let mut lines = Lines::new(text);
+        lines.expect_args(LAYERED_CIRCUIT_HEADER, 0)?;
+        lines.expect_modulus::<C::CircuitField>()?;
+        let expected_num_output_zeros = lines.expect_usize("expected_num_output_zeros")?;
+
+        let mut layers = vec![];
+        while lines.peek_keyword() == Some("layer") {
+            let (line, args) = lines.expect_args("layer", 2)?;
+            let mut layer = CircuitLayer::<C> {
+                input_var_num: parse_usize(line, args[0])?,
+                output_var_num: parse_usize(line, args[1])?,
+                ..Default::default()
+            };
+            let mut gates = Gates {
+                mul: &mut layer.mul,
+                add: &mut layer.add,
+                const_: &mut layer.const_,
+                uni: &mut layer.uni,
+                monomial: Some(&mut layer.monomial),
+            };
+            while gates.parse_next(&mut lines)? {}
+            layers.push(layer);
+        }
+
+        let log_input_size = layers.first().map_or(0, |layer| layer.input_var_num);
+        let mut lookups = vec![];
+        while lines.peek_keyword() == Some("lookup") {
+            let (line, args) = lines.expect("lookup")?;
+            if args.len() < 3 {
+                return error(
+                    line,
+                    format!("`lookup` takes at least 3 arguments, found {}", args.len()),
+                );
+            }
+            let lookup = Lookup {
+                table: args[2..]
+                    .iter()
+                    .map(|token| parse_field(line, token))
+                    .collect::<TextFormatResult<_>>()?,
+                input_offset: parse_usize(line, args[0])?,
+                log_input_num: parse_usize(line, args[1])?,
+            };
+            lookup
+                .validate(log_input_size)
+                .or_else(|message| error(line, message))?;
+            lookups.push(lookup);
+        }
+        lines.finish()?;
+
+        Ok(Self {
+            layers,
+            expected_num_output_zeros,
+            lookups,
+            ..Default::default()
+        })
+    }
+}
+
+impl<C: FieldEngine> TextFormat for Witness<C> {
+    fn to_text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "{WITNESS_HEADER}").unwrap();
+        writeln!(out, "modulus {}", C::CircuitField::MODULUS).unwrap();
+        writeln!(out, "num_witnesses {}", self.num_witnesses).unwrap();
+        writeln!(
+            out,
+            "num_private_inputs_per_witness {}",
+            self.num_private_inputs_per_witness
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "num_public_inputs_per_witness {}",
+            self.num_public_inputs_per_witness
+        )
+        .unwrap();
+
+        let witness_size = self.num_private_inputs_per_witness + self.num_public_inputs_per_witness;
+        if witness_size > 0 {
+            for (i, values) in self.values.chunks(witness_size).enumerate() {
+                let (private, public) = values.split_at(self.num_private_inputs_per_witness);
+                writeln!(out, "\n# witness {i}").unwrap();
+                write_values(&mut out, "private", private);
+                write_values(&mut out, "public", public);
+            }
+        }
+        out
+    }
+
+    fn from_text(text: &str) -> TextFormatResult<Self> {
+        let mut lines = Lines::new(text);
+        lines.expect_args(WITNESS_HEADER, 0)?;
+        lines.expect_modulus::<C::CircuitField>()?;
+        let num_witnesses = lines.expect_usize("num_witnesses")?;
+        let num_private_inputs_per_witness =
+            lines.expect_usize("num_private_inputs_per_witness")?;
+        let num_public_inputs_per_witness = lines.expect_usize("num_public_inputs_per_witness")?;
+
+        let mut values = Vec::with_capacity(
+            num_witnesses * (num_private_inputs_per_witness + num_public_inputs_per_witness),
+        );
+        for _ in 0..num_witnesses {
+            values.extend(parse_values::<C::CircuitField>(
+                &mut lines,
+                "private",
+                num_private_inputs_per_witness,
+            )?);
+            values.extend(parse_values::<C::CircuitField>(
+                &mut lines,
+                "public",
+                num_public_inputs_per_witness,
+            )?);
+        }
+        lines.finish()?;
+
+        Ok(Self {
+            num_witnesses,
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness,
+            values,
+        })
+    }
+}
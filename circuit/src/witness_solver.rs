@@ -0,0 +1,532 @@
+//! Witness generation from a small set of user inputs.
+//!
+//! A `WitnessProgram` declares the user inputs, the intermediate values and the hints
+//! needed to compute the full input layer of a circuit. Hints are plain Rust functions
+//! looked up by name in a `HintRegistry`, so that values which are expensive or impossible
+//! to express with gates (inverses, bit decompositions, ...) can be computed out of circuit
+//! and then constrained by the circuit itself.
+
+use std::collections::HashMap;
+
+use arith::Field;
+use ethnum::U256;
+use gkr_engine::FieldEngine;
+use thiserror::Error;
+
+use crate::{Circuit, Witness};
+
+pub type VarId = usize;
+
+/// A hint reads its input values and fills all of its outputs,
+/// or returns a human readable reason for failing.
+pub type HintFn<F> = Box<dyn Fn(&[F], &mut [F]) -> Result<(), String> + Send + Sync>;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WitnessSolverError {
+    #[error("unknown hint `{0}`")]
+    UnknownHint(String),
+
+    #[error("hint `{name}` failed: {reason}")]
+    HintFailed { name: String, reason: String },
+
+    #[error("expected {expected} {kind} inputs, got {got}")]
+    WrongNumberOfInputs {
+        kind: &'static str,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("variable {0} is read before being assigned")]
+    UnassignedVariable(VarId),
+
+    #[error("variable {0} is assigned twice")]
+    ReassignedVariable(VarId),
+
+    #[error("expected a power of two number of witnesses, got {0}")]
+    WrongNumberOfWitnesses(usize),
+
+    #[error("variable {0} is out of range")]
+    VariableOutOfRange(VarId),
+
+    #[error("public input {0} is out of range")]
+    PublicInputOutOfRange(usize),
+
+    #[error("the program reads {got} {kind} inputs, {expected} are declared")]
+    WrongNumberOfDeclaredInputs {
+        kind: &'static str,
+        expected: usize,
+        got: usize,
+    },
+
+    #[error("the program fills {got} input slots, the circuit has {expected}")]
+    WrongInputLayoutSize { expected: usize, got: usize },
+
+    #[error("input slot {slot} is out of range, the program has {num_slots}")]
+    InputSlotOutOfRange { slot: usize, num_slots: usize },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WitnessInstruction<F: Field> {
+    /// Read the next private user input
+    PrivateInput {
+        dst: VarId,
+    },
+    /// Read the public input at `index`
+    PublicInput {
+        dst: VarId,
+        index: usize,
+    },
+    Constant {
+        dst: VarId,
+        value: F,
+    },
+    /// dst = constant + sum(coef * var)
+    LinComb {
+        dst: VarId,
+        terms: Vec<(F, VarId)>,
+        constant: F,
+    },
+    /// dst = coef * lhs * rhs
+    Mul {
+        dst: VarId,
+        lhs: VarId,
+        rhs: VarId,
+        coef: F,
+    },
+    /// Call the hint registered under `name`
+    Hint {
+        name: String,
+        inputs: Vec<VarId>,
+        outputs: Vec<VarId>,
+    },
+}
+
+/// Hint functions available to the solver, keyed by name.
+pub struct HintRegistry<F: Field> {
+    hints: HashMap<String, HintFn<F>>,
+}
+
+impl<F: Field + 'static> Default for HintRegistry<F> {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("div", div_hint);
+        registry.register("bits", bits_hint);
+        registry.register("limbs", limbs_hint);
+        registry
+    }
+}
+
+impl<F: Field> HintRegistry<F> {
+    /// A registry without the builtin hints
+    pub fn empty() -> Self {
+        Self {
+            hints: HashMap::new(),
+        }
+    }
+
+    /// Register a hint, replacing any hint with the same name
+    pub fn register(
+        &mut self,
+        name: &str,
+        hint: impl Fn(&[F], &mut [F]) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        self.hints.insert(name.to_string(), Box::new(hint));
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.hints.contains_key(name)
+    }
+
+    pub fn call(
+        &self,
+        name: &str,
+        inputs: &[F],
+        outputs: &mut [F],
+    ) -> Result<(), WitnessSolverError> {
+        let hint = self
+            .hints
+            .get(name)
+            .ok_or_else(|| WitnessSolverError::UnknownHint(name.to_string()))?;
+        hint(inputs, outputs).map_err(|reason| WitnessSolverError::HintFailed {
+            name: name.to_string(),
+            reason,
+        })
+    }
+}
+
+/// `[a, b] -> [a / b]`
+pub fn div_hint<F: Field>(inputs: &[F], outputs: &mut [F]) -> Result<(), String> {
+    match (inputs, outputs) {
+        ([a, b], [q]) => {
+            let b_inv = b.inv().ok_or("division by zero")?;
+            *q = *a * b_inv;
+            Ok(())
+        }
+        _ => Err("expects 2 inputs and 1 output".to_string()),
+    }
+}
+
+/// `[x] -> [b_0, ..., b_{n-1}]`, little endian bits of x, fails if x does not fit in n bits
+pub fn bits_hint<F: Field>(inputs: &[F], outputs: &mut [F]) -> Result<(), String> {
+    match inputs {
+        [x] => decompose(x.to_u256(), 1, outputs),
+        _ => Err("expects 1 input".to_string()),
+    }
+}
+
+/// `[x, limb_bits] -> [l_0, ..., l_{n-1}]`, little endian limbs of x with `limb_bits` bits each,
+/// fails if x does not fit in n limbs, i.e., this doubles as a range hint
+pub fn limbs_hint<F: Field>(inputs: &[F], outputs: &mut [F]) -> Result<(), String> {
+    match inputs {
+        [x, limb_bits] => {
+            let limb_bits = limb_bits.to_u256();
+            if limb_bits == U256::ZERO || limb_bits > U256::from(128u32) {
+                return Err(format!("invalid limb size {limb_bits}"));
+            }
+            decompose(x.to_u256(), *limb_bits.low() as u32, outputs)
+        }
+        _ => Err("expects 2 inputs".to_string()),
+    }
+}
+
+fn decompose<F: Field>(mut x: U256, limb_bits: u32, outputs: &mut [F]) -> Result<(), String> {
+    let mask = (U256::ONE << limb_bits) - U256::ONE;
+    for limb in outputs.iter_mut() {
+        *limb = F::from_u256(x & mask);
+        x >>= limb_bits;
+    }
+    if x != U256::ZERO {
+        return Err(format!(
+            "value does not fit in {} limbs of {limb_bits} bits",
+            outputs.len()
+        ));
+    }
+    Ok(())
+}
+
+/// User inputs of a single witness
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WitnessInputs<F: Field> {
+    pub private_inputs: Vec<F>,
+    pub public_inputs: Vec<F>,
+}
+
+/// A straight-line program computing the input layer of a circuit.
+///
+/// Slot `i` of the input layer takes the value of variable `input_layout[i]`,
+/// slots without a variable are padding and set to zero.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WitnessProgram<C: FieldEngine> {
+    pub num_vars: usize,
+    pub num_private_inputs: usize,
+    pub num_public_inputs: usize,
+    pub instructions: Vec<WitnessInstruction<C::CircuitField>>,
+    pub input_layout: Vec<Option<VarId>>,
+}
+
+impl<C: FieldEngine> WitnessProgram<C> {
+    /// An empty program for a circuit with `2^log_input_size` input slots
+    pub fn new(log_input_size: usize) -> Self {
+        Self {
+            input_layout: vec![None; 1 << log_input_size],
+            ..Default::default()
+        }
+    }
+
+    #[inline]
+    fn new_var(&mut self) -> VarId {
+        self.num_vars += 1;
+        self.num_vars - 1
+    }
+
+    pub fn private_input(&mut self) -> VarId {
+        let dst = self.new_var();
+        self.num_private_inputs += 1;
+        self.instructions
+            .push(WitnessInstruction::PrivateInput { dst });
+        dst
+    }
+
+    pub fn public_input(&mut self) -> VarId {
+        let dst = self.new_var();
+        let index = self.num_public_inputs;
+        self.num_public_inputs += 1;
+        self.instructions
+            .push(WitnessInstruction::PublicInput { dst, index });
+        dst
+    }
+
+    pub fn constant(&mut self, value: C::CircuitField) -> VarId {
+        let dst = self.new_var();
+        self.instructions
+            .push(WitnessInstruction::Constant { dst, value });
+        dst
+    }
+
+    pub fn lin_comb(
+        &mut self,
+        terms: Vec<(C::CircuitField, VarId)>,
+        constant: C::CircuitField,
+    ) -> VarId {
+        let dst = self.new_var();
+        self.instructions.push(WitnessInstruction::LinComb {
+            dst,
+            terms,
+            constant,
+        });
+        dst
+    }
+
+    pub fn mul(&mut self, lhs: VarId, rhs: VarId) -> VarId {
+        let dst = self.new_var();
+        self.instructions.push(WitnessInstruction::Mul {
+            dst,
+            lhs,
+            rhs,
+            coef: C::CircuitField::ONE,
+        });
+        dst
+    }
+
+    /// Call the hint `name` on `inputs`, returning its `num_outputs` fresh output variables
+    pub fn hint(&mut self, name: &str, inputs: Vec<VarId>, num_outputs: usize) -> Vec<VarId> {
+        let outputs: Vec<VarId> = (0..num_outputs).map(|_| self.new_var()).collect();
+        self.instructions.push(WitnessInstruction::Hint {
+            name: name.to_string(),
+            inputs,
+            outputs: outputs.clone(),
+        });
+        outputs
+    }
+
+    /// Place the value of `var` at `slot` of the input layer
+    pub fn set_input_slot(&mut self, slot: usize, var: VarId) -> Result<(), WitnessSolverError> {
+        let num_slots = self.input_layout.len();
+        match self.input_layout.get_mut(slot) {
+            Some(entry) => {
+                *entry = Some(var);
+                Ok(())
+            }
+            None => Err(WitnessSolverError::InputSlotOutOfRange { slot, num_slots }),
+        }
+    }
+
+    /// Check that every hint used by the program is registered
+    pub fn check_hints(
+        &self,
+        hints: &HintRegistry<C::CircuitField>,
+    ) -> Result<(), WitnessSolverError> {
+        self.instructions
+            .iter()
+            .try_for_each(|instruction| match instruction {
+                WitnessInstruction::Hint { name, .. } if !hints.contains(name) => {
+                    Err(WitnessSolverError::UnknownHint(name.clone()))
+                }
+                _ => Ok(()),
+            })
+    }
+
+    /// Check that every variable, public input index and input slot used by the program is
+    /// in range, so that solving it cannot index out of bounds
+    pub fn validate(&self) -> Result<(), WitnessSolverError> {
+        let check_var = |var: &VarId| match *var < self.num_vars {
+            true => Ok(()),
+            false => Err(WitnessSolverError::VariableOutOfRange(*var)),
+        };
+
+        let mut num_private_inputs = 0;
+        for instruction in &self.instructions {
+            match instruction {
+                WitnessInstruction::PrivateInput { dst } => {
+                    num_private_inputs += 1;
+                    check_var(dst)?
+                }
+                WitnessInstruction::PublicInput { dst, index } => {
+                    if *index >= self.num_public_inputs {
+                        return Err(WitnessSolverError::PublicInputOutOfRange(*index));
+                    }
+                    check_var(dst)?
+                }
+                WitnessInstruction::Constant { dst, .. } => check_var(dst)?,
+                WitnessInstruction::LinComb { dst, terms, .. } => {
+                    terms.iter().try_for_each(|(_, var)| check_var(var))?;
+                    check_var(dst)?
+                }
+                WitnessInstruction::Mul { dst, lhs, rhs, .. } => {
+                    check_var(lhs)?;
+                    check_var(rhs)?;
+                    check_var(dst)?
+                }
+                WitnessInstruction::Hint {
+                    inputs, outputs, ..
+                } => {
+                    inputs.iter().try_for_each(check_var)?;
+                    outputs.iter().try_for_each(check_var)?
+                }
+            }
+        }
+        if num_private_inputs != self.num_private_inputs {
+            return Err(WitnessSolverError::WrongNumberOfDeclaredInputs {
+                kind: "private",
+                expected: self.num_private_inputs,
+                got: num_private_inputs,
+            });
+        }
+
+        self.input_layout.iter().flatten().try_for_each(check_var)
+    }
+
+    /// `validate`, and check that the program fills the input layer of `circuit` and provides
+    /// every public input its gates refer to
+    pub fn validate_for_circuit(&self, circuit: &Circuit<C>) -> Result<(), WitnessSolverError> {
+        self.validate()?;
+
+        let input_size = 1 << circuit.log_input_size();
+        if self.input_layout.len() != input_size {
+            return Err(WitnessSolverError::WrongInputLayoutSize {
+                expected: input_size,
+                got: self.input_layout.len(),
+            });
+        }
+        let num_public_inputs = circuit.stats().num_public_inputs;
+        if self.num_public_inputs < num_public_inputs {
+            return Err(WitnessSolverError::WrongNumberOfDeclaredInputs {
+                kind: "public",
+                expected: num_public_inputs,
+                got: self.num_public_inputs,
+            });
+        }
+        Ok(())
+    }
+
+    /// Run the program on a single set of user inputs, returning the input layer assignment
+    pub fn solve(
+        &self,
+        hints: &HintRegistry<C::CircuitField>,
+        inputs: &WitnessInputs<C::CircuitField>,
+    ) -> Result<Vec<C::CircuitField>, WitnessSolverError> {
+        self.validate()?;
+        self.solve_validated(hints, inputs)
+    }
+
+    fn solve_validated(
+        &self,
+        hints: &HintRegistry<C::CircuitField>,
+        inputs: &WitnessInputs<C::CircuitField>,
+    ) -> Result<Vec<C::CircuitField>, WitnessSolverError> {
+        if inputs.private_inputs.len() != self.num_private_inputs {
+            return Err(WitnessSolverError::WrongNumberOfInputs {
+                kind: "private",
+                expected: self.num_private_inputs,
+                got: inputs.private_inputs.len(),
+            });
+        }
+        if inputs.public_inputs.len() != self.num_public_inputs {
+            return Err(WitnessSolverError::WrongNumberOfInputs {
+                kind: "public",
+                expected: self.num_public_inputs,
+                got: inputs.public_inputs.len(),
+            });
+        }
+
+        let mut vars: Vec<Option<C::CircuitField>> = vec![None; self.num_vars];
+        let read = |vars: &[Option<C::CircuitField>], var: VarId| {
+            vars[var].ok_or(WitnessSolverError::UnassignedVariable(var))
+        };
+        let write = |vars: &mut [Option<C::CircuitField>], var: VarId, value| match vars[var]
+            .replace(value)
+        {
+            Some(_) => Err(WitnessSolverError::ReassignedVariable(var)),
+            None => Ok(()),
+        };
+
+        let mut private_inputs = inputs.private_inputs.iter();
+        for instruction in &self.instructions {
+            match instruction {
+                WitnessInstruction::PrivateInput { dst } => {
+                    // the number of private inputs has been checked above and by `validate`
+                    write(&mut vars, *dst, *private_inputs.next().unwrap())?
+                }
+                WitnessInstruction::PublicInput { dst, index } => {
+                    write(&mut vars, *dst, inputs.public_inputs[*index])?
+                }
+                WitnessInstruction::Constant { dst, value } => write(&mut vars, *dst, *value)?,
+                WitnessInstruction::LinComb {
+                    dst,
+                    terms,
+                    constant,
+                } => {
+                    let mut value = *constant;
+                    for (coef, var) in terms {
+                        value += read(&vars, *var)? * coef;
+                    }
+                    write(&mut vars, *dst, value)?
+                }
+                WitnessInstruction::Mul {
+                    dst,
+                    lhs,
+                    rhs,
+                    coef,
+                } => {
+                    let value = read(&vars, *lhs)? * read(&vars, *rhs)? * coef;
+                    write(&mut vars, *dst, value)?
+                }
+                WitnessInstruction::Hint {
+                    name,
+                    inputs,
+                    outputs,
+                } => {
+                    let hint_inputs = inputs
+                        .iter()
+                        .map(|&var| read(&vars, var))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let mut hint_outputs = vec![C::CircuitField::ZERO; outputs.len()];
+                    hints.call(name, &hint_inputs, &mut hint_outputs)?;
+                    for (&var, value) in outputs.iter().zip(hint_outputs) {
+                        write(&mut vars, var, value)?;
+                    }
+                }
+            }
+        }
+
+        self.input_layout
+            .iter()
+            .map(|slot| match slot {
+                Some(var) => read(&vars, *var),
+                None => Ok(C::CircuitField::ZERO),
+            })
+            .collect()
+    }
+
+    /// Solve every set of user inputs and lay them out as a `Witness`, ready to be
+    /// passed to `Circuit::prover_process_witness`.
+    ///
+    /// The number of witnesses must be the SIMD width times the number of MPI processes.
+    pub fn solve_witness(
+        &self,
+        hints: &HintRegistry<C::CircuitField>,
+        instances: &[WitnessInputs<C::CircuitField>],
+    ) -> Result<Witness<C>, WitnessSolverError> {
+        if !instances.len().is_power_of_two() || instances.len() < C::get_field_pack_size() {
+            return Err(WitnessSolverError::WrongNumberOfWitnesses(instances.len()));
+        }
+        self.validate()?;
+        self.check_hints(hints)?;
+
+        let num_private_inputs_per_witness = self.input_layout.len();
+        let mut values = Vec::with_capacity(
+            instances.len() * (num_private_inputs_per_witness + self.num_public_inputs),
+        );
+        for inputs in instances {
+            values.extend(self.solve_validated(hints, inputs)?);
+            values.extend_from_slice(&inputs.public_inputs);
+        }
+
+        Ok(Witness {
+            num_witnesses: instances.len(),
+            num_private_inputs_per_witness,
+            num_public_inputs_per_witness: self.num_public_inputs,
+            values,
+        })
+    }
+}
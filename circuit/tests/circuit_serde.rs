@@ -1,4 +1,5 @@
-use circuit::Circuit;
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateMonomial, GateUni, UNI_GATE_TYPE_POW5};
 use config_macros::declare_gkr_config;
 use gkr_engine::{
     root_println, BN254Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine, GKRScheme,
@@ -6,7 +7,7 @@ use gkr_engine::{
 };
 use gkr_hashers::SHA256hasher;
 use poly_commit::RawExpanderGKR;
-use serdes::ExpSerde;
+use serdes::{ExpSerde, SerdeError};
 use transcript::BytesHashTranscript;
 
 // circuit for repeating Keccak for 2 times
@@ -76,3 +77,77 @@ fn test_circuit_serde_helper<Cfg: GKREngine>(mpi_config: &MPIConfig) {
     circuit_deserialized.serialize_into(&mut buffer2).unwrap();
     assert_eq!(buffer, buffer2);
 }
+
+#[test]
+fn test_monomial_gate_serde() {
+    type C = M31x16Config;
+    let layer = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        uni: vec![GateUni {
+            i_ids: [3],
+            o_id: 0,
+            coef_type: CoefType::Constant,
+            coef: <C as FieldEngine>::CircuitField::ONE,
+            gate_type: UNI_GATE_TYPE_POW5,
+        }],
+        monomial: vec![GateMonomial {
+            i_ids: vec![0, 2, 1],
+            exponents: vec![7, 1, 2],
+            o_id: 1,
+            coef_type: CoefType::Random,
+            coef: <C as FieldEngine>::CircuitField::ZERO,
+        }],
+        ..Default::default()
+    };
+
+    let mut buffer = vec![];
+    layer.serialize_into(&mut buffer).unwrap();
+    let layer_deserialized = CircuitLayer::<C>::deserialize_from(&buffer[..]).unwrap();
+    assert_eq!(layer_deserialized.uni.len(), 1);
+    assert_eq!(layer_deserialized.monomial.len(), 1);
+    assert_eq!(layer_deserialized.monomial[0].i_ids, vec![0, 2, 1]);
+    assert_eq!(layer_deserialized.monomial[0].exponents, vec![7, 1, 2]);
+    assert_eq!(layer_deserialized.monomial[0].o_id, 1);
+
+    let mut buffer2 = vec![];
+    layer_deserialized.serialize_into(&mut buffer2).unwrap();
+    assert_eq!(buffer, buffer2);
+}
+
+#[test]
+fn test_layered_circuit_version() {
+    type C = M31x16Config;
+    let circuit = Circuit::<C> {
+        layers: vec![CircuitLayer {
+            input_var_num: 1,
+            output_var_num: 1,
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let mut buffer = vec![];
+    circuit.serialize_into(&mut buffer).unwrap();
+    assert!(Circuit::<C>::deserialize_from(&buffer[..]).is_ok());
+
+    // the lookups are serialized with the circuit, and must be on input wires
+    let mut circuit = circuit;
+    circuit.declare_lookup(vec![<C as FieldEngine>::CircuitField::ONE], 0, 1);
+    let mut buffer = vec![];
+    circuit.serialize_into(&mut buffer).unwrap();
+    let deserialized = Circuit::<C>::deserialize_from(&buffer[..]).unwrap();
+    assert_eq!(deserialized.lookups, circuit.lookups);
+    circuit.lookups[0].input_offset = 2;
+    let mut buffer = vec![];
+    circuit.serialize_into(&mut buffer).unwrap();
+    assert!(Circuit::<C>::deserialize_from(&buffer[..]).is_err());
+
+    // the circuits serialized before the version number start with the number of layers
+    let mut old_buffer = vec![];
+    circuit.layers.serialize_into(&mut old_buffer).unwrap();
+    assert!(matches!(
+        Circuit::<C>::deserialize_from(&old_buffer[..]),
+        Err(SerdeError::UnsupportedVersion(1))
+    ));
+}
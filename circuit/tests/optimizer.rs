@@ -0,0 +1,285 @@
+use arith::Field;
+use ark_std::rand::{Rng, RngCore};
+use ark_std::test_rng;
+use circuit::{
+    Circuit, CircuitLayer, CoefType, Gate, GateAdd, GateConst, GateMul, GateUni,
+    UNI_GATE_TYPE_POW1, UNI_GATE_TYPE_POW5,
+};
+use gkr_engine::{FieldEngine, M31x1Config};
+
+type C = M31x1Config;
+type F = <C as FieldEngine>::CircuitField;
+
+const NUM_PUBLIC_INPUTS: usize = 4;
+
+fn random_coef(rng: &mut impl RngCore) -> (CoefType, F) {
+    match rng.gen_range(0..10) {
+        0 => (CoefType::Random, F::random_unsafe(&mut *rng)),
+        1..=3 => (CoefType::Constant, F::ONE),
+        _ => (CoefType::Constant, F::random_unsafe(&mut *rng)),
+    }
+}
+
+fn random_gate<const INPUT_NUM: usize>(
+    rng: &mut impl RngCore,
+    n_inputs: usize,
+    n_outputs: usize,
+    gate_type: usize,
+) -> Gate<C, INPUT_NUM> {
+    let (coef_type, coef) = random_coef(rng);
+    Gate {
+        i_ids: [(); INPUT_NUM].map(|_| rng.gen_range(0..n_inputs)),
+        o_id: rng.gen_range(0..n_outputs),
+        coef_type,
+        coef,
+        gate_type,
+    }
+}
+
+/// A relay layer forwarding a random subset of the inputs, scaled by constants
+fn random_relay_layer(rng: &mut impl RngCore, var_num: usize) -> CircuitLayer<C> {
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: var_num,
+        output_var_num: var_num,
+        ..Default::default()
+    };
+    for i in 0..(1 << var_num) {
+        if rng.gen_bool(0.8) {
+            layer.add.push(GateAdd {
+                i_ids: [i],
+                o_id: i,
+                coef_type: CoefType::Constant,
+                coef: if rng.gen_bool(0.5) {
+                    F::ONE
+                } else {
+                    F::random_unsafe(&mut *rng)
+                },
+                gate_type: 0,
+            });
+        }
+    }
+    layer
+}
+
+fn random_layer(
+    rng: &mut impl RngCore,
+    input_var_num: usize,
+    output_var_num: usize,
+) -> CircuitLayer<C> {
+    let n_inputs = 1 << input_var_num;
+    let n_outputs = 1 << output_var_num;
+    // leave some wires unwritten to create dead gates and padding
+    let n_used_outputs = rng.gen_range(1..=n_outputs);
+
+    let mut layer = CircuitLayer::<C> {
+        input_var_num,
+        output_var_num,
+        ..Default::default()
+    };
+    for _ in 0..rng.gen_range(0..2 * n_used_outputs) {
+        layer
+            .mul
+            .push(random_gate(rng, n_inputs, n_used_outputs, 0));
+    }
+    for _ in 0..rng.gen_range(0..2 * n_used_outputs) {
+        layer
+            .add
+            .push(random_gate(rng, n_inputs, n_used_outputs, 0));
+    }
+    for _ in 0..rng.gen_range(0..n_used_outputs) {
+        let gate_type = if rng.gen_bool(0.5) {
+            UNI_GATE_TYPE_POW5
+        } else {
+            UNI_GATE_TYPE_POW1
+        };
+        layer
+            .uni
+            .push(random_gate(rng, n_inputs, n_used_outputs, gate_type));
+    }
+    for _ in 0..rng.gen_range(0..n_used_outputs) {
+        let mut gate: GateConst<C> = random_gate(rng, n_inputs, n_used_outputs, 0);
+        if rng.gen_bool(0.2) {
+            gate.coef_type = CoefType::PublicInput(rng.gen_range(0..NUM_PUBLIC_INPUTS));
+        }
+        layer.const_.push(gate);
+    }
+
+    // exact duplicates, and a mul gate with swapped inputs
+    if let Some(gate) = layer.add.first().copied() {
+        layer.add.push(gate);
+    }
+    if let Some(gate) = layer.mul.first().copied() {
+        layer.mul.push(GateMul {
+            i_ids: [gate.i_ids[1], gate.i_ids[0]],
+            ..gate
+        });
+    }
+    layer
+}
+
+fn random_circuit(rng: &mut impl RngCore) -> (Vec<CircuitLayer<C>>, usize) {
+    let mut layers = vec![];
+    let mut var_num = rng.gen_range(2..5);
+    for _ in 0..rng.gen_range(2..6) {
+        if rng.gen_bool(0.4) {
+            layers.push(random_relay_layer(rng, var_num));
+        }
+        let output_var_num = rng.gen_range(1..5);
+        layers.push(random_layer(rng, var_num, output_var_num));
+        var_num = output_var_num;
+    }
+    if rng.gen_bool(0.5) {
+        layers.push(random_relay_layer(rng, var_num));
+    }
+
+    let input_size = 1 << layers[0].input_var_num;
+    (layers, input_size)
+}
+
+fn build_and_evaluate(
+    layers: Vec<CircuitLayer<C>>,
+    input: &[F],
+    public_input: &[F],
+    optimize: bool,
+) -> Circuit<C> {
+    let mut circuit = Circuit::<C> {
+        layers,
+        public_input: public_input.to_vec(),
+        ..Default::default()
+    };
+    if optimize {
+        circuit.optimize();
+    }
+    circuit.layers[0].input_vals = input.to_vec();
+    circuit.evaluate();
+    circuit
+}
+
+#[test]
+fn test_optimizer_preserves_outputs() {
+    let mut rng = test_rng();
+
+    for _ in 0..200 {
+        let (layers, input_size) = random_circuit(&mut rng);
+        let input: Vec<F> = (0..input_size)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect();
+        let public_input: Vec<F> = (0..NUM_PUBLIC_INPUTS)
+            .map(|_| F::random_unsafe(&mut rng))
+            .collect();
+
+        let expected = build_and_evaluate(layers.clone(), &input, &public_input, false);
+        let optimized = build_and_evaluate(layers, &input, &public_input, true);
+
+        assert!(optimized.layers.len() <= expected.layers.len());
+        assert_eq!(
+            optimized.layers[0].input_var_num,
+            expected.layers[0].input_var_num
+        );
+        assert_eq!(
+            optimized.layers.last().unwrap().output_vals,
+            expected.layers.last().unwrap().output_vals
+        );
+    }
+}
+
+#[test]
+fn test_optimizer_report() {
+    let one = |i: usize, o: usize| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: F::ONE,
+        gate_type: 0,
+    };
+
+    // layer 0: out_0 = in_0 * in_1 written twice, out_1 = 3, out_2 = in_2 (dead)
+    // layer 1: relay of out_0 and out_1 into a 16-wire layer
+    // layer 2: out_0 = w_0^5 + w_1, out_1 = w_1 * w_0
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 2,
+        mul: vec![
+            GateMul {
+                i_ids: [0, 1],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: F::ONE,
+                gate_type: 0,
+            },
+            GateMul {
+                i_ids: [1, 0],
+                o_id: 0,
+                coef_type: CoefType::Constant,
+                coef: F::ONE,
+                gate_type: 0,
+            },
+        ],
+        add: vec![one(2, 2)],
+        const_: vec![GateConst {
+            i_ids: [],
+            o_id: 1,
+            coef_type: CoefType::Constant,
+            coef: F::from(3u32),
+            gate_type: 0,
+        }],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 4,
+        add: vec![one(0, 0), one(1, 1)],
+        ..Default::default()
+    };
+    let layer_2 = CircuitLayer::<C> {
+        input_var_num: 4,
+        output_var_num: 1,
+        mul: vec![GateMul {
+            i_ids: [1, 0],
+            o_id: 1,
+            coef_type: CoefType::Constant,
+            coef: F::ONE,
+            gate_type: 0,
+        }],
+        add: vec![one(1, 0)],
+        uni: vec![GateUni {
+            i_ids: [0],
+            o_id: 0,
+            coef_type: CoefType::Constant,
+            coef: F::ONE,
+            gate_type: UNI_GATE_TYPE_POW5,
+        }],
+        ..Default::default()
+    };
+    let input: Vec<F> = (1..=4u32).map(F::from).collect();
+
+    let layers = vec![layer_0, layer_1, layer_2];
+    let expected = build_and_evaluate(layers.clone(), &input, &[], false);
+
+    let mut circuit = Circuit::<C> {
+        layers,
+        ..Default::default()
+    };
+    let report = circuit.optimize();
+    assert_eq!(report.layers_before, 3);
+    assert_eq!(report.layers_after, 2);
+    assert_eq!(report.gates_before, 9);
+    assert!(report.gates_after < report.gates_before);
+    assert!(report.input_size_after < report.input_size_before);
+
+    // the duplicated mul gate is merged, the dead relay is gone,
+    // and the constant wire is folded into the last layer
+    assert_eq!(circuit.layers[0].mul.len(), 1);
+    assert_eq!(circuit.layers[0].mul[0].coef, F::from(2u32));
+    assert!(circuit.layers[0].add.is_empty());
+    assert!(circuit.layers[0].const_.is_empty());
+    assert_eq!(circuit.layers[0].output_var_num, 1);
+    assert!(circuit.layers[1].mul.is_empty());
+
+    circuit.layers[0].input_vals = input;
+    circuit.evaluate();
+    assert_eq!(
+        circuit.layers.last().unwrap().output_vals,
+        expected.layers.last().unwrap().output_vals
+    );
+}
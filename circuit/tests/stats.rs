@@ -0,0 +1,98 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CircuitLint, CoefType, GateAdd, GateConst, GateMul};
+use gkr_engine::{FieldEngine, M31x1Config};
+
+type C = M31x1Config;
+
+fn add_gate(i: usize, o: usize) -> GateAdd<C> {
+    GateAdd {
+        i_ids: [i],
+        o_id: o,
+        coef: <C as FieldEngine>::CircuitField::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    }
+}
+
+fn mul_gate(i0: usize, i1: usize, o: usize) -> GateMul<C> {
+    GateMul {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef: <C as FieldEngine>::CircuitField::ONE,
+        coef_type: CoefType::Constant,
+        gate_type: 0,
+    }
+}
+
+// layer 0: 8 inputs, only 0, 1 and 3 are used, mul(0, 1) is duplicated with swapped inputs
+// layer 1: relays output 0 only, output 1 of layer 0 is dead
+fn build_circuit() -> Circuit<C> {
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: 3,
+        output_var_num: 1,
+        mul: vec![mul_gate(0, 1, 0), mul_gate(1, 0, 0)],
+        add: vec![add_gate(3, 1)],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        add: vec![add_gate(0, 0)],
+        const_: vec![GateConst {
+            i_ids: [],
+            o_id: 1,
+            coef: <C as FieldEngine>::CircuitField::ZERO,
+            coef_type: CoefType::PublicInput(1),
+            gate_type: 0,
+        }],
+        ..Default::default()
+    };
+
+    Circuit {
+        layers: vec![layer_0, layer_1],
+        expected_num_output_zeros: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_circuit_stats() {
+    let stats = build_circuit().stats();
+
+    assert_eq!(stats.num_layers(), 2);
+    assert_eq!(stats.num_public_inputs, 2);
+    assert_eq!(stats.total_gates(), 5);
+    assert_eq!(stats.total_duplicate_gates(), 1);
+    assert_eq!(stats.num_skip_sumcheck_phase_two(), 1);
+
+    let layer_0 = &stats.layers[0];
+    assert_eq!(layer_0.used_inputs, 3);
+    assert_eq!(layer_0.wasted_inputs(), 5);
+    assert_eq!(layer_0.max_input_id, Some(3));
+    assert!(!layer_0.skip_sumcheck_phase_two);
+
+    let layer_1 = &stats.layers[1];
+    assert_eq!(layer_1.n_public_input_coefs, 1);
+    assert_eq!(layer_1.wasted_outputs(), 0);
+}
+
+#[test]
+fn test_circuit_lint() {
+    let lints = build_circuit().lint();
+
+    assert!(lints.contains(&CircuitLint::UnusedInputs { layer: 0, count: 1 }));
+    assert!(lints.contains(&CircuitLint::DuplicateGates { layer: 0, count: 1 }));
+    assert!(lints.contains(&CircuitLint::DeadOutputs { layer: 0, count: 1 }));
+    assert!(!lints
+        .iter()
+        .any(|lint| matches!(lint, CircuitLint::OutOfRange { .. })));
+
+    let mut circuit = build_circuit();
+    circuit.layers[1].add.push(add_gate(2, 0));
+    assert!(circuit.lint().contains(&CircuitLint::OutOfRange {
+        layer: 1,
+        kind: "add",
+        id: 2,
+        size: 2,
+    }));
+}
@@ -0,0 +1,188 @@
+use arith::Field;
+use circuit::{
+    Circuit, RecursiveCircuit, TextFormat, TextFormatError, Witness, UNI_GATE_TYPE_POW5,
+};
+use gkr_engine::{BN254Config, FieldEngine, M31x1Config};
+use serdes::ExpSerde;
+
+type C = M31x1Config;
+type F = <C as FieldEngine>::CircuitField;
+
+const CIRCUIT_TEXT: &str = "
+expander_circuit
+modulus 2147483647
+num_public_inputs 1
+num_outputs 2
+expected_num_output_zeros 1
+
+# out_0 = in_0 * in_1 - in_2, out_1 = in_3^5
+segment 0 2 1
+mul 0 1 0 1
+add 2 0 2147483646
+uni 12345 3 1 1
+
+# two copies of segment 0, and a random linear combination of the public input
+segment 1 3 2
+child 0 0:0 4:2
+const 1 public:0
+add 0 1 random
+
+layers 1
+";
+
+const LAYERED_CIRCUIT_TEXT: &str = "
+expander_layered_circuit
+modulus 2147483647
+expected_num_output_zeros 1
+
+# out_0 = 3 * in_0^2 * in_1^5 - in_2 * in_3, out_1 = r * in_1^7
+layer 2 1
+mul 2 3 0 2147483646
+monomial 0^2 1^5 0 3
+monomial 1^7 1 random
+
+# in_0 and in_1 are 2-bit values
+lookup 0 1 0 1 2 3
+";
+
+fn to_bytes<T: ExpSerde>(value: &T) -> Vec<u8> {
+    let mut buffer = vec![];
+    value.serialize_into(&mut buffer).unwrap();
+    buffer
+}
+
+#[test]
+fn test_recursive_circuit_text_roundtrip() {
+    let rc = RecursiveCircuit::<C>::from_text(CIRCUIT_TEXT).unwrap();
+    assert_eq!(rc.segments.len(), 2);
+    assert_eq!(rc.segments[0].gate_adds[0].coef, -F::ONE);
+    assert_eq!(rc.segments[0].gate_uni[0].gate_type, UNI_GATE_TYPE_POW5);
+    assert_eq!(rc.segments[1].child_segs[0].1.len(), 2);
+
+    // text -> binary -> text is the identity on the canonical text
+    let text = rc.to_text();
+    let bytes = to_bytes(&rc);
+    let deserialized = RecursiveCircuit::<C>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(deserialized.to_text(), text);
+    assert_eq!(to_bytes(&deserialized), bytes);
+    assert_eq!(
+        to_bytes(&RecursiveCircuit::<C>::from_text(&text).unwrap()),
+        bytes
+    );
+
+    // the flattened circuit keeps uni gate types through both formats
+    let circuit = rc.flatten();
+    let text = circuit.to_text();
+    let bytes = to_bytes(&circuit);
+    let from_text = Circuit::<C>::from_text(&text).unwrap();
+    let from_bytes = Circuit::<C>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(from_text.expected_num_output_zeros, 1);
+    assert_eq!(from_text.layers[0].uni[0].gate_type, UNI_GATE_TYPE_POW5);
+    assert_eq!(to_bytes(&from_text), bytes);
+    assert_eq!(from_bytes.to_text(), text);
+}
+
+// the conversions of `circuit-convert` on a layered circuit with monomial gates
+#[test]
+fn test_layered_circuit_text_roundtrip() {
+    let circuit = Circuit::<C>::from_text(LAYERED_CIRCUIT_TEXT).unwrap();
+    let monomial = &circuit.layers[0].monomial;
+    assert_eq!(monomial.len(), 2);
+    assert_eq!(monomial[0].i_ids, vec![0, 1]);
+    assert_eq!(monomial[0].exponents, vec![2, 5]);
+    assert_eq!(monomial[0].coef, F::from(3u32));
+    assert_eq!(monomial[1].degree(), 7);
+    assert_eq!(circuit.lookups.len(), 1);
+    assert_eq!(circuit.lookups[0].table.len(), 4);
+
+    let text = circuit.to_text();
+    let bytes = to_bytes(&circuit);
+    let from_text = Circuit::<C>::from_text(&text).unwrap();
+    let from_bytes = Circuit::<C>::deserialize_from(&bytes[..]).unwrap();
+    assert_eq!(to_bytes(&from_text), bytes);
+    assert_eq!(from_bytes.to_text(), text);
+
+    let error = |text: &str| Circuit::<C>::from_text(text).err().unwrap().line;
+    assert_eq!(error(&LAYERED_CIRCUIT_TEXT.replace("0^2 1^5", "0 1^5")), 9);
+    assert_eq!(error(&LAYERED_CIRCUIT_TEXT.replace("1^7", "1^0")), 10);
+    assert_eq!(
+        error(&LAYERED_CIRCUIT_TEXT.replace("1 random", "1 public:0")),
+        10
+    );
+    // the looked-up wires must be an aligned block of input wires
+    assert_eq!(
+        error(&LAYERED_CIRCUIT_TEXT.replace("lookup 0", "lookup 1")),
+        13
+    );
+    assert_eq!(
+        error(&LAYERED_CIRCUIT_TEXT.replace("lookup 0", "lookup 4")),
+        13
+    );
+
+    // segments of a compiled circuit have no monomial gates
+    assert_eq!(
+        RecursiveCircuit::<C>::from_text(
+            &CIRCUIT_TEXT.replace("uni 12345 3 1 1", "monomial 3^5 1 1")
+        )
+        .err()
+        .unwrap()
+        .line,
+        12
+    );
+}
+
+#[test]
+fn test_witness_text_roundtrip() {
+    let witness = Witness::<BN254Config> {
+        num_witnesses: 2,
+        num_private_inputs_per_witness: 3,
+        num_public_inputs_per_witness: 1,
+        values: (0..8u32)
+            .map(|i| -<BN254Config as FieldEngine>::CircuitField::from(i))
+            .collect(),
+    };
+
+    let text = witness.to_text();
+    let from_text = Witness::<BN254Config>::from_text(&text).unwrap();
+    assert_eq!(from_text.values, witness.values);
+    assert_eq!(to_bytes(&from_text), to_bytes(&witness));
+
+    // hex is accepted and printed back in decimal
+    let hex = text.replacen("private 0 ", "private 0x0 ", 1);
+    assert_eq!(
+        Witness::<BN254Config>::from_text(&hex).unwrap().to_text(),
+        text
+    );
+}
+
+#[test]
+fn test_text_format_errors() {
+    let error = |text: &str| RecursiveCircuit::<C>::from_text(text).err().unwrap();
+
+    // the line numbers count the leading empty line
+    assert_eq!(
+        error(&CIRCUIT_TEXT.replace("add 2 0 2147483646", "add 2 0 2147483647")),
+        TextFormatError {
+            line: 11,
+            message: "field element `2147483647` is not less than the modulus".to_string(),
+        }
+    );
+    assert_eq!(
+        error(&CIRCUIT_TEXT.replace("mul 0 1 0 1", "mul 0 0 1")).line,
+        10
+    );
+    assert_eq!(
+        error(&CIRCUIT_TEXT.replace("segment 1", "segment 2")).line,
+        15
+    );
+    assert_eq!(
+        error(&CIRCUIT_TEXT.replace("layers 1", "layers 2")).line,
+        20
+    );
+    assert_eq!(error(&CIRCUIT_TEXT.replace("2147483647", "7")).line, 3);
+    assert_eq!(
+        error(&CIRCUIT_TEXT.replace("add 0 1 random", "add 0 1 public:0")).line,
+        18
+    );
+    assert!(Witness::<C>::from_text(CIRCUIT_TEXT).is_err());
+}
@@ -0,0 +1,242 @@
+use arith::Field;
+use circuit::{
+    Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul, HintRegistry, Witness,
+    WitnessInputs, WitnessInstruction, WitnessProgram, WitnessSolverError,
+};
+use gkr_engine::{FieldEngine, M31x1Config, MPIConfig};
+use serdes::ExpSerde;
+
+type C = M31x1Config;
+type F = <C as FieldEngine>::CircuitField;
+
+const NUM_BITS: usize = 4;
+
+// input slots: [x, b_0, b_1, b_2, b_3, a, a_inv, padding], public input: [x]
+fn build_program() -> WitnessProgram<C> {
+    let mut program = WitnessProgram::<C>::new(3);
+    let x = program.private_input();
+    let a = program.private_input();
+    let _public_x = program.public_input();
+    let one = program.constant(F::ONE);
+    let bits = program.hint("bits", vec![x], NUM_BITS);
+    let a_inv = program.hint("div", vec![one, a], 1)[0];
+
+    program.set_input_slot(0, x).unwrap();
+    bits.iter()
+        .enumerate()
+        .for_each(|(i, &b)| program.set_input_slot(1 + i, b).unwrap());
+    program.set_input_slot(5, a).unwrap();
+    program.set_input_slot(6, a_inv).unwrap();
+    program
+}
+
+// outputs, all zero for a valid witness:
+//   x - sum(2^i * b_i)
+//   a * a_inv - 1
+//   sum(b_i * b_i - b_i)
+//   public_x - x
+fn build_circuit() -> Circuit<C> {
+    let add = |i: usize, o: usize, coef: F| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: F::ONE,
+        gate_type: 0,
+    };
+
+    let mut layer = CircuitLayer::<C> {
+        input_var_num: 3,
+        output_var_num: 2,
+        ..Default::default()
+    };
+    layer.add.push(add(0, 0, F::ONE));
+    for i in 0..NUM_BITS {
+        layer.add.push(add(1 + i, 0, -F::from(1u32 << i)));
+        layer.mul.push(mul(1 + i, 1 + i, 2));
+        layer.add.push(add(1 + i, 2, -F::ONE));
+    }
+    layer.mul.push(mul(5, 6, 1));
+    layer.const_.push(GateConst {
+        i_ids: [],
+        o_id: 1,
+        coef_type: CoefType::Constant,
+        coef: -F::ONE,
+        gate_type: 0,
+    });
+    layer.const_.push(GateConst {
+        i_ids: [],
+        o_id: 3,
+        coef_type: CoefType::PublicInput(0),
+        coef: F::ZERO,
+        gate_type: 0,
+    });
+    layer.add.push(add(0, 3, -F::ONE));
+
+    Circuit {
+        layers: vec![layer],
+        expected_num_output_zeros: 4,
+        ..Default::default()
+    }
+}
+
+fn inputs(x: u32, a: u32) -> WitnessInputs<F> {
+    WitnessInputs {
+        private_inputs: vec![F::from(x), F::from(a)],
+        public_inputs: vec![F::from(x)],
+    }
+}
+
+#[test]
+fn test_witness_solver() {
+    let program = build_program();
+    let hints = HintRegistry::default();
+
+    let witness = program.solve_witness(&hints, &[inputs(11, 7)]).unwrap();
+    assert_eq!(witness.num_witnesses, 1);
+    assert_eq!(witness.num_private_inputs_per_witness, 8);
+    assert_eq!(witness.num_public_inputs_per_witness, 1);
+    assert_eq!(witness.values[..5], [11u32, 1, 1, 0, 1].map(F::from));
+    assert_eq!(witness.values[7], F::ZERO);
+    assert_eq!(witness.values[8], F::from(11u32));
+
+    let mut buf = vec![];
+    witness.serialize_into(&mut buf).unwrap();
+    let deserialized = Witness::<C>::deserialize_from(&buf[..]).unwrap();
+    assert_eq!(deserialized.values, witness.values);
+
+    let mpi_config = MPIConfig::prover_new(None, None);
+    let mut circuit = build_circuit();
+    circuit.prover_process_witness(witness, &mpi_config);
+    circuit.evaluate();
+    assert!(circuit.layers[0].output_vals.iter().all(|v| v.is_zero()));
+}
+
+#[test]
+fn test_witness_solver_errors() {
+    let program = build_program();
+    let hints = HintRegistry::default();
+
+    // x does not fit in 4 bits
+    assert!(matches!(
+        program.solve(&hints, &inputs(16, 7)),
+        Err(WitnessSolverError::HintFailed { name, .. }) if name == "bits"
+    ));
+    assert!(matches!(
+        program.solve(&hints, &inputs(3, 0)),
+        Err(WitnessSolverError::HintFailed { name, .. }) if name == "div"
+    ));
+    assert_eq!(
+        program.solve(
+            &hints,
+            &WitnessInputs {
+                private_inputs: vec![F::ONE],
+                public_inputs: vec![F::ONE],
+            }
+        ),
+        Err(WitnessSolverError::WrongNumberOfInputs {
+            kind: "private",
+            expected: 2,
+            got: 1,
+        })
+    );
+    assert_eq!(
+        program
+            .solve_witness(&HintRegistry::empty(), &[inputs(3, 1)])
+            .err(),
+        Some(WitnessSolverError::UnknownHint("bits".to_string()))
+    );
+
+    // custom hints can replace the builtin ones
+    let mut hints = HintRegistry::default();
+    hints.register("bits", |_, outputs: &mut [F]| {
+        outputs.fill(F::ONE);
+        Ok(())
+    });
+    let values = program.solve(&hints, &inputs(3, 1)).unwrap();
+    assert_eq!(values[1..5], [F::ONE; NUM_BITS]);
+}
+
+#[test]
+fn test_witness_program_validate() {
+    let program = build_program();
+    assert_eq!(program.validate(), Ok(()));
+    assert_eq!(program.validate_for_circuit(&build_circuit()), Ok(()));
+
+    let hints = HintRegistry::default();
+    let mut bad_program = build_program();
+    bad_program.set_input_slot(7, 100).unwrap();
+    assert_eq!(
+        bad_program.solve(&hints, &inputs(3, 1)),
+        Err(WitnessSolverError::VariableOutOfRange(100))
+    );
+
+    let mut bad_program = build_program();
+    assert_eq!(
+        bad_program.set_input_slot(8, 0),
+        Err(WitnessSolverError::InputSlotOutOfRange {
+            slot: 8,
+            num_slots: 8
+        })
+    );
+
+    let mut bad_program = build_program();
+    bad_program.instructions.push(WitnessInstruction::Mul {
+        dst: 0,
+        lhs: 1,
+        rhs: bad_program.num_vars,
+        coef: F::ONE,
+    });
+    assert_eq!(
+        bad_program.solve(&hints, &inputs(3, 1)),
+        Err(WitnessSolverError::VariableOutOfRange(bad_program.num_vars))
+    );
+
+    let mut bad_program = build_program();
+    bad_program.instructions.push(WitnessInstruction::PublicInput { dst: 0, index: 1 });
+    assert_eq!(
+        bad_program.solve_witness(&hints, &[inputs(3, 1)]).err(),
+        Some(WitnessSolverError::PublicInputOutOfRange(1))
+    );
+
+    // more private input instructions than declared inputs
+    let mut bad_program = build_program();
+    bad_program.num_private_inputs = 1;
+    assert_eq!(
+        bad_program.validate(),
+        Err(WitnessSolverError::WrongNumberOfDeclaredInputs {
+            kind: "private",
+            expected: 1,
+            got: 2,
+        })
+    );
+
+    // the circuit has 8 input slots and reads 1 public input
+    let small_program = WitnessProgram::<C>::new(2);
+    assert_eq!(
+        small_program.validate_for_circuit(&build_circuit()),
+        Err(WitnessSolverError::WrongInputLayoutSize {
+            expected: 8,
+            got: 4,
+        })
+    );
+    let mut private_program = build_program();
+    private_program.num_public_inputs = 0;
+    private_program
+        .instructions
+        .retain(|i| !matches!(i, WitnessInstruction::PublicInput { .. }));
+    assert_eq!(
+        private_program.validate_for_circuit(&build_circuit()),
+        Err(WitnessSolverError::WrongNumberOfDeclaredInputs {
+            kind: "public",
+            expected: 1,
+            got: 0,
+        })
+    );
+}
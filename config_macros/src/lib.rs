@@ -48,11 +48,14 @@ fn parse_field_type(field_expr: ExprPath) -> (String, String) {
     match field_enum.ident.to_string().as_str() {
         "M31x1" => ("M31x1".to_owned(), "M31x1Config".to_owned()),
         "M31x16" => ("M31x16".to_owned(), "M31x16Config".to_owned()),
+        "M31x1Ext6" => ("M31x1Ext6".to_owned(), "M31x1Ext6Config".to_owned()),
+        "M31x16Ext6" => ("M31x16Ext6".to_owned(), "M31x16Ext6Config".to_owned()),
         "BN254" => ("BN254".to_owned(), "BN254Config".to_owned()),
         "GF2Ext128" => ("GF2Ext128".to_owned(), "GF2ExtConfig".to_owned()),
         "Goldilocksx1" => ("Goldilocksx1".to_owned(), "Goldilocksx1Config".to_owned()),
         "Goldilocksx8" => ("Goldilocksx8".to_owned(), "Goldilocksx8Config".to_owned()),
         "BabyBearx16" => ("BabyBearx16".to_owned(), "BabyBearx16Config".to_owned()),
+        "KoalaBearx16" => ("KoalaBearx16".to_owned(), "KoalaBearx16Config".to_owned()),
         _ => panic!("Unknown field type"),
     }
 }
@@ -116,7 +119,7 @@ fn parse_polynomial_commitment_type(
             "Orion".to_owned(),
             format!("OrionPCSForGKR::<{field_config}, GF2x128>").to_owned(),
         ),
-        ("Orion", "M31x16") => (
+        ("Orion", "M31x16") | ("Orion", "M31x16Ext6") => (
             "Orion".to_owned(),
             format!("OrionPCSForGKR::<{field_config}, M31x16>").to_owned(),
         ),
@@ -4,7 +4,7 @@ use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
-    Goldilocksx8Config, M31x16Config, MPIConfig,
+    Goldilocksx8Config, KoalaBearx16Config, M31x16Config, M31x16Ext6Config, MPIConfig,
 };
 use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
 use halo2curves::bn256::Bn256;
@@ -81,6 +81,20 @@ fn main() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        M31Ext6Sha256OrionConfig,
+        FieldType::M31x16Ext6,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        KoalaBearExtSHA256Config,
+        FieldType::KoalaBearx16,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
 
     print_type_name::<M31ExtSha256Config>();
     print_type_name::<M31ExtPoseidonRawConfig>();
@@ -91,4 +105,6 @@ fn main() {
     print_type_name::<GF2ExtKeccak256OrionConfig>();
     print_type_name::<GoldilocksExtSHA256Config>();
     print_type_name::<BabyBearExtSHA256Config>();
+    print_type_name::<KoalaBearExtSHA256Config>();
+    print_type_name::<M31Ext6Sha256OrionConfig>();
 }
@@ -3,10 +3,10 @@ use config_macros::declare_gkr_config;
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use gkr::{
     utils::{KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS, KECCAK_M31_CIRCUIT, KECCAK_M31_WITNESS},
-    Prover,
+    CompressedSumcheck, Prover,
 };
 use gkr_engine::{
-    BN254Config, ExpanderPCS, FieldEngine, GKREngine, GKRScheme, M31x16Config, MPIConfig,
+    BN254Config, ExpanderPCS, FieldEngine, GKREngine, GKRScheme, M31x16Config, MPIConfig, Proof,
     StructuredReferenceString,
 };
 use gkr_hashers::SHA256hasher;
@@ -20,12 +20,51 @@ fn prover_run<Cfg: GKREngine>(
     pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
     pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
     pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
-) where
+) -> Proof
+where
     Cfg::FieldConfig: FieldEngine,
 {
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(circuit);
-    prover.prove(circuit, pcs_params, pcs_proving_key, pcs_scratch);
+    prover
+        .prove(circuit, pcs_params, pcs_proving_key, pcs_scratch)
+        .1
+}
+
+/// Print the proof size saved by compressed sumcheck round messages
+fn report_proof_size<Cfg: GKREngine>(
+    label: &str,
+    mpi_config: &MPIConfig,
+    circuit: &mut Circuit<Cfg::FieldConfig>,
+    pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+    pcs_proving_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::PKey,
+    pcs_scratch: &mut <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::ScratchPad,
+) where
+    Cfg::FieldConfig: FieldEngine,
+{
+    let proof_size = prover_run::<Cfg>(
+        mpi_config,
+        circuit,
+        pcs_params,
+        pcs_proving_key,
+        pcs_scratch,
+    )
+    .bytes
+    .len();
+    let compressed_proof_size = prover_run::<CompressedSumcheck<Cfg>>(
+        mpi_config,
+        circuit,
+        pcs_params,
+        pcs_proving_key,
+        pcs_scratch,
+    )
+    .bytes
+    .len();
+    println!(
+        "{label}: proof size {proof_size} bytes, {compressed_proof_size} bytes with compressed \
+         sumcheck ({:.2}% smaller)",
+        100.0 * (proof_size - compressed_proof_size) as f64 / proof_size as f64
+    );
 }
 
 fn benchmark_setup<Cfg: GKREngine>(
@@ -92,6 +131,23 @@ fn criterion_gkr_keccak(c: &mut Criterion) {
     let num_keccak_m31 = 2 * <M31x16ConfigSha2 as GKREngine>::FieldConfig::get_field_pack_size();
     let num_keccak_bn254 = 2 * <BN254ConfigSha2 as GKREngine>::FieldConfig::get_field_pack_size();
 
+    report_proof_size::<M31x16ConfigSha2>(
+        "keccak over M31",
+        &m31_config,
+        &mut m31_circuit,
+        &m31_pcs_params,
+        &m31_pcs_proving_key,
+        &mut m31_pcs_scratch,
+    );
+    report_proof_size::<BN254ConfigSha2>(
+        "keccak over BN254",
+        &bn254_config,
+        &mut bn254_circuit,
+        &bn254_pcs_params,
+        &bn254_pcs_proving_key,
+        &mut bn254_pcs_scratch,
+    );
+
     let mut group = c.benchmark_group("single thread proving keccak by GKR vanilla");
     group.bench_function(
         BenchmarkId::new(
@@ -0,0 +1,288 @@
+//! Static estimates of the proof size and the prover cost of a circuit,
+//! derived from the circuit structure and the chosen `GKREngine` only.
+
+use arith::{Field, SimdField};
+use circuit::{Circuit, LayerStats, Lookup};
+use gkr_engine::{
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, ThreadMPIConfig, Transcript,
+};
+use polynomials::RefMultiLinearPoly;
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+use sumcheck::{
+    needs_generalized_sumcheck, GeneralizedLayerGates, SUMCHECK_GKR_DEGREE,
+    SUMCHECK_GKR_SIMD_MPI_DEGREE,
+};
+
+/// Serialized size of a value, e.g., of a field element
+#[inline]
+fn serialized_size<T: ExpSerde>(value: &T) -> usize {
+    let mut buf = vec![];
+    value.serialize_into(&mut buf).unwrap();
+    buf.len()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayerCostEstimate {
+    /// Number of sumcheck rounds run for this layer
+    pub sumcheck_rounds: usize,
+    /// Number of challenge field elements the layer contributes to the proof
+    pub proof_field_elements: usize,
+    /// Rough number of SIMD field operations per rank
+    pub prover_field_ops: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GKRCostEstimate {
+    pub layers: Vec<LayerCostEstimate>,
+
+    /// Serialized size of a challenge field element
+    pub challenge_field_bytes: usize,
+    /// Bytes used by the public inputs bound at the start of the proof
+    pub public_input_bytes: usize,
+    /// Bytes used by the lookups and the reduction of the claims on the input, if any lookup
+    pub lookup_bytes: usize,
+
+    /// Number of PCS openings, 2 if the input layer needs both claims
+    pub num_pcs_openings: usize,
+    /// Number of variables of the committed polynomial, including SIMD and MPI variables
+    pub pcs_num_vars: usize,
+}
+
+impl GKRCostEstimate {
+    pub fn sumcheck_rounds(&self) -> usize {
+        self.layers.iter().map(|l| l.sumcheck_rounds).sum()
+    }
+
+    /// Bytes of the GKR sumcheck part of the proof
+    pub fn sumcheck_bytes(&self) -> usize {
+        self.layers
+            .iter()
+            .map(|l| l.proof_field_elements)
+            .sum::<usize>()
+            * self.challenge_field_bytes
+    }
+
+    /// Proof bytes excluding the PCS commitment and openings
+    pub fn iop_proof_bytes(&self) -> usize {
+        self.public_input_bytes + self.sumcheck_bytes() + self.lookup_bytes
+    }
+
+    pub fn prover_field_ops(&self) -> usize {
+        self.layers.iter().map(|l| l.prover_field_ops).sum()
+    }
+}
+
+/// Estimate the cost of proving `circuit` with `Cfg` on `world_size` ranks.
+///
+/// The proof size is exact for the IOP part, i.e., it matches the layout written by
+/// `Prover::prove`, the PCS part is left to `measure_pcs_proof_bytes`. The grinding leaves
+/// nothing in the proof, and the statement and the output claim are absorbed without being written.
+/// The prover cost is a coarse operation count meant for comparing circuits,
+/// not for predicting wall-clock time.
+pub fn estimate_gkr_cost<Cfg: GKREngine>(
+    circuit: &Circuit<Cfg::FieldConfig>,
+    world_size: usize,
+) -> GKRCostEstimate {
+    let stats = circuit.stats();
+    let n_simd_vars =
+        <<Cfg::FieldConfig as FieldEngine>::SimdCircuitField as SimdField>::PACK_SIZE
+            .trailing_zeros() as usize;
+    let n_mpi_vars = world_size.trailing_zeros() as usize;
+    let n_simd_mpi_rounds = n_simd_vars + n_mpi_vars;
+    // a round message of the given degree, whose evaluation at 1 is dropped when compressed
+    let message_len = |degree: usize| degree + 1 - usize::from(Cfg::COMPRESSED_SUMCHECK);
+
+    let layers = circuit
+        .layers
+        .iter()
+        .zip(&stats.layers)
+        .map(|(circuit_layer, layer)| {
+            let n_vars = layer.input_var_num;
+
+            if needs_generalized_sumcheck(circuit_layer) {
+                return estimate_generalized_layer(
+                    &GeneralizedLayerGates::new(circuit_layer),
+                    layer,
+                    n_simd_mpi_rounds,
+                    message_len,
+                );
+            }
+
+            let mut sumcheck_rounds = n_vars + n_simd_mpi_rounds;
+            let mut proof_field_elements = n_vars * message_len(SUMCHECK_GKR_DEGREE)
+                + n_simd_mpi_rounds * message_len(SUMCHECK_GKR_SIMD_MPI_DEGREE)
+                + 1;
+
+            // building the eq tables and scattering the gates into the bookkeeping tables,
+            // then each round halves the table: 2^n + 2^(n-1) + ... < 2 * 2^n
+            let gate_ops = 2 * layer.n_mul + layer.n_add + layer.n_const;
+            let eq_ops = 2 * layer.output_size();
+            let phase_ops = 2 * layer.input_size() * (SUMCHECK_GKR_DEGREE + 1);
+            let mut prover_field_ops = gate_ops + eq_ops + phase_ops;
+
+            if !layer.skip_sumcheck_phase_two {
+                sumcheck_rounds += n_vars;
+                proof_field_elements += n_vars * message_len(SUMCHECK_GKR_DEGREE) + 1;
+                prover_field_ops += phase_ops + layer.input_size();
+            }
+
+            LayerCostEstimate {
+                sumcheck_rounds,
+                proof_field_elements,
+                prover_field_ops,
+            }
+        })
+        .collect();
+
+    let challenge_field_bytes =
+        serialized_size(&<Cfg::FieldConfig as FieldEngine>::ChallengeField::ZERO);
+    let lookup_bytes = if circuit.lookups.is_empty() {
+        0
+    } else {
+        // the claims of the lookups and of GKR are reduced by a degree 2 sumcheck over the
+        // global input variables, ending with the evaluation of the input
+        let n_vars = stats.log_input_size() + n_simd_mpi_rounds;
+        circuit
+            .lookups
+            .iter()
+            .map(|lookup| {
+                estimate_lookup_bytes::<Cfg>(lookup, n_simd_mpi_rounds, challenge_field_bytes)
+            })
+            .sum::<usize>()
+            + n_vars * iop_message_bytes(message_len(2), challenge_field_bytes)
+            + challenge_field_bytes
+    };
+    // the lookups reduce all the claims on the input to a single opening
+    let num_pcs_openings = match stats.layers.first() {
+        Some(l) if !l.skip_sumcheck_phase_two && circuit.lookups.is_empty() => 2,
+        _ => 1,
+    };
+
+    GKRCostEstimate {
+        layers,
+        challenge_field_bytes,
+        public_input_bytes: stats.num_public_inputs
+            * world_size
+            * serialized_size(&<Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO),
+        lookup_bytes,
+        num_pcs_openings,
+        pcs_num_vars: stats.log_input_size() + n_simd_vars + n_mpi_vars,
+    }
+}
+
+/// A layer proven by the generalized sumcheck: one phase per input slot, each with the degree of
+/// the largest exponent of its slot, then the SIMD and MPI variables with the degree of the
+/// largest gate, and a degree 2 phase folding the claims of the slots past the first, if there
+/// are more than two slots.
+fn estimate_generalized_layer<F: FieldEngine>(
+    gates: &GeneralizedLayerGates<F>,
+    layer: &LayerStats,
+    n_simd_mpi_rounds: usize,
+    message_len: impl Fn(usize) -> usize,
+) -> LayerCostEstimate {
+    let n_vars = layer.input_var_num;
+    let mut sumcheck_rounds = gates.slot_num * n_vars + n_simd_mpi_rounds;
+    let mut proof_field_elements = (0..gates.slot_num)
+        .map(|slot| n_vars * message_len(gates.slot_degree(slot)))
+        .sum::<usize>()
+        + n_simd_mpi_rounds * message_len(gates.simd_mpi_degree())
+        + gates.slot_num;
+    if gates.reduces_claims() {
+        sumcheck_rounds += n_vars;
+        proof_field_elements += n_vars * message_len(2) + 1;
+    }
+
+    // every gate is expanded to its slots, and each slot phase halves its tables
+    let gate_ops = gates.simd_mpi_degree() * gates.gates.len() + layer.n_const;
+    let eq_ops = 2 * layer.output_size();
+    let phase_ops = (0..gates.slot_num)
+        .map(|slot| 2 * layer.input_size() * (gates.slot_degree(slot) + 1))
+        .sum::<usize>();
+
+    LayerCostEstimate {
+        sumcheck_rounds,
+        proof_field_elements,
+        prover_field_ops: gate_ops + eq_ops + phase_ops,
+    }
+}
+
+/// Bytes of a round message of the generic sumcheck, serialized with its length
+#[inline]
+fn iop_message_bytes(message_len: usize, challenge_field_bytes: usize) -> usize {
+    serialized_size(&0usize) + message_len * challenge_field_bytes
+}
+
+/// Bytes of a lookup: the multiplicities of the table entries, then the fractional sumcheck over
+/// the looked-up values of all the SIMD lanes and processes, which sends the 2 fractions of the
+/// root and, for each of the next layers, a degree 3 sumcheck and 4 evaluations.
+fn estimate_lookup_bytes<Cfg: GKREngine>(
+    lookup: &Lookup<Cfg::FieldConfig>,
+    n_simd_mpi_rounds: usize,
+    challenge_field_bytes: usize,
+) -> usize {
+    let message_len = 4 - usize::from(Cfg::COMPRESSED_SUMCHECK);
+    let n_leaf_vars = lookup.log_input_num + n_simd_mpi_rounds;
+    let fractional_sum_bytes = 4 * challenge_field_bytes
+        + (1..n_leaf_vars)
+            .map(|n_vars| {
+                n_vars * iop_message_bytes(message_len, challenge_field_bytes)
+                    + 4 * challenge_field_bytes
+            })
+            .sum::<usize>();
+
+    lookup.table.len() * serialized_size(&<Cfg::FieldConfig as FieldEngine>::CircuitField::ZERO)
+        + fractional_sum_bytes
+}
+
+/// Measure the serialized commitment and opening sizes of `Cfg::PCSConfig` by committing to
+/// and opening an all-zero polynomial with `n_input_vars` local variables, in a single process.
+///
+/// This runs the testing-only PCS setup, so it can be slow for pairing based schemes.
+pub fn measure_pcs_proof_bytes<Cfg: GKREngine>(n_input_vars: usize) -> (usize, usize) {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            n_input_vars,
+            &mpi_config,
+        );
+
+    let evals =
+        vec![<Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO; 1 << n_input_vars];
+    let poly = RefMultiLinearPoly::from_ref(&evals);
+
+    let commitment = Cfg::PCSConfig::commit(
+        &pcs_params,
+        &mpi_config,
+        &pcs_proving_key,
+        &poly,
+        &mut pcs_scratch,
+    )
+    .unwrap();
+    let mut commitment_bytes = vec![];
+    commitment.serialize_into(&mut commitment_bytes).unwrap();
+
+    let n_simd_vars =
+        <<Cfg::FieldConfig as FieldEngine>::SimdCircuitField as SimdField>::PACK_SIZE
+            .trailing_zeros() as usize;
+    let challenge = ExpanderSingleVarChallenge::<Cfg::FieldConfig>::new(
+        vec![<Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE; n_input_vars],
+        vec![<Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE; n_simd_vars],
+        vec![],
+    );
+    let mut transcript = Cfg::TranscriptConfig::new();
+    let opening = Cfg::PCSConfig::open(
+        &pcs_params,
+        &mpi_config,
+        &pcs_proving_key,
+        &poly,
+        &challenge,
+        &mut transcript,
+        &pcs_scratch,
+    )
+    .unwrap();
+    let mut opening_bytes = vec![];
+    opening.serialize_into(&mut opening_bytes).unwrap();
+
+    (commitment_bytes.len(), opening_bytes.len())
+}
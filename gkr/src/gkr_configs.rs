@@ -1,16 +1,25 @@
+use std::marker::PhantomData;
+
 use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     BN254Config, BabyBearx16Config, FieldEngine, GF2ExtConfig, GKREngine, GKRScheme,
-    Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
+    Goldilocksx1Config, Goldilocksx8Config, KoalaBearx16Config, M31x16Config, M31x16Ext6Config,
+    M31x1Config, M31x1Ext6Config,
 };
-use gkr_hashers::{MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
+use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
 use goldilocks::Goldilocksx8;
 use halo2curves::bn256::{Bn256, G1Affine};
 use mersenne31::M31x16;
 use poly_commit::{raw::RawExpanderGKR, HyperUniKZGPCS, HyraxPCS, OrionPCSForGKR};
 use transcript::BytesHashTranscript;
 
+#[cfg(feature = "mpi")]
+pub(crate) use gkr_engine::MPIConfig;
+/// Without the `mpi` feature, the configs run all their ranks as threads of one process
+#[cfg(not(feature = "mpi"))]
+pub(crate) type MPIConfig<'a> = gkr_engine::ThreadMPIConfig;
+
 // ============== M31 ==============
 declare_gkr_config!(
     pub M31x1ConfigSha2RawVanilla,
@@ -42,6 +51,29 @@ declare_gkr_config!(
     GKRScheme::Vanilla,
 );
 
+// ============== M31Ext6 ==============
+declare_gkr_config!(
+    pub M31x1Ext6ConfigSha2RawVanilla,
+    FieldType::M31x1Ext6,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub M31x16Ext6ConfigSha2OrionVanilla,
+    FieldType::M31x16Ext6,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Orion,
+    GKRScheme::Vanilla,
+);
+declare_gkr_config!(
+    pub M31x16Ext6ConfigSha2RawVanilla,
+    FieldType::M31x16Ext6,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+
 // ============== BN254 ==============
 declare_gkr_config!(
     pub BN254ConfigMIMC5Raw,
@@ -78,6 +110,13 @@ declare_gkr_config!(
     PolynomialCommitmentType::KZG,
     GKRScheme::Vanilla,
 );
+declare_gkr_config!(
+    pub BN254ConfigKeccakKZG,
+    FieldType::BN254,
+    FiatShamirHashType::Keccak256,
+    PolynomialCommitmentType::KZG,
+    GKRScheme::Vanilla,
+);
 
 // ============== GF2 ==============
 declare_gkr_config!(
@@ -129,3 +168,29 @@ declare_gkr_config!(
     PolynomialCommitmentType::Raw,
     GKRScheme::Vanilla,
 );
+
+// ============== Koalabear ==============
+declare_gkr_config!(
+    pub KoalaBearx16ConfigSha2Raw,
+    FieldType::KoalaBearx16,
+    FiatShamirHashType::SHA256,
+    PolynomialCommitmentType::Raw,
+    GKRScheme::Vanilla,
+);
+
+// ============== Compressed sumcheck ==============
+/// `Cfg` with compressed sumcheck round messages, e.g.
+/// `CompressedSumcheck<M31x16ConfigSha2RawVanilla>`
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedSumcheck<Cfg: GKREngine> {
+    _marker: PhantomData<Cfg>,
+}
+
+impl<Cfg: GKREngine> GKREngine for CompressedSumcheck<Cfg> {
+    type FieldConfig = Cfg::FieldConfig;
+    type MPIConfig = Cfg::MPIConfig;
+    type TranscriptConfig = Cfg::TranscriptConfig;
+    type PCSConfig = Cfg::PCSConfig;
+    const SCHEME: GKRScheme = Cfg::SCHEME;
+    const COMPRESSED_SUMCHECK: bool = true;
+}
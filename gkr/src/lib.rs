@@ -3,7 +3,7 @@
 pub mod prover;
 pub use prover::*;
 
-pub mod verifier;
+pub use expander_verifier as verifier;
 pub use verifier::*;
 
 pub mod utils;
@@ -11,8 +11,8 @@ pub mod utils;
 pub mod gkr_configs;
 pub use gkr_configs::*;
 
+pub mod cost_model;
+pub use cost_model::*;
+
 #[cfg(test)]
 mod tests;
-
-#[cfg(feature = "grinding")]
-const GRINDING_BITS: usize = 10;
@@ -6,3 +6,9 @@ pub use snark::*;
 
 pub mod gkr_batch;
 pub use gkr_batch::*;
+
+pub mod lookup;
+pub use lookup::*;
+
+pub mod profile;
+pub use profile::*;
@@ -12,6 +12,8 @@ use gkr_engine::{
 use polynomials::MultiLinearPoly;
 use sumcheck::{sumcheck_prove_gkr_layer_batch, ProverScratchPad};
 
+use crate::bind_output_claim;
+
 /// Prove N circuit instances in one batch GKR.
 ///
 /// All circuits must have the same structure (same layers, same gates).
@@ -25,6 +27,7 @@ pub fn gkr_prove_batch<F: FieldEngine>(
     circuits: &[Circuit<F>],
     scratch_pads: &mut [ProverScratchPad<F>],
     transcript: &mut impl Transcript,
+    compressed_sumcheck: bool,
 ) -> (F::ChallengeField, ExpanderDualVarChallenge<F>) {
     let n = circuits.len();
     assert_eq!(n, scratch_pads.len());
@@ -43,6 +46,7 @@ pub fn gkr_prove_batch<F: FieldEngine>(
 
     // Compute batch-combined claimed_v from all instances' output values.
     let claimed_v = batch_eval_output_vals::<F>(circuits, &challenge.challenge_x());
+    bind_output_claim(transcript, &claimed_v);
 
     let mut alpha = None;
     for i in (0..layer_num).rev() {
@@ -56,6 +60,7 @@ pub fn gkr_prove_batch<F: FieldEngine>(
             transcript,
             scratch_pads,
             i == layer_num - 1,
+            compressed_sumcheck,
         );
 
         if challenge.rz_1.is_some() {
@@ -2,18 +2,20 @@
 
 use circuit::Circuit;
 use gkr_engine::{
-    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
-    Transcript,
+    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, Transcript,
 };
 use sumcheck::{sumcheck_prove_gkr_layer, ProverScratchPad};
 use utils::timer::Timer;
 
+use crate::bind_output_claim;
+
 #[allow(clippy::type_complexity)]
 pub fn gkr_prove<F: FieldEngine>(
     circuit: &Circuit<F>,
     sp: &mut ProverScratchPad<F>,
     transcript: &mut impl Transcript,
-    mpi_config: &MPIConfig,
+    mpi_config: &impl MPIEngine,
+    compressed_sumcheck: bool,
 ) -> (F::ChallengeField, ExpanderDualVarChallenge<F>) {
     let layer_num = circuit.layers.len();
 
@@ -28,13 +30,15 @@ pub fn gkr_prove<F: FieldEngine>(
     let mut alpha = None;
 
     let output_vals = &circuit.layers.last().unwrap().output_vals;
-    let claimed_v = F::collectively_eval_circuit_vals_at_expander_challenge(
+    let mut claimed_v = F::collectively_eval_circuit_vals_at_expander_challenge(
         output_vals,
         &challenge.challenge_x(),
         &mut sp.hg_evals,
         &mut sp.eq_evals_first_half, // confusing name here..
         mpi_config,
     );
+    mpi_config.root_broadcast_f(&mut claimed_v);
+    bind_output_claim(transcript, &claimed_v);
 
     for i in (0..layer_num).rev() {
         let timer = Timer::new(
@@ -55,6 +59,7 @@ pub fn gkr_prove<F: FieldEngine>(
             sp,
             mpi_config,
             i == layer_num - 1,
+            compressed_sumcheck,
         );
 
         if challenge.rz_1.is_some() {
@@ -0,0 +1,172 @@
+//! This module implements the prover of the lookups declared by a circuit.
+//!
+//! For every lookup, the prover sends the multiplicity of each table entry among the looked-up
+//! values `w_i`, and proves with a fractional sumcheck that
+//!     sum_i 1 / (alpha - w_i) = sum_j m_j / (alpha - t_j)
+//! for a random `alpha`. The fractional sumcheck ends with a claim on the input MLE, which is
+//! reduced together with the GKR claims on the input into a single claim by a degree 2 sumcheck
+//! over `sum_k beta^k * eq(r_k, x) * V(x)`, so that the input is opened only once.
+//! The lookups and the reduction are proven by the root process over the gathered inputs.
+
+use std::{collections::HashMap, sync::Arc};
+
+use arith::{Field, SimdField};
+use circuit::{Circuit, Lookup};
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, MPIEngine, Transcript};
+use polynomials::{EqPolynomial, MultiLinearPoly, VirtualPolynomial};
+use sumcheck::{fractional_sum_prove, SumCheck};
+
+/// Prove the lookups of the circuit and reduce their claims and the claims of GKR on the input,
+/// at `input_challenges`, to a single claim. Returns the challenge to open the input at.
+pub fn prove_lookups<F: FieldEngine>(
+    circuit: &Circuit<F>,
+    input_challenges: &[ExpanderSingleVarChallenge<F>],
+    transcript: &mut impl Transcript,
+    mpi_config: &impl MPIEngine,
+    compressed_sumcheck: bool,
+) -> ExpanderSingleVarChallenge<F> {
+    let log_input_size = circuit.log_input_size();
+    let input_vals = &circuit.layers[0].input_vals;
+    let world_size = mpi_config.world_size();
+
+    let mut challenges = input_challenges.to_vec();
+    for lookup in &circuit.lookups {
+        assert!(
+            lookup.fits_field(world_size),
+            "too many looked-up values for the circuit field"
+        );
+
+        let local_vals = input_vals
+            [lookup.input_offset..lookup.input_offset + (1 << lookup.log_input_num)]
+            .iter()
+            .flat_map(|v| v.unpack())
+            .collect::<Vec<_>>();
+        let mut vals = if mpi_config.is_root() {
+            vec![F::CircuitField::ZERO; local_vals.len() * world_size]
+        } else {
+            vec![]
+        };
+        mpi_config.gather_vec(&local_vals, &mut vals);
+
+        if mpi_config.is_root() {
+            challenges.push(prove_lookup(
+                lookup,
+                &vals,
+                log_input_size,
+                transcript,
+                compressed_sumcheck,
+            ));
+        }
+    }
+
+    let mut inputs = if mpi_config.is_root() {
+        vec![F::SimdCircuitField::ZERO; input_vals.len() * world_size]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(input_vals, &mut inputs);
+
+    let mut challenge = if mpi_config.is_root() {
+        prove_claim_reduction(
+            &challenges,
+            &inputs,
+            log_input_size,
+            transcript,
+            compressed_sumcheck,
+        )
+    } else {
+        ExpanderSingleVarChallenge::new(
+            vec![F::ChallengeField::ZERO; log_input_size],
+            vec![F::ChallengeField::ZERO; F::get_field_pack_size().trailing_zeros() as usize],
+            vec![F::ChallengeField::ZERO; world_size.trailing_zeros() as usize],
+        )
+    };
+    challenge
+        .rz
+        .iter_mut()
+        .chain(challenge.r_simd.iter_mut())
+        .chain(challenge.r_mpi.iter_mut())
+        .for_each(|r| mpi_config.root_broadcast_f(r));
+    challenge
+}
+
+/// Prove that the looked-up values of all processes lie in the table,
+/// and return the challenge of the resulting claim on the input
+fn prove_lookup<F: FieldEngine>(
+    lookup: &Lookup<F>,
+    vals: &[F::CircuitField],
+    log_input_size: usize,
+    transcript: &mut impl Transcript,
+    compressed_sumcheck: bool,
+) -> ExpanderSingleVarChallenge<F> {
+    let mut table_index = HashMap::new();
+    for (i, t) in lookup.table.iter().enumerate() {
+        table_index.entry(*t).or_insert(i);
+    }
+    let mut multiplicities = vec![0u64; lookup.table.len()];
+    for v in vals {
+        let i = table_index
+            .get(v)
+            .expect("a looked-up value is not in the table");
+        multiplicities[*i] += 1;
+    }
+    multiplicities
+        .iter()
+        .for_each(|m| transcript.append_field_element(&F::CircuitField::from(*m)));
+
+    let alpha = transcript.generate_field_element::<F::ChallengeField>();
+    let numerators = vec![F::ChallengeField::ONE; vals.len()];
+    let denominators = vals
+        .iter()
+        .map(|v| alpha - F::ChallengeField::from(*v))
+        .collect();
+    let claim = fractional_sum_prove(numerators, denominators, compressed_sumcheck, transcript);
+
+    lookup.input_challenge(log_input_size, &claim.point)
+}
+
+/// Reduce the claims on the input at `challenges` to a single claim, given the inputs of all
+/// processes
+fn prove_claim_reduction<F: FieldEngine>(
+    challenges: &[ExpanderSingleVarChallenge<F>],
+    inputs: &[F::SimdCircuitField],
+    log_input_size: usize,
+    transcript: &mut impl Transcript,
+    compressed_sumcheck: bool,
+) -> ExpanderSingleVarChallenge<F> {
+    // the variables are ordered as in `ExpanderSingleVarChallenge::global_xs`
+    let vals = inputs
+        .iter()
+        .flat_map(|v| v.unpack())
+        .map(F::ChallengeField::from)
+        .collect::<Vec<_>>();
+    let num_vars = vals.len().trailing_zeros() as usize;
+
+    let beta = transcript.generate_field_element::<F::ChallengeField>();
+    let mut weights = vec![F::ChallengeField::ZERO; vals.len()];
+    let mut coef = F::ChallengeField::ONE;
+    for challenge in challenges {
+        let eq = EqPolynomial::build_eq_x_r(&challenge.global_xs());
+        weights.iter_mut().zip(eq).for_each(|(w, e)| *w += coef * e);
+        coef *= beta;
+    }
+
+    let vals = Arc::new(MultiLinearPoly::new(vals));
+    let mut poly = VirtualPolynomial::new(num_vars);
+    poly.add_mle_list(
+        [Arc::new(MultiLinearPoly::new(weights)), vals.clone()],
+        F::ChallengeField::ONE,
+    );
+    let proof = if compressed_sumcheck {
+        SumCheck::prove_virtual_compressed(&poly, transcript)
+    } else {
+        SumCheck::prove_virtual(&poly, transcript)
+    };
+    transcript.append_field_element(&vals.eval_reverse_order(&proof.point));
+
+    let point = proof.export_point_to_expander();
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let (r_simd, rest) = point.split_at(simd_var_num);
+    let (rz, r_mpi) = rest.split_at(log_input_size);
+    ExpanderSingleVarChallenge::new(rz.to_vec(), r_simd.to_vec(), r_mpi.to_vec())
+}
@@ -0,0 +1,36 @@
+//! Gathering of the profiles recorded by `utils::profiler` on every process.
+
+use gkr_engine::MPIEngine;
+use utils::profiler::Profile;
+
+/// Gather the profiles of all processes on the root, indexed by rank.
+/// Returns an empty vector on the other processes.
+pub fn gather_profiles(profile: &Profile, mpi_config: &impl MPIEngine) -> Vec<Profile> {
+    let mut bytes = profile.to_bytes();
+
+    // the profiles are padded to the longest encoding to be gathered as a vector
+    let mut lens = if mpi_config.is_root() {
+        vec![0u64; mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&[bytes.len() as u64], &mut lens);
+    let mut max_len = lens.iter().copied().max().unwrap_or(0);
+    mpi_config.root_broadcast_f(&mut max_len);
+    bytes.resize(max_len as usize, 0);
+
+    let mut all_bytes = if mpi_config.is_root() {
+        vec![0u8; bytes.len() * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(&bytes, &mut all_bytes);
+
+    if !mpi_config.is_root() {
+        return vec![];
+    }
+    all_bytes
+        .chunks(bytes.len())
+        .map(|chunk| Profile::from_bytes(chunk).expect("malformed profile"))
+        .collect()
+}
@@ -1,10 +1,12 @@
 //! This module implements the whole GKR prover, including the IOP and PCS.
 
+use std::marker::PhantomData;
+
 use arith::Field;
 use circuit::Circuit;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, MPIConfig,
-    MPIEngine, Proof, StructuredReferenceString, Transcript,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, MPIEngine, Proof,
+    StructuredReferenceString, Transcript,
 };
 use polynomials::{
     MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension, RefMultiLinearPoly,
@@ -14,52 +16,26 @@ use sumcheck::ProverScratchPad;
 use transcript::transcript_root_broadcast;
 use utils::timer::Timer;
 
-use crate::gkr_prove;
-
 #[cfg(feature = "grinding")]
-pub(crate) fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, mpi_config: &MPIConfig) {
-    use crate::GRINDING_BITS;
-
-    let timer = Timer::new("grinding", mpi_config.is_root());
-
-    let mut hash_bytes = vec![];
-
-    // ceil(32/field_size)
-    let num_field_elements = (31 + <Cfg::FieldConfig as FieldEngine>::ChallengeField::SIZE)
-        / <Cfg::FieldConfig as FieldEngine>::ChallengeField::SIZE;
-
-    let initial_hash = transcript
-        .generate_field_elements::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>(
-            num_field_elements,
-        );
-    initial_hash
-        .iter()
-        .for_each(|h| h.serialize_into(&mut hash_bytes).unwrap()); // TODO: error propagation
-
-    assert!(hash_bytes.len() >= 32, "hash len: {}", hash_bytes.len());
-    hash_bytes.truncate(32);
-
-    transcript.lock_proof();
-    for _ in 0..(1 << GRINDING_BITS) {
-        transcript.append_u8_slice(&hash_bytes);
-        hash_bytes = transcript.generate_u8_slice(32);
-    }
-    transcript.append_u8_slice(&hash_bytes[..32]);
-    transcript.unlock_proof();
-    timer.stop();
-}
+use crate::grind;
+use crate::{bind_statement, gkr_configs::MPIConfig, gkr_prove, prove_lookups};
 
+/// The GKR prover, running on top of the MPI engine `M`.
+/// `M` is OpenMPI by default, `ThreadMPIConfig` runs all the ranks inside one process and is
+/// the default without the `mpi` feature.
 #[derive(Default)]
-pub struct Prover<'a, Cfg: GKREngine> {
-    pub mpi_config: MPIConfig<'a>,
+pub struct Prover<'a, Cfg: GKREngine, M: MPIEngine = MPIConfig<'a>> {
+    pub mpi_config: M,
     sp: ProverScratchPad<Cfg::FieldConfig>,
+    _marker: PhantomData<&'a ()>,
 }
 
-impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
-    pub fn new(mpi_config: MPIConfig<'a>) -> Self {
+impl<Cfg: GKREngine, M: MPIEngine> Prover<'_, Cfg, M> {
+    pub fn new(mpi_config: M) -> Self {
         Prover {
             mpi_config,
             sp: ProverScratchPad::default(),
+            _marker: PhantomData,
         }
     }
 
@@ -96,9 +72,20 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         let proving_timer = Timer::new("prover", self.mpi_config.is_root());
         let mut transcript = Cfg::TranscriptConfig::new();
 
-        // Bind public input to the FS transcript to prevent malleability attacks
+        // Bind the public inputs of all the processes to the FS transcript to prevent
+        // malleability attacks
+        let mut public_input = if self.mpi_config.is_root() {
+            vec![
+                <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ZERO;
+                c.public_input.len() * self.mpi_config.world_size()
+            ]
+        } else {
+            vec![]
+        };
+        self.mpi_config
+            .gather_vec(&c.public_input, &mut public_input);
         if self.mpi_config.is_root() {
-            for v in &c.public_input {
+            for v in &public_input {
                 transcript.append_field_element(v);
             }
         }
@@ -118,11 +105,12 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
             let mut buffer = vec![];
             commitment.unwrap().serialize_into(&mut buffer).unwrap(); // TODO: error propagation
             transcript.append_commitment(&buffer);
+            bind_statement::<Cfg>(&mut transcript, c, self.mpi_config.world_size());
         }
         pcs_commit_timer.stop();
 
         #[cfg(feature = "grinding")]
-        grind::<Cfg>(&mut transcript, &self.mpi_config);
+        grind::<Cfg>(&mut transcript, self.mpi_config.is_root());
 
         if self.mpi_config.is_root() {
             c.fill_rnd_coefs(&mut transcript);
@@ -134,16 +122,43 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
         transcript_root_broadcast(&mut transcript, &self.mpi_config);
 
         let (claimed_v, challenge) = match Cfg::SCHEME {
-            GKRScheme::Vanilla => gkr_prove(c, &mut self.sp, &mut transcript, &self.mpi_config),
+            GKRScheme::Vanilla => gkr_prove(
+                c,
+                &mut self.sp,
+                &mut transcript,
+                &self.mpi_config,
+                Cfg::COMPRESSED_SUMCHECK,
+            ),
         };
         gkr_prove_timer.stop();
 
         transcript_root_broadcast(&mut transcript, &self.mpi_config);
 
+        // the lookups reduce their claims and the claims of GKR to a single opening
+        let has_lookups = !c.lookups.is_empty();
+        let mut challenge_x = if has_lookups {
+            let lookup_timer = Timer::new("lookup prove", self.mpi_config.is_root());
+            let input_challenges = [Some(challenge.challenge_x()), challenge.challenge_y()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let challenge_x = prove_lookups(
+                c,
+                &input_challenges,
+                &mut transcript,
+                &self.mpi_config,
+                Cfg::COMPRESSED_SUMCHECK,
+            );
+            transcript_root_broadcast(&mut transcript, &self.mpi_config);
+            lookup_timer.stop();
+            challenge_x
+        } else {
+            challenge.challenge_x()
+        };
+
         let pcs_open_timer = Timer::new("pcs open", self.mpi_config.is_root());
 
         // open
-        let mut challenge_x = challenge.challenge_x();
         let mut mle_ref = MutRefMultiLinearPoly::from_ref(&mut c.layers[0].input_vals);
         self.prove_input_layer_claim(
             &mut mle_ref,
@@ -154,7 +169,7 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
             &mut transcript,
         );
 
-        if let Some(mut challenge_y) = challenge.challenge_y() {
+        if let Some(mut challenge_y) = challenge.challenge_y().filter(|_| !has_lookups) {
             transcript_root_broadcast(&mut transcript, &self.mpi_config);
             self.prove_input_layer_claim(
                 &mut mle_ref,
@@ -176,7 +191,7 @@ impl<'a, Cfg: GKREngine> Prover<'a, Cfg> {
     }
 }
 
-impl<Cfg: GKREngine> Prover<'_, Cfg> {
+impl<Cfg: GKREngine, M: MPIEngine> Prover<'_, Cfg, M> {
     fn prove_input_layer_claim(
         &self,
         inputs: &mut MutRefMultiLinearPoly<<Cfg::FieldConfig as FieldEngine>::SimdCircuitField>,
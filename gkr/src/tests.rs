@@ -1,2 +1,14 @@
+mod common;
+mod compressed_sumcheck;
+mod cost_model;
+mod generalized_layer;
+#[cfg(feature = "mpi")]
 mod gkr_correctness;
+mod lookup;
+mod output_statement;
+mod profile;
+mod statement;
 mod system;
+mod tcp_mpi;
+mod thread_mpi;
+mod verify_bytes;
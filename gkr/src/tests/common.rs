@@ -0,0 +1,108 @@
+//! Proving and verifying helpers shared by the tests of small circuits on several ranks.
+
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{Circuit, Witness};
+use gkr_engine::{FieldEngine, GKREngine, MPIEngine, Proof, ThreadMPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+
+use crate::{OutputStatement, Prover, Verifier};
+
+/// Random private inputs of `circuit` for every SIMD lane of `world_size` ranks
+pub(crate) fn random_witness<C: FieldEngine>(
+    circuit: &Circuit<C>,
+    world_size: usize,
+) -> Witness<C> {
+    let mut rng = test_rng();
+    let num_witnesses = C::get_field_pack_size() * world_size;
+    let log_input_size = circuit.log_input_size();
+    Witness {
+        num_witnesses,
+        num_private_inputs_per_witness: 1 << log_input_size,
+        num_public_inputs_per_witness: 0,
+        values: (0..num_witnesses << log_input_size)
+            .map(|_| C::CircuitField::random_unsafe(&mut rng))
+            .collect(),
+    }
+}
+
+/// Prove `circuit` on the share of `witness` of the rank of `mpi_config`.
+/// Returns, on the root rank, the outputs of all the ranks along with the proof.
+/// The circuit is not `Sync`, so each rank of a thread world builds its own.
+pub(crate) fn prove<Cfg: GKREngine, M: MPIEngine + Clone>(
+    mpi_config: M,
+    mut circuit: Circuit<Cfg::FieldConfig>,
+    witness: &Witness<Cfg::FieldConfig>,
+) -> (
+    Vec<<Cfg::FieldConfig as FieldEngine>::SimdCircuitField>,
+    Proof,
+) {
+    circuit.prover_process_witness(witness.clone(), &mpi_config);
+
+    let mut prover = Prover::<Cfg, M>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    let (_, proof) = prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let output_vals = &circuit.layers.last().unwrap().output_vals;
+    let mut outputs = if mpi_config.is_root() {
+        vec![Default::default(); output_vals.len() * mpi_config.world_size()]
+    } else {
+        vec![]
+    };
+    mpi_config.gather_vec(output_vals, &mut outputs);
+    (outputs, proof)
+}
+
+/// Verify the proof of `circuit` generated by `world_size` ranks against the outputs of
+/// all the ranks
+pub(crate) fn verify<Cfg: GKREngine>(
+    world_size: usize,
+    circuit: &Circuit<Cfg::FieldConfig>,
+    witness: &Witness<Cfg::FieldConfig>,
+    outputs: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+    proof: &Proof,
+    parallel: bool,
+) -> bool {
+    // the PCS setup of a `world_size` prover only reads the world size of the root
+    let mpi_config = ThreadMPIConfig::new_world(world_size).swap_remove(0);
+    let mut circuit = circuit.clone();
+    let proof_shape = circuit.proof_shape(world_size);
+    circuit.verifier_process_witness(witness.clone(), &proof_shape);
+
+    let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
+        Cfg::FieldConfig,
+        Cfg::PCSConfig,
+    >(circuit.log_input_size(), &mpi_config);
+    let public_input = circuit.public_input.clone();
+    let output_statement = OutputStatement::Values(outputs.to_vec());
+    let verifier = Verifier::<Cfg>::new(proof_shape);
+    if parallel {
+        verifier.par_verify_output_statement(
+            &mut circuit,
+            &public_input,
+            &output_statement,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    } else {
+        verifier.verify_output_statement(
+            &mut circuit,
+            &public_input,
+            &output_statement,
+            &pcs_params,
+            &pcs_verification_key,
+            proof,
+        )
+    }
+}
@@ -0,0 +1,67 @@
+use arith::Field;
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+
+use super::{
+    common::{prove, random_witness, verify},
+    thread_mpi::build_circuit,
+};
+use crate::{CompressedSumcheck, M31x16ConfigSha2RawVanilla};
+
+#[test]
+fn test_compressed_sumcheck_proof() {
+    type Cfg = M31x16ConfigSha2RawVanilla<'static>;
+    type CompressedCfg = CompressedSumcheck<Cfg>;
+    type ChallengeF = <<Cfg as GKREngine>::FieldConfig as FieldEngine>::ChallengeField;
+    const WORLD_SIZE: usize = 4;
+    let circuit = build_circuit::<<Cfg as GKREngine>::FieldConfig>();
+    let witness = random_witness(&circuit, WORLD_SIZE);
+
+    let (_, proof) = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+    })
+    .swap_remove(0);
+    let (outputs, compressed_proof) = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        prove::<CompressedCfg, _>(mpi_config, build_circuit(), &witness)
+    })
+    .swap_remove(0);
+    assert!(verify::<CompressedCfg>(
+        WORLD_SIZE,
+        &circuit,
+        &witness,
+        &outputs,
+        &compressed_proof,
+        false
+    ));
+
+    // p(1) of the first round is recovered from the claimed value, which binds the transcript
+    let mut wrong_outputs = outputs.clone();
+    wrong_outputs[0] += <<Cfg as GKREngine>::FieldConfig as FieldEngine>::SimdCircuitField::ONE;
+    assert!(!verify::<CompressedCfg>(
+        WORLD_SIZE,
+        &circuit,
+        &witness,
+        &wrong_outputs,
+        &compressed_proof,
+        false
+    ));
+
+    // one challenge field element less per sumcheck round
+    let n_simd_mpi_vars =
+        <Cfg as GKREngine>::FieldConfig::get_field_pack_size().ilog2() + WORLD_SIZE.ilog2();
+    let n_rounds = circuit
+        .layers
+        .iter()
+        .map(|layer| {
+            let n_phases = if layer.structure_info.skip_sumcheck_phase_two {
+                1
+            } else {
+                2
+            };
+            layer.input_var_num * n_phases + n_simd_mpi_vars as usize
+        })
+        .sum::<usize>();
+    assert_eq!(
+        proof.bytes.len() - compressed_proof.bytes.len(),
+        n_rounds * ChallengeF::SIZE
+    );
+}
@@ -0,0 +1,92 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul, Witness};
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+
+use super::{
+    common::{prove, random_witness},
+    generalized_layer, lookup,
+};
+use crate::{
+    estimate_gkr_cost, measure_pcs_proof_bytes, BN254ConfigSha2Raw, CompressedSumcheck,
+    M31x16ConfigSha2RawVanilla, M31x1ConfigSha2RawVanilla,
+};
+
+// layer 0: [in_0 * in_1, in_2 + in_3, in_4 * in_5 + in_6, 0]
+// layer 1: [w_0 * w_1 + w_2]
+fn build_vanilla_circuit<C: FieldEngine>() -> Circuit<C> {
+    let add = |i: usize, o: usize| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: 3,
+        output_var_num: 2,
+        mul: vec![mul(0, 1, 0), mul(4, 5, 2)],
+        add: vec![add(2, 1), add(3, 1), add(6, 2)],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![mul(0, 1, 0)],
+        add: vec![add(2, 0)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer_0, layer_1],
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+    circuit
+}
+
+/// The estimated IOP bytes and the measured PCS bytes add up to the size of a single process proof
+fn check_proof_size<Cfg: GKREngine>(
+    circuit: &Circuit<Cfg::FieldConfig>,
+    witness: &Witness<Cfg::FieldConfig>,
+) {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let (_, proof) = prove::<Cfg, _>(mpi_config, circuit.clone(), witness);
+
+    let estimate = estimate_gkr_cost::<Cfg>(circuit, 1);
+    let (commitment_bytes, opening_bytes) =
+        measure_pcs_proof_bytes::<Cfg>(circuit.log_input_size());
+    assert_eq!(
+        proof.bytes.len(),
+        estimate.iop_proof_bytes() + commitment_bytes + estimate.num_pcs_openings * opening_bytes
+    );
+}
+
+fn test_proof_size_helper<Cfg: GKREngine>() {
+    let circuit = build_vanilla_circuit::<Cfg::FieldConfig>();
+    check_proof_size::<Cfg>(&circuit, &random_witness(&circuit, 1));
+
+    // uni and monomial gates, among which pow5, proven by the generalized sumcheck
+    let circuit = generalized_layer::build_circuit::<Cfg::FieldConfig>();
+    check_proof_size::<Cfg>(&circuit, &random_witness(&circuit, 1));
+
+    let circuit =
+        lookup::build_circuit::<Cfg::FieldConfig>(lookup::range_table::<Cfg::FieldConfig>(0));
+    let witness = lookup::range_checked_witness::<Cfg::FieldConfig>(1, 1 << lookup::LOG_RANGE);
+    check_proof_size::<Cfg>(&circuit, &witness);
+}
+
+#[test]
+fn test_estimated_proof_size() {
+    test_proof_size_helper::<M31x1ConfigSha2RawVanilla>();
+    test_proof_size_helper::<M31x16ConfigSha2RawVanilla>();
+    test_proof_size_helper::<BN254ConfigSha2Raw>();
+    test_proof_size_helper::<CompressedSumcheck<M31x16ConfigSha2RawVanilla>>();
+}
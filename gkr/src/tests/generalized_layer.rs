@@ -0,0 +1,157 @@
+use arith::Field;
+use circuit::{
+    Circuit, CircuitLayer, CoefType, GateAdd, GateMonomial, GateMul, GateUni, UNI_GATE_TYPE_POW5,
+};
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+use sumcheck::{needs_generalized_sumcheck, GeneralizedLayerGates};
+
+use super::common::{prove, random_witness, verify};
+use crate::{
+    BN254ConfigSha2Raw, CompressedSumcheck, GF2ExtConfigSha2Raw, M31x16ConfigSha2RawVanilla,
+    M31x1ConfigSha2RawVanilla,
+};
+
+const LOG_INPUT_SIZE: usize = 3;
+
+// layer 0: [in_0 * in_1 + in_2, in_3^5, in_4^7, 2 * in_5 * in_6 * in_7 + in_0^2 * in_1]
+// layer 1: [w_0 * w_1, w_2^5 + w_3]
+// layer 2: [v_0^5, v_1^3]
+// layer 3: [u_0 * u_1 + u_1]
+pub(super) fn build_circuit<C: FieldEngine>() -> Circuit<C> {
+    let add = |i: usize, o: usize| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::from(3u32),
+        gate_type: 0,
+    };
+    let pow5 = |i: usize, o: usize| GateUni::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: UNI_GATE_TYPE_POW5,
+    };
+    let monomial = |i_ids: Vec<usize>, exponents: Vec<usize>, o: usize| GateMonomial::<C> {
+        i_ids,
+        exponents,
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::from(2u32),
+    };
+
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: LOG_INPUT_SIZE,
+        output_var_num: 2,
+        mul: vec![mul(0, 1, 0)],
+        add: vec![add(2, 0)],
+        uni: vec![pow5(3, 1)],
+        monomial: vec![
+            monomial(vec![4], vec![7], 2),
+            monomial(vec![5, 6, 7], vec![1, 1, 1], 3),
+            monomial(vec![0, 1], vec![2, 1], 3),
+        ],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![mul(0, 1, 0)],
+        add: vec![add(3, 1)],
+        uni: vec![pow5(2, 1)],
+        ..Default::default()
+    };
+    let layer_2 = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        uni: vec![pow5(0, 0)],
+        monomial: vec![monomial(vec![1], vec![3], 1)],
+        ..Default::default()
+    };
+    let layer_3 = CircuitLayer::<C> {
+        input_var_num: 1,
+        output_var_num: 1,
+        mul: vec![mul(0, 1, 0)],
+        add: vec![add(1, 0)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer_0, layer_1, layer_2, layer_3],
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+    circuit
+}
+
+fn test_generalized_layer_helper<Cfg: GKREngine>() {
+    let circuit = build_circuit::<Cfg::FieldConfig>();
+    for world_size in [1, 4] {
+        let witness = random_witness(&circuit, world_size);
+        let (outputs, proof) = ThreadMPIConfig::run(world_size, |mpi_config| {
+            prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+        })
+        .swap_remove(0);
+        assert!(verify::<Cfg>(
+            world_size, &circuit, &witness, &outputs, &proof, false
+        ));
+        assert!(verify::<Cfg>(
+            world_size, &circuit, &witness, &outputs, &proof, true
+        ));
+
+        let mut wrong_outputs = outputs.clone();
+        wrong_outputs[0] += <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ONE;
+        assert!(!verify::<Cfg>(
+            world_size,
+            &circuit,
+            &witness,
+            &wrong_outputs,
+            &proof,
+            false
+        ));
+    }
+}
+
+#[test]
+fn test_generalized_layer_shape() {
+    type C = <M31x1ConfigSha2RawVanilla<'static> as GKREngine>::FieldConfig;
+    let circuit = build_circuit::<C>();
+
+    // the mul gates of the input layer leave two claims, so a relay layer is added below
+    assert_eq!(circuit.layers.len(), 5);
+    assert!(!needs_generalized_sumcheck(&circuit.layers[0]));
+    assert!(!needs_generalized_sumcheck(&circuit.layers[4]));
+
+    let gates = GeneralizedLayerGates::new(&circuit.layers[1]);
+    assert_eq!(gates.slot_num, 3);
+    assert_eq!(gates.slot_degree(0), 8);
+    assert_eq!(gates.slot_degree(1), 2);
+    assert_eq!(gates.simd_mpi_degree(), 8);
+    assert!(gates.reduces_claims());
+
+    let gates = GeneralizedLayerGates::new(&circuit.layers[2]);
+    assert_eq!(gates.slot_num, 2);
+    assert_eq!(gates.simd_mpi_degree(), 6);
+    assert!(!gates.reduces_claims());
+
+    // a single input slot leaves a single claim
+    let gates = GeneralizedLayerGates::new(&circuit.layers[3]);
+    assert_eq!(gates.slot_num, 1);
+    assert!(circuit.layers[3].structure_info.skip_sumcheck_phase_two);
+}
+
+#[test]
+fn test_generalized_layer() {
+    test_generalized_layer_helper::<M31x1ConfigSha2RawVanilla>();
+    test_generalized_layer_helper::<M31x16ConfigSha2RawVanilla>();
+    test_generalized_layer_helper::<GF2ExtConfigSha2Raw>();
+    test_generalized_layer_helper::<BN254ConfigSha2Raw>();
+    test_generalized_layer_helper::<CompressedSumcheck<M31x16ConfigSha2RawVanilla>>();
+}
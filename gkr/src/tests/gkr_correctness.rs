@@ -9,8 +9,8 @@ use config_macros::declare_gkr_config;
 use gf2::GF2x128;
 use gkr_engine::{
     root_println, BN254Config, BabyBearx16Config, FieldEngine, FieldType, GF2ExtConfig, GKREngine,
-    GKRScheme, Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x1Config, MPIConfig,
-    MPIEngine, MPISharedMemory,
+    GKRScheme, Goldilocksx1Config, Goldilocksx8Config, M31x16Config, M31x16Ext6Config, M31x1Config,
+    M31x1Ext6Config, MPIConfig, MPIEngine, MPISharedMemory,
 };
 use gkr_hashers::{Keccak256hasher, MiMC5FiatShamirHasher, PoseidonFiatShamirHasher, SHA256hasher};
 use halo2curves::bn256::{Bn256, G1Affine};
@@ -23,7 +23,7 @@ use serdes::ExpSerde;
 use sha2::Digest;
 use transcript::BytesHashTranscript;
 
-use crate::{utils::*, Prover, Verifier};
+use crate::{utils::*, CompressedSumcheck, OutputStatement, Prover, Verifier};
 
 #[test]
 fn test_gkr_correctness() {
@@ -145,6 +145,27 @@ fn test_gkr_correctness() {
         PolynomialCommitmentType::Raw,
         GKRScheme::Vanilla,
     );
+    declare_gkr_config!(
+        C16,
+        FieldType::M31x16Ext6,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C17,
+        FieldType::M31x16Ext6,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Orion,
+        GKRScheme::Vanilla,
+    );
+    declare_gkr_config!(
+        C18,
+        FieldType::M31x1Ext6,
+        FiatShamirHashType::SHA256,
+        PolynomialCommitmentType::Raw,
+        GKRScheme::Vanilla,
+    );
     test_gkr_correctness_helper::<C0>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C1>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C2>(mpi_config.clone(), None);
@@ -161,6 +182,11 @@ fn test_gkr_correctness() {
     test_gkr_correctness_helper::<C13>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C14>(mpi_config.clone(), None);
     test_gkr_correctness_helper::<C15>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C16>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C17>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<C18>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<CompressedSumcheck<C1>>(mpi_config.clone(), None);
+    test_gkr_correctness_helper::<CompressedSumcheck<C10>>(mpi_config.clone(), None);
 }
 
 #[allow(unreachable_patterns)]
@@ -183,6 +209,8 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         FieldType::Goldilocksx1 => 2,
         FieldType::Goldilocksx8 => 2,
         FieldType::M31x1 => 2,
+        FieldType::M31x16Ext6 => 2,
+        FieldType::M31x1Ext6 => 2,
         FieldType::BabyBearx16 => 2,
         _ => unreachable!(),
     };
@@ -197,6 +225,8 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         FieldType::GF2Ext128 => "../".to_owned() + KECCAK_GF2_CIRCUIT,
         FieldType::M31x1 => "../".to_owned() + KECCAK_M31_CIRCUIT,
         FieldType::M31x16 => "../".to_owned() + KECCAK_M31_CIRCUIT,
+        FieldType::M31x1Ext6 => "../".to_owned() + KECCAK_M31_CIRCUIT,
+        FieldType::M31x16Ext6 => "../".to_owned() + KECCAK_M31_CIRCUIT,
         FieldType::BN254 => "../".to_owned() + KECCAK_BN254_CIRCUIT,
         FieldType::Goldilocksx1 => "../".to_owned() + KECCAK_GOLDILOCKS_CIRCUIT,
         FieldType::Goldilocksx8 => "../".to_owned() + KECCAK_GOLDILOCKS_CIRCUIT,
@@ -211,6 +241,8 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
         FieldType::GF2Ext128 => "../".to_owned() + KECCAK_GF2_WITNESS,
         FieldType::M31x1 => "../".to_owned() + KECCAK_M31_WITNESS,
         FieldType::M31x16 => "../".to_owned() + KECCAK_M31_WITNESS,
+        FieldType::M31x1Ext6 => "../".to_owned() + KECCAK_M31_WITNESS,
+        FieldType::M31x16Ext6 => "../".to_owned() + KECCAK_M31_WITNESS,
         FieldType::BN254 => "../".to_owned() + KECCAK_BN254_WITNESS,
         FieldType::Goldilocksx1 => "../".to_owned() + KECCAK_GOLDILOCKS_WITNESS,
         FieldType::Goldilocksx8 => "../".to_owned() + KECCAK_GOLDILOCKS_WITNESS,
@@ -225,6 +257,12 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
     assert!(output[..circuit.expected_num_output_zeros]
         .iter()
         .all(|f| f.is_zero()));
+    // the keccak circuits only write the outputs they constrain to zero, so that `verify`
+    // checks the claim of the prover against the circuit alone
+    assert!(matches!(
+        OutputStatement::from_circuit(&circuit),
+        Some(OutputStatement::AllZeros)
+    ));
 
     let mut prover = Prover::<Cfg>::new(mpi_config.clone());
     prover.prepare_mem(&circuit);
@@ -285,7 +323,7 @@ fn test_gkr_correctness_helper<Cfg: GKREngine>(
             proof.serialize_into(&mut buf).unwrap();
             file.write_all(&buf).unwrap();
         }
-        let verifier = Verifier::<Cfg>::new(mpi_config.clone());
+        let verifier = Verifier::<Cfg>::new(circuit.proof_shape(mpi_config.world_size()));
         println!("Verifier created.");
         let verification_start = Instant::now();
         assert!(verifier.verify(
@@ -0,0 +1,192 @@
+use std::io::Cursor;
+
+use arith::{Field, Fr};
+use ark_std::{rand::Rng, test_rng};
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul, Witness};
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig, Transcript};
+use gkr_hashers::SHA256hasher;
+use polynomials::MultiLinearPoly;
+use sumcheck::{fractional_sum_prove, fractional_sum_verify};
+use transcript::BytesHashTranscript;
+
+use super::common::{prove, verify};
+use crate::{
+    BN254ConfigSha2Raw, CompressedSumcheck, M31x16ConfigSha2RawVanilla, M31x1ConfigSha2RawVanilla,
+};
+
+const LOG_INPUT_SIZE: usize = 3;
+pub(super) const LOG_RANGE: usize = 4;
+
+// layer 0: [in_0 * in_1 + in_4 + in_5, in_6 * in_7 + in_2 + in_3]
+// the inputs 4..8 are range checked to [0, 2^LOG_RANGE)
+pub(super) fn build_circuit<C: FieldEngine>(table: Vec<C::CircuitField>) -> Circuit<C> {
+    let add = |i: usize, o: usize| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+
+    let layer = CircuitLayer::<C> {
+        input_var_num: LOG_INPUT_SIZE,
+        output_var_num: 1,
+        mul: vec![mul(0, 1, 0), mul(6, 7, 1)],
+        add: vec![add(4, 0), add(5, 0), add(2, 1), add(3, 1)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer],
+        ..Default::default()
+    };
+    circuit.declare_lookup(table, 4, 2);
+    circuit.pre_process_gkr();
+    circuit
+}
+
+pub(super) fn range_table<C: FieldEngine>(start: u32) -> Vec<C::CircuitField> {
+    (start..start + (1 << LOG_RANGE))
+        .map(C::CircuitField::from)
+        .collect()
+}
+
+pub(super) fn range_checked_witness<C: FieldEngine>(world_size: usize, bound: u32) -> Witness<C> {
+    let mut rng = test_rng();
+    let num_witnesses = C::get_field_pack_size() * world_size;
+    Witness {
+        num_witnesses,
+        num_private_inputs_per_witness: 1 << LOG_INPUT_SIZE,
+        num_public_inputs_per_witness: 0,
+        values: (0..num_witnesses)
+            .flat_map(|_| {
+                let mut inputs = (0..4)
+                    .map(|_| C::CircuitField::random_unsafe(&mut rng))
+                    .collect::<Vec<_>>();
+                inputs.extend((0..4).map(|_| C::CircuitField::from(rng.gen_range(0..bound))));
+                inputs
+            })
+            .collect(),
+    }
+}
+
+fn test_lookup_helper<Cfg: GKREngine>() {
+    let circuit = build_circuit::<Cfg::FieldConfig>(range_table::<Cfg::FieldConfig>(0));
+    for world_size in [1, 4] {
+        let witness = range_checked_witness::<Cfg::FieldConfig>(world_size, 1 << LOG_RANGE);
+        let (outputs, proof) = ThreadMPIConfig::run(world_size, |mpi_config| {
+            prove::<Cfg, _>(
+                mpi_config,
+                build_circuit(range_table::<Cfg::FieldConfig>(0)),
+                &witness,
+            )
+        })
+        .swap_remove(0);
+
+        assert!(verify::<Cfg>(
+            world_size, &circuit, &witness, &outputs, &proof, false
+        ));
+        assert!(verify::<Cfg>(
+            world_size, &circuit, &witness, &outputs, &proof, true
+        ));
+
+        let mut wrong_outputs = outputs.clone();
+        wrong_outputs[0] += <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ONE;
+        assert!(!verify::<Cfg>(
+            world_size,
+            &circuit,
+            &witness,
+            &wrong_outputs,
+            &proof,
+            false
+        ));
+
+        // the same proof against a table of the same size but other values
+        let shifted_circuit =
+            build_circuit::<Cfg::FieldConfig>(range_table::<Cfg::FieldConfig>(1 << LOG_RANGE));
+        assert!(!verify::<Cfg>(
+            world_size,
+            &shifted_circuit,
+            &witness,
+            &outputs,
+            &proof,
+            false
+        ));
+    }
+}
+
+#[test]
+fn test_lookup() {
+    test_lookup_helper::<M31x1ConfigSha2RawVanilla>();
+    test_lookup_helper::<M31x16ConfigSha2RawVanilla>();
+    test_lookup_helper::<BN254ConfigSha2Raw>();
+    test_lookup_helper::<CompressedSumcheck<M31x16ConfigSha2RawVanilla>>();
+}
+
+#[test]
+#[should_panic(expected = "a looked-up value is not in the table")]
+fn test_lookup_value_out_of_range() {
+    type C = <M31x1ConfigSha2RawVanilla<'static> as GKREngine>::FieldConfig;
+    let witness = range_checked_witness::<C>(1, 1 << (LOG_RANGE + 1));
+    let circuit = build_circuit::<C>(range_table::<C>(0));
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    prove::<M31x1ConfigSha2RawVanilla, _>(mpi_config, circuit, &witness);
+}
+
+#[test]
+fn test_fractional_sumcheck() {
+    let mut rng = test_rng();
+    for num_vars in 1..6 {
+        let numerators = (0..1 << num_vars)
+            .map(|_| Fr::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let denominators = (0..1 << num_vars)
+            .map(|_| Fr::random_unsafe(&mut rng))
+            .collect::<Vec<_>>();
+        let sum = numerators
+            .iter()
+            .zip(&denominators)
+            .map(|(p, q)| *p * q.inv().unwrap())
+            .sum::<Fr>();
+
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        let prover_claim = fractional_sum_prove(
+            numerators.clone(),
+            denominators.clone(),
+            false,
+            &mut transcript,
+        );
+        let proof = transcript.finalize_and_get_proof();
+
+        let mut transcript = BytesHashTranscript::<SHA256hasher>::new();
+        let claim = fractional_sum_verify::<Fr>(
+            num_vars,
+            false,
+            &mut Cursor::new(&proof.bytes),
+            &mut transcript,
+        )
+        .unwrap();
+        assert_eq!(claim, prover_claim);
+
+        let (p, q) = claim.root;
+        assert_eq!(p, sum * q);
+
+        let mut point = claim.point.clone();
+        point.reverse();
+        assert_eq!(
+            MultiLinearPoly::new(numerators).eval_reverse_order(&point),
+            claim.numerator
+        );
+        assert_eq!(
+            MultiLinearPoly::new(denominators).eval_reverse_order(&point),
+            claim.denominator
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul};
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+
+use crate::{
+    M31x16ConfigSha2RawVanilla, M31x1ConfigSha2RawVanilla, OutputStatement, Prover, Verifier,
+};
+
+// inputs: [a, b, c, d] with a = b, and c = -d if `c_plus_d_is_zero`
+// layer 0: [a - b, c + d, 0, a]
+// layer 1: [(a - b)^2, (c + d) * a], the first `expected_num_output_zeros` outputs are
+// constrained to zero
+fn build_circuit<C: FieldEngine>(
+    expected_num_output_zeros: usize,
+    c_plus_d_is_zero: bool,
+) -> Circuit<C> {
+    let add = |i: usize, o: usize, coef: C::CircuitField| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 2,
+        add: vec![
+            add(0, 0, C::CircuitField::ONE),
+            add(1, 0, -C::CircuitField::ONE),
+            add(2, 1, C::CircuitField::ONE),
+            add(3, 1, C::CircuitField::ONE),
+            add(0, 3, C::CircuitField::ONE),
+        ],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![mul(0, 0, 0), mul(1, 3, 1)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer_0, layer_1],
+        expected_num_output_zeros,
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+
+    let mut rng = test_rng();
+    let a = C::SimdCircuitField::random_unsafe(&mut rng);
+    let c = C::SimdCircuitField::random_unsafe(&mut rng);
+    let d = if c_plus_d_is_zero {
+        -c
+    } else {
+        C::SimdCircuitField::random_unsafe(&mut rng)
+    };
+    circuit.layers[0].input_vals = vec![a, a, c, d];
+    circuit
+}
+
+fn test_output_statement_helper<Cfg: GKREngine>() {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let mut circuit = build_circuit::<Cfg::FieldConfig>(1, false);
+
+    let mut prover = Prover::<Cfg, _>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    let (claimed_v, proof) = prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let outputs = circuit.layers.last().unwrap().output_vals.clone();
+    let statement = OutputStatement::<Cfg::FieldConfig>::from_outputs(
+        &outputs,
+        circuit.expected_num_output_zeros,
+        1,
+    )
+    .expect("the first output should be zero");
+
+    let verifier = Verifier::<Cfg>::new(circuit.proof_shape(1));
+    let public_input = circuit.public_input.clone();
+    let verify_statement =
+        |circuit: &mut Circuit<Cfg::FieldConfig>, statement: &OutputStatement<Cfg::FieldConfig>| {
+            let verified = verifier.verify_output_statement(
+                circuit,
+                &public_input,
+                statement,
+                &pcs_params,
+                &pcs_verification_key,
+                &proof,
+            );
+            let par_verified = verifier.par_verify_output_statement(
+                circuit,
+                &public_input,
+                statement,
+                &pcs_params,
+                &pcs_verification_key,
+                &proof,
+            );
+            assert_eq!(verified, par_verified);
+            verified
+        };
+
+    assert!(verify_statement(&mut circuit, &statement));
+    assert!(verify_statement(
+        &mut circuit,
+        &OutputStatement::Values(outputs.clone())
+    ));
+
+    // the verifier does not trust the prover about the other output
+    let OutputStatement::FirstZeros {
+        num_zeros,
+        mut other_outputs,
+    } = statement
+    else {
+        unreachable!()
+    };
+    other_outputs[0] += <Cfg::FieldConfig as FieldEngine>::SimdCircuitField::ONE;
+    assert!(!verify_statement(
+        &mut circuit,
+        &OutputStatement::FirstZeros {
+            num_zeros,
+            other_outputs,
+        }
+    ));
+    assert!(!verify_statement(&mut circuit, &OutputStatement::AllZeros));
+    assert!(!verify_statement(
+        &mut circuit,
+        &OutputStatement::Values(outputs[..1].to_vec())
+    ));
+
+    // the circuit does not fix its second output, so the claim of the prover cannot be checked
+    assert!(!verifier.verify(
+        &mut circuit,
+        &public_input,
+        &claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        &proof
+    ));
+}
+
+// both outputs are constrained to zero, `verify` checks claimed_v against their MLE
+fn test_claimed_v_helper<Cfg: GKREngine>(c_plus_d_is_zero: bool) {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let mut circuit = build_circuit::<Cfg::FieldConfig>(2, c_plus_d_is_zero);
+
+    let mut prover = Prover::<Cfg, _>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<Cfg::FieldConfig, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    let (claimed_v, proof) = prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    );
+
+    let verifier = Verifier::<Cfg>::new(circuit.proof_shape(1));
+    let public_input = circuit.public_input.clone();
+    let verify = |circuit: &mut Circuit<Cfg::FieldConfig>,
+                  claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField| {
+        let verified = verifier.verify(
+            circuit,
+            &public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &proof,
+        );
+        let par_verified = verifier.par_verify(
+            circuit,
+            &public_input,
+            claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &proof,
+        );
+        assert_eq!(verified, par_verified);
+        verified
+    };
+
+    // with outputs breaking the zero constraint, neither the honest claim nor a zero claim
+    // is accepted
+    assert_eq!(verify(&mut circuit, &claimed_v), c_plus_d_is_zero);
+    assert_eq!(
+        verify(
+            &mut circuit,
+            &<Cfg::FieldConfig as FieldEngine>::ChallengeField::ZERO
+        ),
+        c_plus_d_is_zero
+    );
+
+    // a forged claimed_v is rejected
+    let forged_v = claimed_v + <Cfg::FieldConfig as FieldEngine>::ChallengeField::ONE;
+    assert!(!verify(&mut circuit, &forged_v));
+}
+
+#[test]
+fn test_output_statement() {
+    test_output_statement_helper::<M31x1ConfigSha2RawVanilla>();
+    test_output_statement_helper::<M31x16ConfigSha2RawVanilla>();
+}
+
+#[test]
+fn test_claimed_v() {
+    for c_plus_d_is_zero in [true, false] {
+        test_claimed_v_helper::<M31x1ConfigSha2RawVanilla>(c_plus_d_is_zero);
+        test_claimed_v_helper::<M31x16ConfigSha2RawVanilla>(c_plus_d_is_zero);
+    }
+}
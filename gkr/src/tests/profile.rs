@@ -0,0 +1,45 @@
+use gkr_engine::{GKREngine, MPIEngine, ThreadMPIConfig};
+use utils::profiler;
+
+use super::{
+    common::{prove, random_witness},
+    thread_mpi::build_circuit,
+};
+use crate::{gather_profiles, M31x16ConfigSha2RawVanilla};
+
+#[test]
+fn test_gather_profiles() {
+    type Cfg = M31x16ConfigSha2RawVanilla<'static>;
+    const WORLD_SIZE: usize = 4;
+    let circuit = build_circuit::<<Cfg as GKREngine>::FieldConfig>();
+    let witness = random_witness(&circuit, WORLD_SIZE);
+
+    let results = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        profiler::start();
+        prove::<Cfg, _>(mpi_config.clone(), build_circuit(), &witness);
+        // the ranks record spans of different lengths
+        for _ in 0..mpi_config.world_rank() {
+            profiler::exit(profiler::enter("rank span").unwrap());
+        }
+        let profile = profiler::finish().unwrap();
+        (profile.clone(), gather_profiles(&profile, &mpi_config))
+    });
+
+    let (root_profile, profiles) = &results[0];
+    assert_eq!(profiles.len(), WORLD_SIZE);
+    assert_eq!(&profiles[0], root_profile);
+    for (rank, (profile, gathered)) in results.iter().enumerate() {
+        assert_eq!(&profiles[rank], profile);
+        assert_eq!(gathered.is_empty(), rank != 0);
+
+        let names = profile
+            .spans
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"prover"));
+        assert!(names.contains(&"gkr prove"));
+        assert!(names.contains(&"sumcheck phase one"));
+        assert_eq!(names.iter().filter(|n| **n == "rank span").count(), rank);
+    }
+}
@@ -0,0 +1,107 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul};
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+
+use super::common::{prove, random_witness, verify};
+use crate::{
+    BN254ConfigSha2Raw, CompressedSumcheck, M31x16ConfigPoseidonRawVanilla,
+    M31x16ConfigSha2RawVanilla, M31x1ConfigSha2RawVanilla,
+};
+
+const LOG_INPUT_SIZE: usize = 2;
+
+// layer 0: [mul_coef * in_0 * in_1 + in_2, in_3]
+fn build_circuit<C: FieldEngine>(mul_coef: u32, expected_num_output_zeros: usize) -> Circuit<C> {
+    let layer = CircuitLayer::<C> {
+        input_var_num: LOG_INPUT_SIZE,
+        output_var_num: 1,
+        mul: vec![GateMul {
+            i_ids: [0, 1],
+            o_id: 0,
+            coef_type: CoefType::Constant,
+            coef: C::CircuitField::from(mul_coef),
+            gate_type: 0,
+        }],
+        add: [(2, 0), (3, 1)]
+            .map(|(i, o)| GateAdd {
+                i_ids: [i],
+                o_id: o,
+                coef_type: CoefType::Constant,
+                coef: C::CircuitField::ONE,
+                gate_type: 0,
+            })
+            .to_vec(),
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer],
+        expected_num_output_zeros,
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+    circuit
+}
+
+fn test_proof_bound_to_circuit_helper<Cfg: GKREngine>() {
+    let circuit = build_circuit::<Cfg::FieldConfig>(3, 0);
+    // the same shape, with another coefficient
+    let other_coef = build_circuit::<Cfg::FieldConfig>(5, 0);
+    // the same gates, GKR alone does not tell these apart when the output claim is trusted
+    let other_outputs = build_circuit::<Cfg::FieldConfig>(3, 1);
+    assert_eq!(circuit.proof_shape(2), other_coef.proof_shape(2));
+    assert_eq!(circuit.proof_shape(2), other_outputs.proof_shape(2));
+    assert_ne!(circuit.digest(), other_coef.digest());
+    assert_ne!(circuit.digest(), other_outputs.digest());
+
+    for world_size in [1, 2] {
+        let witness = random_witness(&circuit, world_size);
+        let (outputs, proof) = ThreadMPIConfig::run(world_size, |mpi_config| {
+            prove::<Cfg, _>(mpi_config, build_circuit(3, 0), &witness)
+        })
+        .swap_remove(0);
+
+        assert!(verify::<Cfg>(
+            world_size, &circuit, &witness, &outputs, &proof, false
+        ));
+        assert!(!verify::<Cfg>(
+            world_size,
+            &other_coef,
+            &witness,
+            &outputs,
+            &proof,
+            false
+        ));
+        assert!(!verify::<Cfg>(
+            world_size,
+            &other_outputs,
+            &witness,
+            &outputs,
+            &proof,
+            false
+        ));
+    }
+}
+
+#[test]
+fn test_proof_bound_to_circuit() {
+    test_proof_bound_to_circuit_helper::<M31x1ConfigSha2RawVanilla>();
+    test_proof_bound_to_circuit_helper::<M31x16ConfigSha2RawVanilla>();
+    test_proof_bound_to_circuit_helper::<BN254ConfigSha2Raw>();
+}
+
+#[test]
+fn test_domain_separator() {
+    let separators = [
+        M31x1ConfigSha2RawVanilla::domain_separator(),
+        M31x16ConfigSha2RawVanilla::domain_separator(),
+        M31x16ConfigPoseidonRawVanilla::domain_separator(),
+        CompressedSumcheck::<M31x16ConfigSha2RawVanilla>::domain_separator(),
+        BN254ConfigSha2Raw::domain_separator(),
+    ];
+    for (i, a) in separators.iter().enumerate() {
+        for b in &separators[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
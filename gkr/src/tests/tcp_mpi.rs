@@ -0,0 +1,74 @@
+use std::{
+    env,
+    net::TcpListener,
+    process::{Command, Stdio},
+};
+
+use gkr_engine::{
+    GKREngine, TcpMPIConfig, ThreadMPIConfig, TCP_ROOT_ADDR_ENV, TCP_WORLD_RANK_ENV,
+    TCP_WORLD_SIZE_ENV,
+};
+
+use super::{
+    common::{prove, random_witness, verify},
+    thread_mpi::build_circuit,
+};
+use crate::{M31x16ConfigSha2OrionVanilla, M31x16ConfigSha2RawVanilla};
+
+/// `test_name` is the test calling the helper, run by the worker processes
+fn test_tcp_mpi_prover_helper<Cfg: GKREngine>(test_name: &str) {
+    const WORLD_SIZE: usize = 4;
+    let circuit = build_circuit::<Cfg::FieldConfig>();
+    let witness = random_witness(&circuit, WORLD_SIZE);
+
+    // the workers are processes running this same test, with the rank set in the environment
+    if env::var(TCP_WORLD_RANK_ENV).is_ok() {
+        prove::<Cfg, _>(TcpMPIConfig::from_env().unwrap(), circuit, &witness);
+        return;
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root_addr = listener.local_addr().unwrap().to_string();
+    let mut workers = (1..WORLD_SIZE)
+        .map(|world_rank| {
+            Command::new(env::current_exe().unwrap())
+                .args(["--exact", test_name])
+                .env(TCP_ROOT_ADDR_ENV, &root_addr)
+                .env(TCP_WORLD_SIZE_ENV, WORLD_SIZE.to_string())
+                .env(TCP_WORLD_RANK_ENV, world_rank.to_string())
+                .stdout(Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mpi_config = TcpMPIConfig::root(listener, WORLD_SIZE).unwrap();
+    let (outputs, proof) = prove::<Cfg, _>(mpi_config, circuit.clone(), &witness);
+    workers
+        .iter_mut()
+        .for_each(|worker| assert!(worker.wait().unwrap().success()));
+
+    // the same proof as with a world of threads
+    let (_, thread_proof) = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+    })
+    .swap_remove(0);
+    assert_eq!(thread_proof.bytes, proof.bytes);
+    assert!(verify::<Cfg>(
+        WORLD_SIZE, &circuit, &witness, &outputs, &proof, false
+    ));
+}
+
+#[test]
+fn test_tcp_mpi_prover() {
+    test_tcp_mpi_prover_helper::<M31x16ConfigSha2RawVanilla>("tests::tcp_mpi::test_tcp_mpi_prover");
+}
+
+// Orion commits and opens with the collectives of `orion_mpi_commit_simd_field` and
+// `orion_mpi_open_simd_field`
+#[test]
+fn test_tcp_mpi_prover_orion() {
+    test_tcp_mpi_prover_helper::<M31x16ConfigSha2OrionVanilla>(
+        "tests::tcp_mpi::test_tcp_mpi_prover_orion",
+    );
+}
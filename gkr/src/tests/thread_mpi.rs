@@ -0,0 +1,92 @@
+use arith::Field;
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateMul};
+#[cfg(feature = "mpi")]
+use gkr_engine::MPIConfig;
+use gkr_engine::{FieldEngine, GKREngine, ThreadMPIConfig};
+
+use super::common::{prove, random_witness, verify};
+use crate::{M31x16ConfigSha2RawVanilla, M31x1ConfigSha2RawVanilla};
+
+const LOG_INPUT_SIZE: usize = 3;
+
+// layer 0: [in_0 * in_1 + in_2, in_3 * in_4, in_5 + in_6 + in_7, in_0]
+// layer 1: [w_0 * w_1, w_2 * w_3 + w_0]
+pub(super) fn build_circuit<C: FieldEngine>() -> Circuit<C> {
+    let add = |i: usize, o: usize| GateAdd::<C> {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::ONE,
+        gate_type: 0,
+    };
+    let mul = |i0: usize, i1: usize, o: usize| GateMul::<C> {
+        i_ids: [i0, i1],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: C::CircuitField::from(3u32),
+        gate_type: 0,
+    };
+
+    let layer_0 = CircuitLayer::<C> {
+        input_var_num: LOG_INPUT_SIZE,
+        output_var_num: 2,
+        mul: vec![mul(0, 1, 0), mul(3, 4, 1)],
+        add: vec![add(2, 0), add(5, 2), add(6, 2), add(7, 2), add(0, 3)],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<C> {
+        input_var_num: 2,
+        output_var_num: 1,
+        mul: vec![mul(0, 1, 0), mul(2, 3, 1)],
+        add: vec![add(0, 1)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer_0, layer_1],
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+    circuit
+}
+
+fn test_thread_mpi_prover_helper<Cfg: GKREngine>() {
+    let circuit = build_circuit::<Cfg::FieldConfig>();
+
+    // a world of one thread matches the single process prover byte for byte
+    #[cfg(feature = "mpi")]
+    {
+        let witness = random_witness(&circuit, 1);
+        let (outputs, proof) =
+            prove::<Cfg, _>(MPIConfig::prover_new(None, None), circuit.clone(), &witness);
+        let thread_results = ThreadMPIConfig::run(1, |mpi_config| {
+            prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+        });
+        assert_eq!(thread_results[0].0, outputs);
+        assert_eq!(thread_results[0].1.bytes, proof.bytes);
+    }
+
+    // the proof of the root rank verifies against the whole world
+    const WORLD_SIZE: usize = 8;
+    let witness = random_witness(&circuit, WORLD_SIZE);
+    let (outputs, proof) = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+    })
+    .swap_remove(0);
+    assert!(verify::<Cfg>(
+        WORLD_SIZE, &circuit, &witness, &outputs, &proof, false
+    ));
+
+    // and is reproducible
+    let (_, proof_again) = ThreadMPIConfig::run(WORLD_SIZE, |mpi_config| {
+        prove::<Cfg, _>(mpi_config, build_circuit(), &witness)
+    })
+    .swap_remove(0);
+    assert_eq!(proof_again.bytes, proof.bytes);
+}
+
+#[test]
+fn test_thread_mpi_prover() {
+    test_thread_mpi_prover_helper::<M31x1ConfigSha2RawVanilla>();
+    test_thread_mpi_prover_helper::<M31x16ConfigSha2RawVanilla>();
+}
@@ -0,0 +1,122 @@
+use arith::Field;
+use ark_std::test_rng;
+use circuit::{RecursiveCircuit, TextFormat, Witness};
+use gkr_engine::{FieldEngine, GKREngine, M31x1Config, Proof, ThreadMPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+use serdes::ExpSerde;
+
+use crate::{verify_bytes, M31x1ConfigSha2RawVanilla, Prover};
+
+type F = <M31x1Config as FieldEngine>::CircuitField;
+type E = <M31x1Config as FieldEngine>::ChallengeField;
+
+const CIRCUIT_TEXT: &str = "
+expander_circuit
+modulus 2147483647
+num_public_inputs 1
+num_outputs 2
+expected_num_output_zeros 2
+
+# out_0 = 3 * in_0 * in_1 + in_2 + public_0, out_1 = in_3, both constrained to zero
+segment 0 2 1
+mul 0 1 0 3
+add 2 0 1
+const 0 public:0
+add 3 1 1
+
+layers 0
+";
+
+fn to_bytes<T: ExpSerde>(value: &T) -> Vec<u8> {
+    let mut buffer = vec![];
+    value.serialize_into(&mut buffer).unwrap();
+    buffer
+}
+
+#[test]
+fn test_verify_bytes() {
+    let rc = RecursiveCircuit::<M31x1Config>::from_text(CIRCUIT_TEXT).unwrap();
+    let circuit_bytes = to_bytes(&rc);
+    let mut circuit = rc.flatten();
+    circuit.pre_process_gkr();
+
+    let mut rng = test_rng();
+    for world_size in [1, 2] {
+        // in_2 and in_3 zero the outputs
+        let values = (0..world_size)
+            .flat_map(|_| {
+                let [in_0, in_1, public_0] = [(); 3].map(|_| F::random_unsafe(&mut rng));
+                let in_2 = -(F::from(3u32) * in_0 * in_1 + public_0);
+                [in_0, in_1, in_2, F::ZERO, public_0]
+            })
+            .collect();
+        let witness = Witness::<M31x1Config> {
+            num_witnesses: world_size,
+            num_private_inputs_per_witness: 1 << circuit.log_input_size(),
+            num_public_inputs_per_witness: 1,
+            values,
+        };
+
+        let (claimed_v, proof) = ThreadMPIConfig::run(world_size, |mpi_config| {
+            let mut circuit = rc.flatten();
+            circuit.pre_process_gkr();
+            circuit.prover_process_witness(witness.clone(), &mpi_config);
+
+            let mut prover = Prover::<M31x1ConfigSha2RawVanilla, _>::new(mpi_config.clone());
+            prover.prepare_mem(&circuit);
+            let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+                expander_pcs_init_testing_only::<
+                    M31x1Config,
+                    <M31x1ConfigSha2RawVanilla as GKREngine>::PCSConfig,
+                >(circuit.log_input_size(), &mpi_config);
+            prover.prove(
+                &mut circuit,
+                &pcs_params,
+                &pcs_proving_key,
+                &mut pcs_scratch,
+            )
+        })
+        .swap_remove(0);
+
+        // the public inputs of all the processes, as the verifier sees them
+        let mut verifier_circuit = circuit.clone();
+        verifier_circuit.verifier_process_witness(witness, &circuit.proof_shape(world_size));
+        let public_input = verifier_circuit.public_input;
+        assert_eq!(public_input.len(), world_size);
+
+        let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
+            M31x1Config,
+            <M31x1ConfigSha2RawVanilla as GKREngine>::PCSConfig,
+        >(
+            circuit.log_input_size(),
+            &ThreadMPIConfig::new_world(world_size).swap_remove(0),
+        );
+        let verify = |public_input: &[F], claimed_v: E, proof: &Proof| {
+            let mut proof_bytes = to_bytes(proof);
+            claimed_v.serialize_into(&mut proof_bytes).unwrap();
+            verify_bytes::<M31x1ConfigSha2RawVanilla>(
+                &circuit_bytes,
+                &to_bytes(&public_input.to_vec()),
+                &proof_bytes,
+                world_size,
+                &pcs_params,
+                &pcs_verification_key,
+            )
+        };
+
+        assert_eq!(verify(&public_input, claimed_v, &proof), Ok(true));
+        assert_eq!(verify(&public_input, claimed_v + E::ONE, &proof), Ok(false));
+
+        let mut other_public_input = public_input.clone();
+        other_public_input[0] += F::ONE;
+        assert_eq!(verify(&other_public_input, claimed_v, &proof), Ok(false));
+
+        // the truncated proofs, in well-formed containers, are rejected without panicking
+        for len in 0..proof.bytes.len() {
+            let truncated = Proof {
+                bytes: proof.bytes[..len].to_vec(),
+            };
+            assert_eq!(verify(&public_input, claimed_v, &truncated), Ok(false));
+        }
+    }
+}
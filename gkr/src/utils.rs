@@ -1,3 +1,5 @@
+pub mod bn254_fixture;
+
 use std::fs;
 use std::process::Command;
 
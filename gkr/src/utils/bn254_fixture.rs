@@ -0,0 +1,134 @@
+//! A small BN254 circuit with a public input and its proofs, shared by the tests of the
+//! verifiers of BN254 proofs, i.e., the recursive, Groth16 and Solidity verifiers.
+
+use arith::{Field, Fr};
+use circuit::{Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul, Witness};
+use gkr_engine::{BN254Config, GKREngine, Proof, ThreadMPIConfig};
+use poly_commit::expander_pcs_init_testing_only;
+use rand::RngCore;
+
+use crate::{Prover, Verifier};
+
+pub const LOG_INPUT_SIZE: usize = 2;
+
+// layer 0: [3 * in_0 * in_1 + in_2, in_3 + public_0]
+// layer 1: [r * w_0 * w_1, w_1]
+// both outputs are constrained to zero, i.e., in_3 = -public_0
+pub fn build_circuit() -> Circuit<BN254Config> {
+    let add = |i: usize, o: usize| GateAdd {
+        i_ids: [i],
+        o_id: o,
+        coef_type: CoefType::Constant,
+        coef: Fr::ONE,
+        gate_type: 0,
+    };
+    let layer_0 = CircuitLayer::<BN254Config> {
+        input_var_num: LOG_INPUT_SIZE,
+        output_var_num: 1,
+        mul: vec![GateMul {
+            i_ids: [0, 1],
+            o_id: 0,
+            coef_type: CoefType::Constant,
+            coef: Fr::from(3u32),
+            gate_type: 0,
+        }],
+        add: vec![add(2, 0), add(3, 1)],
+        const_: vec![GateConst {
+            i_ids: [],
+            o_id: 1,
+            coef_type: CoefType::PublicInput(0),
+            coef: Fr::ZERO,
+            gate_type: 0,
+        }],
+        ..Default::default()
+    };
+    let layer_1 = CircuitLayer::<BN254Config> {
+        input_var_num: 1,
+        output_var_num: 1,
+        mul: vec![GateMul {
+            i_ids: [0, 1],
+            o_id: 0,
+            coef_type: CoefType::Random,
+            coef: Fr::ZERO,
+            gate_type: 0,
+        }],
+        add: vec![add(1, 1)],
+        ..Default::default()
+    };
+
+    let mut circuit = Circuit {
+        layers: vec![layer_0, layer_1],
+        expected_num_output_zeros: 2,
+        ..Default::default()
+    };
+    circuit.pre_process_gkr();
+    circuit
+}
+
+/// A witness whose outputs are all zero, and its public input
+pub fn random_witness(mut rng: impl RngCore) -> (Witness<BN254Config>, Vec<Fr>) {
+    let public_input = vec![Fr::random_unsafe(&mut rng)];
+    let mut values = (0..1 << LOG_INPUT_SIZE)
+        .map(|_| Fr::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    values[3] = -public_input[0];
+    values.extend_from_slice(&public_input);
+    let witness = Witness {
+        num_witnesses: 1,
+        num_private_inputs_per_witness: 1 << LOG_INPUT_SIZE,
+        num_public_inputs_per_witness: 1,
+        values,
+    };
+    (witness, public_input)
+}
+
+/// Returns the claimed evaluation of the outputs and the proof
+pub fn prove<Cfg: GKREngine<FieldConfig = BN254Config>>(
+    circuit: &Circuit<BN254Config>,
+    witness: &Witness<BN254Config>,
+) -> (Fr, Proof) {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let mut circuit = circuit.clone();
+    circuit.prover_process_witness(witness.clone(), &mpi_config);
+
+    let mut prover = Prover::<Cfg, _>::new(mpi_config.clone());
+    prover.prepare_mem(&circuit);
+    let (pcs_params, pcs_proving_key, _, mut pcs_scratch) =
+        expander_pcs_init_testing_only::<BN254Config, Cfg::PCSConfig>(
+            circuit.log_input_size(),
+            &mpi_config,
+        );
+    prover.prove(
+        &mut circuit,
+        &pcs_params,
+        &pcs_proving_key,
+        &mut pcs_scratch,
+    )
+}
+
+/// Whether `Verifier::verify` accepts the proof, i.e., the outputs are all zero
+pub fn verify<Cfg: GKREngine<FieldConfig = BN254Config>>(
+    circuit: &Circuit<BN254Config>,
+    witness: &Witness<BN254Config>,
+    claimed_v: &Fr,
+    proof: &Proof,
+) -> bool {
+    let mpi_config = ThreadMPIConfig::new_world(1).swap_remove(0);
+    let mut circuit = circuit.clone();
+    let proof_shape = circuit.proof_shape(1);
+    circuit.verifier_process_witness(witness.clone(), &proof_shape);
+
+    let (pcs_params, _, pcs_verification_key, _) = expander_pcs_init_testing_only::<
+        BN254Config,
+        Cfg::PCSConfig,
+    >(circuit.log_input_size(), &mpi_config);
+    let public_input = circuit.public_input.clone();
+    Verifier::<Cfg>::new(proof_shape).verify(
+        &mut circuit,
+        &public_input,
+        claimed_v,
+        &pcs_params,
+        &pcs_verification_key,
+        proof,
+    )
+}
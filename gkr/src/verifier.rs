@@ -1,11 +0,0 @@
-mod structs;
-pub use structs::*;
-
-mod common;
-pub use common::*;
-
-mod gkr_vanilla;
-pub use gkr_vanilla::gkr_verify;
-
-mod snark;
-pub use snark::Verifier;
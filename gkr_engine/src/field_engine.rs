@@ -5,8 +5,11 @@ mod definition;
 mod gf2_ext128;
 mod goldilocks_x1;
 mod goldilocks_x8;
+mod koalabear_x16;
 mod m31_x1;
 mod m31_x16;
+mod m31_x16_ext6;
+mod m31_x1_ext6;
 
 pub use babybear_x16::*;
 pub use bn254::*;
@@ -15,5 +18,8 @@ pub use definition::*;
 pub use gf2_ext128::*;
 pub use goldilocks_x1::*;
 pub use goldilocks_x8::*;
+pub use koalabear_x16::*;
 pub use m31_x1::*;
 pub use m31_x16::*;
+pub use m31_x16_ext6::*;
+pub use m31_x1_ext6::*;
@@ -17,6 +17,9 @@ pub enum FieldType {
     Goldilocksx8, // GoldilocksExt2x8
     Goldilocksx1, // GoldilocksExt2x1
     BabyBearx16,  // BabyBearExt3x16
+    KoalaBearx16, // KoalaBearExt4x16
+    M31x16Ext6,   // M31Ext6x16
+    M31x1Ext6,    // M31Ext6x1
 }
 
 pub trait FieldEngine: Default + Debug + Clone + Send + Sync + PartialEq + 'static {
@@ -0,0 +1,23 @@
+use koalabear::{KoalaBear, KoalaBearExt4, KoalaBearExt4x16, KoalaBearx16};
+
+use super::{FieldEngine, FieldType};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct KoalaBearx16Config;
+
+impl FieldEngine for KoalaBearx16Config {
+    const FIELD_TYPE: FieldType = FieldType::KoalaBearx16;
+
+    const SENTINEL: [u8; 32] = [
+        1, 0, 0, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0,
+    ];
+
+    type CircuitField = KoalaBear;
+
+    type SimdCircuitField = KoalaBearx16;
+
+    type ChallengeField = KoalaBearExt4;
+
+    type Field = KoalaBearExt4x16;
+}
@@ -0,0 +1,24 @@
+use mersenne31::{M31Ext6, M31Ext6x16, M31x16, M31};
+
+use crate::{FieldEngine, FieldType};
+
+/// Same circuits as `M31x16Config`, with challenges in M31Ext6 for a higher soundness
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct M31x16Ext6Config;
+
+impl FieldEngine for M31x16Ext6Config {
+    const FIELD_TYPE: FieldType = FieldType::M31x16Ext6;
+
+    const SENTINEL: [u8; 32] = [
+        255, 255, 255, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0,
+    ];
+
+    type CircuitField = M31;
+
+    type SimdCircuitField = M31x16;
+
+    type ChallengeField = M31Ext6;
+
+    type Field = M31Ext6x16;
+}
@@ -0,0 +1,26 @@
+use mersenne31::{M31Ext6, M31};
+
+use crate::FieldType;
+
+use super::FieldEngine;
+
+/// Same circuits as `M31x1Config`, with challenges in M31Ext6 for a higher soundness
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct M31x1Ext6Config;
+
+impl FieldEngine for M31x1Ext6Config {
+    const FIELD_TYPE: FieldType = FieldType::M31x1Ext6;
+
+    const SENTINEL: [u8; 32] = [
+        255, 255, 255, 127, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0,
+    ];
+
+    type CircuitField = M31;
+
+    type SimdCircuitField = M31;
+
+    type ChallengeField = M31Ext6;
+
+    type Field = M31Ext6;
+}
@@ -1,7 +1,8 @@
 //! This crate defines the generic APIs that are used by GKR sub-kernals
 //!
 //! - Config trait: a trait that defines the following components of a GKR protocol
-//! - MPI Engine: a simple MPI engine that is used to communicate between processes
+//! - MPI Engine: a simple MPI engine that is used to communicate between processes, the OpenMPI
+//!   backend `MPIConfig` is behind the default `mpi` feature
 //! - Field Engine: a simple field engine that is used to perform field operations and its
 //!   extensions
 //! - Transcript trait: a trait that defines the API for transcript
@@ -45,6 +46,8 @@ pub use gkr_hashers::{FiatShamirHashType, FiatShamirHasher};
 ///   the challenge field
 /// * `PCSConfig` - Configuration for polynomial commitment scheme, implementing `PCSForExpanderGKR`
 /// * `Scheme` - Identifier for the GKR scheme, candidates are `GKRScheme::Vanilla`
+/// * `COMPRESSED_SUMCHECK` - Whether the sumcheck round messages omit the evaluation at 1, which
+///   the verifier recovers from the claimed sum of the round. Off by default
 ///
 /// # Usage
 ///
@@ -78,4 +81,24 @@ pub trait GKREngine: Send + Sync {
 
     /// GKR scheme
     const SCHEME: GKRScheme;
+
+    /// Compressed sumcheck round messages, this changes the proof format
+    const COMPRESSED_SUMCHECK: bool = false;
+
+    /// Domain separator of the Fiat-Shamir transcript, which binds a proof to the field, the PCS,
+    /// the hash and the scheme of the configuration
+    fn domain_separator() -> Vec<u8> {
+        let mut separator = format!(
+            "Expander GKR|{:?}|{:?}|{}|{:?}|{:?}|{}",
+            <Self::FieldConfig as FieldEngine>::FIELD_TYPE,
+            <Self::PCSConfig as ExpanderPCS<Self::FieldConfig>>::PCS_TYPE,
+            <Self::PCSConfig as ExpanderPCS<Self::FieldConfig>>::NAME,
+            <Self::TranscriptConfig as Transcript>::HASH_TYPE,
+            Self::SCHEME,
+            Self::COMPRESSED_SUMCHECK,
+        )
+        .into_bytes();
+        separator.extend_from_slice(&<Self::FieldConfig as FieldEngine>::SENTINEL);
+        separator
+    }
 }
@@ -1,10 +1,18 @@
 mod definition;
+#[cfg(feature = "mpi")]
 mod engine;
 mod shared_mem;
+mod tcp_engine;
+mod thread_engine;
 
 pub use definition::*;
+#[cfg(feature = "mpi")]
 pub use engine::*;
-pub use shared_mem::MPISharedMemory;
+pub use shared_mem::{MPISharedMemory, SharedMemHandle};
+pub use tcp_engine::{
+    TcpMPIConfig, TCP_ROOT_ADDR_ENV, TCP_WORLD_RANK_ENV, TCP_WORLD_SIZE_ENV,
+};
+pub use thread_engine::ThreadMPIConfig;
 
 #[cfg(test)]
 mod tests;
@@ -1,8 +1,16 @@
 use arith::Field;
-use mpi::{ffi::ompi_win_t, topology::Process};
 use serdes::ExpSerde;
 
-use super::MPISharedMemory;
+use super::{MPISharedMemory, SharedMemHandle};
+
+#[macro_export]
+macro_rules! root_println {
+    ($config: expr, $($arg:tt)*) => {
+        if $config.is_root() {
+            println!($($arg)*);
+        }
+    };
+}
 
 /// MPI APIs for distributed computing operations
 pub trait MPIEngine {
@@ -129,20 +137,24 @@ pub trait MPIEngine {
         self.world_rank() == Self::ROOT_RANK as usize
     }
 
-    /// Get the root process handle
-    fn root_process(&self) -> Process;
-
     /// Synchronize all processes at this point
     fn barrier(&self);
 
     /// Create a shared memory segment for inter-process communication
-    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, *mut ompi_win_t);
+    ///
+    /// # Returns
+    /// The base address of the segment, and the handle to release it with `free_shared_mem`
+    ///
+    /// # Behavior
+    /// - Collective: the root allocates `n_bytes`, the `n_bytes` of other processes are ignored
+    /// - All processes get the base address of the segment allocated by the root
+    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, SharedMemHandle);
 
     /// Consume the shared memory segment and create a new shared memory object
     fn consume_obj_and_create_shared<T: MPISharedMemory>(
         &self,
         obj: Option<T>,
-    ) -> (T, *mut ompi_win_t) {
+    ) -> (T, SharedMemHandle) {
         assert!(!self.is_root() || obj.is_some());
 
         if self.is_root() {
@@ -161,11 +173,9 @@ pub trait MPIEngine {
     }
 
     /// Discard the control of shared memory segment
-    fn free_shared_mem(&self, window: &mut *mut ompi_win_t) {
-        unsafe {
-            // Reconstruct an MPI_Win handle from the raw pointer and let MPI release it.
-            let mut win_handle = mpi::ffi::MPI_Win(*window);
-            mpi::ffi::MPI_Win_free(&mut win_handle as *mut mpi::ffi::MPI_Win);
-        }
-    }
+    ///
+    /// # Behavior
+    /// - Collective: no process may access the segment afterwards
+    /// - The handle is reset to `SharedMemHandle::NULL`
+    fn free_shared_mem(&self, handle: &mut SharedMemHandle);
 }
@@ -12,16 +12,7 @@ use mpi::{
 };
 use serdes::ExpSerde;
 
-use super::MPIEngine;
-
-#[macro_export]
-macro_rules! root_println {
-    ($config: expr, $($arg:tt)*) => {
-        if $config.is_root() {
-            println!($($arg)*);
-        }
-    };
-}
+use super::{MPIEngine, SharedMemHandle};
 
 #[derive(Clone)]
 pub struct MPIConfig<'a> {
@@ -103,6 +94,9 @@ impl<'a> MPIConfig<'a> {
 
     /// Create a new MPI engine for the verifier with specified world size
     ///
+    /// Only needed to run the PCS setup of a multi-process prover, the verifier itself is
+    /// configured by a `ProofShape`.
+    ///
     /// # Arguments
     /// * `world_size` - The total number of processes in the MPI world
     #[inline]
@@ -114,6 +108,12 @@ impl<'a> MPIConfig<'a> {
             world_rank: 0,
         }
     }
+
+    /// Get the root process handle
+    #[inline(always)]
+    pub fn root_process(&self) -> Process {
+        self.world.unwrap().process_at_rank(Self::ROOT_RANK)
+    }
 }
 
 /// MPI toolkit:
@@ -411,11 +411,6 @@ impl<'a> MPIEngine for MPIConfig<'a> {
         self.world_rank as usize
     }
 
-    #[inline(always)]
-    fn root_process(&self) -> Process {
-        self.world.unwrap().process_at_rank(Self::ROOT_RANK)
-    }
-
     // Barrier is designed for mpi use only
     // There might be some issues if used with multi-threading
     #[inline(always)]
@@ -426,7 +421,7 @@ impl<'a> MPIEngine for MPIConfig<'a> {
     }
 
     #[inline]
-    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, *mut ompi_win_t) {
+    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, SharedMemHandle) {
         let window_size = if self.is_root() { n_bytes } else { 0 };
         let mut baseptr: *mut c_void = std::ptr::null_mut();
 
@@ -459,7 +454,19 @@ impl<'a> MPIEngine for MPIConfig<'a> {
             }
         }
 
-        (baseptr as *mut u8, window_handle.0)
+        (
+            baseptr as *mut u8,
+            SharedMemHandle::from_raw(window_handle.0 as usize),
+        )
+    }
+
+    fn free_shared_mem(&self, handle: &mut SharedMemHandle) {
+        unsafe {
+            // Reconstruct the MPI_Win handle from the raw value and let MPI release it.
+            let mut window_handle = MPI_Win(handle.into_raw() as *mut ompi_win_t);
+            MPI_Win_free(&mut window_handle as *mut MPI_Win);
+        }
+        *handle = SharedMemHandle::NULL;
     }
 }
 
@@ -1,5 +1,32 @@
 use std::ptr::copy_nonoverlapping;
 
+/// An opaque handle to a shared memory segment created by `MPIEngine::create_shared_mem`.
+///
+/// The raw value is only meaningful to the engine that created the segment, e.g. the OpenMPI
+/// engine stores its `MPI_Win` here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SharedMemHandle(usize);
+
+impl SharedMemHandle {
+    /// The handle of no segment, e.g. of a segment that has been freed
+    pub const NULL: Self = Self(0);
+
+    #[inline(always)]
+    pub fn from_raw(raw: usize) -> Self {
+        Self(raw)
+    }
+
+    #[inline(always)]
+    pub fn into_raw(self) -> usize {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn is_null(&self) -> bool {
+        self.0 == 0
+    }
+}
+
 /// Trait for types that can be serialized to and from shared memory
 /// Compare to traditional deserialization, this trait deserializes
 /// 'in place' as much as possible, without allocating new memory.
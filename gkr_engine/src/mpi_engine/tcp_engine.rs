@@ -0,0 +1,562 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Debug,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    slice,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use arith::Field;
+use serdes::ExpSerde;
+
+use super::{
+    thread_engine::{as_u8_slice, as_u8_slice_mut, SharedMemBlock},
+    MPIEngine, MPISharedMemory, SharedMemHandle,
+};
+
+/// Address of the root process, e.g. `10.0.0.1:7000`
+pub const TCP_ROOT_ADDR_ENV: &str = "EXPANDER_TCP_ROOT_ADDR";
+/// Total number of processes
+pub const TCP_WORLD_SIZE_ENV: &str = "EXPANDER_TCP_WORLD_SIZE";
+/// Rank of the current process, the root has rank 0
+pub const TCP_WORLD_RANK_ENV: &str = "EXPANDER_TCP_WORLD_RANK";
+
+/// First word of the handshake, rejects connections that are not from a worker
+const HANDSHAKE_MAGIC: u64 = u64::from_le_bytes(*b"EXPNDTCP");
+
+/// The collectives send the in-memory bytes of the elements, so all processes must agree on
+/// the byte order and the word size. The handshake compares this word across the processes.
+const MEMORY_LAYOUT: u64 = ((cfg!(target_endian = "big") as u64) << 8) | usize::BITS as u64;
+
+fn describe_layout(layout: u64) -> String {
+    let endian = if layout >> 8 == 0 { "little" } else { "big" };
+    format!("{endian} endian with {}-bit words", layout & 0xff)
+}
+
+/// How long a worker keeps retrying to reach a root that is not listening yet
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The connections of one process
+struct TcpWorld {
+    world_size: usize,
+    world_rank: usize,
+    /// On the root, the streams to ranks `1..world_size` in rank order,
+    /// on a worker, the single stream to the root
+    streams: Vec<TcpStream>,
+    /// Segments allocated by this process, keyed by their base address
+    shared_mem: Mutex<HashMap<usize, Vec<SharedMemBlock>>>,
+}
+
+/// An MPI engine over plain TCP sockets, for clusters without an MPI installation.
+///
+/// The processes form a star: every worker connects to the root, and the root relays the
+/// collectives. Messages are length prefixed, so a mismatch in the collectives called by
+/// different processes fails loudly rather than silently mixing up data.
+///
+/// There is no memory shared across hosts: a shared memory segment is a local copy on every
+/// process, and `consume_obj_and_create_shared` copies the contents written by the root to
+/// all processes.
+///
+/// The `try_*` collectives return the IO errors. The `MPIEngine` collectives have no way to
+/// report them, so they panic like an aborted MPI job.
+#[derive(Clone)]
+pub struct TcpMPIConfig {
+    world: Arc<TcpWorld>,
+}
+
+impl Debug for TcpMPIConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TcpMPIConfig")
+            .field("world_size", &self.world.world_size)
+            .field("world_rank", &self.world.world_rank)
+            .finish()
+    }
+}
+
+impl PartialEq for TcpMPIConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.world, &other.world)
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u64(stream: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    stream.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+impl TcpMPIConfig {
+    /// Create the engine of rank `world_rank`: the root listens on `root_addr`,
+    /// the workers connect to it.
+    ///
+    /// Returns once all `world_size` processes are connected.
+    pub fn new(
+        root_addr: impl ToSocketAddrs,
+        world_size: usize,
+        world_rank: usize,
+    ) -> io::Result<Self> {
+        if world_rank == Self::ROOT_RANK as usize {
+            Self::root(TcpListener::bind(root_addr)?, world_size)
+        } else {
+            Self::worker(root_addr, world_size, world_rank)
+        }
+    }
+
+    /// Create the engine from the `EXPANDER_TCP_*` environment variables,
+    /// which a launcher sets for every process of the host list
+    pub fn from_env() -> io::Result<Self> {
+        let var = |name: &str| {
+            env::var(name)
+                .map_err(|_| io::Error::new(io::ErrorKind::NotFound, format!("{name} is not set")))
+        };
+        let parse = |name: &str| {
+            var(name)?
+                .parse::<usize>()
+                .map_err(|e| invalid_data(format!("{name}: {e}")))
+        };
+
+        Self::new(
+            var(TCP_ROOT_ADDR_ENV)?,
+            parse(TCP_WORLD_SIZE_ENV)?,
+            parse(TCP_WORLD_RANK_ENV)?,
+        )
+    }
+
+    /// Create the engine of the root, accepting the connections of all the workers
+    pub fn root(listener: TcpListener, world_size: usize) -> io::Result<Self> {
+        assert!(world_size > 0);
+        let mut streams: Vec<Option<TcpStream>> = (1..world_size).map(|_| None).collect();
+
+        for _ in 1..world_size {
+            let (mut stream, _) = listener.accept()?;
+            stream.set_nodelay(true)?;
+
+            if read_u64(&mut stream)? != HANDSHAKE_MAGIC {
+                return Err(invalid_data("unexpected handshake".to_string()));
+            }
+            let worker_world_size = read_u64(&mut stream)? as usize;
+            let worker_rank = read_u64(&mut stream)? as usize;
+            let worker_layout = read_u64(&mut stream)?;
+            if worker_world_size != world_size {
+                return Err(invalid_data(format!(
+                    "worker expects {worker_world_size} processes, the root {world_size}"
+                )));
+            }
+            if worker_layout != MEMORY_LAYOUT {
+                return Err(invalid_data(format!(
+                    "worker {worker_rank} is {}, the root is {}",
+                    describe_layout(worker_layout),
+                    describe_layout(MEMORY_LAYOUT)
+                )));
+            }
+            match streams.get_mut(worker_rank.wrapping_sub(1)) {
+                Some(slot @ None) => *slot = Some(stream),
+                Some(Some(_)) => return Err(invalid_data(format!("duplicate rank {worker_rank}"))),
+                None => return Err(invalid_data(format!("invalid rank {worker_rank}"))),
+            }
+        }
+
+        // the workers start only once the whole world is connected
+        let streams = streams.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+        for mut stream in &streams {
+            stream.write_all(&HANDSHAKE_MAGIC.to_le_bytes())?;
+        }
+
+        Ok(Self::from_streams(
+            world_size,
+            Self::ROOT_RANK as usize,
+            streams,
+        ))
+    }
+
+    /// Create the engine of a worker, retrying until the root accepts the connection
+    pub fn worker(
+        root_addr: impl ToSocketAddrs,
+        world_size: usize,
+        world_rank: usize,
+    ) -> io::Result<Self> {
+        assert!(world_rank > 0 && world_rank < world_size);
+
+        let deadline = Instant::now() + CONNECT_TIMEOUT;
+        let mut stream = loop {
+            match TcpStream::connect(&root_addr) {
+                Ok(stream) => break stream,
+                Err(_) if Instant::now() < deadline => thread::sleep(CONNECT_RETRY_INTERVAL),
+                Err(e) => return Err(e),
+            }
+        };
+        stream.set_nodelay(true)?;
+
+        let handshake = [
+            HANDSHAKE_MAGIC,
+            world_size as u64,
+            world_rank as u64,
+            MEMORY_LAYOUT,
+        ]
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect::<Vec<_>>();
+        stream.write_all(&handshake)?;
+        if read_u64(&mut stream)? != HANDSHAKE_MAGIC {
+            return Err(invalid_data("unexpected handshake".to_string()));
+        }
+
+        Ok(Self::from_streams(world_size, world_rank, vec![stream]))
+    }
+
+    fn from_streams(world_size: usize, world_rank: usize, streams: Vec<TcpStream>) -> Self {
+        Self {
+            world: Arc::new(TcpWorld {
+                world_size,
+                world_rank,
+                streams,
+                shared_mem: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// The stream from a worker to the root
+    #[inline(always)]
+    fn root_stream(&self) -> &TcpStream {
+        debug_assert!(!self.is_root());
+        &self.world.streams[0]
+    }
+
+    /// The stream from the root to a worker
+    #[inline(always)]
+    fn worker_stream(&self, rank: usize) -> &TcpStream {
+        debug_assert!(self.is_root());
+        &self.world.streams[rank - 1]
+    }
+
+    fn send(mut stream: &TcpStream, bytes: &[u8]) -> io::Result<()> {
+        stream.write_all(&(bytes.len() as u64).to_le_bytes())?;
+        stream.write_all(bytes)
+    }
+
+    fn recv(mut stream: &TcpStream) -> io::Result<Vec<u8>> {
+        let n_bytes = read_u64(&mut stream)?;
+        let mut bytes = vec![0u8; n_bytes as usize];
+        stream.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Receive a message of exactly `buf.len()` bytes
+    fn recv_into(mut stream: &TcpStream, buf: &mut [u8]) -> io::Result<()> {
+        let n_bytes = read_u64(&mut stream)?;
+        if n_bytes as usize != buf.len() {
+            return Err(invalid_data(format!(
+                "mismatched collectives, received {n_bytes} bytes instead of {}",
+                buf.len()
+            )));
+        }
+        stream.read_exact(buf)
+    }
+
+    fn broadcast_u8s(&self, bytes: &mut [u8]) -> io::Result<()> {
+        if self.is_root() {
+            (1..self.world_size()).try_for_each(|rank| Self::send(self.worker_stream(rank), bytes))
+        } else {
+            Self::recv_into(self.root_stream(), bytes)
+        }
+    }
+
+    /// Unwrap the result of a collective called through `MPIEngine`
+    fn expect_io<T>(&self, result: io::Result<T>) -> T {
+        result.unwrap_or_else(|e| panic!("TcpMPIConfig rank {}: {e}", self.world_rank()))
+    }
+
+    /// `MPIEngine::gather_vec`, returning the IO errors
+    pub fn try_gather_vec<F: Sized + Clone>(
+        &self,
+        local_vec: &[F],
+        global_vec: &mut Vec<F>,
+    ) -> io::Result<()> {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return Ok(());
+        }
+
+        if !self.is_root() {
+            return Self::send(self.root_stream(), as_u8_slice(local_vec));
+        }
+
+        assert_eq!(global_vec.len(), local_vec.len() * self.world_size());
+        let local_n_bytes = size_of_val(local_vec);
+        let global_u8s = as_u8_slice_mut(global_vec);
+        global_u8s[..local_n_bytes].copy_from_slice(as_u8_slice(local_vec));
+        (1..self.world_size()).try_for_each(|rank| {
+            Self::recv_into(
+                self.worker_stream(rank),
+                &mut global_u8s[rank * local_n_bytes..(rank + 1) * local_n_bytes],
+            )
+        })
+    }
+
+    /// `MPIEngine::scatter_vec`, returning the IO errors
+    pub fn try_scatter_vec<F: Sized + Clone>(
+        &self,
+        send_vec: &[F],
+        recv_vec: &mut [F],
+    ) -> io::Result<()> {
+        if self.is_single_process() {
+            recv_vec.clone_from_slice(send_vec);
+            return Ok(());
+        }
+
+        let recv_u8s = as_u8_slice_mut(recv_vec);
+        if !self.is_root() {
+            return Self::recv_into(self.root_stream(), recv_u8s);
+        }
+
+        let n_bytes = recv_u8s.len();
+        let send_u8s = as_u8_slice(send_vec);
+        recv_u8s.copy_from_slice(&send_u8s[..n_bytes]);
+        (1..self.world_size()).try_for_each(|rank| {
+            Self::send(
+                self.worker_stream(rank),
+                &send_u8s[rank * n_bytes..(rank + 1) * n_bytes],
+            )
+        })
+    }
+
+    /// `MPIEngine::root_broadcast_f`, returning the IO errors
+    pub fn try_root_broadcast_f<F: Copy>(&self, f: &mut F) -> io::Result<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        self.broadcast_u8s(as_u8_slice_mut(slice::from_mut(f)))
+    }
+
+    /// `MPIEngine::root_broadcast_bytes`, returning the IO errors
+    pub fn try_root_broadcast_bytes(&self, bytes: &mut Vec<u8>) -> io::Result<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        if self.is_root() {
+            (1..self.world_size()).try_for_each(|rank| Self::send(self.worker_stream(rank), bytes))
+        } else {
+            *bytes = Self::recv(self.root_stream())?;
+            Ok(())
+        }
+    }
+
+    /// `MPIEngine::all_to_all_transpose`, returning the IO errors.
+    /// The root gathers all the rows, and sends every worker its column.
+    pub fn try_all_to_all_transpose<F: Sized>(&self, row: &mut [F]) -> io::Result<()> {
+        assert_eq!(row.len() % self.world_size(), 0);
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        let row_u8s = as_u8_slice_mut(row);
+        if !self.is_root() {
+            Self::send(self.root_stream(), row_u8s)?;
+            return Self::recv_into(self.root_stream(), row_u8s);
+        }
+
+        let n_bytes_per_rank = row_u8s.len() / self.world_size();
+        let mut rows = vec![row_u8s.to_vec()];
+        for rank in 1..self.world_size() {
+            rows.push(Self::recv(self.worker_stream(rank))?);
+        }
+
+        let column = |rank: usize| {
+            rows.iter()
+                .flat_map(|row| &row[rank * n_bytes_per_rank..(rank + 1) * n_bytes_per_rank])
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        (1..self.world_size())
+            .try_for_each(|rank| Self::send(self.worker_stream(rank), &column(rank)))?;
+        row_u8s.copy_from_slice(&column(Self::ROOT_RANK as usize));
+        Ok(())
+    }
+
+    /// `MPIEngine::gather_varlen_vec`, returning the IO errors
+    pub fn try_gather_varlen_vec<F: ExpSerde>(
+        &self,
+        elems: &Vec<F>,
+        global_elems: &mut Vec<Vec<F>>,
+    ) -> io::Result<()> {
+        let mut elems_bytes: Vec<u8> = Vec::new();
+        elems
+            .serialize_into(&mut elems_bytes)
+            .map_err(|e| invalid_data(e.to_string()))?;
+
+        if !self.is_root() {
+            return Self::send(self.root_stream(), &elems_bytes);
+        }
+
+        let mut all_elems_bytes = vec![elems_bytes];
+        for rank in 1..self.world_size() {
+            all_elems_bytes.push(Self::recv(self.worker_stream(rank))?);
+        }
+        *global_elems = all_elems_bytes
+            .iter()
+            .map(|bytes| Vec::deserialize_from(&bytes[..]).map_err(|e| invalid_data(e.to_string())))
+            .collect::<io::Result<_>>()?;
+        Ok(())
+    }
+
+    /// `MPIEngine::barrier`, returning the IO errors
+    pub fn try_barrier(&self) -> io::Result<()> {
+        if self.is_single_process() {
+            return Ok(());
+        }
+
+        if self.is_root() {
+            (1..self.world_size())
+                .try_for_each(|rank| Self::recv_into(self.worker_stream(rank), &mut []))?;
+            (1..self.world_size()).try_for_each(|rank| Self::send(self.worker_stream(rank), &[]))
+        } else {
+            Self::send(self.root_stream(), &[])?;
+            Self::recv_into(self.root_stream(), &mut [])
+        }
+    }
+}
+
+impl MPIEngine for TcpMPIConfig {
+    const ROOT_RANK: i32 = 0;
+
+    fn gather_vec<F: Sized + Clone>(&self, local_vec: &[F], global_vec: &mut Vec<F>) {
+        self.expect_io(self.try_gather_vec(local_vec, global_vec))
+    }
+
+    fn scatter_vec<F: Sized + Clone>(&self, send_vec: &[F], recv_vec: &mut [F]) {
+        self.expect_io(self.try_scatter_vec(send_vec, recv_vec))
+    }
+
+    fn root_broadcast_f<F: Copy>(&self, f: &mut F) {
+        self.expect_io(self.try_root_broadcast_f(f))
+    }
+
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) {
+        self.expect_io(self.try_root_broadcast_bytes(bytes))
+    }
+
+    fn sum_vec<F: Field>(&self, local_vec: &[F]) -> Vec<F> {
+        let coef = vec![F::ONE; self.world_size()];
+        let sum = self.coef_combine_vec(local_vec, &coef);
+        if self.is_root() {
+            sum
+        } else {
+            vec![]
+        }
+    }
+
+    fn coef_combine_vec<F: Field>(&self, local_vec: &[F], coef: &[F]) -> Vec<F> {
+        if self.is_single_process() {
+            // same as the OpenMPI engine, coef[0] is always one for a single process
+            return local_vec.to_vec();
+        }
+
+        let mut global_vec = if self.is_root() {
+            vec![F::ZERO; local_vec.len() * self.world_size()]
+        } else {
+            vec![]
+        };
+        self.gather_vec(local_vec, &mut global_vec);
+
+        let mut ret = vec![F::ZERO; local_vec.len()];
+        if self.is_root() {
+            global_vec
+                .chunks(local_vec.len().max(1))
+                .zip(coef)
+                .for_each(|(rank_vec, c)| {
+                    ret.iter_mut()
+                        .zip(rank_vec)
+                        .for_each(|(r, v)| *r += *v * *c)
+                });
+        }
+        ret
+    }
+
+    fn all_to_all_transpose<F: Sized>(&self, row: &mut [F]) {
+        self.expect_io(self.try_all_to_all_transpose(row))
+    }
+
+    fn gather_varlen_vec<F: ExpSerde>(&self, elems: &Vec<F>, global_elems: &mut Vec<Vec<F>>) {
+        self.expect_io(self.try_gather_varlen_vec(elems, global_elems))
+    }
+
+    #[inline(always)]
+    fn is_single_process(&self) -> bool {
+        self.world.world_size == 1
+    }
+
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world.world_rank
+    }
+
+    fn barrier(&self) {
+        self.expect_io(self.try_barrier())
+    }
+
+    /// Every process allocates a local segment of the size requested by the root,
+    /// the base address doubles as the handle
+    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, SharedMemHandle) {
+        let mut n_bytes = n_bytes as u64;
+        self.root_broadcast_f(&mut n_bytes);
+
+        let mut blocks = vec![SharedMemBlock([0u8; 64]); (n_bytes as usize).div_ceil(64).max(1)];
+        let base_addr = blocks.as_mut_ptr() as usize;
+        self.world
+            .shared_mem
+            .lock()
+            .unwrap()
+            .insert(base_addr, blocks);
+        (base_addr as *mut u8, SharedMemHandle::from_raw(base_addr))
+    }
+
+    /// The root writes the object to its segment, and broadcasts the segment to the local
+    /// segments of the workers. All segments are 64-byte aligned, so the layout written by the
+    /// root is valid on every process.
+    fn consume_obj_and_create_shared<T: MPISharedMemory>(
+        &self,
+        obj: Option<T>,
+    ) -> (T, SharedMemHandle) {
+        assert!(!self.is_root() || obj.is_some());
+
+        let n_bytes = obj.as_ref().map_or(0, |obj| obj.bytes_size());
+        let (mut ptr, handle) = self.create_shared_mem(n_bytes);
+        if let Some(obj) = obj {
+            let mut ptr_copy = ptr;
+            obj.to_memory(&mut ptr_copy);
+        }
+
+        if !self.is_single_process() {
+            let mut shared_mem = self.world.shared_mem.lock().unwrap();
+            let blocks = shared_mem.get_mut(&handle.into_raw()).unwrap();
+            self.expect_io(self.broadcast_u8s(as_u8_slice_mut(blocks)));
+        }
+        (T::new_from_memory(&mut ptr), handle)
+    }
+
+    /// The segments are local, no synchronization is needed
+    fn free_shared_mem(&self, handle: &mut SharedMemHandle) {
+        self.world
+            .shared_mem
+            .lock()
+            .unwrap()
+            .remove(&handle.into_raw());
+        *handle = SharedMemHandle::NULL;
+    }
+}
@@ -4,9 +4,22 @@ use gf2::{GF2x128, GF2x64, GF2x8};
 use itertools::izip;
 use mersenne31::{M31Ext3, M31x16, M31};
 
-use crate::{MPIConfig, MPIEngine};
-
-fn test_gather_vec_helper(mpi_config: &MPIConfig) {
+use std::{
+    env,
+    io::{ErrorKind, Write},
+    net::{TcpListener, TcpStream},
+    process::{Command, Stdio},
+    thread,
+};
+
+#[cfg(feature = "mpi")]
+use crate::MPIConfig;
+use crate::{
+    MPIEngine, MPISharedMemory, TcpMPIConfig, ThreadMPIConfig, TCP_ROOT_ADDR_ENV,
+    TCP_WORLD_RANK_ENV, TCP_WORLD_SIZE_ENV,
+};
+
+fn test_gather_vec_helper(mpi_config: &impl MPIEngine) {
     const TEST_SIZE: usize = (1 << 10) + 1;
 
     let mut local_vec = vec![M31::ZERO; TEST_SIZE];
@@ -28,7 +41,7 @@ fn test_gather_vec_helper(mpi_config: &MPIConfig) {
     }
 }
 
-fn test_varlen_gather_vec_helper(mpi_config: &MPIConfig) {
+fn test_varlen_gather_vec_helper(mpi_config: &impl MPIEngine) {
     let msg: Vec<_> = (0..=mpi_config.world_rank()).collect();
     let mut global_elems: Vec<Vec<usize>> = Vec::new();
 
@@ -43,18 +56,16 @@ fn test_varlen_gather_vec_helper(mpi_config: &MPIConfig) {
     });
 }
 
-fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &MPIConfig) {
-    const TEST_MATRIX_LEN: usize = 1 << 23;
-
+fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &impl MPIEngine, matrix_len: usize) {
     dbg!(F::NAME);
     dbg!(F::SIZE);
 
     let mut rng = test_rng();
-    let global_matrix: Vec<_> = (0..TEST_MATRIX_LEN)
+    let global_matrix: Vec<_> = (0..matrix_len)
         .map(|_| F::random_unsafe(&mut rng))
         .collect();
 
-    let local_length = TEST_MATRIX_LEN / mpi_config.world_size();
+    let local_length = matrix_len / mpi_config.world_size();
 
     let local_share_starts = local_length * mpi_config.world_rank();
     let mut local_shares =
@@ -80,8 +91,21 @@ fn test_all_to_all_transpose_helper<F: Field>(mpi_config: &MPIConfig) {
     });
 }
 
-fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
-    const TEST_SIZE: usize = MPIConfig::CHUNK_SIZE + 1;
+fn test_shared_mem_helper(mpi_config: &impl MPIEngine) {
+    let data = (0..1000usize).collect::<Vec<_>>();
+    let obj = mpi_config.is_root().then(|| (data.len(), data.clone()));
+
+    let (shared, mut handle) = mpi_config.consume_obj_and_create_shared(obj);
+    assert_eq!(shared, (data.len(), data));
+
+    shared.discard_control_of_shared_mem();
+    mpi_config.free_shared_mem(&mut handle);
+    assert!(handle.is_null());
+}
+
+fn test_scatter_vec_helper(mpi_config: &impl MPIEngine) {
+    // one more than the chunk size of the OpenMPI engine
+    const TEST_SIZE: usize = (1 << 20) + 1;
 
     let send_vec: Vec<_> = if mpi_config.is_root() {
         let mut buf = vec![0u8; TEST_SIZE * mpi_config.world_size()];
@@ -108,6 +132,7 @@ fn test_scatter_vec_helper(mpi_config: &MPIConfig) {
     assert!(expected);
 }
 
+#[cfg(feature = "mpi")]
 #[test]
 fn test_mpi_engine() {
     let universe = MPIConfig::init().unwrap();
@@ -116,15 +141,109 @@ fn test_mpi_engine() {
 
     test_gather_vec_helper(&mpi_config);
 
-    test_all_to_all_transpose_helper::<GF2x128>(&mpi_config);
-    test_all_to_all_transpose_helper::<GF2x64>(&mpi_config);
-    test_all_to_all_transpose_helper::<GF2x8>(&mpi_config);
+    test_all_to_all_transpose_helper::<GF2x128>(&mpi_config, 1 << 23);
+    test_all_to_all_transpose_helper::<GF2x64>(&mpi_config, 1 << 23);
+    test_all_to_all_transpose_helper::<GF2x8>(&mpi_config, 1 << 23);
 
-    test_all_to_all_transpose_helper::<M31x16>(&mpi_config);
-    test_all_to_all_transpose_helper::<M31>(&mpi_config);
-    test_all_to_all_transpose_helper::<M31Ext3>(&mpi_config);
+    test_all_to_all_transpose_helper::<M31x16>(&mpi_config, 1 << 23);
+    test_all_to_all_transpose_helper::<M31>(&mpi_config, 1 << 23);
+    test_all_to_all_transpose_helper::<M31Ext3>(&mpi_config, 1 << 23);
 
     test_varlen_gather_vec_helper(&mpi_config);
 
     test_scatter_vec_helper(&mpi_config);
 }
+
+fn test_small_world_engine_helper(mpi_config: &impl MPIEngine) {
+    test_gather_vec_helper(mpi_config);
+
+    test_all_to_all_transpose_helper::<GF2x128>(mpi_config, 1 << 12);
+    test_all_to_all_transpose_helper::<M31x16>(mpi_config, 1 << 12);
+    test_all_to_all_transpose_helper::<M31Ext3>(mpi_config, 1 << 12);
+
+    test_varlen_gather_vec_helper(mpi_config);
+
+    test_scatter_vec_helper(mpi_config);
+
+    test_shared_mem_helper(mpi_config);
+
+    let mut f = M31::from(mpi_config.world_rank() as u32 + 7);
+    mpi_config.root_broadcast_f(&mut f);
+    assert_eq!(f, M31::from(7u32));
+
+    let mut bytes = vec![mpi_config.world_rank() as u8; mpi_config.world_rank() + 1];
+    mpi_config.root_broadcast_bytes(&mut bytes);
+    assert_eq!(bytes, vec![0u8]);
+
+    let sum = mpi_config.sum_vec(&[M31::from(mpi_config.world_rank() as u32)]);
+    if mpi_config.is_root() {
+        let expected = (0..mpi_config.world_size() as u32).sum::<u32>();
+        assert_eq!(sum, vec![M31::from(expected)]);
+    }
+
+    mpi_config.barrier();
+}
+
+#[test]
+fn test_thread_mpi_engine() {
+    for world_size in [1, 2, 8] {
+        ThreadMPIConfig::run(world_size, |mpi_config| {
+            test_small_world_engine_helper(&mpi_config)
+        });
+    }
+}
+
+#[test]
+fn test_tcp_mpi_engine() {
+    // the workers are processes running this same test, with the rank set in the environment
+    if env::var(TCP_WORLD_RANK_ENV).is_ok() {
+        let mpi_config = TcpMPIConfig::from_env().unwrap();
+        test_small_world_engine_helper(&mpi_config);
+        return;
+    }
+
+    const WORLD_SIZE: usize = 4;
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root_addr = listener.local_addr().unwrap().to_string();
+    let mut workers = (1..WORLD_SIZE)
+        .map(|world_rank| {
+            Command::new(env::current_exe().unwrap())
+                .args(["--exact", "mpi_engine::tests::test_tcp_mpi_engine"])
+                .env(TCP_ROOT_ADDR_ENV, &root_addr)
+                .env(TCP_WORLD_SIZE_ENV, WORLD_SIZE.to_string())
+                .env(TCP_WORLD_RANK_ENV, world_rank.to_string())
+                .stdout(Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mpi_config = TcpMPIConfig::root(listener, WORLD_SIZE).unwrap();
+    test_small_world_engine_helper(&mpi_config);
+
+    workers
+        .iter_mut()
+        .for_each(|worker| assert!(worker.wait().unwrap().success()));
+}
+
+#[test]
+fn test_tcp_mpi_layout_mismatch() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root_addr = listener.local_addr().unwrap();
+
+    // a worker with the other byte order
+    let foreign_layout = ((cfg!(target_endian = "little") as u64) << 8) | usize::BITS as u64;
+    let worker = thread::spawn(move || {
+        let mut stream = TcpStream::connect(root_addr).unwrap();
+        let handshake = [u64::from_le_bytes(*b"EXPNDTCP"), 2, 1, foreign_layout]
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>();
+        stream.write_all(&handshake).unwrap();
+    });
+
+    let err = TcpMPIConfig::root(listener, 2).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("endian"));
+    worker.join().unwrap();
+}
@@ -0,0 +1,320 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    slice,
+    sync::{Arc, Barrier, Mutex},
+    thread,
+};
+
+use arith::Field;
+use serdes::ExpSerde;
+
+use super::{MPIEngine, SharedMemHandle};
+
+/// Alignment unit of the shared memory segments, large enough for the avx512 types
+#[derive(Clone, Copy)]
+#[repr(C, align(64))]
+pub(super) struct SharedMemBlock(pub(super) [u8; 64]);
+
+/// The state shared by all ranks of a thread world
+struct ThreadWorld {
+    world_size: usize,
+    barrier: Barrier,
+    /// One message slot per rank, written by the rank and read by every rank in a collective
+    slots: Mutex<Vec<Vec<u8>>>,
+    /// Shared memory segments allocated by the root, keyed by their base address
+    shared_mem: Mutex<HashMap<usize, Vec<SharedMemBlock>>>,
+}
+
+/// An MPI engine simulating the ranks of an MPI world as threads of the current process.
+///
+/// Every collective is a rendezvous: each rank writes its message to its own slot,
+/// waits for all the other ranks, and reads the slots it needs. Like with MPI, all ranks
+/// must call the same collectives in the same order, and a panic on one rank leaves
+/// the other ranks blocked in their next collective.
+#[derive(Clone)]
+pub struct ThreadMPIConfig {
+    world: Arc<ThreadWorld>,
+    world_rank: usize,
+}
+
+impl Debug for ThreadMPIConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadMPIConfig")
+            .field("world_size", &self.world.world_size)
+            .field("world_rank", &self.world_rank)
+            .finish()
+    }
+}
+
+impl PartialEq for ThreadMPIConfig {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.world, &other.world) && self.world_rank == other.world_rank
+    }
+}
+
+impl ThreadMPIConfig {
+    /// Create the engines of all ranks of a world of `world_size` threads, ordered by rank
+    pub fn new_world(world_size: usize) -> Vec<Self> {
+        assert!(world_size > 0);
+        let world = Arc::new(ThreadWorld {
+            world_size,
+            barrier: Barrier::new(world_size),
+            slots: Mutex::new(vec![vec![]; world_size]),
+            shared_mem: Mutex::new(HashMap::new()),
+        });
+
+        (0..world_size)
+            .map(|world_rank| Self {
+                world: world.clone(),
+                world_rank,
+            })
+            .collect()
+    }
+
+    /// Run `f` on `world_size` threads, one per rank, and return the results ordered by rank
+    pub fn run<T: Send>(world_size: usize, f: impl Fn(Self) -> T + Sync) -> Vec<T> {
+        let f = &f;
+        thread::scope(|s| {
+            let handles = Self::new_world(world_size)
+                .into_iter()
+                .map(|config| s.spawn(move || f(config)))
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("a rank panicked"))
+                .collect()
+        })
+    }
+
+    /// Publish `message` to all ranks, and read the messages of all ranks once every rank
+    /// has published its own
+    fn exchange<T>(&self, message: Vec<u8>, read: impl FnOnce(&[Vec<u8>]) -> T) -> T {
+        self.world.slots.lock().unwrap()[self.world_rank] = message;
+        self.world.barrier.wait();
+
+        let ret = read(&self.world.slots.lock().unwrap());
+
+        // no rank may overwrite its slot before every rank is done reading
+        self.world.barrier.wait();
+        self.world.slots.lock().unwrap()[self.world_rank].clear();
+        ret
+    }
+}
+
+#[inline]
+pub(super) fn as_u8_slice<F: Sized>(v: &[F]) -> &[u8] {
+    unsafe { slice::from_raw_parts(v.as_ptr() as *const u8, size_of_val(v)) }
+}
+
+#[inline]
+pub(super) fn as_u8_slice_mut<F: Sized>(v: &mut [F]) -> &mut [u8] {
+    unsafe { slice::from_raw_parts_mut(v.as_mut_ptr() as *mut u8, size_of_val(v)) }
+}
+
+impl MPIEngine for ThreadMPIConfig {
+    const ROOT_RANK: i32 = 0;
+
+    fn gather_vec<F: Sized + Clone>(&self, local_vec: &[F], global_vec: &mut Vec<F>) {
+        if self.is_single_process() {
+            *global_vec = local_vec.to_vec();
+            return;
+        }
+
+        assert!(!self.is_root() || global_vec.len() == local_vec.len() * self.world_size());
+        self.exchange(as_u8_slice(local_vec).to_vec(), |slots| {
+            if self.is_root() {
+                let global_u8s = as_u8_slice_mut(global_vec);
+                let local_n_bytes = size_of_val(local_vec);
+                slots.iter().enumerate().for_each(|(i, slot)| {
+                    global_u8s[i * local_n_bytes..(i + 1) * local_n_bytes].copy_from_slice(slot)
+                });
+            }
+        });
+    }
+
+    fn scatter_vec<F: Sized + Clone>(&self, send_vec: &[F], recv_vec: &mut [F]) {
+        if self.is_single_process() {
+            recv_vec.clone_from_slice(send_vec);
+            return;
+        }
+
+        let message = if self.is_root() {
+            as_u8_slice(send_vec).to_vec()
+        } else {
+            vec![]
+        };
+        self.exchange(message, |slots| {
+            let recv_u8s = as_u8_slice_mut(recv_vec);
+            let n_bytes = recv_u8s.len();
+            recv_u8s.copy_from_slice(
+                &slots[Self::ROOT_RANK as usize]
+                    [self.world_rank * n_bytes..(self.world_rank + 1) * n_bytes],
+            );
+        });
+    }
+
+    fn root_broadcast_f<F: Copy>(&self, f: &mut F) {
+        if self.is_single_process() {
+            return;
+        }
+
+        let f_u8s = as_u8_slice_mut(slice::from_mut(f));
+        let message = if self.is_root() {
+            f_u8s.to_vec()
+        } else {
+            vec![]
+        };
+        self.exchange(message, |slots| {
+            if !self.is_root() {
+                f_u8s.copy_from_slice(&slots[Self::ROOT_RANK as usize]);
+            }
+        });
+    }
+
+    fn root_broadcast_bytes(&self, bytes: &mut Vec<u8>) {
+        if self.is_single_process() {
+            return;
+        }
+
+        let message = if self.is_root() {
+            bytes.clone()
+        } else {
+            vec![]
+        };
+        self.exchange(message, |slots| {
+            if !self.is_root() {
+                *bytes = slots[Self::ROOT_RANK as usize].clone();
+            }
+        });
+    }
+
+    fn sum_vec<F: Field>(&self, local_vec: &[F]) -> Vec<F> {
+        let coef = vec![F::ONE; self.world_size()];
+        let sum = self.coef_combine_vec(local_vec, &coef);
+        if self.is_root() {
+            sum
+        } else {
+            vec![]
+        }
+    }
+
+    fn coef_combine_vec<F: Field>(&self, local_vec: &[F], coef: &[F]) -> Vec<F> {
+        if self.is_single_process() {
+            // same as the OpenMPI engine, coef[0] is always one for a single process
+            return local_vec.to_vec();
+        }
+
+        let mut global_vec = if self.is_root() {
+            vec![F::ZERO; local_vec.len() * self.world_size()]
+        } else {
+            vec![]
+        };
+        self.gather_vec(local_vec, &mut global_vec);
+
+        let mut ret = vec![F::ZERO; local_vec.len()];
+        if self.is_root() {
+            global_vec
+                .chunks(local_vec.len().max(1))
+                .zip(coef)
+                .for_each(|(rank_vec, c)| {
+                    ret.iter_mut()
+                        .zip(rank_vec)
+                        .for_each(|(r, v)| *r += *v * *c)
+                });
+        }
+        ret
+    }
+
+    fn all_to_all_transpose<F: Sized>(&self, row: &mut [F]) {
+        assert_eq!(row.len() % self.world_size(), 0);
+        if self.is_single_process() {
+            return;
+        }
+
+        let row_u8s = as_u8_slice_mut(row);
+        let n_bytes_per_rank = row_u8s.len() / self.world_size();
+        let message = row_u8s.to_vec();
+        self.exchange(message, |slots| {
+            row_u8s
+                .chunks_mut(n_bytes_per_rank)
+                .zip(slots)
+                .for_each(|(chunk, slot)| {
+                    chunk.copy_from_slice(
+                        &slot[self.world_rank * n_bytes_per_rank
+                            ..(self.world_rank + 1) * n_bytes_per_rank],
+                    )
+                });
+        });
+    }
+
+    fn gather_varlen_vec<F: ExpSerde>(&self, elems: &Vec<F>, global_elems: &mut Vec<Vec<F>>) {
+        let mut elems_bytes: Vec<u8> = Vec::new();
+        elems.serialize_into(&mut elems_bytes).unwrap();
+
+        self.exchange(elems_bytes, |slots| {
+            if self.is_root() {
+                *global_elems = slots
+                    .iter()
+                    .map(|slot| Vec::deserialize_from(&slot[..]).unwrap())
+                    .collect();
+            }
+        });
+    }
+
+    #[inline(always)]
+    fn is_single_process(&self) -> bool {
+        self.world.world_size == 1
+    }
+
+    #[inline(always)]
+    fn world_size(&self) -> usize {
+        self.world.world_size
+    }
+
+    #[inline(always)]
+    fn world_rank(&self) -> usize {
+        self.world_rank
+    }
+
+    #[inline(always)]
+    fn barrier(&self) {
+        self.world.barrier.wait();
+    }
+
+    /// The root allocates the segment, the base address doubles as the handle
+    fn create_shared_mem(&self, n_bytes: usize) -> (*mut u8, SharedMemHandle) {
+        let message = if self.is_root() {
+            let mut blocks = vec![SharedMemBlock([0u8; 64]); n_bytes.div_ceil(64).max(1)];
+            let base_addr = blocks.as_mut_ptr() as usize;
+            self.world
+                .shared_mem
+                .lock()
+                .unwrap()
+                .insert(base_addr, blocks);
+            base_addr.to_le_bytes().to_vec()
+        } else {
+            vec![]
+        };
+
+        let base_addr = self.exchange(message, |slots| {
+            usize::from_le_bytes(slots[Self::ROOT_RANK as usize][..].try_into().unwrap())
+        });
+        (base_addr as *mut u8, SharedMemHandle::from_raw(base_addr))
+    }
+
+    fn free_shared_mem(&self, handle: &mut SharedMemHandle) {
+        // like MPI_Win_free, this is collective: no rank may use the segment afterwards
+        self.barrier();
+        if self.is_root() {
+            self.world
+                .shared_mem
+                .lock()
+                .unwrap()
+                .remove(&handle.into_raw());
+        }
+        self.barrier();
+        *handle = SharedMemHandle::NULL;
+    }
+}
@@ -1,12 +1,18 @@
+#[cfg(feature = "prover")]
 use polynomials::MultilinearExtension;
+#[cfg(feature = "prover")]
 use rand::RngCore;
 use serdes::ExpSerde;
+#[cfg(feature = "prover")]
 use std::io::{BufReader, BufWriter};
 use std::{fmt::Debug, str::FromStr};
 
-use crate::{ExpErrors, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, Transcript};
+#[cfg(feature = "prover")]
+use crate::MPIEngine;
+use crate::{ExpErrors, ExpanderSingleVarChallenge, FieldEngine, ProofShape, Transcript};
 
 /// Buffer capacity for SRS file I/O (64 MB).
+#[cfg(feature = "prover")]
 const SRS_IO_BUFFER_CAPACITY: usize = 64 * 1024 * 1024;
 
 pub trait StructuredReferenceString {
@@ -58,6 +64,7 @@ pub trait ExpanderPCS<F: FieldEngine> {
     ///
     /// NOTE(HS) the calibrated number of variables refers to the local SIMD variables
     /// rather than the base field elements.
+    #[cfg(feature = "prover")]
     fn gen_or_load_srs_for_testing(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -76,12 +83,21 @@ pub trait ExpanderPCS<F: FieldEngine> {
                     }
                     Err(_e) => {
                         // file does not exist; generate SRS and store to file
+                        // written aside and renamed, so that concurrent tests never read a
+                        // partially written SRS
                         let srs = Self::gen_srs(params, mpi_engine, rng);
+                        let tmp_path = format!(
+                            "{path}.{}.{:?}.tmp",
+                            std::process::id(),
+                            std::thread::current().id()
+                        );
                         let file =
-                            std::fs::File::create(path).expect("Failed to create SRS file");
+                            std::fs::File::create(&tmp_path).expect("Failed to create SRS file");
                         let mut writer = BufWriter::with_capacity(SRS_IO_BUFFER_CAPACITY, file);
                         srs.serialize_into(&mut writer)
                             .expect("Failed to serialize SRS to file");
+                        writer.into_inner().expect("Failed to write SRS file");
+                        std::fs::rename(&tmp_path, path).expect("Failed to rename SRS file");
                         srs
                     }
                 }
@@ -95,6 +111,7 @@ pub trait ExpanderPCS<F: FieldEngine> {
     }
 
     /// The actual function to generate the SRS.
+    #[cfg(feature = "prover")]
     fn gen_srs(params: &Self::Params, mpi_engine: &impl MPIEngine, rng: impl RngCore) -> Self::SRS;
 
     /// n_input_vars is with respect to the multilinear poly on each machine in MPI,
@@ -103,10 +120,12 @@ pub trait ExpanderPCS<F: FieldEngine> {
 
     /// Initialize the scratch pad.
     /// Each process returns its own scratch pad.
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(params: &Self::Params, mpi_engine: &impl MPIEngine) -> Self::ScratchPad;
 
     /// Commit to a polynomial. Root process returns the commitment, other processes can return
     /// arbitrary value.
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -136,6 +155,7 @@ pub trait ExpanderPCS<F: FieldEngine> {
     /// the opening algorithm.  In such case, only the PCS opening at the root matters,
     /// while opening from the subordinate parties are not used, at a scope of whole GKR
     /// argument system.
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -152,8 +172,12 @@ pub trait ExpanderPCS<F: FieldEngine> {
     /// NOTE(HS): Again, corresponding to the comments in opening, the PCS opening reaching
     /// this verify algorithm should be the one at the MPI root, rather than the ones from
     /// any other subordinate MPI parties.
+    ///
+    /// `proof_shape` stands in for the MPI engine of `open`, and describes the proving run.
+    #[allow(clippy::too_many_arguments)]
     fn verify(
         params: &Self::Params,
+        proof_shape: &ProofShape,
         verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         x: &ExpanderSingleVarChallenge<F>,
@@ -163,6 +187,7 @@ pub trait ExpanderPCS<F: FieldEngine> {
     ) -> bool;
 
     /// Open a set of polynomials at a set of points.
+    #[cfg(feature = "prover")]
     fn multi_points_batch_open(
         _params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
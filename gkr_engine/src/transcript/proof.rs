@@ -1,5 +1,7 @@
 use serdes::ExpSerde;
 
+use crate::FieldEngine;
+
 /// Proof. In the serialized mode.
 #[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
 pub struct Proof {
@@ -12,3 +14,48 @@ impl Proof {
         self.bytes.extend_from_slice(&buffer[..size]);
     }
 }
+
+/// The shape of the proving run a proof comes from, everything the verifier needs to know
+/// about the prover's setup without an MPI engine of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ExpSerde)]
+pub struct ProofShape {
+    /// Number of processes the proof was generated with
+    pub world_size: usize,
+    /// Number of circuit instances packed in a SIMD circuit field element
+    pub simd_size: usize,
+    /// Number of variables of the input layer of one process
+    pub num_input_vars: usize,
+    /// Number of variables of the output layer of one process
+    pub num_output_vars: usize,
+}
+
+impl ProofShape {
+    pub fn new<F: FieldEngine>(
+        world_size: usize,
+        num_input_vars: usize,
+        num_output_vars: usize,
+    ) -> Self {
+        assert!(world_size.is_power_of_two());
+        Self {
+            world_size,
+            simd_size: F::get_field_pack_size(),
+            num_input_vars,
+            num_output_vars,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_single_process(&self) -> bool {
+        self.world_size == 1
+    }
+
+    #[inline(always)]
+    pub fn num_mpi_vars(&self) -> usize {
+        self.world_size.ilog2() as usize
+    }
+
+    #[inline(always)]
+    pub fn num_simd_vars(&self) -> usize {
+        self.simd_size.ilog2() as usize
+    }
+}
@@ -0,0 +1,58 @@
+use halo2curves::bn256::{G1Affine, G2Affine};
+use serdes::ExpSerde;
+
+/// The evaluations at the secret point tau of the QAP of a constraint system, over the groups.
+/// `u_i`, `v_i` and `w_i` are the polynomials of variable `i` in the constraints `a`, `b`
+/// and `c`, and `t` is the vanishing polynomial of the domain.
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct ProvingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub delta_g1: G1Affine,
+    pub delta_g2: G2Affine,
+
+    /// `u_i(tau)` for all the variables
+    pub a_query: Vec<G1Affine>,
+
+    /// `v_i(tau)` for all the variables
+    pub b_g1_query: Vec<G1Affine>,
+    pub b_g2_query: Vec<G2Affine>,
+
+    /// `tau^i t(tau) / delta` for `i < n - 1`, `n` the size of the domain
+    pub h_query: Vec<G1Affine>,
+
+    /// `(beta u_i(tau) + alpha v_i(tau) + w_i(tau)) / delta` for the private variables
+    pub l_query: Vec<G1Affine>,
+}
+
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct VerifyingKey {
+    pub alpha_g1: G1Affine,
+    pub beta_g2: G2Affine,
+    pub gamma_g2: G2Affine,
+    pub delta_g2: G2Affine,
+
+    /// `(beta u_i(tau) + alpha v_i(tau) + w_i(tau)) / gamma` for the constant one and the
+    /// public inputs
+    pub ic: Vec<G1Affine>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ExpSerde)]
+pub struct Groth16Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+}
+
+impl ProvingKey {
+    #[inline]
+    pub fn num_variables(&self) -> usize {
+        self.a_query.len()
+    }
+
+    #[inline]
+    pub fn domain_size(&self) -> usize {
+        self.h_query.len() + 1
+    }
+}
@@ -0,0 +1,45 @@
+//! Groth16 over BN254, to wrap the GKR proofs of BN254 circuits with the MiMC5 transcript into
+//! constant size proofs, e.g., to be verified on chain.
+//!
+//! `GKRWrapper` arithmetizes `gkr::Verifier::verify` as the rank-1 constraint system of
+//! `recursion_circuit::RecursiveVerifier`, and proves it is satisfied by the GKR proof. The
+//! opening of a KZG commitment is deferred, i.e., it is part of the wrapped proof and checked
+//! natively by the verifier.
+//!
+//! The setup is local, i.e., its randomness is known to whoever runs it, which is only fit
+//! for testing.
+
+use recursion_circuit::RecursionError;
+use thiserror::Error;
+
+mod keys;
+pub use keys::*;
+
+mod qap;
+
+mod setup;
+pub use setup::*;
+
+mod prover;
+pub use prover::*;
+
+mod verifier;
+pub use verifier::*;
+
+mod wrapper;
+pub use wrapper::*;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Groth16Error {
+    #[error("the assignment does not satisfy the constraint system")]
+    Unsatisfied,
+
+    #[error("the proving key does not match the constraint system")]
+    KeyMismatch,
+
+    #[error(transparent)]
+    Recursion(#[from] RecursionError),
+}
@@ -0,0 +1,52 @@
+use arith::{Field, Fr};
+use halo2curves::{
+    bn256::G1,
+    group::{prime::PrimeCurveAffine, Curve},
+    msm::best_multiexp,
+};
+use rand::RngCore;
+use recursion_circuit::R1CS;
+
+use crate::{
+    qap::{domain_size, quotient},
+    Groth16Error, Groth16Proof, ProvingKey,
+};
+
+/// A proof that the assignment, whose public inputs are `assignment[1..=num_public_inputs]`,
+/// satisfies the constraint system
+pub fn prove(
+    pk: &ProvingKey,
+    r1cs: &R1CS<Fr>,
+    assignment: &[Fr],
+    mut rng: impl RngCore,
+) -> Result<Groth16Proof, Groth16Error> {
+    let num_ic = 1 + r1cs.num_public_inputs;
+    if pk.num_variables() != r1cs.num_variables
+        || pk.l_query.len() != r1cs.num_variables - num_ic
+        || pk.domain_size() != domain_size(r1cs)
+    {
+        return Err(Groth16Error::KeyMismatch);
+    }
+    if !r1cs.is_satisfied(assignment) {
+        return Err(Groth16Error::Unsatisfied);
+    }
+
+    let h = quotient(r1cs, assignment, pk.domain_size());
+    let r = Fr::random_unsafe(&mut rng);
+    let s = Fr::random_unsafe(&mut rng);
+
+    let a = pk.alpha_g1.to_curve() + best_multiexp(assignment, &pk.a_query) + pk.delta_g1 * r;
+    let b_g1 = pk.beta_g1.to_curve() + best_multiexp(assignment, &pk.b_g1_query) + pk.delta_g1 * s;
+    let b_g2 = pk.beta_g2.to_curve() + best_multiexp(assignment, &pk.b_g2_query) + pk.delta_g2 * s;
+    let c: G1 = best_multiexp(&assignment[num_ic..], &pk.l_query)
+        + best_multiexp(&h, &pk.h_query)
+        + a * s
+        + b_g1 * r
+        - pk.delta_g1 * (r * s);
+
+    Ok(Groth16Proof {
+        a: a.to_affine(),
+        b: b_g2.to_affine(),
+        c: c.to_affine(),
+    })
+}
@@ -0,0 +1,121 @@
+//! The quadratic arithmetic program of a constraint system, interpolating its constraints over
+//! the domain of the 2^k-th roots of unity.
+//!
+//! The domain holds the constraints followed by one constraint `z_i * 0 = 0` per public input
+//! and for the constant one, which keeps the polynomials of the public inputs independent.
+
+use arith::{FFTField, Field, Fr};
+use halo2curves::ff::PrimeField;
+use recursion_circuit::{eval_lc, R1CS};
+
+#[inline]
+pub(crate) fn domain_size(r1cs: &R1CS<Fr>) -> usize {
+    (r1cs.num_constraints() + r1cs.num_public_inputs + 1).next_power_of_two()
+}
+
+/// The Lagrange polynomials of the domain of size `n` at `tau`, not in the domain:
+/// `L_j(tau) = (tau^n - 1) omega^j / (n (tau - omega^j))`
+pub(crate) fn lagrange_at(tau: &Fr, n: usize) -> Vec<Fr> {
+    let omega = Fr::two_adic_generator(n.ilog2() as usize);
+    let n_fr = Fr::from(n as u64);
+    let powers = (0..n)
+        .scan(Fr::ONE, |w, _| {
+            let res = *w;
+            *w *= omega;
+            Some(res)
+        })
+        .collect::<Vec<_>>();
+
+    let denominators = powers.iter().map(|w| n_fr * (*tau - w)).collect::<Vec<_>>();
+    let vanishing = tau.exp(n as u128) - Fr::ONE;
+    batch_inverse(&denominators)
+        .iter()
+        .zip(&powers)
+        .map(|(d_inv, w)| vanishing * w * d_inv)
+        .collect()
+}
+
+/// `u_i(tau)`, `v_i(tau)` and `w_i(tau)` for all the variables, from the Lagrange polynomials
+pub(crate) fn qap_at(r1cs: &R1CS<Fr>, lagrange: &[Fr]) -> (Vec<Fr>, Vec<Fr>, Vec<Fr>) {
+    let mut u = vec![Fr::ZERO; r1cs.num_variables];
+    let mut v = vec![Fr::ZERO; r1cs.num_variables];
+    let mut w = vec![Fr::ZERO; r1cs.num_variables];
+
+    for (constraint, l) in r1cs.constraints.iter().zip(lagrange) {
+        constraint.a.iter().for_each(|(i, c)| u[*i] += *c * l);
+        constraint.b.iter().for_each(|(i, c)| v[*i] += *c * l);
+        constraint.c.iter().for_each(|(i, c)| w[*i] += *c * l);
+    }
+    let public_rows = &lagrange[r1cs.num_constraints()..];
+    (0..=r1cs.num_public_inputs).for_each(|i| u[i] += public_rows[i]);
+
+    (u, v, w)
+}
+
+/// The coefficients of `h = (a b - c) / t`, of degree at most `n - 2`, where `a`, `b` and `c`
+/// interpolate the evaluations of the constraints on the assignment
+pub(crate) fn quotient(r1cs: &R1CS<Fr>, assignment: &[Fr], n: usize) -> Vec<Fr> {
+    let mut a = vec![Fr::ZERO; n];
+    let mut b = vec![Fr::ZERO; n];
+    let mut c = vec![Fr::ZERO; n];
+    for (j, constraint) in r1cs.constraints.iter().enumerate() {
+        a[j] = eval_lc(&constraint.a, assignment);
+        b[j] = eval_lc(&constraint.b, assignment);
+        c[j] = eval_lc(&constraint.c, assignment);
+    }
+    a[r1cs.num_constraints()..=r1cs.num_constraints() + r1cs.num_public_inputs]
+        .copy_from_slice(&assignment[..=r1cs.num_public_inputs]);
+
+    // t is the constant g^n - 1 on the coset g * omega^j
+    let g = <Fr as PrimeField>::MULTIPLICATIVE_GENERATOR;
+    [&mut a, &mut b, &mut c]
+        .into_iter()
+        .for_each(|evals| to_coset(evals, &g));
+    let t_inv = (g.exp(n as u128) - Fr::ONE).inv().unwrap();
+    let mut h = a
+        .iter()
+        .zip(&b)
+        .zip(&c)
+        .map(|((a, b), c)| (*a * b - c) * t_inv)
+        .collect::<Vec<_>>();
+
+    Fr::ifft_in_place(&mut h);
+    let g_inv = g.inv().unwrap();
+    scale_by_powers(&mut h, &g_inv);
+    h.truncate(n - 1);
+    h
+}
+
+/// From the evaluations on the domain to the evaluations on the coset `g * omega^j`
+fn to_coset(evals: &mut [Fr], g: &Fr) {
+    Fr::ifft_in_place(evals);
+    scale_by_powers(evals, g);
+    Fr::fft_in_place(evals);
+}
+
+#[inline]
+fn scale_by_powers(coeffs: &mut [Fr], g: &Fr) {
+    let mut power = Fr::ONE;
+    coeffs.iter_mut().for_each(|c| {
+        *c *= power;
+        power *= g;
+    });
+}
+
+/// Montgomery's trick, the elements are non zero
+pub(crate) fn batch_inverse(elems: &[Fr]) -> Vec<Fr> {
+    let mut prefix = Vec::with_capacity(elems.len());
+    let mut acc = Fr::ONE;
+    for e in elems {
+        prefix.push(acc);
+        acc *= e;
+    }
+
+    let mut inv = acc.inv().unwrap();
+    let mut res = vec![Fr::ZERO; elems.len()];
+    for i in (0..elems.len()).rev() {
+        res[i] = inv * prefix[i];
+        inv *= elems[i];
+    }
+    res
+}
@@ -0,0 +1,83 @@
+use arith::{Field, Fr};
+use halo2curves::{
+    bn256::{G1Affine, G2Affine, G1, G2},
+    group::{prime::PrimeCurveAffine, Curve, Group},
+};
+use rand::RngCore;
+use rayon::prelude::*;
+use recursion_circuit::R1CS;
+
+use crate::{
+    qap::{domain_size, lagrange_at, qap_at},
+    ProvingKey, VerifyingKey,
+};
+
+/// The keys of the constraint system, from randomness known to the caller, i.e., only fit for
+/// testing: whoever knows it can prove anything
+pub fn setup_for_testing(r1cs: &R1CS<Fr>, mut rng: impl RngCore) -> (ProvingKey, VerifyingKey) {
+    let n = domain_size(r1cs);
+    let tau = Fr::random_unsafe(&mut rng);
+    let alpha = Fr::random_unsafe(&mut rng);
+    let beta = Fr::random_unsafe(&mut rng);
+    let gamma = Fr::random_unsafe(&mut rng);
+    let delta = Fr::random_unsafe(&mut rng);
+    let gamma_inv = gamma.inv().unwrap();
+    let delta_inv = delta.inv().unwrap();
+
+    let lagrange = lagrange_at(&tau, n);
+    let (u, v, w) = qap_at(r1cs, &lagrange);
+    let num_ic = 1 + r1cs.num_public_inputs;
+    let k = |i: usize| beta * u[i] + alpha * v[i] + w[i];
+    let ic = (0..num_ic).map(|i| k(i) * gamma_inv).collect::<Vec<_>>();
+    let l = (num_ic..r1cs.num_variables)
+        .map(|i| k(i) * delta_inv)
+        .collect::<Vec<_>>();
+
+    let t_delta = (tau.exp(n as u128) - Fr::ONE) * delta_inv;
+    let h = (0..n - 1)
+        .scan(t_delta, |acc, _| {
+            let res = *acc;
+            *acc *= tau;
+            Some(res)
+        })
+        .collect::<Vec<_>>();
+
+    let pk = ProvingKey {
+        alpha_g1: (G1Affine::generator() * alpha).to_affine(),
+        beta_g1: (G1Affine::generator() * beta).to_affine(),
+        beta_g2: (G2Affine::generator() * beta).to_affine(),
+        delta_g1: (G1Affine::generator() * delta).to_affine(),
+        delta_g2: (G2Affine::generator() * delta).to_affine(),
+        a_query: g1_powers(&u),
+        b_g1_query: g1_powers(&v),
+        b_g2_query: g2_powers(&v),
+        h_query: g1_powers(&h),
+        l_query: g1_powers(&l),
+    };
+    let vk = VerifyingKey {
+        alpha_g1: pk.alpha_g1,
+        beta_g2: pk.beta_g2,
+        gamma_g2: (G2Affine::generator() * gamma).to_affine(),
+        delta_g2: pk.delta_g2,
+        ic: g1_powers(&ic),
+    };
+    (pk, vk)
+}
+
+/// The scalars times the generator of G1
+fn g1_powers(scalars: &[Fr]) -> Vec<G1Affine> {
+    let g = G1::generator();
+    let points = scalars.par_iter().map(|s| g * s).collect::<Vec<_>>();
+    let mut res = vec![G1Affine::identity(); points.len()];
+    G1::batch_normalize(&points, &mut res);
+    res
+}
+
+/// The scalars times the generator of G2
+fn g2_powers(scalars: &[Fr]) -> Vec<G2Affine> {
+    let g = G2::generator();
+    let points = scalars.par_iter().map(|s| g * s).collect::<Vec<_>>();
+    let mut res = vec![G2Affine::identity(); points.len()];
+    G2::batch_normalize(&points, &mut res);
+    res
+}
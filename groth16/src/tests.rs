@@ -0,0 +1,164 @@
+use arith::{Field, Fr};
+use ark_std::test_rng;
+use gkr_engine::BN254Config;
+use recursion_circuit::{CircuitBuilder, R1CS};
+use serdes::ExpSerde;
+
+use crate::*;
+
+// x^5 + x * y + 3 = z, with z public
+fn build_r1cs(x: u32, y: u32, z: u32) -> (R1CS<Fr>, Vec<Fr>) {
+    let mut builder = CircuitBuilder::<BN254Config>::new();
+    let z = builder.public_input(Fr::from(z));
+    let x = builder.input(Fr::from(x));
+    let y = builder.input(Fr::from(y));
+
+    let x5 = builder.pow5(&x);
+    let xy = builder.mul(&x, &y);
+    let lhs = builder.add(&x5, &xy);
+    let lhs = builder.add_constant(&lhs, Fr::from(3u32));
+    builder.assert_equal(&lhs, &z);
+    builder.build_r1cs()
+}
+
+#[test]
+fn test_groth16() {
+    let mut rng = test_rng();
+    let (r1cs, assignment) = build_r1cs(2, 3, 41);
+    let (pk, vk) = setup_for_testing(&r1cs, &mut rng);
+
+    let proof = prove(&pk, &r1cs, &assignment, &mut rng).unwrap();
+    assert!(verify(&vk, &[Fr::from(41u32)], &proof));
+    assert!(!verify(&vk, &[Fr::from(42u32)], &proof));
+    assert!(!verify(&vk, &[], &proof));
+
+    let mut bytes = vec![];
+    proof.serialize_into(&mut bytes).unwrap();
+    let proof = Groth16Proof::deserialize_from(bytes.as_slice()).unwrap();
+    let mut bytes = vec![];
+    vk.serialize_into(&mut bytes).unwrap();
+    let vk = VerifyingKey::deserialize_from(bytes.as_slice()).unwrap();
+    assert!(verify(&vk, &[Fr::from(41u32)], &proof));
+
+    // the same constraints, not satisfied
+    let (r1cs, assignment) = build_r1cs(2, 3, 42);
+    assert_eq!(
+        prove(&pk, &r1cs, &assignment, &mut rng).unwrap_err(),
+        Groth16Error::Unsatisfied
+    );
+
+    let mut builder = CircuitBuilder::<BN254Config>::new();
+    let x = builder.input(Fr::ONE);
+    let y = builder.public_input(Fr::ONE);
+    builder.assert_equal(&x, &y);
+    let (r1cs, assignment) = builder.build_r1cs();
+    assert_eq!(
+        prove(&pk, &r1cs, &assignment, &mut rng).unwrap_err(),
+        Groth16Error::KeyMismatch
+    );
+}
+
+// without the recursion feature, the verifier hashes the commitment a thousand times
+#[cfg(feature = "recursion")]
+mod end_to_end {
+    use arith::{Field, Fr};
+    use ark_std::test_rng;
+    use circuit::Circuit;
+    use gkr::{
+        utils::bn254_fixture::{build_circuit, prove, random_witness, LOG_INPUT_SIZE},
+        BN254ConfigMIMC5KZG, BN254ConfigMIMC5Raw,
+    };
+    use gkr_engine::{BN254Config, GKREngine, MPIConfig};
+    use poly_commit::expander_pcs_init_testing_only;
+    use recursion_circuit::MiMC5Transcript;
+
+    use crate::{GKRWrapper, Groth16Error, WrappedProof};
+
+    /// Wrap a proof and check the verification of the wrapped proof, returns whether the
+    /// opening of the commitment is deferred
+    fn test_wrapper<Cfg>() -> bool
+    where
+        Cfg: GKREngine<FieldConfig = BN254Config, TranscriptConfig = MiMC5Transcript>,
+    {
+        let mut rng = test_rng();
+        let circuit = build_circuit();
+        let (witness, public_input) = random_witness(&mut rng);
+        let (claimed_v, proof) = prove::<Cfg>(&circuit, &witness);
+
+        let wrapper = GKRWrapper::<Cfg>::new(circuit.proof_shape(1));
+        let (pk, vk) = wrapper
+            .setup_for_testing(&circuit, &public_input, &claimed_v, &proof, &mut rng)
+            .unwrap();
+        let wrapped = wrapper
+            .prove(&pk, &circuit, &public_input, &claimed_v, &proof, &mut rng)
+            .unwrap();
+
+        let (pcs_params, _, pcs_verification_key, _) =
+            expander_pcs_init_testing_only::<BN254Config, Cfg::PCSConfig>(
+                LOG_INPUT_SIZE,
+                &MPIConfig::verifier_new(1),
+            );
+        let verify =
+            |circuit: &Circuit<BN254Config>, public_input: &[Fr], wrapped: &WrappedProof| {
+                wrapper.verify(
+                    &vk,
+                    circuit,
+                    public_input,
+                    wrapped,
+                    &pcs_params,
+                    &pcs_verification_key,
+                )
+            };
+        assert!(verify(&circuit, &public_input, &wrapped));
+        assert!(!verify(&circuit, &[public_input[0] + Fr::ONE], &wrapped));
+        assert!(!verify(&circuit, &[], &wrapped));
+
+        // the claimed output of a circuit whose outputs are not constrained to zero is unknown
+        // to the verifier
+        let mut with_outputs = circuit.clone();
+        with_outputs.expected_num_output_zeros = 1;
+        assert!(!verify(&with_outputs, &public_input, &wrapped));
+
+        // the outputs of the witness are not zero, its proof is not wrapped
+        let (mut unsatisfied, unsatisfied_input) = random_witness(&mut rng);
+        unsatisfied.values[3] += Fr::ONE;
+        let (claimed_v, proof) = prove::<Cfg>(&circuit, &unsatisfied);
+        assert!(!claimed_v.is_zero());
+        assert_eq!(
+            wrapper
+                .prove(
+                    &pk,
+                    &circuit,
+                    &unsatisfied_input,
+                    &claimed_v,
+                    &proof,
+                    &mut rng
+                )
+                .unwrap_err(),
+            Groth16Error::Unsatisfied
+        );
+
+        // the deferred opening is part of the statement of the Groth16 proof
+        let Some(deferred_opening) = &wrapped.deferred_opening else {
+            return false;
+        };
+        let mut tampered = wrapped.clone();
+        tampered.deferred_opening.as_mut().unwrap().value += Fr::ONE;
+        assert!(!verify(&circuit, &public_input, &tampered));
+        let mut tampered = wrapped.clone();
+        tampered.deferred_opening = None;
+        assert!(!verify(&circuit, &public_input, &tampered));
+        assert!(!deferred_opening.opening.is_empty());
+        true
+    }
+
+    #[test]
+    fn test_wrap_raw_proof() {
+        assert!(!test_wrapper::<BN254ConfigMIMC5Raw>());
+    }
+
+    #[test]
+    fn test_wrap_kzg_proof() {
+        assert!(test_wrapper::<BN254ConfigMIMC5KZG>());
+    }
+}
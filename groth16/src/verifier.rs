@@ -0,0 +1,27 @@
+use arith::Fr;
+use halo2curves::{
+    bn256::Bn256,
+    group::{prime::PrimeCurveAffine, Curve, Group},
+    msm::best_multiexp,
+    pairing::{MillerLoopResult, MultiMillerLoop},
+};
+
+use crate::{Groth16Proof, VerifyingKey};
+
+/// `e(A, B) = e(alpha, beta) e(IC, gamma) e(C, delta)`, with `IC` the combination of the
+/// public inputs
+pub fn verify(vk: &VerifyingKey, public_input: &[Fr], proof: &Groth16Proof) -> bool {
+    if public_input.len() + 1 != vk.ic.len() {
+        return false;
+    }
+
+    let ic = (vk.ic[0].to_curve() + best_multiexp(public_input, &vk.ic[1..])).to_affine();
+    let gt_result = Bn256::multi_miller_loop(&[
+        (&proof.a, &proof.b.into()),
+        (&-vk.alpha_g1, &vk.beta_g2.into()),
+        (&-ic, &vk.gamma_g2.into()),
+        (&-proof.c, &vk.delta_g2.into()),
+    ]);
+
+    gt_result.final_exponentiation().is_identity().into()
+}
@@ -0,0 +1,116 @@
+use arith::{Field, Fr};
+use circuit::Circuit;
+use gkr_engine::{
+    BN254Config, ExpanderPCS, GKREngine, Proof, ProofShape, StructuredReferenceString,
+};
+use rand::RngCore;
+use recursion_circuit::{
+    recursive_public_input, CircuitBuilder, DeferredOpening, MiMC5Transcript, RecursionError,
+    RecursiveVerifier, R1CS,
+};
+use serdes::ExpSerde;
+
+use crate::{
+    prove, setup_for_testing, verify, Groth16Error, Groth16Proof, ProvingKey, VerifyingKey,
+};
+
+/// A GKR proof wrapped in a Groth16 proof of its verification, with the opening of its
+/// commitment if it is deferred
+#[derive(Debug, Clone, PartialEq, ExpSerde)]
+pub struct WrappedProof {
+    pub proof: Groth16Proof,
+    pub deferred_opening: Option<DeferredOpening>,
+}
+
+/// Wraps the proofs of `Cfg`, e.g., `BN254ConfigMIMC5Raw` or `BN254ConfigMIMC5KZG`, under the
+/// constraints of `RecursiveVerifier`
+pub struct GKRWrapper<Cfg: GKREngine> {
+    pub verifier: RecursiveVerifier<Cfg>,
+}
+
+impl<Cfg> GKRWrapper<Cfg>
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, TranscriptConfig = MiMC5Transcript>,
+{
+    pub fn new(proof_shape: ProofShape) -> Self {
+        Self {
+            verifier: RecursiveVerifier::new(proof_shape),
+        }
+    }
+
+    /// The constraint system verifying the proof, its assignment and the deferred opening
+    #[allow(clippy::type_complexity)]
+    pub fn synthesize(
+        &self,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        claimed_v: &Fr,
+        proof: &Proof,
+    ) -> Result<(R1CS<Fr>, Vec<Fr>, Option<DeferredOpening>), RecursionError> {
+        let mut builder = CircuitBuilder::new();
+        let deferred_opening =
+            self.verifier
+                .synthesize(&mut builder, circuit, public_input, claimed_v, proof)?;
+        let (r1cs, assignment) = builder.build_r1cs();
+        Ok((r1cs, assignment, deferred_opening))
+    }
+
+    /// The keys for the proofs of the circuit. The constraints only depend on the shape of the
+    /// proofs, so that any proof of the circuit, e.g., a first one, sets them up.
+    pub fn setup_for_testing(
+        &self,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        claimed_v: &Fr,
+        proof: &Proof,
+        rng: impl RngCore,
+    ) -> Result<(ProvingKey, VerifyingKey), Groth16Error> {
+        let (r1cs, _, _) = self.synthesize(circuit, public_input, claimed_v, proof)?;
+        Ok(setup_for_testing(&r1cs, rng))
+    }
+
+    pub fn prove(
+        &self,
+        pk: &ProvingKey,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        claimed_v: &Fr,
+        proof: &Proof,
+        rng: impl RngCore,
+    ) -> Result<WrappedProof, Groth16Error> {
+        let (r1cs, assignment, deferred_opening) =
+            self.synthesize(circuit, public_input, claimed_v, proof)?;
+        Ok(WrappedProof {
+            proof: prove(pk, &r1cs, &assignment, rng)?,
+            deferred_opening,
+        })
+    }
+
+    /// Whether `gkr::Verifier::verify` accepts the wrapped proof for the statement. The claimed
+    /// output is not taken from the prover: the outputs of the supported circuits are all
+    /// constrained to zero, the other circuits are rejected.
+    pub fn verify(
+        &self,
+        vk: &VerifyingKey,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        wrapped_proof: &WrappedProof,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<BN254Config>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<BN254Config>>::SRS as StructuredReferenceString>::VKey,
+    ) -> bool {
+        if self.verifier.check_supported(circuit).is_err() {
+            return false;
+        }
+
+        let deferred_opening = wrapped_proof.deferred_opening.as_ref();
+        let statement = recursive_public_input(public_input, &Fr::ZERO, deferred_opening);
+        verify(vk, &statement, &wrapped_proof.proof)
+            && deferred_opening.is_none_or(|deferred_opening| {
+                self.verifier.verify_deferred_opening(
+                    deferred_opening,
+                    pcs_params,
+                    pcs_verification_key,
+                )
+            })
+    }
+}
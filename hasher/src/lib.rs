@@ -6,7 +6,7 @@ pub use traits::{FiatShamirHashType, FiatShamirHasher, PoseidonStateTrait};
 // field hashers
 
 mod mimc;
-pub use mimc::MiMC5FiatShamirHasher;
+pub use mimc::{generate_mimc_constants, MiMC5FiatShamirHasher};
 
 pub mod poseidon;
 pub use poseidon::PoseidonFiatShamirHasher;
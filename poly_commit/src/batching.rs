@@ -6,11 +6,14 @@ use gkr_engine::Transcript;
 use halo2curves::group::Curve;
 use halo2curves::msm::best_multiexp;
 use halo2curves::{ff::PrimeField, CurveAffine};
-use polynomials::{EqPolynomial, MultilinearExtension};
-use polynomials::{MultiLinearPoly, SumOfProductsPoly};
+use polynomials::EqPolynomial;
+#[cfg(feature = "prover")]
+use polynomials::{MultiLinearPoly, MultilinearExtension, SumOfProductsPoly};
+#[cfg(feature = "prover")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serdes::ExpSerde;
 use sumcheck::{IOPProof, SumCheck};
+#[cfg(feature = "prover")]
 use utils::timer::Timer;
 
 /// Merge a list of polynomials and its corresponding points into a single polynomial
@@ -23,6 +26,7 @@ use utils::timer::Timer;
 /// recomputed afterwards for g_prime construction. This reduces peak memory at
 /// the cost of redundant computation.
 #[allow(clippy::type_complexity)]
+#[cfg(feature = "prover")]
 pub fn prover_merge_points<C>(
     polys: &[impl MultilinearExtension<C::Scalar>],
     points: &[impl AsRef<[C::Scalar]>],
@@ -91,7 +95,6 @@ where
     // sumcheck's point \tilde eq_i(a2) = eq(a2, point_i)
     let timer = Timer::new("Building g'(X)", true);
 
-    let mut g_prime_evals = vec![C::Scalar::zero(); 1 << num_vars];
     let eq_i_a2_polys = points
         .par_iter()
         .map(|point| {
@@ -105,19 +108,13 @@ where
     // Recompute them for g_prime construction.
     let tilde_gs_for_gprime = tilde_gs.unwrap_or_else(|| build_tilde_gs(polys, &eq_t_i));
 
-    for (tilde_g, eq_i_a2) in tilde_gs_for_gprime.iter().zip(eq_i_a2_polys.iter()) {
-        for (j, &tilde_g_eval) in tilde_g.coeffs.iter().enumerate() {
-            g_prime_evals[j] += tilde_g_eval * eq_i_a2;
-        }
-    }
-    let g_prime = MultiLinearPoly {
-        coeffs: g_prime_evals,
-    };
+    let g_prime = MultiLinearPoly::linear_combination(&tilde_gs_for_gprime, &eq_i_a2_polys);
     timer.stop();
     (a2, g_prime, proof)
 }
 
 /// Build tilde_gs: \tilde g_i(b) = eq(t, i) * f_i(b)
+#[cfg(feature = "prover")]
 fn build_tilde_gs<S: ExtensionField + PrimeField>(
     polys: &[impl MultilinearExtension<S>],
     eq_t_i: &[S],
@@ -228,6 +225,7 @@ fn transpose<C: CurveAffine>(m: &[&[C]]) -> Vec<Vec<C>> {
 #[inline]
 #[allow(clippy::type_complexity)]
 #[allow(unused)]
+#[cfg(feature = "prover")]
 fn pad_polynomials_and_points<C>(
     polys: &[impl MultilinearExtension<C::Scalar>],
     points: &[impl AsRef<[C::Scalar]>],
@@ -1,28 +1,29 @@
 use arith::ExtensionField;
+#[cfg(feature = "prover")]
+use gkr_engine::MPIEngine;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, PolynomialCommitmentType, ProofShape,
     StructuredReferenceString, Transcript,
 };
 use halo2curves::{ff::PrimeField, group::UncompressedEncoding, msm, CurveAffine};
-use polynomials::{
-    EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
-    RefMultiLinearPoly,
-};
+use polynomials::{EqPolynomial, MultilinearExtension, RefMultiLinearPoly};
+#[cfg(feature = "prover")]
+use polynomials::{MutRefMultiLinearPoly, MutableMultilinearExtension};
 use serdes::ExpSerde;
 
+#[cfg(feature = "prover")]
+use crate::hyrax::hyrax_impl::{
+    hyrax_commit, hyrax_multi_points_batch_open_internal, hyrax_open, hyrax_setup,
+};
 use crate::{
     hyrax::{
-        hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
+        hyrax_impl::{hyrax_multi_points_batch_verify_internal, hyrax_verify},
         pedersen::pedersen_commit,
     },
     traits::BatchOpening,
     HyraxCommitment, HyraxOpening, HyraxPCS, PedersenParams,
 };
 
-use super::hyrax_impl::{
-    hyrax_multi_points_batch_open_internal, hyrax_multi_points_batch_verify_internal,
-};
-
 impl<G, C> ExpanderPCS<G> for HyraxPCS<C>
 where
     G: FieldEngine<ChallengeField = C::Scalar, SimdCircuitField = C::Scalar>,
@@ -48,8 +49,10 @@ where
         n_input_vars
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn gen_srs(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -60,6 +63,7 @@ where
         hyrax_setup(*params, mpi_vars, rng)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         _params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -87,6 +91,7 @@ where
         HyraxCommitment(global_commit).into()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -121,6 +126,7 @@ where
 
     fn verify(
         _params: &Self::Params,
+        proof_shape: &ProofShape,
         verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         x: &ExpanderSingleVarChallenge<G>,
@@ -128,7 +134,11 @@ where
         _transcript: &mut impl Transcript,
         opening: &Self::Opening,
     ) -> bool {
-        if x.r_mpi.is_empty() {
+        if x.r_mpi.len() != proof_shape.num_mpi_vars() {
+            return false;
+        }
+
+        if proof_shape.is_single_process() {
             return hyrax_verify(verifying_key, commitment, &x.local_xs(), evals, opening);
         }
 
@@ -153,6 +163,7 @@ where
     }
 
     /// Open a set of polynomials at a set of points.
+    #[cfg(feature = "prover")]
     fn multi_points_batch_open(
         _params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -1,26 +1,29 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::Transcript;
 use halo2curves::{ff::PrimeField, group::UncompressedEncoding, msm, CurveAffine};
-use polynomials::{
-    EqPolynomial, MultilinearExtension, MutRefMultiLinearPoly, MutableMultilinearExtension,
-    RefMultiLinearPoly,
-};
+use polynomials::{EqPolynomial, MultilinearExtension, RefMultiLinearPoly};
+#[cfg(feature = "prover")]
+use polynomials::{MutRefMultiLinearPoly, MutableMultilinearExtension};
+#[cfg(feature = "prover")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serdes::ExpSerde;
+#[cfg(feature = "prover")]
 use utils::timer::Timer;
 
-use crate::batching::{prover_merge_points, verifier_merge_points};
+#[cfg(feature = "prover")]
+use crate::batching::prover_merge_points;
+use crate::batching::verifier_merge_points;
+#[cfg(feature = "prover")]
+use crate::hyrax::pedersen::pedersen_setup;
 use crate::traits::BatchOpening;
 use crate::{
-    hyrax::{
-        pedersen::{pedersen_commit, pedersen_setup},
-        PedersenParams,
-    },
+    hyrax::{pedersen::pedersen_commit, PedersenParams},
     powers_series,
 };
 
 use super::HyraxPCS;
 
+#[cfg(feature = "prover")]
 pub(crate) fn hyrax_setup<C: CurveAffine + ExpSerde>(
     local_vars: usize,
     mpi_vars: usize,
@@ -114,6 +117,7 @@ where
     }
 }
 
+#[cfg(feature = "prover")]
 pub(crate) fn hyrax_commit<C>(
     params: &PedersenParams<C>,
     mle_poly: &impl MultilinearExtension<C::Scalar>,
@@ -149,6 +153,7 @@ where
 }
 
 // NOTE(HS) the hyrax opening returns an eval and an opening against the eval_point on input.
+#[cfg(feature = "prover")]
 pub(crate) fn hyrax_open<C>(
     params: &PedersenParams<C>,
     mle_poly: &impl MultilinearExtension<C::Scalar>,
@@ -213,6 +218,7 @@ where
 // batch open a set of mle_polys at the same point
 // returns a set of eval points and a signle opening
 // NOTE: random linear combination is used to merge polynomials
+#[cfg(feature = "prover")]
 pub(crate) fn hyrax_batch_open<C>(
     params: &PedersenParams<C>,
     mle_poly_list: &[impl MultilinearExtension<C::Scalar>],
@@ -335,6 +341,7 @@ fn scale<F: Field>(base: &[F], scalar: &F) -> Vec<F> {
 /// - the evaluations of the polynomials at their corresponding points
 /// - the batch opening proof containing the sumcheck proof and the opening of g'(X)
 #[allow(clippy::type_complexity)]
+#[cfg(feature = "prover")]
 pub(crate) fn hyrax_multi_points_batch_open_internal<C>(
     proving_key: &PedersenParams<C>,
     polys: &[impl MultilinearExtension<C::Scalar>],
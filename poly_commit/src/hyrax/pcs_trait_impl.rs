@@ -6,16 +6,20 @@ use halo2curves::{ff::PrimeField, group::UncompressedEncoding, CurveAffine};
 use polynomials::MultiLinearPoly;
 use serdes::ExpSerde;
 
+#[cfg(feature = "prover")]
+use crate::hyrax::hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup};
 use crate::traits::BatchOpening;
 use crate::{
-    hyrax::hyrax_impl::{hyrax_commit, hyrax_open, hyrax_setup, hyrax_verify},
-    traits::BatchOpeningPCS,
-    HyraxCommitment, HyraxOpening, PedersenParams, PolynomialCommitmentScheme,
+    hyrax::hyrax_impl::hyrax_verify, traits::BatchOpeningPCS, HyraxCommitment, HyraxOpening,
+    PedersenParams, PolynomialCommitmentScheme,
 };
 
+#[cfg(feature = "prover")]
+use super::hyrax_impl::hyrax_batch_open;
+use super::hyrax_impl::hyrax_batch_verify;
+#[cfg(feature = "prover")]
 use super::hyrax_impl::hyrax_multi_points_batch_open_internal;
 use super::hyrax_impl::hyrax_multi_points_batch_verify_internal;
-use super::hyrax_impl::{hyrax_batch_open, hyrax_batch_verify};
 
 pub struct HyraxPCS<C>
 where
@@ -44,12 +48,15 @@ where
     type Commitment = HyraxCommitment<C>;
     type Opening = HyraxOpening<C>;
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
         (hyrax_setup(*params, 0, rng), *params)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -59,6 +66,7 @@ where
         hyrax_commit(proving_key, poly)
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -90,6 +98,7 @@ where
     C::ScalarExt: ExtensionField + PrimeField,
     C::Base: PrimeField<Repr = [u8; 32]>,
 {
+    #[cfg(feature = "prover")]
     fn single_point_batch_open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -126,6 +135,7 @@ where
     /// Returns:
     /// - the evaluations of the polynomials at their corresponding points
     /// - the batch opening proof containing the sumcheck proof and the opening of g'(X)
+    #[cfg(feature = "prover")]
     fn multiple_points_batch_open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -1,9 +1,7 @@
 use gkr_engine::StructuredReferenceString;
-use halo2curves::{
-    ff::{Field, PrimeField},
-    group::Curve,
-    msm, CurveAffine,
-};
+#[cfg(feature = "prover")]
+use halo2curves::ff::Field;
+use halo2curves::{ff::PrimeField, group::Curve, msm, CurveAffine};
 use serdes::ExpSerde;
 
 #[derive(Clone, Debug, Default)]
@@ -99,6 +97,7 @@ where
     }
 }
 
+#[cfg(feature = "prover")]
 pub(crate) fn pedersen_setup<C>(length: usize, mut rng: impl rand::RngCore) -> PedersenParams<C>
 where
     C: CurveAffine,
@@ -1,7 +1,7 @@
 use arith::ExtensionField;
 use gkr_engine::{
     ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
-    StructuredReferenceString, Transcript,
+    ProofShape, StructuredReferenceString, Transcript,
 };
 use halo2curves::{
     ff::PrimeField,
@@ -39,12 +39,14 @@ where
     type ScratchPad = ();
     type BatchOpening = ();
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
 
     fn gen_params(n_input_vars: usize, _world_size: usize) -> Self::Params {
         std::cmp::max(n_input_vars, Self::MINIMUM_SUPPORTED_NUM_VARS)
     }
 
+    #[cfg(feature = "prover")]
     fn gen_srs(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -59,6 +61,7 @@ where
         generate_coef_form_bi_kzg_local_srs_for_testing(x_degree_po2, y_degree_po2, rank, rng)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         _params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -99,6 +102,7 @@ where
         BiKZGCommitment(final_commit).into()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -137,6 +141,7 @@ where
 
     fn verify(
         _params: &Self::Params,
+        proof_shape: &ProofShape,
         verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         x: &ExpanderSingleVarChallenge<G>,
@@ -148,6 +153,7 @@ where
             let x = lift_expander_challenge_to_n_vars(x, Self::MINIMUM_SUPPORTED_NUM_VARS);
             return <Self as ExpanderPCS<G>>::verify(
                 _params,
+                proof_shape,
                 verifying_key,
                 commitment,
                 &x,
@@ -159,6 +165,7 @@ where
 
         coeff_form_hyper_bikzg_verify(
             verifying_key,
+            proof_shape,
             &x.local_xs(),
             &x.r_mpi,
             v,
@@ -4,7 +4,7 @@
 use std::{io::Cursor, iter};
 
 use arith::ExtensionField;
-use gkr_engine::{MPIEngine, Transcript};
+use gkr_engine::{MPIEngine, ProofShape, Transcript};
 use halo2curves::{
     ff::Field,
     group::{prime::PrimeCurveAffine, Curve, Group, GroupEncoding},
@@ -445,6 +445,7 @@ where
 #[allow(clippy::too_many_arguments)]
 pub fn coeff_form_hyper_bikzg_verify<E, T>(
     vk: &BiKZGVerifierParam<E>,
+    proof_shape: &ProofShape,
     local_alphas: &[E::Fr],
     mpi_alphas: &[E::Fr],
     eval: E::Fr,
@@ -459,6 +460,10 @@ where
     E::G2Affine: CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2> + ExpSerde,
     E::Fr: ExtensionField,
 {
+    if mpi_alphas.len() != proof_shape.num_mpi_vars() {
+        return false;
+    }
+
     // NOTE(HS) deteriorate to vanilla HyperKZG verify if mpi_alphas is empty
     if mpi_alphas.is_empty() {
         let hyper_bikzg_opening = opening.clone();
@@ -476,15 +481,13 @@ where
         return what;
     }
 
-    let mpi_world_size = 1 << mpi_alphas.len();
-
     opening
         .folded_oracle_commitments
         .iter()
         .for_each(|f| fs_transcript.append_u8_slice(f.to_bytes().as_ref()));
 
     // NOTE(HS) transcript MPI thing ...
-    transcript_verifier_sync(fs_transcript, mpi_world_size);
+    transcript_verifier_sync(fs_transcript, proof_shape);
 
     let beta_x = fs_transcript.generate_field_element::<E::Fr>();
     let beta_y = fs_transcript.generate_field_element::<E::Fr>();
@@ -547,7 +550,7 @@ where
     opening.leader_evals.append_to_transcript(fs_transcript);
 
     // NOTE(HS) transcript MPI thing ...
-    transcript_verifier_sync(fs_transcript, mpi_world_size);
+    transcript_verifier_sync(fs_transcript, proof_shape);
 
     let gamma = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -589,7 +592,7 @@ where
     fs_transcript.append_u8_slice(opening.beta_x_commitment.to_bytes().as_ref());
 
     // NOTE(HS) transcript MPI thing ...
-    transcript_verifier_sync(fs_transcript, mpi_world_size);
+    transcript_verifier_sync(fs_transcript, proof_shape);
 
     let delta_x = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -612,7 +615,7 @@ where
     fs_transcript.append_u8_slice(opening.beta_y_commitment.to_bytes().as_ref());
 
     // NOTE(HS) transcript MPI thing ...
-    transcript_verifier_sync(fs_transcript, mpi_world_size);
+    transcript_verifier_sync(fs_transcript, proof_shape);
 
     let delta_y = fs_transcript.generate_field_element::<E::Fr>();
 
@@ -54,8 +54,10 @@ where
     type Commitment = BiKZGCommitment<E>;
     type Opening = HyperBiKZGOpening<E>;
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
         let local_num_vars = if *params == 0 { 1 } else { *params };
 
@@ -64,6 +66,7 @@ where
         (srs, local_num_vars)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -76,6 +79,7 @@ where
         BiKZGCommitment(local_commitment)
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -2,14 +2,17 @@ use arith::{ExtensionField, Field};
 use gkr_engine::Transcript;
 use halo2curves::group::Group;
 use halo2curves::{group::Curve, msm::multiexp_serial, pairing::MultiMillerLoop, CurveAffine};
+#[cfg(feature = "prover")]
 use polynomials::MultiLinearPoly;
 use serdes::ExpSerde;
 
+#[cfg(feature = "prover")]
+use crate::{coeff_form_uni_hyperkzg_open, CoefFormUniKZGSRS};
 use crate::{
-    coeff_form_uni_hyperkzg_open, coeff_form_uni_hyperkzg_verify, powers_series, CoefFormUniKZGSRS,
-    HyperUniKZGOpening, UniKZGVerifierParams,
+    coeff_form_uni_hyperkzg_verify, powers_series, HyperUniKZGOpening, UniKZGVerifierParams,
 };
 
+#[cfg(feature = "prover")]
 pub(crate) fn kzg_single_point_batch_open<E>(
     proving_key: &CoefFormUniKZGSRS<E>,
     polys: &[MultiLinearPoly<E::Fr>],
@@ -1,20 +1,18 @@
 use arith::ExtensionField;
+#[cfg(feature = "prover")]
+use gkr_engine::MPIEngine;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, PolynomialCommitmentType, ProofShape,
     StructuredReferenceString, Transcript,
 };
 use halo2curves::{ff::PrimeField, pairing::MultiMillerLoop, CurveAffine};
+#[cfg(feature = "prover")]
 use polynomials::MultilinearExtension;
 use serdes::ExpSerde;
 
-use crate::{
-    traits::BatchOpening,
-    utils::{
-        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
-        lift_poly_to_n_vars,
-    },
-    *,
-};
+#[cfg(feature = "prover")]
+use crate::utils::{lift_poly_and_expander_challenge_to_n_vars, lift_poly_to_n_vars};
+use crate::{traits::BatchOpening, utils::lift_expander_challenge_to_n_vars, *};
 
 impl<G, E> ExpanderPCS<G> for HyperUniKZGPCS<E>
 where
@@ -41,6 +39,7 @@ where
     type ScratchPad = ();
     type BatchOpening = BatchOpening<E::Fr, Self>;
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
 
     fn gen_params(n_input_vars: usize, world_size: usize) -> Self::Params {
@@ -51,6 +50,7 @@ where
         std::cmp::max(n_input_vars, Self::MINIMUM_SUPPORTED_NUM_VARS)
     }
 
+    #[cfg(feature = "prover")]
     fn gen_srs(
         params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
@@ -65,6 +65,7 @@ where
         generate_coef_form_uni_kzg_srs_for_testing(size, rng)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -88,6 +89,7 @@ where
         Some(UniKZGCommitment(commitment))
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -123,6 +125,7 @@ where
 
     fn verify(
         _params: &Self::Params,
+        proof_shape: &ProofShape,
         verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         x: &ExpanderSingleVarChallenge<G>,
@@ -134,6 +137,7 @@ where
             let x = lift_expander_challenge_to_n_vars(x, Self::MINIMUM_SUPPORTED_NUM_VARS);
             return <Self as ExpanderPCS<G>>::verify(
                 _params,
+                proof_shape,
                 verifying_key,
                 commitment,
                 &x,
@@ -154,6 +158,7 @@ where
     }
 
     /// Open a set of polynomials at a point.
+    #[cfg(feature = "prover")]
     fn multi_points_batch_open(
         _params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
@@ -1,26 +1,32 @@
+#[cfg(feature = "prover")]
 use std::iter;
 
+#[cfg(feature = "prover")]
 use ::utils::timer::Timer;
 use arith::ExtensionField;
 use gkr_engine::Transcript;
+#[cfg(feature = "prover")]
+use halo2curves::ff::Field;
 use halo2curves::{
-    ff::{Field, PrimeField},
+    ff::PrimeField,
     group::{prime::PrimeCurveAffine, GroupEncoding},
     pairing::{Engine, MultiMillerLoop},
     CurveAffine,
 };
+#[cfg(feature = "prover")]
 use itertools::izip;
+#[cfg(feature = "prover")]
 use polynomials::MultilinearExtension;
+#[cfg(feature = "prover")]
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serdes::ExpSerde;
 
-use crate::{
-    batching::{prover_merge_points, verifier_merge_points},
-    traits::BatchOpening,
-    *,
-};
+#[cfg(feature = "prover")]
+use crate::batching::prover_merge_points;
+use crate::{batching::verifier_merge_points, traits::BatchOpening, *};
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn coeff_form_hyperkzg_local_poly_oracles<E>(
     srs: &CoefFormUniKZGSRS<E>,
     coeffs: &[E::Fr],
@@ -56,6 +62,7 @@ where
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn coeff_form_hyperkzg_local_evals<E>(
     coeffs: &[E::Fr],
     folded_oracle_coeffs: &[Vec<E::Fr>],
@@ -101,6 +108,7 @@ where
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn coeff_form_hyperkzg_local_oracle_polys_aggregate<E>(
     coeffs: &[E::Fr],
     folded_oracle_coeffs: &[Vec<E::Fr>],
@@ -119,6 +127,7 @@ where
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub fn coeff_form_uni_hyperkzg_open<E, T>(
     srs: &CoefFormUniKZGSRS<E>,
     coeffs: &[E::Fr],
@@ -257,6 +266,7 @@ where
     true
 }
 
+#[cfg(feature = "prover")]
 pub fn multiple_points_batch_open_impl<E, PCS>(
     proving_key: &CoefFormUniKZGSRS<E>,
     polys: &[impl MultilinearExtension<E::Fr>],
@@ -11,7 +11,9 @@ use crate::{
     *,
 };
 
-use super::batch::{kzg_single_point_batch_open, kzg_single_point_batch_verify};
+#[cfg(feature = "prover")]
+use super::batch::kzg_single_point_batch_open;
+use super::batch::kzg_single_point_batch_verify;
 
 pub struct HyperUniKZGPCS<E>
 where
@@ -65,8 +67,10 @@ where
     type Commitment = UniKZGCommitment<E>;
     type Opening = HyperUniKZGOpening<E>;
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
         let local_num_vars = if *params == 0 { 1 } else { *params };
 
@@ -75,6 +79,7 @@ where
         (srs, local_num_vars)
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -84,6 +89,7 @@ where
         UniKZGCommitment(coeff_form_uni_kzg_commit(proving_key, &poly.coeffs))
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -121,6 +127,7 @@ where
     E::G1Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
     E::G2Affine: ExpSerde + Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G2>,
 {
+    #[cfg(feature = "prover")]
     fn single_point_batch_open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -168,6 +175,7 @@ where
     /// Returns:
     /// - the evaluations of the polynomials at their corresponding points
     /// - the batch opening proof containing the sumcheck proof and the opening of g'(X)
+    #[cfg(feature = "prover")]
     fn multiple_points_batch_open(
         _params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
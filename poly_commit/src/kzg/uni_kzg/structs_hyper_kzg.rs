@@ -127,6 +127,7 @@ where
     // pos/neg beta evals.
     //
     // The return order is the evals at beta2, beta, and -beta.
+    #[cfg(feature = "prover")]
     pub(crate) fn gamma_aggregate_evals(&self, gamma: E::Fr) -> (E::Fr, E::Fr, E::Fr) {
         assert_eq!(self.pos_beta_evals.len(), self.neg_beta_evals.len());
         assert_eq!(self.pos_beta_evals.len() + 1, self.beta2_evals.len());
@@ -139,6 +140,7 @@ where
         (v_beta2, v_beta, v_neg_beta)
     }
 
+    #[cfg(feature = "prover")]
     pub(crate) fn interpolate_degree2_aggregated_evals(
         &self,
         beta: E::Fr,
@@ -155,6 +157,7 @@ where
         self.beta2_evals[self.beta2_evals.len() - 1]
     }
 
+    #[cfg(feature = "prover")]
     pub(crate) fn append_to_transcript<T>(&self, fs_transcript: &mut T)
     where
         T: Transcript,
@@ -275,6 +278,7 @@ where
     }
 
     #[allow(dead_code)]
+    #[cfg(feature = "prover")]
     pub(crate) fn append_to_transcript<T>(&self, fs_transcript: &mut T)
     where
         T: Transcript,
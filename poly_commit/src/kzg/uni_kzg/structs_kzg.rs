@@ -3,6 +3,7 @@ use gkr_engine::StructuredReferenceString;
 use halo2curves::group::prime::PrimeCurveAffine;
 use halo2curves::group::UncompressedEncoding;
 use halo2curves::{pairing::Engine, CurveAffine};
+#[cfg(feature = "prover")]
 use rayon::prelude::*;
 use serdes::{ExpSerde, SerdeResult};
 use std::io::{Read, Write};
@@ -74,13 +75,17 @@ where
 
         // Pre-allocate buffer and convert all points to uncompressed format in parallel
         let mut buffer = vec![0u8; total_size];
-        buffer
+        #[cfg(feature = "prover")]
+        let chunks = buffer
             .par_chunks_mut(point_size)
-            .zip(self.powers_of_tau.par_iter())
-            .for_each(|(chunk, point)| {
-                let uncompressed = point.to_uncompressed();
-                chunk.copy_from_slice(uncompressed.as_ref());
-            });
+            .zip(self.powers_of_tau.par_iter());
+        // the verifier has no rayon, and only loads the verifying key
+        #[cfg(not(feature = "prover"))]
+        let chunks = buffer.chunks_mut(point_size).zip(self.powers_of_tau.iter());
+        chunks.for_each(|(chunk, point)| {
+            let uncompressed = point.to_uncompressed();
+            chunk.copy_from_slice(uncompressed.as_ref());
+        });
 
         // Write all at once
         writer.write_all(&buffer)?;
@@ -103,8 +108,11 @@ where
         reader.read_exact(&mut buffer)?;
 
         // Parse points in parallel - using from_uncompressed_unchecked (no square root needed)
-        let powers_of_tau: Vec<E::G1Affine> = buffer
-            .par_chunks(point_size)
+        #[cfg(feature = "prover")]
+        let chunks = buffer.par_chunks(point_size);
+        #[cfg(not(feature = "prover"))]
+        let chunks = buffer.chunks(point_size);
+        let powers_of_tau: Vec<E::G1Affine> = chunks
             .map(|chunk| {
                 let mut uncompressed =
                     <E::G1Affine as UncompressedEncoding>::Uncompressed::default();
@@ -1,10 +1,12 @@
+#[cfg(feature = "prover")]
+use halo2curves::ff::Field;
 use halo2curves::{
-    ff::Field,
     group::{prime::PrimeCurveAffine, Curve, Group},
     msm,
     pairing::{MillerLoopResult, MultiMillerLoop},
     CurveAffine,
 };
+#[cfg(feature = "prover")]
 use rayon::prelude::*;
 use serdes::ExpSerde;
 
@@ -14,6 +16,7 @@ use msm_cuda::*;
 use crate::*;
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn generate_coef_form_uni_kzg_srs_for_testing<E: MultiMillerLoop>(
     length: usize,
     mut rng: impl rand::RngCore,
@@ -51,6 +54,7 @@ where
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn coeff_form_uni_kzg_commit<E>(
     srs: &CoefFormUniKZGSRS<E>,
     coeffs: &[E::Fr],
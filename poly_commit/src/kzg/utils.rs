@@ -36,6 +36,7 @@ pub(crate) fn univariate_degree_one_quotient<F: Field>(coeffs: &[F], alpha: F) -
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn univariate_roots_quotient<F: Field>(mut coeffs: Vec<F>, roots: &[F]) -> Vec<F> {
     roots.iter().enumerate().for_each(|(ith_root, r)| {
         for i in ((1 + ith_root)..coeffs.len()).rev() {
@@ -84,6 +85,7 @@ pub(crate) fn univariate_degree_b_quotient<F: Field>(
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn polynomial_add<F: Field>(coeffs: &mut Vec<F>, weight: F, another_coeffs: &[F]) {
     if coeffs.len() < another_coeffs.len() {
         coeffs.resize(another_coeffs.len(), F::ZERO);
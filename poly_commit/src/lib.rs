@@ -6,6 +6,7 @@ pub use traits::{BatchOpeningPCS, PolynomialCommitmentScheme};
 pub const PCS_SOUNDNESS_BITS: usize = 128;
 
 mod utils;
+#[cfg(feature = "prover")]
 pub use utils::expander_pcs_init_testing_only;
 
 pub mod raw;
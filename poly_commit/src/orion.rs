@@ -10,12 +10,17 @@ pub use linear_code::{OrionCodeParameter, ORION_CODE_PARAMETER_INSTANCE};
 #[cfg(test)]
 mod linear_code_tests;
 
+#[cfg(feature = "prover")]
 mod simd_field_impl;
+#[cfg(feature = "prover")]
 pub use simd_field_impl::{orion_commit_simd_field, orion_open_simd_field};
 
+#[cfg(feature = "prover")]
 mod mpi_utils;
 
+#[cfg(feature = "prover")]
 mod simd_field_mpi_impl;
+#[cfg(feature = "prover")]
 pub use simd_field_mpi_impl::{orion_mpi_commit_simd_field, orion_mpi_open_simd_field};
 
 mod verify;
@@ -1,22 +1,28 @@
 use arith::{Field, SimdField};
+#[cfg(feature = "prover")]
+use gkr_engine::MPIEngine;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, PolynomialCommitmentType, ProofShape,
     StructuredReferenceString, Transcript,
 };
+#[cfg(feature = "prover")]
 use polynomials::MultilinearExtension;
 
+#[cfg(feature = "prover")]
 use crate::{
     orion::{
         simd_field_impl::{orion_commit_simd_field, orion_open_simd_field},
         simd_field_mpi_impl::{orion_mpi_commit_simd_field, orion_mpi_open_simd_field},
-        verify::orion_verify,
-        OrionCommitment, OrionProof, OrionSIMDFieldPCS, OrionSRS, OrionScratchPad,
         ORION_CODE_PARAMETER_INSTANCE,
     },
-    utils::{
-        lift_expander_challenge_to_n_vars, lift_poly_and_expander_challenge_to_n_vars,
-        lift_poly_to_n_vars,
+    utils::{lift_poly_and_expander_challenge_to_n_vars, lift_poly_to_n_vars},
+};
+use crate::{
+    orion::{
+        verify::orion_verify, OrionCommitment, OrionProof, OrionSIMDFieldPCS, OrionSRS,
+        OrionScratchPad,
     },
+    utils::lift_expander_challenge_to_n_vars,
 };
 
 use super::utils::orion_eval_shape;
@@ -52,6 +58,7 @@ where
         scaled_num_local_vars - C::SimdCircuitField::PACK_SIZE.ilog2() as usize
     }
 
+    #[cfg(feature = "prover")]
     fn gen_srs(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -70,10 +77,12 @@ where
         srs
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {
         Self::ScratchPad::default()
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -113,6 +122,7 @@ where
         .ok()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -163,6 +173,7 @@ where
 
     fn verify(
         params: &Self::Params,
+        proof_shape: &ProofShape,
         verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         eval_point: &ExpanderSingleVarChallenge<C>,
@@ -171,10 +182,15 @@ where
                                            * interactive arguments */
         opening: &Self::Opening,
     ) -> bool {
+        if eval_point.r_mpi.len() != proof_shape.num_mpi_vars() {
+            return false;
+        }
+
         if eval_point.num_vars() < *params {
             let eval_point = lift_expander_challenge_to_n_vars(eval_point, *params);
             return <Self as ExpanderPCS<C>>::verify(
                 params,
+                proof_shape,
                 verifying_key,
                 commitment,
                 &eval_point,
@@ -2,14 +2,17 @@ use std::marker::PhantomData;
 
 use arith::{ExtensionField, Field, SimdField};
 use gkr_engine::{StructuredReferenceString, Transcript};
-use polynomials::{MultiLinearPoly, MultilinearExtension, RefMultiLinearPoly};
-
+use polynomials::MultiLinearPoly;
+#[cfg(feature = "prover")]
+use polynomials::{MultilinearExtension, RefMultiLinearPoly};
+
+#[cfg(feature = "prover")]
+use crate::orion::{
+    simd_field_impl::{orion_commit_simd_field, orion_open_simd_field},
+    ORION_CODE_PARAMETER_INSTANCE,
+};
 use crate::{
-    orion::{
-        simd_field_impl::{orion_commit_simd_field, orion_open_simd_field},
-        verify::orion_verify,
-        OrionCommitment, OrionProof, OrionSRS, OrionScratchPad, ORION_CODE_PARAMETER_INSTANCE,
-    },
+    orion::{verify::orion_verify, OrionCommitment, OrionProof, OrionSRS, OrionScratchPad},
     PolynomialCommitmentScheme,
 };
 
@@ -23,6 +26,7 @@ impl StructuredReferenceString for OrionSRS {
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 fn pack_from_base<F, PackF>(es: &[F]) -> Vec<PackF>
 where
     F: Field,
@@ -64,6 +68,7 @@ where
     type Commitment = OrionCommitment;
     type Opening = OrionProof<EvalF>;
 
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
         OrionSRS::from_random(
             1,
@@ -75,10 +80,12 @@ where
         )
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {
         OrionScratchPad::default()
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         pk: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -94,6 +101,7 @@ where
         orion_commit_simd_field::<_, OpenPackF, ComPackF>(pk, &simd_poly, scratch_pad).unwrap()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         pk: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -166,6 +174,7 @@ where
     // NOTE: here we say the number of variables is the sum of 2 following things:
     // - number of variables of the multilinear polynomial
     // - number of variables reside in the SIMD field - e.g., 3 vars for a SIMD 8 field
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl rand::RngCore) -> (Self::SRS, usize) {
         OrionSRS::from_random(
             1,
@@ -177,10 +186,12 @@ where
         )
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {
         OrionScratchPad::default()
     }
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -195,6 +206,7 @@ where
         orion_commit_simd_field::<_, SimdF, ComPackF>(proving_key, poly, scratch_pad).unwrap()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
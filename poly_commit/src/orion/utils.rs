@@ -1,15 +1,19 @@
 use arith::{ExtensionField, Field, SimdField};
+#[cfg(feature = "prover")]
 use gkr_engine::Transcript;
 use itertools::izip;
 use serdes::{ExpSerde, SerdeError};
 use thiserror::Error;
-use transpose::{transpose, transpose_inplace};
+use transpose::transpose;
+#[cfg(feature = "prover")]
+use transpose::transpose_inplace;
 use tree::{Node, LEAF_BYTES};
 
+#[cfg(feature = "prover")]
+use crate::PCS_SOUNDNESS_BITS;
 use crate::{
     orion::linear_code::{OrionCode, OrionCodeParameter, ORION_CODE_PARAMETER_INSTANCE},
     traits::TensorCodeIOPPCS,
-    PCS_SOUNDNESS_BITS,
 };
 
 /*
@@ -202,6 +206,7 @@ pub struct OrionScratchPad {
 // Used by PCS opening to access VRAM-resident tree without modifying OrionScratchPad's serialization.
 use std::sync::Mutex;
 use std::collections::HashMap;
+#[cfg(feature = "prover")]
 pub static GPU_TREE_REGISTRY: std::sync::LazyLock<Mutex<HashMap<[u8; 32], (i32, u32)>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 
@@ -226,6 +231,7 @@ pub struct OrionProof<EvalF: Field> {
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn commit_encoded<PackF>(
     pk: &OrionSRS,
     packed_evals: &[PackF],
@@ -368,6 +374,7 @@ where
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn orion_mt_openings<T>(
     pk: &OrionSRS,
     transcript: &mut T,
@@ -475,6 +482,7 @@ impl<F: Field> SubsetSumLUTs<F> {
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub(crate) fn lut_open_linear_combine<F, EvalF, SimdF>(
     com_pack_size: usize,
     packed_evals: &[SimdF],
@@ -697,6 +705,7 @@ fn gpu_server_command(cmd: &str) {
     eprintln!("  [gpu-lc] {}: {}", cmd, line.trim());
 }
 
+#[cfg(feature = "prover")]
 pub(crate) fn simd_open_linear_combine<F, EvalF, SimdF>(
     com_pack_size: usize,
     packed_evals: &[SimdF],
@@ -1,11 +1,16 @@
 /// Raw commitment for multi-linear polynomials
 use arith::{ExtensionField, Field};
 use ethnum::U256;
+#[cfg(feature = "prover")]
+use gkr_engine::MPIEngine;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, PolynomialCommitmentType,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, PolynomialCommitmentType, ProofShape,
     StructuredReferenceString, Transcript,
 };
-use polynomials::{MultiLinearPoly, MultilinearExtension};
+use polynomials::MultiLinearPoly;
+#[cfg(feature = "prover")]
+use polynomials::MultilinearExtension;
+#[cfg(feature = "prover")]
 use rand::RngCore;
 use serdes::{ExpSerde, SerdeResult};
 
@@ -67,12 +72,15 @@ impl<F: ExtensionField> PolynomialCommitmentScheme<F> for RawMultiLinearPCS {
 
     type Opening = ();
 
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, _rng: impl RngCore) -> (Self::SRS, usize) {
         (Self::SRS::default(), *params)
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         _proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -85,6 +93,7 @@ impl<F: ExtensionField> PolynomialCommitmentScheme<F> for RawMultiLinearPCS {
         }
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         _proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -138,6 +147,7 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
 
     type BatchOpening = ();
 
+    #[cfg(feature = "prover")]
     fn gen_srs(
         _params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
@@ -149,8 +159,10 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         n_input_vars
     }
 
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(_params: &Self::Params, _mpi_engine: &impl MPIEngine) -> Self::ScratchPad {}
 
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         mpi_engine: &impl MPIEngine,
@@ -182,6 +194,7 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         Self::Commitment { evals: buffer }.into()
     }
 
+    #[cfg(feature = "prover")]
     fn open(
         _params: &Self::Params,
         _mpi_engine: &impl MPIEngine,
@@ -195,7 +208,8 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
     }
 
     fn verify(
-        _params: &Self::Params,
+        params: &Self::Params,
+        proof_shape: &ProofShape,
         _verifying_key: &<Self::SRS as StructuredReferenceString>::VKey,
         commitment: &Self::Commitment,
         challenge: &ExpanderSingleVarChallenge<C>,
@@ -203,6 +217,15 @@ impl<C: FieldEngine> ExpanderPCS<C> for RawExpanderGKR<C> {
         _transcript: &mut impl Transcript,
         _opening: &Self::Opening,
     ) -> bool {
+        // the commitment comes from the proof, it holds the inputs of all the processes
+        if challenge.rz.len() != *params
+            || challenge.r_simd.len() != proof_shape.num_simd_vars()
+            || challenge.r_mpi.len() != proof_shape.num_mpi_vars()
+            || commitment.evals.len() != proof_shape.world_size << *params
+        {
+            return false;
+        }
+
         let v_target =
             C::single_core_eval_circuit_vals_at_expander_challenge(&commitment.evals, challenge);
         v == v_target
@@ -1,5 +1,6 @@
 use arith::{ExtensionField, Field};
 use gkr_engine::{StructuredReferenceString, Transcript};
+#[cfg(feature = "prover")]
 use rand::RngCore;
 use serdes::ExpSerde;
 use std::fmt::Debug;
@@ -24,12 +25,15 @@ pub trait PolynomialCommitmentScheme<F: ExtensionField> {
     /// Additionally, this method returns a calibrated number of variables for
     /// the polynomial, that the PCS might need to accept a length extended
     /// version of polynomial as input.
+    #[cfg(feature = "prover")]
     fn gen_srs_for_testing(params: &Self::Params, rng: impl RngCore) -> (Self::SRS, usize);
 
     /// Initialize the scratch pad.
+    #[cfg(feature = "prover")]
     fn init_scratch_pad(params: &Self::Params) -> Self::ScratchPad;
 
     /// Commit to a polynomial.
+    #[cfg(feature = "prover")]
     fn commit(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -38,6 +42,7 @@ pub trait PolynomialCommitmentScheme<F: ExtensionField> {
     ) -> Self::Commitment;
 
     /// Open the polynomial at a point.
+    #[cfg(feature = "prover")]
     fn open(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -75,6 +80,7 @@ where
 pub trait BatchOpeningPCS<F: ExtensionField>: PolynomialCommitmentScheme<F> + Sized {
     /// This trait is implemented for homomorphic polynomial commitment schemes such as Hyrax and
     /// KZG Open a set of polynomials at a single point.
+    #[cfg(feature = "prover")]
     fn single_point_batch_open(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -97,6 +103,7 @@ pub trait BatchOpeningPCS<F: ExtensionField>: PolynomialCommitmentScheme<F> + Si
 
     /// Open a set of polynomials at a multiple points.
     /// Requires the length of the polys to be the same as points.
+    #[cfg(feature = "prover")]
     fn multiple_points_batch_open(
         params: &Self::Params,
         proving_key: &<Self::SRS as StructuredReferenceString>::PKey,
@@ -1,11 +1,14 @@
 use arith::Field;
+#[cfg(feature = "prover")]
 use ark_std::test_rng;
-use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIEngine, StructuredReferenceString,
-};
+#[cfg(feature = "prover")]
+use gkr_engine::{ExpanderPCS, MPIEngine, StructuredReferenceString};
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine};
+#[cfg(feature = "prover")]
 use polynomials::{MultiLinearPoly, MultilinearExtension, MutableMultilinearExtension};
 
-/// Initialize PCS for testing without SRS caching (always regenerates SRS)
+/// Initialize PCS for testing, caching the SRS in a file per PCS, size and world size
+#[cfg(feature = "prover")]
 #[allow(clippy::type_complexity)]
 pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS<FieldConfig>>(
     n_input_vars: usize,
@@ -16,7 +19,12 @@ pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS
     <PCS::SRS as StructuredReferenceString>::VKey,
     PCS::ScratchPad,
 ) {
-    let srs_path = format!("/tmp/kzg_srs_{}.bin", n_input_vars);
+    let srs_path = format!(
+        "/tmp/{}_srs_{}_{}.bin",
+        PCS::NAME,
+        n_input_vars,
+        mpi_config.world_size()
+    );
     expander_pcs_init_with_srs_path::<FieldConfig, PCS>(n_input_vars, mpi_config, Some(&srs_path))
 }
 
@@ -28,6 +36,7 @@ pub fn expander_pcs_init_testing_only<FieldConfig: FieldEngine, PCS: ExpanderPCS
 /// * `srs_path` - Optional path to SRS cache file:
 ///   - `Some(path)`: If file exists, load SRS from it; otherwise generate and save to it
 ///   - `None`: Always regenerate SRS (no caching)
+#[cfg(feature = "prover")]
 #[allow(clippy::type_complexity)]
 pub fn expander_pcs_init_with_srs_path<FieldConfig: FieldEngine, PCS: ExpanderPCS<FieldConfig>>(
     n_input_vars: usize,
@@ -64,6 +73,7 @@ pub fn expander_pcs_init_with_srs_path<FieldConfig: FieldEngine, PCS: ExpanderPC
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub fn lift_poly_to_n_vars<F: Field>(
     poly: &impl MultilinearExtension<F>,
     n_vars: usize,
@@ -86,6 +96,7 @@ pub fn lift_expander_challenge_to_n_vars<F: FieldEngine>(
 }
 
 #[inline(always)]
+#[cfg(feature = "prover")]
 pub fn lift_poly_and_expander_challenge_to_n_vars<F: FieldEngine>(
     poly: &impl MultilinearExtension<F::SimdCircuitField>,
     expander_challenge: &ExpanderSingleVarChallenge<F>,
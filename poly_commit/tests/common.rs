@@ -1,7 +1,7 @@
 use arith::{ExtensionField, Field};
 use ark_std::test_rng;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, MPIConfig, MPIEngine, ProofShape,
     StructuredReferenceString, Transcript,
 };
 use poly_commit::{BatchOpeningPCS, PolynomialCommitmentScheme};
@@ -249,10 +249,12 @@ pub fn test_pcs_for_expander_gkr<C: FieldEngine, T: Transcript, P: ExpanderPCS<C
         if mpi_config.is_root() {
             // this will always pass for RawExpanderGKR, so make sure it is correct
             let v = C::single_core_eval_circuit_vals_at_expander_challenge(&coeffs_gathered, xx);
+            let proof_shape = ProofShape::new::<C>(mpi_config.world_size(), poly.num_vars(), 0);
 
             transcript.lock_proof();
             assert!(P::verify(
                 params,
+                &proof_shape,
                 &verification_key,
                 &commitment.clone().unwrap(),
                 xx,
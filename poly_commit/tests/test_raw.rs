@@ -2,11 +2,11 @@ mod common;
 
 use arith::{Field, Fr};
 use gkr_engine::{
-    BN254Config, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, M31x16Config, MPIConfig,
-    MPIEngine, Transcript,
+    BN254Config, ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GF2ExtConfig, M31x16Config,
+    MPIConfig, MPIEngine, ProofShape, Transcript,
 };
 use gkr_hashers::{Keccak256hasher, SHA256hasher};
-use poly_commit::raw::{RawExpanderGKR, RawMultiLinearPCS};
+use poly_commit::raw::{RawCommitment, RawExpanderGKR, RawMultiLinearPCS};
 use polynomials::{MultiLinearPoly, RefMultiLinearPoly};
 use rand::thread_rng;
 use transcript::BytesHashTranscript;
@@ -70,3 +70,65 @@ fn test_raw_gkr() {
     type TBN254 = BytesHashTranscript<Keccak256hasher>;
     test_raw_gkr_helper::<BN254Config, TBN254>(&mpi_config, &mut TBN254::new());
 }
+
+#[test]
+fn test_raw_gkr_proof_shape() {
+    type C = M31x16Config;
+    type T = BytesHashTranscript<Keccak256hasher>;
+    const WORLD_SIZE: usize = 2;
+    let params = 4;
+    let mut rng = thread_rng();
+
+    let commitment = RawCommitment {
+        evals: (0..WORLD_SIZE << params)
+            .map(|_| <C as FieldEngine>::SimdCircuitField::random_unsafe(&mut rng))
+            .collect(),
+    };
+    let proof_shape = ProofShape::new::<C>(WORLD_SIZE, params, 0);
+    let x = ExpanderSingleVarChallenge::<C> {
+        rz: (0..params)
+            .map(|_| <C as FieldEngine>::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        r_simd: (0..proof_shape.num_simd_vars())
+            .map(|_| <C as FieldEngine>::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+        r_mpi: (0..proof_shape.num_mpi_vars())
+            .map(|_| <C as FieldEngine>::ChallengeField::random_unsafe(&mut rng))
+            .collect(),
+    };
+    let v = C::single_core_eval_circuit_vals_at_expander_challenge(&commitment.evals, &x);
+
+    let verify = |proof_shape: &ProofShape,
+                  commitment: &RawCommitment<_>,
+                  x: &ExpanderSingleVarChallenge<C>| {
+        RawExpanderGKR::<C>::verify(
+            &params,
+            proof_shape,
+            &(),
+            commitment,
+            x,
+            v,
+            &mut T::new(),
+            &(),
+        )
+    };
+    assert!(verify(&proof_shape, &commitment, &x));
+
+    // the commitment of a single process, or with a missing input
+    assert!(!verify(
+        &ProofShape::new::<C>(1, params, 0),
+        &commitment,
+        &x
+    ));
+    let mut truncated = commitment.clone();
+    truncated.evals.pop();
+    assert!(!verify(&proof_shape, &truncated, &x));
+
+    // a challenge of another number of variables
+    let mut short_x = x.clone();
+    short_x.rz.pop();
+    assert!(!verify(&proof_shape, &commitment, &short_x));
+    let mut short_x = x;
+    short_x.r_mpi.pop();
+    assert!(!verify(&proof_shape, &commitment, &short_x));
+}
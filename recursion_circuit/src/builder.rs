@@ -0,0 +1,672 @@
+//! A builder of layered circuits from arithmetic over variables.
+//!
+//! Additions and multiplications by constants are free: a `Variable` is a sum of gate terms
+//! over the nodes of the circuit, and only becomes a node, i.e., a value of a layer, when it
+//! is the operand of a multiplication or asserted to be zero. Each node is placed at the layer
+//! right after its deepest operand, and the values read by later layers are relayed, so that
+//! the emitted circuit only has gates between consecutive layers.
+//!
+//! The same constraints are also emitted as a rank-1 constraint system, see `build_r1cs`.
+
+use std::collections::BTreeSet;
+
+use arith::Field;
+use circuit::{
+    Circuit, CircuitLayer, CoefType, GateAdd, GateConst, GateMul, GateUni, Witness,
+    UNI_GATE_TYPE_POW5,
+};
+use gkr_engine::FieldEngine;
+
+use crate::R1CS;
+
+pub type NodeId = usize;
+
+/// Past this number of products, the operands of a multiplication become nodes before being
+/// multiplied, which costs a layer but keeps the number of gates linear
+const MAX_EXPANDED_PRODUCTS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Term {
+    Linear(NodeId),
+    Mul(NodeId, NodeId),
+    Pow5(NodeId),
+}
+
+impl Term {
+    #[inline]
+    fn operands(self) -> impl Iterator<Item = NodeId> {
+        let (a, b) = match self {
+            Term::Linear(a) | Term::Pow5(a) => (a, None),
+            Term::Mul(a, b) => (a, Some(b)),
+        };
+        std::iter::once(a).chain(b)
+    }
+
+    #[inline]
+    fn linear_node(self) -> NodeId {
+        match self {
+            Term::Linear(a) => a,
+            _ => unreachable!("not a linear term"),
+        }
+    }
+}
+
+/// `constant + sum_i coef_i * term_i`, where a term is a node, the product of two nodes or the
+/// fifth power of a node. The value is assigned while building the circuit.
+#[derive(Debug, Clone)]
+pub struct Variable<F: Field> {
+    terms: Vec<(Term, F)>,
+    constant: F,
+    value: F,
+}
+
+impl<F: Field> Variable<F> {
+    #[inline]
+    pub fn constant(c: F) -> Self {
+        Self {
+            terms: vec![],
+            constant: c,
+            value: c,
+        }
+    }
+
+    #[inline]
+    pub fn value(&self) -> F {
+        self.value
+    }
+
+    #[inline]
+    pub fn is_constant(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    #[inline]
+    fn node(id: NodeId, value: F) -> Self {
+        Self {
+            terms: vec![(Term::Linear(id), F::ONE)],
+            constant: F::ZERO,
+            value,
+        }
+    }
+
+    #[inline]
+    fn is_linear(&self) -> bool {
+        self.terms
+            .iter()
+            .all(|(term, _)| matches!(term, Term::Linear(_)))
+    }
+
+    /// The node and the coefficient of `coef * node`
+    #[inline]
+    fn as_scaled_node(&self) -> Option<(NodeId, F)> {
+        match self.terms.as_slice() {
+            [(Term::Linear(id), coef)] if self.constant.is_zero() => Some((*id, *coef)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node<F: Field> {
+    /// The layer computing the node, 0 for the inputs
+    depth: usize,
+    terms: Vec<(Term, F)>,
+    constant: F,
+
+    /// The index of the node in the public inputs, if it is one
+    public_input: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBuilder<C: FieldEngine> {
+    nodes: Vec<Node<C::CircuitField>>,
+    inputs: Vec<NodeId>,
+    input_values: Vec<C::CircuitField>,
+    public_inputs: Vec<NodeId>,
+    public_input_values: Vec<C::CircuitField>,
+    outputs: Vec<NodeId>,
+    /// Number of assertions which do not hold for the assigned values
+    num_failed_assertions: usize,
+}
+
+impl<C: FieldEngine> Default for CircuitBuilder<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: FieldEngine> CircuitBuilder<C> {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            inputs: vec![],
+            input_values: vec![],
+            public_inputs: vec![],
+            public_input_values: vec![],
+            outputs: vec![],
+            num_failed_assertions: 0,
+        }
+    }
+
+    #[inline]
+    pub fn num_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    #[inline]
+    pub fn num_public_inputs(&self) -> usize {
+        self.public_inputs.len()
+    }
+
+    #[inline]
+    pub fn num_outputs(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// Whether all the assertions hold for the values assigned to the inputs
+    #[inline]
+    pub fn is_satisfied(&self) -> bool {
+        self.num_failed_assertions == 0
+    }
+
+    /// A new value of the input layer of the circuit, i.e., of the witness
+    pub fn input(&mut self, value: C::CircuitField) -> Variable<C::CircuitField> {
+        let id = self.push_node(0, vec![], C::CircuitField::ZERO);
+        self.inputs.push(id);
+        self.input_values.push(value);
+        Variable::node(id, value)
+    }
+
+    /// A new public input of the circuit, i.e., a value of the statement given to the verifier,
+    /// read by a constant gate of the first layer
+    pub fn public_input(&mut self, value: C::CircuitField) -> Variable<C::CircuitField> {
+        let id = self.push_node(1, vec![], C::CircuitField::ZERO);
+        self.nodes[id].public_input = Some(self.public_inputs.len());
+        self.public_inputs.push(id);
+        self.public_input_values.push(value);
+        Variable::node(id, value)
+    }
+
+    pub fn add(
+        &self,
+        a: &Variable<C::CircuitField>,
+        b: &Variable<C::CircuitField>,
+    ) -> Variable<C::CircuitField> {
+        Variable {
+            terms: [a.terms.as_slice(), b.terms.as_slice()].concat(),
+            constant: a.constant + b.constant,
+            value: a.value + b.value,
+        }
+    }
+
+    /// The sum of many variables, without copying the terms of the partial sums
+    pub fn sum<'a>(
+        &self,
+        vars: impl IntoIterator<Item = &'a Variable<C::CircuitField>>,
+    ) -> Variable<C::CircuitField> {
+        let mut res = Variable::constant(C::CircuitField::ZERO);
+        for v in vars {
+            res.terms.extend_from_slice(&v.terms);
+            res.constant += v.constant;
+            res.value += v.value;
+        }
+        res
+    }
+
+    pub fn sub(
+        &self,
+        a: &Variable<C::CircuitField>,
+        b: &Variable<C::CircuitField>,
+    ) -> Variable<C::CircuitField> {
+        self.add(a, &self.neg(b))
+    }
+
+    pub fn neg(&self, a: &Variable<C::CircuitField>) -> Variable<C::CircuitField> {
+        self.scale(a, -C::CircuitField::ONE)
+    }
+
+    pub fn add_constant(
+        &self,
+        a: &Variable<C::CircuitField>,
+        c: C::CircuitField,
+    ) -> Variable<C::CircuitField> {
+        self.add(a, &Variable::constant(c))
+    }
+
+    pub fn scale(
+        &self,
+        a: &Variable<C::CircuitField>,
+        c: C::CircuitField,
+    ) -> Variable<C::CircuitField> {
+        if c.is_zero() {
+            return Variable::constant(C::CircuitField::ZERO);
+        }
+
+        Variable {
+            terms: a
+                .terms
+                .iter()
+                .map(|(term, coef)| (*term, *coef * c))
+                .collect(),
+            constant: a.constant * c,
+            value: a.value * c,
+        }
+    }
+
+    pub fn mul(
+        &mut self,
+        a: &Variable<C::CircuitField>,
+        b: &Variable<C::CircuitField>,
+    ) -> Variable<C::CircuitField> {
+        if a.is_constant() {
+            return self.scale(b, a.constant);
+        }
+        if b.is_constant() {
+            return self.scale(a, b.constant);
+        }
+
+        let a = self.linearize(a, b.terms.len());
+        let b = self.linearize(b, a.terms.len());
+
+        // (a0 + sum_i a_i x_i) * (b0 + sum_j b_j y_j)
+        let mut terms = Vec::with_capacity((a.terms.len() + 1) * (b.terms.len() + 1));
+        for (x, a_i) in &a.terms {
+            for (y, b_j) in &b.terms {
+                terms.push((Term::Mul(x.linear_node(), y.linear_node()), *a_i * *b_j));
+            }
+        }
+        if !a.constant.is_zero() {
+            terms.extend(b.terms.iter().map(|(y, b_j)| (*y, *b_j * a.constant)));
+        }
+        if !b.constant.is_zero() {
+            terms.extend(a.terms.iter().map(|(x, a_i)| (*x, *a_i * b.constant)));
+        }
+
+        Variable {
+            terms,
+            constant: a.constant * b.constant,
+            value: a.value * b.value,
+        }
+    }
+
+    pub fn square(&mut self, a: &Variable<C::CircuitField>) -> Variable<C::CircuitField> {
+        self.mul(a, a)
+    }
+
+    /// x^5 with a single pow5 gate
+    pub fn pow5(&mut self, a: &Variable<C::CircuitField>) -> Variable<C::CircuitField> {
+        let value = a.value.exp(5);
+        if a.is_constant() {
+            return Variable::constant(value);
+        }
+
+        // (c * x)^5 = c^5 * x^5
+        let (id, coef) = match a.as_scaled_node() {
+            Some((id, coef)) => (id, coef.exp(5)),
+            None => (self.node_of(a), C::CircuitField::ONE),
+        };
+        Variable {
+            terms: vec![(Term::Pow5(id), coef)],
+            constant: C::CircuitField::ZERO,
+            value,
+        }
+    }
+
+    /// The variable as a single node, to be read by many gates without copying its terms
+    pub fn materialize(&mut self, a: &Variable<C::CircuitField>) -> Variable<C::CircuitField> {
+        if a.is_constant() {
+            return a.clone();
+        }
+        Variable::node(self.node_of(a), a.value)
+    }
+
+    /// Constrain the variable to be zero: it becomes an output of the circuit
+    pub fn assert_zero(&mut self, a: &Variable<C::CircuitField>) {
+        if !a.value.is_zero() {
+            self.num_failed_assertions += 1;
+        }
+        let id = self.node_of(a);
+        self.outputs.push(id);
+    }
+
+    pub fn assert_equal(&mut self, a: &Variable<C::CircuitField>, b: &Variable<C::CircuitField>) {
+        let diff = self.sub(a, b);
+        self.assert_zero(&diff);
+    }
+
+    /// Emit the layered circuit, whose outputs are the asserted values, and its witness
+    pub fn build(self) -> (Circuit<C>, Witness<C>) {
+        let outputs = self.sorted_outputs();
+        let reachable = self.reachable(&outputs);
+
+        let output_depth = outputs
+            .iter()
+            .map(|id| self.nodes[*id].depth)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        // a node is a value of the layers from its depth to the last layer reading it
+        let mut last_layer = vec![0; self.nodes.len()];
+        for id in (0..self.nodes.len()).filter(|id| reachable[*id]) {
+            let depth = self.nodes[id].depth;
+            for operand in self.operands(id) {
+                last_layer[operand] = last_layer[operand].max(depth - 1);
+            }
+        }
+        outputs.iter().for_each(|id| last_layer[*id] = output_depth);
+
+        let mut entering = vec![vec![]; output_depth + 1];
+        let mut leaving = vec![vec![]; output_depth + 1];
+        for id in (0..self.nodes.len()).filter(|id| reachable[*id]) {
+            entering[self.nodes[id].depth].push(id);
+            leaving[last_layer[id]].push(id);
+        }
+
+        // the input layer keeps all the inputs, in order
+        let mut prev_pos = vec![0; self.nodes.len()];
+        let mut cur_pos = vec![0; self.nodes.len()];
+        self.inputs
+            .iter()
+            .enumerate()
+            .for_each(|(i, id)| prev_pos[*id] = i);
+        let input_var_num = log_size(self.inputs.len());
+
+        let mut active = entering[0].iter().copied().collect::<BTreeSet<_>>();
+        let mut prev_var_num = input_var_num;
+        let mut layers = Vec::with_capacity(output_depth);
+        for layer_id in 1..=output_depth {
+            leaving[layer_id - 1].iter().for_each(|id| {
+                active.remove(id);
+            });
+            active.extend(&entering[layer_id]);
+
+            let var_num = log_size(active.len());
+            let mut layer = CircuitLayer::<C> {
+                input_var_num: prev_var_num,
+                output_var_num: var_num,
+                ..Default::default()
+            };
+            for (o_id, id) in active.iter().enumerate() {
+                cur_pos[*id] = o_id;
+                let node = &self.nodes[*id];
+                if node.depth == layer_id {
+                    push_gates(&mut layer, o_id, node, &prev_pos);
+                } else {
+                    // relay
+                    layer.add.push(GateAdd {
+                        i_ids: [prev_pos[*id]],
+                        o_id,
+                        coef_type: CoefType::Constant,
+                        coef: C::CircuitField::ONE,
+                        gate_type: 0,
+                    });
+                }
+            }
+
+            layers.push(layer);
+            prev_var_num = var_num;
+            std::mem::swap(&mut prev_pos, &mut cur_pos);
+        }
+
+        let mut circuit = Circuit {
+            layers,
+            expected_num_output_zeros: outputs.len(),
+            ..Default::default()
+        };
+        circuit.pre_process_gkr();
+
+        let input_size = 1 << input_var_num;
+        let mut values = self.input_values;
+        values.resize(input_size, C::CircuitField::ZERO);
+        values.extend_from_slice(&self.public_input_values);
+        let num_witnesses = C::get_field_pack_size();
+        let witness = Witness {
+            num_witnesses,
+            num_private_inputs_per_witness: input_size,
+            num_public_inputs_per_witness: self.public_inputs.len(),
+            values: values.repeat(num_witnesses),
+        };
+
+        (circuit, witness)
+    }
+
+    /// Emit the rank-1 constraint system of the assertions, and its assignment.
+    ///
+    /// Products and fifth powers get a variable each, and so do the nodes of more than one
+    /// term read by other nodes, the others are inlined in the constraints reading them.
+    pub fn build_r1cs(self) -> (R1CS<C::CircuitField>, Vec<C::CircuitField>) {
+        let outputs = self.sorted_outputs();
+        let reachable = self.reachable(&outputs);
+        let mut is_operand = vec![false; self.nodes.len()];
+        for id in (0..self.nodes.len()).filter(|id| reachable[*id]) {
+            self.operands(id)
+                .for_each(|operand| is_operand[operand] = true);
+        }
+
+        let mut r1cs = R1CS::new(self.public_inputs.len());
+        let mut assignment = vec![C::CircuitField::ONE];
+        assignment.extend_from_slice(&self.public_input_values);
+
+        let mut values = vec![C::CircuitField::ZERO; self.nodes.len()];
+        let mut lcs = vec![vec![]; self.nodes.len()];
+        for (i, id) in self.public_inputs.iter().enumerate() {
+            values[*id] = self.public_input_values[i];
+            lcs[*id] = vec![(1 + i, C::CircuitField::ONE)];
+        }
+        for (id, value) in self.inputs.iter().zip(&self.input_values) {
+            values[*id] = *value;
+            lcs[*id] = vec![(r1cs.new_variable(), C::CircuitField::ONE)];
+            assignment.push(*value);
+        }
+
+        // the nodes are created after their operands
+        for id in (0..self.nodes.len()).filter(|id| reachable[*id]) {
+            let node = &self.nodes[id];
+            if node.depth == 0 || node.public_input.is_some() {
+                continue;
+            }
+
+            let mut value = node.constant;
+            let mut lc = vec![];
+            if !node.constant.is_zero() {
+                lc.push((0, node.constant));
+            }
+            for (term, coef) in node.terms.iter().filter(|(_, coef)| !coef.is_zero()) {
+                let (var, term_value) = match *term {
+                    Term::Linear(a) => {
+                        lc.extend(lcs[a].iter().map(|(var, c)| (*var, *c * *coef)));
+                        value += values[a] * *coef;
+                        continue;
+                    }
+                    Term::Mul(a, b) => {
+                        let ab = values[a] * values[b];
+                        (
+                            product(&mut r1cs, &mut assignment, &lcs[a], &lcs[b], ab),
+                            ab,
+                        )
+                    }
+                    Term::Pow5(a) => {
+                        let a2 = values[a].square();
+                        let a4 = a2.square();
+                        let a5 = a4 * values[a];
+                        let x2 = product(&mut r1cs, &mut assignment, &lcs[a], &lcs[a], a2);
+                        let x2 = [(x2, C::CircuitField::ONE)];
+                        let x4 = product(&mut r1cs, &mut assignment, &x2, &x2, a4);
+                        let x4 = [(x4, C::CircuitField::ONE)];
+                        (product(&mut r1cs, &mut assignment, &x4, &lcs[a], a5), a5)
+                    }
+                };
+                lc.push((var, *coef));
+                value += term_value * *coef;
+            }
+
+            if lc.len() > 1 && is_operand[id] {
+                let var = r1cs.new_variable();
+                r1cs.enforce_equal(&lc, var);
+                assignment.push(value);
+                lc = vec![(var, C::CircuitField::ONE)];
+            }
+            values[id] = value;
+            lcs[id] = lc;
+        }
+
+        for id in outputs {
+            r1cs.enforce_zero(&lcs[id]);
+        }
+
+        (r1cs, assignment)
+    }
+
+    #[inline]
+    fn push_node(
+        &mut self,
+        depth: usize,
+        terms: Vec<(Term, C::CircuitField)>,
+        constant: C::CircuitField,
+    ) -> NodeId {
+        self.nodes.push(Node {
+            depth,
+            terms,
+            constant,
+            public_input: None,
+        });
+        self.nodes.len() - 1
+    }
+
+    #[inline]
+    fn sorted_outputs(&self) -> Vec<NodeId> {
+        let mut outputs = self.outputs.clone();
+        outputs.sort_unstable();
+        outputs.dedup();
+        outputs
+    }
+
+    /// Whether the outputs depend on the node, the other ones are not emitted
+    fn reachable(&self, outputs: &[NodeId]) -> Vec<bool> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut stack = outputs.to_vec();
+        while let Some(id) = stack.pop() {
+            if !reachable[id] {
+                reachable[id] = true;
+                stack.extend(self.operands(id));
+            }
+        }
+        reachable
+    }
+
+    #[inline]
+    fn operands(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes[id]
+            .terms
+            .iter()
+            .flat_map(|(term, _)| term.operands())
+    }
+
+    /// The node equal to the variable, a new one unless the variable already is a node
+    fn node_of(&mut self, a: &Variable<C::CircuitField>) -> NodeId {
+        if let Some((id, coef)) = a.as_scaled_node() {
+            if coef == C::CircuitField::ONE {
+                return id;
+            }
+        }
+
+        let depth = 1 + a
+            .terms
+            .iter()
+            .flat_map(|(term, _)| term.operands())
+            .map(|id| self.nodes[id].depth)
+            .max()
+            .unwrap_or(0);
+        self.push_node(depth, a.terms.clone(), a.constant)
+    }
+
+    /// A linear variable equal to `a`, to be multiplied by a variable of `other_len` terms
+    fn linearize(
+        &mut self,
+        a: &Variable<C::CircuitField>,
+        other_len: usize,
+    ) -> Variable<C::CircuitField> {
+        if !a.is_linear()
+            || (a.terms.len() > 1 && a.terms.len() * other_len > MAX_EXPANDED_PRODUCTS)
+        {
+            self.materialize(a)
+        } else {
+            a.clone()
+        }
+    }
+}
+
+/// Number of variables of a layer of `len` values, at least one
+#[inline]
+fn log_size(len: usize) -> usize {
+    len.max(2).next_power_of_two().trailing_zeros() as usize
+}
+
+/// A new variable of the system constrained to be `a * b`, assigned `value`
+#[inline]
+fn product<F: Field>(
+    r1cs: &mut R1CS<F>,
+    assignment: &mut Vec<F>,
+    a: &[(usize, F)],
+    b: &[(usize, F)],
+    value: F,
+) -> usize {
+    let var = r1cs.mul(a, b);
+    assignment.push(value);
+    var
+}
+
+fn push_gates<C: FieldEngine>(
+    layer: &mut CircuitLayer<C>,
+    o_id: usize,
+    node: &Node<C::CircuitField>,
+    prev_pos: &[usize],
+) {
+    for (term, coef) in node.terms.iter().filter(|(_, coef)| !coef.is_zero()) {
+        match *term {
+            Term::Linear(a) => layer.add.push(GateAdd {
+                i_ids: [prev_pos[a]],
+                o_id,
+                coef_type: CoefType::Constant,
+                coef: *coef,
+                gate_type: 0,
+            }),
+            Term::Mul(a, b) => layer.mul.push(GateMul {
+                i_ids: [prev_pos[a], prev_pos[b]],
+                o_id,
+                coef_type: CoefType::Constant,
+                coef: *coef,
+                gate_type: 0,
+            }),
+            Term::Pow5(a) => layer.uni.push(GateUni {
+                i_ids: [prev_pos[a]],
+                o_id,
+                coef_type: CoefType::Constant,
+                coef: *coef,
+                gate_type: UNI_GATE_TYPE_POW5,
+            }),
+        }
+    }
+
+    // the coefficient of a public input gate is the public input itself
+    if let Some(i) = node.public_input {
+        layer.const_.push(GateConst {
+            i_ids: [],
+            o_id,
+            coef_type: CoefType::PublicInput(i),
+            coef: C::CircuitField::ONE,
+            gate_type: 0,
+        });
+    }
+
+    if !node.constant.is_zero() {
+        layer.const_.push(GateConst {
+            i_ids: [],
+            o_id,
+            coef_type: CoefType::Constant,
+            coef: node.constant,
+            gate_type: 0,
+        });
+    }
+}
@@ -0,0 +1,40 @@
+//! Circuits verifying GKR proofs, so that Expander proves its own proofs, e.g., to aggregate
+//! them. A `RecursiveVerifier` emits the layered circuit running the checks of
+//! `gkr::Verifier::verify` on a proof of a given circuit: the sumcheck of every layer, the
+//! MiMC5 transcript and the opening of the raw commitment, while the opening of a KZG
+//! commitment is deferred to the verifier of the emitted circuit. The proof is the witness of
+//! the emitted circuit, whose outputs are all zero iff the proof is accepted.
+//!
+//! The same checks are emitted as a rank-1 constraint system, to wrap GKR proofs in a
+//! pairing based SNARK, see the `groth16` crate.
+//!
+//! Only a subset of the proofs verified by `gkr::Verifier` is supported, the others are
+//! rejected with a `RecursionError` before anything is emitted:
+//! - the proofs generated by a single process, i.e., a world size of 1;
+//! - of circuits whose outputs are all constrained to zero, see `OutputStatement::from_circuit`,
+//!   the claimed evaluation of the outputs is then zero;
+//! - of circuits without uni or monomial gates, whose layers are proven by the plain sumcheck, and
+//!   without lookups;
+//! - with a deferred opening, i.e., a commitment which is not raw, of circuits whose input layer is
+//!   only read by addition gates, i.e., a single claim on the input.
+//!
+//! The transcript must be MiMC5, which `RecursiveVerifier` requires of its `GKREngine`: there
+//! is no gadget for the Poseidon transcript, nor for the byte hashes, e.g., SHA256.
+
+mod builder;
+pub use builder::*;
+
+mod mimc;
+pub use mimc::*;
+
+mod r1cs;
+pub use r1cs::*;
+
+mod transcript_gadget;
+pub use transcript_gadget::*;
+
+mod verifier;
+pub use verifier::*;
+
+#[cfg(test)]
+mod tests;
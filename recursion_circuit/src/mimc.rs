@@ -0,0 +1,60 @@
+//! MiMC5 over the variables of the circuit, matching `MiMC5FiatShamirHasher`.
+
+use arith::Field;
+use gkr_engine::FieldEngine;
+use gkr_hashers::generate_mimc_constants;
+
+use crate::{CircuitBuilder, Variable};
+
+#[derive(Debug, Clone)]
+pub struct MiMC5Gadget<F: Field> {
+    constants: Vec<F>,
+}
+
+impl<F: Field> Default for MiMC5Gadget<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> MiMC5Gadget<F> {
+    pub fn new() -> Self {
+        Self {
+            constants: generate_mimc_constants::<F>(),
+        }
+    }
+
+    /// One round per constant: x = (x + h + c)^5, each round being a layer of the circuit
+    pub fn mimc5_hash<C: FieldEngine<CircuitField = F>>(
+        &self,
+        builder: &mut CircuitBuilder<C>,
+        h: &Variable<F>,
+        x: &Variable<F>,
+    ) -> Variable<F> {
+        // h is read by every round
+        let h = builder.materialize(h);
+        let mut x = x.clone();
+        for c in &self.constants {
+            let t = builder.add(&x, &h);
+            let t = builder.add_constant(&t, *c);
+            x = builder.pow5(&t);
+        }
+        builder.add(&x, &h)
+    }
+
+    /// The state after absorbing the inputs, each being a chunk of `F::SIZE` bytes of the
+    /// native hasher: h = h + mimc5(h, x) + x
+    pub fn hash_to_state<C: FieldEngine<CircuitField = F>>(
+        &self,
+        builder: &mut CircuitBuilder<C>,
+        inputs: &[Variable<F>],
+    ) -> Variable<F> {
+        let mut h = Variable::constant(F::ZERO);
+        for x in inputs {
+            let r = self.mimc5_hash(builder, &h, x);
+            let sum = builder.add(&h, &r);
+            h = builder.add(&sum, x);
+        }
+        h
+    }
+}
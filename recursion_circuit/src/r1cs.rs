@@ -0,0 +1,88 @@
+//! Rank-1 constraint systems, the arithmetization of pairing based SNARKs such as Groth16.
+
+use arith::Field;
+
+/// `sum_i coef_i * z_i`, as pairs `(i, coef_i)`, over the variables `z` of the system
+pub type LinearCombination<F> = Vec<(usize, F)>;
+
+/// `<a, z> * <b, z> = <c, z>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint<F: Field> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+}
+
+/// The variables are `z = (1, public inputs, private values)`, i.e., the public inputs are
+/// the variables `1..=num_public_inputs`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct R1CS<F: Field> {
+    pub num_public_inputs: usize,
+    pub num_variables: usize,
+    pub constraints: Vec<Constraint<F>>,
+}
+
+impl<F: Field> R1CS<F> {
+    pub fn new(num_public_inputs: usize) -> Self {
+        Self {
+            num_public_inputs,
+            num_variables: 1 + num_public_inputs,
+            constraints: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    #[inline]
+    pub fn new_variable(&mut self) -> usize {
+        self.num_variables += 1;
+        self.num_variables - 1
+    }
+
+    /// A new variable constrained to be `a * b`
+    pub fn mul(&mut self, a: &[(usize, F)], b: &[(usize, F)]) -> usize {
+        let var = self.new_variable();
+        self.constraints.push(Constraint {
+            a: a.to_vec(),
+            b: b.to_vec(),
+            c: vec![(var, F::ONE)],
+        });
+        var
+    }
+
+    /// `lc * 1 = var`
+    pub fn enforce_equal(&mut self, lc: &[(usize, F)], var: usize) {
+        self.constraints.push(Constraint {
+            a: lc.to_vec(),
+            b: vec![(0, F::ONE)],
+            c: vec![(var, F::ONE)],
+        });
+    }
+
+    /// `lc * 1 = 0`
+    pub fn enforce_zero(&mut self, lc: &[(usize, F)]) {
+        self.constraints.push(Constraint {
+            a: lc.to_vec(),
+            b: vec![(0, F::ONE)],
+            c: vec![],
+        });
+    }
+
+    pub fn is_satisfied(&self, assignment: &[F]) -> bool {
+        assignment.len() == self.num_variables
+            && assignment[0] == F::ONE
+            && self.constraints.iter().all(|constraint| {
+                eval_lc(&constraint.a, assignment) * eval_lc(&constraint.b, assignment)
+                    == eval_lc(&constraint.c, assignment)
+            })
+    }
+}
+
+#[inline]
+pub fn eval_lc<F: Field>(lc: &[(usize, F)], assignment: &[F]) -> F {
+    lc.iter()
+        .fold(F::ZERO, |acc, (var, coef)| acc + assignment[*var] * *coef)
+}
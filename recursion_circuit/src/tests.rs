@@ -0,0 +1,396 @@
+use arith::{Field, Fr};
+use ark_std::test_rng;
+use circuit::{Circuit, Witness};
+use gkr::{utils::bn254_fixture::build_circuit, BN254ConfigMIMC5KZG, BN254ConfigMIMC5Raw};
+use gkr_engine::{BN254Config, MPIConfig, Proof, Transcript};
+use gkr_hashers::{FiatShamirHasher, MiMC5FiatShamirHasher};
+use serdes::ExpSerde;
+
+use crate::*;
+
+/// The asserted values of the circuit, all zero iff the assertions hold
+fn evaluate_outputs(circuit: &Circuit<BN254Config>, witness: &Witness<BN254Config>) -> Vec<Fr> {
+    let mut circuit = circuit.clone();
+    circuit.prover_process_witness(witness.clone(), &MPIConfig::prover_new(None, None));
+    circuit.evaluate();
+    circuit.layers.last().unwrap().output_vals[..circuit.expected_num_output_zeros].to_vec()
+}
+
+// 3 * 5 + (3 - 1)^5 + 7 = c and (3 * 5)^2 * 5 = 1125, with c public
+fn build_arithmetic_constraints(c: u32) -> CircuitBuilder<BN254Config> {
+    let mut builder = CircuitBuilder::<BN254Config>::new();
+    let a = builder.input(Fr::from(3u32));
+    let b = builder.input(Fr::from(5u32));
+    let c = builder.public_input(Fr::from(c));
+
+    let ab = builder.mul(&a, &b);
+    let a_1 = builder.add_constant(&a, -Fr::ONE);
+    let a_1_5 = builder.pow5(&a_1);
+    let lhs = builder.sum([&ab, &a_1_5]);
+    let lhs = builder.add_constant(&lhs, Fr::from(7u32));
+    builder.assert_equal(&lhs, &c);
+
+    // b is relayed up to the layer of the last product
+    let ab_2 = builder.square(&ab);
+    let ab_2_b = builder.mul(&ab_2, &b);
+    builder.assert_equal(&ab_2_b, &Variable::constant(Fr::from(1125u32)));
+
+    builder
+}
+
+#[test]
+fn test_builder() {
+    let builder = build_arithmetic_constraints(54);
+    assert!(builder.is_satisfied());
+    let (circuit, witness) = builder.build();
+    assert_eq!(circuit.expected_num_output_zeros, 2);
+    assert_eq!(witness.num_public_inputs_per_witness, 1);
+    assert!(evaluate_outputs(&circuit, &witness)
+        .iter()
+        .all(|v| v.is_zero()));
+
+    let builder = build_arithmetic_constraints(55);
+    assert!(!builder.is_satisfied());
+    let (circuit, witness) = builder.build();
+    assert!(!evaluate_outputs(&circuit, &witness)
+        .iter()
+        .all(|v| v.is_zero()));
+}
+
+#[test]
+fn test_r1cs() {
+    let (r1cs, assignment) = build_arithmetic_constraints(54).build_r1cs();
+    assert_eq!(r1cs.num_public_inputs, 1);
+    assert_eq!(assignment[1], Fr::from(54u32));
+    assert!(r1cs.is_satisfied(&assignment));
+
+    let (r1cs, mut assignment) = build_arithmetic_constraints(55).build_r1cs();
+    assert!(!r1cs.is_satisfied(&assignment));
+
+    // the constraints do not depend on the assigned values
+    let (expected, _) = build_arithmetic_constraints(54).build_r1cs();
+    assert_eq!(r1cs, expected);
+    assignment[1] = Fr::from(54u32);
+    assert!(r1cs.is_satisfied(&assignment));
+}
+
+#[test]
+fn test_mimc_gadget() {
+    let mut rng = test_rng();
+    let inputs = (0..3)
+        .map(|_| Fr::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let mut bytes = vec![];
+    inputs
+        .iter()
+        .for_each(|x| x.serialize_into(&mut bytes).unwrap());
+    let mut expected = [0u8; 32];
+    MiMC5FiatShamirHasher::<Fr>::new().hash(&mut expected, &bytes);
+
+    let mut builder = CircuitBuilder::<BN254Config>::new();
+    let vars = inputs.iter().map(|x| builder.input(*x)).collect::<Vec<_>>();
+    let h = MiMC5Gadget::new().hash_to_state(&mut builder, &vars);
+    assert_eq!(h.value(), Fr::from_uniform_bytes(&expected));
+
+    let expected = builder.input(h.value());
+    builder.assert_equal(&h, &expected);
+    let (circuit, witness) = builder.build();
+    assert!(evaluate_outputs(&circuit, &witness)
+        .iter()
+        .all(|v| v.is_zero()));
+}
+
+#[test]
+fn test_transcript_gadget() {
+    let mut rng = test_rng();
+    let mut native = MiMC5Transcript::new();
+    let mut builder = CircuitBuilder::<BN254Config>::new();
+    let mut transcript = TranscriptGadget::new(Fr::from_uniform_bytes(&native.digest));
+
+    let mut append = |native: &mut MiMC5Transcript,
+                      transcript: &mut TranscriptGadget<Fr>,
+                      builder: &mut CircuitBuilder<BN254Config>,
+                      n: usize| {
+        (0..n)
+            .map(|_| {
+                let x = Fr::random_unsafe(&mut rng);
+                native.append_field_element(&x);
+                let x = builder.input(x);
+                transcript.append_field_element(&x);
+                x
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut challenges = vec![];
+    for n in [0, 1, 3, 2] {
+        append(&mut native, &mut transcript, &mut builder, n);
+        challenges.extend(transcript.generate_field_elements(&mut builder, 2));
+        let expected = native.generate_field_elements::<Fr>(2);
+        assert_eq!(challenges[challenges.len() - 2].value(), expected[0]);
+        assert_eq!(challenges[challenges.len() - 1].value(), expected[1]);
+    }
+
+    // the chunks of a raw commitment, its length followed by its evaluations
+    let mut commitment = vec![Variable::constant(Fr::from(2u32))];
+    let evals = (0..2)
+        .map(|_| Fr::random_unsafe(&mut rng))
+        .collect::<Vec<_>>();
+    let mut bytes = vec![];
+    ethnum::U256::from(2u64).serialize_into(&mut bytes).unwrap();
+    evals
+        .iter()
+        .for_each(|x| x.serialize_into(&mut bytes).unwrap());
+    commitment.extend(evals.iter().map(|x| builder.input(*x)));
+    native.append_commitment(&bytes);
+    transcript.append_commitment(&mut builder, &commitment);
+    let challenge = transcript.generate_field_element(&mut builder);
+    assert_eq!(challenge.value(), native.generate_field_element::<Fr>());
+    challenges.push(challenge);
+
+    for challenge in &challenges {
+        let expected = builder.input(challenge.value());
+        builder.assert_equal(challenge, &expected);
+    }
+    let (circuit, witness) = builder.build();
+    assert!(evaluate_outputs(&circuit, &witness)
+        .iter()
+        .all(|v| v.is_zero()));
+}
+
+#[test]
+fn test_unsupported_proofs() {
+    let circuit = build_circuit();
+    let proof = Proof::default();
+
+    let verifier = RecursiveVerifier::<BN254ConfigMIMC5KZG>::new(circuit.proof_shape(1));
+    assert!(matches!(
+        verifier.build(&circuit, &[], &Fr::ZERO, &proof),
+        Err(RecursionError::MalformedProof(_))
+    ));
+
+    let verifier = RecursiveVerifier::<BN254ConfigMIMC5Raw>::new(circuit.proof_shape(2));
+    assert_eq!(
+        verifier
+            .build(&circuit, &[], &Fr::ZERO, &proof)
+            .unwrap_err(),
+        RecursionError::UnsupportedWorldSize(2)
+    );
+
+    let verifier = RecursiveVerifier::<BN254ConfigMIMC5Raw>::new(circuit.proof_shape(1));
+    assert!(matches!(
+        verifier.build(&circuit, &[], &Fr::ZERO, &proof),
+        Err(RecursionError::MalformedProof(_))
+    ));
+
+    // the claimed evaluation of outputs which are not constrained to zero is not checked
+    let mut with_outputs = circuit.clone();
+    with_outputs.expected_num_output_zeros = 1;
+    assert!(matches!(
+        verifier.build(&with_outputs, &[], &Fr::ZERO, &proof),
+        Err(RecursionError::UnsupportedCircuit(_))
+    ));
+}
+
+// without the recursion feature, the emitted circuits hash the commitment a thousand times,
+// they are checked but only proven by Expander with the feature
+mod end_to_end {
+    use arith::{Field, Fr};
+    use ark_std::test_rng;
+    use circuit::Circuit;
+    use gkr::{
+        utils::{
+            bn254_fixture::{build_circuit, prove, random_witness, verify, LOG_INPUT_SIZE},
+            dev_env_data_setup, KECCAK_BN254_CIRCUIT, KECCAK_BN254_WITNESS,
+        },
+        BN254ConfigMIMC5KZG, BN254ConfigMIMC5Raw, Prover, Verifier,
+    };
+    use gkr_engine::{BN254Config, GKREngine, MPIConfig};
+    use poly_commit::expander_pcs_init_testing_only;
+    use serdes::ExpSerde;
+
+    use super::evaluate_outputs;
+    use crate::{recursive_public_input, CircuitBuilder, RecursiveVerifier};
+
+    #[test]
+    fn test_recursive_verifier() {
+        let circuit = build_circuit();
+        let (witness, public_input) = random_witness(test_rng());
+        let (claimed_v, proof) = prove::<BN254ConfigMIMC5Raw>(&circuit, &witness);
+        assert!(verify::<BN254ConfigMIMC5Raw>(
+            &circuit, &witness, &claimed_v, &proof
+        ));
+
+        let verifier = RecursiveVerifier::<BN254ConfigMIMC5Raw>::new(circuit.proof_shape(1));
+        let recursive = verifier
+            .build(&circuit, &public_input, &claimed_v, &proof)
+            .unwrap();
+        assert!(recursive.accepted);
+        assert!(recursive.deferred_opening.is_none());
+        assert!(evaluate_outputs(&recursive.circuit, &recursive.witness)
+            .iter()
+            .all(|v| v.is_zero()));
+
+        // the same checks as a rank-1 constraint system, whose public inputs are the statement
+        let mut builder = CircuitBuilder::new();
+        verifier
+            .synthesize(&mut builder, &circuit, &public_input, &claimed_v, &proof)
+            .unwrap();
+        let (r1cs, assignment) = builder.build_r1cs();
+        assert!(r1cs.is_satisfied(&assignment));
+        assert_eq!(
+            assignment[1..=r1cs.num_public_inputs],
+            recursive_public_input(&public_input, &claimed_v, None)
+        );
+
+        // Expander proves the verification of its own proof
+        #[cfg(feature = "recursion")]
+        {
+            let (recursive_v, recursive_proof) =
+                prove::<BN254ConfigMIMC5Raw>(&recursive.circuit, &recursive.witness);
+            assert!(recursive_v.is_zero());
+            assert!(verify::<BN254ConfigMIMC5Raw>(
+                &recursive.circuit,
+                &recursive.witness,
+                &recursive_v,
+                &recursive_proof
+            ));
+        }
+
+        // the proof ends with the claim on the input layer, the raw openings being empty
+        let mut tampered = proof.clone();
+        let claim_start = tampered.bytes.len() - 32;
+        let claim = Fr::deserialize_from(&tampered.bytes[claim_start..]).unwrap();
+        let mut bytes = vec![];
+        (claim + Fr::ONE).serialize_into(&mut bytes).unwrap();
+        tampered.bytes[claim_start..].copy_from_slice(&bytes);
+        assert!(!verify::<BN254ConfigMIMC5Raw>(
+            &circuit, &witness, &claimed_v, &tampered
+        ));
+
+        let recursive = verifier
+            .build(&circuit, &public_input, &claimed_v, &tampered)
+            .unwrap();
+        assert!(!recursive.accepted);
+        assert!(!evaluate_outputs(&recursive.circuit, &recursive.witness)
+            .iter()
+            .all(|v| v.is_zero()));
+    }
+
+    #[test]
+    fn test_recursive_verifier_deferred_opening() {
+        let circuit = build_circuit();
+        let (witness, public_input) = random_witness(test_rng());
+        let (claimed_v, proof) = prove::<BN254ConfigMIMC5KZG>(&circuit, &witness);
+        assert!(verify::<BN254ConfigMIMC5KZG>(
+            &circuit, &witness, &claimed_v, &proof
+        ));
+
+        let verifier = RecursiveVerifier::<BN254ConfigMIMC5KZG>::new(circuit.proof_shape(1));
+        let recursive = verifier
+            .build(&circuit, &public_input, &claimed_v, &proof)
+            .unwrap();
+        assert!(recursive.accepted);
+        assert!(evaluate_outputs(&recursive.circuit, &recursive.witness)
+            .iter()
+            .all(|v| v.is_zero()));
+
+        // the opening is checked out of the circuit, from the state of the transcript
+        let deferred_opening = recursive.deferred_opening.unwrap();
+        assert_eq!(deferred_opening.point.len(), LOG_INPUT_SIZE);
+        let (pcs_params, _, pcs_verification_key, _) =
+            expander_pcs_init_testing_only::<
+                BN254Config,
+                <BN254ConfigMIMC5KZG as GKREngine>::PCSConfig,
+            >(LOG_INPUT_SIZE, &MPIConfig::verifier_new(1));
+        assert!(verifier.verify_deferred_opening(
+            &deferred_opening,
+            &pcs_params,
+            &pcs_verification_key
+        ));
+
+        let mut tampered = deferred_opening.clone();
+        tampered.value += Fr::ONE;
+        assert!(!verifier.verify_deferred_opening(&tampered, &pcs_params, &pcs_verification_key));
+        let mut tampered = deferred_opening;
+        tampered.transcript_digest += Fr::ONE;
+        assert!(!verifier.verify_deferred_opening(&tampered, &pcs_params, &pcs_verification_key));
+    }
+
+    #[test]
+    fn test_recursive_verifier_unsatisfied() {
+        // the outputs are not zero, the proof is valid but proves a false statement
+        let circuit = build_circuit();
+        let (mut witness, public_input) = random_witness(test_rng());
+        witness.values[3] += Fr::ONE;
+        let (claimed_v, proof) = prove::<BN254ConfigMIMC5Raw>(&circuit, &witness);
+        assert!(!claimed_v.is_zero());
+        assert!(!verify::<BN254ConfigMIMC5Raw>(
+            &circuit, &witness, &claimed_v, &proof
+        ));
+
+        let verifier = RecursiveVerifier::<BN254ConfigMIMC5Raw>::new(circuit.proof_shape(1));
+        let recursive = verifier
+            .build(&circuit, &public_input, &claimed_v, &proof)
+            .unwrap();
+        assert!(!recursive.accepted);
+        assert!(!evaluate_outputs(&recursive.circuit, &recursive.witness)
+            .iter()
+            .all(|v| v.is_zero()));
+
+        // nor can the prover claim the evaluation of the expected outputs
+        let recursive = verifier
+            .build(&circuit, &public_input, &Fr::ZERO, &proof)
+            .unwrap();
+        assert!(!recursive.accepted);
+    }
+
+    // the keccak circuit is far too large for the recursive verifier to be proven in a test,
+    // run with `cargo test -p recursion_circuit --release --features recursion -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_recursive_verifier_keccak() {
+        dev_env_data_setup();
+        let mpi_config = MPIConfig::prover_new(None, None);
+        let mut circuit = Circuit::<BN254Config>::single_thread_prover_load_circuit::<
+            BN254ConfigMIMC5Raw,
+        >(&("../".to_owned() + KECCAK_BN254_CIRCUIT));
+        circuit.load_witness_allow_padding_testing_only(
+            &("../".to_owned() + KECCAK_BN254_WITNESS),
+            &mpi_config,
+        );
+
+        let mut prover = Prover::<BN254ConfigMIMC5Raw>::new(mpi_config.clone());
+        prover.prepare_mem(&circuit);
+        let (pcs_params, pcs_proving_key, pcs_verification_key, mut pcs_scratch) =
+            expander_pcs_init_testing_only::<
+                BN254Config,
+                <BN254ConfigMIMC5Raw as GKREngine>::PCSConfig,
+            >(circuit.log_input_size(), &mpi_config);
+        let (claimed_v, proof) = prover.prove(
+            &mut circuit,
+            &pcs_params,
+            &pcs_proving_key,
+            &mut pcs_scratch,
+        );
+
+        let public_input = circuit.public_input.clone();
+        let proof_shape = circuit.proof_shape(1);
+        assert!(Verifier::<BN254ConfigMIMC5Raw>::new(proof_shape).verify(
+            &mut circuit,
+            &public_input,
+            &claimed_v,
+            &pcs_params,
+            &pcs_verification_key,
+            &proof,
+        ));
+
+        let recursive = RecursiveVerifier::<BN254ConfigMIMC5Raw>::new(proof_shape)
+            .build(&circuit, &public_input, &claimed_v, &proof)
+            .unwrap();
+        assert!(recursive.accepted);
+        assert!(evaluate_outputs(&recursive.circuit, &recursive.witness)
+            .iter()
+            .all(|v| v.is_zero()));
+    }
+}
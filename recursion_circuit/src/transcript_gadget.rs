@@ -0,0 +1,133 @@
+//! The Fiat-Shamir transcript over the variables of the circuit, matching
+//! `BytesHashTranscript<MiMC5FiatShamirHasher>` when every appended item is a field element,
+//! i.e., a single chunk of the hash.
+
+use arith::Field;
+use gkr_engine::FieldEngine;
+#[cfg(not(feature = "recursion"))]
+use transcript::PCS_DIGEST_LOOP;
+
+use crate::{CircuitBuilder, MiMC5Gadget, Variable};
+
+#[derive(Debug, Clone)]
+pub struct TranscriptGadget<F: Field> {
+    hasher: MiMC5Gadget<F>,
+
+    /// The digest, as a field element
+    digest: Variable<F>,
+
+    /// The appended elements not hashed yet
+    pending: Vec<Variable<F>>,
+}
+
+impl<F: Field> TranscriptGadget<F> {
+    /// A transcript from a known state, e.g., of a new transcript
+    pub fn new(state: F) -> Self {
+        Self {
+            hasher: MiMC5Gadget::new(),
+            digest: Variable::constant(state),
+            pending: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn digest(&self) -> &Variable<F> {
+        &self.digest
+    }
+
+    /// The appended elements not hashed yet
+    #[inline]
+    pub fn pending(&self) -> &[Variable<F>] {
+        &self.pending
+    }
+
+    #[inline]
+    pub fn append_field_element(&mut self, f: &Variable<F>) {
+        self.pending.push(f.clone());
+    }
+
+    /// Set the digest, discarding the unhashed elements
+    #[inline]
+    pub fn set_state(&mut self, state: Variable<F>) {
+        self.digest = state;
+        self.pending.clear();
+    }
+
+    pub fn generate_field_element<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+    ) -> Variable<F> {
+        self.refresh_digest(builder);
+        self.digest.clone()
+    }
+
+    pub fn generate_field_elements<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+        n: usize,
+    ) -> Vec<Variable<F>> {
+        (0..n)
+            .map(|_| self.generate_field_element(builder))
+            .collect()
+    }
+
+    /// Append the chunks of a serialized commitment, the digest is then reset to the hash of
+    /// the commitment unless the proofs are generated with the recursion friendly transcript
+    #[cfg_attr(feature = "recursion", allow(unused_variables))]
+    pub fn append_commitment<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+        chunks: &[Variable<F>],
+    ) {
+        self.pending.extend_from_slice(chunks);
+
+        #[cfg(not(feature = "recursion"))]
+        {
+            // the digest is a single node, otherwise its terms pile up and every former
+            // digest is relayed to the last layer
+            let mut digest = self.hasher.hash_to_state(builder, chunks);
+            for _ in 0..PCS_DIGEST_LOOP {
+                let x = builder.materialize(&digest);
+                digest = self.hasher.hash_to_state(builder, &[x]);
+            }
+            let digest = builder.materialize(&digest);
+            self.set_state(digest);
+        }
+    }
+
+    /// Append constant chunks known to both parties, e.g., of the statement, and hash them at
+    /// once, see `transcript_absorb_statement`
+    pub fn absorb_statement<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+        chunks: &[F],
+    ) {
+        self.pending
+            .extend(chunks.iter().map(|chunk| Variable::constant(*chunk)));
+        if !self.pending.is_empty() {
+            self.refresh_digest(builder);
+        }
+    }
+
+    /// Append an element known to both parties, e.g., the claimed value, and hash it at once,
+    /// see `bind_output_claim`
+    pub fn absorb_field_element<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+        f: &Variable<F>,
+    ) {
+        self.pending.push(f.clone());
+        self.refresh_digest(builder);
+    }
+
+    /// Hash the digest with the pending elements, or the digest alone if there are none
+    fn refresh_digest<C: FieldEngine<CircuitField = F>>(
+        &mut self,
+        builder: &mut CircuitBuilder<C>,
+    ) {
+        let inputs = [std::slice::from_ref(&self.digest), self.pending.as_slice()].concat();
+        let digest = self.hasher.hash_to_state(builder, &inputs);
+        self.digest = builder.materialize(&digest);
+        self.pending.clear();
+    }
+}
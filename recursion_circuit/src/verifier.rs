@@ -0,0 +1,662 @@
+//! The GKR verifier of `gkr::Verifier` over the variables of the circuit.
+//!
+//! The statement, i.e., the public inputs and the claimed output, are public inputs of the
+//! emitted circuit, the proof is its witness. The outputs of the verified circuits are all
+//! constrained to zero, and so is the claimed output. The opening of a commitment which is not
+//! raw is deferred: the commitment, the claim on it and the state of the transcript are public
+//! inputs too, and the opening is checked natively by `RecursiveVerifier::verify_deferred_opening`.
+
+use std::{io::Cursor, marker::PhantomData};
+
+use arith::{Field, Fr};
+use circuit::{Circuit, CircuitLayer, CoefType, Gate, Witness};
+use ethnum::U256;
+use gkr::{statement_bytes, OutputStatement};
+use gkr_engine::{
+    BN254Config, ExpanderPCS, ExpanderSingleVarChallenge, GKREngine, PolynomialCommitmentType,
+    Proof, ProofShape, StructuredReferenceString, Transcript,
+};
+use gkr_hashers::MiMC5FiatShamirHasher;
+use serdes::ExpSerde;
+use sumcheck::needs_generalized_sumcheck;
+use thiserror::Error;
+use transcript::BytesHashTranscript;
+
+use crate::{CircuitBuilder, TranscriptGadget, Variable};
+
+pub type MiMC5Transcript = BytesHashTranscript<MiMC5FiatShamirHasher<Fr>>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum RecursionError {
+    #[error("unsupported circuit: {0}")]
+    UnsupportedCircuit(&'static str),
+
+    #[error("unsupported proof generated by {0} processes, expected a single process")]
+    UnsupportedWorldSize(usize),
+
+    #[error("the proof shape does not match the circuit")]
+    ProofShapeMismatch,
+
+    #[error("the public inputs of the proof do not match the statement")]
+    PublicInputMismatch,
+
+    #[error("malformed proof: {0}")]
+    MalformedProof(String),
+}
+
+/// The opening of the commitment left to the verifier of the emitted circuit
+#[derive(Debug, Clone, Default, PartialEq, ExpSerde)]
+pub struct DeferredOpening {
+    /// The serialized commitment
+    pub commitment: Vec<u8>,
+
+    /// The claim on the committed polynomial
+    pub point: Vec<Fr>,
+    pub value: Fr,
+
+    /// The state of the transcript before the opening, its digest and the elements appended
+    /// since the last hash
+    pub transcript_digest: Fr,
+    pub transcript_pending: Vec<Fr>,
+
+    /// The serialized opening, i.e., the remaining of the proof
+    pub opening: Vec<u8>,
+}
+
+impl DeferredOpening {
+    /// The public inputs binding the opening to the emitted circuit: the commitment as the
+    /// chunks of its hash, the claim and the state of the transcript
+    pub fn public_input(&self) -> Vec<Fr> {
+        let mut res = byte_chunks(&self.commitment);
+        res.extend_from_slice(&self.point);
+        res.push(self.value);
+        res.push(self.transcript_digest);
+        res.extend_from_slice(&self.transcript_pending);
+        res
+    }
+}
+
+/// The public inputs of the circuit emitted by `RecursiveVerifier` for a statement
+pub fn recursive_public_input(
+    public_input: &[Fr],
+    claimed_v: &Fr,
+    deferred_opening: Option<&DeferredOpening>,
+) -> Vec<Fr> {
+    let mut res = public_input.to_vec();
+    res.push(*claimed_v);
+    if let Some(deferred_opening) = deferred_opening {
+        res.extend(deferred_opening.public_input());
+    }
+    res
+}
+
+/// A layered circuit verifying a GKR proof, and its witness assigned from the proof.
+/// The outputs of the circuit are all zero iff the proof is accepted, but for the opening of
+/// the commitment if it is deferred.
+#[derive(Debug)]
+pub struct RecursiveVerifierCircuit {
+    pub circuit: Circuit<BN254Config>,
+    pub witness: Witness<BN254Config>,
+
+    /// Whether `gkr::Verifier::verify` accepts the proof, but for the deferred opening
+    pub accepted: bool,
+
+    pub deferred_opening: Option<DeferredOpening>,
+}
+
+/// Emits the circuits verifying the proofs of `Cfg`, i.e., BN254 proofs with the MiMC5
+/// transcript generated by a single process. The raw commitment is opened in the circuit, the
+/// opening of any other commitment, e.g., KZG, is deferred.
+///
+/// The proofs must be generated without the `grinding` feature of `gkr`, and with its
+/// `recursion` feature iff this crate is built with its `recursion` feature. Without it, the
+/// commitment is hashed a thousand times, i.e., the emitted circuit is about 110k layers deep.
+pub struct RecursiveVerifier<Cfg: GKREngine> {
+    pub proof_shape: ProofShape,
+    phantom: PhantomData<Cfg>,
+}
+
+impl<Cfg> RecursiveVerifier<Cfg>
+where
+    Cfg: GKREngine<FieldConfig = BN254Config, TranscriptConfig = MiMC5Transcript>,
+{
+    pub fn new(proof_shape: ProofShape) -> Self {
+        Self {
+            proof_shape,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The circuit running the checks of `gkr::Verifier::verify` on the proof
+    pub fn build(
+        &self,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        claimed_v: &Fr,
+        proof: &Proof,
+    ) -> Result<RecursiveVerifierCircuit, RecursionError> {
+        let mut builder = CircuitBuilder::<BN254Config>::new();
+        let deferred_opening =
+            self.synthesize(&mut builder, circuit, public_input, claimed_v, proof)?;
+
+        let accepted = builder.is_satisfied();
+        let (circuit, witness) = builder.build();
+        Ok(RecursiveVerifierCircuit {
+            circuit,
+            witness,
+            accepted,
+            deferred_opening,
+        })
+    }
+
+    /// Add the checks of `gkr::Verifier::verify` on the proof to the builder, whose public
+    /// inputs are then `recursive_public_input`. The constraints only depend on the circuit and
+    /// the shape of the proof, not on the values of the proof.
+    pub fn synthesize(
+        &self,
+        builder: &mut CircuitBuilder<BN254Config>,
+        circuit: &Circuit<BN254Config>,
+        public_input: &[Fr],
+        claimed_v: &Fr,
+        proof: &Proof,
+    ) -> Result<Option<DeferredOpening>, RecursionError> {
+        self.check_supported(circuit)?;
+
+        let mut reader = ProofReader::new(&proof.bytes);
+
+        let mut transcript =
+            TranscriptGadget::new(Fr::from_uniform_bytes(&MiMC5Transcript::new().digest));
+
+        let public_input = public_input
+            .iter()
+            .map(|v| builder.public_input(*v))
+            .collect::<Vec<_>>();
+        // the evaluation of the outputs, which are all zero, see `check_supported`
+        let claimed_v = builder.public_input(*claimed_v);
+        builder.assert_zero(&claimed_v);
+        for v in &public_input {
+            if reader.read_fr()? != v.value() {
+                return Err(RecursionError::PublicInputMismatch);
+            }
+            transcript.append_field_element(v);
+        }
+
+        // the raw commitment is the input layer of the proven circuit, any other commitment is
+        // a public input
+        let is_raw =
+            <Cfg::PCSConfig as ExpanderPCS<BN254Config>>::PCS_TYPE == PolynomialCommitmentType::Raw;
+        let (raw_commitment, commitment_bytes) = if is_raw {
+            let commitment_len = reader.read_u256()?;
+            if commitment_len != U256::from(1u64 << circuit.log_input_size()) {
+                return Err(RecursionError::MalformedProof(format!(
+                    "commitment of length {commitment_len}"
+                )));
+            }
+            let commitment = (0..1usize << circuit.log_input_size())
+                .map(|_| reader.read_fr().map(|v| builder.input(v)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut commitment_chunks = vec![Variable::constant(Fr::from_u256(commitment_len))];
+            commitment_chunks.extend_from_slice(&commitment);
+            transcript.append_commitment(builder, &commitment_chunks);
+            (commitment, vec![])
+        } else {
+            let bytes = reader.read_commitment::<Cfg>()?;
+            let chunks = byte_chunks(&bytes)
+                .into_iter()
+                .map(|chunk| builder.public_input(chunk))
+                .collect::<Vec<_>>();
+            transcript.append_commitment(builder, &chunks);
+            (vec![], bytes)
+        };
+        let statement = statement_bytes::<Cfg>(circuit, self.proof_shape.world_size);
+        transcript.absorb_statement(builder, &byte_chunks(&statement));
+
+        let num_rnd_coefs = circuit
+            .layers
+            .iter()
+            .map(|layer| {
+                let is_random = |coef_type: &CoefType| *coef_type == CoefType::Random;
+                layer.mul.iter().filter(|g| is_random(&g.coef_type)).count()
+                    + layer.add.iter().filter(|g| is_random(&g.coef_type)).count()
+                    + layer
+                        .const_
+                        .iter()
+                        .filter(|g| is_random(&g.coef_type))
+                        .count()
+            })
+            .sum();
+        let mut rnd_coefs = transcript
+            .generate_field_elements(builder, num_rnd_coefs)
+            .into_iter();
+        let layer_coefs = circuit
+            .layers
+            .iter()
+            .map(|layer| LayerCoefs::new(layer, &public_input, &mut rnd_coefs))
+            .collect::<Vec<_>>();
+
+        // GKR, from the output layer down to the input layer
+        let output_var_num = circuit.layers.last().unwrap().output_var_num;
+        let rz = transcript.generate_field_elements(builder, output_var_num);
+        transcript.absorb_field_element(builder, &claimed_v);
+        let mut eq_rx = eq_table(builder, &rz);
+        let mut eq_ry: Option<Vec<Variable<Fr>>> = None;
+        let mut claimed_v0 = claimed_v;
+        let mut claimed_v1 = None;
+        let mut alpha = None;
+        let mut rx = vec![];
+        let mut ry = None;
+        for (layer, coefs) in circuit.layers.iter().zip(&layer_coefs).rev() {
+            let eq_rz = match (&alpha, &eq_ry) {
+                (Some(alpha), Some(eq_ry)) => eq_rx
+                    .iter()
+                    .zip(eq_ry)
+                    .map(|(x, y)| {
+                        let alpha_y = builder.mul(alpha, y);
+                        let sum = builder.add(x, &alpha_y);
+                        builder.materialize(&sum)
+                    })
+                    .collect(),
+                _ => eq_rx,
+            };
+
+            let mut sum = claimed_v0;
+            if let (Some(v1), Some(alpha)) = (&claimed_v1, &alpha) {
+                let alpha_v1 = builder.mul(v1, alpha);
+                sum = builder.add(&sum, &alpha_v1);
+            }
+            let cst = eval_cst(builder, &layer.const_, &coefs.const_, &eq_rz);
+            sum = builder.sub(&sum, &cst);
+
+            rx = (0..layer.input_var_num)
+                .map(|_| {
+                    verify_sumcheck_step(
+                        builder,
+                        &mut reader,
+                        &mut transcript,
+                        &mut sum,
+                        Cfg::COMPRESSED_SUMCHECK,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            eq_rx = eq_table(builder, &rx);
+
+            let vx = builder.input(reader.read_fr()?);
+            let add = eval_add(builder, &layer.add, &coefs.add, &eq_rz, &eq_rx);
+            let vx_add = builder.mul(&vx, &add);
+            sum = builder.sub(&sum, &vx_add);
+            transcript.append_field_element(&vx);
+
+            if !layer.structure_info.skip_sumcheck_phase_two {
+                let r = (0..layer.input_var_num)
+                    .map(|_| {
+                        verify_sumcheck_step(
+                            builder,
+                            &mut reader,
+                            &mut transcript,
+                            &mut sum,
+                            Cfg::COMPRESSED_SUMCHECK,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let eq = eq_table(builder, &r);
+
+                let vy = builder.input(reader.read_fr()?);
+                transcript.append_field_element(&vy);
+                let mul = eval_mul(builder, &layer.mul, &coefs.mul, &eq_rz, &eq_rx, &eq);
+                let vxy = builder.mul(&vx, &vy);
+                let expected = builder.mul(&vxy, &mul);
+                builder.assert_equal(&sum, &expected);
+
+                ry = Some(r);
+                eq_ry = Some(eq);
+                claimed_v1 = Some(vy);
+            } else {
+                builder.assert_zero(&sum);
+
+                ry = None;
+                eq_ry = None;
+                claimed_v1 = None;
+            }
+            claimed_v0 = vx;
+
+            alpha = ry
+                .is_some()
+                .then(|| transcript.generate_field_element(builder));
+        }
+
+        if !is_raw {
+            // the input layer of a preprocessed circuit is a relay layer, i.e., the claim is
+            // only on rx, which the proof opens next
+            let mut expose = |v: &Variable<Fr>| {
+                let public = builder.public_input(v.value());
+                builder.assert_equal(v, &public);
+                v.value()
+            };
+            return Ok(Some(DeferredOpening {
+                commitment: commitment_bytes,
+                point: rx.iter().map(&mut expose).collect(),
+                value: expose(&claimed_v0),
+                transcript_digest: expose(transcript.digest()),
+                transcript_pending: transcript.pending().iter().map(&mut expose).collect(),
+                opening: reader.read_remaining(),
+            }));
+        }
+
+        // the raw commitment is opened by evaluating its MLE, the openings are empty
+        let v = mle_eval(builder, &raw_commitment, &rx);
+        builder.assert_equal(&claimed_v0, &v);
+        if let (Some(ry), Some(vy)) = (&ry, &claimed_v1) {
+            let v = mle_eval(builder, &raw_commitment, ry);
+            builder.assert_equal(vy, &v);
+        }
+
+        Ok(None)
+    }
+
+    /// Run the deferred opening of a proof, which `gkr::Verifier::verify` accepts iff the
+    /// circuit emitted for it is satisfied and the opening is accepted
+    pub fn verify_deferred_opening(
+        &self,
+        deferred_opening: &DeferredOpening,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<BN254Config>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<BN254Config>>::SRS as StructuredReferenceString>::VKey,
+    ) -> bool {
+        let (Ok(commitment), Ok(opening)) = (
+            <Cfg::PCSConfig as ExpanderPCS<BN254Config>>::Commitment::deserialize_from(
+                deferred_opening.commitment.as_slice(),
+            ),
+            <Cfg::PCSConfig as ExpanderPCS<BN254Config>>::Opening::deserialize_from(
+                deferred_opening.opening.as_slice(),
+            ),
+        ) else {
+            return false;
+        };
+
+        let mut transcript = MiMC5Transcript::new();
+        let mut digest = vec![];
+        deferred_opening
+            .transcript_digest
+            .serialize_into(&mut digest)
+            .unwrap();
+        transcript.set_state(&digest);
+        deferred_opening
+            .transcript_pending
+            .iter()
+            .for_each(|v| transcript.append_field_element(v));
+
+        let open_at =
+            ExpanderSingleVarChallenge::new(deferred_opening.point.clone(), vec![], vec![]);
+        transcript.lock_proof();
+        let verified = Cfg::PCSConfig::verify(
+            pcs_params,
+            &self.proof_shape,
+            pcs_verification_key,
+            &commitment,
+            &open_at,
+            deferred_opening.value,
+            &mut transcript,
+            &opening,
+        );
+        transcript.unlock_proof();
+        verified
+    }
+
+    /// Whether the proofs of the circuit are supported, see the crate documentation. The
+    /// outputs of a supported circuit are all constrained to zero, so is the claimed output.
+    pub fn check_supported(&self, circuit: &Circuit<BN254Config>) -> Result<(), RecursionError> {
+        if self.proof_shape.world_size != 1 {
+            return Err(RecursionError::UnsupportedWorldSize(
+                self.proof_shape.world_size,
+            ));
+        }
+        if circuit.proof_shape(self.proof_shape.world_size) != self.proof_shape {
+            return Err(RecursionError::ProofShapeMismatch);
+        }
+        if OutputStatement::from_circuit(circuit).is_none() {
+            return Err(RecursionError::UnsupportedCircuit(
+                "outputs which are not constrained to zero",
+            ));
+        }
+        if !circuit.lookups.is_empty() {
+            return Err(RecursionError::UnsupportedCircuit("lookups"));
+        }
+        if circuit.layers.iter().any(needs_generalized_sumcheck) {
+            return Err(RecursionError::UnsupportedCircuit(
+                "layers with uni or monomial gates",
+            ));
+        }
+        let pcs_type = <Cfg::PCSConfig as ExpanderPCS<BN254Config>>::PCS_TYPE;
+        if pcs_type != PolynomialCommitmentType::Raw
+            && !circuit.layers[0].structure_info.skip_sumcheck_phase_two
+        {
+            return Err(RecursionError::UnsupportedCircuit(
+                "two claims on the input layer with a deferred opening",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The chunks of serialized bytes, e.g., of a commitment, hashed by the transcript, see
+/// `MiMC5FiatShamirHasher`
+fn byte_chunks(bytes: &[u8]) -> Vec<Fr> {
+    bytes
+        .chunks(Fr::SIZE)
+        .map(|chunk| {
+            let mut chunk = chunk.to_vec();
+            chunk.resize(Fr::SIZE, 0);
+            Fr::from_uniform_bytes(&chunk)
+        })
+        .collect()
+}
+
+struct ProofReader<'a> {
+    cursor: Cursor<&'a Vec<u8>>,
+}
+
+impl<'a> ProofReader<'a> {
+    fn new(bytes: &'a Vec<u8>) -> Self {
+        Self {
+            cursor: Cursor::new(bytes),
+        }
+    }
+
+    fn read_fr(&mut self) -> Result<Fr, RecursionError> {
+        Fr::deserialize_from(&mut self.cursor)
+            .map_err(|e| RecursionError::MalformedProof(format!("{e:?}")))
+    }
+
+    fn read_u256(&mut self) -> Result<U256, RecursionError> {
+        U256::deserialize_from(&mut self.cursor)
+            .map_err(|e| RecursionError::MalformedProof(format!("{e:?}")))
+    }
+
+    /// The serialized commitment of the proof
+    fn read_commitment<Cfg: GKREngine<FieldConfig = BN254Config>>(
+        &mut self,
+    ) -> Result<Vec<u8>, RecursionError> {
+        let commitment =
+            <Cfg::PCSConfig as ExpanderPCS<BN254Config>>::Commitment::deserialize_from(
+                &mut self.cursor,
+            )
+            .map_err(|e| RecursionError::MalformedProof(format!("{e:?}")))?;
+        let mut bytes = vec![];
+        commitment.serialize_into(&mut bytes).unwrap();
+        Ok(bytes)
+    }
+
+    fn read_remaining(&mut self) -> Vec<u8> {
+        let start = self.cursor.position() as usize;
+        self.cursor.get_ref()[start..].to_vec()
+    }
+}
+
+/// The coefficients of the gates of a layer, either constants, public inputs or
+/// sampled from the transcript
+struct LayerCoefs {
+    mul: Vec<Variable<Fr>>,
+    add: Vec<Variable<Fr>>,
+    const_: Vec<Variable<Fr>>,
+}
+
+impl LayerCoefs {
+    /// Takes the random coefficients of the layer in the order of `Circuit::identify_rnd_coefs`
+    fn new(
+        layer: &CircuitLayer<BN254Config>,
+        public_input: &[Variable<Fr>],
+        rnd_coefs: &mut impl Iterator<Item = Variable<Fr>>,
+    ) -> Self {
+        Self {
+            mul: gate_coefs(&layer.mul, public_input, rnd_coefs),
+            add: gate_coefs(&layer.add, public_input, rnd_coefs),
+            const_: gate_coefs(&layer.const_, public_input, rnd_coefs),
+        }
+    }
+}
+
+fn gate_coefs<const INPUT_NUM: usize>(
+    gates: &[Gate<BN254Config, INPUT_NUM>],
+    public_input: &[Variable<Fr>],
+    rnd_coefs: &mut impl Iterator<Item = Variable<Fr>>,
+) -> Vec<Variable<Fr>> {
+    gates
+        .iter()
+        .map(|gate| match gate.coef_type {
+            CoefType::Constant => Variable::constant(gate.coef),
+            CoefType::Random => rnd_coefs.next().unwrap(),
+            CoefType::PublicInput(i) => public_input[i].clone(),
+        })
+        .collect()
+}
+
+/// eq(r, x) for all x of the hypercube, bit j of x matching r[j], see `build_eq_x_r`
+fn eq_table(builder: &mut CircuitBuilder<BN254Config>, r: &[Variable<Fr>]) -> Vec<Variable<Fr>> {
+    let mut eq = vec![Variable::constant(Fr::ONE)];
+    for r_i in r {
+        let cur = eq.len();
+        let mut next = vec![Variable::constant(Fr::ZERO); cur * 2];
+        for j in 0..cur {
+            let hi = builder.mul(&eq[j], r_i);
+            let lo = builder.sub(&eq[j], &hi);
+            next[j] = builder.materialize(&lo);
+            next[j + cur] = builder.materialize(&hi);
+        }
+        eq = next;
+    }
+    eq
+}
+
+/// The MLE of `evals` at `r`, folding the lowest bit of the index first
+fn mle_eval(
+    builder: &mut CircuitBuilder<BN254Config>,
+    evals: &[Variable<Fr>],
+    r: &[Variable<Fr>],
+) -> Variable<Fr> {
+    assert_eq!(evals.len(), 1 << r.len());
+
+    let mut folded = evals.to_vec();
+    for r_i in r {
+        folded = folded
+            .chunks(2)
+            .map(|pair| {
+                let diff = builder.sub(&pair[1], &pair[0]);
+                let t = builder.mul(&diff, r_i);
+                let v = builder.add(&pair[0], &t);
+                builder.materialize(&v)
+            })
+            .collect();
+    }
+    folded.swap_remove(0)
+}
+
+/// See `gkr::verify_sumcheck_step` for the degree 2 rounds, returns the challenge of the round
+fn verify_sumcheck_step(
+    builder: &mut CircuitBuilder<BN254Config>,
+    reader: &mut ProofReader,
+    transcript: &mut TranscriptGadget<Fr>,
+    claimed_sum: &mut Variable<Fr>,
+    compressed: bool,
+) -> Result<Variable<Fr>, RecursionError> {
+    let n_evals = if compressed { 2 } else { 3 };
+    let mut ps = vec![];
+    for _ in 0..n_evals {
+        let p = builder.input(reader.read_fr()?);
+        transcript.append_field_element(&p);
+        ps.push(p);
+    }
+    if compressed {
+        let p1 = builder.sub(claimed_sum, &ps[0]);
+        ps.insert(1, p1);
+    } else {
+        let sum = builder.add(&ps[0], &ps[1]);
+        builder.assert_equal(&sum, claimed_sum);
+    }
+
+    let r = transcript.generate_field_element(builder);
+
+    // c0 + c1 r + c2 r^2, with c2 = (p2 - 2 p1 + p0) / 2 and c1 = p1 - p0 - c2
+    let p0_p2 = builder.add(&ps[0], &ps[2]);
+    let p1_2 = builder.scale(&ps[1], Fr::from(2u32));
+    let c2 = builder.sub(&p0_p2, &p1_2);
+    let c2 = builder.scale(&c2, Fr::INV_2);
+    let c1 = builder.sub(&ps[1], &ps[0]);
+    let c1 = builder.sub(&c1, &c2);
+    let r2 = builder.square(&r);
+    let c1_r = builder.mul(&c1, &r);
+    let c2_r2 = builder.mul(&c2, &r2);
+    *claimed_sum = builder.sum([&ps[0], &c1_r, &c2_r2]);
+
+    Ok(r)
+}
+
+fn eval_cst(
+    builder: &mut CircuitBuilder<BN254Config>,
+    gates: &[Gate<BN254Config, 0>],
+    coefs: &[Variable<Fr>],
+    eq_rz: &[Variable<Fr>],
+) -> Variable<Fr> {
+    let terms = gates
+        .iter()
+        .zip(coefs)
+        .map(|(gate, coef)| builder.mul(&eq_rz[gate.o_id], coef))
+        .collect::<Vec<_>>();
+    builder.sum(&terms)
+}
+
+fn eval_add(
+    builder: &mut CircuitBuilder<BN254Config>,
+    gates: &[Gate<BN254Config, 1>],
+    coefs: &[Variable<Fr>],
+    eq_rz: &[Variable<Fr>],
+    eq_rx: &[Variable<Fr>],
+) -> Variable<Fr> {
+    let terms = gates
+        .iter()
+        .zip(coefs)
+        .map(|(gate, coef)| {
+            let eq = builder.mul(&eq_rz[gate.o_id], &eq_rx[gate.i_ids[0]]);
+            builder.mul(&eq, coef)
+        })
+        .collect::<Vec<_>>();
+    builder.sum(&terms)
+}
+
+fn eval_mul(
+    builder: &mut CircuitBuilder<BN254Config>,
+    gates: &[Gate<BN254Config, 2>],
+    coefs: &[Variable<Fr>],
+    eq_rz: &[Variable<Fr>],
+    eq_rx: &[Variable<Fr>],
+    eq_ry: &[Variable<Fr>],
+) -> Variable<Fr> {
+    let terms = gates
+        .iter()
+        .zip(coefs)
+        .map(|(gate, coef)| {
+            let eq = builder.mul(&eq_rx[gate.i_ids[0]], &eq_ry[gate.i_ids[1]]);
+            let eq = builder.mul(&eq_rz[gate.o_id], &eq);
+            builder.mul(&eq, coef)
+        })
+        .collect::<Vec<_>>();
+    builder.sum(&terms)
+}
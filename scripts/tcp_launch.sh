@@ -0,0 +1,45 @@
+#!/bin/bash
+# Runs a command on every host of a host list, with the environment of `TcpMPIConfig::from_env`.
+# The first host is the root, listening on the given port, the rank of a host is its line.
+#
+# usage: ./scripts/tcp_launch.sh <host_file> <root_port> <command>...
+# e.g.:  ./scripts/tcp_launch.sh hosts.txt 7000 ./expander-exec --tcp prove -c circuit.txt -w witness.txt -o proof.bin
+#
+# A host `localhost` runs the command locally, the others over ssh, in the same working directory.
+
+set -e
+
+if [ "$#" -lt 3 ]; then
+    echo "usage: $0 <host_file> <root_port> <command>..."
+    exit 1
+fi
+
+host_file=$1
+root_port=$2
+shift 2
+
+hosts=($(grep -v '^\s*#' "$host_file" | grep -v '^\s*$'))
+world_size=${#hosts[@]}
+root_addr="${hosts[0]}:$root_port"
+
+pids=()
+for rank in "${!hosts[@]}"; do
+    host=${hosts[$rank]}
+    vars=(
+        "EXPANDER_TCP_ROOT_ADDR=$root_addr"
+        "EXPANDER_TCP_WORLD_SIZE=$world_size"
+        "EXPANDER_TCP_WORLD_RANK=$rank"
+    )
+    if [ "$host" == "localhost" ]; then
+        env "${vars[@]}" "$@" &
+    else
+        ssh "$host" "cd $(printf '%q' "$PWD") && env ${vars[*]} $(printf '%q ' "$@")" &
+    fi
+    pids+=($!)
+done
+
+status=0
+for pid in "${pids[@]}"; do
+    wait "$pid" || status=1
+done
+exit $status
@@ -10,6 +10,9 @@ pub enum SerdeError {
 
     #[error("Invalid variant index: {0}")]
     InvalidVariantIndex(usize),
+
+    #[error("Unsupported format version: {0:#x}")]
+    UnsupportedVersion(usize),
 }
 
 pub type SerdeResult<T> = std::result::Result<T, SerdeError>;
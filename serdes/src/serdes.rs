@@ -12,6 +12,10 @@ use halo2curves::{
 
 use crate::{exp_serde_for_generic_slices, exp_serde_for_number, SerdeError, SerdeResult};
 
+/// The most elements reserved ahead of a deserialized length, which is not trusted: a length
+/// beyond the bytes of the reader fails on reading instead of allocating
+const MAX_RESERVED_LEN: usize = 1 << 20;
+
 /// Serde for Arithmetic types such as field and group operations
 pub trait ExpSerde: Sized {
     /// serialize self into bytes
@@ -86,7 +90,7 @@ impl<V: ExpSerde> ExpSerde for Vec<V> {
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
         let len = usize::deserialize_from(&mut reader)?;
-        let mut v = Vec::with_capacity(len);
+        let mut v = Vec::with_capacity(len.min(MAX_RESERVED_LEN));
         for _ in 0..len {
             v.push(V::deserialize_from(&mut reader)?);
         }
@@ -180,7 +184,7 @@ impl<K: ExpSerde + Eq + Hash, V: ExpSerde> ExpSerde for HashMap<K, V> {
 
     fn deserialize_from<R: Read>(mut reader: R) -> SerdeResult<Self> {
         let len = usize::deserialize_from(&mut reader)?;
-        let mut map = HashMap::with_capacity(len);
+        let mut map = HashMap::with_capacity(len.min(MAX_RESERVED_LEN));
         for _ in 0..len {
             let k = K::deserialize_from(&mut reader)?;
             let v = V::deserialize_from(&mut reader)?;
@@ -0,0 +1,707 @@
+//! Compiles the checks of `gkr::Verifier::verify` on the proofs of a circuit into a
+//! `VerifierProgram`, following the layers of the circuit from the output layer down to the
+//! input layer, as `recursion_circuit::RecursiveVerifier` does over the variables of a circuit.
+
+use std::{collections::HashMap, marker::PhantomData};
+
+use arith::{Field, Fr};
+use circuit::{Circuit, CircuitLayer, CoefType, Gate};
+use gkr::{statement_bytes, OutputStatement};
+use gkr_engine::{BN254Config, FiatShamirHashType, GKREngine, ProofShape, Transcript};
+use gkr_hashers::FiatShamirHasher;
+use halo2curves::bn256::Bn256;
+use poly_commit::{HyperUniKZGPCS, UniKZGVerifierParams};
+use serdes::ExpSerde;
+use sumcheck::needs_generalized_sumcheck;
+use thiserror::Error;
+use transcript::BytesHashTranscript;
+
+use crate::{Instruction, VerifierProgram};
+
+/// The size of a compressed point of G1
+pub const G1_COMPRESSED_SIZE: usize = 32;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SolidityVerifierError {
+    #[error("unsupported circuit: {0}")]
+    UnsupportedCircuit(&'static str),
+
+    #[error("unsupported proof generated by {0} processes, expected a single process")]
+    UnsupportedWorldSize(usize),
+
+    #[error("unsupported transcript hash {0:?}, expected MIMC5 or Keccak256")]
+    UnsupportedHash(FiatShamirHashType),
+
+    #[error("the proof shape does not match the circuit")]
+    ProofShapeMismatch,
+}
+
+/// Generates the verifiers of the proofs of `Cfg`, i.e., BN254 proofs with the MiMC5 or the
+/// Keccak256 transcript and the KZG commitment, generated by a single process.
+///
+/// The proofs must be generated without the `grinding` feature of `gkr`, and with its
+/// `recursion` feature iff this crate is built with its `recursion` feature.
+pub struct SolidityVerifierGenerator<Cfg: GKREngine> {
+    pub proof_shape: ProofShape,
+    phantom: PhantomData<Cfg>,
+}
+
+impl<Cfg, H> SolidityVerifierGenerator<Cfg>
+where
+    H: FiatShamirHasher,
+    Cfg: GKREngine<
+        FieldConfig = BN254Config,
+        TranscriptConfig = BytesHashTranscript<H>,
+        PCSConfig = HyperUniKZGPCS<Bn256>,
+    >,
+{
+    pub fn new(proof_shape: ProofShape) -> Self {
+        Self {
+            proof_shape,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The program running the checks of `gkr::Verifier::verify` on the proofs of the circuit
+    /// with `num_public_inputs` public inputs, against the verifying key of the commitment.
+    ///
+    /// Unlike `coeff_form_uni_hyperkzg_verify`, which leaves the result of its final pairing
+    /// check out, the program rejects the openings whose pairing check fails.
+    pub fn generate(
+        &self,
+        circuit: &Circuit<BN254Config>,
+        num_public_inputs: usize,
+        pcs_verification_key: &UniKZGVerifierParams<Bn256>,
+    ) -> Result<VerifierProgram, SolidityVerifierError> {
+        self.check_supported(circuit)?;
+
+        let transcript = Cfg::TranscriptConfig::new();
+
+        let mut builder = ProgramBuilder::default();
+        let public_input = (0..num_public_inputs)
+            .map(|index| builder.public_input(index))
+            .collect::<Vec<_>>();
+        // the evaluation of the outputs, which are all zero, see `check_supported`
+        let claimed_v = builder.constant(Fr::ZERO);
+
+        // the proof starts with the public inputs, then the commitment
+        let start = builder.offset;
+        for v in &public_input {
+            let p = builder.read_fr();
+            builder.assert_equal(p, *v);
+        }
+        builder.absorb(start, builder.offset - start);
+
+        let commitment_offset = builder.offset;
+        let commitment = builder.read_g1();
+        builder.absorb_commitment(commitment_offset, G1_COMPRESSED_SIZE);
+        builder.absorb_statement(statement_bytes::<Cfg>(circuit, self.proof_shape.world_size));
+
+        let num_rnd_coefs = circuit
+            .layers
+            .iter()
+            .map(|layer| {
+                let is_random = |coef_type: &CoefType| *coef_type == CoefType::Random;
+                layer.mul.iter().filter(|g| is_random(&g.coef_type)).count()
+                    + layer.add.iter().filter(|g| is_random(&g.coef_type)).count()
+                    + layer
+                        .const_
+                        .iter()
+                        .filter(|g| is_random(&g.coef_type))
+                        .count()
+            })
+            .sum();
+        let mut rnd_coefs = (0..num_rnd_coefs)
+            .map(|_| builder.challenge())
+            .collect::<Vec<_>>()
+            .into_iter();
+        let layer_coefs = circuit
+            .layers
+            .iter()
+            .map(|layer| LayerCoefs::new(&mut builder, layer, &public_input, &mut rnd_coefs))
+            .collect::<Vec<_>>();
+
+        // GKR, from the output layer down to the input layer
+        let output_var_num = circuit.layers.last().unwrap().output_var_num;
+        let rz = (0..output_var_num)
+            .map(|_| builder.challenge())
+            .collect::<Vec<_>>();
+        builder.absorb_fr(claimed_v);
+        let mut eq_rx = builder.eq_table(&rz);
+        let mut eq_ry: Option<Vec<usize>> = None;
+        let mut claimed_v0 = claimed_v;
+        let mut claimed_v1 = None;
+        let mut alpha = None;
+        let mut rx = vec![];
+        let mut ry = None;
+        for (layer, coefs) in circuit.layers.iter().zip(&layer_coefs).rev() {
+            let eq_rz = match (alpha, &eq_ry) {
+                (Some(alpha), Some(eq_ry)) => eq_rx
+                    .iter()
+                    .zip(eq_ry)
+                    .map(|(x, y)| {
+                        let alpha_y = builder.mul(alpha, *y);
+                        builder.add(*x, alpha_y)
+                    })
+                    .collect(),
+                _ => eq_rx,
+            };
+
+            let mut sum = claimed_v0;
+            if let (Some(v1), Some(alpha)) = (claimed_v1, alpha) {
+                let alpha_v1 = builder.mul(v1, alpha);
+                sum = builder.add(sum, alpha_v1);
+            }
+            let cst = builder.eval_cst(&layer.const_, &coefs.const_, &eq_rz);
+            sum = builder.sub(sum, cst);
+
+            rx = (0..layer.input_var_num)
+                .map(|_| builder.verify_sumcheck_step(&mut sum, Cfg::COMPRESSED_SUMCHECK))
+                .collect();
+            eq_rx = builder.eq_table(&rx);
+
+            let vx = builder.read_fr_absorbed();
+            let add = builder.eval_add(&layer.add, &coefs.add, &eq_rz, &eq_rx);
+            let vx_add = builder.mul(vx, add);
+            sum = builder.sub(sum, vx_add);
+
+            if !layer.structure_info.skip_sumcheck_phase_two {
+                let r = (0..layer.input_var_num)
+                    .map(|_| builder.verify_sumcheck_step(&mut sum, Cfg::COMPRESSED_SUMCHECK))
+                    .collect::<Vec<_>>();
+                let eq = builder.eq_table(&r);
+
+                let vy = builder.read_fr_absorbed();
+                let mul = builder.eval_mul(&layer.mul, &coefs.mul, &eq_rz, &eq_rx, &eq);
+                let vxy = builder.mul(vx, vy);
+                let expected = builder.mul(vxy, mul);
+                builder.assert_equal(sum, expected);
+
+                ry = Some(r);
+                eq_ry = Some(eq);
+                claimed_v1 = Some(vy);
+            } else {
+                let zero = builder.constant(Fr::ZERO);
+                builder.assert_equal(sum, zero);
+
+                ry = None;
+                eq_ry = None;
+                claimed_v1 = None;
+            }
+            claimed_v0 = vx;
+
+            alpha = ry.is_some().then(|| builder.challenge());
+        }
+
+        // the openings of the claims on the input layer, see `Verifier::post_gkr`
+        builder.verify_opening(commitment, &rx, claimed_v0);
+        if let (Some(ry), Some(vy)) = (&ry, claimed_v1) {
+            builder.verify_opening(commitment, ry, vy);
+        }
+
+        Ok(VerifierProgram {
+            hash_type: H::TYPE,
+            initial_digest: transcript.digest.clone(),
+            num_public_inputs,
+            proof_len: builder.offset,
+            num_registers: builder.num_registers,
+            num_points: builder.num_points,
+            tau_g2: pcs_verification_key.tau_g2,
+            instructions: builder.instructions,
+        })
+    }
+
+    fn check_supported(&self, circuit: &Circuit<BN254Config>) -> Result<(), SolidityVerifierError> {
+        if !matches!(
+            H::TYPE,
+            FiatShamirHashType::MIMC5 | FiatShamirHashType::Keccak256
+        ) {
+            return Err(SolidityVerifierError::UnsupportedHash(H::TYPE));
+        }
+        if self.proof_shape.world_size != 1 {
+            return Err(SolidityVerifierError::UnsupportedWorldSize(
+                self.proof_shape.world_size,
+            ));
+        }
+        if circuit.proof_shape(self.proof_shape.world_size) != self.proof_shape {
+            return Err(SolidityVerifierError::ProofShapeMismatch);
+        }
+        // the claimed evaluation of the outputs is hard-coded
+        if OutputStatement::from_circuit(circuit).is_none() {
+            return Err(SolidityVerifierError::UnsupportedCircuit(
+                "outputs which are not constrained to zero",
+            ));
+        }
+        if !circuit.lookups.is_empty() {
+            return Err(SolidityVerifierError::UnsupportedCircuit("lookups"));
+        }
+        if circuit.layers.iter().any(needs_generalized_sumcheck) {
+            return Err(SolidityVerifierError::UnsupportedCircuit(
+                "layers with uni or monomial gates",
+            ));
+        }
+        // a polynomial without variables is lifted before it is committed
+        if circuit.log_input_size() == 0 {
+            return Err(SolidityVerifierError::UnsupportedCircuit(
+                "an input layer without variables",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Appends the instructions of the program, allocating their registers and reading the proof
+/// from the start
+#[derive(Default)]
+struct ProgramBuilder {
+    instructions: Vec<Instruction>,
+    num_registers: usize,
+    num_points: usize,
+
+    /// The offset in the proof of the next element to read
+    offset: usize,
+
+    /// The registers of the constants, by serialized value
+    constants: HashMap<Vec<u8>, usize>,
+}
+
+impl ProgramBuilder {
+    fn register(&mut self) -> usize {
+        self.num_registers += 1;
+        self.num_registers - 1
+    }
+
+    fn point(&mut self) -> usize {
+        self.num_points += 1;
+        self.num_points - 1
+    }
+
+    fn public_input(&mut self, index: usize) -> usize {
+        let dst = self.register();
+        self.instructions
+            .push(Instruction::PublicInput { dst, index });
+        dst
+    }
+
+    fn constant(&mut self, value: Fr) -> usize {
+        let mut key = vec![];
+        value.serialize_into(&mut key).unwrap();
+        if let Some(register) = self.constants.get(&key) {
+            return *register;
+        }
+
+        let dst = self.register();
+        self.instructions.push(Instruction::Constant { dst, value });
+        self.constants.insert(key, dst);
+        dst
+    }
+
+    fn read_fr(&mut self) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::ReadFr {
+            dst,
+            offset: self.offset,
+        });
+        self.offset += Fr::SIZE;
+        dst
+    }
+
+    /// Read an element of the proof and append it to the transcript
+    fn read_fr_absorbed(&mut self) -> usize {
+        let offset = self.offset;
+        let dst = self.read_fr();
+        self.absorb(offset, Fr::SIZE);
+        dst
+    }
+
+    fn read_g1(&mut self) -> usize {
+        let dst = self.point();
+        self.instructions.push(Instruction::ReadG1 {
+            dst,
+            offset: self.offset,
+        });
+        self.offset += G1_COMPRESSED_SIZE;
+        dst
+    }
+
+    /// The serialized length of a vector of the proof
+    fn check_len(&mut self, len: usize) {
+        let mut bytes = vec![];
+        len.serialize_into(&mut bytes).unwrap();
+        let offset = self.offset;
+        self.offset += bytes.len();
+        self.instructions
+            .push(Instruction::CheckBytes { offset, bytes });
+    }
+
+    fn add(&mut self, a: usize, b: usize) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::Add { dst, a, b });
+        dst
+    }
+
+    fn sub(&mut self, a: usize, b: usize) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::Sub { dst, a, b });
+        dst
+    }
+
+    fn mul(&mut self, a: usize, b: usize) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::Mul { dst, a, b });
+        dst
+    }
+
+    fn inverse(&mut self, src: usize) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::Inverse { dst, src });
+        dst
+    }
+
+    fn sum(&mut self, terms: &[usize]) -> usize {
+        match terms.split_first() {
+            Some((first, rest)) => rest.iter().fold(*first, |acc, t| self.add(acc, *t)),
+            None => self.constant(Fr::ZERO),
+        }
+    }
+
+    fn inner_product(&mut self, a: &[usize], b: &[usize]) -> usize {
+        let terms = a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| self.mul(*a, *b))
+            .collect::<Vec<_>>();
+        self.sum(&terms)
+    }
+
+    /// 1, x, ..., x^(n - 1)
+    fn powers(&mut self, x: usize, n: usize) -> Vec<usize> {
+        let mut powers = vec![self.constant(Fr::ONE)];
+        for i in 1..n {
+            let power = if i == 1 {
+                x
+            } else {
+                self.mul(powers[i - 1], x)
+            };
+            powers.push(power);
+        }
+        powers
+    }
+
+    fn assert_equal(&mut self, a: usize, b: usize) {
+        self.instructions.push(Instruction::AssertEqual { a, b });
+    }
+
+    fn absorb(&mut self, offset: usize, len: usize) {
+        if len > 0 {
+            self.instructions.push(Instruction::Absorb { offset, len });
+        }
+    }
+
+    fn absorb_commitment(&mut self, offset: usize, len: usize) {
+        #[cfg(not(feature = "recursion"))]
+        self.instructions.push(Instruction::AbsorbCommitment {
+            offset,
+            len,
+            rehashes: transcript::PCS_DIGEST_LOOP,
+        });
+
+        // the recursion friendly transcript appends the commitment as any other bytes
+        #[cfg(feature = "recursion")]
+        self.absorb(offset, len);
+    }
+
+    fn absorb_statement(&mut self, bytes: Vec<u8>) {
+        self.instructions
+            .push(Instruction::AbsorbStatement { bytes });
+    }
+
+    fn absorb_fr(&mut self, src: usize) {
+        self.instructions.push(Instruction::AbsorbFr { src });
+    }
+
+    fn challenge(&mut self) -> usize {
+        let dst = self.register();
+        self.instructions.push(Instruction::Challenge { dst });
+        dst
+    }
+
+    fn g1_generator(&mut self) -> usize {
+        let dst = self.point();
+        self.instructions.push(Instruction::G1Generator { dst });
+        dst
+    }
+
+    fn g1_lincomb(&mut self, terms: Vec<(usize, usize)>) -> usize {
+        let dst = self.point();
+        self.instructions
+            .push(Instruction::G1Lincomb { dst, terms });
+        dst
+    }
+
+    /// eq(r, x) for all x of the hypercube, bit j of x matching r[j], see `build_eq_x_r`
+    fn eq_table(&mut self, r: &[usize]) -> Vec<usize> {
+        let mut eq = vec![self.constant(Fr::ONE)];
+        for r_i in r {
+            let (lo, hi): (Vec<_>, Vec<_>) = eq
+                .iter()
+                .map(|e| {
+                    let hi = self.mul(*e, *r_i);
+                    (self.sub(*e, hi), hi)
+                })
+                .unzip();
+            eq = [lo, hi].concat();
+        }
+        eq
+    }
+
+    /// See `gkr::verify_sumcheck_step` for the degree 2 rounds, returns the challenge of the round
+    fn verify_sumcheck_step(&mut self, claimed_sum: &mut usize, compressed: bool) -> usize {
+        let n_evals = if compressed { 2 } else { 3 };
+        let start = self.offset;
+        let mut ps = (0..n_evals).map(|_| self.read_fr()).collect::<Vec<_>>();
+        self.absorb(start, self.offset - start);
+        if compressed {
+            let p1 = self.sub(*claimed_sum, ps[0]);
+            ps.insert(1, p1);
+        } else {
+            let sum = self.add(ps[0], ps[1]);
+            self.assert_equal(sum, *claimed_sum);
+        }
+
+        let r = self.challenge();
+
+        // c0 + c1 r + c2 r^2, with c2 = (p2 - 2 p1 + p0) / 2 and c1 = p1 - p0 - c2
+        let p0_p2 = self.add(ps[0], ps[2]);
+        let p1_2 = self.add(ps[1], ps[1]);
+        let c2 = self.sub(p0_p2, p1_2);
+        let inv_2 = self.constant(Fr::INV_2);
+        let c2 = self.mul(c2, inv_2);
+        let c1 = self.sub(ps[1], ps[0]);
+        let c1 = self.sub(c1, c2);
+        let r2 = self.mul(r, r);
+        let c1_r = self.mul(c1, r);
+        let c2_r2 = self.mul(c2, r2);
+        *claimed_sum = self.sum(&[ps[0], c1_r, c2_r2]);
+
+        r
+    }
+
+    fn eval_cst(
+        &mut self,
+        gates: &[Gate<BN254Config, 0>],
+        coefs: &[usize],
+        eq_rz: &[usize],
+    ) -> usize {
+        let terms = gates
+            .iter()
+            .zip(coefs)
+            .map(|(gate, coef)| self.mul(eq_rz[gate.o_id], *coef))
+            .collect::<Vec<_>>();
+        self.sum(&terms)
+    }
+
+    fn eval_add(
+        &mut self,
+        gates: &[Gate<BN254Config, 1>],
+        coefs: &[usize],
+        eq_rz: &[usize],
+        eq_rx: &[usize],
+    ) -> usize {
+        let terms = gates
+            .iter()
+            .zip(coefs)
+            .map(|(gate, coef)| {
+                let eq = self.mul(eq_rz[gate.o_id], eq_rx[gate.i_ids[0]]);
+                self.mul(eq, *coef)
+            })
+            .collect::<Vec<_>>();
+        self.sum(&terms)
+    }
+
+    fn eval_mul(
+        &mut self,
+        gates: &[Gate<BN254Config, 2>],
+        coefs: &[usize],
+        eq_rz: &[usize],
+        eq_rx: &[usize],
+        eq_ry: &[usize],
+    ) -> usize {
+        let terms = gates
+            .iter()
+            .zip(coefs)
+            .map(|(gate, coef)| {
+                let eq = self.mul(eq_rx[gate.i_ids[0]], eq_ry[gate.i_ids[1]]);
+                let eq = self.mul(eq_rz[gate.o_id], eq);
+                self.mul(eq, *coef)
+            })
+            .collect::<Vec<_>>();
+        self.sum(&terms)
+    }
+
+    /// The degree 2 polynomial through `(roots[i], evals[i])` evaluated at `x`, see
+    /// `coeff_form_degree2_lagrange`
+    fn degree2_lagrange_eval(&mut self, roots: [usize; 3], evals: [usize; 3], x: usize) -> usize {
+        let terms = (0..3)
+            .map(|i| {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let xj = self.sub(x, roots[j]);
+                let xk = self.sub(x, roots[k]);
+                let nom = self.mul(xj, xk);
+                let ij = self.sub(roots[i], roots[j]);
+                let ik = self.sub(roots[i], roots[k]);
+                let denom = self.mul(ij, ik);
+                let denom_inv = self.inverse(denom);
+                let weight = self.mul(evals[i], denom_inv);
+                self.mul(nom, weight)
+            })
+            .collect::<Vec<_>>();
+        self.sum(&terms)
+    }
+
+    /// The HyperKZG opening of the commitment at `point`, see `coeff_form_uni_hyperkzg_verify`,
+    /// followed by the opening appended to the transcript, see
+    /// `Verifier::get_pcs_opening_from_proof_and_verify`
+    fn verify_opening(&mut self, commitment: usize, point: &[usize], value: usize) {
+        let n = point.len();
+        let opening_start = self.offset;
+
+        // the opening, see `HyperUniKZGOpening`
+        self.check_len(n - 1);
+        let folded = (0..n - 1)
+            .map(|_| (self.offset, self.read_g1()))
+            .collect::<Vec<_>>();
+        let beta_x2_offset = self.offset;
+        let beta_x2_eval = self.read_fr();
+        self.check_len(n);
+        let pos_beta_x = (0..n)
+            .map(|_| (self.offset, self.read_fr()))
+            .collect::<Vec<_>>();
+        self.check_len(n);
+        let neg_beta_x = (0..n)
+            .map(|_| (self.offset, self.read_fr()))
+            .collect::<Vec<_>>();
+        let beta_x_commitment_offset = self.offset;
+        let beta_x_commitment = self.read_g1();
+        let quotient_commitment = self.read_g1();
+        let opening_len = self.offset - opening_start;
+
+        // the proof is locked, i.e., the transcript is appended the opening in its own order
+        folded
+            .iter()
+            .for_each(|(offset, _)| self.absorb(*offset, G1_COMPRESSED_SIZE));
+        let beta = self.challenge();
+        let beta2 = self.mul(beta, beta);
+
+        // the evaluations at beta^2 of the folded polynomials, the last one being the value
+        let one = self.constant(Fr::ONE);
+        let inv_2 = self.constant(Fr::INV_2);
+        let beta_inv = self.inverse(beta);
+        let mut beta2_evals = vec![beta_x2_eval];
+        for (((_, pos), (_, neg)), alpha) in pos_beta_x.iter().zip(&neg_beta_x).zip(point) {
+            let even = self.add(*pos, *neg);
+            let one_alpha = self.sub(one, *alpha);
+            let even = self.mul(even, one_alpha);
+            let odd = self.sub(*pos, *neg);
+            let odd = self.mul(odd, beta_inv);
+            let odd = self.mul(odd, *alpha);
+            let eval = self.add(even, odd);
+            beta2_evals.push(self.mul(inv_2, eval));
+        }
+
+        self.absorb(beta_x2_offset, Fr::SIZE);
+        for ((pos, _), (neg, _)) in pos_beta_x.iter().zip(&neg_beta_x) {
+            self.absorb(*pos, Fr::SIZE);
+            self.absorb(*neg, Fr::SIZE);
+        }
+        self.assert_equal(beta2_evals[n], value);
+
+        let gamma = self.challenge();
+        let gamma_powers = self.powers(gamma, n);
+        let pos_beta_x = pos_beta_x.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        let neg_beta_x = neg_beta_x.iter().map(|(_, v)| *v).collect::<Vec<_>>();
+        let v_beta = self.inner_product(&pos_beta_x, &gamma_powers);
+        let v_neg_beta = self.inner_product(&neg_beta_x, &gamma_powers);
+        let v_beta2 = self.inner_product(&beta2_evals[..n], &gamma_powers);
+
+        self.absorb(beta_x_commitment_offset, G1_COMPRESSED_SIZE);
+        let tau = self.challenge();
+
+        let zero = self.constant(Fr::ZERO);
+        let neg_beta = self.sub(zero, beta);
+        let lagrange_eval =
+            self.degree2_lagrange_eval([beta, neg_beta, beta2], [v_beta, v_neg_beta, v_beta2], tau);
+        let tau_beta = self.sub(tau, beta);
+        let tau_beta2 = self.sub(tau, beta2);
+        let tau_neg_beta = self.add(tau, beta);
+        let q_weight = self.mul(tau_beta, tau_beta2);
+        let q_weight = self.mul(q_weight, tau_neg_beta);
+
+        // the KZG opening at tau of the aggregated commitment, minus the weighted commitment to
+        // the quotient by the vanishing polynomial of beta, -beta and beta^2, i.e.,
+        // e(quotient, tau G2) = e(aggregated - q_weight beta_x - eval G1 + tau quotient, G2)
+        let generator = self.g1_generator();
+        let neg_q_weight = self.sub(zero, q_weight);
+        let neg_eval = self.sub(zero, lagrange_eval);
+        let mut terms = vec![(commitment, one)];
+        terms.extend(
+            folded
+                .iter()
+                .zip(&gamma_powers[1..])
+                .map(|((_, f), gamma_i)| (*f, *gamma_i)),
+        );
+        terms.extend([
+            (beta_x_commitment, neg_q_weight),
+            (generator, neg_eval),
+            (quotient_commitment, tau),
+        ]);
+        let rhs = self.g1_lincomb(terms);
+        self.instructions.push(Instruction::PairingCheck {
+            lhs: quotient_commitment,
+            rhs,
+        });
+
+        self.instructions.push(Instruction::UnlockProof);
+        self.absorb(opening_start, opening_len);
+    }
+}
+
+/// The registers of the coefficients of the gates of a layer, either constants, public inputs
+/// or sampled from the transcript
+struct LayerCoefs {
+    mul: Vec<usize>,
+    add: Vec<usize>,
+    const_: Vec<usize>,
+}
+
+impl LayerCoefs {
+    /// Takes the random coefficients of the layer in the order of `Circuit::identify_rnd_coefs`
+    fn new(
+        builder: &mut ProgramBuilder,
+        layer: &CircuitLayer<BN254Config>,
+        public_input: &[usize],
+        rnd_coefs: &mut impl Iterator<Item = usize>,
+    ) -> Self {
+        Self {
+            mul: gate_coefs(builder, &layer.mul, public_input, rnd_coefs),
+            add: gate_coefs(builder, &layer.add, public_input, rnd_coefs),
+            const_: gate_coefs(builder, &layer.const_, public_input, rnd_coefs),
+        }
+    }
+}
+
+fn gate_coefs<const INPUT_NUM: usize>(
+    builder: &mut ProgramBuilder,
+    gates: &[Gate<BN254Config, INPUT_NUM>],
+    public_input: &[usize],
+    rnd_coefs: &mut impl Iterator<Item = usize>,
+) -> Vec<usize> {
+    gates
+        .iter()
+        .map(|gate| match gate.coef_type {
+            CoefType::Constant => builder.constant(gate.coef),
+            CoefType::Random => rnd_coefs.next().unwrap(),
+            CoefType::PublicInput(i) => public_input[i],
+        })
+        .collect()
+}
@@ -0,0 +1,284 @@
+//! The local execution of a `VerifierProgram`, one instruction at a time, to cross-check the
+//! generated contract without a chain.
+
+use arith::{Field, Fr};
+use gkr_engine::{FiatShamirHashType, Proof};
+use gkr_hashers::{FiatShamirHasher, Keccak256hasher, MiMC5FiatShamirHasher};
+use halo2curves::{
+    bn256::{Bn256, G1Affine, G2Affine, G2Prepared, G1},
+    group::{prime::PrimeCurveAffine, Curve, Group},
+    pairing::{MillerLoopResult, MultiMillerLoop},
+};
+use serdes::ExpSerde;
+use thiserror::Error;
+
+use crate::{Instruction, VerifierProgram, G1_COMPRESSED_SIZE};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Rejection {
+    #[error("expected {expected} public inputs, got {got}")]
+    PublicInputCount { expected: usize, got: usize },
+
+    #[error("expected a proof of {expected} bytes, got {got}")]
+    ProofLength { expected: usize, got: usize },
+
+    #[error("non canonical element at offset {0} of the proof")]
+    NonCanonicalElement(usize),
+
+    #[error("invalid point at offset {0} of the proof")]
+    InvalidPoint(usize),
+
+    #[error("unexpected bytes at offset {0} of the proof")]
+    UnexpectedBytes(usize),
+
+    #[error("assertion failed at instruction {0}")]
+    AssertionFailed(usize),
+
+    #[error("pairing check failed at instruction {0}")]
+    PairingCheckFailed(usize),
+
+    #[error("unsupported transcript hash {0:?}, expected MIMC5 or Keccak256")]
+    UnsupportedHash(FiatShamirHashType),
+}
+
+#[derive(Debug, Clone)]
+enum Hasher {
+    MiMC5(MiMC5FiatShamirHasher<Fr>),
+    Keccak256(Keccak256hasher),
+}
+
+impl Hasher {
+    fn hash(&self, input: &[u8]) -> Vec<u8> {
+        let mut output = vec![0u8; 32];
+        match self {
+            Self::MiMC5(hasher) => hasher.hash(&mut output, input),
+            Self::Keccak256(hasher) => hasher.hash(&mut output, input),
+        }
+        output
+    }
+}
+
+/// The transcript as the program sees it, i.e., the digest and the bytes appended since it was
+/// last refreshed, see `BytesHashTranscript`
+#[derive(Debug, Clone)]
+pub(crate) struct TranscriptState {
+    hasher: Hasher,
+    pub(crate) digest: Vec<u8>,
+    pending: Vec<u8>,
+}
+
+impl TranscriptState {
+    pub(crate) fn new(hash_type: FiatShamirHashType, digest: Vec<u8>) -> Result<Self, Rejection> {
+        let hasher = match hash_type {
+            FiatShamirHashType::MIMC5 => Hasher::MiMC5(MiMC5FiatShamirHasher::new()),
+            FiatShamirHashType::Keccak256 => Hasher::Keccak256(Keccak256hasher::new()),
+            _ => return Err(Rejection::UnsupportedHash(hash_type)),
+        };
+        Ok(Self {
+            hasher,
+            digest,
+            pending: vec![],
+        })
+    }
+
+    pub(crate) fn absorb(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    /// The digest is the hash of the digest and the pending bytes, or of the digest alone
+    pub(crate) fn refresh(&mut self) {
+        let mut input = std::mem::take(&mut self.digest);
+        input.append(&mut self.pending);
+        self.digest = self.hasher.hash(&input);
+    }
+
+    pub(crate) fn challenge(&mut self) -> Fr {
+        self.refresh();
+        Fr::from_uniform_bytes(&self.digest)
+    }
+
+    pub(crate) fn absorb_commitment(&mut self, commitment: &[u8], rehashes: usize) {
+        self.pending.clear();
+        self.digest = self.hasher.hash(commitment);
+        for _ in 0..rehashes {
+            self.digest = self.hasher.hash(&self.digest);
+        }
+    }
+
+    pub(crate) fn unlock_proof(&mut self) {
+        if !self.pending.is_empty() {
+            self.refresh();
+        }
+    }
+}
+
+/// Executes a program on a proof and the public inputs
+pub struct Interpreter<'a> {
+    program: &'a VerifierProgram,
+    public_input: &'a [Fr],
+    proof: &'a [u8],
+
+    registers: Vec<Fr>,
+    points: Vec<G1Affine>,
+    transcript: TranscriptState,
+
+    /// The index of the next instruction
+    pc: usize,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(
+        program: &'a VerifierProgram,
+        public_input: &'a [Fr],
+        proof: &'a [u8],
+    ) -> Result<Self, Rejection> {
+        if public_input.len() != program.num_public_inputs {
+            return Err(Rejection::PublicInputCount {
+                expected: program.num_public_inputs,
+                got: public_input.len(),
+            });
+        }
+        if proof.len() != program.proof_len {
+            return Err(Rejection::ProofLength {
+                expected: program.proof_len,
+                got: proof.len(),
+            });
+        }
+
+        Ok(Self {
+            program,
+            public_input,
+            proof,
+            registers: vec![Fr::ZERO; program.num_registers],
+            points: vec![G1Affine::identity(); program.num_points],
+            transcript: TranscriptState::new(
+                program.hash_type.clone(),
+                program.initial_digest.clone(),
+            )?,
+            pc: 0,
+        })
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pc == self.program.instructions.len()
+    }
+
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    pub fn registers(&self) -> &[Fr] {
+        &self.registers
+    }
+
+    pub fn points(&self) -> &[G1Affine] {
+        &self.points
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        &self.transcript.digest
+    }
+
+    /// Execute the next instruction
+    pub fn step(&mut self) -> Result<(), Rejection> {
+        let pc = self.pc;
+        match &self.program.instructions[pc] {
+            Instruction::PublicInput { dst, index } => {
+                self.registers[*dst] = self.public_input[*index]
+            }
+            Instruction::Constant { dst, value } => self.registers[*dst] = *value,
+            Instruction::ReadFr { dst, offset } => {
+                self.registers[*dst] =
+                    Fr::deserialize_from(&self.proof[*offset..*offset + Fr::SIZE])
+                        .map_err(|_| Rejection::NonCanonicalElement(*offset))?;
+            }
+            Instruction::ReadG1 { dst, offset } => {
+                self.points[*dst] =
+                    G1Affine::deserialize_from(&self.proof[*offset..*offset + G1_COMPRESSED_SIZE])
+                        .map_err(|_| Rejection::InvalidPoint(*offset))?;
+            }
+            Instruction::CheckBytes { offset, bytes } => {
+                if self.proof[*offset..*offset + bytes.len()] != bytes[..] {
+                    return Err(Rejection::UnexpectedBytes(*offset));
+                }
+            }
+            Instruction::Add { dst, a, b } => {
+                self.registers[*dst] = self.registers[*a] + self.registers[*b]
+            }
+            Instruction::Sub { dst, a, b } => {
+                self.registers[*dst] = self.registers[*a] - self.registers[*b]
+            }
+            Instruction::Mul { dst, a, b } => {
+                self.registers[*dst] = self.registers[*a] * self.registers[*b]
+            }
+            Instruction::Inverse { dst, src } => {
+                self.registers[*dst] = self.registers[*src].inv().unwrap_or(Fr::ZERO)
+            }
+            Instruction::AssertEqual { a, b } => {
+                if self.registers[*a] != self.registers[*b] {
+                    return Err(Rejection::AssertionFailed(pc));
+                }
+            }
+            Instruction::Absorb { offset, len } => {
+                self.transcript.absorb(&self.proof[*offset..*offset + *len])
+            }
+            Instruction::AbsorbCommitment {
+                offset,
+                len,
+                rehashes,
+            } => self
+                .transcript
+                .absorb_commitment(&self.proof[*offset..*offset + *len], *rehashes),
+            Instruction::AbsorbStatement { bytes } => {
+                self.transcript.absorb(bytes);
+                self.transcript.unlock_proof();
+            }
+            Instruction::AbsorbFr { src } => {
+                let mut bytes = vec![];
+                self.registers[*src].serialize_into(&mut bytes).unwrap();
+                self.transcript.absorb(&bytes);
+                self.transcript.unlock_proof();
+            }
+            Instruction::Challenge { dst } => self.registers[*dst] = self.transcript.challenge(),
+            Instruction::UnlockProof => self.transcript.unlock_proof(),
+            Instruction::G1Generator { dst } => self.points[*dst] = G1Affine::generator(),
+            Instruction::G1Lincomb { dst, terms } => {
+                self.points[*dst] = terms
+                    .iter()
+                    .fold(G1::identity(), |acc, (point, scalar)| {
+                        acc + self.points[*point] * self.registers[*scalar]
+                    })
+                    .to_affine();
+            }
+            Instruction::PairingCheck { lhs, rhs } => {
+                let gt = Bn256::multi_miller_loop(&[
+                    (&self.points[*lhs], &G2Prepared::from(self.program.tau_g2)),
+                    (
+                        &-self.points[*rhs],
+                        &G2Prepared::from(G2Affine::generator()),
+                    ),
+                ]);
+                if !bool::from(gt.final_exponentiation().is_identity()) {
+                    return Err(Rejection::PairingCheckFailed(pc));
+                }
+            }
+        }
+        self.pc += 1;
+        Ok(())
+    }
+
+    /// Execute the remaining instructions
+    pub fn run(mut self) -> Result<(), Rejection> {
+        while !self.is_done() {
+            self.step()?;
+        }
+        Ok(())
+    }
+}
+
+impl VerifierProgram {
+    /// Run the program on the proof, as the generated contract does
+    pub fn execute(&self, public_input: &[Fr], proof: &Proof) -> Result<(), Rejection> {
+        Interpreter::new(self, public_input, &proof.bytes)?.run()
+    }
+}
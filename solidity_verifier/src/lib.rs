@@ -0,0 +1,28 @@
+//! Solidity verifiers of GKR proofs, for BN254 circuits with the MiMC5 or Keccak256 transcript
+//! and the KZG commitment.
+//!
+//! A `SolidityVerifierGenerator` compiles the checks of `gkr::Verifier::verify` on the proofs
+//! of a given circuit into a `VerifierProgram`, a straight-line program over the elements and
+//! the points read from the proof: the sumcheck of every layer, the replay of the
+//! `BytesHashTranscript` and the HyperKZG opening, down to a pairing check. The layer structure
+//! of the circuit is hard-coded into the program, whose layout of the proof is fixed. So is the
+//! claimed evaluation of the outputs, zero, as the outputs of the supported circuits are all
+//! constrained to zero.
+//!
+//! The program is emitted as a Solidity contract with `VerifierProgram::to_solidity`, running
+//! the pairing check with the BN254 precompiles, and executed locally by the `Interpreter`,
+//! one instruction at a time, to cross-check the contract without a chain.
+
+mod program;
+pub use program::*;
+
+mod generator;
+pub use generator::*;
+
+mod interpreter;
+pub use interpreter::*;
+
+mod solidity;
+
+#[cfg(test)]
+mod tests;
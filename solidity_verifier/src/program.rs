@@ -0,0 +1,178 @@
+//! The verification of the proofs of a circuit as a straight-line program.
+//!
+//! The program works on registers of elements of the scalar field and of points of G1, which
+//! are written once, and on the transcript, i.e., its digest and the bytes appended since the
+//! last hash. Every element and point is read from a fixed offset of the proof.
+
+use arith::Fr;
+use gkr_engine::FiatShamirHashType;
+use halo2curves::bn256::G2Affine;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instruction {
+    /// `r[dst]` is the public input at `index`
+    PublicInput {
+        dst: usize,
+        index: usize,
+    },
+
+    Constant {
+        dst: usize,
+        value: Fr,
+    },
+
+    /// `r[dst]` is the element serialized at `offset` of the proof, which must be canonical
+    ReadFr {
+        dst: usize,
+        offset: usize,
+    },
+
+    /// `p[dst]` is the point serialized, i.e., compressed, at `offset` of the proof
+    ReadG1 {
+        dst: usize,
+        offset: usize,
+    },
+
+    /// The proof must have these bytes at `offset`, e.g., the length of a vector
+    CheckBytes {
+        offset: usize,
+        bytes: Vec<u8>,
+    },
+
+    Add {
+        dst: usize,
+        a: usize,
+        b: usize,
+    },
+
+    Sub {
+        dst: usize,
+        a: usize,
+        b: usize,
+    },
+
+    Mul {
+        dst: usize,
+        a: usize,
+        b: usize,
+    },
+
+    /// `r[dst]` is the inverse of `r[src]`, or zero if `r[src]` is zero
+    Inverse {
+        dst: usize,
+        src: usize,
+    },
+
+    AssertEqual {
+        a: usize,
+        b: usize,
+    },
+
+    /// Append the bytes of the proof in `offset..offset + len` to the transcript
+    Absorb {
+        offset: usize,
+        len: usize,
+    },
+
+    /// Append the commitment in `offset..offset + len` of the proof to the transcript, whose
+    /// state is then the hash of the commitment hashed again `rehashes` times, see
+    /// `BytesHashTranscript::append_commitment`
+    AbsorbCommitment {
+        offset: usize,
+        len: usize,
+        rehashes: usize,
+    },
+
+    /// Append constant bytes known to both parties, e.g., the statement, to the transcript and
+    /// hash them at once, see `transcript_absorb_statement`
+    AbsorbStatement {
+        bytes: Vec<u8>,
+    },
+
+    /// Append `r[src]`, known to both parties, e.g., the claimed value, to the transcript and
+    /// hash it at once, see `bind_output_claim`
+    AbsorbFr {
+        src: usize,
+    },
+
+    /// `r[dst]` is sampled from the transcript
+    Challenge {
+        dst: usize,
+    },
+
+    /// Hash the bytes appended to the transcript since the proof was locked, i.e., during the
+    /// opening of the commitment, and drop them from the proof, see `Transcript::unlock_proof`
+    UnlockProof,
+
+    /// `p[dst]` is the generator of G1
+    G1Generator {
+        dst: usize,
+    },
+
+    /// `p[dst]` is the sum of `r[scalar] * p[point]` over the `(point, scalar)` terms
+    G1Lincomb {
+        dst: usize,
+        terms: Vec<(usize, usize)>,
+    },
+
+    /// `e(p[lhs], tau * G2) == e(p[rhs], G2)`, with `tau * G2` from the verifying key
+    PairingCheck {
+        lhs: usize,
+        rhs: usize,
+    },
+}
+
+/// The checks of `gkr::Verifier::verify` on the proofs of a circuit, see
+/// `SolidityVerifierGenerator::generate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifierProgram {
+    /// The hash of the transcript, MiMC5 or Keccak256
+    pub hash_type: FiatShamirHashType,
+
+    /// The digest of a new transcript
+    pub initial_digest: Vec<u8>,
+
+    pub num_public_inputs: usize,
+
+    /// The length in bytes of the proofs
+    pub proof_len: usize,
+
+    pub num_registers: usize,
+    pub num_points: usize,
+
+    /// tau over G2, from the verifying key of the commitment
+    pub tau_g2: G2Affine,
+
+    pub instructions: Vec<Instruction>,
+}
+
+impl VerifierProgram {
+    /// The largest number of bytes appended to the transcript between two hashes, i.e., the
+    /// size of the buffer of the transcript but for the digest
+    pub fn max_pending_len(&self) -> usize {
+        let mut pending = 0;
+        let mut max_pending = 0;
+        for instruction in &self.instructions {
+            match instruction {
+                Instruction::Absorb { len, .. } => pending += len,
+                // the commitment is hashed on its own, from the same buffer
+                Instruction::AbsorbCommitment { len, .. } => {
+                    max_pending = max_pending.max(*len);
+                    pending = 0;
+                }
+                Instruction::AbsorbStatement { bytes } => {
+                    max_pending = max_pending.max(pending + bytes.len());
+                    pending = 0;
+                }
+                Instruction::AbsorbFr { .. } => {
+                    max_pending = max_pending.max(pending + 32);
+                    pending = 0;
+                }
+                Instruction::Challenge { .. } | Instruction::UnlockProof => pending = 0,
+                _ => (),
+            }
+            max_pending = max_pending.max(pending);
+        }
+        max_pending
+    }
+}
@@ -0,0 +1,522 @@
+//! The Solidity contract running a `VerifierProgram`, with the scalar field arithmetic on
+//! `addmod` and `mulmod`, and the curve arithmetic and the pairing check on the BN254
+//! precompiles.
+
+use std::fmt::Write;
+
+use arith::Fr;
+use gkr_engine::FiatShamirHashType;
+use gkr_hashers::generate_mimc_constants;
+use halo2curves::{
+    bn256::{Fq, G2Affine},
+    ff::PrimeField,
+    group::prime::PrimeCurveAffine,
+};
+use serdes::ExpSerde;
+
+use crate::{Instruction, SolidityVerifierError, VerifierProgram};
+
+/// The modulus of the scalar field of BN254, i.e., of `Fr`
+const SCALAR_MODULUS: &str = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+/// The modulus of the base field of BN254, i.e., of `Fq`
+const BASE_MODULUS: &str = "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+
+/// A uint256 literal from its little endian bytes
+fn uint256_literal(le_bytes: &[u8]) -> String {
+    let hex = le_bytes
+        .iter()
+        .rev()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("0x{hex}")
+}
+
+fn fr_literal(value: &Fr) -> String {
+    let mut bytes = vec![];
+    value.serialize_into(&mut bytes).unwrap();
+    uint256_literal(&bytes)
+}
+
+fn fq_literal(value: &Fq) -> String {
+    uint256_literal(value.to_repr().as_ref())
+}
+
+/// The constants of a point of G2, in the order of the pairing precompile, i.e., the imaginary
+/// part of each coordinate first
+fn g2_constants(name: &str, point: &G2Affine) -> String {
+    [
+        ("X_IM", &point.x.c1),
+        ("X_RE", &point.x.c0),
+        ("Y_IM", &point.y.c1),
+        ("Y_RE", &point.y.c0),
+    ]
+    .iter()
+    .map(|(coordinate, value)| {
+        format!(
+            "    uint256 internal constant {name}_{coordinate} = {};\n",
+            fq_literal(value)
+        )
+    })
+    .collect()
+}
+
+/// Swaps the bytes of a uint256, between the little endian serialization and the value
+fn reverse_bytes_function() -> String {
+    let mut body = String::new();
+    for shift in [8, 16, 32, 64] {
+        let chunk = "00".repeat(shift / 8) + &"ff".repeat(shift / 8);
+        let mask = chunk.repeat(32 / (shift / 4));
+        writeln!(
+            body,
+            "        v = ((v >> {shift}) & 0x{mask}) | ((v & 0x{mask}) << {shift});"
+        )
+        .unwrap();
+    }
+    writeln!(body, "        v = (v >> 128) | (v << 128);").unwrap();
+    format!(
+        "    function reverseBytes(uint256 v) internal pure returns (uint256) {{\n{body}        return v;\n    }}\n"
+    )
+}
+
+fn hash_function(hash_type: &FiatShamirHashType) -> Result<String, SolidityVerifierError> {
+    match hash_type {
+        FiatShamirHashType::Keccak256 => Ok(KECCAK256_HASH.to_string()),
+        FiatShamirHashType::MIMC5 => {
+            let rounds = generate_mimc_constants::<Fr>()
+                .iter()
+                .map(|c| {
+                    format!(
+                        "        x = pow5(addmod(addmod(x, h, R), {}, R));\n",
+                        fr_literal(c)
+                    )
+                })
+                .collect::<String>();
+            Ok(format!(
+                "{MIMC5_HASH}\n    function mimc5(uint256 h, uint256 x) internal pure returns (uint256) {{\n{rounds}        return addmod(x, h, R);\n    }}\n"
+            ))
+        }
+        _ => Err(SolidityVerifierError::UnsupportedHash(hash_type.clone())),
+    }
+}
+
+/// The statements of an instruction at index `pc` of the program
+fn instruction_statements(pc: usize, instruction: &Instruction) -> String {
+    match instruction {
+        Instruction::PublicInput { dst, index } => format!("r[{dst}] = publicInputs[{index}];"),
+        Instruction::Constant { dst, value } => format!("r[{dst}] = {};", fr_literal(value)),
+        Instruction::ReadFr { dst, offset } => format!("r[{dst}] = readFr(proof, {offset});"),
+        Instruction::ReadG1 { dst, offset } => {
+            format!("(px[{dst}], py[{dst}]) = readG1(proof, {offset});")
+        }
+        Instruction::CheckBytes { offset, bytes } => bytes
+            .chunks(32)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let start = offset + i * 32;
+                let end = start + chunk.len();
+                let hex = chunk.iter().map(|b| format!("{b:02x}")).collect::<String>();
+                format!(
+                    "if (bytes{}(proof[{start}:{end}]) != 0x{hex}) revert UnexpectedBytes({offset});",
+                    chunk.len()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n        "),
+        Instruction::Add { dst, a, b } => format!("r[{dst}] = addmod(r[{a}], r[{b}], R);"),
+        Instruction::Sub { dst, a, b } => format!("r[{dst}] = addmod(r[{a}], R - r[{b}], R);"),
+        Instruction::Mul { dst, a, b } => format!("r[{dst}] = mulmod(r[{a}], r[{b}], R);"),
+        Instruction::Inverse { dst, src } => format!("r[{dst}] = inverse(r[{src}]);"),
+        Instruction::AssertEqual { a, b } => {
+            format!("if (r[{a}] != r[{b}]) revert AssertionFailed({pc});")
+        }
+        Instruction::Absorb { offset, len } => format!("absorb(t, proof, {offset}, {len});"),
+        Instruction::AbsorbCommitment {
+            offset,
+            len,
+            rehashes,
+        } => format!("absorbCommitment(t, proof, {offset}, {len}, {rehashes});"),
+        Instruction::AbsorbStatement { bytes } => {
+            let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+            format!("absorbStatement(t, hex\"{hex}\");")
+        }
+        Instruction::AbsorbFr { src } => format!("absorbFr(t, r[{src}]);"),
+        Instruction::Challenge { dst } => format!("r[{dst}] = challenge(t);"),
+        Instruction::UnlockProof => "unlockProof(t);".to_string(),
+        Instruction::G1Generator { dst } => format!("(px[{dst}], py[{dst}]) = (1, 2);"),
+        Instruction::G1Lincomb { dst, terms } => {
+            let mut statements = vec!["{".to_string(), "    uint256 x;".to_string(), "    uint256 y;".to_string()];
+            statements.push(format!("    (px[{dst}], py[{dst}]) = (0, 0);"));
+            for (point, scalar) in terms {
+                statements.push(format!(
+                    "    (x, y) = ecMul(px[{point}], py[{point}], r[{scalar}]);"
+                ));
+                statements.push(format!(
+                    "    (px[{dst}], py[{dst}]) = ecAdd(px[{dst}], py[{dst}], x, y);"
+                ));
+            }
+            statements.push("}".to_string());
+            statements.join("\n        ")
+        }
+        Instruction::PairingCheck { lhs, rhs } => format!(
+            "if (!pairing(px[{lhs}], py[{lhs}], px[{rhs}], py[{rhs}])) revert PairingCheckFailed({pc});"
+        ),
+    }
+}
+
+impl VerifierProgram {
+    /// The contract whose `verify` accepts exactly the proofs and public inputs
+    /// accepted by `VerifierProgram::execute`, reverting with the reason of the rejection
+    /// otherwise. Fails on the transcript hashes without a Solidity implementation.
+    pub fn to_solidity(&self, contract_name: &str) -> Result<String, SolidityVerifierError> {
+        let mut contract = String::new();
+
+        writeln!(contract, "// SPDX-License-Identifier: MIT").unwrap();
+        writeln!(contract, "pragma solidity ^0.8.20;").unwrap();
+        writeln!(contract).unwrap();
+        writeln!(
+            contract,
+            "/// Verifies the Expander GKR proofs of a fixed circuit, generated by `solidity_verifier`"
+        )
+        .unwrap();
+        writeln!(contract, "contract {contract_name} {{").unwrap();
+
+        // the constants of the program
+        writeln!(
+            contract,
+            "    uint256 internal constant R = {SCALAR_MODULUS};"
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant P = {BASE_MODULUS};"
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant NUM_PUBLIC_INPUTS = {};",
+            self.num_public_inputs
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant PROOF_LENGTH = {};",
+            self.proof_len
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant NUM_REGISTERS = {};",
+            self.num_registers
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant NUM_POINTS = {};",
+            self.num_points
+        )
+        .unwrap();
+        writeln!(
+            contract,
+            "    uint256 internal constant BUFFER_SIZE = {};",
+            32 + self.max_pending_len()
+        )
+        .unwrap();
+        let initial_digest = self
+            .initial_digest
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        writeln!(
+            contract,
+            "    bytes32 internal constant INITIAL_DIGEST = 0x{initial_digest};"
+        )
+        .unwrap();
+        contract += &g2_constants("TAU_G2", &self.tau_g2);
+        contract += &g2_constants("G2", &G2Affine::generator());
+        contract += COMMON;
+
+        contract += &reverse_bytes_function();
+        contract += "\n";
+        contract += &hash_function(&self.hash_type)?;
+
+        // the program
+        contract += VERIFY_PROLOGUE;
+        for (pc, instruction) in self.instructions.iter().enumerate() {
+            writeln!(
+                contract,
+                "        {}",
+                instruction_statements(pc, instruction)
+            )
+            .unwrap();
+        }
+        contract += VERIFY_EPILOGUE;
+
+        Ok(contract)
+    }
+}
+
+const COMMON: &str = r#"
+    error PublicInputCount();
+    error ProofLength();
+    error NonCanonicalInput();
+    error NonCanonicalElement(uint256 offset);
+    error InvalidPoint(uint256 offset);
+    error UnexpectedBytes(uint256 offset);
+    error AssertionFailed(uint256 pc);
+    error PairingCheckFailed(uint256 pc);
+    error PrecompileFailed();
+
+    /// The transcript, whose buffer at `ptr` holds the digest followed by the `pending` bytes
+    /// appended since it was last refreshed
+    struct Transcript {
+        uint256 ptr;
+        uint256 pending;
+    }
+
+    function newTranscript() internal pure returns (Transcript memory t) {
+        bytes memory buffer = new bytes(BUFFER_SIZE);
+        bytes32 digest = INITIAL_DIGEST;
+        uint256 ptr;
+        assembly ("memory-safe") {
+            ptr := add(buffer, 0x20)
+            mstore(ptr, digest)
+        }
+        t.ptr = ptr;
+    }
+
+    function absorb(Transcript memory t, bytes calldata proof, uint256 offset, uint256 len) internal pure {
+        uint256 dst = t.ptr + 0x20 + t.pending;
+        assembly ("memory-safe") {
+            calldatacopy(dst, add(proof.offset, offset), len)
+        }
+        t.pending += len;
+    }
+
+    function setDigest(Transcript memory t, bytes32 digest) internal pure {
+        uint256 ptr = t.ptr;
+        assembly ("memory-safe") {
+            mstore(ptr, digest)
+        }
+        t.pending = 0;
+    }
+
+    function refresh(Transcript memory t) internal pure {
+        setDigest(t, hashMemory(t.ptr, 0x20 + t.pending));
+    }
+
+    function challenge(Transcript memory t) internal pure returns (uint256) {
+        refresh(t);
+        uint256 ptr = t.ptr;
+        uint256 digest;
+        assembly ("memory-safe") {
+            digest := mload(ptr)
+        }
+        return reverseBytes(digest) % R;
+    }
+
+    /// The state is the hash of the commitment, hashed again `rehashes` times, dropping the
+    /// pending bytes
+    function absorbCommitment(
+        Transcript memory t,
+        bytes calldata proof,
+        uint256 offset,
+        uint256 len,
+        uint256 rehashes
+    ) internal pure {
+        t.pending = 0;
+        absorb(t, proof, offset, len);
+        setDigest(t, hashMemory(t.ptr + 0x20, len));
+        for (uint256 i = 0; i < rehashes; i++) {
+            setDigest(t, hashMemory(t.ptr, 0x20));
+        }
+    }
+
+    /// Append constant bytes, e.g., the statement, and hash them at once
+    function absorbStatement(Transcript memory t, bytes memory data) internal pure {
+        uint256 dst = t.ptr + 0x20 + t.pending;
+        for (uint256 i = 0; i < data.length; i++) {
+            uint8 b = uint8(data[i]);
+            assembly ("memory-safe") {
+                mstore8(add(dst, i), b)
+            }
+        }
+        t.pending += data.length;
+        unlockProof(t);
+    }
+
+    /// The element is appended little endian, as serialized in the proofs
+    function absorbFr(Transcript memory t, uint256 value) internal pure {
+        uint256 dst = t.ptr + 0x20 + t.pending;
+        uint256 bytesLe = reverseBytes(value);
+        assembly ("memory-safe") {
+            mstore(dst, bytesLe)
+        }
+        t.pending += 0x20;
+        unlockProof(t);
+    }
+
+    function unlockProof(Transcript memory t) internal pure {
+        if (t.pending > 0) {
+            refresh(t);
+        }
+    }
+
+    function readFr(bytes calldata proof, uint256 offset) internal pure returns (uint256 value) {
+        value = reverseBytes(uint256(bytes32(proof[offset:offset + 32])));
+        if (value >= R) revert NonCanonicalElement(offset);
+    }
+
+    /// The compressed point has the little endian x, with the parity of y in the top bit and
+    /// the identity flag in the next one
+    function readG1(bytes calldata proof, uint256 offset) internal view returns (uint256 x, uint256 y) {
+        uint256 encoding = reverseBytes(uint256(bytes32(proof[offset:offset + 32])));
+        uint256 sign = encoding >> 255;
+        x = encoding & ((1 << 254) - 1);
+        if ((encoding >> 254) & 1 == 1) {
+            if (x != 0 || sign != 0) revert InvalidPoint(offset);
+            return (0, 0);
+        }
+        if (x >= P) revert InvalidPoint(offset);
+        uint256 y2 = addmod(mulmod(mulmod(x, x, P), x, P), 3, P);
+        y = expmod(y2, (P + 1) / 4, P);
+        if (mulmod(y, y, P) != y2) revert InvalidPoint(offset);
+        if (y & 1 != sign) {
+            if (y == 0) revert InvalidPoint(offset);
+            y = P - y;
+        }
+    }
+
+    function expmod(uint256 base, uint256 exponent, uint256 modulus) internal view returns (uint256 result) {
+        bool success;
+        assembly ("memory-safe") {
+            let m := mload(0x40)
+            mstore(m, 0x20)
+            mstore(add(m, 0x20), 0x20)
+            mstore(add(m, 0x40), 0x20)
+            mstore(add(m, 0x60), base)
+            mstore(add(m, 0x80), exponent)
+            mstore(add(m, 0xa0), modulus)
+            success := staticcall(gas(), 0x05, m, 0xc0, m, 0x20)
+            result := mload(m)
+        }
+        if (!success) revert PrecompileFailed();
+    }
+
+    /// The inverse, or zero for zero
+    function inverse(uint256 value) internal view returns (uint256) {
+        return expmod(value, R - 2, R);
+    }
+
+    function ecAdd(uint256 x0, uint256 y0, uint256 x1, uint256 y1) internal view returns (uint256 x, uint256 y) {
+        bool success;
+        assembly ("memory-safe") {
+            let m := mload(0x40)
+            mstore(m, x0)
+            mstore(add(m, 0x20), y0)
+            mstore(add(m, 0x40), x1)
+            mstore(add(m, 0x60), y1)
+            success := staticcall(gas(), 0x06, m, 0x80, m, 0x40)
+            x := mload(m)
+            y := mload(add(m, 0x20))
+        }
+        if (!success) revert PrecompileFailed();
+    }
+
+    function ecMul(uint256 x0, uint256 y0, uint256 scalar) internal view returns (uint256 x, uint256 y) {
+        bool success;
+        assembly ("memory-safe") {
+            let m := mload(0x40)
+            mstore(m, x0)
+            mstore(add(m, 0x20), y0)
+            mstore(add(m, 0x40), scalar)
+            success := staticcall(gas(), 0x07, m, 0x60, m, 0x40)
+            x := mload(m)
+            y := mload(add(m, 0x20))
+        }
+        if (!success) revert PrecompileFailed();
+    }
+
+    /// e(lhs, tau G2) == e(rhs, G2)
+    function pairing(uint256 lhsX, uint256 lhsY, uint256 rhsX, uint256 rhsY) internal view returns (bool) {
+        uint256[12] memory input = [
+            lhsX,
+            lhsY,
+            TAU_G2_X_IM,
+            TAU_G2_X_RE,
+            TAU_G2_Y_IM,
+            TAU_G2_Y_RE,
+            rhsX,
+            rhsY == 0 ? 0 : P - rhsY,
+            G2_X_IM,
+            G2_X_RE,
+            G2_Y_IM,
+            G2_Y_RE
+        ];
+        uint256[1] memory output;
+        bool success;
+        assembly ("memory-safe") {
+            success := staticcall(gas(), 0x08, input, 0x180, output, 0x20)
+        }
+        if (!success) revert PrecompileFailed();
+        return output[0] == 1;
+    }
+
+"#;
+
+const KECCAK256_HASH: &str = r#"
+    function hashMemory(uint256 ptr, uint256 len) internal pure returns (bytes32 digest) {
+        assembly ("memory-safe") {
+            digest := keccak256(ptr, len)
+        }
+    }
+"#;
+
+const MIMC5_HASH: &str = r#"
+    /// See `MiMC5FiatShamirHasher`, the 32 bytes chunks being little endian and the last one
+    /// padded with zeros
+    function hashMemory(uint256 ptr, uint256 len) internal pure returns (bytes32) {
+        uint256 h = 0;
+        for (uint256 i = 0; i < len; i += 32) {
+            uint256 word;
+            assembly ("memory-safe") {
+                word := mload(add(ptr, i))
+            }
+            if (len - i < 32) {
+                word &= ~((1 << (8 * (32 - (len - i)))) - 1);
+            }
+            uint256 x = reverseBytes(word) % R;
+            h = addmod(h, addmod(mimc5(h, x), x, R), R);
+        }
+        return bytes32(reverseBytes(h));
+    }
+
+    function pow5(uint256 x) internal pure returns (uint256) {
+        uint256 x2 = mulmod(x, x, R);
+        return mulmod(mulmod(x2, x2, R), x, R);
+    }
+"#;
+
+const VERIFY_PROLOGUE: &str = r#"
+    function verify(
+        uint256[] calldata publicInputs,
+        bytes calldata proof
+    ) external view returns (bool) {
+        if (publicInputs.length != NUM_PUBLIC_INPUTS) revert PublicInputCount();
+        if (proof.length != PROOF_LENGTH) revert ProofLength();
+        for (uint256 i = 0; i < NUM_PUBLIC_INPUTS; i++) {
+            if (publicInputs[i] >= R) revert NonCanonicalInput();
+        }
+
+        uint256[] memory r = new uint256[](NUM_REGISTERS);
+        uint256[] memory px = new uint256[](NUM_POINTS);
+        uint256[] memory py = new uint256[](NUM_POINTS);
+        Transcript memory t = newTranscript();
+
+"#;
+
+const VERIFY_EPILOGUE: &str = r#"
+        return true;
+    }
+}
+"#;
@@ -0,0 +1,264 @@
+use arith::{Field, Fr};
+use std::{io::ErrorKind, process::Command};
+
+use ark_std::{rand::RngCore, test_rng};
+use gkr::{
+    utils::bn254_fixture::{build_circuit, prove, random_witness, verify, LOG_INPUT_SIZE},
+    BN254ConfigKeccakKZG, BN254ConfigMIMC5KZG,
+};
+use gkr_engine::{BN254Config, FiatShamirHashType, GKREngine, MPIConfig, Proof, Transcript};
+use gkr_hashers::{FiatShamirHasher, Keccak256hasher, MiMC5FiatShamirHasher};
+use halo2curves::{
+    bn256::{Bn256, G2Affine},
+    group::prime::PrimeCurveAffine,
+};
+use poly_commit::{expander_pcs_init_testing_only, HyperUniKZGPCS, UniKZGVerifierParams};
+use serdes::ExpSerde;
+use transcript::BytesHashTranscript;
+
+use crate::{interpreter::TranscriptState, *};
+
+fn test_transcript_state_helper<H: FiatShamirHasher>() {
+    let mut rng = test_rng();
+    let mut random_bytes = |len: usize| {
+        let mut bytes = vec![0u8; len];
+        rng.fill_bytes(&mut bytes);
+        bytes
+    };
+
+    let mut native = BytesHashTranscript::<H>::new();
+    let mut state = TranscriptState::new(H::TYPE, native.digest.clone()).unwrap();
+
+    // the last chunk of the bytes is padded for MiMC5
+    for len in [0, 8, 32, 45, 100] {
+        let bytes = random_bytes(len);
+        native.append_u8_slice(&bytes);
+        state.absorb(&bytes);
+        assert_eq!(native.generate_field_element::<Fr>(), state.challenge());
+    }
+
+    let pending = random_bytes(40);
+    native.append_u8_slice(&pending);
+    let commitment = random_bytes(32);
+    native.append_commitment(&commitment);
+    #[cfg(not(feature = "recursion"))]
+    state.absorb_commitment(&commitment, transcript::PCS_DIGEST_LOOP);
+    #[cfg(feature = "recursion")]
+    {
+        state.absorb(&pending);
+        state.absorb(&commitment);
+    }
+    assert_eq!(native.generate_field_element::<Fr>(), state.challenge());
+
+    // the bytes appended while the proof is locked are hashed once it is unlocked
+    let opening = [random_bytes(32), random_bytes(64)];
+    native.lock_proof();
+    state.absorb(&opening[0]);
+    native.append_u8_slice(&opening[0]);
+    assert_eq!(native.generate_field_element::<Fr>(), state.challenge());
+    state.absorb(&opening[1]);
+    native.append_u8_slice(&opening[1]);
+    native.unlock_proof();
+    state.unlock_proof();
+    assert_eq!(native.digest, state.digest);
+
+    let opening = opening.concat();
+    native.append_u8_slice(&opening);
+    state.absorb(&opening);
+    assert_eq!(native.generate_field_element::<Fr>(), state.challenge());
+}
+
+#[test]
+fn test_transcript_state() {
+    test_transcript_state_helper::<MiMC5FiatShamirHasher<Fr>>();
+    test_transcript_state_helper::<Keccak256hasher>();
+}
+
+fn verification_key() -> UniKZGVerifierParams<Bn256> {
+    expander_pcs_init_testing_only::<BN254Config, HyperUniKZGPCS<Bn256>>(
+        LOG_INPUT_SIZE,
+        &MPIConfig::verifier_new(1),
+    )
+    .2
+}
+
+fn test_generated_verifier_helper<H, Cfg>()
+where
+    H: FiatShamirHasher,
+    Cfg: GKREngine<
+        FieldConfig = BN254Config,
+        TranscriptConfig = BytesHashTranscript<H>,
+        PCSConfig = HyperUniKZGPCS<Bn256>,
+    >,
+{
+    let mut rng = test_rng();
+    let circuit = build_circuit();
+    let (witness, public_input) = random_witness(&mut rng);
+    let (claimed_v, proof) = prove::<Cfg>(&circuit, &witness);
+    assert!(verify::<Cfg>(&circuit, &witness, &claimed_v, &proof));
+
+    let program = SolidityVerifierGenerator::<Cfg>::new(circuit.proof_shape(1))
+        .generate(&circuit, public_input.len(), &verification_key())
+        .unwrap();
+    assert_eq!(program.proof_len, proof.bytes.len());
+    assert_eq!(program.execute(&public_input, &proof), Ok(()));
+
+    // the interpreter runs the program one instruction at a time
+    let mut interpreter = Interpreter::new(&program, &public_input, &proof.bytes).unwrap();
+    while !interpreter.is_done() {
+        interpreter.step().unwrap();
+    }
+    assert_eq!(interpreter.pc(), program.instructions.len());
+
+    assert_eq!(
+        program.execute(&[], &proof),
+        Err(Rejection::PublicInputCount {
+            expected: 1,
+            got: 0
+        })
+    );
+    assert!(matches!(
+        program.execute(&[public_input[0] + Fr::ONE], &proof),
+        Err(Rejection::AssertionFailed(_))
+    ));
+
+    // the outputs of the witness are not zero, the claimed evaluation of the outputs is not
+    let (mut unsatisfied, unsatisfied_input) = random_witness(&mut rng);
+    unsatisfied.values[3] += Fr::ONE;
+    let (unsatisfied_v, unsatisfied_proof) = prove::<Cfg>(&circuit, &unsatisfied);
+    assert!(!unsatisfied_v.is_zero());
+    assert!(matches!(
+        program.execute(&unsatisfied_input, &unsatisfied_proof),
+        Err(Rejection::AssertionFailed(_))
+    ));
+
+    // the proof starts with the public input and the commitment, followed by the first
+    // evaluation of the sumcheck
+    let mut tampered = proof.clone();
+    let offset = 32 * public_input.len() + G1_COMPRESSED_SIZE;
+    let value = Fr::deserialize_from(&tampered.bytes[offset..offset + 32]).unwrap();
+    let mut bytes = vec![];
+    (value + Fr::ONE).serialize_into(&mut bytes).unwrap();
+    tampered.bytes[offset..offset + 32].copy_from_slice(&bytes);
+    assert!(!verify::<Cfg>(&circuit, &witness, &claimed_v, &tampered));
+    assert!(matches!(
+        program.execute(&public_input, &tampered),
+        Err(Rejection::AssertionFailed(_))
+    ));
+
+    // a non canonical element
+    let mut tampered = proof.clone();
+    tampered.bytes[offset..offset + 32].copy_from_slice(&[0xff; 32]);
+    assert_eq!(
+        program.execute(&public_input, &tampered),
+        Err(Rejection::NonCanonicalElement(offset))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.bytes.push(0);
+    assert!(matches!(
+        program.execute(&public_input, &tampered),
+        Err(Rejection::ProofLength { .. })
+    ));
+
+    // the openings are checked against another verifying key
+    let mut wrong_key = program.clone();
+    wrong_key.tau_g2 = G2Affine::generator();
+    assert!(matches!(
+        wrong_key.execute(&public_input, &proof),
+        Err(Rejection::PairingCheckFailed(_))
+    ));
+
+    let contract = program.to_solidity("ExpanderVerifier").unwrap();
+    assert!(contract.contains("contract ExpanderVerifier {"));
+    assert!(contract.contains(&format!(
+        "uint256 internal constant PROOF_LENGTH = {};",
+        proof.bytes.len()
+    )));
+    assert_eq!(
+        contract.matches("revert PairingCheckFailed").count(),
+        program
+            .instructions
+            .iter()
+            .filter(|instruction| matches!(instruction, Instruction::PairingCheck { .. }))
+            .count()
+    );
+    compile_solidity(&contract);
+}
+
+// the contract is compiled to bytecode, which requires solc 0.8.20 or later, e.g.,
+// `pip install solc-select && solc-select install 0.8.28 && solc-select use 0.8.28`
+fn compile_solidity(contract: &str) {
+    let path = std::env::temp_dir().join(format!("expander_verifier_{}.sol", std::process::id()));
+    std::fs::write(&path, contract).unwrap();
+    let output = Command::new("solc").arg("--bin").arg(&path).output();
+    std::fs::remove_file(&path).unwrap();
+
+    match output {
+        Ok(output) => assert!(
+            output.status.success(),
+            "solc rejected the contract: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            panic!("solc not found, the generated contracts are compiled by the tests")
+        }
+        Err(e) => panic!("failed to run solc: {e}"),
+    }
+}
+
+#[test]
+fn test_generated_verifier() {
+    test_generated_verifier_helper::<MiMC5FiatShamirHasher<Fr>, BN254ConfigMIMC5KZG>();
+    test_generated_verifier_helper::<Keccak256hasher, BN254ConfigKeccakKZG>();
+}
+
+#[test]
+fn test_unsupported_circuits() {
+    let circuit = build_circuit();
+    let vk = verification_key();
+
+    let generator = SolidityVerifierGenerator::<BN254ConfigMIMC5KZG>::new(circuit.proof_shape(2));
+    assert_eq!(
+        generator.generate(&circuit, 1, &vk).unwrap_err(),
+        SolidityVerifierError::UnsupportedWorldSize(2)
+    );
+
+    let mut proof_shape = circuit.proof_shape(1);
+    proof_shape.simd_size += 1;
+    let generator = SolidityVerifierGenerator::<BN254ConfigMIMC5KZG>::new(proof_shape);
+    assert_eq!(
+        generator.generate(&circuit, 1, &vk).unwrap_err(),
+        SolidityVerifierError::ProofShapeMismatch
+    );
+
+    // the claimed evaluation of the outputs is hard-coded to zero
+    let mut with_outputs = circuit.clone();
+    with_outputs.expected_num_output_zeros = 1;
+    let generator = SolidityVerifierGenerator::<BN254ConfigMIMC5KZG>::new(circuit.proof_shape(1));
+    assert!(matches!(
+        generator.generate(&with_outputs, 1, &vk).unwrap_err(),
+        SolidityVerifierError::UnsupportedCircuit(_)
+    ));
+}
+
+#[test]
+fn test_unsupported_hash() {
+    let circuit = build_circuit();
+    let mut program = SolidityVerifierGenerator::<BN254ConfigMIMC5KZG>::new(circuit.proof_shape(1))
+        .generate(&circuit, 1, &verification_key())
+        .unwrap();
+    program.hash_type = FiatShamirHashType::SHA256;
+
+    assert_eq!(
+        program.to_solidity("ExpanderVerifier").unwrap_err(),
+        SolidityVerifierError::UnsupportedHash(FiatShamirHashType::SHA256)
+    );
+    let proof = Proof {
+        bytes: vec![0; program.proof_len],
+    };
+    assert_eq!(
+        program.execute(&[Fr::ZERO], &proof),
+        Err(Rejection::UnsupportedHash(FiatShamirHashType::SHA256))
+    );
+}
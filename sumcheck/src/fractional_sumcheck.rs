@@ -0,0 +1,188 @@
+//! GKR for a sum of fractions, as used by LogUp lookups.
+//!
+//! The fractions `p_i / q_i` at the leaves are summed pairwise up a binary tree, the children
+//! of fraction `i` of a layer with `2^k` fractions being `i` and `i + 2^k` of the layer below:
+//!     p(b) = p(b, 0) * q(b, 1) + p(b, 1) * q(b, 0),    q(b) = q(b, 0) * q(b, 1)
+//! The prover sends the layer with two fractions, and every layer below is proven by a degree 3
+//! sumcheck over `eq(r, b) * (p(b, 0) * q(b, 1) + p(b, 1) * q(b, 0) + lambda * q(b, 0) * q(b, 1))`,
+//! which reduces the claims on `p(r)` and `q(r)` to claims on the layer below.
+//! The proof ends with a claim on the MLEs of the numerators and the denominators of the leaves.
+
+use std::io::Read;
+#[cfg(feature = "prover")]
+use std::sync::Arc;
+
+use arith::Field;
+use gkr_engine::Transcript;
+use polynomials::EqPolynomial;
+#[cfg(feature = "prover")]
+use polynomials::{MultiLinearPoly, VirtualPolynomial};
+use serdes::ExpSerde;
+
+use crate::{IOPProof, IOPProverMessage, SumCheck};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FractionalSumClaim<F: Field> {
+    /// Numerator and denominator of the whole sum
+    pub root: (F, F),
+    /// The point of the claims on the leaves, variable 0 first
+    pub point: Vec<F>,
+    /// MLE of the numerators of the leaves at `point`
+    pub numerator: F,
+    /// MLE of the denominators of the leaves at `point`
+    pub denominator: F,
+}
+
+/// Prove the sum of `numerators[i] / denominators[i]`, the number of fractions is a power of 2
+/// and at least 2.
+#[cfg(feature = "prover")]
+pub fn fractional_sum_prove<F: Field>(
+    numerators: Vec<F>,
+    denominators: Vec<F>,
+    compressed: bool,
+    transcript: &mut impl Transcript,
+) -> FractionalSumClaim<F> {
+    assert!(numerators.len() >= 2 && numerators.len().is_power_of_two());
+    assert_eq!(numerators.len(), denominators.len());
+
+    // layers[k] has 2^(k + 1) fractions, the leaves come last
+    let mut layers = vec![(numerators, denominators)];
+    while layers.last().unwrap().0.len() > 2 {
+        let (p, q) = layers.last().unwrap();
+        let half = p.len() / 2;
+        let (p_lo, p_hi) = p.split_at(half);
+        let (q_lo, q_hi) = q.split_at(half);
+        let next_p = (0..half)
+            .map(|i| p_lo[i] * q_hi[i] + p_hi[i] * q_lo[i])
+            .collect::<Vec<_>>();
+        let next_q = (0..half).map(|i| q_lo[i] * q_hi[i]).collect::<Vec<_>>();
+        layers.push((next_p, next_q));
+    }
+    layers.reverse();
+
+    let (p, q) = &layers[0];
+    [p[0], p[1], q[0], q[1]]
+        .iter()
+        .for_each(|v| transcript.append_field_element(v));
+    let root = (p[0] * q[1] + p[1] * q[0], q[0] * q[1]);
+
+    let c = transcript.generate_field_element::<F>();
+    let mut point = vec![c];
+    let mut p_claim = p[0] + c * (p[1] - p[0]);
+    let mut q_claim = q[0] + c * (q[1] - q[0]);
+
+    for (num_vars, (p, q)) in layers.iter().enumerate().skip(1) {
+        let lambda = transcript.generate_field_element::<F>();
+
+        let half = p.len() / 2;
+        let eq = Arc::new(MultiLinearPoly::new(EqPolynomial::build_eq_x_r(&point)));
+        let p_lo = Arc::new(MultiLinearPoly::new(p[..half].to_vec()));
+        let p_hi = Arc::new(MultiLinearPoly::new(p[half..].to_vec()));
+        let q_lo = Arc::new(MultiLinearPoly::new(q[..half].to_vec()));
+        let q_hi = Arc::new(MultiLinearPoly::new(q[half..].to_vec()));
+
+        let mut poly = VirtualPolynomial::new(num_vars);
+        poly.add_mle_list([eq.clone(), p_lo.clone(), q_hi.clone()], F::ONE);
+        poly.add_mle_list([eq.clone(), p_hi.clone(), q_lo.clone()], F::ONE);
+        poly.add_mle_list([eq, q_lo.clone(), q_hi.clone()], lambda);
+
+        let proof = if compressed {
+            SumCheck::prove_virtual_compressed(&poly, transcript)
+        } else {
+            SumCheck::prove_virtual(&poly, transcript)
+        };
+
+        let evals = [p_lo, p_hi, q_lo, q_hi].map(|mle| mle.eval_reverse_order(&proof.point));
+        evals
+            .iter()
+            .for_each(|v| transcript.append_field_element(v));
+
+        let c = transcript.generate_field_element::<F>();
+        point = proof.export_point_to_expander();
+        point.push(c);
+        p_claim = evals[0] + c * (evals[1] - evals[0]);
+        q_claim = evals[2] + c * (evals[3] - evals[2]);
+    }
+
+    FractionalSumClaim {
+        root,
+        point,
+        numerator: p_claim,
+        denominator: q_claim,
+    }
+}
+
+/// Verify a proof of `fractional_sum_prove` over `2^num_vars` fractions, read from
+/// `proof_reader`. The returned claims on the leaves are left to the caller.
+pub fn fractional_sum_verify<F: Field>(
+    num_vars: usize,
+    compressed: bool,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+) -> Option<FractionalSumClaim<F>> {
+    assert!(num_vars > 0);
+
+    let [p_0, p_1, q_0, q_1] = read_field_elements::<F, 4>(&mut proof_reader, transcript)?;
+    let root = (p_0 * q_1 + p_1 * q_0, q_0 * q_1);
+
+    let c = transcript.generate_field_element::<F>();
+    let mut point = vec![c];
+    let mut p_claim = p_0 + c * (p_1 - p_0);
+    let mut q_claim = q_0 + c * (q_1 - q_0);
+
+    for layer_num_vars in 1..num_vars {
+        let lambda = transcript.generate_field_element::<F>();
+
+        let proofs = (0..layer_num_vars)
+            .map(|_| IOPProverMessage::<F>::deserialize_from(&mut proof_reader).ok())
+            .collect::<Option<Vec<_>>>()?;
+        let proof = IOPProof {
+            proofs,
+            point: vec![],
+        };
+        let claimed_sum = p_claim + lambda * q_claim;
+        let (verified, subclaim) = if compressed {
+            SumCheck::verify_virtual_compressed(claimed_sum, &proof, layer_num_vars, 3, transcript)
+        } else {
+            SumCheck::verify_virtual(claimed_sum, &proof, layer_num_vars, 3, transcript)
+        };
+        if !verified {
+            return None;
+        }
+
+        let [p_lo, p_hi, q_lo, q_hi] = read_field_elements::<F, 4>(&mut proof_reader, transcript)?;
+        let mut r = subclaim.point;
+        r.reverse();
+        let expected =
+            EqPolynomial::eq_vec(&point, &r) * (p_lo * q_hi + p_hi * q_lo + lambda * q_lo * q_hi);
+        if expected != subclaim.expected_evaluation {
+            return None;
+        }
+
+        let c = transcript.generate_field_element::<F>();
+        point = r;
+        point.push(c);
+        p_claim = p_lo + c * (p_hi - p_lo);
+        q_claim = q_lo + c * (q_hi - q_lo);
+    }
+
+    Some(FractionalSumClaim {
+        root,
+        point,
+        numerator: p_claim,
+        denominator: q_claim,
+    })
+}
+
+#[inline]
+fn read_field_elements<F: Field, const N: usize>(
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+) -> Option<[F; N]> {
+    let mut vals = [F::ZERO; N];
+    for v in vals.iter_mut() {
+        *v = F::deserialize_from(&mut proof_reader).ok()?;
+        transcript.append_field_element(v);
+    }
+    Some(vals)
+}
@@ -4,16 +4,27 @@ pub use sumcheck::*;
 mod sumcheck_generic;
 pub use sumcheck_generic::*;
 
+mod sumcheck_generalized;
+pub use sumcheck_generalized::*;
+
+mod fractional_sumcheck;
+pub use fractional_sumcheck::*;
+
+#[cfg(feature = "prover")]
 mod prover_helper;
 
 mod verifier_helper;
 pub use verifier_helper::*;
 
 mod scratch_pad;
-pub use scratch_pad::{ProverScratchPad, ScratchPadBatch, VerifierScratchPad};
+pub use scratch_pad::VerifierScratchPad;
+#[cfg(feature = "prover")]
+pub use scratch_pad::{ProverScratchPad, ScratchPadBatch};
 
 mod utils;
 pub use utils::*;
 
+#[cfg(feature = "prover")]
 pub mod sumcheck_batch;
+#[cfg(feature = "prover")]
 pub use sumcheck_batch::*;
@@ -1,5 +1,7 @@
 pub(crate) mod product_gate;
 pub(crate) mod simd_gate;
+mod sumcheck_gkr_generalized;
 mod sumcheck_gkr_vanilla;
 
+pub(crate) use sumcheck_gkr_generalized::SumcheckGkrGeneralizedHelper;
 pub(crate) use sumcheck_gkr_vanilla::SumcheckGkrVanillaHelper;
@@ -0,0 +1,398 @@
+use arith::{Field, SimdField};
+use circuit::CircuitLayer;
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, MPIEngine};
+use polynomials::EqPolynomial;
+
+use crate::{gkr_round_eval_points, unpack_and_combine, GeneralizedLayerGates, ProverScratchPad};
+
+pub(crate) struct SumcheckGkrGeneralizedHelper<'a, F: FieldEngine> {
+    pub(crate) r_slots: Vec<Vec<F::ChallengeField>>,
+    pub(crate) r_simd_var: Vec<F::ChallengeField>,
+    pub(crate) r_mpi_var: Vec<F::ChallengeField>,
+    pub(crate) r_reduction: Vec<F::ChallengeField>,
+
+    layer: &'a CircuitLayer<F>,
+    sp: &'a mut ProverScratchPad<F>,
+    pub(crate) gates: GeneralizedLayerGates<F>,
+
+    pub(crate) input_var_num: usize,
+    pub(crate) simd_var_num: usize,
+    pub(crate) mpi_var_num: usize,
+
+    eq_evals_at_r_simd: Vec<F::ChallengeField>,
+    eq_evals_at_r_mpi: Vec<F::ChallengeField>,
+
+    // eq(rz, o) * coef * prod eq(r_k, i_k) over the bound slots, per gate
+    gate_wirings: Vec<F::ChallengeField>,
+    // the wiring times prod V(r_k)^e_k over the bound slots, lane by lane
+    gate_prefixes: Vec<F::Field>,
+
+    // phase over the inputs of a slot: V, and one table per exponent of the slot
+    slot_exponents: Vec<usize>,
+    v_evals: Vec<F::Field>,
+    hg_evals: Vec<Vec<F::Field>>,
+    // V(r_k) lane by lane, and eq(r_k, x), for the bound slots
+    slot_v_evals: Vec<F::Field>,
+    slot_eq_evals: Vec<Vec<F::ChallengeField>>,
+
+    // phases over the SIMD and MPI variables: eq, and V(r_k) of every slot
+    simd_mpi_eq_evals: Vec<F::ChallengeField>,
+    simd_mpi_v_evals: Vec<Vec<F::ChallengeField>>,
+    wiring_by_exponents: Vec<(Vec<usize>, F::ChallengeField)>,
+
+    // folding of the claims past the first slot
+    reduction_eq_evals: Vec<F::ChallengeField>,
+    reduction_v_evals: Vec<F::ChallengeField>,
+    eq_evals_at_r_mpi_var: Vec<F::ChallengeField>,
+}
+
+/// internal helper functions
+impl<F: FieldEngine> SumcheckGkrGeneralizedHelper<'_, F> {
+    /// Evaluations of `sum eq * sum_E W_E prod_k V_k^E_k` over the pairs of the tables
+    #[inline]
+    fn simd_mpi_poly_evals(
+        &self,
+        var_idx: usize,
+        var_num: usize,
+        degree: usize,
+    ) -> Vec<F::ChallengeField> {
+        let points = gkr_round_eval_points::<F>(degree);
+        let eval_size = 1 << (var_num - var_idx - 1);
+        let slot_num = self.simd_mpi_v_evals.len();
+
+        let mut evals = vec![F::ChallengeField::ZERO; degree + 1];
+        let mut v = vec![F::ChallengeField::ZERO; slot_num];
+        for i in 0..eval_size {
+            let eq_0 = self.simd_mpi_eq_evals[2 * i];
+            let eq_diff = self.simd_mpi_eq_evals[2 * i + 1] - eq_0;
+            for (eval, point) in evals.iter_mut().zip(&points) {
+                for (v_k, bk) in v.iter_mut().zip(&self.simd_mpi_v_evals) {
+                    *v_k = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * point;
+                }
+                let gate_sum: F::ChallengeField = self
+                    .wiring_by_exponents
+                    .iter()
+                    .map(|(exponents, wiring)| {
+                        exponents
+                            .iter()
+                            .zip(&v)
+                            .fold(*wiring, |acc, (e, v_k)| acc * v_k.exp(*e as u128))
+                    })
+                    .sum();
+                *eval += (eq_0 + eq_diff * point) * gate_sum;
+            }
+        }
+        evals
+    }
+
+    #[inline]
+    fn receive_simd_mpi_challenge(&mut self, var_idx: usize, var_num: usize, r: F::ChallengeField) {
+        let eval_size = 1 << (var_num - var_idx - 1);
+        for bk in std::iter::once(&mut self.simd_mpi_eq_evals).chain(&mut self.simd_mpi_v_evals) {
+            for i in 0..eval_size {
+                bk[i] = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * r;
+            }
+        }
+    }
+}
+
+/// Helper functions to be called
+impl<'a, F: FieldEngine> SumcheckGkrGeneralizedHelper<'a, F> {
+    #[inline]
+    pub(crate) fn new(
+        layer: &'a CircuitLayer<F>,
+        challenge: &ExpanderDualVarChallenge<F>,
+        alpha: Option<F::ChallengeField>,
+        sp: &'a mut ProverScratchPad<F>,
+        mpi_config: &impl MPIEngine,
+    ) -> Self {
+        assert_eq!(challenge.rz_1.is_none(), alpha.is_none());
+
+        let mut eq_evals_at_rz = EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.rz_0);
+        if let (Some(rz_1), Some(alpha)) = (&challenge.rz_1, alpha) {
+            EqPolynomial::<F::ChallengeField>::build_eq_x_r(rz_1)
+                .iter()
+                .zip(eq_evals_at_rz.iter_mut())
+                .for_each(|(eq_1, eq)| *eq += alpha * eq_1);
+        }
+
+        let gates = GeneralizedLayerGates::new(layer);
+        let gate_wirings: Vec<F::ChallengeField> = gates
+            .gates
+            .iter()
+            .map(|g| eq_evals_at_rz[g.o_id] * g.coef)
+            .collect();
+        let gate_prefixes = gate_wirings.iter().map(|w| F::Field::from(*w)).collect();
+
+        SumcheckGkrGeneralizedHelper {
+            r_slots: vec![],
+            r_simd_var: vec![],
+            r_mpi_var: vec![],
+            r_reduction: vec![],
+
+            layer,
+            sp,
+            gates,
+
+            input_var_num: layer.input_var_num,
+            simd_var_num: F::get_field_pack_size().trailing_zeros() as usize,
+            mpi_var_num: mpi_config.world_size().trailing_zeros() as usize,
+
+            eq_evals_at_r_simd: EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.r_simd),
+            eq_evals_at_r_mpi: EqPolynomial::<F::ChallengeField>::build_eq_x_r(&challenge.r_mpi),
+
+            gate_wirings,
+            gate_prefixes,
+
+            slot_exponents: vec![],
+            v_evals: vec![],
+            hg_evals: vec![],
+            slot_v_evals: vec![],
+            slot_eq_evals: vec![],
+
+            simd_mpi_eq_evals: vec![],
+            simd_mpi_v_evals: vec![],
+            wiring_by_exponents: vec![],
+
+            reduction_eq_evals: vec![],
+            reduction_v_evals: vec![],
+            eq_evals_at_r_mpi_var: vec![],
+        }
+    }
+
+    /// Scatter the gates into the tables of the phase over the inputs of `slot`, with the
+    /// bound slots evaluated at their challenges and the later slots at the gate inputs
+    #[inline]
+    pub(crate) fn prepare_slot_vals(&mut self, slot: usize) {
+        let vals = &self.layer.input_vals;
+        self.r_slots.push(vec![]);
+        self.v_evals = vals.iter().map(|v| F::Field::from(*v)).collect();
+
+        self.slot_exponents = self.gates.gates.iter().map(|g| g.exponents[slot]).collect();
+        self.slot_exponents.sort_unstable();
+        self.slot_exponents.dedup();
+        self.hg_evals = vec![vec![F::Field::zero(); vals.len()]; self.slot_exponents.len()];
+
+        for (g, prefix) in self.gates.gates.iter().zip(&self.gate_prefixes) {
+            let mut hg = *prefix;
+            for (i, e) in g.i_ids.iter().zip(&g.exponents).skip(slot + 1) {
+                if *e > 0 {
+                    hg = hg * vals[*i].exp(*e as u128);
+                }
+            }
+            let table = self
+                .slot_exponents
+                .binary_search(&g.exponents[slot])
+                .unwrap();
+            self.hg_evals[table][g.i_ids[slot]] += hg;
+        }
+    }
+
+    pub(crate) fn poly_evals_at_r_slot(
+        &self,
+        var_idx: usize,
+        degree: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> Vec<F::ChallengeField> {
+        assert!(var_idx < self.input_var_num);
+        let points = gkr_round_eval_points::<F>(degree);
+        let eval_size = 1 << (self.input_var_num - var_idx - 1);
+
+        let mut local_vals_simd = vec![F::Field::zero(); degree + 1];
+        for i in 0..eval_size {
+            let v_0 = self.v_evals[2 * i];
+            let v_diff = self.v_evals[2 * i + 1] - v_0;
+            for (p, point) in local_vals_simd.iter_mut().zip(&points) {
+                let v = v_0 + v_diff * *point;
+                for (e, bk) in self.slot_exponents.iter().zip(&self.hg_evals) {
+                    let hg = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * *point;
+                    *p += v.exp(*e as u128) * hg;
+                }
+            }
+        }
+
+        // SIMD
+        let local_vals = local_vals_simd
+            .iter()
+            .map(|p| unpack_and_combine(p, &self.eq_evals_at_r_simd))
+            .collect::<Vec<F::ChallengeField>>();
+
+        // MPI
+        mpi_config.coef_combine_vec(&local_vals, &self.eq_evals_at_r_mpi)
+    }
+
+    #[inline]
+    pub(crate) fn receive_r_slot(&mut self, slot: usize, var_idx: usize, r: F::ChallengeField) {
+        let eval_size = 1 << (self.input_var_num - var_idx - 1);
+        for bk in std::iter::once(&mut self.v_evals).chain(&mut self.hg_evals) {
+            for i in 0..eval_size {
+                bk[i] = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * r;
+            }
+        }
+        self.r_slots[slot].push(r);
+    }
+
+    /// Fold the challenge of `slot` into the wiring of every gate
+    #[inline]
+    pub(crate) fn finish_slot(&mut self, slot: usize) {
+        let v = self.v_evals[0];
+        let eq_evals = EqPolynomial::<F::ChallengeField>::build_eq_x_r(&self.r_slots[slot]);
+        for ((g, wiring), prefix) in self
+            .gates
+            .gates
+            .iter()
+            .zip(self.gate_wirings.iter_mut())
+            .zip(self.gate_prefixes.iter_mut())
+        {
+            let eq = eq_evals[g.i_ids[slot]];
+            *wiring *= eq;
+            *prefix = *prefix * eq * v.exp(g.exponents[slot] as u128);
+        }
+        self.slot_v_evals.push(v);
+        self.slot_eq_evals.push(eq_evals);
+    }
+
+    #[inline]
+    pub(crate) fn prepare_simd_var_vals(&mut self) {
+        self.simd_mpi_eq_evals = self.eq_evals_at_r_simd.clone();
+        self.simd_mpi_v_evals = self.slot_v_evals.iter().map(|v| v.unpack()).collect();
+        self.wiring_by_exponents = self.gates.wiring_by_exponents(&self.gate_wirings);
+    }
+
+    pub(crate) fn poly_evals_at_r_simd_var(
+        &self,
+        var_idx: usize,
+        degree: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> Vec<F::ChallengeField> {
+        assert!(var_idx < self.simd_var_num);
+        let local_vals = self.simd_mpi_poly_evals(var_idx, self.simd_var_num, degree);
+        mpi_config.coef_combine_vec(&local_vals, &self.eq_evals_at_r_mpi)
+    }
+
+    #[inline]
+    pub(crate) fn receive_r_simd_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        self.receive_simd_mpi_challenge(var_idx, self.simd_var_num, r);
+        self.r_simd_var.push(r);
+    }
+
+    /// Gather V(r_k, r_simd) of all the processes, the MPI phases only run on the root
+    #[inline]
+    pub(crate) fn prepare_mpi_var_vals(&mut self, mpi_config: &impl MPIEngine) {
+        let world_size = mpi_config.world_size();
+        let eq_simd = self.simd_mpi_eq_evals[0];
+        self.simd_mpi_eq_evals = self
+            .eq_evals_at_r_mpi
+            .iter()
+            .map(|eq| *eq * eq_simd)
+            .collect();
+        for bk in self.simd_mpi_v_evals.iter_mut() {
+            let mut gathered = vec![F::ChallengeField::ZERO; world_size];
+            mpi_config.gather_vec(&[bk[0]], &mut gathered);
+            *bk = gathered;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn poly_evals_at_r_mpi_var(
+        &self,
+        var_idx: usize,
+        degree: usize,
+    ) -> Vec<F::ChallengeField> {
+        assert!(var_idx < self.mpi_var_num);
+        self.simd_mpi_poly_evals(var_idx, self.mpi_var_num, degree)
+    }
+
+    #[inline]
+    pub(crate) fn receive_r_mpi_var(&mut self, var_idx: usize, r: F::ChallengeField) {
+        self.receive_simd_mpi_challenge(var_idx, self.mpi_var_num, r);
+        self.r_mpi_var.push(r);
+    }
+
+    /// Warning:
+    /// The claims are only correct on the root, after the MPI phase
+    #[inline]
+    pub(crate) fn slot_claims(&self) -> Vec<F::ChallengeField> {
+        self.simd_mpi_v_evals.iter().map(|bk| bk[0]).collect()
+    }
+
+    /// Tables of `sum_k beta^(k - 1) eq(r_k, x)` over the slots past the first,
+    /// and of V(x, r_simd) of this process
+    #[inline]
+    pub(crate) fn prepare_reduction_vals(&mut self, beta: F::ChallengeField) {
+        let input_size = 1 << self.input_var_num;
+        self.reduction_eq_evals = vec![F::ChallengeField::ZERO; input_size];
+        let mut coef = F::ChallengeField::ONE;
+        for eq_evals in self.slot_eq_evals.iter().skip(1) {
+            self.reduction_eq_evals
+                .iter_mut()
+                .zip(eq_evals)
+                .for_each(|(acc, eq)| *acc += coef * eq);
+            coef *= beta;
+        }
+
+        let eq_evals_at_r_simd_var =
+            EqPolynomial::<F::ChallengeField>::build_eq_x_r(&self.r_simd_var);
+        self.reduction_v_evals = self
+            .layer
+            .input_vals
+            .iter()
+            .map(|v| unpack_and_combine(&F::Field::from(*v), &eq_evals_at_r_simd_var))
+            .collect();
+        self.eq_evals_at_r_mpi_var =
+            EqPolynomial::<F::ChallengeField>::build_eq_x_r(&self.r_mpi_var);
+    }
+
+    pub(crate) fn poly_evals_at_r_reduction(
+        &self,
+        var_idx: usize,
+        mpi_config: &impl MPIEngine,
+    ) -> Vec<F::ChallengeField> {
+        assert!(var_idx < self.input_var_num);
+        let points = gkr_round_eval_points::<F>(2);
+        let eval_size = 1 << (self.input_var_num - var_idx - 1);
+
+        let mut local_vals = vec![F::ChallengeField::ZERO; 3];
+        for i in 0..eval_size {
+            let eq_0 = self.reduction_eq_evals[2 * i];
+            let eq_diff = self.reduction_eq_evals[2 * i + 1] - eq_0;
+            let v_0 = self.reduction_v_evals[2 * i];
+            let v_diff = self.reduction_v_evals[2 * i + 1] - v_0;
+            for (p, point) in local_vals.iter_mut().zip(&points) {
+                *p += (eq_0 + eq_diff * point) * (v_0 + v_diff * point);
+            }
+        }
+
+        mpi_config.coef_combine_vec(&local_vals, &self.eq_evals_at_r_mpi_var)
+    }
+
+    #[inline]
+    pub(crate) fn receive_r_reduction(&mut self, var_idx: usize, r: F::ChallengeField) {
+        let eval_size = 1 << (self.input_var_num - var_idx - 1);
+        for bk in [&mut self.reduction_eq_evals, &mut self.reduction_v_evals] {
+            for i in 0..eval_size {
+                bk[i] = bk[2 * i] + (bk[2 * i + 1] - bk[2 * i]) * r;
+            }
+        }
+        self.r_reduction.push(r);
+    }
+
+    #[inline]
+    pub(crate) fn reduction_claim(&self, mpi_config: &impl MPIEngine) -> F::ChallengeField {
+        mpi_config.coef_combine_vec(&[self.reduction_v_evals[0]], &self.eq_evals_at_r_mpi_var)[0]
+    }
+
+    /// The vanilla sumcheck of the next layer reuses the eq evaluations at rz_0, r_simd and
+    /// r_mpi from the scratch pad when it receives two claims
+    #[inline]
+    pub(crate) fn prepare_next_layer(&mut self) {
+        let input_size = 1 << self.input_var_num;
+        self.sp.eq_evals_at_rx[..input_size].copy_from_slice(&self.slot_eq_evals[0]);
+        self.sp.eq_evals_at_r_simd0.copy_from_slice(
+            &EqPolynomial::<F::ChallengeField>::build_eq_x_r(&self.r_simd_var),
+        );
+        self.sp.eq_evals_at_r_mpi0.copy_from_slice(
+            &EqPolynomial::<F::ChallengeField>::build_eq_x_r(&self.r_mpi_var),
+        );
+    }
+}
@@ -4,8 +4,9 @@ use std::cmp::max;
 
 use arith::{ExtensionField, Field};
 use circuit::Circuit;
-use gkr_engine::{FieldEngine, FieldType};
+use gkr_engine::{FieldEngine, FieldType, ProofShape};
 
+#[cfg(feature = "prover")]
 #[derive(Clone, Debug, Default)]
 pub struct ProverScratchPad<F: FieldEngine> {
     pub v_evals: Vec<F::Field>,
@@ -27,6 +28,7 @@ pub struct ProverScratchPad<F: FieldEngine> {
     pub phase2_coef: F::ChallengeField,
 }
 
+#[cfg(feature = "prover")]
 impl<F: FieldEngine> ProverScratchPad<F> {
     /// Compute the smallest power of 2 that is greater than or equal to the square root of n.
     pub fn pow2_sqrt_ceil(n: usize) -> usize {
@@ -81,6 +83,7 @@ impl<F: FieldEngine> ProverScratchPad<F> {
 /// - `ScratchPadBatch` MUST outlive all references to the scratch pads.
 /// - The custom `Drop` impl forgets all inner Vecs before dropping the flat buffers.
 /// - The scratch pads MUST NOT be individually dropped or moved out of `sps`.
+#[cfg(feature = "prover")]
 pub struct ScratchPadBatch<F: FieldEngine> {
     /// Backing buffer for v_evals + hg_evals (F::Field elements)
     _f_buf: Vec<F::Field>,
@@ -92,6 +95,7 @@ pub struct ScratchPadBatch<F: FieldEngine> {
     sps: Vec<ProverScratchPad<F>>,
 }
 
+#[cfg(feature = "prover")]
 impl<F: FieldEngine> ScratchPadBatch<F> {
     /// Create a batch of `n` scratch pads sharing 3 flat buffers.
     ///
@@ -258,6 +262,7 @@ impl<F: FieldEngine> ScratchPadBatch<F> {
     }
 }
 
+#[cfg(feature = "prover")]
 impl<F: FieldEngine> Drop for ScratchPadBatch<F> {
     fn drop(&mut self) {
         // Forget all inner Vecs to prevent double-free.
@@ -309,7 +314,8 @@ pub struct VerifierScratchPad<F: FieldEngine> {
 }
 
 impl<F: FieldEngine> VerifierScratchPad<F> {
-    pub fn new(circuit: &Circuit<F>, mpi_world_size: usize) -> Self {
+    pub fn new(circuit: &Circuit<F>, proof_shape: &ProofShape) -> Self {
+        let mpi_world_size = proof_shape.world_size;
         let mut max_num_var = circuit
             .layers
             .iter()
@@ -319,7 +325,7 @@ impl<F: FieldEngine> VerifierScratchPad<F> {
         max_num_var = max(max_num_var, circuit.log_input_size());
 
         let max_io_size = 1usize << max_num_var;
-        let simd_size = F::get_field_pack_size();
+        let simd_size = proof_shape.simd_size;
 
         let gf2_deg2_eval_coef = if F::FIELD_TYPE == FieldType::GF2Ext128 {
             (F::ChallengeField::X - F::ChallengeField::one())
@@ -1,7 +1,15 @@
+#[cfg(feature = "prover")]
 use circuit::CircuitLayer;
+#[cfg(feature = "prover")]
 use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, MPIEngine, Transcript};
+#[cfg(feature = "prover")]
+use utils::timer::Timer;
 
-use crate::{prover_helper::SumcheckGkrVanillaHelper, utils::transcript_io, ProverScratchPad};
+#[cfg(feature = "prover")]
+use crate::{
+    needs_generalized_sumcheck, prover_helper::SumcheckGkrVanillaHelper,
+    sumcheck_prove_gkr_generalized_layer, utils::transcript_io, ProverScratchPad,
+};
 
 /// The degree of the polynomial for sumcheck, which is 2 for non-SIMD/MPI variables
 /// and 3 for SIMD/MPI variables.
@@ -9,10 +17,12 @@ pub const SUMCHECK_GKR_DEGREE: usize = 2;
 pub const SUMCHECK_GKR_SIMD_MPI_DEGREE: usize = 3;
 
 // FIXME
+#[cfg(feature = "prover")]
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 // essentially the prev level of challenge passes here, once this level is done, new challenge gets
 // written back into the prev space
+// with `compressed`, the round messages omit the evaluation at 1, see `append_round_message`
 pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     layer: &CircuitLayer<F>,
     challenge: &mut ExpanderDualVarChallenge<F>,
@@ -21,7 +31,14 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     sp: &mut ProverScratchPad<F>,
     mpi_config: &impl MPIEngine,
     is_output_layer: bool,
+    compressed: bool,
 ) -> (F::ChallengeField, Option<F::ChallengeField>) {
+    if needs_generalized_sumcheck(layer) {
+        return sumcheck_prove_gkr_generalized_layer(
+            layer, challenge, alpha, transcript, sp, mpi_config, compressed,
+        );
+    }
+
     let mut helper =
         SumcheckGkrVanillaHelper::new(layer, challenge, alpha, sp, mpi_config, is_output_layer);
 
@@ -29,10 +46,11 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     helper.prepare_mpi();
 
     // gkr phase 1 over variable x
+    let phase_one_timer = Timer::new("sumcheck phase one", mpi_config.is_root());
     helper.prepare_x_vals();
     for i_var in 0..helper.input_var_num {
         let evals = helper.poly_evals_at_rx(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
         helper.receive_rx(i_var, r);
         log::trace!("x i_var={i_var} evals: {evals:?} r: {r:?}");
     }
@@ -41,7 +59,7 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     for i_var in 0..helper.simd_var_num {
         let evals =
             helper.poly_evals_at_r_simd_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE, mpi_config);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
         helper.receive_r_simd_var(i_var, r);
         log::trace!("SIMD i_var={i_var} evals: {evals:?} r: {r:?}");
     }
@@ -49,24 +67,28 @@ pub fn sumcheck_prove_gkr_layer<F: FieldEngine, T: Transcript>(
     helper.prepare_mpi_var_vals(mpi_config);
     for i_var in 0..mpi_config.world_size().trailing_zeros() as usize {
         let evals = helper.poly_evals_at_r_mpi_var(i_var, SUMCHECK_GKR_SIMD_MPI_DEGREE);
-        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
         helper.receive_r_mpi_var(i_var, r);
     }
 
     let vx_claim = helper.vx_claim();
     transcript.append_field_element(&vx_claim);
+    phase_one_timer.stop();
 
     // gkr phase 2 over variable y
     let mut vy_claim = None;
     if !layer.structure_info.skip_sumcheck_phase_two {
+        let phase_two_timer = Timer::new("sumcheck phase two", mpi_config.is_root());
         helper.prepare_y_vals(mpi_config);
         for i_var in 0..helper.input_var_num {
             let evals = helper.poly_evals_at_ry(i_var, SUMCHECK_GKR_DEGREE, mpi_config);
-            let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, transcript);
+            let r =
+                transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
             helper.receive_ry(i_var, r);
         }
         vy_claim = Some(helper.vy_claim(mpi_config));
         transcript.append_field_element(&vy_claim.unwrap());
+        phase_two_timer.stop();
     }
 
     let rx = helper.rx;
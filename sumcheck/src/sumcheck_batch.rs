@@ -12,6 +12,7 @@ use polynomials::EqPolynomial;
 use rayon::prelude::*;
 
 use crate::{
+    append_round_message, needs_generalized_sumcheck,
     prover_helper::{
         product_gate::SumcheckProductGateHelper, simd_gate::SumcheckSimdProdGateHelper,
     },
@@ -22,11 +23,10 @@ use crate::{
 #[inline]
 fn transcript_io_local<F: arith::ExtensionField, T: Transcript>(
     evals: &[F],
+    compressed: bool,
     transcript: &mut T,
 ) -> F {
-    for p in evals {
-        transcript.append_field_element(p);
-    }
+    append_round_message(evals, compressed, transcript);
     transcript.generate_field_element::<F>()
 }
 
@@ -154,6 +154,9 @@ fn prepare_y_vals_for_instance<F: FieldEngine>(
 /// The challenge.r_mpi must have log2(N) elements on entry (from the previous
 /// layer or initial sampling). On exit, challenge is updated with new
 /// (rx, ry, r_simd, r_mpi).
+///
+/// With `compressed`, the round messages omit the evaluation at 1, like
+/// `sumcheck_prove_gkr_layer`.
 pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
     layers: &[&CircuitLayer<F>],
     challenge: &mut ExpanderDualVarChallenge<F>,
@@ -161,10 +164,15 @@ pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
     transcript: &mut T,
     scratch_pads: &mut [ProverScratchPad<F>],
     is_output_layer: bool,
+    compressed: bool,
 ) -> (F::ChallengeField, Option<F::ChallengeField>) {
     let n = layers.len();
     assert_eq!(n, scratch_pads.len());
     assert!(n > 1 && n.is_power_of_two());
+    assert!(
+        !needs_generalized_sumcheck(layers[0]),
+        "batch sumcheck only supports layers of add and mul gates"
+    );
 
     let input_var_num = layers[0].input_var_num;
     let simd_var_num = <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize;
@@ -272,7 +280,7 @@ pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
                     },
                 )
         };
-        let r = transcript_io_local::<F::ChallengeField, T>(&agg, transcript);
+        let r = transcript_io_local::<F::ChallengeField, T>(&agg, compressed, transcript);
         if use_seq {
             for ((helper, sp), layer) in xy_helpers
                 .iter_mut()
@@ -380,7 +388,7 @@ pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
                     },
                 )
         };
-        let r = transcript_io_local::<F::ChallengeField, T>(&agg, transcript);
+        let r = transcript_io_local::<F::ChallengeField, T>(&agg, compressed, transcript);
         if use_seq {
             for (helper, sp) in simd_helpers.iter_mut().zip(scratch_pads.iter_mut()) {
                 helper.receive_challenge(
@@ -430,7 +438,7 @@ pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
             &mut mpi_v_evals,
             &mut mpi_hg_evals,
         );
-        let r = transcript_io_local::<F::ChallengeField, T>(&evals, transcript);
+        let r = transcript_io_local::<F::ChallengeField, T>(&evals, compressed, transcript);
         mpi_helper.receive_challenge(
             i_var,
             r,
@@ -532,7 +540,7 @@ pub fn sumcheck_prove_gkr_layer_batch<F: FieldEngine, T: Transcript>(
                         },
                     )
             };
-            let r = transcript_io_local::<F::ChallengeField, T>(&agg, transcript);
+            let r = transcript_io_local::<F::ChallengeField, T>(&agg, compressed, transcript);
             if use_seq {
                 for ((helper, sp), layer) in xy_helpers_y
                     .iter_mut()
@@ -0,0 +1,233 @@
+//! Generalized GKR layer sumcheck, for layers with gates of arbitrary fan-in and degree.
+//!
+//! Every gate is brought into the shape `coef * V(x_1)^e_1 * ... * V(x_D)^e_D`, where D is the
+//! largest fan-in of the layer; gates of a smaller fan-in read input 0 with exponent 0 in their
+//! remaining slots. The sumcheck runs one phase per slot over the input variables, lane by lane,
+//! then one phase over the SIMD and one over the MPI variables, and leaves one claim per slot.
+//! The claims of all slots but the first are then folded into one by a degree 2 sumcheck,
+//! so that the next layer receives at most two claims, as from the vanilla sumcheck.
+//!
+//! The round degree of a slot phase is one more than the largest exponent in the slot, and the
+//! round degree of the SIMD and MPI phases is one more than the largest degree of a gate.
+
+#[cfg(feature = "prover")]
+use std::collections::BTreeMap;
+
+use arith::Field;
+use circuit::{CircuitLayer, UNI_GATE_TYPE_POW1, UNI_GATE_TYPE_POW5};
+use gkr_engine::FieldEngine;
+#[cfg(feature = "prover")]
+use gkr_engine::{ExpanderDualVarChallenge, MPIEngine, Transcript};
+
+#[cfg(feature = "prover")]
+use crate::{prover_helper::SumcheckGkrGeneralizedHelper, utils::transcript_io, ProverScratchPad};
+
+/// A gate of a generalized layer, with exactly one input and exponent per slot
+#[derive(Debug, Clone)]
+pub struct GeneralizedGate<F: FieldEngine> {
+    pub i_ids: Vec<usize>,
+    pub exponents: Vec<usize>,
+    pub o_id: usize,
+    pub coef: F::CircuitField,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneralizedLayerGates<F: FieldEngine> {
+    pub slot_num: usize,
+    pub gates: Vec<GeneralizedGate<F>>,
+}
+
+/// Whether the layer has gates beyond the add and mul gates of the vanilla GKR sumcheck
+#[inline]
+pub fn needs_generalized_sumcheck<F: FieldEngine>(layer: &CircuitLayer<F>) -> bool {
+    !layer.uni.is_empty() || !layer.monomial.is_empty()
+}
+
+impl<F: FieldEngine> GeneralizedLayerGates<F> {
+    /// Constant gates are not included, they are accounted for before the sumcheck
+    pub fn new(layer: &CircuitLayer<F>) -> Self {
+        let mut gates = vec![];
+        for g in &layer.mul {
+            gates.push((g.i_ids.to_vec(), vec![1, 1], g.o_id, g.coef));
+        }
+        for g in &layer.add {
+            gates.push((g.i_ids.to_vec(), vec![1], g.o_id, g.coef));
+        }
+        for g in &layer.uni {
+            let exponent = match g.gate_type {
+                UNI_GATE_TYPE_POW5 => 5,
+                UNI_GATE_TYPE_POW1 => 1,
+                _ => panic!("Unknown gate type: {}", g.gate_type),
+            };
+            gates.push((g.i_ids.to_vec(), vec![exponent], g.o_id, g.coef));
+        }
+        for g in &layer.monomial {
+            assert!(
+                !g.i_ids.is_empty() && g.exponents.iter().all(|e| *e > 0),
+                "a monomial gate needs at least one input and positive exponents"
+            );
+            gates.push((g.i_ids.clone(), g.exponents.clone(), g.o_id, g.coef));
+        }
+
+        let slot_num = gates.iter().map(|g| g.0.len()).max().unwrap_or(1);
+        let gates = gates
+            .into_iter()
+            .map(|(mut i_ids, mut exponents, o_id, coef)| {
+                i_ids.resize(slot_num, 0);
+                exponents.resize(slot_num, 0);
+                GeneralizedGate {
+                    i_ids,
+                    exponents,
+                    o_id,
+                    coef,
+                }
+            })
+            .collect();
+
+        Self { slot_num, gates }
+    }
+
+    /// Round degree of the phase over the input variables of `slot`
+    #[inline]
+    pub fn slot_degree(&self, slot: usize) -> usize {
+        self.gates
+            .iter()
+            .map(|g| g.exponents[slot])
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    /// Round degree of the phases over the SIMD and MPI variables
+    #[inline]
+    pub fn simd_mpi_degree(&self) -> usize {
+        self.gates
+            .iter()
+            .map(|g| g.exponents.iter().sum::<usize>())
+            .max()
+            .unwrap_or(0)
+            + 1
+    }
+
+    /// Whether the claims of the slots past the first are folded into one
+    #[inline]
+    pub fn reduces_claims(&self) -> bool {
+        self.slot_num > 2
+    }
+
+    /// Sum over the gates of `eq(rz, o) * coef * prod_k eq(r_k, i_k) * v_k^e_k`,
+    /// given the eq evaluations at the challenge of each slot and the claim `v_k` of each slot
+    pub fn evaluate(
+        &self,
+        eq_evals_at_rz: &[F::ChallengeField],
+        eq_evals_at_slots: &[Vec<F::ChallengeField>],
+        claims: &[F::ChallengeField],
+    ) -> F::ChallengeField {
+        self.gates
+            .iter()
+            .map(|g| {
+                g.i_ids
+                    .iter()
+                    .zip(&g.exponents)
+                    .zip(eq_evals_at_slots.iter().zip(claims))
+                    .fold(eq_evals_at_rz[g.o_id] * g.coef, |acc, ((i, e), (eq, v))| {
+                        acc * eq[*i] * v.exp(*e as u128)
+                    })
+            })
+            .sum()
+    }
+
+    /// Sum of `eq(rz, o) * coef * prod_k eq(r_k, i_k)` over the gates of each exponent vector
+    #[cfg(feature = "prover")]
+    pub(crate) fn wiring_by_exponents(
+        &self,
+        wirings: &[F::ChallengeField],
+    ) -> Vec<(Vec<usize>, F::ChallengeField)> {
+        let mut classes = BTreeMap::<Vec<usize>, F::ChallengeField>::new();
+        for (g, wiring) in self.gates.iter().zip(wirings) {
+            *classes
+                .entry(g.exponents.clone())
+                .or_insert(F::ChallengeField::ZERO) += *wiring;
+        }
+        classes.into_iter().collect()
+    }
+}
+
+/// Prove a layer with the generalized sumcheck, see the module documentation.
+/// The challenge of the first slot becomes `rz_0` and the folded challenge of the other slots
+/// becomes `rz_1` of the next layer.
+#[cfg(feature = "prover")]
+#[allow(clippy::too_many_arguments)]
+pub fn sumcheck_prove_gkr_generalized_layer<F: FieldEngine, T: Transcript>(
+    layer: &CircuitLayer<F>,
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    alpha: Option<F::ChallengeField>,
+    transcript: &mut T,
+    sp: &mut ProverScratchPad<F>,
+    mpi_config: &impl MPIEngine,
+    compressed: bool,
+) -> (F::ChallengeField, Option<F::ChallengeField>) {
+    let mut helper = SumcheckGkrGeneralizedHelper::new(layer, challenge, alpha, sp, mpi_config);
+    let slot_num = helper.gates.slot_num;
+
+    for slot in 0..slot_num {
+        let degree = helper.gates.slot_degree(slot);
+        helper.prepare_slot_vals(slot);
+        for i_var in 0..helper.input_var_num {
+            let evals = helper.poly_evals_at_r_slot(i_var, degree, mpi_config);
+            let r =
+                transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
+            helper.receive_r_slot(slot, i_var, r);
+        }
+        helper.finish_slot(slot);
+    }
+
+    let degree = helper.gates.simd_mpi_degree();
+    helper.prepare_simd_var_vals();
+    for i_var in 0..helper.simd_var_num {
+        let evals = helper.poly_evals_at_r_simd_var(i_var, degree, mpi_config);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
+        helper.receive_r_simd_var(i_var, r);
+    }
+
+    helper.prepare_mpi_var_vals(mpi_config);
+    for i_var in 0..helper.mpi_var_num {
+        let evals = helper.poly_evals_at_r_mpi_var(i_var, degree);
+        let r = transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
+        helper.receive_r_mpi_var(i_var, r);
+    }
+
+    let claims = helper.slot_claims();
+    claims
+        .iter()
+        .for_each(|claim| transcript.append_field_element(claim));
+
+    let (rz_1, claim_1) = if helper.gates.reduces_claims() {
+        let mut beta = transcript.generate_field_element::<F::ChallengeField>();
+        mpi_config.root_broadcast_f(&mut beta);
+
+        helper.prepare_reduction_vals(beta);
+        for i_var in 0..helper.input_var_num {
+            let evals = helper.poly_evals_at_r_reduction(i_var, mpi_config);
+            let r =
+                transcript_io::<F::ChallengeField, T>(mpi_config, &evals, compressed, transcript);
+            helper.receive_r_reduction(i_var, r);
+        }
+        let claim = helper.reduction_claim(mpi_config);
+        transcript.append_field_element(&claim);
+        (Some(helper.r_reduction.clone()), Some(claim))
+    } else if slot_num == 2 {
+        (Some(helper.r_slots[1].clone()), Some(claims[1]))
+    } else {
+        (None, None)
+    };
+
+    helper.prepare_next_layer();
+
+    let rx = helper.r_slots.swap_remove(0);
+    let r_simd = helper.r_simd_var;
+    let r_mpi = helper.r_mpi_var;
+
+    *challenge = ExpanderDualVarChallenge::new(rx, rz_1, r_simd, r_mpi);
+    (claims[0], claim_1)
+}
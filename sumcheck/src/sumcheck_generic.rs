@@ -1,13 +1,20 @@
 //! This module implements the SumCheck protocol for verifying the sum of
 //! evaluations of a set of multi-linear polynomial over the hypercube {0,1}^n.
-//! This is hardcoded for sum of product of MLEs for simplicity and efficiency.
+//! This is hardcoded for sum of product of MLEs for simplicity and efficiency,
+//! with `VirtualPolynomial` for sums of products of more MLEs, of any degree.
+//! A `VirtualPolynomial` over a `SimdField` is evaluated over the packed field, with scalar
+//! challenges, until only the variables of the lanes are left.
 // Credit: code adopted from https://github.com/EspressoSystems/hyperplonk/ with modification
 
 use arith::Field;
+#[cfg(feature = "prover")]
+use arith::SimdField;
 use gkr_engine::Transcript;
-use polynomials::SumOfProductsPoly;
+#[cfg(feature = "prover")]
+use polynomials::{SumOfProductsPoly, VirtualPolynomial};
 use serdes::ExpSerde;
 
+#[cfg(feature = "prover")]
 mod prover;
 mod verifier;
 
@@ -39,8 +46,18 @@ pub struct IOPProverMessage<F: Field> {
     pub(crate) evaluations: Vec<F>,
 }
 
-/// Prover State of a PolyIOP.
-pub struct IOPProverState<F: Field> {
+#[cfg(feature = "prover")]
+impl<F: Field> IOPProverMessage<F> {
+    /// Drop the evaluation at 1, which the verifier recovers from the claimed sum
+    pub(crate) fn compress(mut self) -> Self {
+        self.evaluations.remove(1);
+        self
+    }
+}
+
+/// Prover State of a PolyIOP, over a `SumOfProductsPoly` or a `VirtualPolynomial`.
+#[cfg(feature = "prover")]
+pub struct IOPProverState<F: Field, P = SumOfProductsPoly<F>> {
     /// sampled randomness given by the verifier
     pub challenges: Vec<F>,
     /// number of variables in the polynomial
@@ -49,10 +66,12 @@ pub struct IOPProverState<F: Field> {
     pub(crate) round: usize,
     /// list of MLE poly
     // todo: change this to reference
-    pub mle_list: SumOfProductsPoly<F>,
+    pub mle_list: P,
 
+    /// only used with `SumOfProductsPoly`
     pub init_sum_of_vals: Vec<F>,
 
+    /// only used with `SumOfProductsPoly`
     pub eq_prefix: Vec<F>,
 }
 
@@ -60,8 +79,10 @@ pub struct IOPProverState<F: Field> {
 pub struct IOPVerifierState<F: Field> {
     pub(crate) round: usize,
     pub(crate) num_vars: usize,
-    // pub(crate) max_degree: usize,
+    pub(crate) max_degree: usize,
     pub(crate) finished: bool,
+    /// whether the prover messages omit the evaluation at 1
+    pub(crate) compressed: bool,
     /// a list storing the univariate polynomial in evaluation form sent by the
     /// prover at each round
     pub(crate) polynomials_received: Vec<Vec<F>>,
@@ -85,27 +106,193 @@ pub struct SumCheck<F: Field> {
 }
 
 impl<F: Field> SumCheck<F> {
-    /// Extract sum from the proof
+    /// Extract sum from the proof, which is not possible for a compressed proof
     pub fn extract_sum(proof: &IOPProof<F>) -> F {
         proof.proofs[0].evaluations[0] + proof.proofs[0].evaluations[1]
     }
 
+    /// Verify the claimed sum using the proof
+    pub fn verify(
+        claimed_sum: F,
+        proof: &IOPProof<F>,
+        num_vars: usize,
+        transcript: &mut impl Transcript,
+    ) -> (bool, SumCheckSubClaim<F>) {
+        let verifier_state = IOPVerifierState::verifier_init(num_vars);
+        Self::verify_internal(claimed_sum, proof, verifier_state, transcript)
+    }
+
+    /// Verify the claimed sum using a proof from `prove_compressed`
+    pub fn verify_compressed(
+        claimed_sum: F,
+        proof: &IOPProof<F>,
+        num_vars: usize,
+        transcript: &mut impl Transcript,
+    ) -> (bool, SumCheckSubClaim<F>) {
+        let verifier_state = IOPVerifierState::verifier_init_compressed(num_vars);
+        Self::verify_internal(claimed_sum, proof, verifier_state, transcript)
+    }
+
+    /// Verify the claimed sum of a `VirtualPolynomial` of the given degree using the proof
+    pub fn verify_virtual(
+        claimed_sum: F,
+        proof: &IOPProof<F>,
+        num_vars: usize,
+        degree: usize,
+        transcript: &mut impl Transcript,
+    ) -> (bool, SumCheckSubClaim<F>) {
+        let verifier_state = IOPVerifierState::verifier_init_with_degree(num_vars, degree);
+        Self::verify_internal(claimed_sum, proof, verifier_state, transcript)
+    }
+
+    /// Verify the claimed sum of a `VirtualPolynomial` using a proof from
+    /// `prove_virtual_compressed`
+    pub fn verify_virtual_compressed(
+        claimed_sum: F,
+        proof: &IOPProof<F>,
+        num_vars: usize,
+        degree: usize,
+        transcript: &mut impl Transcript,
+    ) -> (bool, SumCheckSubClaim<F>) {
+        let verifier_state = IOPVerifierState {
+            compressed: true,
+            ..IOPVerifierState::verifier_init_with_degree(num_vars, degree)
+        };
+        Self::verify_internal(claimed_sum, proof, verifier_state, transcript)
+    }
+
+    fn verify_internal(
+        claimed_sum: F,
+        proof: &IOPProof<F>,
+        mut verifier_state: IOPVerifierState<F>,
+        transcript: &mut impl Transcript,
+    ) -> (bool, SumCheckSubClaim<F>) {
+        let num_vars = verifier_state.num_vars;
+        for i in 0..num_vars {
+            let prover_msg = proof.proofs.get(i).expect("proof is incomplete");
+            transcript.append_serializable_data(prover_msg);
+            IOPVerifierState::verify_round_and_update_state(
+                &mut verifier_state,
+                prover_msg,
+                transcript,
+            );
+        }
+
+        IOPVerifierState::check_and_generate_subclaim(&verifier_state, &claimed_sum)
+    }
+}
+
+#[cfg(feature = "prover")]
+impl<F: Field> SumCheck<F> {
     /// Generate proof of the sum of polynomial over {0,1}^`num_vars`
     ///
     /// The polynomial is represented in the form of a VirtualPolynomial.
     /// Takes ownership of poly_list to avoid cloning internally.
-    pub fn prove(
+    pub fn prove(poly_list: SumOfProductsPoly<F>, transcript: &mut impl Transcript) -> IOPProof<F> {
+        Self::prove_internal(poly_list, false, transcript)
+    }
+
+    /// Same as `prove`, but the round messages omit the evaluation at 1, which saves one
+    /// field element per round. The proof is verified by `verify_compressed`.
+    pub fn prove_compressed(
         poly_list: SumOfProductsPoly<F>,
         transcript: &mut impl Transcript,
     ) -> IOPProof<F> {
-        let num_vars = poly_list.num_vars();
+        Self::prove_internal(poly_list, true, transcript)
+    }
 
-        let mut prover_state = IOPProverState::prover_init_owned(poly_list);
+    /// Generate proof of the sum of a `VirtualPolynomial` over {0,1}^`num_vars`.
+    /// The round messages have `poly.degree() + 1` evaluations.
+    pub fn prove_virtual(
+        poly: &VirtualPolynomial<F>,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        Self::prove_virtual_internal(poly, false, transcript)
+    }
+
+    /// Same as `prove_virtual`, with the compressed round messages of `prove_compressed`
+    pub fn prove_virtual_compressed(
+        poly: &VirtualPolynomial<F>,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        Self::prove_virtual_internal(poly, true, transcript)
+    }
+
+    /// Generate proof of the sum of a `VirtualPolynomial` over a `SimdField`, over
+    /// {0,1}^`poly.unpacked_num_vars()`: the proof is that of `prove_virtual` for `poly.unpack()`.
+    pub fn prove_virtual_simd<S: SimdField<Scalar = F>>(
+        poly: &VirtualPolynomial<S>,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        Self::prove_virtual_simd_internal(poly, false, transcript)
+    }
+
+    /// Same as `prove_virtual_simd`, with the compressed round messages of `prove_compressed`
+    pub fn prove_virtual_simd_compressed<S: SimdField<Scalar = F>>(
+        poly: &VirtualPolynomial<S>,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        Self::prove_virtual_simd_internal(poly, true, transcript)
+    }
+
+    fn prove_virtual_simd_internal<S: SimdField<Scalar = F>>(
+        poly: &VirtualPolynomial<S>,
+        compressed: bool,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        let num_vars = poly.unpacked_num_vars();
+
+        let mut simd_prover_state = IOPProverState::prover_init_virtual_simd(poly);
+        let mut challenge = None;
+        let mut prover_msgs = Vec::with_capacity(num_vars);
+        for _ in 0..poly.num_vars {
+            let mut prover_msg =
+                simd_prover_state.prove_virtual_simd_round_and_update_state(&challenge);
+            if compressed {
+                prover_msg = prover_msg.compress();
+            }
+            transcript.append_serializable_data(&prover_msg);
+            prover_msgs.push(prover_msg);
+            challenge = Some(transcript.generate_field_element::<F>());
+        }
+
+        // the variables of the lanes
+        let mut prover_state = simd_prover_state.into_unpacked();
+        for _ in poly.num_vars..num_vars {
+            let mut prover_msg = prover_state.prove_virtual_round_and_update_state(&challenge);
+            if compressed {
+                prover_msg = prover_msg.compress();
+            }
+            transcript.append_serializable_data(&prover_msg);
+            prover_msgs.push(prover_msg);
+            challenge = Some(transcript.generate_field_element::<F>());
+        }
+        // pushing the last challenge point to the state
+        if let Some(p) = challenge {
+            prover_state.challenges.push(p)
+        };
+
+        IOPProof {
+            point: prover_state.challenges,
+            proofs: prover_msgs,
+        }
+    }
+
+    fn prove_virtual_internal(
+        poly: &VirtualPolynomial<F>,
+        compressed: bool,
+        transcript: &mut impl Transcript,
+    ) -> IOPProof<F> {
+        let num_vars = poly.num_vars;
+
+        let mut prover_state = IOPProverState::prover_init_virtual(poly);
         let mut challenge = None;
         let mut prover_msgs = Vec::with_capacity(num_vars);
         for _ in 0..num_vars {
-            let prover_msg =
-                IOPProverState::prove_round_and_update_state(&mut prover_state, &challenge);
+            let mut prover_msg = prover_state.prove_virtual_round_and_update_state(&challenge);
+            if compressed {
+                prover_msg = prover_msg.compress();
+            }
             transcript.append_serializable_data(&prover_msg);
             prover_msgs.push(prover_msg);
             challenge = Some(transcript.generate_field_element::<F>());
@@ -121,24 +308,34 @@ impl<F: Field> SumCheck<F> {
         }
     }
 
-    /// Verify the claimed sum using the proof
-    pub fn verify(
-        claimed_sum: F,
-        proof: &IOPProof<F>,
-        num_vars: usize,
+    fn prove_internal(
+        poly_list: SumOfProductsPoly<F>,
+        compressed: bool,
         transcript: &mut impl Transcript,
-    ) -> (bool, SumCheckSubClaim<F>) {
-        let mut verifier_state = IOPVerifierState::verifier_init(num_vars);
-        for i in 0..num_vars {
-            let prover_msg = proof.proofs.get(i).expect("proof is incomplete");
-            transcript.append_serializable_data(prover_msg);
-            IOPVerifierState::verify_round_and_update_state(
-                &mut verifier_state,
-                prover_msg,
-                transcript,
-            );
+    ) -> IOPProof<F> {
+        let num_vars = poly_list.num_vars();
+
+        let mut prover_state = IOPProverState::prover_init_owned(poly_list);
+        let mut challenge = None;
+        let mut prover_msgs = Vec::with_capacity(num_vars);
+        for _ in 0..num_vars {
+            let mut prover_msg =
+                IOPProverState::prove_round_and_update_state(&mut prover_state, &challenge);
+            if compressed {
+                prover_msg = prover_msg.compress();
+            }
+            transcript.append_serializable_data(&prover_msg);
+            prover_msgs.push(prover_msg);
+            challenge = Some(transcript.generate_field_element::<F>());
         }
+        // pushing the last challenge point to the state
+        if let Some(p) = challenge {
+            prover_state.challenges.push(p)
+        };
 
-        IOPVerifierState::check_and_generate_subclaim(&verifier_state, &claimed_sum)
+        IOPProof {
+            point: prover_state.challenges,
+            proofs: prover_msgs,
+        }
     }
 }
@@ -1,7 +1,10 @@
-use arith::Field;
-use polynomials::{MultilinearExtension, SumOfProductsPoly};
+use std::sync::Arc;
+
+use arith::{Field, SimdField};
+use polynomials::{MultilinearExtension, SumOfProductsPoly, VirtualPolynomial};
 use rayon::iter::{
-    IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelIterator,
 };
 
 use super::{IOPProverMessage, IOPProverState};
@@ -211,3 +214,163 @@ impl<F: Field> IOPProverState<F> {
             });
     }
 }
+
+impl<F: Field> IOPProverState<F, VirtualPolynomial<F>> {
+    /// Initialize the prover state to argue for the sum of a `VirtualPolynomial`.
+    /// The polynomials are only copied when their first variable is fixed.
+    pub fn prover_init_virtual(polynomial: &VirtualPolynomial<F>) -> Self {
+        assert!(polynomial.degree() > 0, "the polynomial has no product");
+        let num_vars = polynomial.num_vars;
+        Self {
+            challenges: Vec::with_capacity(num_vars),
+            round: 0,
+            init_num_vars: num_vars,
+            mle_list: polynomial.clone(),
+            init_sum_of_vals: vec![],
+            eq_prefix: vec![],
+        }
+    }
+
+    /// Receive message from verifier, generate prover message, and proceed to
+    /// next round.
+    ///
+    /// The message has the evaluations at 0, 1, ..., degree, see `virtual_round_evaluations`.
+    pub fn prove_virtual_round_and_update_state(
+        &mut self,
+        challenge: &Option<F>,
+    ) -> IOPProverMessage<F> {
+        if self.round >= self.init_num_vars {
+            panic!("prover is not active")
+        }
+
+        if let Some(chal) = challenge {
+            if self.round == 0 {
+                panic!("first round should not have a challenge");
+            }
+            self.challenges.push(*chal);
+
+            let r = self.challenges[self.round - 1];
+            self.mle_list
+                .flattened_ml_extensions
+                .par_iter_mut()
+                .for_each(|mle| Arc::make_mut(mle).fix_top_variable(r));
+        } else if self.round > 0 {
+            panic!("verifier message is empty")
+        }
+
+        self.round += 1;
+
+        let len = 1 << (self.init_num_vars - self.round);
+        IOPProverMessage {
+            evaluations: virtual_round_evaluations(&self.mle_list, len),
+        }
+    }
+}
+
+impl<S: SimdField> IOPProverState<S::Scalar, VirtualPolynomial<S>> {
+    /// Initialize the prover state to argue for the sum of a `VirtualPolynomial` over a
+    /// `SimdField`, including the sum over the lanes.
+    /// The rounds of the packed variables are run by `prove_virtual_simd_round_and_update_state`,
+    /// then `into_unpacked` gives the state for the rounds of the lanes.
+    pub fn prover_init_virtual_simd(polynomial: &VirtualPolynomial<S>) -> Self {
+        assert!(polynomial.degree() > 0, "the polynomial has no product");
+        let num_vars = polynomial.unpacked_num_vars();
+        Self {
+            challenges: Vec::with_capacity(num_vars),
+            round: 0,
+            init_num_vars: num_vars,
+            mle_list: polynomial.clone(),
+            init_sum_of_vals: vec![],
+            eq_prefix: vec![],
+        }
+    }
+
+    /// Same as `prove_virtual_round_and_update_state`, with the products evaluated over the
+    /// packed field and the lanes summed into the message.
+    pub fn prove_virtual_simd_round_and_update_state(
+        &mut self,
+        challenge: &Option<S::Scalar>,
+    ) -> IOPProverMessage<S::Scalar> {
+        if self.round >= self.mle_list.num_vars {
+            panic!("the packed variables are all fixed")
+        }
+
+        if let Some(chal) = challenge {
+            if self.round == 0 {
+                panic!("first round should not have a challenge");
+            }
+            self.challenges.push(*chal);
+
+            let r = self.challenges[self.round - 1];
+            self.mle_list
+                .flattened_ml_extensions
+                .par_iter_mut()
+                .for_each(|mle| Arc::make_mut(mle).fix_top_variable_scalar(&r));
+        } else if self.round > 0 {
+            panic!("verifier message is empty")
+        }
+
+        self.round += 1;
+
+        let len = 1 << (self.mle_list.num_vars - self.round);
+        IOPProverMessage {
+            evaluations: virtual_round_evaluations(&self.mle_list, len)
+                .iter()
+                .map(|eval| eval.horizontal_sum())
+                .collect(),
+        }
+    }
+
+    /// Unpack the lanes of the polynomials, to run the remaining rounds with
+    /// `prove_virtual_round_and_update_state`.
+    pub fn into_unpacked(mut self) -> IOPProverState<S::Scalar, VirtualPolynomial<S::Scalar>> {
+        // the challenge of the last round is not applied yet
+        self.mle_list.num_vars = self.mle_list.flattened_ml_extensions[0].get_num_vars();
+        IOPProverState {
+            challenges: self.challenges,
+            round: self.round,
+            init_num_vars: self.init_num_vars,
+            mle_list: self.mle_list.unpack(),
+            init_sum_of_vals: vec![],
+            eq_prefix: vec![],
+        }
+    }
+}
+
+/// The evaluations at 0, 1, ..., degree of the polynomial along its top variable, summed over
+/// the `len` points of the other variables.
+/// Each polynomial is evaluated at the consecutive points by repeated additions of its slope,
+/// so that the only multiplications are those of the products.
+fn virtual_round_evaluations<F: Field>(poly: &VirtualPolynomial<F>, len: usize) -> Vec<F> {
+    let degree = poly.degree();
+    let mles = &poly.flattened_ml_extensions;
+
+    (0..len)
+        .into_par_iter()
+        .fold(
+            || (vec![F::zero(); degree + 1], vec![F::zero(); degree + 1]),
+            |(mut acc, mut prod), b| {
+                for (coef, indices) in poly.products.iter() {
+                    prod.fill(*coef);
+                    for &i in indices {
+                        let mut v = mles[i][b];
+                        let step = mles[i][b + len] - v;
+                        for p in prod.iter_mut() {
+                            *p *= v;
+                            v += step;
+                        }
+                    }
+                    acc.iter_mut().zip(prod.iter()).for_each(|(a, p)| *a += p);
+                }
+                (acc, prod)
+            },
+        )
+        .map(|(acc, _)| acc)
+        .reduce(
+            || vec![F::zero(); degree + 1],
+            |mut a, b| {
+                a.iter_mut().zip(b.iter()).for_each(|(a, b)| *a += b);
+                a
+            },
+        )
+}
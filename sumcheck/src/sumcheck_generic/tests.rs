@@ -1,9 +1,11 @@
 use super::*;
 
-use arith::Fr;
+use arith::{Fr, SimdField};
 use ark_std::{rand::thread_rng, test_rng};
 use gkr_hashers::{Keccak256hasher, SHA256hasher};
-use polynomials::{MultiLinearPoly, MutableMultilinearExtension};
+use mersenne31::{M31Ext3, M31Ext3x16};
+use polynomials::{MultiLinearPoly, MutableMultilinearExtension, VirtualPolynomial};
+use std::sync::Arc;
 use transcript::BytesHashTranscript;
 
 #[test]
@@ -154,3 +156,180 @@ fn test_sumcheck_generic_padding_helper<F: Field, T: Transcript>() {
 fn test_sumcheck_generic_padding() {
     test_sumcheck_generic_padding_helper::<Fr, BytesHashTranscript<SHA256hasher>>();
 }
+
+#[test]
+fn test_sumcheck_compressed() {
+    type T = BytesHashTranscript<SHA256hasher>;
+    let num_vars = 8;
+    let mut rng = test_rng();
+
+    let mle_list = SumOfProductsPoly {
+        f_and_g_pairs: (0..4)
+            .map(|_| {
+                (
+                    MultiLinearPoly::<Fr>::random(num_vars, &mut rng),
+                    MultiLinearPoly::<Fr>::random(num_vars, &mut rng),
+                )
+            })
+            .collect(),
+    };
+    let claimed_sum = mle_list.sum();
+
+    let proof = SumCheck::prove(mle_list.clone(), &mut T::new());
+    let compressed_proof = SumCheck::prove_compressed(mle_list.clone(), &mut T::new());
+    assert!(compressed_proof
+        .proofs
+        .iter()
+        .all(|msg| msg.evaluations.len() == 2));
+
+    // one field element less per round
+    let size = |proof: &IOPProof<Fr>| {
+        let mut buffer = vec![];
+        proof.serialize_into(&mut buffer).unwrap();
+        buffer.len()
+    };
+    assert_eq!(size(&proof) - size(&compressed_proof), num_vars * Fr::SIZE);
+
+    let (verified, subclaim) =
+        SumCheck::verify_compressed(claimed_sum, &compressed_proof, num_vars, &mut T::new());
+    assert!(verified, "sumcheck verification failed");
+    assert_eq!(
+        mle_list.evaluate(&subclaim.point),
+        subclaim.expected_evaluation
+    );
+
+    // the formats are not interchangeable
+    assert!(!SumCheck::verify(claimed_sum, &compressed_proof, num_vars, &mut T::new()).0);
+    assert!(!SumCheck::verify_compressed(claimed_sum, &proof, num_vars, &mut T::new()).0);
+
+    // a wrong sum is caught in the last round
+    let (verified, subclaim) = SumCheck::verify_compressed(
+        claimed_sum + Fr::ONE,
+        &compressed_proof,
+        num_vars,
+        &mut T::new(),
+    );
+    assert!(!verified || mle_list.evaluate(&subclaim.point) != subclaim.expected_evaluation);
+}
+
+#[test]
+fn test_sumcheck_virtual_polynomial() {
+    type T = BytesHashTranscript<SHA256hasher>;
+    let mut rng = test_rng();
+
+    for num_vars in 1..8 {
+        let mles = (0..4)
+            .map(|_| Arc::new(MultiLinearPoly::<Fr>::random(num_vars, &mut rng)))
+            .collect::<Vec<_>>();
+
+        // c0 * m0 * m1 * m2 + c1 * m3 + c2 * m0 * m0 * m3 * m1
+        let mut poly = VirtualPolynomial::new(num_vars);
+        poly.add_mle_list(mles[..3].to_vec(), Fr::random_unsafe(&mut rng));
+        poly.add_mle_list([mles[3].clone()], Fr::random_unsafe(&mut rng));
+        poly.add_mle_list(
+            [&mles[0], &mles[0], &mles[3], &mles[1]].map(Arc::clone),
+            Fr::random_unsafe(&mut rng),
+        );
+        let degree = poly.degree();
+        assert_eq!(degree, 4);
+        let claimed_sum = poly.sum();
+
+        let proof = SumCheck::prove_virtual(&poly, &mut T::new());
+        let (verified, subclaim) =
+            SumCheck::verify_virtual(claimed_sum, &proof, num_vars, degree, &mut T::new());
+        assert!(verified, "sumcheck verification failed");
+        assert_eq!(poly.evaluate(&subclaim.point), subclaim.expected_evaluation);
+
+        let compressed_proof = SumCheck::prove_virtual_compressed(&poly, &mut T::new());
+        let (verified, subclaim) = SumCheck::verify_virtual_compressed(
+            claimed_sum,
+            &compressed_proof,
+            num_vars,
+            degree,
+            &mut T::new(),
+        );
+        assert!(verified, "sumcheck verification failed");
+        assert_eq!(poly.evaluate(&subclaim.point), subclaim.expected_evaluation);
+
+        // the shared polynomials are untouched by the prover
+        assert_eq!(poly.sum(), claimed_sum);
+
+        // a wrong degree is rejected
+        assert!(
+            !SumCheck::verify_virtual(claimed_sum, &proof, num_vars, degree + 1, &mut T::new()).0
+        );
+    }
+}
+
+#[test]
+fn test_sumcheck_virtual_polynomial_of_pairs() {
+    type T = BytesHashTranscript<SHA256hasher>;
+    let num_vars = 6;
+    let mut rng = test_rng();
+
+    // a virtual polynomial of pairs gives the same proof as the sum of products
+    let mut mle_list = SumOfProductsPoly::new();
+    let mut poly = VirtualPolynomial::new(num_vars);
+    for _ in 0..3 {
+        let f = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        let g = MultiLinearPoly::<Fr>::random(num_vars, &mut rng);
+        poly.add_mle_list([Arc::new(f.clone()), Arc::new(g.clone())], Fr::ONE);
+        mle_list.add_pair(f, g);
+    }
+    assert_eq!(poly.sum(), mle_list.sum());
+
+    let proof = SumCheck::prove(mle_list, &mut T::new());
+    let virtual_proof = SumCheck::prove_virtual(&poly, &mut T::new());
+    assert_eq!(proof, virtual_proof);
+}
+
+#[test]
+fn test_sumcheck_virtual_polynomial_simd() {
+    type T = BytesHashTranscript<SHA256hasher>;
+    let mut rng = test_rng();
+
+    for num_vars in 0..5 {
+        let mles = (0..3)
+            .map(|_| Arc::new(MultiLinearPoly::<M31Ext3x16>::random(num_vars, &mut rng)))
+            .collect::<Vec<_>>();
+        let coef = |rng: &mut _| M31Ext3x16::pack_full(&M31Ext3::random_unsafe(rng));
+
+        // c0 * m0 * m1 * m2 + c1 * m2 * m2
+        let mut poly = VirtualPolynomial::new(num_vars);
+        poly.add_mle_list(mles.clone(), coef(&mut rng));
+        poly.add_mle_list([&mles[2], &mles[2]].map(Arc::clone), coef(&mut rng));
+        let unpacked = poly.unpack();
+        let unpacked_num_vars = poly.unpacked_num_vars();
+        assert_eq!(unpacked_num_vars, num_vars + 4);
+        let claimed_sum = unpacked.sum();
+
+        // the packed rounds give the same proof as the unpacked polynomial
+        let proof = SumCheck::prove_virtual_simd(&poly, &mut T::new());
+        assert_eq!(proof, SumCheck::prove_virtual(&unpacked, &mut T::new()));
+        let (verified, subclaim) =
+            SumCheck::verify_virtual(claimed_sum, &proof, unpacked_num_vars, 3, &mut T::new());
+        assert!(verified, "sumcheck verification failed");
+        assert_eq!(
+            unpacked.evaluate(&subclaim.point),
+            subclaim.expected_evaluation
+        );
+
+        let compressed_proof = SumCheck::prove_virtual_simd_compressed(&poly, &mut T::new());
+        assert_eq!(
+            compressed_proof,
+            SumCheck::prove_virtual_compressed(&unpacked, &mut T::new())
+        );
+        let (verified, subclaim) = SumCheck::verify_virtual_compressed(
+            claimed_sum,
+            &compressed_proof,
+            unpacked_num_vars,
+            3,
+            &mut T::new(),
+        );
+        assert!(verified, "sumcheck verification failed");
+        assert_eq!(
+            unpacked.evaluate(&subclaim.point),
+            subclaim.expected_evaluation
+        );
+    }
+}
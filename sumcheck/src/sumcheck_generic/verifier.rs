@@ -2,19 +2,37 @@ use arith::Field;
 use gkr_engine::Transcript;
 
 use super::{IOPProverMessage, IOPVerifierState, SumCheckSubClaim};
+use crate::decompress_round_message;
 
 impl<F: Field> IOPVerifierState<F> {
     /// Initialize the verifier's state.
     pub fn verifier_init(num_vars: usize) -> Self {
+        Self::verifier_init_with_degree(num_vars, 2)
+    }
+
+    /// Initialize the verifier's state for a polynomial of any degree, e.g. a
+    /// `VirtualPolynomial`.
+    pub fn verifier_init_with_degree(num_vars: usize, max_degree: usize) -> Self {
+        assert!(max_degree > 0);
         Self {
             round: 1,
             num_vars,
+            max_degree,
             finished: false,
+            compressed: false,
             polynomials_received: Vec::with_capacity(num_vars),
             challenges: Vec::with_capacity(num_vars),
         }
     }
 
+    /// Initialize the verifier's state for prover messages without the evaluation at 1.
+    pub fn verifier_init_compressed(num_vars: usize) -> Self {
+        Self {
+            compressed: true,
+            ..Self::verifier_init(num_vars)
+        }
+    }
+
     /// Run verifier for the current round, given a prover message.
     ///
     /// Note that `verify_round_and_update_state` only samples and stores
@@ -72,15 +90,35 @@ impl<F: Field> IOPVerifierState<F> {
         }
 
         let mut expected = *asserted_sum;
+        let message_len = if self.compressed {
+            self.max_degree
+        } else {
+            self.max_degree + 1
+        };
 
         for i in 0..self.num_vars {
-            let evals = &self.polynomials_received[i];
+            let received = &self.polynomials_received[i];
+            if received.len() != message_len {
+                return (false, SumCheckSubClaim::default());
+            }
+
+            // a compressed message omits h(1) = expected - h(0)
+            let evals = &if self.compressed {
+                decompress_round_message(received, expected)
+            } else {
+                received.clone()
+            };
 
             // check that the sum received from last round is correct
             if expected != evals[0] + evals[1] {
                 return (false, SumCheckSubClaim::default());
             }
 
+            if self.max_degree != 2 {
+                expected = interpolate_uni_poly(evals, self.challenges[i]);
+                continue;
+            }
+
             // the univariate polynomial f is received in its extrapolated form, i.e.,
             //   h(0) = evals[0], h(1) = evals[1], h(2) = evals[2]
             // that is, suppose h = h_0 + h_1 * x + h_2 * x^2, then
@@ -112,3 +150,25 @@ impl<F: Field> IOPVerifierState<F> {
         )
     }
 }
+
+/// Evaluate at `x` the univariate polynomial given by its evaluations at 0, 1, ..., n - 1
+fn interpolate_uni_poly<F: Field>(evals: &[F], x: F) -> F {
+    let points = (0..evals.len())
+        .map(|i| F::from(i as u32))
+        .collect::<Vec<_>>();
+
+    let mut v = F::zero();
+    for (i, eval) in evals.iter().enumerate() {
+        let mut numerator = F::one();
+        let mut denominator = F::one();
+        for (j, point) in points.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            numerator *= x - *point;
+            denominator *= points[i] - *point;
+        }
+        v += numerator * denominator.inv().unwrap() * eval;
+    }
+    v
+}
@@ -1,4 +1,6 @@
-use arith::{ExtensionField, SimdField};
+use arith::{ExtensionField, Field, SimdField};
+use gkr_engine::{FieldEngine, FieldType};
+#[cfg(feature = "prover")]
 use gkr_engine::{MPIEngine, Transcript};
 
 #[inline(always)]
@@ -22,21 +24,66 @@ pub fn unpack_and_combine<F: SimdField>(p: &F, coef: &[F::Scalar]) -> F::Scalar
         .sum()
 }
 
+/// The points 0, 1, ..., degree at which the round polynomials of the GKR sumcheck are evaluated.
+/// The integers collapse to 0 and 1 in GF2 extensions, which use 0, 1, x, x^2, ... instead.
+#[inline]
+pub fn gkr_round_eval_points<F: FieldEngine>(degree: usize) -> Vec<F::ChallengeField> {
+    let mut points = vec![F::ChallengeField::ZERO, F::ChallengeField::ONE];
+    if F::FIELD_TYPE == FieldType::GF2Ext128 {
+        let mut x = F::ChallengeField::X;
+        for _ in 2..=degree {
+            points.push(x);
+            x = x.mul_by_x();
+        }
+    } else {
+        points.extend((2..=degree).map(|i| F::ChallengeField::from(i as u32)));
+    }
+    points
+}
+
+/// Append the evaluations p(0), p(1), ... of a sumcheck round polynomial to the transcript.
+///
+/// A compressed round message omits p(1), which the verifier recovers from the claimed sum
+/// of the round, see `decompress_round_message`.
+#[cfg(feature = "prover")]
+#[inline(always)]
+pub fn append_round_message<F, T>(ps: &[F], compressed: bool, transcript: &mut T)
+where
+    F: ExtensionField,
+    T: Transcript,
+{
+    ps.iter()
+        .enumerate()
+        .filter(|(i, _)| !compressed || *i != 1)
+        .for_each(|(_, p)| transcript.append_field_element(p));
+}
+
+/// Recover p(0), p(1), ... from a compressed round message, using p(0) + p(1) = claimed sum
+#[inline(always)]
+pub fn decompress_round_message<F: Field>(message: &[F], claimed_sum: F) -> Vec<F> {
+    let mut ps = Vec::with_capacity(message.len() + 1);
+    ps.push(message[0]);
+    ps.push(claimed_sum - message[0]);
+    ps.extend_from_slice(&message[1..]);
+    ps
+}
+
 /// Transcript IO between sumcheck steps
+#[cfg(feature = "prover")]
 #[inline]
-pub fn transcript_io<F, T>(mpi_config: &impl MPIEngine, ps: &[F], transcript: &mut T) -> F
+pub fn transcript_io<F, T>(
+    mpi_config: &impl MPIEngine,
+    ps: &[F],
+    compressed: bool,
+    transcript: &mut T,
+) -> F
 where
     F: ExtensionField,
     T: Transcript,
 {
-    // 3 for x, y; 4 for simd var; 7 for pow5, 9 for pow7
-    assert!(
-        ps.len() == 3 || ps.len() == 4 || ps.len() == 7 || ps.len() == 9,
-        "Unexpected polynomial size"
-    );
-    for p in ps {
-        transcript.append_field_element(p);
-    }
+    // 3 for x, y; 4 for simd var; more for the higher degree gates of a generalized layer
+    assert!(ps.len() >= 3, "Unexpected polynomial size");
+    append_round_message(ps, compressed, transcript);
     let mut r = transcript.generate_field_element::<F>();
     mpi_config.root_broadcast_f(&mut r);
     r
@@ -3,7 +3,7 @@ use circuit::{CircuitLayer, CoefType, GateAdd, GateConst, GateMul, GateUni};
 use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, FieldType};
 use polynomials::EqPolynomial;
 
-use crate::{scratch_pad::VerifierScratchPad, unpack_and_combine};
+use crate::{gkr_round_eval_points, scratch_pad::VerifierScratchPad, unpack_and_combine};
 
 #[derive(Default)]
 pub struct GKRVerifierHelper<F: FieldEngine> {
@@ -281,6 +281,28 @@ impl<F: FieldEngine> GKRVerifierHelper<F> {
         Self::lag_eval(vals, x, sp)
     }
 
+    /// Evaluate a round polynomial of any degree, given at the points of
+    /// `gkr_round_eval_points`, used by the generalized GKR layer sumcheck
+    #[allow(clippy::needless_range_loop)]
+    pub fn degree_d_eval(vals: &[F::ChallengeField], x: F::ChallengeField) -> F::ChallengeField {
+        let evals = gkr_round_eval_points::<F>(vals.len() - 1);
+
+        let mut v = F::ChallengeField::ZERO;
+        for i in 0..vals.len() {
+            let mut numerator = F::ChallengeField::ONE;
+            let mut denominator = F::ChallengeField::ONE;
+            for j in 0..vals.len() {
+                if j == i {
+                    continue;
+                }
+                numerator *= x - evals[j];
+                denominator *= evals[i] - evals[j];
+            }
+            v += numerator * denominator.inv().unwrap() * vals[i];
+        }
+        v
+    }
+
     #[inline(always)]
     #[allow(clippy::needless_range_loop)]
     fn lag_eval(
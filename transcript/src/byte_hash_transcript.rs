@@ -5,7 +5,7 @@ use gkr_hashers::FiatShamirHasher;
 // for sufficient number of times, so that the FS hash has a sufficient circuit depth
 
 #[cfg(not(feature = "recursion"))]
-const PCS_DIGEST_LOOP: usize = 1000;
+pub const PCS_DIGEST_LOOP: usize = 1000;
 
 #[derive(Clone, Default, Debug, PartialEq)]
 pub struct BytesHashTranscript<H: FiatShamirHasher> {
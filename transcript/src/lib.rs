@@ -2,12 +2,16 @@
 
 mod byte_hash_transcript;
 pub use byte_hash_transcript::BytesHashTranscript;
+#[cfg(not(feature = "recursion"))]
+pub use byte_hash_transcript::PCS_DIGEST_LOOP;
 
 mod random_tape_transcript;
 pub use random_tape_transcript::RandomTape;
 
 mod transcript_utils;
-pub use transcript_utils::{transcript_root_broadcast, transcript_verifier_sync};
+pub use transcript_utils::{
+    transcript_absorb_statement, transcript_root_broadcast, transcript_verifier_sync,
+};
 
 #[cfg(test)]
 mod tests;
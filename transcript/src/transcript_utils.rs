@@ -1,4 +1,4 @@
-use gkr_engine::{MPIEngine, Transcript};
+use gkr_engine::{MPIEngine, ProofShape, Transcript};
 
 /// broadcast root transcript state. incurs an additional hash if self.world_size > 1
 pub fn transcript_root_broadcast(transcript: &mut impl Transcript, mpi_engine: &impl MPIEngine) {
@@ -14,12 +14,23 @@ pub fn transcript_root_broadcast(transcript: &mut impl Transcript, mpi_engine: &
 /// Note: Currently, the verifier is assumed to run on a single core with no mpi sync,
 /// the word 'sync' here refers to the verifier syncing up with the prover's transcript state,
 /// which is updated by 'transcript_root_broadcast' if mpi_size > 1.
-pub fn transcript_verifier_sync<T>(transcript: &mut T, mpi_world_size: usize)
+pub fn transcript_verifier_sync<T>(transcript: &mut T, proof_shape: &ProofShape)
 where
     T: Transcript,
 {
-    if mpi_world_size > 1 {
+    if !proof_shape.is_single_process() {
         let state = transcript.hash_and_return_state(); // Sync up the Fiat-Shamir randomness
         transcript.set_state(&state);
     }
 }
+
+/// Absorb data known to both the prover and the verifier, e.g. the statement being proven,
+/// into the Fiat-Shamir state without writing it to the proof.
+pub fn transcript_absorb_statement<T>(transcript: &mut T, statement: &[u8])
+where
+    T: Transcript,
+{
+    transcript.lock_proof();
+    transcript.append_u8_slice(statement);
+    transcript.unlock_proof();
+}
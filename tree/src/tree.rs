@@ -1,6 +1,7 @@
 use std::fmt;
 use std::fmt::{Debug, Display};
 use std::mem::forget;
+use std::ops::Range;
 
 use arith::{Field, SimdField};
 use ark_std::{end_timer, log2, start_timer};
@@ -8,6 +9,35 @@ use serdes::ExpSerde;
 
 use crate::{Leaf, Node, Path, RangePath, LEAF_BYTES};
 
+/// With the `parallel` feature, the levels of at least this many nodes are hashed on the
+/// rayon thread pool
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 256;
+
+/// Run `f` on every element of `slice` and its index
+fn for_each_mut<T: Send>(slice: &mut [T], f: impl Fn(usize, &mut T) + Send + Sync) {
+    #[cfg(feature = "parallel")]
+    if slice.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        slice.par_iter_mut().enumerate().for_each(|(i, e)| f(i, e));
+        return;
+    }
+
+    slice.iter_mut().enumerate().for_each(|(i, e)| f(i, e));
+}
+
+/// Run `f` on every index of `range`
+fn for_each_index(range: Range<usize>, f: impl Fn(usize) + Send + Sync) {
+    #[cfg(feature = "parallel")]
+    if range.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        range.into_par_iter().for_each(f);
+        return;
+    }
+
+    range.for_each(f);
+}
+
 /// Represents a Merkle tree structure.
 #[derive(Clone, Debug, PartialEq, Default, ExpSerde)]
 pub struct Tree {
@@ -72,12 +102,8 @@ impl Tree {
         }
 
         // CPU fallback
-        let mut leaf_nodes: Vec<Node> = if n_leaves >= 256 {
-            use rayon::prelude::*;
-            leaves.par_iter().map(|leaf| leaf.leaf_hash()).collect()
-        } else {
-            leaves.iter().map(|leaf| leaf.leaf_hash()).collect()
-        };
+        let mut leaf_nodes = vec![Node::default(); n_leaves];
+        for_each_mut(&mut leaf_nodes, |i, e| *e = leaves[i].leaf_hash());
         let mut nodes = Self::new_with_leaf_nodes(&leaf_nodes, tree_height);
         nodes.append(&mut leaf_nodes);
         Self { nodes, leaves }
@@ -157,38 +183,14 @@ impl Tree {
         {
             let start_index = level_indices.pop().unwrap();
             let upper_bound = left_child_index(start_index);
-            let level_size = upper_bound - start_index;
-
-            if level_size >= 256 {
-                // Parallel: each parent reads from leaf_nodes (separate array), no aliasing
-                use rayon::prelude::*;
-                non_leaf_nodes[start_index..upper_bound]
-                    .par_iter_mut()
-                    .enumerate()
-                    .for_each(|(i, e)| {
-                        let current_index = start_index + i;
-                        let left_leaf_index = left_child_index(current_index) - upper_bound;
-                        let right_leaf_index = left_leaf_index + 1;
-                        *e = Node::node_hash(
-                            &leaf_nodes[left_leaf_index],
-                            &leaf_nodes[right_leaf_index],
-                        );
-                    });
-            } else {
-                non_leaf_nodes
-                    .iter_mut()
-                    .enumerate()
-                    .take(upper_bound)
-                    .skip(start_index)
-                    .for_each(|(current_index, e)| {
-                        let left_leaf_index = left_child_index(current_index) - upper_bound;
-                        let right_leaf_index = left_leaf_index + 1;
-                        *e = Node::node_hash(
-                            &leaf_nodes[left_leaf_index],
-                            &leaf_nodes[right_leaf_index],
-                        );
-                    });
-            }
+
+            // each parent reads from leaf_nodes (separate array), no aliasing
+            for_each_mut(&mut non_leaf_nodes[start_index..upper_bound], |i, e| {
+                let current_index = start_index + i;
+                let left_leaf_index = left_child_index(current_index) - upper_bound;
+                let right_leaf_index = left_leaf_index + 1;
+                *e = Node::node_hash(&leaf_nodes[left_leaf_index], &leaf_nodes[right_leaf_index]);
+            });
         }
 
         // Compute the hash values for nodes in every other layer in the tree
@@ -198,28 +200,18 @@ impl Tree {
 
         for &start_index in &level_indices {
             let upper_bound = left_child_index(start_index);
-            let level_size = upper_bound - start_index;
-            if level_size >= 256 {
-                use rayon::prelude::*;
-                let ptr = non_leaf_nodes.as_mut_ptr() as usize;
-                (start_index..upper_bound).into_par_iter().for_each(|i| {
-                    let ptr = ptr as *mut Node;
-                    let left = left_child_index(i);
-                    let right = left + 1;
-                    // SAFETY: writing to index i, reading from left/right where left > i.
-                    // All writes in this level are to [start_index..upper_bound).
-                    // All reads are from [upper_bound..) which is not written in this level.
-                    unsafe {
-                        *ptr.add(i) = Node::node_hash(&*ptr.add(left), &*ptr.add(right));
-                    }
-                });
-            } else {
-                for i in start_index..upper_bound {
-                    let left = left_child_index(i);
-                    let right = left + 1;
-                    non_leaf_nodes[i] = Node::node_hash(&non_leaf_nodes[left], &non_leaf_nodes[right]);
+            let ptr = non_leaf_nodes.as_mut_ptr() as usize;
+            for_each_index(start_index..upper_bound, |i| {
+                let ptr = ptr as *mut Node;
+                let left = left_child_index(i);
+                let right = left + 1;
+                // SAFETY: writing to index i, reading from left/right where left > i.
+                // All writes in this level are to [start_index..upper_bound).
+                // All reads are from [upper_bound..) which is not written in this level.
+                unsafe {
+                    *ptr.add(i) = Node::node_hash(&*ptr.add(left), &*ptr.add(right));
                 }
-            }
+            });
         }
         end_timer!(timer);
 
@@ -0,0 +1,275 @@
+//! A span profiler, recording a tree of timed spans per thread.
+//!
+//! Recording is off until `start` is called on a thread, after which every `Timer` created on
+//! that thread opens a span, closed by `Timer::stop`. `finish` returns the recorded `Profile`,
+//! which can be exported to JSON or to the Chrome trace format (chrome://tracing, Perfetto).
+//! Spans record the duration and, on Linux, the memory high-water mark of the process when
+//! the span is closed.
+
+use std::{cell::RefCell, fmt::Write, time::Instant};
+
+/// A closed span, the spans of a profile are in pre-order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub name: String,
+    /// Number of enclosing spans
+    pub depth: usize,
+    /// Start of the span, in microseconds since the start of the recording
+    pub start_us: u64,
+    pub duration_us: u64,
+    /// Peak resident memory of the process in KiB, when available
+    pub memory_high_water_kb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub spans: Vec<Span>,
+}
+
+struct Recorder {
+    origin: Instant,
+    spans: Vec<Span>,
+    /// Indices of the open spans, innermost last
+    open: Vec<usize>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Start recording the spans of the current thread, discarding any unfinished recording
+pub fn start() {
+    RECORDER.with(|r| {
+        *r.borrow_mut() = Some(Recorder {
+            origin: Instant::now(),
+            spans: vec![],
+            open: vec![],
+        })
+    });
+}
+
+#[inline]
+pub fn is_recording() -> bool {
+    RECORDER.with(|r| r.borrow().is_some())
+}
+
+/// Stop recording on the current thread and return the profile, spans still open are closed.
+/// Returns `None` if the thread was not recording.
+pub fn finish() -> Option<Profile> {
+    RECORDER.with(|r| {
+        let mut recorder = r.borrow_mut().take()?;
+        while let Some(&id) = recorder.open.last() {
+            close(&mut recorder, id);
+        }
+        Some(Profile {
+            spans: recorder.spans,
+        })
+    })
+}
+
+/// Open a span, returns its id if the current thread is recording
+pub fn enter(name: &str) -> Option<usize> {
+    RECORDER.with(|r| {
+        let mut r = r.borrow_mut();
+        let recorder = r.as_mut()?;
+        let id = recorder.spans.len();
+        recorder.spans.push(Span {
+            name: name.to_string(),
+            depth: recorder.open.len(),
+            start_us: recorder.origin.elapsed().as_micros() as u64,
+            duration_us: 0,
+            memory_high_water_kb: None,
+        });
+        recorder.open.push(id);
+        Some(id)
+    })
+}
+
+/// Close the span `id`, along with the spans opened in it and left open.
+/// Spans that are not open are ignored.
+pub fn exit(id: usize) {
+    RECORDER.with(|r| {
+        if let Some(recorder) = r.borrow_mut().as_mut() {
+            if recorder.open.contains(&id) {
+                close(recorder, id);
+            }
+        }
+    });
+}
+
+fn close(recorder: &mut Recorder, id: usize) {
+    let now = recorder.origin.elapsed().as_micros() as u64;
+    let memory_high_water_kb = memory_high_water_kb();
+    while let Some(top) = recorder.open.pop() {
+        let span = &mut recorder.spans[top];
+        span.duration_us = now - span.start_us;
+        span.memory_high_water_kb = memory_high_water_kb;
+        if top == id {
+            break;
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn memory_high_water_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))
+        .and_then(|v| v.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn memory_high_water_kb() -> Option<u64> {
+    None
+}
+
+impl Profile {
+    /// Encode the profile, e.g. to gather the profiles of all processes.
+    /// Trailing zero bytes are ignored by `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&(self.spans.len() as u64).to_le_bytes());
+        for span in &self.spans {
+            bytes.extend_from_slice(&(span.name.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(span.name.as_bytes());
+            for v in [
+                span.depth as u64,
+                span.start_us,
+                span.duration_us,
+                span.memory_high_water_kb.unwrap_or(u64::MAX),
+            ] {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        fn read_u64(bytes: &mut &[u8]) -> Option<u64> {
+            let (v, rest) = bytes.split_first_chunk::<8>()?;
+            *bytes = rest;
+            Some(u64::from_le_bytes(*v))
+        }
+
+        let mut bytes = bytes;
+        let span_num = read_u64(&mut bytes)?;
+        let mut spans = vec![];
+        for _ in 0..span_num {
+            let name_len = read_u64(&mut bytes)? as usize;
+            if bytes.len() < name_len {
+                return None;
+            }
+            let (name, rest) = bytes.split_at(name_len);
+            let name = String::from_utf8(name.to_vec()).ok()?;
+            bytes = rest;
+            let depth = read_u64(&mut bytes)? as usize;
+            let start_us = read_u64(&mut bytes)?;
+            let duration_us = read_u64(&mut bytes)?;
+            let memory = read_u64(&mut bytes)?;
+            spans.push(Span {
+                name,
+                depth,
+                start_us,
+                duration_us,
+                memory_high_water_kb: (memory != u64::MAX).then_some(memory),
+            });
+        }
+        Some(Self { spans })
+    }
+}
+
+/// Export the profiles of all ranks, indexed by rank, as a JSON tree of spans:
+/// `{"ranks": [{"rank": 0, "spans": [{"name", "start_us", "duration_us",
+/// "memory_high_water_kb", "children": [...]}, ...]}, ...]}`
+pub fn to_json(profiles: &[Profile]) -> String {
+    let mut out = String::from("{\"ranks\":[");
+    for (rank, profile) in profiles.iter().enumerate() {
+        if rank > 0 {
+            out.push(',');
+        }
+        write!(out, "{{\"rank\":{rank},\"spans\":").unwrap();
+        write_span_tree(&profile.spans, &mut 0, 0, &mut out);
+        out.push('}');
+    }
+    out.push_str("]}");
+    out
+}
+
+/// Write the spans at `depth` starting from `spans[*i]`, and their children, as a JSON array
+fn write_span_tree(spans: &[Span], i: &mut usize, depth: usize, out: &mut String) {
+    out.push('[');
+    let mut first = true;
+    while *i < spans.len() && spans[*i].depth == depth {
+        let span = &spans[*i];
+        *i += 1;
+        if !first {
+            out.push(',');
+        }
+        first = false;
+
+        out.push_str("{\"name\":");
+        write_json_string(&span.name, out);
+        write!(
+            out,
+            ",\"start_us\":{},\"duration_us\":{},\"memory_high_water_kb\":{},\"children\":",
+            span.start_us,
+            span.duration_us,
+            json_option(span.memory_high_water_kb),
+        )
+        .unwrap();
+        write_span_tree(spans, i, depth + 1, out);
+        out.push('}');
+    }
+    out.push(']');
+}
+
+/// Export the profiles of all ranks, indexed by rank, in the Chrome trace event format,
+/// with one process per rank
+pub fn to_chrome_trace(profiles: &[Profile]) -> String {
+    let mut events = vec![];
+    for (rank, profile) in profiles.iter().enumerate() {
+        events.push(format!(
+            "{{\"name\":\"process_name\",\"ph\":\"M\",\"pid\":{rank},\"tid\":0,\
+             \"args\":{{\"name\":\"rank {rank}\"}}}}"
+        ));
+        for span in &profile.spans {
+            let mut event = String::from("{\"name\":");
+            write_json_string(&span.name, &mut event);
+            write!(
+                event,
+                ",\"cat\":\"expander\",\"ph\":\"X\",\"ts\":{},\"dur\":{},\"pid\":{rank},\"tid\":0,\
+                 \"args\":{{\"memory_high_water_kb\":{}}}}}",
+                span.start_us,
+                span.duration_us,
+                json_option(span.memory_high_water_kb),
+            )
+            .unwrap();
+            events.push(event);
+        }
+    }
+    format!(
+        "{{\"traceEvents\":[{}],\"displayTimeUnit\":\"ms\"}}",
+        events.join(",")
+    )
+}
+
+fn json_option(v: Option<u64>) -> String {
+    v.map_or("null".to_string(), |v| v.to_string())
+}
+
+fn write_json_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
@@ -1,5 +1,9 @@
 // credit: https://github.com/microsoft/Spartan/blob/master/src/timer.rs
 
+// Timers open a span of the profiler on every rank, printing is enabled by the `profile` feature.
+
+use crate::profiler;
+
 #[cfg(feature = "profile")]
 use colored::Colorize;
 #[cfg(feature = "profile")]
@@ -17,17 +21,20 @@ pub struct Timer {
     label: String,
     timer: Instant,
     is_root: bool,
+    span: Option<usize>,
 }
 
 #[cfg(feature = "profile")]
 impl Timer {
     #[inline(always)]
     pub fn new(label: &str, is_root: bool) -> Self {
+        let span = profiler::enter(label);
         if !is_root {
             Self {
                 label: label.to_string(),
                 timer: Instant::now(),
                 is_root: false,
+                span,
             }
         } else {
             let timer = Instant::now();
@@ -44,12 +51,16 @@ impl Timer {
                 label: label.to_string(),
                 timer,
                 is_root,
+                span,
             }
         }
     }
 
     #[inline(always)]
     pub fn stop(&self) {
+        if let Some(span) = self.span {
+            profiler::exit(span);
+        }
         if self.is_root {
             let duration = self.timer.elapsed();
             let star = "* ";
@@ -83,17 +94,25 @@ impl Timer {
 }
 
 #[cfg(not(feature = "profile"))]
-pub struct Timer {}
+pub struct Timer {
+    span: Option<usize>,
+}
 
 #[cfg(not(feature = "profile"))]
 impl Timer {
     #[inline(always)]
-    pub fn new(_label: &str, _is_root: bool) -> Self {
-        Self {}
+    pub fn new(label: &str, _is_root: bool) -> Self {
+        Self {
+            span: profiler::enter(label),
+        }
     }
 
     #[inline(always)]
-    pub fn stop(&self) {}
+    pub fn stop(&self) {
+        if let Some(span) = self.span {
+            profiler::exit(span);
+        }
+    }
 
     #[inline(always)]
     pub fn print(&self, _msg: &str) {}
@@ -0,0 +1,69 @@
+use utils::{
+    profiler::{self, Profile},
+    timer::Timer,
+};
+
+#[test]
+fn test_profiler_span_tree() {
+    assert!(profiler::finish().is_none());
+    // timers outside of a recording are not recorded
+    Timer::new("before", true).stop();
+
+    profiler::start();
+    let prover = Timer::new("prover", true);
+    let commit = Timer::new("pcs \"commit\"", true);
+    commit.stop();
+    let gkr = Timer::new("gkr prove", true);
+    // left open, closed by its parent
+    let _layer = Timer::new("layer 0", true);
+    gkr.stop();
+    prover.stop();
+    let verifier = Timer::new("verifier", true);
+    let profile = profiler::finish().unwrap();
+    verifier.stop();
+
+    let names = profile
+        .spans
+        .iter()
+        .map(|s| (s.name.as_str(), s.depth))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("prover", 0),
+            ("pcs \"commit\"", 1),
+            ("gkr prove", 1),
+            ("layer 0", 2),
+            ("verifier", 0),
+        ]
+    );
+    for (parent, child) in [(0, 1), (0, 2), (2, 3)] {
+        let (parent, child) = (&profile.spans[parent], &profile.spans[child]);
+        assert!(parent.start_us <= child.start_us);
+        assert!(child.start_us + child.duration_us <= parent.start_us + parent.duration_us);
+    }
+    #[cfg(target_os = "linux")]
+    assert!(profile
+        .spans
+        .iter()
+        .all(|s| s.memory_high_water_kb.is_some()));
+
+    let mut bytes = profile.to_bytes();
+    bytes.resize(bytes.len() + 13, 0);
+    assert_eq!(Profile::from_bytes(&bytes), Some(profile.clone()));
+    assert_eq!(Profile::from_bytes(&bytes[..bytes.len() - 20]), None);
+
+    let json = profiler::to_json(&[profile.clone(), Profile::default()]);
+    assert!(
+        json.starts_with("{\"ranks\":[{\"rank\":0,\"spans\":[{\"name\":\"prover\",\"start_us\":")
+    );
+    assert!(json.contains("\"children\":[{\"name\":\"pcs \\\"commit\\\"\",\"start_us\":"));
+    assert!(json.ends_with(",{\"rank\":1,\"spans\":[]}]}"));
+
+    let trace = profiler::to_chrome_trace(&[Profile::default(), profile]);
+    assert!(
+        trace.starts_with("{\"traceEvents\":[{\"name\":\"process_name\",\"ph\":\"M\",\"pid\":0")
+    );
+    assert_eq!(trace.matches("\"ph\":\"X\"").count(), 5);
+    assert_eq!(trace.matches("\"pid\":1,").count(), 6);
+}
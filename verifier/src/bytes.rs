@@ -0,0 +1,211 @@
+//! Verification from the serialized circuit, public inputs and proof, as a light client
+//! receives them.
+
+use std::io::Cursor;
+
+use circuit::{Circuit, CoefType, RecursiveCircuit, UNI_GATE_TYPE_POW1, UNI_GATE_TYPE_POW5};
+use gkr_engine::{ExpanderPCS, FieldEngine, GKREngine, Proof, StructuredReferenceString};
+use serdes::ExpSerde;
+use thiserror::Error;
+
+use crate::Verifier;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum VerifierError {
+    #[error("invalid circuit bytes")]
+    InvalidCircuit,
+
+    #[error("invalid public input bytes")]
+    InvalidPublicInput,
+
+    #[error("invalid proof bytes")]
+    InvalidProof,
+
+    #[error("the number of processes {0} is not a power of two")]
+    InvalidWorldSize(usize),
+
+    #[error("expected {expected} public inputs, got {got}")]
+    PublicInputCount { expected: usize, got: usize },
+}
+
+/// Deserialize a value taking up all of the bytes
+fn deserialize_exact<T: ExpSerde>(bytes: &[u8]) -> Option<T> {
+    let mut cursor = Cursor::new(bytes);
+    let value = T::deserialize_from(&mut cursor).ok()?;
+    (cursor.position() == bytes.len() as u64).then_some(value)
+}
+
+/// Deserialize a circuit in the recursive format of the compiler, along with the number of
+/// public inputs of one instance, and flatten it as `Circuit::verifier_load_circuit` does.
+pub fn deserialize_circuit<C: FieldEngine>(
+    circuit_bytes: &[u8],
+) -> Result<(Circuit<C>, usize), VerifierError> {
+    let rc = deserialize_exact::<RecursiveCircuit<C>>(circuit_bytes)
+        .ok_or(VerifierError::InvalidCircuit)?;
+    if !is_well_formed(&rc) {
+        return Err(VerifierError::InvalidCircuit);
+    }
+
+    let mut circuit = rc.flatten();
+    circuit.pre_process_gkr();
+    Ok((circuit, rc.num_public_inputs))
+}
+
+/// The largest number of variables of a segment, far above the circuits of the compiler and
+/// keeping the sizes in range on 32-bit targets
+const MAX_VAR_NUM: usize = 30;
+
+/// Whether the circuit can be flattened: the segments form a DAG, the gates and the
+/// allocations of the children lie within the segments, the consecutive layers agree on their
+/// sizes, and the gates are of known types reading existing public inputs.
+fn is_well_formed<C: FieldEngine>(rc: &RecursiveCircuit<C>) -> bool {
+    let num_segments = rc.segments.len();
+    if rc.layers.is_empty() || rc.layers.iter().any(|id| *id >= num_segments) {
+        return false;
+    }
+
+    let segments_well_formed = rc.segments.iter().all(|segment| {
+        if segment.i_var_num > MAX_VAR_NUM || segment.o_var_num > MAX_VAR_NUM {
+            return false;
+        }
+        let (i_size, o_size) = (1 << segment.i_var_num, 1 << segment.o_var_num);
+        let coef_well_formed = |coef_type: &CoefType| match coef_type {
+            CoefType::PublicInput(id) => *id < rc.num_public_inputs,
+            _ => true,
+        };
+
+        let children_well_formed = segment.child_segs.iter().all(|(id, allocs)| {
+            let Some(child) = rc.segments.get(*id) else {
+                return false;
+            };
+            allocs.iter().all(|alloc| {
+                alloc
+                    .i_offset
+                    .checked_add(1 << child.i_var_num.min(MAX_VAR_NUM))
+                    .is_some_and(|end| end <= i_size)
+                    && alloc
+                        .o_offset
+                        .checked_add(1 << child.o_var_num.min(MAX_VAR_NUM))
+                        .is_some_and(|end| end <= o_size)
+            })
+        });
+        let gates_well_formed = segment.gate_muls.iter().all(|g| {
+            g.i_ids.iter().all(|id| *id < i_size)
+                && g.o_id < o_size
+                && coef_well_formed(&g.coef_type)
+        }) && segment
+            .gate_adds
+            .iter()
+            .all(|g| g.i_ids[0] < i_size && g.o_id < o_size && coef_well_formed(&g.coef_type))
+            && segment
+                .gate_consts
+                .iter()
+                .all(|g| g.o_id < o_size && coef_well_formed(&g.coef_type))
+            && segment.gate_uni.iter().all(|g| {
+                g.i_ids[0] < i_size
+                    && g.o_id < o_size
+                    && coef_well_formed(&g.coef_type)
+                    && [UNI_GATE_TYPE_POW1, UNI_GATE_TYPE_POW5].contains(&g.gate_type)
+            });
+
+        children_well_formed && gates_well_formed
+    });
+    if !segments_well_formed || !is_acyclic(rc) {
+        return false;
+    }
+
+    // the outputs of a layer are the inputs of the next one
+    rc.layers
+        .windows(2)
+        .all(|ids| rc.segments[ids[0]].o_var_num.max(1) == rc.segments[ids[1]].i_var_num.max(1))
+}
+
+/// Whether no segment is its own descendant, which would make the flattening recurse forever
+fn is_acyclic<C: FieldEngine>(rc: &RecursiveCircuit<C>) -> bool {
+    // 0: not visited, 1: on the current path, 2: done
+    let mut state = vec![0u8; rc.segments.len()];
+    for root in 0..rc.segments.len() {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack = vec![(root, 0)];
+        while let Some((id, next_child)) = stack.pop() {
+            match rc.segments[id].child_segs.get(next_child) {
+                Some((child, _)) => {
+                    stack.push((id, next_child + 1));
+                    match state[*child] {
+                        0 => {
+                            state[*child] = 1;
+                            stack.push((*child, 0));
+                        }
+                        1 => return false,
+                        _ => (),
+                    }
+                }
+                None => state[id] = 2,
+            }
+        }
+    }
+    true
+}
+
+/// Deserialize the public inputs, i.e., the vector of the SIMD circuit field elements the
+/// verifier passes to `Verifier::verify`.
+pub fn deserialize_public_input<C: FieldEngine>(
+    public_input_bytes: &[u8],
+) -> Result<Vec<C::SimdCircuitField>, VerifierError> {
+    deserialize_exact(public_input_bytes).ok_or(VerifierError::InvalidPublicInput)
+}
+
+/// Deserialize a proof followed by the claimed value of the output layer, as written by the
+/// prover of `expander-exec`.
+pub fn deserialize_proof_and_claimed_v<C: FieldEngine>(
+    proof_bytes: &[u8],
+) -> Result<(Proof, C::ChallengeField), VerifierError> {
+    let mut cursor = Cursor::new(proof_bytes);
+    let proof = Proof::deserialize_from(&mut cursor).map_err(|_| VerifierError::InvalidProof)?;
+    let claimed_v = C::ChallengeField::deserialize_from(&mut cursor)
+        .map_err(|_| VerifierError::InvalidProof)?;
+    if cursor.position() != proof_bytes.len() as u64 {
+        return Err(VerifierError::InvalidProof);
+    }
+    Ok((proof, claimed_v))
+}
+
+/// Verify a proof generated by `world_size` processes from the bytes of the circuit, the public
+/// inputs and the proof, see the `deserialize_*` functions for the formats.
+///
+/// The malformed bytes are rejected with an error, `Ok(false)` is a proof that does not verify.
+pub fn verify_bytes<Cfg: GKREngine>(
+    circuit_bytes: &[u8],
+    public_input_bytes: &[u8],
+    proof_bytes: &[u8],
+    world_size: usize,
+    pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+    pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+) -> Result<bool, VerifierError> {
+    if !world_size.is_power_of_two() {
+        return Err(VerifierError::InvalidWorldSize(world_size));
+    }
+
+    let (mut circuit, num_public_inputs) = deserialize_circuit::<Cfg::FieldConfig>(circuit_bytes)?;
+    let public_input = deserialize_public_input::<Cfg::FieldConfig>(public_input_bytes)?;
+    if public_input.len() != num_public_inputs * world_size {
+        return Err(VerifierError::PublicInputCount {
+            expected: num_public_inputs * world_size,
+            got: public_input.len(),
+        });
+    }
+    let (proof, claimed_v) = deserialize_proof_and_claimed_v::<Cfg::FieldConfig>(proof_bytes)?;
+
+    let verifier = Verifier::<Cfg>::new(circuit.proof_shape(world_size));
+    Ok(verifier.verify(
+        &mut circuit,
+        &public_input,
+        &claimed_v,
+        pcs_params,
+        pcs_verification_key,
+        &proof,
+    ))
+}
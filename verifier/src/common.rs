@@ -0,0 +1,341 @@
+use std::{io::Read, vec};
+
+use arith::Field;
+use circuit::CircuitLayer;
+use gkr_engine::{ExpanderDualVarChallenge, FieldEngine, Transcript};
+use polynomials::EqPolynomial;
+use serdes::ExpSerde;
+use sumcheck::{
+    decompress_round_message, needs_generalized_sumcheck, GKRVerifierHelper, GeneralizedLayerGates,
+    VerifierScratchPad, SUMCHECK_GKR_DEGREE, SUMCHECK_GKR_SIMD_MPI_DEGREE,
+};
+
+/// With `compressed`, the round message omits p(1), which is recovered from the claimed sum,
+/// so the check p(0) + p(1) = claimed sum holds by construction.
+/// Returns `None` if the proof is malformed.
+#[inline(always)]
+pub fn verify_sumcheck_step<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    degree: usize,
+    compressed: bool,
+    transcript: &mut impl Transcript,
+    claimed_sum: &mut F::ChallengeField,
+    randomness_vec: &mut Vec<F::ChallengeField>,
+    sp: &VerifierScratchPad<F>,
+) -> Option<bool> {
+    let n_evals = if compressed { degree } else { degree + 1 };
+    let mut ps = vec![];
+    for i in 0..n_evals {
+        ps.push(F::ChallengeField::deserialize_from(&mut proof_reader).ok()?);
+        transcript.append_field_element(&ps[i]);
+    }
+    if compressed {
+        ps = decompress_round_message(&ps, *claimed_sum);
+    }
+
+    let r = transcript.generate_field_element::<F::ChallengeField>();
+    randomness_vec.push(r);
+
+    let verified = (ps[0] + ps[1]) == *claimed_sum;
+
+    // This assumes SUMCHECK_GKR_DEGREE == 2, SUMCHECK_GKR_SIMD_MPI_DEGREE == 3,
+    // other degrees only come from the generalized layer sumcheck
+    if degree == SUMCHECK_GKR_DEGREE {
+        *claimed_sum = GKRVerifierHelper::degree_2_eval(&ps, r, sp);
+    } else if degree == SUMCHECK_GKR_SIMD_MPI_DEGREE {
+        *claimed_sum = GKRVerifierHelper::degree_3_eval(&ps, r, sp);
+    } else {
+        *claimed_sum = GKRVerifierHelper::<F>::degree_d_eval(&ps, r);
+    }
+
+    Some(verified)
+}
+
+/// Returns `None` if the proof is malformed.
+// todo: FIXME
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+#[allow(clippy::unnecessary_unwrap)]
+pub fn sumcheck_verify_gkr_layer<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    layer: &CircuitLayer<F>,
+    public_input: &[F::SimdCircuitField],
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    claimed_v0: &mut F::ChallengeField,
+    claimed_v1: &mut Option<F::ChallengeField>,
+    alpha: Option<F::ChallengeField>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &mut VerifierScratchPad<F>,
+    is_output_layer: bool,
+    parallel_verify: bool,
+    compressed_sumcheck: bool,
+) -> Option<bool> {
+    assert_eq!(challenge.rz_1.is_none(), claimed_v1.is_none());
+    assert_eq!(challenge.rz_1.is_none(), alpha.is_none());
+
+    if parallel_verify {
+        GKRVerifierHelper::prepare_layer_non_sequential(layer, &alpha, challenge, sp);
+    } else {
+        GKRVerifierHelper::prepare_layer(layer, &alpha, challenge, sp, is_output_layer);
+    }
+
+    if needs_generalized_sumcheck(layer) {
+        return sumcheck_verify_gkr_generalized_layer(
+            proving_time_mpi_size,
+            layer,
+            public_input,
+            challenge,
+            claimed_v0,
+            claimed_v1,
+            alpha,
+            proof_reader,
+            transcript,
+            sp,
+            compressed_sumcheck,
+        );
+    }
+
+    let var_num = layer.input_var_num;
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let mut sum = *claimed_v0;
+    if let Some(v1) = claimed_v1 {
+        if let Some(a) = alpha {
+            sum += *v1 * a;
+        }
+    }
+
+    sum -= GKRVerifierHelper::eval_cst(&layer.const_, public_input, sp);
+
+    let mut rx = vec![];
+    let mut ry = None;
+    let mut r_simd_xy = vec![];
+    let mut r_mpi_xy = vec![];
+    let mut verified = true;
+
+    for _i_var in 0..var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_DEGREE,
+            compressed_sumcheck,
+            transcript,
+            &mut sum,
+            &mut rx,
+            sp,
+        )?;
+        // println!("x {} var, verified? {}", _i_var, verified);
+    }
+    GKRVerifierHelper::set_rx(&rx, sp);
+
+    for _i_var in 0..simd_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            compressed_sumcheck,
+            transcript,
+            &mut sum,
+            &mut r_simd_xy,
+            sp,
+        )?;
+        // println!("{} simd var, verified? {}", _i_var, verified);
+    }
+    GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
+
+    for _i_var in 0..proving_time_mpi_size.ilog2() {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            SUMCHECK_GKR_SIMD_MPI_DEGREE,
+            compressed_sumcheck,
+            transcript,
+            &mut sum,
+            &mut r_mpi_xy,
+            sp,
+        )?;
+        // println!("{} mpi var, verified? {}", _i_var, verified);
+    }
+    GKRVerifierHelper::set_r_mpi_xy(&r_mpi_xy, sp);
+
+    let vx_claim = F::ChallengeField::deserialize_from(&mut proof_reader).ok()?;
+
+    sum -= vx_claim * GKRVerifierHelper::eval_add(&layer.add, sp);
+    transcript.append_field_element(&vx_claim);
+
+    let vy_claim = if !layer.structure_info.skip_sumcheck_phase_two {
+        ry = Some(vec![]);
+        for _i_var in 0..var_num {
+            verified &= verify_sumcheck_step::<F>(
+                &mut proof_reader,
+                SUMCHECK_GKR_DEGREE,
+                compressed_sumcheck,
+                transcript,
+                &mut sum,
+                ry.as_mut().unwrap(),
+                sp,
+            )?;
+            // println!("y {} var, verified? {}", _i_var, verified);
+        }
+        GKRVerifierHelper::set_ry(ry.as_ref().unwrap(), sp);
+
+        let vy_claim = F::ChallengeField::deserialize_from(&mut proof_reader).ok()?;
+        transcript.append_field_element(&vy_claim);
+        verified &= sum == vx_claim * vy_claim * GKRVerifierHelper::eval_mul(&layer.mul, sp);
+        Some(vy_claim)
+    } else {
+        verified &= sum == F::ChallengeField::ZERO;
+        None
+    };
+
+    *challenge = ExpanderDualVarChallenge::new(rx, ry, r_simd_xy, r_mpi_xy);
+    *claimed_v0 = vx_claim;
+    *claimed_v1 = vy_claim;
+
+    Some(verified)
+}
+
+/// Verifier of `sumcheck_prove_gkr_generalized_layer`, the layer is already prepared.
+/// Returns `None` if the proof is malformed.
+#[allow(clippy::too_many_arguments)]
+pub fn sumcheck_verify_gkr_generalized_layer<F: FieldEngine>(
+    proving_time_mpi_size: usize,
+    layer: &CircuitLayer<F>,
+    public_input: &[F::SimdCircuitField],
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    claimed_v0: &mut F::ChallengeField,
+    claimed_v1: &mut Option<F::ChallengeField>,
+    alpha: Option<F::ChallengeField>,
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    sp: &mut VerifierScratchPad<F>,
+    compressed_sumcheck: bool,
+) -> Option<bool> {
+    let gates = GeneralizedLayerGates::new(layer);
+    let var_num = layer.input_var_num;
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let mut sum = *claimed_v0;
+    if let (Some(v1), Some(a)) = (*claimed_v1, alpha) {
+        sum += v1 * a;
+    }
+
+    sum -= GKRVerifierHelper::eval_cst(&layer.const_, public_input, sp);
+
+    let mut r_slots = vec![];
+    let mut r_simd_xy = vec![];
+    let mut r_mpi_xy = vec![];
+    let mut verified = true;
+
+    for slot in 0..gates.slot_num {
+        let mut r_slot = vec![];
+        for _i_var in 0..var_num {
+            verified &= verify_sumcheck_step::<F>(
+                &mut proof_reader,
+                gates.slot_degree(slot),
+                compressed_sumcheck,
+                transcript,
+                &mut sum,
+                &mut r_slot,
+                sp,
+            )?;
+        }
+        r_slots.push(r_slot);
+    }
+
+    let degree = gates.simd_mpi_degree();
+    for _i_var in 0..simd_var_num {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            degree,
+            compressed_sumcheck,
+            transcript,
+            &mut sum,
+            &mut r_simd_xy,
+            sp,
+        )?;
+    }
+    GKRVerifierHelper::set_r_simd_xy(&r_simd_xy, sp);
+
+    for _i_var in 0..proving_time_mpi_size.ilog2() {
+        verified &= verify_sumcheck_step::<F>(
+            &mut proof_reader,
+            degree,
+            compressed_sumcheck,
+            transcript,
+            &mut sum,
+            &mut r_mpi_xy,
+            sp,
+        )?;
+    }
+    GKRVerifierHelper::set_r_mpi_xy(&r_mpi_xy, sp);
+
+    let claims = (0..gates.slot_num)
+        .map(|_| F::ChallengeField::deserialize_from(&mut proof_reader).ok())
+        .collect::<Option<Vec<_>>>()?;
+    claims
+        .iter()
+        .for_each(|claim| transcript.append_field_element(claim));
+
+    let eq_evals_at_slots = r_slots
+        .iter()
+        .map(|r| EqPolynomial::<F::ChallengeField>::build_eq_x_r(r))
+        .collect::<Vec<_>>();
+    let output_size = 1 << layer.output_var_num;
+    verified &= sum
+        == sp.eq_r_simd_r_simd_xy
+            * sp.eq_r_mpi_r_mpi_xy
+            * gates.evaluate(
+                &sp.eq_evals_at_rz0[..output_size],
+                &eq_evals_at_slots,
+                &claims,
+            );
+
+    let (rz_1, claim_1) = if gates.reduces_claims() {
+        let beta = transcript.generate_field_element::<F::ChallengeField>();
+        let mut sum = F::ChallengeField::ZERO;
+        let mut coef = F::ChallengeField::ONE;
+        for claim in claims.iter().skip(1) {
+            sum += coef * claim;
+            coef *= beta;
+        }
+
+        let mut r_reduction = vec![];
+        for _i_var in 0..var_num {
+            verified &= verify_sumcheck_step::<F>(
+                &mut proof_reader,
+                SUMCHECK_GKR_DEGREE,
+                compressed_sumcheck,
+                transcript,
+                &mut sum,
+                &mut r_reduction,
+                sp,
+            )?;
+        }
+
+        let claim = F::ChallengeField::deserialize_from(&mut proof_reader).ok()?;
+        transcript.append_field_element(&claim);
+
+        let mut eq_sum = F::ChallengeField::ZERO;
+        let mut coef = F::ChallengeField::ONE;
+        for r in r_slots.iter().skip(1) {
+            eq_sum += coef * EqPolynomial::<F::ChallengeField>::eq_vec(r, &r_reduction);
+            coef *= beta;
+        }
+        verified &= sum == eq_sum * claim;
+
+        (Some(r_reduction), Some(claim))
+    } else if gates.slot_num == 2 {
+        (Some(r_slots[1].clone()), Some(claims[1]))
+    } else {
+        (None, None)
+    };
+
+    let rx = r_slots.swap_remove(0);
+    GKRVerifierHelper::set_rx(&rx, sp);
+    if let Some(ry) = &rz_1 {
+        GKRVerifierHelper::set_ry(ry, sp);
+    }
+
+    *challenge = ExpanderDualVarChallenge::new(rx, rz_1, r_simd_xy, r_mpi_xy);
+    *claimed_v0 = claims[0];
+    *claimed_v1 = claim_1;
+
+    Some(verified)
+}
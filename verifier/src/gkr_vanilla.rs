@@ -1,40 +1,48 @@
 use std::io::Read;
 
 use circuit::Circuit;
-use gkr_engine::{ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, Transcript};
+use gkr_engine::{
+    ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, ProofShape, Transcript,
+};
 use sumcheck::VerifierScratchPad;
 use utils::timer::Timer;
 
-use super::common::sumcheck_verify_gkr_layer;
+use super::{bind_output_claim, common::sumcheck_verify_gkr_layer};
 
+/// `output_claim` computes the claimed evaluation of the output MLE at the output challenge,
+/// or rejects the challenge with `None`.
+/// Returns `None` if the proof is malformed or the output claim is rejected.
 #[allow(clippy::type_complexity)]
 pub fn gkr_verify<F: FieldEngine>(
-    proving_time_mpi_size: usize,
+    proof_shape: &ProofShape,
+    compressed_sumcheck: bool,
     circuit: &Circuit<F>,
     public_input: &[F::SimdCircuitField],
-    claimed_v: &F::ChallengeField,
+    output_claim: impl FnOnce(&ExpanderSingleVarChallenge<F>) -> Option<F::ChallengeField>,
     transcript: &mut impl Transcript,
     mut proof_reader: impl Read,
-) -> (
+) -> Option<(
     bool,
     ExpanderDualVarChallenge<F>,
     F::ChallengeField,
     Option<F::ChallengeField>,
-) {
+)> {
     let timer = Timer::new("gkr_verify", true);
-    let mut sp = VerifierScratchPad::<F>::new(circuit, proving_time_mpi_size);
+    let proving_time_mpi_size = proof_shape.world_size;
+    let mut sp = VerifierScratchPad::<F>::new(circuit, proof_shape);
 
     let layer_num = circuit.layers.len();
 
-    let mut challenge = ExpanderSingleVarChallenge::sample_from_transcript(
+    let output_challenge = ExpanderSingleVarChallenge::sample_from_transcript(
         transcript,
         circuit.layers.last().unwrap().output_var_num,
         proving_time_mpi_size,
-    )
-    .into();
+    );
+    let mut claimed_v0 = output_claim(&output_challenge)?;
+    bind_output_claim(transcript, &claimed_v0);
+    let mut challenge = output_challenge.into();
 
     let mut alpha = None;
-    let mut claimed_v0 = *claimed_v;
     let mut claimed_v1 = None;
 
     let mut verified = true;
@@ -52,7 +60,8 @@ pub fn gkr_verify<F: FieldEngine>(
             &mut sp,
             i == layer_num - 1,
             false,
-        );
+            compressed_sumcheck,
+        )?;
 
         verified &= cur_verified;
         alpha = if challenge.rz_1.is_some() {
@@ -69,5 +78,5 @@ pub fn gkr_verify<F: FieldEngine>(
         challenge.r_mpi,
     );
 
-    (verified, challenge, claimed_v0, claimed_v1)
+    Some((verified, challenge, claimed_v0, claimed_v1))
 }
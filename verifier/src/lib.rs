@@ -0,0 +1,35 @@
+//! The standalone verifier of GKR proofs, without MPI or proving code, to be embedded in light
+//! clients and other services, e.g., compiled to wasm32.
+//!
+//! `Verifier` checks a proof against a flattened circuit, `verify_bytes` against the bytes of
+//! the circuit in the format of the compiler, of the public inputs and of the proof. The
+//! configurations are declared as for the prover, from the field engines of `gkr_engine`, the
+//! transcripts of `transcript` and the `ExpanderPCS` of `poly_commit`, with an MPI engine not
+//! depending on MPI, e.g. `ThreadMPIConfig`, which the verifier never uses.
+
+mod structs;
+pub use structs::*;
+
+mod common;
+pub use common::*;
+
+mod gkr_vanilla;
+pub use gkr_vanilla::gkr_verify;
+
+mod lookup;
+pub use lookup::verify_lookups;
+
+mod output_statement;
+pub use output_statement::OutputStatement;
+
+mod snark;
+pub use snark::Verifier;
+
+mod statement;
+pub use statement::*;
+
+mod bytes;
+pub use bytes::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,157 @@
+//! This module implements the verifier of the lookups declared by a circuit,
+//! see `prove_lookups` for the protocol.
+
+use std::io::Read;
+
+use arith::Field;
+use circuit::{Circuit, Lookup};
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine, ProofShape, Transcript};
+use polynomials::EqPolynomial;
+use serdes::ExpSerde;
+use sumcheck::{fractional_sum_verify, IOPProof, IOPProverMessage, SumCheck};
+
+/// Verify the lookups of the circuit and the reduction of their claims and of the GKR claims
+/// on the input, `input_claims`, to a single claim.
+/// Returns the single claim, to be checked against the input commitment.
+#[allow(clippy::type_complexity)]
+pub fn verify_lookups<F: FieldEngine>(
+    circuit: &Circuit<F>,
+    proof_shape: &ProofShape,
+    input_claims: &[(ExpanderSingleVarChallenge<F>, F::ChallengeField)],
+    compressed_sumcheck: bool,
+    transcript: &mut impl Transcript,
+    mut proof_reader: impl Read,
+) -> Option<(ExpanderSingleVarChallenge<F>, F::ChallengeField)> {
+    let log_input_size = circuit.log_input_size();
+
+    let mut claims = input_claims.to_vec();
+    for lookup in &circuit.lookups {
+        claims.push(verify_lookup(
+            lookup,
+            proof_shape.world_size,
+            log_input_size,
+            compressed_sumcheck,
+            transcript,
+            &mut proof_reader,
+        )?);
+    }
+
+    verify_claim_reduction(
+        &claims,
+        proof_shape.world_size,
+        log_input_size,
+        compressed_sumcheck,
+        transcript,
+        &mut proof_reader,
+    )
+}
+
+/// Verify that the looked-up values lie in the table,
+/// and return the resulting claim on the input
+fn verify_lookup<F: FieldEngine>(
+    lookup: &Lookup<F>,
+    world_size: usize,
+    log_input_size: usize,
+    compressed_sumcheck: bool,
+    transcript: &mut impl Transcript,
+    mut proof_reader: impl Read,
+) -> Option<(ExpanderSingleVarChallenge<F>, F::ChallengeField)> {
+    if !lookup.fits_field(world_size) {
+        return None;
+    }
+
+    let mut multiplicities = Vec::with_capacity(lookup.table.len());
+    for _ in 0..lookup.table.len() {
+        let m = F::CircuitField::deserialize_from(&mut proof_reader).ok()?;
+        transcript.append_field_element(&m);
+        multiplicities.push(m);
+    }
+
+    let alpha = transcript.generate_field_element::<F::ChallengeField>();
+    let claim = fractional_sum_verify::<F::ChallengeField>(
+        lookup.value_var_num(world_size),
+        compressed_sumcheck,
+        &mut proof_reader,
+        transcript,
+    )?;
+
+    // sum_j m_j / (alpha - t_j)
+    let table_sum = lookup
+        .table
+        .iter()
+        .zip(&multiplicities)
+        .map(|(t, m)| {
+            (alpha - F::ChallengeField::from(*t))
+                .inv()
+                .map(|inv| inv * *m)
+        })
+        .sum::<Option<F::ChallengeField>>()?;
+
+    let (numerator, denominator) = claim.root;
+    // every looked-up value contributes 1 / (alpha - w_i)
+    if denominator.is_zero()
+        || numerator != table_sum * denominator
+        || claim.numerator != F::ChallengeField::ONE
+    {
+        return None;
+    }
+
+    Some((
+        lookup.input_challenge(log_input_size, &claim.point),
+        alpha - claim.denominator,
+    ))
+}
+
+/// Verify the reduction of the claims on the input to a single claim
+fn verify_claim_reduction<F: FieldEngine>(
+    claims: &[(ExpanderSingleVarChallenge<F>, F::ChallengeField)],
+    world_size: usize,
+    log_input_size: usize,
+    compressed_sumcheck: bool,
+    transcript: &mut impl Transcript,
+    mut proof_reader: impl Read,
+) -> Option<(ExpanderSingleVarChallenge<F>, F::ChallengeField)> {
+    let simd_var_num = F::get_field_pack_size().trailing_zeros() as usize;
+    let num_vars = simd_var_num + log_input_size + world_size.trailing_zeros() as usize;
+
+    let beta = transcript.generate_field_element::<F::ChallengeField>();
+    let claimed_sum = claims
+        .iter()
+        .rev()
+        .fold(F::ChallengeField::ZERO, |acc, (_, v)| acc * beta + *v);
+
+    let proofs = (0..num_vars)
+        .map(|_| IOPProverMessage::deserialize_from(&mut proof_reader).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let proof = IOPProof {
+        proofs,
+        point: vec![],
+    };
+    let (verified, subclaim) = if compressed_sumcheck {
+        SumCheck::verify_virtual_compressed(claimed_sum, &proof, num_vars, 2, transcript)
+    } else {
+        SumCheck::verify_virtual(claimed_sum, &proof, num_vars, 2, transcript)
+    };
+
+    let v = F::ChallengeField::deserialize_from(&mut proof_reader).ok()?;
+    transcript.append_field_element(&v);
+
+    let mut point = subclaim.point;
+    point.reverse();
+    let weight = claims
+        .iter()
+        .rev()
+        .fold(F::ChallengeField::ZERO, |acc, (challenge, _)| {
+            acc * beta + EqPolynomial::eq_vec(&challenge.global_xs(), &point)
+        });
+    if !verified || weight * v != subclaim.expected_evaluation {
+        return None;
+    }
+
+    let (r_simd, rest) = point.split_at(simd_var_num);
+    let (rz, r_mpi) = rest.split_at(log_input_size);
+    Some((
+        ExpanderSingleVarChallenge::new(rz.to_vec(), r_simd.to_vec(), r_mpi.to_vec()),
+        v,
+    ))
+}
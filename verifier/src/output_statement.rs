@@ -0,0 +1,139 @@
+use arith::Field;
+use circuit::Circuit;
+use gkr_engine::{ExpanderSingleVarChallenge, FieldEngine};
+
+/// The outputs of the circuit expected by the verifier.
+///
+/// Outputs are laid out as the `output_vals` of the last layer,
+/// `2^output_var_num` values per MPI rank, concatenated over the ranks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutputStatement<F: FieldEngine> {
+    /// Every output is zero
+    AllZeros,
+    /// The first `num_zeros` outputs of every rank are zero, the remaining
+    /// `2^output_var_num - num_zeros` outputs of every rank are `other_outputs`
+    FirstZeros {
+        num_zeros: usize,
+        other_outputs: Vec<F::SimdCircuitField>,
+    },
+    /// The full output vector
+    Values(Vec<F::SimdCircuitField>),
+}
+
+impl<F: FieldEngine> OutputStatement<F> {
+    /// The outputs the circuit is compiled to constrain to zero, i.e., the first
+    /// `expected_num_output_zeros` outputs, along with the values of the other outputs
+    pub fn expected_zeros(circuit: &Circuit<F>, other_outputs: Vec<F::SimdCircuitField>) -> Self {
+        Self::FirstZeros {
+            num_zeros: circuit.expected_num_output_zeros,
+            other_outputs,
+        }
+    }
+
+    /// The statement the circuit makes on its own: the first `expected_num_output_zeros`
+    /// outputs of every rank are zero, and so are the outputs no gate of the last layer writes.
+    /// Returns `None` if another output is written, its value is then only known to the prover
+    /// and must be given in an explicit statement.
+    pub fn from_circuit(circuit: &Circuit<F>) -> Option<Self> {
+        let layer = circuit.layers.last()?;
+        layer
+            .mul
+            .iter()
+            .map(|g| g.o_id)
+            .chain(layer.add.iter().map(|g| g.o_id))
+            .chain(layer.const_.iter().map(|g| g.o_id))
+            .chain(layer.uni.iter().map(|g| g.o_id))
+            .chain(layer.monomial.iter().map(|g| g.o_id))
+            .all(|o_id| o_id < circuit.expected_num_output_zeros)
+            .then_some(Self::AllZeros)
+    }
+
+    /// Split the outputs computed by the prover, concatenated over `world_size` ranks, into
+    /// the leading zeros of every rank and the other outputs.
+    /// Returns `None` if some leading output is not zero.
+    pub fn from_outputs(
+        outputs: &[F::SimdCircuitField],
+        num_zeros: usize,
+        world_size: usize,
+    ) -> Option<Self> {
+        let outputs_per_rank = outputs.len() / world_size;
+        if outputs_per_rank == 0 || num_zeros > outputs_per_rank {
+            return None;
+        }
+
+        let mut other_outputs = Vec::with_capacity(outputs.len() - num_zeros * world_size);
+        for rank_outputs in outputs.chunks(outputs_per_rank) {
+            let (zeros, others) = rank_outputs.split_at(num_zeros);
+            if !zeros.iter().all(|v| v.is_zero()) {
+                return None;
+            }
+            other_outputs.extend_from_slice(others);
+        }
+
+        Some(Self::FirstZeros {
+            num_zeros,
+            other_outputs,
+        })
+    }
+
+    /// Whether the statement describes exactly `2^output_var_num` outputs on each of the
+    /// `world_size` ranks
+    pub fn is_well_formed(&self, output_var_num: usize, world_size: usize) -> bool {
+        let outputs_per_rank = 1 << output_var_num;
+        match self {
+            Self::AllZeros => true,
+            Self::FirstZeros {
+                num_zeros,
+                other_outputs,
+            } => {
+                *num_zeros <= outputs_per_rank
+                    && other_outputs.len() == (outputs_per_rank - num_zeros) * world_size
+            }
+            Self::Values(values) => values.len() == outputs_per_rank * world_size,
+        }
+    }
+
+    /// The full output vector, if the statement is well formed
+    pub fn expected_outputs(
+        &self,
+        output_var_num: usize,
+        world_size: usize,
+    ) -> Option<Vec<F::SimdCircuitField>> {
+        if !self.is_well_formed(output_var_num, world_size) {
+            return None;
+        }
+
+        let outputs_per_rank = 1 << output_var_num;
+        let outputs = match self {
+            Self::AllZeros => vec![F::SimdCircuitField::ZERO; outputs_per_rank * world_size],
+            Self::FirstZeros {
+                num_zeros,
+                other_outputs,
+            } => {
+                let mut outputs = Vec::with_capacity(outputs_per_rank * world_size);
+                let n_others = outputs_per_rank - num_zeros;
+                for rank in 0..world_size {
+                    outputs.resize(outputs.len() + num_zeros, F::SimdCircuitField::ZERO);
+                    outputs
+                        .extend_from_slice(&other_outputs[rank * n_others..(rank + 1) * n_others]);
+                }
+                outputs
+            }
+            Self::Values(values) => values.clone(),
+        };
+        Some(outputs)
+    }
+
+    /// Evaluate the MLE of the expected outputs at the output challenge of GKR,
+    /// this is the claim the verifier starts the GKR with.
+    pub fn evaluate(&self, challenge: &ExpanderSingleVarChallenge<F>) -> Option<F::ChallengeField> {
+        if matches!(self, Self::AllZeros) {
+            return Some(F::ChallengeField::ZERO);
+        }
+
+        let outputs = self.expected_outputs(challenge.rz.len(), 1 << challenge.r_mpi.len())?;
+        Some(F::single_core_eval_circuit_vals_at_expander_challenge(
+            &outputs, challenge,
+        ))
+    }
+}
@@ -6,9 +6,10 @@ use std::{
 
 use circuit::Circuit;
 use gkr_engine::{
-    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, MPIConfig,
-    MPIEngine, Proof, StructuredReferenceString, Transcript,
+    ExpanderPCS, ExpanderSingleVarChallenge, FieldEngine, GKREngine, GKRScheme, Proof, ProofShape,
+    StructuredReferenceString, Transcript,
 };
+#[cfg(feature = "parallel")]
 use rayon::iter::{
     IndexedParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
 };
@@ -19,18 +20,22 @@ use utils::timer::Timer;
 
 #[cfg(feature = "grinding")]
 use crate::grind;
-use crate::{gkr_verify, parse_proof, sumcheck_verify_gkr_layer};
+use crate::{
+    bind_statement, gkr_verify, parse_proof, sumcheck_verify_gkr_layer, verify_lookups,
+    OutputStatement,
+};
 
-#[derive(Default)]
-pub struct Verifier<'a, Cfg: GKREngine> {
-    pub mpi_config: MPIConfig<'a>,
+/// The verifier only needs to know the shape of the proving run, e.g. from
+/// `Circuit::proof_shape`, and never depends on an MPI engine.
+pub struct Verifier<Cfg: GKREngine> {
+    pub proof_shape: ProofShape,
     phantom: PhantomData<Cfg>,
 }
 
-impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
-    pub fn new(mpi_config: MPIConfig<'a>) -> Self {
+impl<Cfg: GKREngine> Verifier<Cfg> {
+    pub fn new(proof_shape: ProofShape) -> Self {
         Self {
-            mpi_config,
+            proof_shape,
             phantom: PhantomData,
         }
     }
@@ -39,23 +44,24 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
     /// 1. Parse the commitment from the proof reader and use that to initialize the transcript.
     /// 2. (Optionally) grinding.
     /// 3. Fill the circuit with random coefficients.
+    ///
+    /// Returns `None` if the commitment is malformed.
     #[inline(always)]
     pub(crate) fn pre_gkr(
         &self,
         mut proof_reader: impl Read,
         circuit: &mut Circuit<Cfg::FieldConfig>,
         transcript: &mut Cfg::TranscriptConfig,
-        proving_time_mpi_size: usize,
-    ) -> <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment {
+    ) -> Option<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment> {
         let timer = Timer::new("pre_gkr", true);
 
         let commitment =
             <<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Commitment as ExpSerde>::deserialize_from(
                 &mut proof_reader,
             )
-            .unwrap();
+            .ok()?;
         let mut buffer = vec![];
-        commitment.serialize_into(&mut buffer).unwrap();
+        commitment.serialize_into(&mut buffer).ok()?;
 
         // this function will iteratively hash the commitment, and append the
         // final hash output to the transcript.
@@ -64,24 +70,27 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         // note that this function is almost identical to grind, except that grind uses a
         // fixed hasher, where as this function uses the transcript hasher
         transcript.append_commitment(&buffer);
+        bind_statement::<Cfg>(transcript, circuit, self.proof_shape.world_size);
 
         // ZZ: shall we use probabilistic grinding so the verifier can avoid this cost?
         // (and also be recursion friendly)
         #[cfg(feature = "grinding")]
-        grind::<Cfg>(transcript, &self.mpi_config);
+        grind::<Cfg>(transcript, true);
 
         circuit.fill_rnd_coefs(transcript);
-        transcript_verifier_sync(transcript, proving_time_mpi_size);
+        transcript_verifier_sync(transcript, &self.proof_shape);
 
         timer.stop();
 
-        commitment
+        Some(commitment)
     }
 
     /// Main body of the GKR verification.
     /// We have two schemes:
     /// 1. Vanilla GKR
     /// 2. GKR square: This is a dedicated scheme for the circuit that only contains pow gates.
+    ///
+    /// Returns `None` if the proof is malformed.
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
@@ -89,28 +98,30 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         &self,
         circuit: &Circuit<Cfg::FieldConfig>,
         public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
-        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
-        proving_time_mpi_size: usize,
+        output_claim: impl FnOnce(
+            &ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+        ) -> Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
-    ) -> (
+    ) -> Option<(
         bool,
         ExpanderSingleVarChallenge<Cfg::FieldConfig>,
         Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
         <Cfg::FieldConfig as FieldEngine>::ChallengeField,
         Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
-    ) {
+    )> {
         let timer = Timer::new("gkr", true);
         let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
             GKRScheme::Vanilla => {
                 let (gkr_verified, challenge, claim_x, claim_y) = gkr_verify(
-                    proving_time_mpi_size,
+                    &self.proof_shape,
+                    Cfg::COMPRESSED_SUMCHECK,
                     circuit,
                     public_input,
-                    claimed_v,
+                    output_claim,
                     transcript,
                     &mut proof_reader,
-                );
+                )?;
 
                 (
                     gkr_verified,
@@ -121,15 +132,16 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                 )
             }
         };
-        transcript_verifier_sync(transcript, proving_time_mpi_size);
+        transcript_verifier_sync(transcript, &self.proof_shape);
 
         log::info!("GKR verification: {verified}");
 
         timer.stop();
-        (verified, challenge_x, challenge_y, claim_x, claim_y)
+        Some((verified, challenge_x, challenge_y, claim_x, claim_y))
     }
 
     /// Parallel version of the GKR verification.
+    /// Returns `None` if the proof is malformed.
     #[inline(always)]
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
@@ -137,39 +149,48 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         &self,
         circuit: &Circuit<Cfg::FieldConfig>,
         public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
-        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
-        proving_time_mpi_size: usize,
+        output_claim: impl FnOnce(
+            &ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+        ) -> Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
         transcript: &mut Cfg::TranscriptConfig,
         mut proof_reader: impl Read,
-    ) -> (
+    ) -> Option<(
         bool,
         ExpanderSingleVarChallenge<Cfg::FieldConfig>,
         Option<ExpanderSingleVarChallenge<Cfg::FieldConfig>>,
         <Cfg::FieldConfig as FieldEngine>::ChallengeField,
         Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
-    ) {
+    )> {
         let parse_proof_timer = Timer::new("parse_proof", true);
         let xy_var_degree = match Cfg::SCHEME {
             GKRScheme::Vanilla => SUMCHECK_GKR_DEGREE,
         };
 
+        let proving_time_mpi_size = self.proof_shape.world_size;
         let (mut verification_units, challenge, claim_x, claim_y) = parse_proof(
             &mut proof_reader,
             circuit,
             proving_time_mpi_size,
             xy_var_degree,
-            *claimed_v,
+            Cfg::COMPRESSED_SUMCHECK,
+            output_claim,
             transcript,
-        );
+        )?;
         parse_proof_timer.stop();
 
         let gkr_parallel_timer = Timer::new("gkr_parallel", true);
-        let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, proving_time_mpi_size);
+        let sp = VerifierScratchPad::<Cfg::FieldConfig>::new(circuit, &self.proof_shape);
         let (verified, challenge_x, challenge_y, claim_x, claim_y) = match Cfg::SCHEME {
             GKRScheme::Vanilla => {
-                let gkr_verified = verification_units
+                // without the `parallel` feature, the layers are checked one after the other
+                #[cfg(feature = "parallel")]
+                let units = verification_units
                     .par_iter_mut()
-                    .zip(circuit.layers.par_iter())
+                    .zip(circuit.layers.par_iter());
+                #[cfg(not(feature = "parallel"))]
+                let units = verification_units.iter_mut().zip(circuit.layers.iter());
+
+                let gkr_verified = units
                     .map(|(verification_unit, layer)| {
                         let mut challenge = verification_unit.claim.challenge.clone();
                         let alpha = verification_unit.claim.alpha;
@@ -190,9 +211,10 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
                             &mut sp,
                             false,
                             true,
+                            Cfg::COMPRESSED_SUMCHECK,
                         )
                     })
-                    .all(|verified| verified);
+                    .all(|verified| verified == Some(true));
 
                 (
                     gkr_verified,
@@ -204,9 +226,9 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             }
         };
         gkr_parallel_timer.stop();
-        transcript_verifier_sync(transcript, proving_time_mpi_size);
+        transcript_verifier_sync(transcript, &self.proof_shape);
 
-        (verified, challenge_x, challenge_y, claim_x, claim_y)
+        Some((verified, challenge_x, challenge_y, claim_x, claim_y))
     }
 
     /// Verify the PCS opening against the commitment and the claim from GKR.
@@ -236,16 +258,20 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
             &mut proof_reader,
         );
 
-        if let Some(challenge_y) = challenge_y {
-            verified &= self.get_pcs_opening_from_proof_and_verify(
-                pcs_params,
-                pcs_verification_key,
-                commitment,
-                challenge_y,
-                claim_y.as_ref().unwrap(),
-                transcript,
-                &mut proof_reader,
-            );
+        match (challenge_y, claim_y) {
+            (Some(challenge_y), Some(claim_y)) => {
+                verified &= self.get_pcs_opening_from_proof_and_verify(
+                    pcs_params,
+                    pcs_verification_key,
+                    commitment,
+                    challenge_y,
+                    claim_y,
+                    transcript,
+                    &mut proof_reader,
+                );
+            }
+            (None, None) => (),
+            _ => verified = false,
         }
 
         timer.stop();
@@ -254,6 +280,11 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
 
     /// Paritially verify the proof.
     /// Conduct the whole procedure except for pairing, if any.
+    ///
+    /// `claimed_v` is the evaluation of the output MLE returned by the prover, it is rejected
+    /// unless it is the evaluation of the outputs the circuit constrains to zero, see
+    /// `OutputStatement::from_circuit`. Use `verify_output_statement` for circuits with
+    /// other outputs.
     #[allow(clippy::too_many_arguments)]
     pub fn verify(
         &self,
@@ -264,72 +295,184 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
         proof: &Proof,
     ) -> bool {
-        let timer = Timer::new("snark verify", true);
+        let Some(output_statement) = OutputStatement::from_circuit(circuit) else {
+            return false;
+        };
 
-        let proving_time_mpi_size = self.mpi_config.world_size();
-        let mut transcript = Cfg::TranscriptConfig::new();
-        let mut cursor = Cursor::new(&proof.bytes);
+        self.verify_with_output_claim(
+            circuit,
+            public_input,
+            |challenge| {
+                output_statement
+                    .evaluate(challenge)
+                    .filter(|claim| claim == claimed_v)
+            },
+            pcs_params,
+            pcs_verification_key,
+            proof,
+            false,
+        )
+    }
 
-        if !self.bind_and_check_public_inputs(&mut cursor, public_input, &mut transcript) {
+    pub fn par_verify(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> bool {
+        let Some(output_statement) = OutputStatement::from_circuit(circuit) else {
             return false;
-        }
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        };
 
-        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr(
+        self.verify_with_output_claim(
             circuit,
             public_input,
-            claimed_v,
-            proving_time_mpi_size,
-            &mut transcript,
-            &mut cursor,
-        );
+            |challenge| {
+                output_statement
+                    .evaluate(challenge)
+                    .filter(|claim| claim == claimed_v)
+            },
+            pcs_params,
+            pcs_verification_key,
+            proof,
+            true,
+        )
+    }
 
-        verified &= self.post_gkr(
+    /// Verify the proof against the outputs expected by the verifier.
+    /// The initial GKR claim is the MLE of `output_statement` evaluated by the verifier at the
+    /// output challenge, so no value from the prover is trusted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_output_statement(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        output_statement: &OutputStatement<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> bool {
+        if !output_statement.is_well_formed(
+            circuit.layers.last().unwrap().output_var_num,
+            self.proof_shape.world_size,
+        ) {
+            return false;
+        }
+
+        self.verify_with_output_claim(
+            circuit,
+            public_input,
+            |challenge| output_statement.evaluate(challenge),
             pcs_params,
             pcs_verification_key,
-            &commitment,
-            &mut challenge_x,
-            &claim_x,
-            &mut challenge_y,
-            &claim_y,
-            &mut transcript,
-            &mut cursor,
-        );
+            proof,
+            false,
+        )
+    }
 
-        timer.stop();
+    pub fn par_verify_output_statement(
+        &self,
+        circuit: &mut Circuit<Cfg::FieldConfig>,
+        public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
+        output_statement: &OutputStatement<Cfg::FieldConfig>,
+        pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
+        pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
+        proof: &Proof,
+    ) -> bool {
+        if !output_statement.is_well_formed(
+            circuit.layers.last().unwrap().output_var_num,
+            self.proof_shape.world_size,
+        ) {
+            return false;
+        }
 
-        verified
+        self.verify_with_output_claim(
+            circuit,
+            public_input,
+            |challenge| output_statement.evaluate(challenge),
+            pcs_params,
+            pcs_verification_key,
+            proof,
+            true,
+        )
     }
 
-    pub fn par_verify(
+    #[allow(clippy::too_many_arguments)]
+    fn verify_with_output_claim(
         &self,
         circuit: &mut Circuit<Cfg::FieldConfig>,
         public_input: &[<Cfg::FieldConfig as FieldEngine>::SimdCircuitField],
-        claimed_v: &<Cfg::FieldConfig as FieldEngine>::ChallengeField,
+        output_claim: impl FnOnce(
+            &ExpanderSingleVarChallenge<Cfg::FieldConfig>,
+        ) -> Option<<Cfg::FieldConfig as FieldEngine>::ChallengeField>,
         pcs_params: &<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Params,
         pcs_verification_key: &<<Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::SRS as StructuredReferenceString>::VKey,
         proof: &Proof,
+        parallel: bool,
     ) -> bool {
+        // the proof must come from a run of this circuit
+        if circuit.proof_shape(self.proof_shape.world_size) != self.proof_shape {
+            return false;
+        }
+
         let timer = Timer::new("snark verify", true);
 
-        let proving_time_mpi_size = self.mpi_config.world_size();
         let mut transcript = Cfg::TranscriptConfig::new();
         let mut cursor = Cursor::new(&proof.bytes);
 
         if !self.bind_and_check_public_inputs(&mut cursor, public_input, &mut transcript) {
             return false;
         }
+        let Some(commitment) = self.pre_gkr(&mut cursor, circuit, &mut transcript) else {
+            return false;
+        };
 
-        let commitment = self.pre_gkr(&mut cursor, circuit, &mut transcript, proving_time_mpi_size);
+        let Some((mut verified, challenge_x, challenge_y, claim_x, claim_y)) = (if parallel {
+            self.gkr_parallel(
+                circuit,
+                public_input,
+                output_claim,
+                &mut transcript,
+                &mut cursor,
+            )
+        } else {
+            self.gkr(
+                circuit,
+                public_input,
+                output_claim,
+                &mut transcript,
+                &mut cursor,
+            )
+        }) else {
+            return false;
+        };
 
-        let (mut verified, mut challenge_x, mut challenge_y, claim_x, claim_y) = self.gkr_parallel(
-            circuit,
-            public_input,
-            claimed_v,
-            proving_time_mpi_size,
-            &mut transcript,
-            &mut cursor,
-        );
+        // the lookups reduce their claims and the claims of GKR to a single opening
+        let (mut challenge_x, claim_x, mut challenge_y, claim_y) = if circuit.lookups.is_empty() {
+            (challenge_x, claim_x, challenge_y, claim_y)
+        } else {
+            let lookup_timer = Timer::new("lookup verify", true);
+            let input_claims = [Some((challenge_x, claim_x)), challenge_y.zip(claim_y)]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+            let Some((challenge, claim)) = verify_lookups(
+                circuit,
+                &self.proof_shape,
+                &input_claims,
+                Cfg::COMPRESSED_SUMCHECK,
+                &mut transcript,
+                &mut cursor,
+            ) else {
+                return false;
+            };
+            transcript_verifier_sync(&mut transcript, &self.proof_shape);
+            lookup_timer.stop();
+            (challenge, claim, None, None)
+        };
 
         verified &= self.post_gkr(
             pcs_params,
@@ -347,7 +490,7 @@ impl<'a, Cfg: GKREngine> Verifier<'a, Cfg> {
         verified
     }
 }
-impl<Cfg: GKREngine> Verifier<'_, Cfg> {
+impl<Cfg: GKREngine> Verifier<Cfg> {
     #[allow(clippy::too_many_arguments)]
     fn get_pcs_opening_from_proof_and_verify(
         &self,
@@ -359,14 +502,18 @@ impl<Cfg: GKREngine> Verifier<'_, Cfg> {
         transcript: &mut impl Transcript,
         proof_reader: impl Read,
     ) -> bool {
-        let opening = <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Opening::deserialize_from(
-            proof_reader,
-        )
-        .unwrap();
+        let Ok(opening) =
+            <Cfg::PCSConfig as ExpanderPCS<Cfg::FieldConfig>>::Opening::deserialize_from(
+                proof_reader,
+            )
+        else {
+            return false;
+        };
 
         transcript.lock_proof();
         let verified = Cfg::PCSConfig::verify(
             pcs_params,
+            &self.proof_shape,
             pcs_verification_key,
             commitment,
             open_at,
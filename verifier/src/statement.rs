@@ -0,0 +1,80 @@
+//! The statement bound to the transcript before any challenge is sampled.
+
+use arith::Field;
+use circuit::Circuit;
+#[cfg(feature = "grinding")]
+use gkr_engine::FieldEngine;
+use gkr_engine::{GKREngine, Transcript};
+use serdes::ExpSerde;
+use transcript::transcript_absorb_statement;
+#[cfg(feature = "grinding")]
+use utils::timer::Timer;
+
+/// Number of bits of the proof of work
+#[cfg(feature = "grinding")]
+pub const GRINDING_BITS: usize = 10;
+
+/// The statement shared by the prover and the verifier: the domain separator of the
+/// configuration, the digest of the circuit and the number of processes.
+pub fn statement_bytes<Cfg: GKREngine>(
+    circuit: &Circuit<Cfg::FieldConfig>,
+    world_size: usize,
+) -> Vec<u8> {
+    let mut statement = Cfg::domain_separator();
+    statement.extend_from_slice(&circuit.digest());
+    world_size.serialize_into(&mut statement).unwrap();
+    statement
+}
+
+/// Absorb the statement into the transcript. Both parties bind it right after the commitment,
+/// whose absorption resets the state of the transcript, see
+/// `BytesHashTranscript::append_commitment`. The claimed output, evaluated at a challenge sampled
+/// after the statement, is bound before the first sumcheck round, see `bind_output_claim`.
+pub fn bind_statement<Cfg: GKREngine>(
+    transcript: &mut impl Transcript,
+    circuit: &Circuit<Cfg::FieldConfig>,
+    world_size: usize,
+) {
+    transcript_absorb_statement(transcript, &statement_bytes::<Cfg>(circuit, world_size));
+}
+
+/// Absorb the claimed evaluation of the output MLE into the transcript before the first sumcheck
+/// round. It is not written to the proof and, as p(1) of a compressed round message is recovered
+/// from it, would not otherwise be bound to the sumcheck challenges.
+pub fn bind_output_claim<F: Field>(transcript: &mut impl Transcript, claimed_v: &F) {
+    let mut claim = vec![];
+    claimed_v.serialize_into(&mut claim).unwrap();
+    transcript_absorb_statement(transcript, &claim);
+}
+
+/// Proof of work on the transcript, run by both the prover and the verifier after the commitment
+#[cfg(feature = "grinding")]
+pub fn grind<Cfg: GKREngine>(transcript: &mut impl Transcript, print_timer: bool) {
+    let timer = Timer::new("grinding", print_timer);
+
+    let mut hash_bytes = vec![];
+
+    // ceil(32/field_size)
+    let num_field_elements = (31 + <Cfg::FieldConfig as FieldEngine>::ChallengeField::SIZE)
+        / <Cfg::FieldConfig as FieldEngine>::ChallengeField::SIZE;
+
+    let initial_hash = transcript
+        .generate_field_elements::<<Cfg::FieldConfig as FieldEngine>::ChallengeField>(
+            num_field_elements,
+        );
+    initial_hash
+        .iter()
+        .for_each(|h| h.serialize_into(&mut hash_bytes).unwrap()); // TODO: error propagation
+
+    assert!(hash_bytes.len() >= 32, "hash len: {}", hash_bytes.len());
+    hash_bytes.truncate(32);
+
+    transcript.lock_proof();
+    for _ in 0..(1 << GRINDING_BITS) {
+        transcript.append_u8_slice(&hash_bytes);
+        hash_bytes = transcript.generate_u8_slice(32);
+    }
+    transcript.append_u8_slice(&hash_bytes[..32]);
+    transcript.unlock_proof();
+    timer.stop();
+}
@@ -0,0 +1,334 @@
+use std::io::{Cursor, Read};
+
+use arith::{ExtensionField, SimdField};
+use circuit::{Circuit, CircuitLayer};
+use gkr_engine::{ExpanderDualVarChallenge, ExpanderSingleVarChallenge, FieldEngine, Transcript};
+use sumcheck::{
+    needs_generalized_sumcheck, GeneralizedLayerGates, SUMCHECK_GKR_DEGREE,
+    SUMCHECK_GKR_SIMD_MPI_DEGREE,
+};
+use transcript::RandomTape;
+
+use crate::bind_output_claim;
+
+// ================ Structured Claims ================
+#[derive(Clone, Debug, Default)]
+pub struct SumcheckClaim<F: FieldEngine> {
+    pub challenge: ExpanderDualVarChallenge<F>,
+    pub claim_x: F::ChallengeField,
+    pub alpha: Option<F::ChallengeField>,
+    pub claim_y: Option<F::ChallengeField>,
+}
+
+// ================ Verification Unit ================
+#[derive(Clone, Debug, Default)]
+pub struct SumcheckVerificationUnit<F: FieldEngine> {
+    pub random_tape: RandomTape<F::ChallengeField>,
+    pub claim: SumcheckClaim<F>,
+    pub proof: Vec<u8>,
+}
+
+/// Read a challenge field from the proof reader and
+///   1. Append the bytes to the proof_bytes vector.
+///   2. Append the field element to the transcript.
+///
+/// Returns `None` if the proof is malformed.
+#[inline(always)]
+pub fn parse_challenge_field<ChallengeF: ExtensionField>(
+    mut proof_reader: impl Read,
+    transcript: &mut impl Transcript,
+    proof_bytes: &mut Vec<u8>,
+) -> Option<ChallengeF> {
+    let mut buffer = vec![0; ChallengeF::SIZE];
+    proof_reader.read_exact(&mut buffer).ok()?;
+    proof_bytes.extend_from_slice(&buffer);
+    let challenge = ChallengeF::deserialize_from(Cursor::new(buffer)).ok()?;
+    transcript.append_field_element(&challenge);
+    Some(challenge)
+}
+
+/// A compressed round message has `degree` evaluations instead of `degree + 1`.
+/// Returns `None` if the proof is malformed.
+#[allow(clippy::too_many_arguments)]
+pub fn parse_sumcheck_rounds<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    n_rounds: usize,
+    degree: usize,
+    compressed: bool,
+    transcript: &mut impl Transcript,
+    challenge_vec: &mut Vec<F::ChallengeField>,
+    proof_bytes: &mut Vec<u8>,
+    random_tape: &mut RandomTape<F::ChallengeField>,
+) -> Option<()> {
+    challenge_vec.clear();
+    let n_evals = if compressed { degree } else { degree + 1 };
+    for _ in 0..n_rounds {
+        for _ in 0..n_evals {
+            parse_challenge_field::<F::ChallengeField>(&mut proof_reader, transcript, proof_bytes)?;
+        }
+
+        challenge_vec.push(transcript.generate_field_element());
+    }
+    random_tape.tape.extend_from_slice(challenge_vec);
+    Some(())
+}
+
+#[allow(clippy::type_complexity)]
+/// Parse the proof into a vector of verification units.
+/// `output_claim` computes the claimed evaluation of the output MLE at the output challenge,
+/// or rejects the challenge with `None`.
+/// Returns `None` if the proof is malformed or the output claim is rejected.
+pub fn parse_proof<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    circuit: &Circuit<F>,
+    proving_time_mpi_size: usize,
+    xy_var_degree: usize,
+    compressed_sumcheck: bool,
+    output_claim: impl FnOnce(&ExpanderSingleVarChallenge<F>) -> Option<F::ChallengeField>,
+    transcript: &mut impl Transcript,
+) -> Option<(
+    Vec<SumcheckVerificationUnit<F>>,
+    ExpanderDualVarChallenge<F>,
+    F::ChallengeField,
+    Option<F::ChallengeField>,
+)> {
+    let mut verification_units =
+        vec![SumcheckVerificationUnit::<F>::default(); circuit.layers.len()];
+    let n_output_vars = circuit.layers.last().unwrap().output_var_num;
+    let n_simd_vars = <F::SimdCircuitField as SimdField>::PACK_SIZE.trailing_zeros() as usize;
+    let n_mpi_vars = proving_time_mpi_size.trailing_zeros() as usize;
+
+    let output_challenge = ExpanderSingleVarChallenge::<F>::sample_from_transcript(
+        transcript,
+        n_output_vars,
+        proving_time_mpi_size,
+    );
+    let mut claim_x = output_claim(&output_challenge)?;
+    bind_output_claim(transcript, &claim_x);
+    let mut challenge: ExpanderDualVarChallenge<F> = output_challenge.into();
+    let mut alpha = None;
+    let mut claim_y = None;
+
+    for i in (0..circuit.layers.len()).rev() {
+        let verification_unit = &mut verification_units[i];
+        verification_unit.claim = SumcheckClaim {
+            challenge: challenge.clone(),
+            claim_x,
+            alpha,
+            claim_y,
+        };
+        challenge = ExpanderDualVarChallenge::default(); // reset challenge for next layer
+
+        let layer = &circuit.layers[i];
+        let sumcheck_proof = &mut verification_unit.proof;
+        let random_tape = &mut verification_unit.random_tape;
+
+        if needs_generalized_sumcheck(layer) {
+            (claim_x, claim_y) = parse_generalized_layer(
+                &mut proof_reader,
+                layer,
+                n_simd_vars,
+                n_mpi_vars,
+                compressed_sumcheck,
+                transcript,
+                &mut challenge,
+                sumcheck_proof,
+                random_tape,
+            )?;
+        } else {
+            (claim_x, claim_y) = parse_vanilla_layer(
+                &mut proof_reader,
+                layer,
+                n_simd_vars,
+                n_mpi_vars,
+                xy_var_degree,
+                compressed_sumcheck,
+                transcript,
+                &mut challenge,
+                sumcheck_proof,
+                random_tape,
+            )?;
+        }
+
+        alpha = if challenge.rz_1.is_some() {
+            let alpha = transcript.generate_field_element();
+            random_tape.tape.push(alpha);
+            Some(alpha)
+        } else {
+            None
+        };
+    }
+
+    Some((verification_units, challenge, claim_x, claim_y))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn parse_vanilla_layer<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    layer: &CircuitLayer<F>,
+    n_simd_vars: usize,
+    n_mpi_vars: usize,
+    xy_var_degree: usize,
+    compressed_sumcheck: bool,
+    transcript: &mut impl Transcript,
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    sumcheck_proof: &mut Vec<u8>,
+    random_tape: &mut RandomTape<F::ChallengeField>,
+) -> Option<(F::ChallengeField, Option<F::ChallengeField>)> {
+    let n_vars = layer.input_var_num;
+
+    parse_sumcheck_rounds::<F>(
+        &mut proof_reader,
+        n_vars,
+        xy_var_degree,
+        compressed_sumcheck,
+        transcript,
+        &mut challenge.rz_0,
+        sumcheck_proof,
+        random_tape,
+    )?;
+
+    parse_sumcheck_rounds::<F>(
+        &mut proof_reader,
+        n_simd_vars,
+        SUMCHECK_GKR_SIMD_MPI_DEGREE,
+        compressed_sumcheck,
+        transcript,
+        &mut challenge.r_simd,
+        sumcheck_proof,
+        random_tape,
+    )?;
+
+    parse_sumcheck_rounds::<F>(
+        &mut proof_reader,
+        n_mpi_vars,
+        SUMCHECK_GKR_SIMD_MPI_DEGREE,
+        compressed_sumcheck,
+        transcript,
+        &mut challenge.r_mpi,
+        sumcheck_proof,
+        random_tape,
+    )?;
+
+    let claim_x =
+        parse_challenge_field::<F::ChallengeField>(&mut proof_reader, transcript, sumcheck_proof)?;
+
+    let claim_y = if !layer.structure_info.skip_sumcheck_phase_two {
+        challenge.rz_1 = Some(vec![]);
+        parse_sumcheck_rounds::<F>(
+            &mut proof_reader,
+            n_vars,
+            xy_var_degree,
+            compressed_sumcheck,
+            transcript,
+            challenge.rz_1.as_mut().unwrap(),
+            sumcheck_proof,
+            random_tape,
+        )?;
+        Some(parse_challenge_field::<F::ChallengeField>(
+            &mut proof_reader,
+            transcript,
+            sumcheck_proof,
+        )?)
+    } else {
+        None
+    };
+
+    Some((claim_x, claim_y))
+}
+
+/// The generalized layer sumcheck has one phase per input slot of the gates, and folds the
+/// claims of the slots past the first when there are more than two
+#[allow(clippy::too_many_arguments)]
+fn parse_generalized_layer<F: FieldEngine>(
+    mut proof_reader: impl Read,
+    layer: &CircuitLayer<F>,
+    n_simd_vars: usize,
+    n_mpi_vars: usize,
+    compressed_sumcheck: bool,
+    transcript: &mut impl Transcript,
+    challenge: &mut ExpanderDualVarChallenge<F>,
+    sumcheck_proof: &mut Vec<u8>,
+    random_tape: &mut RandomTape<F::ChallengeField>,
+) -> Option<(F::ChallengeField, Option<F::ChallengeField>)> {
+    let gates = GeneralizedLayerGates::new(layer);
+    let n_vars = layer.input_var_num;
+
+    let mut r_slots = vec![vec![]; gates.slot_num];
+    for (slot, r_slot) in r_slots.iter_mut().enumerate() {
+        parse_sumcheck_rounds::<F>(
+            &mut proof_reader,
+            n_vars,
+            gates.slot_degree(slot),
+            compressed_sumcheck,
+            transcript,
+            r_slot,
+            sumcheck_proof,
+            random_tape,
+        )?;
+    }
+
+    let degree = gates.simd_mpi_degree();
+    parse_sumcheck_rounds::<F>(
+        &mut proof_reader,
+        n_simd_vars,
+        degree,
+        compressed_sumcheck,
+        transcript,
+        &mut challenge.r_simd,
+        sumcheck_proof,
+        random_tape,
+    )?;
+
+    parse_sumcheck_rounds::<F>(
+        &mut proof_reader,
+        n_mpi_vars,
+        degree,
+        compressed_sumcheck,
+        transcript,
+        &mut challenge.r_mpi,
+        sumcheck_proof,
+        random_tape,
+    )?;
+
+    let claims = (0..gates.slot_num)
+        .map(|_| {
+            parse_challenge_field::<F::ChallengeField>(
+                &mut proof_reader,
+                transcript,
+                sumcheck_proof,
+            )
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let claim_y = if gates.reduces_claims() {
+        let beta = transcript.generate_field_element();
+        random_tape.tape.push(beta);
+
+        let mut r_reduction = vec![];
+        parse_sumcheck_rounds::<F>(
+            &mut proof_reader,
+            n_vars,
+            SUMCHECK_GKR_DEGREE,
+            compressed_sumcheck,
+            transcript,
+            &mut r_reduction,
+            sumcheck_proof,
+            random_tape,
+        )?;
+        challenge.rz_1 = Some(r_reduction);
+        Some(parse_challenge_field::<F::ChallengeField>(
+            &mut proof_reader,
+            transcript,
+            sumcheck_proof,
+        )?)
+    } else if gates.slot_num == 2 {
+        challenge.rz_1 = Some(r_slots[1].clone());
+        Some(claims[1])
+    } else {
+        None
+    };
+    challenge.rz_0 = r_slots.swap_remove(0);
+
+    Some((claims[0], claim_y))
+}
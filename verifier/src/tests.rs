@@ -0,0 +1,367 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use arith::Field;
+use circuit::{Allocation, CoefType, RecursiveCircuit, Segment, TextFormat, UNI_GATE_TYPE_POW5};
+use gkr_engine::{FieldEngine, GKREngine, GKRScheme, M31x1Config, Proof, ThreadMPIConfig};
+use gkr_hashers::SHA256hasher;
+use poly_commit::raw::RawExpanderGKR;
+use serdes::ExpSerde;
+use transcript::BytesHashTranscript;
+
+use crate::*;
+
+/// A configuration declared without the config macro, whose MPI engine does not need MPI
+struct M31x1ConfigSha2Raw;
+
+impl GKREngine for M31x1ConfigSha2Raw {
+    type FieldConfig = M31x1Config;
+    type MPIConfig = ThreadMPIConfig;
+    type TranscriptConfig = BytesHashTranscript<SHA256hasher>;
+    type PCSConfig = RawExpanderGKR<M31x1Config>;
+    const SCHEME: GKRScheme = GKRScheme::Vanilla;
+}
+
+type F = <M31x1Config as FieldEngine>::CircuitField;
+type E = <M31x1Config as FieldEngine>::ChallengeField;
+
+const CIRCUIT_TEXT: &str = "
+expander_circuit
+modulus 2147483647
+num_public_inputs 1
+num_outputs 2
+expected_num_output_zeros 0
+
+# out_0 = 3 * in_0 * in_1 + in_2 + public_0, out_1 = in_3
+segment 0 2 1
+mul 0 1 0 3
+add 2 0 1
+const 0 public:0
+add 3 1 1
+
+layers 0
+";
+
+fn to_bytes<T: ExpSerde>(value: &T) -> Vec<u8> {
+    let mut buffer = vec![];
+    value.serialize_into(&mut buffer).unwrap();
+    buffer
+}
+
+fn circuit_bytes() -> Vec<u8> {
+    to_bytes(&RecursiveCircuit::<M31x1Config>::from_text(CIRCUIT_TEXT).unwrap())
+}
+
+fn verify(
+    circuit_bytes: &[u8],
+    public_input_bytes: &[u8],
+    proof_bytes: &[u8],
+    world_size: usize,
+) -> Result<bool, VerifierError> {
+    verify_bytes::<M31x1ConfigSha2Raw>(
+        circuit_bytes,
+        public_input_bytes,
+        proof_bytes,
+        world_size,
+        &2,
+        &(),
+    )
+}
+
+#[test]
+fn test_deserialize_circuit() {
+    let (circuit, num_public_inputs) =
+        deserialize_circuit::<M31x1Config>(&circuit_bytes()).unwrap();
+    assert_eq!(num_public_inputs, 1);
+    // the input layer has a mul gate, so `pre_process_gkr` adds a relay layer
+    assert_eq!(circuit.layers.len(), 2);
+    assert_eq!(circuit.log_input_size(), 2);
+
+    let public_input = vec![F::ONE, F::from(2u32)];
+    assert_eq!(
+        deserialize_public_input::<M31x1Config>(&to_bytes(&public_input)),
+        Ok(public_input)
+    );
+}
+
+#[test]
+fn test_malformed_bytes() {
+    let circuit = circuit_bytes();
+    let public_input = to_bytes(&vec![F::ONE]);
+    let mut proof = to_bytes(&Proof::default());
+    E::ONE.serialize_into(&mut proof).unwrap();
+
+    for world_size in [0, 3] {
+        assert_eq!(
+            verify(&circuit, &public_input, &proof, world_size),
+            Err(VerifierError::InvalidWorldSize(world_size))
+        );
+    }
+
+    // truncated, with trailing bytes, or of another field
+    let mut other_field = circuit.clone();
+    other_field[8] ^= 1;
+    for bytes in [
+        &circuit[..circuit.len() - 1],
+        &[circuit.clone(), vec![0]].concat(),
+        &other_field,
+    ] {
+        assert_eq!(
+            verify(bytes, &public_input, &proof, 1),
+            Err(VerifierError::InvalidCircuit)
+        );
+    }
+
+    // a layer of a missing segment
+    let missing_segment = RecursiveCircuit::<M31x1Config> {
+        segments: vec![Segment::default()],
+        layers: vec![1],
+        ..Default::default()
+    };
+    assert_eq!(
+        verify(&to_bytes(&missing_segment), &public_input, &proof, 1),
+        Err(VerifierError::InvalidCircuit)
+    );
+
+    // out of range gates, allocations or public inputs, unknown gates and cycles of segments
+    let out_of_range: [fn(&mut RecursiveCircuit<M31x1Config>); 7] = [
+        |rc| rc.segments[0].gate_muls[0].i_ids[1] = 4,
+        |rc| rc.segments[0].gate_adds[0].o_id = 2,
+        |rc| rc.segments[0].gate_consts[0].coef_type = CoefType::PublicInput(1),
+        |rc| rc.segments[0].i_var_num = 40,
+        |rc| {
+            rc.segments.push(Segment {
+                i_var_num: 1,
+                o_var_num: 1,
+                child_segs: vec![(
+                    0,
+                    vec![Allocation {
+                        i_offset: 0,
+                        o_offset: 0,
+                    }],
+                )],
+                ..Default::default()
+            });
+            rc.layers = vec![1];
+        },
+        |rc| {
+            let mut gate = rc.segments[0].gate_adds[0];
+            gate.gate_type = UNI_GATE_TYPE_POW5 + 100;
+            rc.segments[0].gate_uni.push(gate);
+        },
+        |rc| {
+            rc.segments.push(Segment {
+                i_var_num: 2,
+                o_var_num: 1,
+                child_segs: vec![(1, vec![])],
+                ..Default::default()
+            });
+            rc.layers = vec![1];
+        },
+    ];
+    for mutate in out_of_range {
+        let mut rc = RecursiveCircuit::<M31x1Config>::from_text(CIRCUIT_TEXT).unwrap();
+        mutate(&mut rc);
+        assert_eq!(
+            verify(&to_bytes(&rc), &public_input, &proof, 1),
+            Err(VerifierError::InvalidCircuit)
+        );
+    }
+
+    assert_eq!(
+        verify(&circuit, &public_input[..public_input.len() - 1], &proof, 1),
+        Err(VerifierError::InvalidPublicInput)
+    );
+    assert_eq!(
+        verify(&circuit, &public_input, &proof, 2),
+        Err(VerifierError::PublicInputCount {
+            expected: 2,
+            got: 1
+        })
+    );
+
+    for bytes in [
+        &[][..],
+        &proof[..proof.len() - 1],
+        &[proof.clone(), vec![0]].concat(),
+    ] {
+        assert_eq!(
+            verify(&circuit, &public_input, bytes, 1),
+            Err(VerifierError::InvalidProof)
+        );
+    }
+
+    // a well-formed container of the bytes of a malformed proof does not verify
+    let pseudo_random = (0..1024u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 24) as u8)
+        .collect::<Vec<_>>();
+    for bytes in [vec![], vec![0; 40], vec![0xff; 40], pseudo_random] {
+        let mut proof = to_bytes(&Proof { bytes });
+        E::ONE.serialize_into(&mut proof).unwrap();
+        assert_eq!(verify(&circuit, &public_input, &proof, 1), Ok(false));
+    }
+}
+
+/// The features of the path dependencies pulling in MPI or the proving code
+const PROVING_FEATURES: [&str; 2] = ["mpi", "prover"];
+
+/// The registry crates the verifier must not depend on, even indirectly
+const DENIED_DEPENDENCIES: [&str; 3] = ["mpi", "rayon", "tokio"];
+
+/// The budget of the source bytes of the verifier, excluding the tests. It bounds the code to
+/// review and to embed, not the size of the compiled library.
+const SOURCE_BYTES_BUDGET: u64 = 80 * 1024;
+
+struct Dependency {
+    name: String,
+    path: Option<PathBuf>,
+    default_features: bool,
+    features: Vec<String>,
+}
+
+/// The items of a one-line array of strings of a manifest, e.g. `[ "a", "b" ]`
+fn string_array(value: &str) -> Vec<String> {
+    value
+        .split(['[', ']', ','])
+        .map(|item| item.trim().trim_matches('"'))
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The dependencies of a crate built for every target, leaving out the optional ones
+fn dependencies(manifest_dir: &Path) -> Vec<Dependency> {
+    let manifest = fs::read_to_string(manifest_dir.join("Cargo.toml")).unwrap();
+    let mut section = "";
+    let mut dependencies = vec![];
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        let is_dependency = section == "[dependencies]"
+            || (section.starts_with("[target.") && section.ends_with(".dependencies]"));
+        if !is_dependency
+            || line.is_empty()
+            || line.starts_with('#')
+            || line.contains("optional = true")
+        {
+            continue;
+        }
+
+        let path = line.split("path = \"").nth(1).map(|rest| {
+            let end = rest.find('"').unwrap();
+            manifest_dir.join(&rest[..end])
+        });
+        let features = line
+            .split("features = [")
+            .nth(1)
+            .map(|rest| string_array(&rest[..rest.find(']').unwrap()]))
+            .unwrap_or_default();
+        dependencies.push(Dependency {
+            name: line.split(['=', '.']).next().unwrap().trim().to_owned(),
+            path,
+            default_features: !line.contains("default-features = false"),
+            features,
+        });
+    }
+    dependencies
+}
+
+/// The features of a crate which enable, directly or through other features of the crate,
+/// one of the `PROVING_FEATURES` or one of the `DENIED_DEPENDENCIES`
+fn proving_features(manifest_dir: &Path) -> HashSet<String> {
+    let manifest = fs::read_to_string(manifest_dir.join("Cargo.toml")).unwrap();
+    let mut section = "";
+    let mut features = vec![];
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        if section != "[features]" || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, enabled) = line.split_once('=').unwrap();
+        features.push((name.trim().to_owned(), string_array(enabled)));
+    }
+
+    let denied = DENIED_DEPENDENCIES.map(|name| format!("dep:{name}"));
+    let mut proving = features
+        .iter()
+        .filter(|(name, enabled)| {
+            PROVING_FEATURES.contains(&name.as_str())
+                || enabled.iter().any(|feature| denied.contains(feature))
+        })
+        .map(|(name, _)| name.clone())
+        .collect::<HashSet<_>>();
+    loop {
+        let len = proving.len();
+        for (name, enabled) in &features {
+            if enabled.iter().any(|feature| proving.contains(feature)) {
+                proving.insert(name.clone());
+            }
+        }
+        if proving.len() == len {
+            return proving;
+        }
+    }
+}
+
+#[test]
+fn test_dependency_budget() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut visited = HashSet::from([root.canonicalize().unwrap()]);
+    let mut stack = vec![root];
+    while let Some(manifest_dir) = stack.pop() {
+        for dependency in dependencies(&manifest_dir) {
+            let Some(path) = dependency.path else {
+                assert!(
+                    !DENIED_DEPENDENCIES.contains(&dependency.name.as_str()),
+                    "{} depends on {}",
+                    manifest_dir.display(),
+                    dependency.name
+                );
+                continue;
+            };
+
+            let proving = proving_features(&path);
+            assert!(
+                !dependency.default_features || !proving.contains("default"),
+                "{} depends on the default features of {}, which include the proving code",
+                manifest_dir.display(),
+                dependency.name
+            );
+            for feature in &dependency.features {
+                assert!(
+                    !proving.contains(feature),
+                    "{} enables the feature {feature} of {}, which pulls in the proving code",
+                    manifest_dir.display(),
+                    dependency.name
+                );
+            }
+
+            if visited.insert(path.canonicalize().unwrap()) {
+                stack.push(path);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_source_bytes_budget() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let size: u64 = fs::read_dir(src)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.file_name().unwrap() != "tests.rs")
+        .map(|path| fs::metadata(path).unwrap().len())
+        .sum();
+    assert!(
+        size <= SOURCE_BYTES_BUDGET,
+        "the sources of the verifier take {size} bytes, over the budget of {SOURCE_BYTES_BUDGET}"
+    );
+}